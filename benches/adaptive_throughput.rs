@@ -0,0 +1,194 @@
+//! Compares fixed and adaptive message budgets on a shared pool with a single thread
+//!
+//! A cheap and an expensive Actor drain their mailboxes while a probe Actor keeps sending itself ticks and measures how long they wait for the thread.
+//! `cargo bench --bench adaptive_throughput`
+use crossbeam_channel::{unbounded, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use tyractorsaur::prelude::*;
+
+const POOL: &str = "shared";
+const CHEAP_MESSAGES: usize = 300_000;
+const CHEAP_COST: Duration = Duration::from_nanos(0);
+const EXPENSIVE_MESSAGES: usize = 200;
+const EXPENSIVE_COST: Duration = Duration::from_millis(2);
+
+struct Work {
+    last: bool,
+}
+
+impl ActorMessage for Work {}
+
+struct Worker {
+    cost: Duration,
+    done: Sender<()>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Worker {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Work> for Worker {
+    fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+        let started = Instant::now();
+        while started.elapsed() < self.cost {}
+        if msg.last {
+            let _ = self.done.send(());
+        }
+    }
+}
+
+struct WorkerFactory {
+    cost: Duration,
+    done: Sender<()>,
+}
+
+impl ActorFactory<Worker> for WorkerFactory {
+    fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+        Worker {
+            cost: self.cost,
+            done: self.done.clone(),
+            context,
+        }
+    }
+}
+
+struct Tick {
+    sent: Instant,
+}
+
+impl ActorMessage for Tick {}
+
+struct Probe {
+    latencies: Arc<Mutex<Vec<Duration>>>,
+    is_finished: Arc<AtomicBool>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Probe {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Tick> for Probe {
+    fn handle(&mut self, msg: Tick, _context: &ActorContext<Self>) {
+        self.latencies.lock().unwrap().push(msg.sent.elapsed());
+        if !self.is_finished.load(Ordering::Relaxed) {
+            let _ = self.context.actor_ref.send(Tick { sent: Instant::now() });
+        }
+    }
+}
+
+struct ProbeFactory {
+    latencies: Arc<Mutex<Vec<Duration>>>,
+    is_finished: Arc<AtomicBool>,
+}
+
+impl ActorFactory<Probe> for ProbeFactory {
+    fn new_actor(&self, context: ActorContext<Probe>) -> Probe {
+        Probe {
+            latencies: self.latencies.clone(),
+            is_finished: self.is_finished.clone(),
+            context,
+        }
+    }
+}
+
+struct Outcome {
+    elapsed: Duration,
+    /// longest time a tick of the probe waited for the thread
+    max_wait: Duration,
+}
+
+fn run(throughput: Throughput) -> Outcome {
+    let mut config = TyractorsaurConfig::new().unwrap();
+    config
+        .thread_pool
+        .config
+        .insert(String::from(POOL), ThreadPoolConfig::new(0, 1, 1, 1.0));
+    let actor_system = ActorSystem::new(config);
+    let (done, finished) = unbounded();
+    let latencies = Arc::new(Mutex::new(Vec::new()));
+    let is_finished = Arc::new(AtomicBool::new(false));
+    let spawn_worker = |name: &str, cost: Duration| {
+        actor_system
+            .builder()
+            .set_pool_name(POOL)
+            .unwrap()
+            .set_throughput(throughput)
+            .spawn(name, WorkerFactory { cost, done: done.clone() })
+            .unwrap()
+    };
+    let cheap = spawn_worker("cheap", CHEAP_COST);
+    let expensive = spawn_worker("expensive", EXPENSIVE_COST);
+    let probe = actor_system
+        .builder()
+        .set_pool_name(POOL)
+        .unwrap()
+        .set_throughput(throughput)
+        .spawn(
+            "probe",
+            ProbeFactory {
+                latencies: latencies.clone(),
+                is_finished: is_finished.clone(),
+            },
+        )
+        .unwrap();
+    // the pool thread is started by the next tick of the thread pool manager
+    sleep(Duration::from_millis(1500));
+
+    let started = Instant::now();
+    probe.send(Tick { sent: started }).unwrap();
+    for i in 0..CHEAP_MESSAGES {
+        cheap.send(Work {
+            last: i + 1 == CHEAP_MESSAGES,
+        }).unwrap();
+    }
+    for i in 0..EXPENSIVE_MESSAGES {
+        expensive.send(Work {
+            last: i + 1 == EXPENSIVE_MESSAGES,
+        }).unwrap();
+    }
+    finished.recv().unwrap();
+    finished.recv().unwrap();
+    let elapsed = started.elapsed();
+    is_finished.store(true, Ordering::Relaxed);
+    actor_system.stop(Duration::from_secs(10));
+    actor_system.await_shutdown();
+
+    let max_wait = latencies.lock().unwrap().iter().max().copied().unwrap_or_default();
+    Outcome { elapsed, max_wait }
+}
+
+fn main() {
+    let settings = vec![
+        ("fixed 1", Throughput::Fixed(1)),
+        ("fixed 1000", Throughput::Fixed(1000)),
+        (
+            "adaptive 2ms [1, 1000]",
+            Throughput::Adaptive(AdaptiveThroughput::new(Duration::from_millis(2), 1, 1000)),
+        ),
+    ];
+    println!(
+        "{} messages of {:?} and {} messages of {:?} on a single thread\n",
+        CHEAP_MESSAGES, CHEAP_COST, EXPENSIVE_MESSAGES, EXPENSIVE_COST
+    );
+    println!("{:<24} {:>12} {:>12} {:>16}", "throughput", "drained ms", "msgs/s", "max tick wait ms");
+    for (name, throughput) in settings {
+        let outcome = run(throughput);
+        let messages = (CHEAP_MESSAGES + EXPENSIVE_MESSAGES) as f64;
+        println!(
+            "{:<24} {:>12.1} {:>12.0} {:>16.2}",
+            name,
+            outcome.elapsed.as_secs_f64() * 1000.0,
+            messages / outcome.elapsed.as_secs_f64(),
+            outcome.max_wait.as_secs_f64() * 1000.0
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Compares handling messages one by one with handling them in batches through a BatchHandler
+//!
+//! A producer keeps the mailbox of a single Actor filled for a fixed duration, the Actor adds up the payload of every message it handles.
+//! Both variants use the same handler code, the batched variant is spawned with `add_batching`.
+//! `cargo bench --bench batch_handling`
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{sleep, spawn};
+use std::time::{Duration, Instant};
+use tyractorsaur::prelude::*;
+
+const DURATION: Duration = Duration::from_secs(3);
+const MAILBOX_SIZE: usize = 10_000;
+const THROUGHPUT: usize = 100;
+
+struct Sample {
+    value: u64,
+}
+
+impl ActorMessage for Sample {}
+
+struct Summer {
+    sum: u64,
+    processed: Arc<AtomicUsize>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Summer {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Sample> for Summer {
+    fn handle(&mut self, msg: Sample, context: &ActorContext<Self>) {
+        self.handle_batch(vec![msg], context);
+    }
+}
+
+impl BatchHandler<Sample> for Summer {
+    fn handle_batch(&mut self, msgs: Vec<Sample>, _context: &ActorContext<Self>) {
+        let len = msgs.len();
+        for msg in msgs {
+            self.sum = self.sum.wrapping_add(msg.value);
+        }
+        self.processed.fetch_add(len, Ordering::Relaxed);
+    }
+}
+
+struct SummerFactory {
+    processed: Arc<AtomicUsize>,
+}
+
+impl ActorFactory<Summer> for SummerFactory {
+    fn new_actor(&self, context: ActorContext<Summer>) -> Summer {
+        Summer {
+            sum: 0,
+            processed: self.processed.clone(),
+            context,
+        }
+    }
+}
+
+fn run(is_batched: bool) -> usize {
+    let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    let processed = Arc::new(AtomicUsize::new(0));
+    let mut builder = actor_system
+        .builder()
+        .set_mailbox_size(MAILBOX_SIZE)
+        .set_message_throughput(THROUGHPUT);
+    if is_batched {
+        builder = builder.add_batching::<Sample>();
+    }
+    let summer = builder
+        .spawn(
+            "summer",
+            SummerFactory {
+                processed: processed.clone(),
+            },
+        )
+        .unwrap();
+    // the pool threads are started by the next tick of the thread pool manager
+    sleep(Duration::from_millis(1500));
+
+    let is_running = Arc::new(AtomicBool::new(true));
+    let producer = {
+        let is_running = is_running.clone();
+        spawn(move || {
+            let mut value = 0;
+            while is_running.load(Ordering::Relaxed) {
+                let _ = summer.send(Sample { value });
+                value += 1;
+            }
+        })
+    };
+    let started = Instant::now();
+    let before = processed.load(Ordering::Relaxed);
+    sleep(DURATION);
+    let after = processed.load(Ordering::Relaxed);
+    let elapsed = started.elapsed();
+    is_running.store(false, Ordering::Relaxed);
+    producer.join().unwrap();
+    actor_system.stop(Duration::from_secs(10));
+    actor_system.await_shutdown();
+
+    ((after - before) as f64 / elapsed.as_secs_f64()) as usize
+}
+
+fn main() {
+    println!(
+        "single producer, mailbox size {}, message throughput {}, {:?} per variant\n",
+        MAILBOX_SIZE, THROUGHPUT, DURATION
+    );
+    println!("{:<12} {:>14}", "handling", "messages/s");
+    for (name, is_batched) in [("individual", false), ("batched", true)] {
+        println!("{:<12} {:>14}", name, run(is_batched));
+    }
+}
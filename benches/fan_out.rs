@@ -0,0 +1,224 @@
+//! Compares individual sends with a send batch for a handler that fans out to 200 Actors per event
+//!
+//! Every event is only completed once all leaves handled their update, the hub measures how long its handler spent sending.
+//! Warm events reach leaves that are still awake from the previous event, cold events are injected from outside into freshly spawned and sleeping leaves.
+//! `cargo bench --bench fan_out`
+use crossbeam_channel::{unbounded, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use tyractorsaur::prelude::*;
+
+const LEAVES: usize = 200;
+const EVENTS: usize = 2_000;
+const COLD_EVENTS: usize = 20;
+const POOLS: [&str; 2] = ["default", "leaves"];
+
+struct Update {}
+
+impl ActorMessage for Update {}
+
+struct Leaf {
+    remaining: Arc<AtomicUsize>,
+    done: Sender<()>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Leaf {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Update> for Leaf {
+    fn handle(&mut self, _msg: Update, _context: &ActorContext<Self>) {
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let _ = self.done.send(());
+        }
+    }
+}
+
+struct LeafFactory {
+    remaining: Arc<AtomicUsize>,
+    done: Sender<()>,
+}
+
+impl ActorFactory<Leaf> for LeafFactory {
+    fn new_actor(&self, context: ActorContext<Leaf>) -> Leaf {
+        Leaf {
+            remaining: self.remaining.clone(),
+            done: self.done.clone(),
+            context,
+        }
+    }
+}
+
+struct Event {}
+
+impl ActorMessage for Event {}
+
+struct Hub {
+    leaves: Vec<ActorWrapper<Leaf>>,
+    is_batched: bool,
+    send_time: Arc<Mutex<Duration>>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Hub {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Event> for Hub {
+    fn handle(&mut self, _msg: Event, context: &ActorContext<Self>) {
+        let started = Instant::now();
+        if self.is_batched {
+            let leaves = &self.leaves;
+            context.batch_sends(|batch| {
+                for leaf in leaves.iter() {
+                    batch.send(leaf, Update {});
+                }
+            });
+        } else {
+            for leaf in self.leaves.iter() {
+                let _ = leaf.send(Update {});
+            }
+        }
+        *self.send_time.lock().unwrap() += started.elapsed();
+    }
+}
+
+struct HubFactory {
+    leaves: Vec<ActorWrapper<Leaf>>,
+    is_batched: bool,
+    send_time: Arc<Mutex<Duration>>,
+}
+
+impl ActorFactory<Hub> for HubFactory {
+    fn new_actor(&self, context: ActorContext<Hub>) -> Hub {
+        Hub {
+            leaves: self.leaves.clone(),
+            is_batched: self.is_batched,
+            send_time: self.send_time.clone(),
+            context,
+        }
+    }
+}
+
+struct Outcome {
+    elapsed: Duration,
+    send_time: Duration,
+    cold_elapsed: Duration,
+    cold_send_time: Duration,
+}
+
+fn run(is_batched: bool) -> Outcome {
+    let mut config = TyractorsaurConfig::new().unwrap();
+    config
+        .thread_pool
+        .config
+        .insert(String::from(POOLS[1]), ThreadPoolConfig::new(0, 2, 2, 1.0));
+    let actor_system = ActorSystem::new(config);
+    let (done, finished) = unbounded();
+    let remaining = Arc::new(AtomicUsize::new(0));
+    let send_time = Arc::new(Mutex::new(Duration::from_secs(0)));
+    let spawn_leaves = |prefix: &str| -> Vec<ActorWrapper<Leaf>> {
+        (0..LEAVES)
+            .map(|index| {
+                actor_system
+                    .builder()
+                    .set_pool_name(POOLS[index % POOLS.len()])
+                    .unwrap()
+                    .spawn(
+                        format!("{}-{}", prefix, index),
+                        LeafFactory {
+                            remaining: remaining.clone(),
+                            done: done.clone(),
+                        },
+                    )
+                    .unwrap()
+            })
+            .collect()
+    };
+    let leaves = spawn_leaves("leaf");
+    let hub = actor_system
+        .builder()
+        .spawn(
+            "hub",
+            HubFactory {
+                leaves,
+                is_batched,
+                send_time: send_time.clone(),
+            },
+        )
+        .unwrap();
+    // the pool threads are started by the next tick of the thread pool manager
+    sleep(Duration::from_millis(1500));
+
+    let started = Instant::now();
+    for _ in 0..EVENTS {
+        remaining.store(LEAVES, Ordering::Release);
+        hub.send(Event {}).unwrap();
+        finished.recv().unwrap();
+    }
+    let elapsed = started.elapsed();
+
+    let mut cold_elapsed = Duration::from_secs(0);
+    let mut cold_send_time = Duration::from_secs(0);
+    for event in 0..COLD_EVENTS {
+        let leaves = spawn_leaves(&format!("cold-{}", event));
+        remaining.store(LEAVES, Ordering::Release);
+        let started = Instant::now();
+        if is_batched {
+            actor_system.batch_sends(|batch| {
+                for leaf in leaves.iter() {
+                    batch.send(leaf, Update {});
+                }
+            });
+        } else {
+            for leaf in leaves.iter() {
+                leaf.send(Update {}).unwrap();
+            }
+        }
+        cold_send_time += started.elapsed();
+        finished.recv().unwrap();
+        cold_elapsed += started.elapsed();
+    }
+    actor_system.stop(Duration::from_secs(10));
+    actor_system.await_shutdown();
+
+    let send_time = *send_time.lock().unwrap();
+    Outcome {
+        elapsed,
+        send_time,
+        cold_elapsed,
+        cold_send_time,
+    }
+}
+
+fn main() {
+    println!(
+        "{} warm and {} cold events fanned out to {} leaves on {} pools\n",
+        EVENTS,
+        COLD_EVENTS,
+        LEAVES,
+        POOLS.len()
+    );
+    println!(
+        "{:<12} {:>14} {:>18} {:>14} {:>18}",
+        "sends", "warm events/s", "warm fan-out us", "cold event ms", "cold fan-out us"
+    );
+    for (name, is_batched) in [("individual", false), ("batched", true)] {
+        let outcome = run(is_batched);
+        println!(
+            "{:<12} {:>14.0} {:>18.1} {:>14.2} {:>18.1}",
+            name,
+            EVENTS as f64 / outcome.elapsed.as_secs_f64(),
+            outcome.send_time.as_secs_f64() * 1_000_000.0 / EVENTS as f64,
+            outcome.cold_elapsed.as_secs_f64() * 1000.0 / COLD_EVENTS as f64,
+            outcome.cold_send_time.as_secs_f64() * 1_000_000.0 / COLD_EVENTS as f64
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Cost of a large number of idle Actors: heap and allocations per Actor, and the latency to wake up a random one of them
+//!
+//! All Actors are spawned and left asleep, afterwards single messages are sent to random Actors one at a time.
+//! The latency covers the wakeup, the scheduling on the pool and the handler.
+//! `cargo bench --bench idle_actors -- [--actors N] [--wakes N]`
+use std::sync::mpsc::{channel, Sender};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use tyractorsaur::bench::CountingAllocator;
+use tyractorsaur::prelude::*;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct Touch {
+    sent: Instant,
+}
+
+impl ActorMessage for Touch {}
+
+struct Entity {
+    handled: Sender<Duration>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Entity {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Touch> for Entity {
+    fn handle(&mut self, msg: Touch, _context: &ActorContext<Self>) {
+        let _ = self.handled.send(msg.sent.elapsed());
+    }
+}
+
+#[derive(Clone)]
+struct EntityFactory {
+    handled: Sender<Duration>,
+}
+
+impl ActorFactory<Entity> for EntityFactory {
+    fn new_actor(&self, context: ActorContext<Entity>) -> Entity {
+        Entity {
+            handled: self.handled.clone(),
+            context,
+        }
+    }
+}
+
+/// xorshift, the picked Actors only have to be spread over all of them
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+    sorted[(sorted.len() * percentile / 100).min(sorted.len() - 1)]
+}
+
+fn main() {
+    let mut actors = 100_000;
+    let mut wakes = 1_000;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().and_then(|value| value.parse::<usize>().ok());
+        match arg.as_str() {
+            "--actors" => actors = value().expect("--actors requires a number"),
+            "--wakes" => wakes = value().expect("--wakes requires a number"),
+            // e.g. `--bench`, added by cargo
+            _ => {}
+        }
+    }
+
+    let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    let (handled, latencies) = channel();
+    let factory = EntityFactory { handled };
+    let builder = actor_system.builder();
+    // the pool threads are started by the next tick of the thread pool manager
+    sleep(Duration::from_millis(1500));
+
+    let (bytes_before, allocations_before) = (CountingAllocator::live_bytes(), CountingAllocator::allocations());
+    let started = Instant::now();
+    let entities: Vec<ActorWrapper<Entity>> = (0..actors)
+        .map(|i| builder.spawn(format!("entity-{}", i), factory.clone()).unwrap())
+        .collect();
+    let spawn_duration = started.elapsed();
+    // the wrappers are part of what a user keeps per Actor
+    let bytes = CountingAllocator::live_bytes().saturating_sub(bytes_before);
+    let allocations = CountingAllocator::allocations() - allocations_before;
+    println!("{} idle Actors spawned in {:?}\n", actors, spawn_duration);
+    println!("{:<24} {:>12}", "heap bytes per Actor", bytes / actors.max(1));
+    println!("{:<24} {:>12}", "allocations per Actor", allocations / actors.max(1));
+
+    let mut random = 0x2545_f491_4f6c_dd1d;
+    let mut measured: Vec<Duration> = (0..wakes)
+        .map(|_| {
+            let entity = &entities[next_random(&mut random) as usize % entities.len()];
+            entity.send(Touch { sent: Instant::now() }).unwrap();
+            latencies.recv_timeout(Duration::from_secs(10)).expect("the Actor has not been woken up")
+        })
+        .collect();
+    measured.sort();
+    println!("\nwakeup and handling of {} random Actors, one at a time", wakes);
+    for p in [50, 99, 100] {
+        println!("{:<24} {:>12?}", format!("p{}", p), percentile(&measured, p));
+    }
+
+    actor_system.stop(Duration::from_secs(60));
+    actor_system.await_shutdown();
+}
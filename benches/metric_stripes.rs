@@ -0,0 +1,62 @@
+//! Compares the cost of counting handled messages with shared atomics and with per-thread stripes against not counting at all
+//!
+//! Every thread replays the counter writes of scheduling slots that rotate over the same Actors, so that every Actor is handled by every thread over time.
+//! The gap only shows on machines with several cores, by default the thread counts go up to the number of cores.
+//! `cargo bench --bench metric_stripes -- [--threads N] [--actors N] [--slots N] [--budget N] [--handler-work N]`
+use tyractorsaur::bench::{CounterContention, CounterLayout};
+
+fn main() {
+    let mut max_threads = num_cpus();
+    let mut contention = CounterContention::new(1);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().and_then(|value| value.parse::<usize>().ok());
+        match arg.as_str() {
+            "--threads" => max_threads = value().expect("--threads requires a number"),
+            "--actors" => contention = contention.with_actors(value().expect("--actors requires a number")),
+            "--slots" => contention = contention.with_slots(value().expect("--slots requires a number")),
+            "--budget" => contention = contention.with_budget(value().expect("--budget requires a number")),
+            "--handler-work" => {
+                contention = contention.with_handler_work(value().expect("--handler-work requires a number"))
+            }
+            // e.g. `--bench`, added by cargo
+            _ => {}
+        }
+    }
+    println!(
+        "{} Actors, {} slots of {} messages per thread, {} rounds of handler work per message\n",
+        contention.actors, contention.slots, contention.budget, contention.handler_work
+    );
+    println!(
+        "{:>8} {:>14} {:>14} {:>14} {:>12} {:>12}",
+        "threads", "off msg/s", "shared msg/s", "striped msg/s", "shared gap", "striped gap"
+    );
+    let mut threads = 1;
+    loop {
+        let run = CounterContention { threads, ..contention.clone() };
+        let messages = run.messages();
+        let off = run.measure(CounterLayout::Off);
+        let shared = run.measure(CounterLayout::Shared);
+        let striped = run.measure(CounterLayout::Striped);
+        assert_eq!(messages as u64, shared.counted);
+        assert_eq!(messages as u64, striped.counted);
+        let gap = |elapsed: f64| (1.0 - off.elapsed.as_secs_f64() / elapsed) * 100.0;
+        println!(
+            "{:>8} {:>14.0} {:>14.0} {:>14.0} {:>11.1}% {:>11.1}%",
+            threads,
+            off.messages_per_second(messages),
+            shared.messages_per_second(messages),
+            striped.messages_per_second(messages),
+            gap(shared.elapsed.as_secs_f64()),
+            gap(striped.elapsed.as_secs_f64())
+        );
+        if threads >= max_threads {
+            break;
+        }
+        threads = (threads * 2).min(max_threads);
+    }
+}
+
+fn num_cpus() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get())
+}
@@ -0,0 +1,35 @@
+//! Prints the cost of every optional feature on this machine
+//!
+//! `cargo bench --bench overhead [--features replay] -- [--messages N] [--payload-bytes N] [--runs N] [--json]`
+use tyractorsaur::bench::{CountingAllocator, MatrixConfig, OverheadReport};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let mut config = MatrixConfig::new();
+    let mut json = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().and_then(|value| value.parse::<usize>().ok());
+        match arg.as_str() {
+            "--messages" => {
+                config = config.with_messages(value().expect("--messages requires a number"))
+            }
+            "--payload-bytes" => {
+                config =
+                    config.with_payload_bytes(value().expect("--payload-bytes requires a number"))
+            }
+            "--runs" => config = config.with_runs(value().expect("--runs requires a number")),
+            "--json" => json = true,
+            // e.g. `--bench`, added by cargo
+            _ => {}
+        }
+    }
+    let report = OverheadReport::measure(config);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{}", report);
+    }
+}
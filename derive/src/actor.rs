@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, DeriveInput, Ident, LitStr, Result};
+
+/// Settings of `#[actor(..)]`
+struct ActorAttributes {
+    /// name of the generated factory
+    default_factory: Option<Ident>,
+}
+
+fn parse_attributes(input: &DeriveInput) -> Result<ActorAttributes> {
+    let mut attributes = ActorAttributes { default_factory: None };
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("actor")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default_factory") {
+                let name = if meta.input.peek(syn::Token![=]) {
+                    let name: LitStr = meta.value()?.parse()?;
+                    name.parse()?
+                } else {
+                    format_ident!("{}Factory", input.ident)
+                };
+                attributes.default_factory = Some(name);
+                return Ok(());
+            }
+            Err(meta.error("unsupported actor attribute, expected `default_factory`"))
+        })?;
+    }
+    Ok(attributes)
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let attributes = parse_attributes(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut expanded = quote! {
+        impl #impl_generics ::tyractorsaur::prelude::Actor for #name #ty_generics #where_clause {}
+    };
+    let factory = match attributes.default_factory {
+        Some(factory) => factory,
+        None => return Ok(expanded),
+    };
+
+    let vis = &input.vis;
+    let params = &input.generics.params;
+    let doc = format!("Creates every `{}` through `Default`", name);
+    let mut factory_generics = input.generics.clone();
+    factory_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#name #ty_generics: ::std::default::Default + ::std::panic::UnwindSafe + 'static));
+    let (_, _, factory_where_clause) = factory_generics.split_for_impl();
+    expanded.extend(quote! {
+        #[doc = #doc]
+        #vis struct #factory <#params> #where_clause {
+            _actor: ::std::marker::PhantomData<fn() -> #name #ty_generics>,
+        }
+
+        impl #impl_generics ::std::default::Default for #factory #ty_generics #where_clause {
+            fn default() -> Self {
+                Self { _actor: ::std::marker::PhantomData }
+            }
+        }
+
+        impl #impl_generics ::tyractorsaur::prelude::ActorFactory<#name #ty_generics> for #factory #ty_generics #factory_where_clause {
+            fn new_actor(&self, _context: ::tyractorsaur::prelude::ActorContext<#name #ty_generics>) -> #name #ty_generics {
+                ::std::default::Default::default()
+            }
+        }
+    });
+    Ok(expanded)
+}
@@ -0,0 +1,80 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Error, FnArg, ImplItem, ImplItemFn, ItemImpl, ReceiverKind, Result, ReturnType, Type};
+
+const SIGNATURE: &str = "expected `fn(&mut self, msg: M, context: &ActorContext<Self>)`, helper methods belong into a separate impl block";
+
+/// Returns the message type of a method with the signature of `Handler.handle`
+fn message_type(method: &ImplItemFn) -> Result<&Type> {
+    let sig = &method.sig;
+    if sig.asyncness.is_some() || sig.constness.is_some() || !sig.generics.params.is_empty() {
+        return Err(Error::new(sig.span(), SIGNATURE));
+    }
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.mutability.is_none() && matches!(receiver.kind, ReceiverKind::Reference(_, _, Some(_))) => {}
+        Some(arg) => return Err(Error::new(arg.span(), SIGNATURE)),
+        None => return Err(Error::new(sig.span(), SIGNATURE)),
+    }
+    let msg = match inputs.next() {
+        Some(FnArg::Typed(msg)) => &*msg.ty,
+        Some(arg) => return Err(Error::new(arg.span(), SIGNATURE)),
+        None => return Err(Error::new(sig.span(), SIGNATURE)),
+    };
+    match inputs.next() {
+        Some(FnArg::Typed(context)) if matches!(&*context.ty, Type::Reference(_)) => {}
+        Some(arg) => return Err(Error::new(arg.span(), SIGNATURE)),
+        None => return Err(Error::new(sig.span(), SIGNATURE)),
+    }
+    if let Some(arg) = inputs.next() {
+        return Err(Error::new(arg.span(), SIGNATURE));
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        return Err(Error::new(ty.span(), "handlers return nothing"));
+    }
+    Ok(msg)
+}
+
+pub fn expand(item: ItemImpl) -> Result<TokenStream> {
+    if let Some((path, _)) = &item.trait_ {
+        return Err(Error::new(path.span(), "`#[handlers]` expects an inherent impl block"));
+    }
+    let self_ty = &item.self_ty;
+    let (impl_generics, _, where_clause) = item.generics.split_for_impl();
+    let mut handlers = TokenStream::new();
+    let mut errors: Option<Error> = None;
+    for method in item.items.iter().filter_map(|item| match item {
+        ImplItem::Fn(method) => Some(method),
+        _ => None,
+    }) {
+        let msg = match message_type(method) {
+            Ok(msg) => msg,
+            Err(error) => {
+                match errors.as_mut() {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+                continue;
+            }
+        };
+        let method = &method.sig.ident;
+        // unsatisfied bounds of the message type point at the method
+        let handler = quote_spanned!(msg.span()=> ::tyractorsaur::prelude::Handler<#msg>);
+        handlers.extend(quote! {
+            impl #impl_generics #handler for #self_ty #where_clause {
+                fn handle(&mut self, msg: #msg, context: &::tyractorsaur::prelude::ActorContext<Self>) {
+                    Self::#method(self, msg, context)
+                }
+            }
+        });
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+    Ok(quote! {
+        #item
+        #handlers
+    })
+}
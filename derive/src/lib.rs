@@ -0,0 +1,312 @@
+//! Derive macros for [tyractorsaur](https://docs.rs/tyractorsaur), re-exported through its `derive` feature
+//!
+//! Generated code refers to `::tyractorsaur` only, hand-written implementations can be mixed freely with derived ones
+
+extern crate proc_macro;
+
+mod actor;
+mod handlers;
+mod message;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemImpl};
+
+/// Implements `ActorMessage`
+///
+/// `#[message(size = "<expr>")]` also implements `ActorMessage::approx_size`, the expression returns the heap memory owned by the message in bytes and is added to its shallow size.
+/// Fields of structs with named fields are accessible by their name, all other messages use `self`
+///
+/// `#[message(fallible)]` also implements `FallibleMessage`, so that the message is handled by a `TryHandler`
+///
+/// `#[message(control_flow)]` also implements `FallibleMessage` and `ControlFlowMessage`, so that the message is handled by a `ControlFlowHandler`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(ActorMessage)]
+/// struct Ping;
+///
+/// #[derive(ActorMessage)]
+/// #[message(size = "payload.len() + name.capacity()")]
+/// struct Upload {
+///     name: String,
+///     payload: Vec<u8>,
+/// }
+///
+/// #[derive(ActorMessage)]
+/// #[message(size = "self.0.len()")]
+/// struct Chunk<T: Send + Sync>(Vec<T>);
+///
+/// #[derive(ActorMessage)]
+/// #[message(fallible, size = "self.0.capacity()")]
+/// struct Parse(String);
+///
+/// #[derive(Actor)]
+/// struct Parser;
+/// impl TryHandler<Parse> for Parser {
+///     fn try_handle(&mut self, msg: Parse, _context: &ActorContext<Self>) -> Result<(), ActorError> {
+///         msg.0.parse::<u32>()?;
+///         Ok(())
+///     }
+/// }
+///
+/// #[derive(ActorMessage)]
+/// #[message(control_flow)]
+/// struct Shutdown;
+///
+/// #[derive(Actor)]
+/// struct Session;
+/// impl ControlFlowHandler<Shutdown> for Session {
+///     fn handle_flow(&mut self, _msg: Shutdown, _context: &ActorContext<Self>) -> ActorResult {
+///         ActorResult::Stop
+///     }
+/// }
+///
+/// let upload = Upload { name: String::new(), payload: vec![0; 1000] };
+/// assert_eq!(std::mem::size_of::<Upload>() + 1000, upload.approx_size());
+/// let chunk = Chunk(vec![1u8, 2, 3]);
+/// assert_eq!(std::mem::size_of::<Chunk<u8>>() + 3, chunk.approx_size());
+/// ```
+///
+/// Messages have no priority of their own:
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(ActorMessage)]
+/// #[message(priority = "high")]
+/// struct Urgent;
+/// ```
+#[proc_macro_derive(ActorMessage, attributes(message))]
+pub fn derive_actor_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    message::expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `Actor` without any hooks, Actors with hooks implement it by hand
+///
+/// `#[actor(default_factory)]` also generates `<Actor>Factory`, an `ActorFactory` that creates the Actor through `Default`.
+/// `#[actor(default_factory = "Name")]` chooses the name of the factory
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::panic::UnwindSafe;
+/// use std::time::Duration;
+///
+/// #[derive(Actor, Default)]
+/// #[actor(default_factory)]
+/// struct Counter {
+///     count: usize,
+/// }
+///
+/// #[derive(Actor)]
+/// #[actor(default_factory = "BufferSpawner")]
+/// struct Buffer<T: Send + Sync + UnwindSafe + 'static> {
+///     items: Vec<T>,
+/// }
+/// impl<T: Send + Sync + UnwindSafe + 'static> Default for Buffer<T> {
+///     fn default() -> Self {
+///         Self { items: Vec::new() }
+///     }
+/// }
+///
+/// #[derive(ActorMessage)]
+/// struct Item<T: Send + Sync>(T);
+/// #[handlers]
+/// impl<T: Send + Sync + UnwindSafe + 'static> Buffer<T> {
+///     fn on_item(&mut self, msg: Item<T>, _context: &ActorContext<Self>) {
+///         self.items.push(msg.0);
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// actor_system.builder().spawn("counter", CounterFactory::default()).unwrap();
+/// let buffer = actor_system.builder().spawn("buffer", BufferSpawner::<String>::default()).unwrap();
+/// buffer.send(Item(String::from("first")));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+///
+/// The factory requires `Default`:
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(Actor)]
+/// #[actor(default_factory)]
+/// struct Counter {
+///     count: usize,
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// actor_system.builder().spawn("counter", CounterFactory::default()).unwrap();
+/// ```
+#[proc_macro_derive(Actor, attributes(actor))]
+pub fn derive_actor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    actor::expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Implements `Handler<M>` for every method of an inherent impl block with the signature `fn(&mut self, msg: M, context: &ActorContext<Self>)`
+///
+/// The methods are kept as they are and each `Handler.handle` calls its method. Every method of the block has to be a handler, helper methods belong into a separate impl block
+///
+/// # Examples
+///
+/// Basic usage, the same Actor with hand-written and with generated handlers:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// #[derive(ActorMessage)]
+/// struct Add(usize);
+/// #[derive(ActorMessage)]
+/// struct Sub(usize);
+///
+/// type Log = Arc<Mutex<Vec<usize>>>;
+///
+/// struct Manual { total: usize, log: Log }
+/// impl Actor for Manual {}
+/// impl Handler<Add> for Manual {
+///     fn handle(&mut self, msg: Add, _context: &ActorContext<Self>) {
+///         self.total += msg.0;
+///         self.log.lock().unwrap().push(self.total);
+///     }
+/// }
+/// impl Handler<Sub> for Manual {
+///     fn handle(&mut self, msg: Sub, _context: &ActorContext<Self>) {
+///         self.total -= msg.0;
+///         self.log.lock().unwrap().push(self.total);
+///     }
+/// }
+/// struct ManualFactory { log: Log }
+/// impl ActorFactory<Manual> for ManualFactory {
+///     fn new_actor(&self, _context: ActorContext<Manual>) -> Manual {
+///         Manual { total: 0, log: self.log.clone() }
+///     }
+/// }
+///
+/// #[derive(Actor)]
+/// struct Derived { total: usize, log: Log }
+/// #[handlers]
+/// impl Derived {
+///     fn on_add(&mut self, msg: Add, _context: &ActorContext<Self>) {
+///         self.total += msg.0;
+///         self.log.lock().unwrap().push(self.total);
+///     }
+///     fn on_sub(&mut self, msg: Sub, _context: &ActorContext<Self>) {
+///         self.total -= msg.0;
+///         self.log.lock().unwrap().push(self.total);
+///     }
+/// }
+/// // hand-written handlers coexist with generated ones
+/// impl Handler<Ping> for Derived {
+///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+/// }
+/// #[derive(ActorMessage)]
+/// struct Ping;
+/// struct DerivedFactory { log: Log }
+/// impl ActorFactory<Derived> for DerivedFactory {
+///     fn new_actor(&self, _context: ActorContext<Derived>) -> Derived {
+///         Derived { total: 0, log: self.log.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let manual_log = Log::default();
+/// let derived_log = Log::default();
+/// let manual = actor_system.builder().spawn("manual", ManualFactory { log: manual_log.clone() }).unwrap();
+/// let derived = actor_system.builder().spawn("derived", DerivedFactory { log: derived_log.clone() }).unwrap();
+/// for i in 1..=20 {
+///     manual.send(Add(i * 2));
+///     derived.send(Add(i * 2));
+///     manual.send(Sub(i));
+///     derived.send(Sub(i));
+/// }
+/// derived.send(Ping);
+/// sleep(Duration::from_millis(300));
+/// assert_eq!(40, manual_log.lock().unwrap().len());
+/// assert_eq!(*manual_log.lock().unwrap(), *derived_log.lock().unwrap());
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+///
+/// Methods with a different signature are rejected:
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(ActorMessage)]
+/// struct Add(usize);
+///
+/// #[derive(Actor)]
+/// struct Counter { total: usize }
+/// #[handlers]
+/// impl Counter {
+///     fn on_add(&self, msg: Add, _context: &ActorContext<Self>) {}
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(ActorMessage)]
+/// struct Add(usize);
+///
+/// #[derive(Actor)]
+/// struct Counter { total: usize }
+/// #[handlers]
+/// impl Counter {
+///     fn on_add(&mut self, msg: Add, _context: &ActorContext<Self>) -> usize {
+///         self.total
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// #[derive(Actor)]
+/// struct Counter { total: usize }
+/// #[handlers]
+/// impl Counter {
+///     fn total(&self) -> usize {
+///         self.total
+///     }
+/// }
+/// ```
+///
+/// Message types have to implement `ActorMessage`:
+///
+/// ```compile_fail
+/// use tyractorsaur::prelude::*;
+///
+/// struct Add(usize);
+///
+/// #[derive(Actor)]
+/// struct Counter { total: usize }
+/// #[handlers]
+/// impl Counter {
+///     fn on_add(&mut self, msg: Add, _context: &ActorContext<Self>) {
+///         self.total += msg.0;
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn handlers(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let error = syn::Error::new(proc_macro2::Span::call_site(), "`#[handlers]` takes no arguments");
+        return error.into_compile_error().into();
+    }
+    let item = parse_macro_input!(input as ItemImpl);
+    handlers::expand(item).unwrap_or_else(syn::Error::into_compile_error).into()
+}
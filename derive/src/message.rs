@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Fields, LitStr, Result};
+
+/// Settings of `#[message(..)]`
+struct MessageAttributes {
+    size: Option<Expr>,
+    fallible: bool,
+    control_flow: bool,
+}
+
+fn parse_attributes(input: &DeriveInput) -> Result<MessageAttributes> {
+    let mut attributes = MessageAttributes {
+        size: None,
+        fallible: false,
+        control_flow: false,
+    };
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("message")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("size") {
+                let expr: LitStr = meta.value()?.parse()?;
+                attributes.size = Some(expr.parse()?);
+                return Ok(());
+            }
+            if meta.path.is_ident("fallible") {
+                attributes.fallible = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("control_flow") {
+                attributes.control_flow = true;
+                return Ok(());
+            }
+            if meta.path.is_ident("priority") {
+                return Err(meta.error(
+                    "messages have no priority, the priority of the receiving Actor is set through `ActorBuilder.set_priority`",
+                ));
+            }
+            Err(meta.error("unsupported message attribute, expected `size`, `fallible` or `control_flow`"))
+        })?;
+    }
+    Ok(attributes)
+}
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let attributes = parse_attributes(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // fields of named structs are accessible by name, everything else through `self`
+    let approx_size = match attributes.size {
+        Some(size) => {
+            let fields = match &input.data {
+                Data::Struct(data) => match &data.fields {
+                    Fields::Named(fields) => {
+                        let names = fields.named.iter().map(|field| &field.ident);
+                        quote! {
+                            #[allow(unused_variables)]
+                            let Self { #(#names,)* } = self;
+                        }
+                    }
+                    _ => TokenStream::new(),
+                },
+                _ => TokenStream::new(),
+            };
+            quote! {
+                fn approx_size(&self) -> usize {
+                    #fields
+                    ::std::mem::size_of::<Self>() + (#size)
+                }
+            }
+        }
+        None => TokenStream::new(),
+    };
+    let mut expanded = quote! {
+        impl #impl_generics ::tyractorsaur::prelude::ActorMessage for #name #ty_generics #where_clause {
+            #approx_size
+        }
+    };
+    if attributes.fallible || attributes.control_flow {
+        expanded.extend(quote! {
+            impl #impl_generics ::tyractorsaur::prelude::FallibleMessage for #name #ty_generics #where_clause {}
+        });
+    }
+    if attributes.control_flow {
+        expanded.extend(quote! {
+            impl #impl_generics ::tyractorsaur::prelude::ControlFlowMessage for #name #ty_generics #where_clause {}
+        });
+    }
+    Ok(expanded)
+}
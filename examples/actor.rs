@@ -16,10 +16,6 @@ impl ActorMessage for MessageA {}
 
 impl ActorMessage for MessageB {}
 
-struct MessageUnsupported {}
-
-impl ActorMessage for MessageUnsupported {}
-
 struct HelloWorld {
     text: String,
     count: usize,
@@ -42,7 +38,7 @@ impl ActorFactory<HelloWorld> for HelloWorldFactory {
 }
 impl Handler<MessageA> for HelloWorld {
     fn handle(&mut self, msg: MessageA, _context: &ActorContext<Self>) {
-        let text: String = [self.text.clone(), String::from(msg.text)].join(" -> ");
+        let text: String = [self.text.clone(), msg.text].join(" -> ");
         self.count += 1;
         println!("AAAA: {} Count: {}", text, self.count)
     }
@@ -50,7 +46,7 @@ impl Handler<MessageA> for HelloWorld {
 
 impl Handler<MessageB> for HelloWorld {
     fn handle(&mut self, msg: MessageB, _context: &ActorContext<Self>) {
-        let text: String = [self.text.clone(), String::from(msg.text)].join(" -> ");
+        let text: String = [self.text.clone(), msg.text].join(" -> ");
         self.count -= 1;
         println!("BBBB: {} Count: {}", text, self.count)
     }
@@ -71,27 +67,26 @@ fn main() {
         .builder()
         .set_mailbox_size(7)
         .set_pool_name("aye")
+        .unwrap()
         .spawn("hello-world", hw).unwrap();
     x.send(MessageA {
         text: String::from("sers+1"),
-    });
+    }).unwrap();
     x.send(MessageA {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(MessageB {
         text: String::from("sers-1"),
-    });
+    }).unwrap();
     x.send(MessageA {
         text: String::from("sers+3"),
-    });
+    }).unwrap();
     x.send(MessageA {
         text: String::from("sers+4"),
-    });
+    }).unwrap();
     x.send(MessageA {
         text: String::from("sers+5"),
-    });
-
-    //x.send(MessageUnsupported{text: String::from("sers")});
+    }).unwrap();
 
     actor_system.stop(Duration::from_secs(1));
     exit(actor_system.await_shutdown());
@@ -47,10 +47,12 @@ impl Actor for Benchmark {
 }
 
 impl Handler<MessageA> for Benchmark {
+    // is_multiple_of needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
     fn handle(&mut self, _msg: MessageA, context: &ActorContext<Self>) {
         if self.count == 0 {
             println!("Sleep 3 now");
-            sleep(Duration::from_secs((3) as u64));
+            sleep(Duration::from_secs(3));
             println!("Sleep 3 end");
             self.start = Instant::now();
         }
@@ -89,7 +91,7 @@ fn main() {
 
     for _i in 0..message_count {
         let msg = MessageA {};
-        actor.send(msg);
+        actor.send(msg).unwrap();
     }
     let duration = start.elapsed();
     println!("It took {:?} to send {} messages", duration, message_count);
@@ -0,0 +1,78 @@
+use std::process::exit;
+use std::time::Duration;
+use tyractorsaur::prelude::{handlers, Actor, ActorContext, ActorFactory, ActorMessage, ActorSystem, TyractorsaurConfig};
+
+#[derive(ActorMessage)]
+struct MessageA {
+    text: String,
+}
+
+#[derive(ActorMessage)]
+struct MessageB {
+    text: String,
+}
+
+#[derive(Actor)]
+struct HelloWorld {
+    text: String,
+    count: usize,
+}
+
+struct HelloWorldFactory {
+    text: String,
+    count: usize,
+}
+
+impl ActorFactory<HelloWorld> for HelloWorldFactory {
+    fn new_actor(&self, _context: ActorContext<HelloWorld>) -> HelloWorld {
+        HelloWorld {
+            count: self.count,
+            text: self.text.clone(),
+        }
+    }
+}
+
+#[handlers]
+impl HelloWorld {
+    fn on_message_a(&mut self, msg: MessageA, _context: &ActorContext<Self>) {
+        let text: String = [self.text.clone(), msg.text].join(" -> ");
+        self.count += 1;
+        println!("AAAA: {} Count: {}", text, self.count)
+    }
+
+    fn on_message_b(&mut self, msg: MessageB, _context: &ActorContext<Self>) {
+        let text: String = [self.text.clone(), msg.text].join(" -> ");
+        self.count -= 1;
+        println!("BBBB: {} Count: {}", text, self.count)
+    }
+}
+
+fn main() {
+    let actor_config = TyractorsaurConfig::new().unwrap();
+    let actor_system = ActorSystem::new(actor_config);
+
+    actor_system.add_pool("aye");
+
+    let hw = HelloWorldFactory {
+        text: String::from("sers"),
+        count: 0,
+    };
+    let x = actor_system
+        .builder()
+        .set_mailbox_size(7)
+        .set_pool_name("aye")
+        .unwrap()
+        .spawn("hello-world", hw).unwrap();
+    x.send(MessageA {
+        text: String::from("sers+1"),
+    }).unwrap();
+    x.send(MessageA {
+        text: String::from("sers+2"),
+    }).unwrap();
+    x.send(MessageB {
+        text: String::from("sers-1"),
+    }).unwrap();
+
+    actor_system.stop(Duration::from_secs(1));
+    exit(actor_system.await_shutdown());
+}
@@ -25,7 +25,7 @@ impl Handler<ErrMsg> for ErrActor {
         if msg.text == "sers+1" {
             panic!("ficl");
         }
-        println!("Received SERS: {}", self.counter);
+        println!("Received {}: {}", self.text, self.counter);
     }
 }
 
@@ -57,28 +57,28 @@ fn main() {
         .spawn("hello-world", hw).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+1"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+1"),
-    });
+    }).unwrap();
     x.send(ErrMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
 
     actor_system.stop(Duration::from_secs(5));
     exit(actor_system.await_shutdown())
@@ -49,23 +49,23 @@ fn main() {
     let router_factory = RoundRobinRouterFactory::new();
     let router = actor_system.builder().spawn("hello-router", router_factory).unwrap();
 
-    router.send(AddActorMessage::new(x));
-    router.send(AddActorMessage::new(y.clone()));
+    router.send(AddActorMessage::new(x)).unwrap();
+    router.send(AddActorMessage::new(y.clone())).unwrap();
 
     // the sleep is simply there, so that the output clearly shows the messages are routed round robin
     // since the routers work async it would not be visible otherwise
-    router.send(RouterMessage::new(MessageA {}));
-    router.send(RouterMessage::new(MessageA {}));
+    router.send(RouterMessage::new(MessageA {})).unwrap();
+    router.send(RouterMessage::new(MessageA {})).unwrap();
     sleep(Duration::from_millis(50));
-    router.send(RouterMessage::new(MessageA {}));
-    router.send(RouterMessage::new(MessageA {}));
+    router.send(RouterMessage::new(MessageA {})).unwrap();
+    router.send(RouterMessage::new(MessageA {})).unwrap();
     sleep(Duration::from_millis(50));
-    router.send(RouterMessage::new(MessageA {}));
-    router.send(RouterMessage::new(MessageA {}));
+    router.send(RouterMessage::new(MessageA {})).unwrap();
+    router.send(RouterMessage::new(MessageA {})).unwrap();
 
-    router.send(RemoveActorMessage::new(y));
-    router.send(RouterMessage::new(MessageA {}));
-    router.send(RouterMessage::new(MessageA {}));
+    router.send(RemoveActorMessage::new(y)).unwrap();
+    router.send(RouterMessage::new(MessageA {})).unwrap();
+    router.send(RouterMessage::new(MessageA {})).unwrap();
 
     actor_system.stop(Duration::from_secs(1));
     exit(actor_system.await_shutdown());
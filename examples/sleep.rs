@@ -21,13 +21,13 @@ struct SleepActor {
 impl Actor for SleepActor {}
 
 impl Handler<SleepMsg> for SleepActor {
-    fn handle(&mut self, _msg: SleepMsg, _context: &ActorContext<Self>) {
+    fn handle(&mut self, msg: SleepMsg, _context: &ActorContext<Self>) {
         self.counter += 1;
         //if self.counter == 1 {
         sleep(Duration::from_secs(3));
         //}
         //if self.counter % 1000000 == 0 {
-        println!("Received SERS: {}", self.counter);
+        println!("{} received {}: {}", self.text, msg.text, self.counter);
         //}
     }
 }
@@ -60,19 +60,19 @@ fn main() {
         .spawn("hello-world", hw).unwrap();
     x.send(SleepMsg {
         text: String::from("sers+1"),
-    });
+    }).unwrap();
 
     sleep(Duration::from_secs(1));
 
     x.send(SleepMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
 
     sleep(Duration::from_secs(1));
 
     x.send(SleepMsg {
         text: String::from("sers+2"),
-    });
+    }).unwrap();
     //loop {
     //    //sleep(Duration::from_micros(1));
     //    x.send(SleepMsg {
@@ -2,7 +2,7 @@ use std::process::exit;
 use std::thread::sleep;
 use std::time::Duration;
 use tyractorsaur::prelude::{
-    Actor, ActorFactory, ActorMessage, ActorSystem, ActorContext, Handler, TyractorsaurConfig,
+    Actor, ActorFactory, ActorMessage, ActorSystem, ActorContext, Handler, SendError, TyractorsaurConfig,
 };
 
 #[derive(Clone)]
@@ -27,7 +27,8 @@ impl Actor for StopActor {
 
 impl Handler<TestMsg> for StopActor {
     fn handle(&mut self, _msg: TestMsg, context: &ActorContext<Self>) {
-        context.actor_ref.send(TestMsg {});
+        // fails once the actor has stopped
+        let _ = context.actor_ref.send(TestMsg {});
         println!("Message received!");
         sleep(Duration::from_millis(100));
     }
@@ -51,15 +52,17 @@ fn main() {
         .set_mailbox_size(7)
         .spawn("hello-world", hw).unwrap();
     // this is obviously handled, because it's the actor is still running
-    x.send(TestMsg {});
+    x.send(TestMsg {}).unwrap();
     sleep(Duration::from_millis(700));
 
     x.stop();
     // this is still handled, because the actor has not handled the stop Message yet
-    x.send(TestMsg {});
+    x.send(TestMsg {}).unwrap();
     sleep(Duration::from_millis(200));
     // this is no longer handled, because the actor has stopped by now
-    x.send(TestMsg {});
+    if let Err(SendError::Stopped(_)) = x.send(TestMsg {}) {
+        println!("Message rejected!");
+    }
 
     let result = actor_system.await_shutdown();
 
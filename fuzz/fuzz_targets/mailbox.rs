@@ -0,0 +1,25 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use tyractorsaur::prelude::RestartPolicy;
+use tyractorsaur::testing::*;
+
+// the first byte selects the harness configuration, all remaining bytes form the script
+fuzz_target!(|data: &[u8]| {
+    if data.is_empty() {
+        return;
+    }
+    let restart_policy = if data[0] & 0x80 == 0 {
+        RestartPolicy::Always
+    } else {
+        RestartPolicy::Never
+    };
+    let harness = MailboxHarness::new(HarnessConfig {
+        threads: 1 + (data[0] & 0x03) as usize,
+        message_throughput: 1 + ((data[0] >> 2) & 0x07) as usize,
+        restart_policy,
+        ..HarnessConfig::default()
+    });
+    if let Err(violation) = harness.run_bytes(&data[1..]) {
+        panic!("{:?}", violation);
+    }
+});
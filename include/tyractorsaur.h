@@ -0,0 +1,76 @@
+/*
+ * C API of tyractorsaur, available with the `ffi` feature
+ *
+ * Ownership:
+ * - handles are created by tyra_system_new or TyraSystem::into_raw on the Rust side
+ * - every handle is released exactly once through tyra_system_free, debug builds detect double frees
+ * - tyra_system_free does not stop the system, call tyra_system_stop first
+ * - all pointer arguments are only borrowed for the duration of the call, payloads are copied
+ *
+ * All functions except tyra_system_free may be called from any thread.
+ */
+
+#ifndef TYRACTORSAUR_H
+#define TYRACTORSAUR_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct TyraSystem TyraSystem;
+
+typedef enum TyraStatus {
+    TYRA_STATUS_OK = 0,
+    TYRA_STATUS_NULL_POINTER = 1,
+    TYRA_STATUS_INVALID_UTF8 = 2,
+    TYRA_STATUS_INVALID_CONFIG = 3,
+    TYRA_STATUS_UNKNOWN_ACTOR = 4,
+    TYRA_STATUS_UNKNOWN_MESSAGE_TYPE = 5,
+    TYRA_STATUS_INVALID_PAYLOAD = 6,
+    TYRA_STATUS_SYSTEM_STOPPED = 7,
+    TYRA_STATUS_INVALID_HANDLE = 8,
+    TYRA_STATUS_PANIC = 9,
+    TYRA_STATUS_SYSTEM_NAME_IN_USE = 10
+} TyraStatus;
+
+typedef enum TyraShutdownCode {
+    TYRA_SHUTDOWN_GRACEFUL = 0,
+    TYRA_SHUTDOWN_FORCED = 1,
+    TYRA_SHUTDOWN_FAILED = 2,
+    TYRA_SHUTDOWN_PANICKED = 3
+} TyraShutdownCode;
+
+/* event_json is a NUL-terminated JSON object that is only valid during the call */
+typedef void (*TyraEventCallback)(const char *event_json, void *user_data);
+
+/* status of the last call on the current thread */
+TyraStatus tyra_last_status(void);
+
+/* NULL uses the default configuration, returns NULL on failure */
+TyraSystem *tyra_system_new(const char *config_toml);
+
+/* payload may be NULL if payload_len is 0 */
+TyraStatus tyra_system_send(const TyraSystem *system,
+                            const char *actor_name,
+                            const char *type_name,
+                            const uint8_t *payload,
+                            size_t payload_len);
+
+/* callback may be NULL to remove it, user_data must be usable from any thread */
+TyraStatus tyra_system_register_event_callback(const TyraSystem *system,
+                                               TyraEventCallback callback,
+                                               void *user_data);
+
+/* blocks until the system stopped */
+TyraShutdownCode tyra_system_stop(const TyraSystem *system, uint64_t timeout_ms);
+
+TyraStatus tyra_system_free(TyraSystem *system);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
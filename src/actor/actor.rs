@@ -1,3 +1,8 @@
+use crate::actor::context::ActorContext;
+use crate::actor::panic_report::ActorPanicReport;
+use crate::actor::supervision::ChildTerminated;
+use crate::actor::suspension::SuspensionEndReason;
+use crate::actor::unhandled::UnhandledInfo;
 use crate::message::serialized_message::SerializedMessage;
 use std::panic::UnwindSafe;
 
@@ -13,6 +18,8 @@ use std::panic::UnwindSafe;
 /// 5. Stops accepting new messages, but will continue to work through all existing Messages in Mailbox
 /// 6. [post_stop](../prelude/trait.Actor.html#method.post_stop)
 ///
+/// A panic executes [on_panic](../prelude/trait.Actor.html#method.on_panic) and [post_stop](../prelude/trait.Actor.html#method.post_stop), followed by [pre_restart](../prelude/trait.Actor.html#method.pre_restart) and 1. if the Actor is restarted
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -77,24 +84,380 @@ pub trait Actor: Send + Sync + UnwindSafe {
     /// executed before the first message is handled
     ///
     /// re-executed after actor restart before first message is handled
+    ///
+    /// A panic within `pre_start` is treated like a panic while handling a message, the Actor is restarted or stopped according to its [RestartPolicy](../prelude/enum.RestartPolicy.html)
     fn pre_start(&mut self) {}
     /// executed after the last message is handled
     ///
-    /// also executed in case the actor panics while it handles a message
+    /// also executed in case the actor panics while it handles a message, and when a forced stop of the system ends the Actor before it handled all queued messages.
+    /// An Actor that is handling a message while the system is force stopped executes `post_stop` once the handler returned
+    ///
+    /// Not executed if `pre_start` has not been executed before
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Worker {
+    ///     log: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn pre_start(&mut self) {
+    ///         self.log.lock().unwrap().push("pre_start");
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.log.lock().unwrap().push("post_stop");
+    ///     }
+    /// }
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push("work");
+    ///         sleep(Duration::from_millis(100));
+    ///     }
+    /// }
+    ///
+    /// struct WorkerFactory {
+    ///     log: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let worker = actor_system.builder().spawn("worker", WorkerFactory { log: log.clone() }).unwrap();
+    /// for _ in 0..10 {
+    ///     worker.send(Work {});
+    /// }
+    /// sleep(Duration::from_millis(50));
+    ///
+    /// // the Worker does not implement on_system_stop, the stop only ends through the force stop
+    /// actor_system.force_stop();
+    /// assert_eq!(1, actor_system.await_shutdown());
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec!["pre_start", "work", "post_stop"], *log.lock().unwrap());
+    /// ```
     fn post_stop(&mut self) {}
+    /// executed when the Actor panicked and is about to be replaced according to [RestartPolicy::Always](../prelude/enum.RestartPolicy.html#variant.Always)
+    ///
+    /// Executed on the crashed instance right after [post_stop](../prelude/trait.Actor.html#method.post_stop), before [ActorFactory.new_actor](../prelude/trait.ActorFactory.html#tymethod.new_actor) creates its replacement
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Worker {
+    ///     log: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn pre_start(&mut self) {
+    ///         self.log.lock().unwrap().push("pre_start");
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.log.lock().unwrap().push("post_stop");
+    ///     }
+    ///     fn pre_restart(&mut self) {
+    ///         self.log.lock().unwrap().push("pre_restart");
+    ///     }
+    /// }
+    /// impl Handler<Crash> for Worker {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         panic!("crash");
+    ///     }
+    /// }
+    ///
+    /// struct WorkerFactory {
+    ///     log: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = WorkerFactory { log: log.clone() };
+    /// let worker = actor_system.builder().set_restart_policy(RestartPolicy::Always).spawn("worker", factory).unwrap();
+    /// worker.send(Crash {});
+    /// sleep(Duration::from_millis(200));
+    /// worker.stop();
+    /// sleep(Duration::from_millis(200));
+    /// let expected = vec!["pre_start", "post_stop", "pre_restart", "pre_start", "post_stop"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    /// ```
+    fn pre_restart(&mut self) {}
+    /// executed when the Actor panics while it handles a message or a [TryHandler](../prelude/trait.TryHandler.html) returns an error, right before [post_stop](../prelude/trait.Actor.html#method.post_stop)
+    ///
+    /// See [ActorPanicReport](../prelude/struct.ActorPanicReport.html) for the captured payloads
+    fn on_panic(&mut self, _report: ActorPanicReport) {}
     /// executed when Actor handles internal ActorStopMessage
     ///
-    /// After this is called, the Actor will not accept any more messages, but messages within the mailbox will still be processed
+    /// After this is called, the Actor will not accept any more messages, but messages within the mailbox will still be processed.
+    /// The stop message overtakes all messages that are still queued, see [ActorWrapper.send_priority](../prelude/struct.ActorWrapper.html#method.send_priority)
     fn on_actor_stop(&mut self) {}
+    /// executed when the Actor stops itself after its idle timeout, see [ActorBuilder.set_idle_timeout](../prelude/struct.ActorBuilder.html#method.set_idle_timeout)
+    ///
+    /// Followed by [on_actor_stop](#method.on_actor_stop) and [post_stop](#method.post_stop) like any other stop
+    fn on_idle_stop(&mut self) {}
+    /// executed every tick interval, see [ActorBuilder.set_tick_interval](../prelude/struct.ActorBuilder.html#method.set_tick_interval)
+    ///
+    /// Executed between two messages on the thread of the Actor, a panic is treated like a panicking handler
+    fn on_tick(&mut self, _context: &ActorContext<Self>)
+    where
+        Self: Sized + 'static,
+    {
+    }
     /// executed when Actor handles internal SystemStopMessage initiated by [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop)
     ///
-    /// Without any custom implementation, the [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) will always end in timeout
+    /// Without any custom implementation, the [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) will always end in timeout.
+    /// Handled before or after the queued messages, see [SystemStopOrder](../prelude/enum.SystemStopOrder.html), a hook that does not return in time is left behind by the forced stop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Stuck {}
+    /// impl Actor for Stuck {
+    ///     fn on_system_stop(&mut self) {
+    ///         sleep(Duration::from_secs(2));
+    ///     }
+    /// }
+    /// struct StuckFactory {}
+    /// impl ActorFactory<Stuck> for StuckFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stuck>) -> Stuck {
+    ///         Stuck {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.builder().spawn("stuck", StuckFactory {}).unwrap();
+    /// let stopped = Instant::now();
+    /// actor_system.stop(Duration::from_millis(300));
+    /// assert_eq!(1, actor_system.await_shutdown());
+    /// assert!(stopped.elapsed() < Duration::from_secs(1));
+    /// ```
     fn on_system_stop(&mut self) {}
-    /// executed when [ActorSystem.send_to_address](../prelude/struct.ActorSystem.html#method.send_to_address) is called
+    /// executed when a suspension started through [ActorContext.suspend_until](../prelude/struct.ActorContext.html#method.suspend_until) ends
     ///
-    /// # Important Note
+    /// Runs before any other queued message is handled, except for the awaited message of [SuspendCondition::UntilMessage](../prelude/enum.SuspendCondition.html#variant.UntilMessage)
     ///
-    /// This is the only function that is not necessarily executed on the thread_pool of the Actor
-    /// It is executed on whatever thread calls [ActorSystem.send_to_address](../prelude/struct.ActorSystem.html#method.send_to_address)
+    /// Not executed if the suspension is cancelled by stopping the Actor
+    fn on_suspension_ended(&mut self, _reason: SuspensionEndReason) {}
+    /// executed when a child spawned through [ActorContext.spawn_child](../prelude/struct.ActorContext.html#method.spawn_child) stopped for good
+    ///
+    /// Delivered as a message, so it is not executed once this Actor stopped accepting messages
+    fn on_child_terminated(&mut self, _child: ChildTerminated) {}
+    /// checked after [pre_start](#method.pre_start) and after every handled message until it returns `true` once, see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    ///
+    /// An Actor that returns `false` becomes ready through [ActorContext.notify_ready](../prelude/struct.ActorContext.html#method.notify_ready) as well
+    fn is_ready(&self) -> bool {
+        true
+    }
+    /// executed by the default of [handle_serialized_message_mut](#method.handle_serialized_message_mut)
     fn handle_serialized_message(&self, _msg: SerializedMessage) {}
+    /// executed for every [SerializedMessage] without type tag sent through [ActorSystem.send_to_address](../prelude/struct.ActorSystem.html#method.send_to_address)
+    ///
+    /// The message is queued in the mailbox and handled in order with all other messages, just like a typed message.
+    /// A returned response is sent to the `reply_to` address of `msg`, with the `correlation_id` of `msg` unless the response has one of its own.
+    /// A response to a message without `reply_to` address is a dead letter
+    ///
+    /// Defaults to [handle_serialized_message](#method.handle_serialized_message) without a response
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Add { amount: u64 }
+    /// impl ActorMessage for Add {}
+    ///
+    /// struct Counter { count: u64 }
+    /// impl Actor for Counter {
+    ///     fn handle_serialized_message_mut(&mut self, msg: SerializedMessage, _context: &ActorContext<Self>) -> Option<SerializedMessage> {
+    ///         // increments by the amount of bytes and answers with the new count
+    ///         self.count += msg.content.len() as u64;
+    ///         Some(SerializedMessage::new(self.count.to_be_bytes().to_vec()))
+    ///     }
+    /// }
+    /// impl Handler<Add> for Counter {
+    ///     fn handle(&mut self, msg: Add, _context: &ActorContext<Self>) {
+    ///         self.count += msg.amount;
+    ///     }
+    /// }
+    /// struct CounterFactory {}
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { count: 0 }
+    ///     }
+    /// }
+    ///
+    /// struct Requester { responses: Arc<Mutex<Vec<(Option<u128>, u64)>>> }
+    /// impl Actor for Requester {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         let mut count = [0; 8];
+    ///         count.copy_from_slice(&msg.content);
+    ///         let count = u64::from_be_bytes(count);
+    ///         self.responses.lock().unwrap().push((msg.correlation_id, count));
+    ///     }
+    /// }
+    /// struct RequesterFactory { responses: Arc<Mutex<Vec<(Option<u128>, u64)>>> }
+    /// impl ActorFactory<Requester> for RequesterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Requester>) -> Requester {
+    ///         Requester { responses: self.responses.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let responses = Arc::new(Mutex::new(Vec::new()));
+    /// let counter = actor_system.builder().spawn("counter", CounterFactory {}).unwrap();
+    /// let requester = actor_system.builder().spawn("requester", RequesterFactory { responses: responses.clone() }).unwrap();
+    ///
+    /// // serialized and typed messages mutate the same state in the order they have been sent
+    /// let request = |content: &[u8], correlation_id: u128| {
+    ///     let request = SerializedMessage::new(content.to_vec())
+    ///         .with_correlation_id(correlation_id)
+    ///         .with_reply_to(requester.get_address().clone());
+    ///     actor_system.try_send_to_address(counter.get_address(), request).unwrap();
+    /// };
+    /// request(b"abc", 1);
+    /// counter.send(Add { amount: 10 }).unwrap();
+    /// request(b"de", 2);
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![(Some(1), 3), (Some(2), 15)], *responses.lock().unwrap());
+    ///
+    /// // nobody to answer to
+    /// actor_system.send_to_address(counter.get_address(), SerializedMessage::new(vec![0]));
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(1, actor_system.dead_letter_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    fn handle_serialized_message_mut(&mut self, msg: SerializedMessage, _context: &ActorContext<Self>) -> Option<SerializedMessage>
+    where
+        Self: Sized + 'static,
+    {
+        self.handle_serialized_message(msg);
+        None
+    }
+    /// executed on the thread of the Actor for messages that arrived but could not be handled
+    ///
+    /// That is a serialized message with a type tag the Actor did not register through [ActorBuilder.register_serialized](../prelude/struct.ActorBuilder.html#method.register_serialized) or whose content could not be deserialized,
+    /// or a typed message that could not be dispatched to its handler.
+    /// Every such message is counted through [ActorWrapper.unhandled_count](../prelude/struct.ActorWrapper.html#method.unhandled_count),
+    /// the default reports it as [unhandled dead letter](../prelude/enum.DeadLetterReason.html#variant.Unhandled)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Order { id: u64 }
+    /// impl ActorMessage for Order {}
+    ///
+    /// const ORDER: u16 = 1;
+    /// const REFUND: u16 = 2;
+    ///
+    /// struct Shop { unhandled: Arc<Mutex<Vec<(&'static str, Option<u16>)>>> }
+    /// impl Actor for Shop {
+    ///     fn on_unhandled_message(&mut self, info: UnhandledInfo, _context: &ActorContext<Self>) {
+    ///         let type_tag = match &info {
+    ///             UnhandledInfo::Serialized(msg) => msg.type_tag,
+    ///             UnhandledInfo::Typed { .. } => None,
+    ///         };
+    ///         self.unhandled.lock().unwrap().push((info.type_name(), type_tag));
+    ///     }
+    /// }
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, _msg: Order, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct ShopFactory { unhandled: Arc<Mutex<Vec<(&'static str, Option<u16>)>>> }
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+    ///         Shop { unhandled: self.unhandled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let unhandled = Arc::new(Mutex::new(Vec::new()));
+    /// let shop = actor_system
+    ///     .builder()
+    ///     .register_serialized(ORDER, |content: &[u8]| match content {
+    ///         [id] => Ok(Order { id: *id as u64 }),
+    ///         _ => Err(String::from("expected a single byte")),
+    ///     })
+    ///     .spawn("shop", ShopFactory { unhandled: unhandled.clone() })
+    ///     .unwrap();
+    ///
+    /// actor_system.send_to_address(shop.get_address(), SerializedMessage::new(vec![7]).with_type_tag(ORDER));
+    /// // the Shop has no Handler for refunds
+    /// actor_system.send_to_address(shop.get_address(), SerializedMessage::new(vec![7]).with_type_tag(REFUND));
+    /// // garbage
+    /// let result = actor_system.try_send_to_address(shop.get_address(), SerializedMessage::new(vec![]).with_type_tag(ORDER));
+    /// assert_eq!(Err(ProtocolError::Undecodable { type_tag: ORDER }), result);
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let serialized = std::any::type_name::<SerializedMessage>();
+    /// assert_eq!(vec![(serialized, Some(REFUND)), (serialized, Some(ORDER))], *unhandled.lock().unwrap());
+    /// assert_eq!(2, shop.unhandled_count());
+    /// // overriding the hook keeps them out of the dead letters
+    /// assert_eq!(0, actor_system.dead_letter_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    fn on_unhandled_message(&mut self, info: UnhandledInfo, context: &ActorContext<Self>)
+    where
+        Self: Sized + 'static,
+    {
+        context.report_unhandled(&info);
+    }
+    /// serialized state of the Actor, executed on the thread of the Actor between two messages
+    ///
+    /// Interpreted according to the [SnapshotFormat](../prelude/enum.SnapshotFormat.html) of the Actor, see [ActorWrapper.capture_state](../prelude/struct.ActorWrapper.html#method.capture_state)
+    fn snapshot(&self) -> Vec<u8> {
+        Vec::new()
+    }
+    /// state handed to the Actor that replaces this one, see [ActorSystem.replace_actor](../prelude/struct.ActorSystem.html#method.replace_actor)
+    ///
+    /// Executed right before [post_stop](#method.post_stop) of the replaced Actor, `None` hands nothing over
+    fn handover(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+    /// receives the state handed over by the replaced Actor, executed right after the factory created the Actor
+    ///
+    /// A panic is handled like a panic of the factory
+    fn receive_handover(&mut self, _state: Vec<u8>) {}
 }
@@ -1,7 +1,30 @@
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Unique address of an Actor, displayed as `system://pool/actor`
+///
+/// Addresses are equal if all of their parts are equal, the `remote` part is not displayed
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct ActorAddress {
     pub remote: String,
     pub system: String,
     pub pool: String,
     pub actor: String,
 }
+
+impl fmt::Display for ActorAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}/{}", self.system, self.pool, self.actor)
+    }
+}
+
+/// Returned by [ActorSystem.resolve](../prelude/struct.ActorSystem.html#method.resolve)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResolveError {
+    /// no Actor is running at the address, it has not been spawned yet or already stopped
+    NotFound,
+    /// the Actor at the address is of another type
+    TypeMismatch,
+    /// the address belongs to another system
+    WrongSystem,
+}
@@ -1,20 +1,58 @@
 use crate::actor::actor::Actor;
-use crate::actor::actor_config::{ActorConfig, RestartPolicy};
-use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_config::{
+    self, ActorConfig, ActorConfigSources, ActorDefaults, FailedMessageStrategy, MailboxOverflowPolicy, PanicEscalation,
+    ResolvedActorConfig, RestartMailboxPolicy, RestartPolicy, SystemStopOrder, DEFAULT_SHUTDOWN_PHASE,
+};
+use crate::actor::actor_factory::{ActorInitError, TryActorFactory};
+use crate::actor::actor_metrics::ActorMetrics;
 use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
 use crate::config::tyractorsaur_config::DEFAULT_POOL;
 use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::{self, BulkSpawnConfig, BulkSpawnReport, SpawnError, SpawnSpec};
+use crate::system::pool_stats::{PoolError, PoolRef};
+use crate::system::entity_region::EntityRegion;
+use crate::system::startup_order::DependencyFailurePolicy;
 use std::panic::UnwindSafe;
 use crossbeam_channel::{unbounded, bounded};
-use crate::actor::mailbox::Mailbox;
-use std::sync::{Arc, RwLock};
-use std::sync::atomic::AtomicBool;
+use crate::actor::mailbox::{Mailbox, MailboxFlags, STOP_NONE};
+use crate::actor::mailbox_bytes::MailboxBytes;
+use crate::actor::extraction::FinalState;
+use crate::actor::effect_guard::{EffectLedger, MemoryEffectLedger};
+use crate::actor::handoff::HandoffState;
+use crate::actor::journal::{Journal, JournalSink};
+use crate::actor::message_stream::Emitter;
+use crate::actor::pressure::{PressureState, Watermarks};
+use crate::actor::priority::{ActorPriority, PriorityState};
+use crate::actor::protocol::{ActorProtocol, ProtocolEntry, ProtocolUpgrade};
+use crate::actor::rng::{ActorRng, RngReseed};
+use crate::actor::slo::{SloConfig, SloStatus};
+use crate::actor::stash::Stash;
+use crate::actor::state_capture::{SnapshotFormat, StateCaptures};
+use crate::actor::supersession::{Supersedes, Supersession};
+use crate::actor::batch_handler::{BatchHandler, Batching};
+use crate::actor::message_class::{ClassedQueues, MessageClasses};
+use crate::actor::middleware::{ActorMiddleware, Middlewares};
+use crate::actor::message_registry::MessageRegistry;
+use crate::actor::debug_snapshot::{DebugSnapshotFn, DebuggableActor};
+use crate::actor::recovery::{MemorySnapshotStore, RecoverableActor, Recovery, SnapshotStore};
+use crate::actor::rate_limit::RateLimit;
+use crate::actor::retry::Retrying;
+use crate::actor::throughput::{AdaptiveThroughput, Throughput};
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use crate::actor::actor_address::ActorAddress;
 use crate::actor::context::ActorContext;
 use crate::actor::executor::{Executor, ExecutorTrait};
 use crate::system::wakeup_manager::WakeupManager;
+use crate::message::actor_message::ActorMessage;
+use crate::message::serialized_dispatch::{Decoder, SerializedDispatch};
 use crate::system::system_state::SystemState;
 use dashmap::DashMap;
+use std::time::Duration;
 
 /// Used to create [Actor]s in the [ActorSystem]
 ///
@@ -31,7 +69,61 @@ where
     system: ActorSystem,
     system_state: SystemState,
     wakeup_manager: WakeupManager,
-    actor_config: ActorConfig,
+    pool_name: String,
+    overrides: ActorDefaults,
+    capture_last_n_messages: Option<usize>,
+    latency_slo: Option<SloConfig>,
+    watermarks: Option<Watermarks>,
+    start_suspended: bool,
+    sheddable: bool,
+    protocol_version: Option<u32>,
+    accepted_versions: Option<RangeInclusive<u32>>,
+    protocol_upgrades: BTreeMap<u32, ProtocolUpgrade>,
+    /// decoders of typed serialized messages by type tag
+    serialized: HashMap<u16, Decoder<A>>,
+    journal_capacity: usize,
+    journal_sink: Option<Arc<dyn JournalSink>>,
+    adaptive_throughput: Option<AdaptiveThroughput>,
+    effect_ledger: Option<Arc<dyn EffectLedger>>,
+    rng: Option<ActorRng>,
+    rng_reseed: RngReseed,
+    cycle_exempt: bool,
+    state_capture_capacity: usize,
+    snapshot_format: SnapshotFormat,
+    capture_around: Vec<String>,
+    max_message_bytes: Option<usize>,
+    superseding: HashMap<TypeId, Vec<TypeId>>,
+    batching: Batching<A>,
+    message_classes: MessageClasses,
+    middlewares: Middlewares<A>,
+    registered_messages: Vec<(TypeId, &'static str)>,
+    strict_messages: bool,
+    exempt_from_shedding: bool,
+    retrying: Retrying<A>,
+    recovery: Option<Recovery<A>>,
+    snapshot_store: Arc<dyn SnapshotStore>,
+    snapshot_interval: usize,
+    recover_after_stop: bool,
+    debug_snapshot: Option<DebugSnapshotFn<A>>,
+    dedicated_thread: bool,
+    lazy: bool,
+    overflow_policy: MailboxOverflowPolicy,
+    overflow_dead_letters: bool,
+    max_mailbox_bytes: Option<usize>,
+    failed_message_strategy: FailedMessageStrategy,
+    restart_mailbox_policy: RestartMailboxPolicy,
+    rate_limit: Option<RateLimit>,
+    stash_capacity: usize,
+    shutdown_phase: u8,
+    system_stop_order: SystemStopOrder,
+    idle_timeout: Option<Duration>,
+    message_ttl: Option<Duration>,
+    daemon: bool,
+    tick_interval: Option<Duration>,
+    slow_handler_threshold: Option<Duration>,
+    panic_escalation: Option<PanicEscalation>,
+    dependencies: Vec<ActorAddress>,
+    dependency_failure_policy: DependencyFailurePolicy,
 }
 
 impl<A> ActorBuilder<A>
@@ -40,48 +132,3051 @@ impl<A> ActorBuilder<A>
 {
     /// This is called through [ActorSystem.builder](../prelude/struct.ActorSystem.html#method.builder)
     pub fn new(system: ActorSystem, system_state: SystemState, wakeup_manager: WakeupManager) -> ActorBuilder<A> {
-        let config = system.get_config();
+        ActorBuilder {
+            existing: Arc::new(DashMap::new()),
+            system,
+            system_state,
+            wakeup_manager,
+            pool_name: String::from(DEFAULT_POOL),
+            overrides: ActorDefaults::default(),
+            capture_last_n_messages: None,
+            latency_slo: None,
+            watermarks: None,
+            start_suspended: false,
+            sheddable: false,
+            protocol_version: None,
+            accepted_versions: None,
+            protocol_upgrades: BTreeMap::new(),
+            serialized: HashMap::new(),
+            journal_capacity: 0,
+            journal_sink: None,
+            adaptive_throughput: None,
+            effect_ledger: None,
+            rng: None,
+            rng_reseed: RngReseed::default(),
+            cycle_exempt: false,
+            state_capture_capacity: 0,
+            snapshot_format: SnapshotFormat::default(),
+            capture_around: Vec::new(),
+            max_message_bytes: None,
+            superseding: HashMap::new(),
+            batching: Batching::new(),
+            message_classes: MessageClasses::default(),
+            middlewares: Middlewares::new(),
+            registered_messages: Vec::new(),
+            strict_messages: false,
+            exempt_from_shedding: false,
+            retrying: Retrying::new(),
+            recovery: None,
+            snapshot_store: Arc::new(MemorySnapshotStore::new()),
+            snapshot_interval: 0,
+            recover_after_stop: false,
+            debug_snapshot: None,
+            dedicated_thread: false,
+            lazy: false,
+            overflow_policy: MailboxOverflowPolicy::default(),
+            overflow_dead_letters: false,
+            max_mailbox_bytes: None,
+            failed_message_strategy: FailedMessageStrategy::default(),
+            restart_mailbox_policy: RestartMailboxPolicy::default(),
+            rate_limit: None,
+            stash_capacity: 0,
+            shutdown_phase: DEFAULT_SHUTDOWN_PHASE,
+            system_stop_order: SystemStopOrder::default(),
+            idle_timeout: None,
+            message_ttl: None,
+            daemon: false,
+            tick_interval: None,
+            slow_handler_threshold: None,
+            panic_escalation: None,
+            dependencies: Vec::new(),
+            dependency_failure_policy: DependencyFailurePolicy::default(),
+        }
+    }
+
+    /// Overrides `general.default_restart_policy` and the restart policy of the pool, see [RestartPolicy](../prelude/enum.RestartPolicy.html)
+    pub fn set_restart_policy(mut self, restart_policy: RestartPolicy) -> ActorBuilder<A> {
+        self.overrides.restart_policy = Some(restart_policy);
+        self
+    }
+
+    /// Overrides `general.default_panic_escalation`, see [PanicEscalation](../prelude/enum.PanicEscalation.html)
+    pub fn set_panic_escalation(mut self, panic_escalation: PanicEscalation) -> ActorBuilder<A> {
+        self.panic_escalation = Some(panic_escalation);
+        self
+    }
+
+    /// Thread pool that executes the Actor, defaults to the `default` pool
+    ///
+    /// Meant for config driven setups, prefer [set_pool](#method.set_pool).
+    /// Fails with [PoolError::UnknownPool](../prelude/enum.PoolError.html#variant.UnknownPool) if the pool has neither been added nor configured in `thread_pool.config`, see [ActorSystem.get_pool](../prelude/struct.ActorSystem.html#method.get_pool)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::mpsc::{channel, Sender};
+    /// use std::sync::Mutex;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Chatty { pool: String, handled: Mutex<Sender<String>> }
+    /// impl Actor for Chatty {}
+    /// impl Handler<Ping> for Chatty {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.handled.lock().unwrap().send(self.pool.clone()).unwrap();
+    ///     }
+    /// }
+    /// struct ChattyFactory { handled: Mutex<Sender<String>> }
+    /// impl ActorFactory<Chatty> for ChattyFactory {
+    ///     fn new_actor(&self, context: ActorContext<Chatty>) -> Chatty {
+    ///         let pool = context.actor_ref.get_address().pool.clone();
+    ///         Chatty { pool, handled: Mutex::new(self.handled.lock().unwrap().clone()) }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.thread_pool.config.insert(String::from("chatty"), ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let actor_system = ActorSystem::new(config);
+    /// let (handled, received) = channel();
+    /// // typos are rejected instead of starting a new pool
+    /// assert_eq!(Some(PoolError::UnknownPool), actor_system.builder::<Chatty>().set_pool_name("chaty").err());
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .set_pool_name("chatty")
+    ///     .unwrap()
+    ///     .spawn("chatty", ChattyFactory { handled: Mutex::new(handled) })
+    ///     .unwrap();
+    /// assert_eq!("chatty", actor.get_address().pool);
+    /// assert!(actor_system.pool_worker_stats("chatty").is_some());
+    /// assert!(actor_system.get_actor::<Chatty>("chatty", "chatty").is_some());
+    /// assert!(actor_system.get_actor::<Chatty>("default", "chatty").is_none());
+    ///
+    /// actor.send(Ping {}).unwrap();
+    /// assert_eq!("chatty", received.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_pool_name(self, pool_name: impl Into<String>) -> Result<ActorBuilder<A>, PoolError> {
+        let pool_name = pool_name.into();
+        match self.system.get_pool(&pool_name) {
+            Some(pool) => Ok(self.set_pool(pool)),
+            None => Err(PoolError::UnknownPool),
+        }
+    }
+
+    /// Thread pool that executes the Actor, defaults to the `default` pool
+    ///
+    /// [spawn](#method.spawn) fails if the pool does not belong to the system of this builder
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Busy {}
+    /// impl ActorMessage for Busy {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Busy> for Worker {
+    ///     fn handle(&mut self, _msg: Busy, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(5));
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let io = actor_system.add_pool_with_config("io", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let worker = actor_system.builder().set_pool(io.clone()).spawn("worker", WorkerFactory {}).unwrap();
+    /// assert_eq!(io.name(), worker.get_address().pool);
+    ///
+    /// // the handle survives resizing the pool
+    /// actor_system.resize_pool(io.name(), 2, 2).unwrap();
+    /// sleep(Duration::from_millis(1500));
+    /// assert_eq!(2, actor_system.pool_stats(io.name()).unwrap().threads);
+    /// let second = actor_system.builder().set_pool(io.clone()).spawn("second", WorkerFactory {}).unwrap();
+    /// assert_eq!("io", second.get_address().pool);
+    ///
+    /// // handles of other systems are rejected
+    /// let other_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let foreign = other_system.add_pool("foreign");
+    /// let result = actor_system.builder().set_pool(foreign).try_spawn("foreign", WorkerFactory {});
+    /// assert!(matches!(result, Err(SpawnError::UnknownPool)));
+    /// other_system.stop(Duration::from_secs(1));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_pool(mut self, pool: PoolRef) -> ActorBuilder<A> {
+        self.pool_name = String::from(pool.name());
+        self
+    }
+
+    /// Maximum amount of consecutive messages handled before the thread switches to the next Actor, overrides `general.default_message_throughput` and the throughput of the pool
+    ///
+    /// Once the budget is used up, the Actor goes to the back of the pool queue, behind every Actor that has been woken up in the meantime.
+    /// A saturated Actor therefore only delays a sleeping Actor that receives a message by a single slot
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Load {}
+    /// impl ActorMessage for Load {}
+    ///
+    /// struct Busy {}
+    /// impl Actor for Busy {}
+    /// impl Handler<Load> for Busy {
+    ///     fn handle(&mut self, _msg: Load, _context: &ActorContext<Self>) {
+    ///         let started = Instant::now();
+    ///         while started.elapsed() < Duration::from_micros(20) {}
+    ///     }
+    /// }
+    /// struct BusyFactory {}
+    /// impl ActorFactory<Busy> for BusyFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Busy>) -> Busy {
+    ///         Busy {}
+    ///     }
+    /// }
+    ///
+    /// struct Probe {
+    ///     sent: Instant,
+    /// }
+    /// impl ActorMessage for Probe {}
+    ///
+    /// struct Idle {
+    ///     latencies: Arc<Mutex<Vec<Duration>>>,
+    /// }
+    /// impl Actor for Idle {}
+    /// impl Handler<Probe> for Idle {
+    ///     fn handle(&mut self, msg: Probe, _context: &ActorContext<Self>) {
+    ///         self.latencies.lock().unwrap().push(msg.sent.elapsed());
+    ///     }
+    /// }
+    /// struct IdleFactory {
+    ///     latencies: Arc<Mutex<Vec<Duration>>>,
+    /// }
+    /// impl ActorFactory<Idle> for IdleFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Idle>) -> Idle {
+    ///         Idle { latencies: self.latencies.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let busy = actor_system
+    ///     .builder()
+    ///     .set_mailbox_unbounded()
+    ///     .set_message_throughput(100)
+    ///     .spawn("busy", BusyFactory {})
+    ///     .unwrap();
+    /// let is_producing = Arc::new(AtomicBool::new(true));
+    /// let producing = is_producing.clone();
+    /// let producer = std::thread::spawn(move || {
+    ///     while producing.load(Ordering::Relaxed) {
+    ///         for _ in 0..100 {
+    ///             busy.send(Load {}).unwrap();
+    ///         }
+    ///         std::thread::yield_now();
+    ///     }
+    /// });
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// // freshly spawned Actors sleep until their first message
+    /// let latencies = Arc::new(Mutex::new(Vec::new()));
+    /// for i in 0..20 {
+    ///     let idle = actor_system.builder().spawn(format!("idle-{}", i), IdleFactory { latencies: latencies.clone() }).unwrap();
+    ///     sleep(Duration::from_millis(10));
+    ///     idle.send(Probe { sent: Instant::now() }).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// is_producing.store(false, Ordering::Relaxed);
+    /// producer.join().unwrap();
+    ///
+    /// let latencies = latencies.lock().unwrap();
+    /// assert_eq!(20, latencies.len());
+    /// assert!(latencies.iter().all(|latency| *latency < Duration::from_millis(10)), "{:?}", latencies);
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_message_throughput(mut self, message_throughput: usize) -> ActorBuilder<A> {
+        self.overrides.message_throughput = Some(message_throughput);
+        self.adaptive_throughput = None;
+        self
+    }
+
+    /// Ledger of the [EffectGuard](../prelude/struct.EffectGuard.html) of the Actor, shared by all Actors spawned by this builder
+    ///
+    /// Defaults to a [MemoryEffectLedger](../prelude/struct.MemoryEffectLedger.html) per Actor with `general.effect_ledger_capacity` and `general.effect_ledger_ttl`
+    pub fn set_effect_ledger(mut self, ledger: impl EffectLedger + 'static) -> ActorBuilder<A> {
+        self.effect_ledger = Some(Arc::new(ledger));
+        self
+    }
+
+    /// Starts the Actor with the given random number generator instead of deriving it from `general.rng_seed`
+    ///
+    /// Used to resume an [ActorRng](../prelude/struct.ActorRng.html) from a snapshot, or to hand out a generator from [ActorRng.derive_for](../prelude/struct.ActorRng.html#method.derive_for)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Walk {}
+    /// impl ActorMessage for Walk {}
+    /// struct Snapshot {}
+    /// impl ActorMessage for Snapshot {}
+    ///
+    /// struct Walker { steps: Arc<Mutex<Vec<u64>>>, snapshot: Arc<Mutex<Option<ActorRng>>> }
+    /// impl Actor for Walker {}
+    /// impl Handler<Walk> for Walker {
+    ///     fn handle(&mut self, _msg: Walk, context: &ActorContext<Self>) {
+    ///         self.steps.lock().unwrap().push(context.rng().gen_range(0..4));
+    ///     }
+    /// }
+    /// impl Handler<Snapshot> for Walker {
+    ///     fn handle(&mut self, _msg: Snapshot, context: &ActorContext<Self>) {
+    ///         *self.snapshot.lock().unwrap() = Some(context.rng().clone());
+    ///     }
+    /// }
+    /// #[derive(Clone)]
+    /// struct WalkerFactory { steps: Arc<Mutex<Vec<u64>>>, snapshot: Arc<Mutex<Option<ActorRng>>> }
+    /// impl ActorFactory<Walker> for WalkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Walker>) -> Walker {
+    ///         Walker { steps: self.steps.clone(), snapshot: self.snapshot.clone() }
+    ///     }
+    /// }
+    ///
+    /// let factory = WalkerFactory { steps: Arc::new(Mutex::new(Vec::new())), snapshot: Arc::new(Mutex::new(None)) };
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.rng_seed = Some(11);
+    /// let actor_system = ActorSystem::new(config);
+    /// let walker = actor_system.builder().spawn("walker", factory.clone()).unwrap();
+    /// for i in 0..20 {
+    ///     if i == 10 {
+    ///         walker.send(Snapshot {});
+    ///     }
+    ///     walker.send(Walk {});
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// actor_system.await_shutdown();
+    /// let uninterrupted = factory.steps.lock().unwrap().split_off(0);
+    ///
+    /// // the snapshot is plain data and could as well have been written to disk in between
+    /// let snapshot = factory.snapshot.lock().unwrap().take().unwrap();
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let walker = actor_system.builder().set_rng(snapshot).spawn("walker", factory.clone()).unwrap();
+    /// for _ in 10..20 {
+    ///     walker.send(Walk {});
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// actor_system.await_shutdown();
+    /// assert_eq!(uninterrupted[10..], factory.steps.lock().unwrap()[..]);
+    /// ```
+    pub fn set_rng(mut self, rng: ActorRng) -> ActorBuilder<A> {
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Defines how the random number generator of the Actor is re-seeded on a restart, defaults to [RngReseed::Repeat](../prelude/enum.RngReseed.html#variant.Repeat)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Roll {}
+    /// impl ActorMessage for Roll {}
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Dice { rolls: Arc<Mutex<Vec<u64>>> }
+    /// impl Actor for Dice {}
+    /// impl Handler<Roll> for Dice {
+    ///     fn handle(&mut self, _msg: Roll, context: &ActorContext<Self>) {
+    ///         self.rolls.lock().unwrap().push(context.rng().next_u64());
+    ///     }
+    /// }
+    /// impl Handler<Crash> for Dice {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         panic!("crash");
+    ///     }
+    /// }
+    /// struct DiceFactory { rolls: Arc<Mutex<Vec<u64>>> }
+    /// impl ActorFactory<Dice> for DiceFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Dice>) -> Dice {
+    ///         Dice { rolls: self.rolls.clone() }
+    ///     }
+    /// }
+    ///
+    /// let run = |reseed: RngReseed| -> Vec<u64> {
+    ///     let mut config = TyractorsaurConfig::new().unwrap();
+    ///     config.general.rng_seed = Some(3);
+    ///     let actor_system = ActorSystem::new(config);
+    ///     let rolls = Arc::new(Mutex::new(Vec::new()));
+    ///     let dice = actor_system
+    ///         .builder()
+    ///         .set_restart_policy(RestartPolicy::Always)
+    ///         .set_rng_reseed(reseed)
+    ///         .spawn("dice", DiceFactory { rolls: rolls.clone() })
+    ///         .unwrap();
+    ///     dice.send(Roll {});
+    ///     dice.send(Crash {});
+    ///     dice.send(Roll {});
+    ///     sleep(Duration::from_millis(100));
+    ///     actor_system.stop(Duration::from_secs(1));
+    ///     actor_system.await_shutdown();
+    ///     let rolls = rolls.lock().unwrap().clone();
+    ///     rolls
+    /// };
+    ///
+    /// let repeated = run(RngReseed::Repeat);
+    /// assert_eq!(repeated[0], repeated[1]);
+    /// let per_restart = run(RngReseed::PerRestart);
+    /// assert_eq!(repeated[0], per_restart[0]);
+    /// assert_ne!(per_restart[0], per_restart[1]);
+    /// assert_eq!(per_restart, run(RngReseed::PerRestart));
+    /// ```
+    pub fn set_rng_reseed(mut self, reseed: RngReseed) -> ActorBuilder<A> {
+        self.rng_reseed = reseed;
+        self
+    }
+
+    /// Excludes the Actor from the cycle detection, every message it sends starts a new causal chain
+    ///
+    /// Meant for Actors that legitimately feed back into themselves, like retry loops or tickers, see [CycleConfig](../prelude/struct.CycleConfig.html)
+    pub fn set_cycle_exempt(mut self, cycle_exempt: bool) -> ActorBuilder<A> {
+        self.cycle_exempt = cycle_exempt;
+        self
+    }
+
+    /// Keeps the Actor out of the message budget of the system, sends to it are never shed, see [MessageBudgetConfig](../prelude/struct.MessageBudgetConfig.html)
+    ///
+    /// Meant for control plane Actors that have to stay reachable while the system is overloaded
+    pub fn set_exempt_from_shedding(mut self, exempt_from_shedding: bool) -> ActorBuilder<A> {
+        self.exempt_from_shedding = exempt_from_shedding;
+        self
+    }
+
+    /// Rejects sends to the Actor whose approximate size exceeds `max_bytes`, overrides `general.max_message_bytes`
+    ///
+    /// See [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size) and [ActorWrapper.try_send](../prelude/struct.ActorWrapper.html#method.try_send)
+    pub fn set_max_message_bytes(mut self, max_bytes: usize) -> ActorBuilder<A> {
+        self.max_message_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Fixed or adaptive message budget per scheduling slot, defaults to a fixed `general.default_message_throughput`
+    ///
+    /// Either way the Actor yields its worker once the budget is used up, see [ActorWrapper.message_budget](../prelude/struct.ActorWrapper.html#method.message_budget)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// // every tick costs `cost` and schedules the next one
+    /// struct Tick { remaining: usize }
+    /// impl ActorMessage for Tick {}
+    /// struct SetCost(Duration);
+    /// impl ActorMessage for SetCost {}
+    ///
+    /// struct Ticker { cost: Duration, context: ActorContext<Self> }
+    /// impl Actor for Ticker {}
+    /// impl Handler<Tick> for Ticker {
+    ///     fn handle(&mut self, msg: Tick, _context: &ActorContext<Self>) {
+    ///         sleep(self.cost);
+    ///         if msg.remaining > 0 {
+    ///             self.context.actor_ref.send(Tick { remaining: msg.remaining - 1 });
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<SetCost> for Ticker {
+    ///     fn handle(&mut self, msg: SetCost, _context: &ActorContext<Self>) {
+    ///         self.cost = msg.0;
+    ///     }
+    /// }
+    /// struct TickerFactory;
+    /// impl ActorFactory<Ticker> for TickerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Ticker>) -> Ticker {
+    ///         Ticker { cost: Duration::from_micros(1), context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let adaptive = AdaptiveThroughput::new(Duration::from_millis(20), 1, 64);
+    /// let ticker = actor_system
+    ///     .builder()
+    ///     .set_throughput(Throughput::Adaptive(adaptive))
+    ///     .spawn("ticker", TickerFactory)
+    ///     .unwrap();
+    /// assert_eq!(1, ticker.message_budget());
+    ///
+    /// // cheap handlers grow the budget up to its maximum
+    /// ticker.send(Tick { remaining: 2000 });
+    /// sleep(Duration::from_millis(1500));
+    /// assert_eq!(64, ticker.message_budget());
+    ///
+    /// // 5ms handlers shrink it to about 20ms / 5ms within a few slots
+    /// ticker.send(SetCost(Duration::from_millis(5)));
+    /// ticker.send(Tick { remaining: 120 });
+    /// sleep(Duration::from_millis(1500));
+    /// let budget = ticker.message_budget();
+    /// assert!((3..=5).contains(&budget), "{}", budget);
+    /// ```
+    pub fn set_throughput(mut self, throughput: Throughput) -> ActorBuilder<A> {
+        match throughput {
+            Throughput::Fixed(message_throughput) => return self.set_message_throughput(message_throughput),
+            Throughput::Adaptive(adaptive) => self.adaptive_throughput = Some(adaptive),
+        }
+        self
+    }
+
+    /// Maximum duration the Actor stays suspended through [ActorContext.suspend_until](../prelude/struct.ActorContext.html#method.suspend_until)
+    ///
+    /// Defaults to `general.default_max_suspension`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Waiter {
+    ///     reason: Arc<Mutex<Option<SuspensionEndReason>>>,
+    /// }
+    /// impl Actor for Waiter {
+    ///     fn on_suspension_ended(&mut self, reason: SuspensionEndReason) {
+    ///         *self.reason.lock().unwrap() = Some(reason);
+    ///     }
+    /// }
+    /// impl Handler<Work> for Waiter {
+    ///     fn handle(&mut self, _msg: Work, context: &ActorContext<Self>) {
+    ///         context.suspend_until(SuspendCondition::external());
+    ///     }
+    /// }
+    ///
+    /// struct WaiterFactory {
+    ///     reason: Arc<Mutex<Option<SuspensionEndReason>>>,
+    /// }
+    /// impl ActorFactory<Waiter> for WaiterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Waiter>) -> Waiter {
+    ///         Waiter { reason: self.reason.clone() }
+    ///     }
+    /// }
+    ///
+    /// let reason = Arc::new(Mutex::new(None));
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .set_max_suspension(Duration::from_millis(100))
+    ///     .spawn("waiter", WaiterFactory { reason: reason.clone() })
+    ///     .unwrap();
+    /// actor.send(Work {});
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(Some(SuspensionEndReason::TimedOut), *reason.lock().unwrap());
+    /// assert!(actor.suspended_time() >= Duration::from_millis(100));
+    /// ```
+    pub fn set_max_suspension(mut self, max_suspension: Duration) -> ActorBuilder<A> {
+        self.overrides.max_suspension = Some(max_suspension);
+        self
+    }
+
+    /// Scheduling priority of the Actor within its thread pool
+    ///
+    /// Defaults to `general.default_priority`
+    ///
+    /// Messages sent while an Actor with a raised priority handles a message start a request chain.
+    /// Receivers with a lower priority inherit the priority of the chain: its messages overtake their regular mailbox and they are scheduled with the inherited priority until all messages of the chain are handled.
+    /// Inheritance is passed on to further sends for up to `general.max_priority_inheritance_depth` Actors
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Bulk {}
+    /// impl ActorMessage for Bulk {}
+    /// struct Query { done: Arc<Mutex<Option<Instant>>> }
+    /// impl ActorMessage for Query {}
+    /// struct Request { done: Arc<Mutex<Option<Instant>>> }
+    /// impl ActorMessage for Request {}
+    ///
+    /// struct Backend {}
+    /// impl Actor for Backend {}
+    /// impl Handler<Bulk> for Backend {
+    ///     fn handle(&mut self, _msg: Bulk, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(2));
+    ///     }
+    /// }
+    /// impl Handler<Query> for Backend {
+    ///     fn handle(&mut self, msg: Query, _context: &ActorContext<Self>) {
+    ///         *msg.done.lock().unwrap() = Some(Instant::now());
+    ///     }
+    /// }
+    /// struct BackendFactory {}
+    /// impl ActorFactory<Backend> for BackendFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Backend>) -> Backend {
+    ///         Backend {}
+    ///     }
+    /// }
+    ///
+    /// struct Frontend { backend: ActorWrapper<Backend> }
+    /// impl Actor for Frontend {}
+    /// impl Handler<Request> for Frontend {
+    ///     fn handle(&mut self, msg: Request, _context: &ActorContext<Self>) {
+    ///         self.backend.send(Query { done: msg.done });
+    ///     }
+    /// }
+    /// struct FrontendFactory { backend: ActorWrapper<Backend> }
+    /// impl ActorFactory<Frontend> for FrontendFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Frontend>) -> Frontend {
+    ///         Frontend { backend: self.backend.clone() }
+    ///     }
+    /// }
+    ///
+    /// fn request_latency(max_depth: u64) -> (Duration, ActorWrapper<Backend>) {
+    ///     let mut config = TyractorsaurConfig::new().unwrap();
+    ///     config.general.max_priority_inheritance_depth = max_depth;
+    ///     config.thread_pool.config.insert(String::from("single"), ThreadPoolConfig::new(0, 1, 1, 1.0));
+    ///     let actor_system = ActorSystem::new(config);
+    ///     let backend = actor_system.builder().set_pool_name("single").unwrap().spawn("backend", BackendFactory {}).unwrap();
+    ///     let frontend = actor_system
+    ///         .builder()
+    ///         .set_pool_name("single")
+    ///         .unwrap()
+    ///         .set_priority(ActorPriority::High)
+    ///         .spawn("frontend", FrontendFactory { backend: backend.clone() })
+    ///         .unwrap();
+    ///     for _ in 0..250 {
+    ///         backend.send(Bulk {});
+    ///     }
+    ///     sleep(Duration::from_millis(20));
+    ///
+    ///     let done = Arc::new(Mutex::new(None));
+    ///     let start = Instant::now();
+    ///     frontend.send(Request { done: done.clone() });
+    ///     while done.lock().unwrap().is_none() {
+    ///         sleep(Duration::from_millis(1));
+    ///     }
+    ///     let latency = done.lock().unwrap().unwrap() - start;
+    ///     (latency, backend)
+    /// }
+    ///
+    /// let (inherited, backend) = request_latency(3);
+    /// let (not_inherited, _) = request_latency(0);
+    /// assert!(inherited < Duration::from_millis(150));
+    /// assert!(not_inherited > Duration::from_millis(300));
+    ///
+    /// // the boost ends together with the request chain
+    /// assert_eq!(ActorPriority::Normal, backend.effective_priority());
+    /// assert!(backend.boosted_time() > Duration::from_secs(0));
+    /// ```
+    pub fn set_priority(mut self, priority: ActorPriority) -> ActorBuilder<A> {
+        self.overrides.priority = Some(priority);
+        self
+    }
+
+    /// Keeps the payloads of the last `n` handled messages and attaches them to the [ActorPanicReport](../prelude/struct.ActorPanicReport.html)
+    ///
+    /// Only messages with a registered serializer or `Debug` capture are kept, requires `general.max_captured_payload_bytes` to be greater than `0`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Step { value: u8 }
+    /// impl ActorMessage for Step {}
+    ///
+    /// struct Machine { report: Arc<Mutex<Option<ActorPanicReport>>> }
+    /// impl Actor for Machine {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         self.report.lock().unwrap().get_or_insert(report);
+    ///     }
+    /// }
+    /// impl Handler<Step> for Machine {
+    ///     fn handle(&mut self, msg: Step, _context: &ActorContext<Self>) {
+    ///         assert!(msg.value < 10);
+    ///     }
+    /// }
+    /// struct MachineFactory { report: Arc<Mutex<Option<ActorPanicReport>>> }
+    /// impl ActorFactory<Machine> for MachineFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Machine>) -> Machine {
+    ///         Machine { report: self.report.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_captured_payload_bytes = 16;
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.register_payload_serializer(|msg: &Step| vec![msg.value]);
+    /// let report = Arc::new(Mutex::new(None));
+    /// let machine = actor_system
+    ///     .builder()
+    ///     .set_capture_last_n_messages(5)
+    ///     .spawn("machine", MachineFactory { report: report.clone() })
+    ///     .unwrap();
+    /// for value in 0..12 {
+    ///     machine.send(Step { value });
+    /// }
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let report = report.lock().unwrap().clone().unwrap();
+    /// assert_eq!(vec![10], report.payload.unwrap().bytes);
+    /// let recent: Vec<Vec<u8>> = report.recent.into_iter().map(|p| p.bytes).collect();
+    /// assert_eq!(vec![vec![5], vec![6], vec![7], vec![8], vec![9]], recent);
+    /// ```
+    pub fn set_capture_last_n_messages(mut self, n: usize) -> ActorBuilder<A> {
+        self.capture_last_n_messages = Some(n);
+        self
+    }
+
+    /// Keeps the last `capacity` lifecycle events and entries of [ActorContext.journal](../prelude/struct.ActorContext.html#method.journal), defaults to `0` which disables the journal
+    ///
+    /// The journal survives restarts, but not the final stop of the Actor.
+    /// It is readable through [ActorWrapper.journal](../prelude/struct.ActorWrapper.html#method.journal) and its tail is attached to every [ActorPanicReport](../prelude/struct.ActorPanicReport.html)
+    pub fn set_journal_capacity(mut self, capacity: usize) -> ActorBuilder<A> {
+        self.journal_capacity = capacity;
+        self
+    }
+
+    /// Hands every entry that is evicted from the journal to `sink`, only used if the journal capacity is greater than `0`
+    pub fn set_journal_sink(mut self, sink: impl JournalSink + 'static) -> ActorBuilder<A> {
+        self.journal_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Keeps the last `capacity` captures of [ActorWrapper.capture_state](../prelude/struct.ActorWrapper.html#method.capture_state), defaults to `0` which disables state captures
+    ///
+    /// The captures survive restarts and the final stop of the Actor
+    pub fn set_state_capture_capacity(mut self, capacity: usize) -> ActorBuilder<A> {
+        self.state_capture_capacity = capacity;
+        self
+    }
+
+    /// Declares how [ActorSystem.diff_captures](../prelude/struct.ActorSystem.html#method.diff_captures) compares the result of [Actor.snapshot](../prelude/trait.Actor.html#method.snapshot), defaults to [SnapshotFormat::Opaque](../prelude/enum.SnapshotFormat.html#variant.Opaque)
+    pub fn set_snapshot_format(mut self, format: SnapshotFormat) -> ActorBuilder<A> {
+        self.snapshot_format = format;
+        self
+    }
+
+    /// Captures the state right before and right after every message whose type name is or ends with `::message_type`
+    ///
+    /// The captures are labeled `before <message_type>` and `after <message_type>`, the second one is skipped if the handler panics.
+    /// Only used if the state capture capacity is greater than `0`
+    pub fn add_capture_around(mut self, message_type: impl Into<String>) -> ActorBuilder<A> {
+        self.capture_around.push(message_type.into());
+        self
+    }
+
+    /// Tracks the latency of every message against the given objective and emits [SloEvent](../prelude/enum.SloEvent.html)s when it is breached or recovered
+    ///
+    /// Actors without an SLO do not take any timestamps
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Quote { work: Duration }
+    /// impl ActorMessage for Quote {}
+    ///
+    /// struct Pricing {}
+    /// impl Actor for Pricing {}
+    /// impl Handler<Quote> for Pricing {
+    ///     fn handle(&mut self, msg: Quote, _context: &ActorContext<Self>) {
+    ///         sleep(msg.work);
+    ///     }
+    /// }
+    /// struct PricingFactory {}
+    /// impl ActorFactory<Pricing> for PricingFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Pricing>) -> Pricing {
+    ///         Pricing {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let events = actor_system.subscribe_slo_events();
+    /// let slo = SloConfig::new(Duration::from_millis(15), 99.0)
+    ///     .with_window(Duration::from_millis(100))
+    ///     .with_evaluated_windows(4)
+    ///     .with_breach_threshold(0.5);
+    /// let pricing = actor_system
+    ///     .builder()
+    ///     .set_latency_slo(slo)
+    ///     .spawn("pricing", PricingFactory {})
+    ///     .unwrap();
+    ///
+    /// // normal load, degraded load with 30ms per quote, normal load again
+    /// let phases = [(300, 0, 10), (600, 30, 40), (700, 0, 10)];
+    /// for (duration, work, interval) in phases {
+    ///     let end = Instant::now() + Duration::from_millis(duration);
+    ///     while Instant::now() < end {
+    ///         pricing.send(Quote { work: Duration::from_millis(work) });
+    ///         sleep(Duration::from_millis(interval));
+    ///     }
+    /// }
+    ///
+    /// let events: Vec<SloEvent> = events.try_iter().collect();
+    /// assert_eq!(events.len(), 2);
+    /// match &events[0] {
+    ///     SloEvent::Breached(report) => {
+    ///         assert!(report.measured > Duration::from_millis(15));
+    ///         assert!(report.top_message_types[0].0.ends_with("Quote"));
+    ///     }
+    ///     _ => panic!("expected a breach first"),
+    /// }
+    /// assert!(matches!(events[1], SloEvent::Recovered(_)));
+    /// assert!(!pricing.slo_status().unwrap().breached);
+    /// ```
+    pub fn set_latency_slo(mut self, slo: SloConfig) -> ActorBuilder<A> {
+        self.latency_slo = Some(slo);
+        self
+    }
+
+    /// Tracks the pressure of the mailbox, so that producers can slow down before the mailbox is full
+    ///
+    /// Pressure is exposed through [ActorWrapper.pressure](../prelude/struct.ActorWrapper.html#method.pressure) and delivered to Actors registered through [ActorContext.watch_pressure](../prelude/struct.ActorContext.html#method.watch_pressure)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    /// struct Produce {}
+    /// impl ActorMessage for Produce {}
+    ///
+    /// struct Consumer { max_len: Arc<Mutex<usize>> }
+    /// impl Actor for Consumer {}
+    /// impl Handler<Work> for Consumer {
+    ///     fn handle(&mut self, _msg: Work, context: &ActorContext<Self>) {
+    ///         let mut max_len = self.max_len.lock().unwrap();
+    ///         *max_len = (*max_len).max(context.actor_ref.mailbox_len());
+    ///         sleep(Duration::from_micros(1500));
+    ///     }
+    /// }
+    /// struct ConsumerFactory { max_len: Arc<Mutex<usize>> }
+    /// impl ActorFactory<Consumer> for ConsumerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Consumer>) -> Consumer {
+    ///         Consumer { max_len: self.max_len.clone() }
+    ///     }
+    /// }
+    ///
+    /// // halves its rate while the consumer is under high pressure
+    /// struct Producer {
+    ///     consumer: ActorWrapper<Consumer>,
+    ///     interval: Duration,
+    ///     until: Instant,
+    ///     levels: Arc<Mutex<Vec<(PressureLevel, PressureLevel)>>>,
+    ///     done: Sender<()>,
+    /// }
+    /// impl Actor for Producer {}
+    /// impl Handler<Produce> for Producer {
+    ///     fn handle(&mut self, _msg: Produce, context: &ActorContext<Self>) {
+    ///         if Instant::now() < self.until {
+    ///             self.consumer.send(Work {});
+    ///             sleep(self.interval);
+    ///             context.actor_ref.send(Produce {});
+    ///         } else {
+    ///             self.done.send(()).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<MailboxPressure> for Producer {
+    ///     fn handle(&mut self, msg: MailboxPressure, _context: &ActorContext<Self>) {
+    ///         self.levels.lock().unwrap().push((msg.level, self.consumer.pressure()));
+    ///         self.interval = match msg.level {
+    ///             PressureLevel::High => Duration::from_millis(2),
+    ///             PressureLevel::Normal => Duration::from_millis(1),
+    ///         };
+    ///     }
+    /// }
+    /// struct ProducerFactory {
+    ///     consumer: ActorWrapper<Consumer>,
+    ///     levels: Arc<Mutex<Vec<(PressureLevel, PressureLevel)>>>,
+    ///     done: Sender<()>,
+    /// }
+    /// impl ActorFactory<Producer> for ProducerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Producer>) -> Producer {
+    ///         assert!(context.watch_pressure(&self.consumer));
+    ///         Producer {
+    ///             consumer: self.consumer.clone(),
+    ///             interval: Duration::from_millis(1),
+    ///             until: Instant::now() + Duration::from_secs(2),
+    ///             levels: self.levels.clone(),
+    ///             done: self.done.clone(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let max_len = Arc::new(Mutex::new(0));
+    /// let consumer = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(100)
+    ///     .set_watermarks(Watermarks::new(40, 10))
+    ///     .spawn("consumer", ConsumerFactory { max_len: max_len.clone() })
+    ///     .unwrap();
+    /// let levels = Arc::new(Mutex::new(Vec::new()));
+    /// let (done, produced) = unbounded();
+    /// let producer = actor_system
+    ///     .builder()
+    ///     .spawn("producer", ProducerFactory { consumer: consumer.clone(), levels: levels.clone(), done })
+    ///     .unwrap();
+    /// producer.send(Produce {});
+    /// produced.recv_timeout(Duration::from_secs(10)).unwrap();
+    /// // the consumer drains what is left, the return to Normal is reported as well
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while levels.lock().unwrap().len() % 2 == 1 && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(1));
+    /// }
+    ///
+    /// // the queue oscillates between the watermarks, every crossing is reported once
+    /// let levels = levels.lock().unwrap();
+    /// assert!(levels.len() >= 4);
+    /// for (index, (level, polled)) in levels.iter().enumerate() {
+    ///     let expected = if index % 2 == 0 { PressureLevel::High } else { PressureLevel::Normal };
+    ///     assert_eq!(*level, expected);
+    ///     assert_eq!(*polled, expected);
+    /// }
+    /// assert_eq!(levels.len() % 2, 0);
+    /// assert_eq!(consumer.pressure(), PressureLevel::Normal);
+    /// assert!(*max_len.lock().unwrap() < 100);
+    /// ```
+    pub fn set_watermarks(mut self, watermarks: Watermarks) -> ActorBuilder<A> {
+        self.watermarks = Some(watermarks);
+        self
+    }
+
+    /// Spawns the Actor without starting it
+    ///
+    /// Messages are queued until [ActorWrapper.release](../prelude/struct.ActorWrapper.html#method.release) or [ActorSystem.release_all](../prelude/struct.ActorSystem.html#method.release_all) is called, `pre_start` is executed at the release.
+    /// An Actor that is stopped before its release never executes `pre_start` or `post_stop` and drops its queued messages
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Step { id: usize }
+    /// impl ActorMessage for Step {}
+    ///
+    /// struct Worker {
+    ///     log: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn pre_start(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("pre_start"));
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("post_stop"));
+    ///     }
+    /// }
+    /// impl Handler<Step> for Worker {
+    ///     fn handle(&mut self, msg: Step, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(msg.id.to_string());
+    ///     }
+    /// }
+    ///
+    /// struct WorkerFactory {
+    ///     log: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let builder = actor_system.builder().start_suspended(true);
+    ///
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let worker = builder.spawn("worker", WorkerFactory { log: log.clone() }).unwrap();
+    /// for id in 0..3 {
+    ///     worker.send(Step { id });
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// assert!(log.lock().unwrap().is_empty());
+    /// assert!(worker.is_awaiting_release());
+    /// assert_eq!(3, worker.mailbox_len());
+    ///
+    /// assert!(worker.release());
+    /// assert!(!worker.release());
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec!["pre_start", "0", "1", "2"], *log.lock().unwrap());
+    /// assert!(!worker.is_awaiting_release());
+    ///
+    /// // stopped before its release, the Actor never starts
+    /// let unreleased_log = Arc::new(Mutex::new(Vec::new()));
+    /// let unreleased = builder.spawn("unreleased", WorkerFactory { log: unreleased_log.clone() }).unwrap();
+    /// unreleased.send(Step { id: 0 });
+    /// unreleased.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert!(!unreleased.release());
+    /// assert!(unreleased_log.lock().unwrap().is_empty());
+    /// assert_eq!(0, unreleased.mailbox_len());
+    /// // the address is free again
+    /// assert!(actor_system.builder().spawn("unreleased", WorkerFactory { log: unreleased_log.clone() }).is_some());
+    /// ```
+    pub fn start_suspended(mut self, start_suspended: bool) -> ActorBuilder<A> {
+        self.start_suspended = start_suspended;
+        self
+    }
+
+    /// Holds the Actor back until the Actor at `dependency` is ready, can be called once per dependency
+    ///
+    /// The Actor is spawned as if [start_suspended](#method.start_suspended) was set, messages are queued and `pre_start` is executed once all dependencies are ready,
+    /// see [Actor.is_ready](../prelude/trait.Actor.html#method.is_ready). Dependencies that are not running yet are waited for, an explicit [ActorWrapper.release](../prelude/struct.ActorWrapper.html#method.release) starts the Actor right away.
+    ///
+    /// Spawning fails with [SpawnError::DependencyCycle](../prelude/enum.SpawnError.html#variant.DependencyCycle) if the dependencies of running Actors lead back to the Actor.
+    /// A dependency that stops before it became ready is published on [DEPENDENCY_TOPIC](../prelude/constant.DEPENDENCY_TOPIC.html) and handled according to [set_dependency_failure_policy](#method.set_dependency_failure_policy)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Request;
+    /// impl ActorMessage for Request {}
+    ///
+    /// struct Cache { warmed: Arc<AtomicBool> }
+    /// impl Actor for Cache {
+    ///     fn pre_start(&mut self) {
+    ///         sleep(Duration::from_millis(100));
+    ///         self.warmed.store(true, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// struct RequestHandler { warmed: Arc<AtomicBool>, observed: Sender<bool> }
+    /// impl Actor for RequestHandler {}
+    /// impl Handler<Request> for RequestHandler {
+    ///     fn handle(&mut self, _msg: Request, _context: &ActorContext<Self>) {
+    ///         self.observed.send(self.warmed.load(Ordering::SeqCst)).unwrap();
+    ///     }
+    /// }
+    ///
+    /// struct Failing;
+    /// impl Actor for Failing {
+    ///     fn is_ready(&self) -> bool {
+    ///         false
+    ///     }
+    /// }
+    ///
+    /// struct Monitor { events: Sender<DependencyFailed> }
+    /// impl Actor for Monitor {}
+    /// impl Handler<DependencyFailed> for Monitor {
+    ///     fn handle(&mut self, msg: DependencyFailed, _context: &ActorContext<Self>) {
+    ///         self.events.send(msg).unwrap();
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let warmed = Arc::new(AtomicBool::new(false));
+    /// let (log, observed) = unbounded();
+    ///
+    /// // the handler is declared first and waits for the cache, even before it is spawned
+    /// let cache_address = actor_system.address_of("default", "cache");
+    /// let flag = warmed.clone();
+    /// let handler = actor_system
+    ///     .builder()
+    ///     .depends_on(&cache_address)
+    ///     .spawn_fn("handler", move |_context| RequestHandler { warmed: flag.clone(), observed: log.clone() })
+    ///     .unwrap();
+    /// handler.send(Request).unwrap();
+    /// assert!(handler.is_awaiting_release());
+    /// let flag = warmed.clone();
+    /// let cache = actor_system.builder().spawn_fn("cache", move |_context| Cache { warmed: flag.clone() }).unwrap();
+    /// handler.send(Request).unwrap();
+    /// // the first handled message already observes the warmed cache
+    /// for _ in 0..2 {
+    ///     assert!(observed.recv_timeout(Duration::from_secs(5)).unwrap());
+    /// }
+    ///
+    /// // dependencies that lead back to the Actor are rejected
+    /// let first = actor_system.address_of("default", "first");
+    /// let second = actor_system.address_of("default", "second");
+    /// actor_system.builder().depends_on(&second).spawn_fn("first", |_context| Failing).unwrap();
+    /// let cycle = actor_system.builder().depends_on(&first).try_spawn("second", |_context: ActorContext<Failing>| Failing);
+    /// assert_eq!(Err(SpawnError::DependencyCycle(vec![second.clone(), first.clone(), second.clone()])), cycle.map(|_| ()));
+    /// let itself = actor_system.builder().depends_on(&second).try_spawn("second", |_context: ActorContext<Failing>| Failing);
+    /// assert_eq!(Err(SpawnError::DependencyCycle(vec![second.clone(), second.clone()])), itself.map(|_| ()));
+    ///
+    /// // a dependency that stops before it became ready stops or releases its dependents
+    /// let (log, events) = unbounded();
+    /// let monitor = actor_system.builder().spawn_fn("monitor", move |_context| Monitor { events: log.clone() }).unwrap();
+    /// actor_system.get_event_bus().subscribe_pattern::<DependencyFailed, _>(DEPENDENCY_TOPIC, &monitor).unwrap();
+    /// let never_ready = actor_system.builder().spawn_fn("never-ready", |_context| Failing).unwrap();
+    /// let stopped = actor_system.builder().depends_on(never_ready.get_address()).spawn_fn("stopped", |_context| Failing).unwrap();
+    /// let proceeding = actor_system
+    ///     .builder()
+    ///     .depends_on(never_ready.get_address())
+    ///     .set_dependency_failure_policy(DependencyFailurePolicy::Proceed)
+    ///     .spawn_fn("proceeding", |_context| Failing)
+    ///     .unwrap();
+    /// never_ready.stop();
+    /// assert!(stopped.await_stop(Duration::from_secs(1)));
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while proceeding.is_awaiting_release() && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(1));
+    /// }
+    /// assert!(!proceeding.is_awaiting_release());
+    /// let mut events: Vec<DependencyFailed> = (0..2).map(|_| events.recv_timeout(Duration::from_secs(5)).unwrap()).collect();
+    /// events.sort_by(|a, b| a.dependent.cmp(&b.dependent));
+    /// assert_eq!(2, events.len());
+    /// assert_eq!((proceeding.get_address(), DependencyFailurePolicy::Proceed), (&events[0].dependent, events[0].policy));
+    /// assert_eq!((stopped.get_address(), DependencyFailurePolicy::Stop), (&events[1].dependent, events[1].policy));
+    /// assert!(events.iter().all(|event| &event.dependency == never_ready.get_address()));
+    ///
+    /// cache.stop();
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn depends_on(mut self, dependency: &ActorAddress) -> ActorBuilder<A> {
+        self.dependencies.push(dependency.clone());
+        self
+    }
+
+    /// Defines what happens once a dependency stops before it became ready, defaults to [DependencyFailurePolicy::Stop](../prelude/enum.DependencyFailurePolicy.html#variant.Stop), see [depends_on](#method.depends_on)
+    pub fn set_dependency_failure_policy(mut self, policy: DependencyFailurePolicy) -> ActorBuilder<A> {
+        self.dependency_failure_policy = policy;
+        self
+    }
+
+    /// Skips all queued messages of the types listed by [Supersedes](../prelude/trait.Supersedes.html) once a message of type `M` is sent
+    ///
+    /// Superseded messages are dropped when they are dispatched, without being constructed or handled, and are counted through [ActorWrapper.superseded_count](../prelude/struct.ActorWrapper.html#method.superseded_count).
+    /// Sending and dispatching stay constant in time, Actors without superseding types do not pay anything
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::any::TypeId;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Update { value: u64 }
+    /// impl ActorMessage for Update {}
+    /// struct Ping { id: u64 }
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct ResetAll {}
+    /// impl ActorMessage for ResetAll {}
+    /// impl Supersedes for ResetAll {
+    ///     fn superseded_types() -> Vec<TypeId> {
+    ///         vec![TypeId::of::<Update>()]
+    ///     }
+    /// }
+    ///
+    /// struct Store { log: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Store {}
+    /// impl Handler<Update> for Store {
+    ///     fn handle(&mut self, msg: Update, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("update {}", msg.value));
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Store {
+    ///     fn handle(&mut self, msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("ping {}", msg.id));
+    ///     }
+    /// }
+    /// impl Handler<ResetAll> for Store {
+    ///     fn handle(&mut self, _msg: ResetAll, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(String::from("reset"));
+    ///     }
+    /// }
+    /// struct StoreFactory { log: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Store> for StoreFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Store>) -> Store {
+    ///         Store { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let store = actor_system
+    ///     .builder()
+    ///     .add_superseding::<ResetAll>()
+    ///     .start_suspended(true)
+    ///     .spawn("store", StoreFactory { log: log.clone() })
+    ///     .unwrap();
+    ///
+    /// for value in 0..500 {
+    ///     store.send(Update { value });
+    ///     if value % 100 == 0 {
+    ///         store.send(Ping { id: value });
+    ///     }
+    /// }
+    /// store.send(ResetAll {});
+    /// store.send(Update { value: 500 });
+    /// store.release();
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let expected = vec!["ping 0", "ping 100", "ping 200", "ping 300", "ping 400", "reset", "update 500"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    /// assert_eq!(500, store.superseded_count());
+    /// ```
+    pub fn add_superseding<M>(mut self) -> ActorBuilder<A>
+    where
+        A: Handler<M>,
+        M: Supersedes,
+    {
+        self.superseding.insert(TypeId::of::<M>(), M::superseded_types());
+        self
+    }
+
+    /// Hands queued messages of type `M` to [BatchHandler.handle_batch](../prelude/trait.BatchHandler.html#tymethod.handle_batch) instead of handling them one by one
+    ///
+    /// A batch holds the messages that directly follow each other in the regular mailbox, up to the message throughput of the Actor.
+    /// It ends at the first message of another type, at a message with [Headers](../prelude/struct.Headers.html) or a sender, and as soon as the priority lane holds a message, which are all handled next as usual.
+    /// Every message of a batch counts as processed, a panic loses the whole batch. Actors without batched types do not pay anything
+    pub fn add_batching<M>(mut self) -> ActorBuilder<A>
+    where
+        A: BatchHandler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.batching.add::<M>();
+        self
+    }
+
+    /// Queues messages of type `M` in their own class, so that a flood of other messages does not delay them
+    ///
+    /// The Executor takes up to `weight` messages of a class in turn with the other classes and the regular mailbox, which holds the messages of all types without a class and has a weight of `1`.
+    /// Classes that are empty pass their turn. Messages within a class keep their order, messages of different classes do not.
+    /// The classes are taken in the order they have been added, adding a type again replaces its weight, a weight of `0` counts as `1`.
+    /// Each class has the capacity of the mailbox, the message throughput still bounds all of them together. The priority lane is handled before any class
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// type Log = Arc<Mutex<Vec<String>>>;
+    ///
+    /// struct Control(u32);
+    /// impl ActorMessage for Control {}
+    /// struct Data(u32);
+    /// impl ActorMessage for Data {}
+    ///
+    /// struct Pipeline {
+    ///     log: Log,
+    /// }
+    /// impl Actor for Pipeline {}
+    /// impl Handler<Control> for Pipeline {
+    ///     fn handle(&mut self, msg: Control, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("c{}", msg.0));
+    ///     }
+    /// }
+    /// impl Handler<Data> for Pipeline {
+    ///     fn handle(&mut self, msg: Data, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("d{}", msg.0));
+    ///     }
+    /// }
+    /// struct PipelineFactory {
+    ///     log: Log,
+    /// }
+    /// impl ActorFactory<Pipeline> for PipelineFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Pipeline>) -> Pipeline {
+    ///         Pipeline { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let run = |name: &str, builder: ActorBuilder<Pipeline>, data: u32, control: u32| -> Vec<String> {
+    ///     let log = Log::default();
+    ///     let pipeline = builder.start_suspended(true).spawn(name, PipelineFactory { log: log.clone() }).unwrap();
+    ///     for i in 0..data {
+    ///         pipeline.send(Data(i)).unwrap();
+    ///     }
+    ///     for i in 0..control {
+    ///         pipeline.send(Control(i)).unwrap();
+    ///     }
+    ///     pipeline.release();
+    ///     sleep(Duration::from_millis(300));
+    ///     let log = log.lock().unwrap().clone();
+    ///     log
+    /// };
+    /// let classified = || actor_system.builder().add_message_class::<Control>(4).add_message_class::<Data>(1);
+    ///
+    /// // four control messages per data message while both are queued
+    /// let log = run("weighted", classified(), 4, 8);
+    /// let expected = ["c0", "c1", "c2", "c3", "d0", "c4", "c5", "c6", "c7", "d1", "d2", "d3"];
+    /// assert_eq!(expected.to_vec(), log);
+    ///
+    /// // control messages do not wait for a flood of data messages
+    /// let log = run("flooded", classified(), 1000, 2);
+    /// assert_eq!(1002, log.len());
+    /// assert_eq!(["c0", "c1"].to_vec(), log[..2].to_vec());
+    /// assert!(log[2..].iter().enumerate().all(|(i, entry)| *entry == format!("d{}", i)));
+    ///
+    /// // without classes everything is handled in the order it has been sent
+    /// let log = run("fifo", actor_system.builder(), 1000, 2);
+    /// assert_eq!(["c0", "c1"].to_vec(), log[1000..].to_vec());
+    /// ```
+    pub fn add_message_class<M>(mut self, weight: usize) -> ActorBuilder<A>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.message_classes.add(TypeId::of::<M>(), weight);
+        self
+    }
+
+    /// Executes `middleware` around every message the Actor handles, see [ActorMiddleware](../prelude/trait.ActorMiddleware.html)
+    ///
+    /// Middlewares are executed in the order they have been added before the handler and in reverse order afterwards, stop messages bypass them.
+    /// Each middleware is shared by all Actors spawned through this builder and kept across restarts.
+    /// Messages of Actors with middlewares are never batched, see [add_batching](#method.add_batching)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// type Log = Arc<Mutex<Vec<String>>>;
+    ///
+    /// struct Deposit { amount: u64 }
+    /// impl ActorMessage for Deposit {}
+    /// struct Withdrawal { amount: u64 }
+    /// impl ActorMessage for Withdrawal {}
+    /// struct Audit {}
+    /// impl ActorMessage for Audit {}
+    ///
+    /// struct Account { log: Log }
+    /// impl Actor for Account {}
+    /// impl Handler<Deposit> for Account {
+    ///     fn handle(&mut self, msg: Deposit, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("deposit {}", msg.amount));
+    ///     }
+    /// }
+    /// impl Handler<Withdrawal> for Account {
+    ///     fn handle(&mut self, msg: Withdrawal, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("withdrawal {}", msg.amount));
+    ///     }
+    /// }
+    /// impl Handler<Audit> for Account {
+    ///     fn handle(&mut self, _msg: Audit, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(String::from("audit"));
+    ///     }
+    /// }
+    /// struct AccountFactory { log: Log }
+    /// impl ActorFactory<Account> for AccountFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Account>) -> Account {
+    ///         Account { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// // usable for any Actor
+    /// struct Trace { name: &'static str, log: Log }
+    /// impl<A: Actor> ActorMiddleware<A> for Trace {
+    ///     fn before_handle(&mut self, _meta: &MessageMeta, _context: &ActorContext<A>) -> MiddlewareDecision {
+    ///         self.log.lock().unwrap().push(format!("{} before", self.name));
+    ///         MiddlewareDecision::Continue
+    ///     }
+    ///     fn after_handle(&mut self, _meta: &MessageMeta, _duration: Duration, outcome: HandleOutcome) {
+    ///         self.log.lock().unwrap().push(format!("{} after {:?}", self.name, outcome));
+    ///     }
+    /// }
+    ///
+    /// struct ReadOnly {}
+    /// impl ActorMiddleware<Account> for ReadOnly {
+    ///     fn before_handle(&mut self, meta: &MessageMeta, _context: &ActorContext<Account>) -> MiddlewareDecision {
+    ///         match meta.message_type.rsplit("::").next().unwrap() {
+    ///             "Withdrawal" => MiddlewareDecision::DeadLetter,
+    ///             "Audit" => MiddlewareDecision::Drop,
+    ///             _ => MiddlewareDecision::Continue,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Log::default();
+    /// let account = actor_system
+    ///     .builder()
+    ///     .add_middleware(Box::new(Trace { name: "outer", log: log.clone() }))
+    ///     .add_middleware(Box::new(ReadOnly {}))
+    ///     .add_middleware(Box::new(Trace { name: "inner", log: log.clone() }))
+    ///     .spawn("account", AccountFactory { log: log.clone() })
+    ///     .unwrap();
+    ///
+    /// account.send(Deposit { amount: 10 }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// let expected = vec!["outer before", "inner before", "deposit 10", "inner after Handled", "outer after Handled"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    ///
+    /// log.lock().unwrap().clear();
+    /// account.send(Withdrawal { amount: 5 }).unwrap();
+    /// account.send(Audit {}).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// // neither reached the inner middleware or the handler, only the withdrawal is a dead letter
+    /// let expected = vec!["outer before", "outer after Rejected", "outer before", "outer after Rejected"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    /// assert_eq!(1, actor_system.dead_letter_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn add_middleware(mut self, middleware: Box<dyn ActorMiddleware<A>>) -> ActorBuilder<A> {
+        self.middlewares.add(middleware);
+        self
+    }
+
+    /// Adds `M` to the message types the Actor accepts, see [ActorSystem.accepted_messages](../prelude/struct.ActorSystem.html#method.accepted_messages)
+    ///
+    /// Types that have not been registered are added once the Actor handled a message of them successfully, unless [set_strict_messages](#method.set_strict_messages) is set.
+    /// Messages every Actor handles, like stop messages, are never listed
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::any::type_name;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Order {}
+    /// impl ActorMessage for Order {}
+    /// struct Refund {}
+    /// impl ActorMessage for Refund {}
+    ///
+    /// struct Shop {}
+    /// impl Actor for Shop {}
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, _msg: Order, _context: &ActorContext<Self>) {}
+    /// }
+    /// impl Handler<Refund> for Shop {
+    ///     fn handle(&mut self, _msg: Refund, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct ShopFactory {}
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+    ///         Shop {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let shop = actor_system.builder().register_message::<Order>().spawn("shop", ShopFactory {}).unwrap();
+    /// assert_eq!(vec![type_name::<Order>()], actor_system.accepted_messages(shop.get_address()));
+    ///
+    /// // added on the first dispatch
+    /// shop.send(Refund {}).unwrap();
+    /// let mut expected = vec![type_name::<Order>(), type_name::<Refund>()];
+    /// expected.sort();
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while actor_system.accepted_messages(shop.get_address()) != expected && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(1));
+    /// }
+    /// assert_eq!(expected, actor_system.accepted_messages(shop.get_address()));
+    /// assert_eq!(expected, actor_system.list_actors()[0].accepted_messages);
+    ///
+    /// // stopped Actors do not accept anything
+    /// shop.stop();
+    /// assert!(shop.await_stop(Duration::from_secs(1)));
+    /// assert!(actor_system.accepted_messages(shop.get_address()).is_empty());
+    /// ```
+    pub fn register_message<M>(mut self) -> ActorBuilder<A>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.registered_messages.push((TypeId::of::<M>(), std::any::type_name::<M>()));
+        self
+    }
+
+    /// Only accepts the message types registered through [register_message](#method.register_message), messages of other types are dead letters with [DeadLetterReason::Unregistered](../prelude/enum.DeadLetterReason.html#variant.Unregistered)
+    ///
+    /// Messages are checked once they are handled, so the sender does not notice the rejection.
+    /// Messages every Actor handles, like stop messages, are always accepted
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::any::type_name;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Order {}
+    /// impl ActorMessage for Order {}
+    /// struct Refund {}
+    /// impl ActorMessage for Refund {}
+    ///
+    /// struct Shop {
+    ///     handled: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Shop {}
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, _msg: Order, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// impl Handler<Refund> for Shop {
+    ///     fn handle(&mut self, _msg: Refund, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct ShopFactory {
+    ///     handled: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+    ///         Shop { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(AtomicUsize::new(0));
+    /// let shop = actor_system
+    ///     .builder()
+    ///     .register_message::<Order>()
+    ///     .set_strict_messages(true)
+    ///     .spawn("shop", ShopFactory { handled: handled.clone() })
+    ///     .unwrap();
+    ///
+    /// shop.send(Order {}).unwrap();
+    /// // accepted by the mailbox, but never handled
+    /// shop.send(Refund {}).unwrap();
+    /// // the order has been handled before the refund is dispatched
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while actor_system.dead_letter_count() == 0 && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(1));
+    /// }
+    /// assert_eq!(1, handled.load(Ordering::Relaxed));
+    /// assert_eq!(1, actor_system.dead_letter_count());
+    /// assert_eq!(vec![type_name::<Order>()], actor_system.list_actors()[0].accepted_messages);
+    ///
+    /// // stop messages are always accepted
+    /// shop.stop();
+    /// assert!(shop.await_stop(Duration::from_secs(1)));
+    /// ```
+    pub fn set_strict_messages(mut self, strict_messages: bool) -> ActorBuilder<A> {
+        self.strict_messages = strict_messages;
+        self
+    }
+
+    /// Lets [FailedMessageStrategy::RetryOnce](../prelude/enum.FailedMessageStrategy.html#variant.RetryOnce) retry messages of type `M`
+    ///
+    /// The message is cloned before it is handled as long as the strategy is set, lazily constructed messages are never retried
+    pub fn add_retryable<M>(mut self) -> ActorBuilder<A>
+    where
+        A: Handler<M>,
+        M: ActorMessage + Clone + 'static,
+    {
+        self.retrying.add::<M>();
+        self
+    }
+
+    /// Recovers the state of the Actor after a panic, see [RecoverableActor](../prelude/trait.RecoverableActor.html)
+    ///
+    /// A snapshot is taken every `snapshot_interval` handled messages and when the Actor stops gracefully, `0` only takes it on stop.
+    /// Restarted Actors recover from the latest snapshot, Actors that are spawned again after they stopped only if [set_recover_after_stop](#method.set_recover_after_stop) is set
+    pub fn set_recovery(mut self, snapshot_interval: usize) -> ActorBuilder<A>
+    where
+        A: RecoverableActor,
+    {
+        self.recovery = Some(Recovery::new(self.snapshot_store.clone()));
+        self.snapshot_interval = snapshot_interval;
+        self
+    }
+
+    /// Storage of the snapshots taken for [set_recovery](#method.set_recovery), shared by all Actors spawned by this builder
+    ///
+    /// Defaults to a [MemorySnapshotStore](../prelude/struct.MemorySnapshotStore.html) per builder
+    pub fn set_snapshot_store(mut self, store: impl SnapshotStore + 'static) -> ActorBuilder<A> {
+        self.snapshot_store = Arc::new(store);
+        if let Some(recovery) = self.recovery.as_mut() {
+            recovery.store = self.snapshot_store.clone();
+        }
+        self
+    }
+
+    /// Actors spawned again after a graceful stop recover from the snapshot of their previous run, defaults to `false`
+    ///
+    /// Without it the snapshot of a previous run is removed when the Actor is spawned, only restarts after a panic recover
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// struct Increment {}
+    /// impl ActorMessage for Increment {}
+    ///
+    /// struct Counter { count: u8 }
+    /// impl Actor for Counter {}
+    /// impl Handler<Increment> for Counter {
+    ///     fn handle(&mut self, _msg: Increment, _context: &ActorContext<Self>) {
+    ///         self.count += 1;
+    ///     }
+    /// }
+    /// impl RecoverableActor for Counter {
+    ///     fn recovery_snapshot(&self) -> Option<Vec<u8>> {
+    ///         Some(vec![self.count])
+    ///     }
+    ///     fn recover(&mut self, snapshot: Vec<u8>) {
+    ///         self.count = snapshot[0];
+    ///     }
+    /// }
+    ///
+    /// struct CounterFactory {}
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { count: 0 }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let run = |builder: &ActorBuilder<Counter>| {
+    ///     let counter = builder.spawn("counter", CounterFactory {}).unwrap();
+    ///     counter.send(Increment {}).unwrap();
+    ///     counter.send(Increment {}).unwrap();
+    ///     counter.stop_and_extract(Duration::from_secs(1)).unwrap().count
+    /// };
+    ///
+    /// let resuming = actor_system.builder().set_recovery(0).set_recover_after_stop(true);
+    /// assert_eq!(2, run(&resuming));
+    /// assert_eq!(4, run(&resuming));
+    ///
+    /// let fresh = actor_system.builder().set_recovery(0);
+    /// assert_eq!(2, run(&fresh));
+    /// assert_eq!(2, run(&fresh));
+    /// ```
+    pub fn set_recover_after_stop(mut self, recover_after_stop: bool) -> ActorBuilder<A> {
+        self.recover_after_stop = recover_after_stop;
+        self
+    }
+
+    /// Lets [ActorSystem.debug_snapshot](../prelude/struct.ActorSystem.html#method.debug_snapshot) inspect the state of the Actor, see [DebuggableActor](../prelude/trait.DebuggableActor.html)
+    pub fn set_debuggable(mut self) -> ActorBuilder<A>
+    where
+        A: DebuggableActor,
+    {
+        self.debug_snapshot = Some(A::debug_snapshot);
+        self
+    }
+
+    /// Executes the Actor on a thread of its own for its entire lifetime, defaults to `false`
+    ///
+    /// [ActorFactory.new_actor](../prelude/trait.ActorFactory.html#tymethod.new_actor), all handlers and restarts are executed on that thread, for resources that must not leave the thread that created them.
+    /// The thread exits once the Actor stopped, the Actor counts towards the shutdown of the system like any other.
+    /// A [TestActorSystem](../testing/struct.TestActorSystem.html) executes the Actor like any other Actor of its pool
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::{self, sleep, ThreadId};
+    /// use std::time::Duration;
+    ///
+    /// struct Work { crash: bool }
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Device {
+    ///     threads: Arc<Mutex<Vec<ThreadId>>>,
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Device {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Work> for Device {
+    ///     fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+    ///         self.threads.lock().unwrap().push(thread::current().id());
+    ///         if msg.crash {
+    ///             panic!("device reset");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct DeviceFactory {
+    ///     threads: Arc<Mutex<Vec<ThreadId>>>,
+    /// }
+    /// impl ActorFactory<Device> for DeviceFactory {
+    ///     fn new_actor(&self, context: ActorContext<Device>) -> Device {
+    ///         self.threads.lock().unwrap().push(thread::current().id());
+    ///         Device { threads: self.threads.clone(), context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let builder = actor_system
+    ///     .builder()
+    ///     .set_dedicated_thread(true)
+    ///     .set_restart_policy(RestartPolicy::Always);
+    /// let threads = Arc::new(Mutex::new(Vec::new()));
+    /// let device = builder.spawn("device", DeviceFactory { threads: threads.clone() }).unwrap();
+    /// for i in 0..1000 {
+    ///     device.send(Work { crash: i == 500 }).unwrap();
+    /// }
+    /// // a panic after the stop request is not restarted
+    /// sleep(Duration::from_millis(500));
+    /// device.stop();
+    /// assert!(device.await_stop(Duration::from_secs(5)));
+    ///
+    /// // two creations, one of them after the panic, and 1000 handled messages
+    /// let threads = std::mem::take(&mut *threads.lock().unwrap());
+    /// assert_eq!(1002, threads.len());
+    /// assert!(threads.iter().all(|thread| *thread == threads[0]));
+    /// assert_ne!(thread::current().id(), threads[0]);
+    ///
+    /// // the system waits for Actors on dedicated threads as well
+    /// let other_threads = Arc::new(Mutex::new(Vec::new()));
+    /// builder.spawn("other", DeviceFactory { threads: other_threads.clone() }).unwrap();
+    /// actor_system.stop(Duration::from_secs(5));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert_ne!(threads[0], other_threads.lock().unwrap()[0]);
+    /// ```
+    pub fn set_dedicated_thread(mut self, dedicated_thread: bool) -> ActorBuilder<A> {
+        self.dedicated_thread = dedicated_thread;
+        self
+    }
+
+    /// Defers the creation of the Actor until its first message, defaults to `false`
+    ///
+    /// `spawn` only registers the address and the mailbox, [ActorFactory.new_actor](../prelude/trait.ActorFactory.html#tymethod.new_actor) and [Actor.pre_start](../prelude/trait.Actor.html#method.pre_start) are executed on a thread of the pool once the first message arrived.
+    /// Messages keep their order, a factory that fails or panics is handled by the [RestartPolicy](../prelude/enum.RestartPolicy.html) instead of failing the spawn.
+    /// An Actor that has not been created yet is stopped without creating it, unless messages are still queued
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Query { id: u32 }
+    /// impl ActorMessage for Query {}
+    ///
+    /// struct Index { log: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Index {
+    ///     fn pre_start(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("pre_start"));
+    ///     }
+    /// }
+    /// impl Handler<Query> for Index {
+    ///     fn handle(&mut self, msg: Query, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("query {}", msg.id));
+    ///     }
+    /// }
+    /// struct IndexFactory { log: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Index> for IndexFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Index>) -> Index {
+    ///         self.log.lock().unwrap().push(String::from("new_actor"));
+    ///         Index { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let builder = actor_system.builder().set_lazy(true);
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let index = builder.spawn("index", IndexFactory { log: log.clone() }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert!(log.lock().unwrap().is_empty());
+    ///
+    /// for id in 0..3 {
+    ///     index.send(Query { id }).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec!["new_actor", "pre_start", "query 0", "query 1", "query 2"], *log.lock().unwrap());
+    ///
+    /// // the Index does not implement on_system_stop, only Actors that have never been created stop on their own
+    /// let unused = Arc::new(Mutex::new(Vec::new()));
+    /// builder.spawn("unused", IndexFactory { log: unused.clone() }).unwrap();
+    /// index.stop();
+    /// assert!(index.await_stop(Duration::from_secs(1)));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert!(unused.lock().unwrap().is_empty());
+    /// ```
+    pub fn set_lazy(mut self, lazy: bool) -> ActorBuilder<A> {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Defines what happens to sends to the full bounded mailbox of the Actor, see [MailboxOverflowPolicy](../prelude/enum.MailboxOverflowPolicy.html)
+    ///
+    /// Defaults to [Block](../prelude/enum.MailboxOverflowPolicy.html#variant.Block), has no effect if the mailbox size is `0`
+    pub fn set_overflow_policy(mut self, overflow_policy: MailboxOverflowPolicy) -> ActorBuilder<A> {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Reports messages dropped by the overflow policy as [Overflow](../prelude/enum.DeadLetterReason.html#variant.Overflow) dead letters, see [ActorSystem.subscribe_dead_letters](../prelude/struct.ActorSystem.html#method.subscribe_dead_letters)
+    ///
+    /// They are counted in [ActorSystem.drop_stats](../prelude/struct.ActorSystem.html#method.drop_stats) either way
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// struct Sample { id: usize }
+    /// impl ActorMessage for Sample {}
+    ///
+    /// struct Sink {}
+    /// impl Actor for Sink {}
+    /// impl Handler<Sample> for Sink {
+    ///     fn handle(&mut self, _msg: Sample, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct SinkFactory {}
+    /// impl ActorFactory<Sink> for SinkFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+    ///         Sink {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let sink = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(2)
+    ///     .set_overflow_policy(MailboxOverflowPolicy::DropOldest)
+    ///     .set_overflow_dead_letters(true)
+    ///     .start_suspended(true)
+    ///     .spawn("sink", SinkFactory {})
+    ///     .unwrap();
+    /// for id in 0..5 {
+    ///     sink.send(Sample { id }).unwrap();
+    /// }
+    /// assert_eq!(3, sink.dropped_count());
+    /// assert_eq!(3, actor_system.dead_letter_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_overflow_dead_letters(mut self, overflow_dead_letters: bool) -> ActorBuilder<A> {
+        self.overflow_dead_letters = overflow_dead_letters;
+        self
+    }
+
+    /// Limits the mailbox by the approximate size of its messages instead of their number, see [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size)
+    ///
+    /// A send that would exceed `max_bytes` is treated like a send to a full mailbox, the [overflow policy](#method.set_overflow_policy) decides what happens to it.
+    /// A message counts from the moment it is queued until it has been handled, expired or dropped, see [ActorWrapper.mailbox_bytes](../prelude/struct.ActorWrapper.html#method.mailbox_bytes).
+    /// A single message larger than `max_bytes` is only accepted by an empty mailbox, use [set_max_message_bytes](#method.set_max_message_bytes) to reject it right away.
+    /// Priority sends, sends to self and stop messages are not counted
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Chunk { data: Vec<u8> }
+    /// impl ActorMessage for Chunk {
+    ///     fn approx_size(&self) -> usize {
+    ///         std::mem::size_of::<Self>() + self.data.capacity()
+    ///     }
+    /// }
+    ///
+    /// struct Uploader { handled: Sender<()> }
+    /// impl Actor for Uploader {}
+    /// impl Handler<Chunk> for Uploader {
+    ///     fn handle(&mut self, _msg: Chunk, _context: &ActorContext<Self>) {
+    ///         self.handled.send(()).unwrap();
+    ///     }
+    /// }
+    ///
+    /// // the bytes of a message are released right after its handler returned
+    /// let released = |uploader: &ActorWrapper<Uploader>| {
+    ///     let deadline = Instant::now() + Duration::from_secs(5);
+    ///     while uploader.mailbox_bytes() > 0 && Instant::now() < deadline {
+    ///         sleep(Duration::from_millis(1));
+    ///     }
+    ///     uploader.mailbox_bytes() == 0
+    /// };
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (sender, handled) = unbounded();
+    /// let chunk = |len: usize| Chunk { data: Vec::with_capacity(len) };
+    /// // room for three large chunks
+    /// let max_bytes = 3 * chunk(4096).approx_size();
+    /// let builder = actor_system
+    ///     .builder()
+    ///     .set_max_mailbox_bytes(max_bytes)
+    ///     .set_overflow_policy(MailboxOverflowPolicy::Fail)
+    ///     .start_suspended(true);
+    /// let uploader_sender = sender.clone();
+    /// let uploader = builder.spawn_fn("uploader", move |_context| Uploader { handled: uploader_sender.clone() }).unwrap();
+    ///
+    /// // many small chunks fit, the large one does not
+    /// for _ in 0..100 {
+    ///     uploader.send(chunk(64)).unwrap();
+    /// }
+    /// assert_eq!(100 * chunk(64).approx_size(), uploader.mailbox_bytes());
+    /// assert!(matches!(uploader.send(chunk(8 * 4096)), Err(SendError::Full(_))));
+    ///
+    /// // the bytes are released once the queue has been drained
+    /// uploader.release();
+    /// for _ in 0..100 {
+    ///     handled.recv_timeout(Duration::from_secs(5)).unwrap();
+    /// }
+    /// assert!(released(&uploader));
+    /// uploader.send(chunk(4096)).unwrap();
+    /// handled.recv_timeout(Duration::from_secs(5)).unwrap();
+    /// assert!(released(&uploader));
+    ///
+    /// // and as well if the queue is discarded by a stop
+    /// let discarding = builder.spawn_fn("discarding", move |_context| Uploader { handled: sender.clone() }).unwrap();
+    /// for _ in 0..3 {
+    ///     discarding.send(chunk(4096)).unwrap();
+    /// }
+    /// assert_eq!(max_bytes, discarding.mailbox_bytes());
+    /// discarding.stop_now();
+    /// discarding.release();
+    /// assert!(discarding.await_stop(Duration::from_secs(1)));
+    /// assert_eq!(0, discarding.mailbox_bytes());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_max_mailbox_bytes(mut self, max_bytes: usize) -> ActorBuilder<A> {
+        self.max_mailbox_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Defines what happens to the message whose handler panicked or failed, see [FailedMessageStrategy](../prelude/enum.FailedMessageStrategy.html)
+    ///
+    /// Defaults to [Drop](../prelude/enum.FailedMessageStrategy.html#variant.Drop)
+    pub fn set_failed_message_strategy(mut self, failed_message_strategy: FailedMessageStrategy) -> ActorBuilder<A> {
+        self.failed_message_strategy = failed_message_strategy;
+        self
+    }
 
-        let actor_config = ActorConfig {
-            pool_name: String::from(DEFAULT_POOL),
-            mailbox_size: config.general.default_mailbox_size,
-            message_throughput: config.general.default_message_throughput,
-            restart_policy: config.general.default_restart_policy,
-        };
+    /// Defines what happens to the messages that are queued when the Actor restarts, see [RestartMailboxPolicy](../prelude/enum.RestartMailboxPolicy.html)
+    ///
+    /// Defaults to [Keep](../prelude/enum.RestartMailboxPolicy.html#variant.Keep)
+    pub fn set_restart_mailbox_policy(mut self, restart_mailbox_policy: RestartMailboxPolicy) -> ActorBuilder<A> {
+        self.restart_mailbox_policy = restart_mailbox_policy;
+        self
+    }
 
-        ActorBuilder {
-            existing: Arc::new(DashMap::new()),
-            system,
-            system_state,
-            wakeup_manager,
-            actor_config,
-        }
+    /// Limits the Actor to `max_messages` handled messages within every window of length `per`
+    ///
+    /// Once a window is exhausted, the Actor goes to sleep until the next one starts instead of blocking its thread.
+    /// The message budget of a scheduling slot is the minimum of its throughput and the permits left in the window, a restart keeps the used permits.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Call {}
+    /// impl ActorMessage for Call {}
+    ///
+    /// struct Client { calls: Sender<Instant> }
+    /// impl Actor for Client {}
+    /// impl Handler<Call> for Client {
+    ///     fn handle(&mut self, _msg: Call, _context: &ActorContext<Self>) {
+    ///         self.calls.send(Instant::now()).unwrap();
+    ///     }
+    /// }
+    /// struct ClientFactory { calls: Sender<Instant> }
+    /// impl ActorFactory<Client> for ClientFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Client>) -> Client {
+    ///         Client { calls: self.calls.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.thread_pool.config.insert(String::from("single"), ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let actor_system = ActorSystem::new(config);
+    /// let (limited_sender, limited_calls) = unbounded();
+    /// let (other_sender, other_calls) = unbounded();
+    /// let limited = actor_system
+    ///     .builder()
+    ///     .set_pool_name("single")
+    ///     .unwrap()
+    ///     .set_rate_limit(10, Duration::from_millis(200))
+    ///     .spawn("limited", ClientFactory { calls: limited_sender })
+    ///     .unwrap();
+    /// let other = actor_system
+    ///     .builder()
+    ///     .set_pool_name("single")
+    ///     .unwrap()
+    ///     .spawn("other", ClientFactory { calls: other_sender })
+    ///     .unwrap();
+    /// let start = Instant::now();
+    /// for _ in 0..25 {
+    ///     limited.send(Call {}).unwrap();
+    /// }
+    ///
+    /// // the only thread of the pool keeps working while the limited Actor waits for its next window
+    /// other.send(Call {}).unwrap();
+    /// other_calls.recv_timeout(Duration::from_secs(5)).unwrap();
+    /// let windows = start.elapsed().as_millis() as usize / 200 + 1;
+    /// assert!(limited_calls.len() <= 10 * windows);
+    ///
+    /// let calls: Vec<Instant> = (0..25).map(|_| limited_calls.recv_timeout(Duration::from_secs(5)).unwrap()).collect();
+    /// assert!(calls[10].duration_since(calls[0]) >= Duration::from_millis(200));
+    /// assert!(calls[20].duration_since(calls[0]) >= Duration::from_millis(400));
+    /// assert!(start.elapsed() >= Duration::from_millis(400));
+    /// ```
+    pub fn set_rate_limit(mut self, max_messages: u32, per: Duration) -> ActorBuilder<A> {
+        self.rate_limit = Some(RateLimit::new(max_messages, per));
+        self
     }
 
-    pub fn set_restart_policy(mut self, restart_policy: RestartPolicy) -> ActorBuilder<A> {
-        self.actor_config.restart_policy = restart_policy;
+    /// Maximum amount of messages the Actor can stash at the same time, defaults to `0`, which allows an unbounded stash
+    ///
+    /// A full stash applies the overflow policy of the mailbox, see [ActorContext.stash](../prelude/struct.ActorContext.html#method.stash)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Request { id: usize }
+    /// impl ActorMessage for Request {}
+    ///
+    /// struct Gate { rejected: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Gate {}
+    /// impl Handler<Request> for Gate {
+    ///     fn handle(&mut self, msg: Request, context: &ActorContext<Self>) {
+    ///         if let Err(error) = context.stash(msg) {
+    ///             self.rejected.lock().unwrap().push(error.into_inner().id);
+    ///         }
+    ///     }
+    /// }
+    /// struct GateFactory { rejected: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Gate> for GateFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Gate>) -> Gate {
+    ///         Gate { rejected: self.rejected.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let rejected = Arc::new(Mutex::new(Vec::new()));
+    /// let gate = actor_system
+    ///     .builder()
+    ///     .set_stash_capacity(2)
+    ///     .set_overflow_policy(MailboxOverflowPolicy::Fail)
+    ///     .spawn("gate", GateFactory { rejected: rejected.clone() })
+    ///     .unwrap();
+    /// for id in 0..4 {
+    ///     gate.send(Request { id }).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![2, 3], *rejected.lock().unwrap());
+    /// ```
+    pub fn set_stash_capacity(mut self, stash_capacity: usize) -> ActorBuilder<A> {
+        self.stash_capacity = stash_capacity;
+        self
+    }
+
+    /// Phase in which the Actor receives the system stop, defaults to [DEFAULT_SHUTDOWN_PHASE](../prelude/constant.DEFAULT_SHUTDOWN_PHASE.html)
+    ///
+    /// [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) stops the phases in ascending order, each phase receives the system stop once all Actors of the previous phases stopped.
+    /// A phase is awaited for its share of the graceful termination timeout, afterwards the next phase is stopped regardless, the timeout itself still applies to the whole system.
+    /// Actors spawned during the shutdown join their phase, unless it already completed
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Record { value: usize }
+    /// impl ActorMessage for Record {}
+    ///
+    /// struct Stage {
+    ///     context: ActorContext<Self>,
+    ///     name: &'static str,
+    ///     next: Option<ActorWrapper<Stage>>,
+    ///     stopped: Arc<Mutex<Vec<(&'static str, Instant)>>>,
+    ///     received: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl Actor for Stage {
+    ///     fn on_system_stop(&mut self) {
+    ///         // the next stage is still running and accepts what is left
+    ///         if let Some(next) = &self.next {
+    ///             next.send(Record { value: 0 }).unwrap();
+    ///         }
+    ///         self.context.actor_ref.stop();
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.stopped.lock().unwrap().push((self.name, Instant::now()));
+    ///     }
+    /// }
+    /// impl Handler<Record> for Stage {
+    ///     fn handle(&mut self, _msg: Record, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(self.name);
+    ///     }
+    /// }
+    /// struct StageFactory {
+    ///     name: &'static str,
+    ///     next: Option<ActorWrapper<Stage>>,
+    ///     stopped: Arc<Mutex<Vec<(&'static str, Instant)>>>,
+    ///     received: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, context: ActorContext<Stage>) -> Stage {
+    ///         Stage {
+    ///             context,
+    ///             name: self.name,
+    ///             next: self.next.clone(),
+    ///             stopped: self.stopped.clone(),
+    ///             received: self.received.clone(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let stopped = Arc::new(Mutex::new(Vec::new()));
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let mut next = None;
+    /// for (phase, name) in [(2, "sink"), (1, "processor"), (0, "source")] {
+    ///     let factory = StageFactory { name, next, stopped: stopped.clone(), received: received.clone() };
+    ///     next = actor_system.builder().set_shutdown_phase(phase).spawn(name, factory);
+    /// }
+    ///
+    /// actor_system.stop(Duration::from_secs(3));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// let stopped = stopped.lock().unwrap();
+    /// let order: Vec<&str> = stopped.iter().map(|(name, _)| *name).collect();
+    /// assert_eq!(vec!["source", "processor", "sink"], order);
+    /// assert!(stopped[0].1 <= stopped[1].1 && stopped[1].1 <= stopped[2].1);
+    /// let mut received = received.lock().unwrap().clone();
+    /// received.sort();
+    /// assert_eq!(vec!["processor", "sink"], received);
+    /// ```
+    pub fn set_shutdown_phase(mut self, shutdown_phase: u8) -> ActorBuilder<A> {
+        self.shutdown_phase = shutdown_phase;
         self
     }
 
-    pub fn set_pool_name(mut self, pool_name: impl Into<String>) -> ActorBuilder<A> {
-        self.actor_config.pool_name = pool_name.into();
+    /// Defines whether the system stop overtakes the queued messages or waits until they have been handled, see [SystemStopOrder](../prelude/enum.SystemStopOrder.html)
+    ///
+    /// Defaults to [BeforeQueued](../prelude/enum.SystemStopOrder.html#variant.BeforeQueued)
+    pub fn set_system_stop_order(mut self, system_stop_order: SystemStopOrder) -> ActorBuilder<A> {
+        self.system_stop_order = system_stop_order;
         self
     }
 
-    pub fn set_message_throughput(mut self, message_throughput: usize) -> ActorBuilder<A> {
-        self.actor_config.message_throughput = message_throughput;
+    /// Excludes the Actor from the graceful drain of [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop), made for housekeeping Actors that have to run until the very end
+    ///
+    /// Once all other Actors stopped, daemons receive the system stop one after another in reverse spawn order.
+    /// A daemon stops by itself after [Actor.on_system_stop](../prelude/trait.Actor.html#method.on_system_stop), messages queued until then are still handled.
+    /// Each daemon gets a fixed grace of 200ms to stop, on top of the graceful termination timeout, daemons that are left afterwards are stopped forcefully.
+    /// Daemons are marked in [ActorSystem.list_actors](../prelude/struct.ActorSystem.html#method.list_actors)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// type Log = Arc<Mutex<Vec<String>>>;
+    ///
+    /// struct Flush { from: &'static str }
+    /// impl ActorMessage for Flush {}
+    ///
+    /// struct Flusher { log: Log }
+    /// impl Actor for Flusher {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("flusher on_system_stop"));
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("flusher post_stop"));
+    ///     }
+    /// }
+    /// impl Handler<Flush> for Flusher {
+    ///     fn handle(&mut self, msg: Flush, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("flushed {}", msg.from));
+    ///     }
+    /// }
+    /// struct FlusherFactory { log: Log }
+    /// impl ActorFactory<Flusher> for FlusherFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Flusher>) -> Flusher {
+    ///         Flusher { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Job {}
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { context: ActorContext<Worker>, flusher: ActorWrapper<Flusher>, log: Log }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.log.lock().unwrap().push(String::from("worker post_stop"));
+    ///         self.flusher.send(Flush { from: "worker" }).unwrap();
+    ///     }
+    /// }
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, _msg: Job, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(300));
+    ///     }
+    /// }
+    /// struct WorkerFactory { flusher: ActorWrapper<Flusher>, log: Log }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context, flusher: self.flusher.clone(), log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Log::default();
+    /// let flusher = actor_system.builder().set_daemon(true).spawn("flusher", FlusherFactory { log: log.clone() }).unwrap();
+    /// let worker = actor_system.builder().spawn("worker", WorkerFactory { flusher, log: log.clone() }).unwrap();
+    /// let actors = actor_system.list_actors();
+    /// assert!(actors.iter().any(|info| info.address.actor == "flusher" && info.daemon));
+    /// assert!(actors.iter().any(|info| info.address.actor == "worker" && !info.daemon));
+    ///
+    /// worker.send(Job {}).unwrap();
+    /// sleep(Duration::from_millis(50));
+    /// let start = Instant::now();
+    /// actor_system.stop(Duration::from_secs(10));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// // the running daemon did not hold up the stop until the timeout
+    /// assert!(start.elapsed() < Duration::from_secs(2));
+    /// // the flush may be handled before or after on_system_stop, but always before the daemon stopped
+    /// let log = log.lock().unwrap();
+    /// assert_eq!(4, log.len());
+    /// assert_eq!("worker post_stop", log[0]);
+    /// assert!(log[1..3].contains(&String::from("flushed worker")));
+    /// assert!(log[1..3].contains(&String::from("flusher on_system_stop")));
+    /// assert_eq!("flusher post_stop", log[3]);
+    /// ```
+    pub fn set_daemon(mut self, daemon: bool) -> ActorBuilder<A> {
+        self.daemon = daemon;
+        self
+    }
+
+    /// Stops the Actor once it has not handled any message for `idle_timeout`, see [Actor.on_idle_stop](../prelude/trait.Actor.html#method.on_idle_stop)
+    ///
+    /// The Actor is woken up once at the deadline instead of checking it continuously, every handled message moves the deadline.
+    /// The Actor stops like through [ActorWrapper.stop](../prelude/struct.ActorWrapper.html#method.stop), messages sent until the stop message has been handled are still handled, later ones are dead letters
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Session {
+    ///     pings: Arc<AtomicUsize>,
+    ///     persisted: Arc<AtomicBool>,
+    /// }
+    /// impl Actor for Session {
+    ///     fn on_idle_stop(&mut self) {
+    ///         self.persisted.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Session {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.pings.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct SessionFactory {
+    ///     pings: Arc<AtomicUsize>,
+    ///     persisted: Arc<AtomicBool>,
+    /// }
+    /// impl ActorFactory<Session> for SessionFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Session>) -> Session {
+    ///         Session { pings: self.pings.clone(), persisted: self.persisted.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let pings = Arc::new(AtomicUsize::new(0));
+    /// let persisted = Arc::new(AtomicBool::new(false));
+    /// let factory = SessionFactory { pings: pings.clone(), persisted: persisted.clone() };
+    /// let session = actor_system
+    ///     .builder()
+    ///     .set_idle_timeout(Duration::from_millis(200))
+    ///     .spawn("session", factory)
+    ///     .unwrap();
+    ///
+    /// // every message before the deadline keeps the Actor alive
+    /// for _ in 0..4 {
+    ///     sleep(Duration::from_millis(100));
+    ///     session.send(Ping {}).unwrap();
+    /// }
+    /// assert_eq!(1, actor_system.get_actor_count());
+    /// assert!(!persisted.load(Ordering::Relaxed));
+    ///
+    /// assert!(session.await_stop(Duration::from_secs(2)));
+    /// assert_eq!(0, actor_system.get_actor_count());
+    /// assert!(persisted.load(Ordering::Relaxed));
+    /// assert_eq!(4, pings.load(Ordering::Relaxed));
+    /// assert!(matches!(session.send(Ping {}), Err(SendError::Stopped(_))));
+    /// ```
+    pub fn set_idle_timeout(mut self, idle_timeout: Duration) -> ActorBuilder<A> {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Executes [Actor.on_tick](../prelude/trait.Actor.html#method.on_tick) every `tick_interval`, regardless of whether the Actor receives any messages
+    ///
+    /// The Actor is woken up at the interval, a due tick is executed between two messages and is never delayed by more than one of them.
+    /// Ticks that have been missed are skipped instead of being caught up, they pause during the restart backoff and end once the Actor is asked to stop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Entry {}
+    /// impl ActorMessage for Entry {}
+    ///
+    /// struct Cache {
+    ///     ticks: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Cache {
+    ///     fn on_tick(&mut self, _context: &ActorContext<Self>) {
+    ///         self.ticks.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// impl Handler<Entry> for Cache {
+    ///     fn handle(&mut self, _msg: Entry, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(2));
+    ///     }
+    /// }
+    /// struct CacheFactory {
+    ///     ticks: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Cache> for CacheFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Cache>) -> Cache {
+    ///         Cache { ticks: self.ticks.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let ticks = Arc::new(AtomicUsize::new(0));
+    /// let cache = actor_system
+    ///     .builder()
+    ///     .set_tick_interval(Duration::from_millis(50))
+    ///     .spawn("cache", CacheFactory { ticks: ticks.clone() })
+    ///     .unwrap();
+    ///
+    /// // the Actor ticks without receiving a single message
+    /// sleep(Duration::from_secs(1));
+    /// let idle_ticks = ticks.load(Ordering::Relaxed);
+    /// assert!((15..=21).contains(&idle_ticks));
+    ///
+    /// // and keeps ticking while its mailbox is never empty
+    /// for _ in 0..500 {
+    ///     cache.send(Entry {}).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(500));
+    /// assert!(ticks.load(Ordering::Relaxed) - idle_ticks >= 7);
+    ///
+    /// cache.stop();
+    /// assert!(cache.await_stop(Duration::from_secs(2)));
+    /// let stopped_ticks = ticks.load(Ordering::Relaxed);
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(stopped_ticks, ticks.load(Ordering::Relaxed));
+    /// ```
+    pub fn set_tick_interval(mut self, tick_interval: Duration) -> ActorBuilder<A> {
+        self.tick_interval = Some(tick_interval);
+        self
+    }
+
+    /// Counts every handler invocation that takes longer than `threshold` and hands it to [ActorSystem.set_slow_handler_hook](../prelude/struct.ActorSystem.html#method.set_slow_handler_hook)
+    ///
+    /// The handler time is measured anyway, Actors without a threshold skip the comparison. See [ActorStats.slow_handlers](../prelude/struct.ActorStats.html#structfield.slow_handlers)
+    pub fn set_slow_handler_threshold(mut self, threshold: Duration) -> ActorBuilder<A> {
+        self.slow_handler_threshold = Some(threshold);
+        self
+    }
+
+    /// Time to live of every message sent to the Actor, messages that have not been handled in time are skipped and reported as [expired dead letters](../prelude/enum.DeadLetterReason.html#variant.Expired)
+    ///
+    /// Does not apply to stop messages and to messages of the priority lane, [ActorWrapper.send_with_ttl](../prelude/struct.ActorWrapper.html#method.send_with_ttl) takes precedence.
+    /// A time to live of zero keeps all messages, messages are only timestamped if the time to live is set
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Update(u32);
+    /// impl ActorMessage for Update {}
+    ///
+    /// struct Display {
+    ///     shown: Arc<Mutex<Vec<u32>>>,
+    /// }
+    /// impl Actor for Display {}
+    /// impl Handler<Update> for Display {
+    ///     fn handle(&mut self, msg: Update, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(50));
+    ///         self.shown.lock().unwrap().push(msg.0);
+    ///     }
+    /// }
+    /// struct DisplayFactory {
+    ///     shown: Arc<Mutex<Vec<u32>>>,
+    /// }
+    /// impl ActorFactory<Display> for DisplayFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Display>) -> Display {
+    ///         Display { shown: self.shown.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let shown = Arc::new(Mutex::new(Vec::new()));
+    /// let display = actor_system
+    ///     .builder()
+    ///     .set_message_ttl(Duration::from_millis(120))
+    ///     .spawn("display", DisplayFactory { shown: shown.clone() })
+    ///     .unwrap();
+    /// for i in 0..10 {
+    ///     display.send(Update(i)).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(500));
+    ///
+    /// // every update that has been handled was handled in order, the late ones expired
+    /// let shown = shown.lock().unwrap().clone();
+    /// assert!(shown.len() >= 2 && shown.len() < 10);
+    /// assert_eq!((0..shown.len() as u32).collect::<Vec<u32>>(), shown);
+    /// assert_eq!(10 - shown.len(), display.expired_count());
+    /// ```
+    pub fn set_message_ttl(mut self, message_ttl: Duration) -> ActorBuilder<A> {
+        self.message_ttl = Some(message_ttl).filter(|message_ttl| !message_ttl.is_zero());
+        self
+    }
+
+    /// Allows the system to shed the load of the Actor under memory pressure, see `general.memory_pressure`
+    ///
+    /// While the system is [Degraded](../prelude/enum.MemoryMode.html#variant.Degraded) messages sent to the Actor are dropped.
+    /// While it is [Critical](../prelude/enum.MemoryMode.html#variant.Critical) the Actor additionally stops handling its queued messages.
+    /// [SystemMemoryPressure](../prelude/struct.SystemMemoryPressure.html) and stop messages are never dropped
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Worker {
+    ///     handled: Arc<AtomicUsize>,
+    ///     log: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl Actor for Worker {}
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::SeqCst);
+    ///         self.log.lock().unwrap().push(String::from("work"));
+    ///     }
+    /// }
+    /// impl Handler<SystemMemoryPressure> for Worker {
+    ///     fn handle(&mut self, msg: SystemMemoryPressure, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("{:?}", msg.mode));
+    ///     }
+    /// }
+    ///
+    /// struct WorkerFactory {
+    ///     handled: Arc<AtomicUsize>,
+    ///     log: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { handled: self.handled.clone(), log: self.log.clone() }
+    ///     }
+    /// }
+    /// fn factory() -> (WorkerFactory, Arc<AtomicUsize>, Arc<Mutex<Vec<String>>>) {
+    ///     let handled = Arc::new(AtomicUsize::new(0));
+    ///     let log = Arc::new(Mutex::new(Vec::new()));
+    ///     (WorkerFactory { handled: handled.clone(), log: log.clone() }, handled, log)
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.memory_pressure = Some(MemoryPressureConfig::new(0.8, 0.9, 0.7, Duration::from_millis(10)));
+    /// let actor_system = ActorSystem::new(config);
+    /// let used = Arc::new(AtomicU64::new(500));
+    /// let source = used.clone();
+    /// actor_system.set_memory_source(move || Some(MemorySample { used: source.load(Ordering::SeqCst), limit: 1000 }));
+    /// let set_usage = |usage: u64| {
+    ///     used.store(usage, Ordering::SeqCst);
+    ///     sleep(Duration::from_millis(200));
+    /// };
+    ///
+    /// let (cache_factory, _, cache_log) = factory();
+    /// let cache = actor_system.builder().set_sheddable(true).spawn("cache", cache_factory).unwrap();
+    /// actor_system.get_event_bus().subscribe_pattern::<SystemMemoryPressure, _>(MEMORY_PRESSURE_TOPIC, &cache).unwrap();
+    /// let (sheddable_factory, sheddable_handled, _) = factory();
+    /// let sheddable = actor_system.builder().set_sheddable(true).spawn("sheddable", sheddable_factory).unwrap();
+    /// let (important_factory, important_handled, _) = factory();
+    /// let important = actor_system.builder().spawn("important", important_factory).unwrap();
+    /// // queued while the memory is fine, handled once it recovered
+    /// let (paused_factory, paused_handled, _) = factory();
+    /// let paused = actor_system.builder().set_sheddable(true).start_suspended(true).spawn("paused", paused_factory).unwrap();
+    /// paused.send(Work {});
+    /// let send_all = || {
+    ///     for actor in [&cache, &sheddable, &important].iter() {
+    ///         actor.send(Work {});
+    ///     }
+    /// };
+    ///
+    /// set_usage(600);
+    /// send_all();
+    /// set_usage(850);
+    /// assert_eq!(MemoryMode::Degraded, actor_system.memory_status().mode);
+    /// send_all();
+    /// set_usage(950);
+    /// assert_eq!(MemoryMode::Critical, actor_system.memory_status().mode);
+    /// send_all();
+    /// paused.release();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(0, paused_handled.load(Ordering::SeqCst));
+    /// // hysteresis, still above the recovery fraction
+    /// set_usage(750);
+    /// assert_eq!(MemoryMode::Degraded, actor_system.memory_status().mode);
+    /// assert_eq!(1, paused_handled.load(Ordering::SeqCst));
+    /// set_usage(900);
+    /// set_usage(500);
+    /// assert_eq!(MemoryMode::Normal, actor_system.memory_status().mode);
+    /// send_all();
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// assert_eq!(5, actor_system.memory_status().transitions);
+    /// assert_eq!(vec!["work", "Degraded", "Critical", "Degraded", "Critical", "Normal", "work"], *cache_log.lock().unwrap());
+    /// assert_eq!(2, sheddable_handled.load(Ordering::SeqCst));
+    /// assert_eq!(2, sheddable.shed_count());
+    /// assert_eq!(4, important_handled.load(Ordering::SeqCst));
+    /// assert_eq!(0, important.shed_count());
+    /// ```
+    pub fn set_sheddable(mut self, sheddable: bool) -> ActorBuilder<A> {
+        self.sheddable = sheddable;
+        self
+    }
+
+    /// Declares the protocol version the Actor speaks, [SerializedMessage](../prelude/struct.SerializedMessage.html)s with a version are checked against it
+    ///
+    /// The Actor accepts only its own version unless [accepts_versions](#method.accepts_versions) is set.
+    /// Older accepted versions are converted through the upgrades registered with [add_protocol_upgrade](#method.add_protocol_upgrade) before they reach [Actor.handle_serialized_message](../prelude/trait.Actor.html#method.handle_serialized_message).
+    /// The protocols of all running Actors are listed by [ActorSystem.protocol_table](../prelude/struct.ActorSystem.html#method.protocol_table), a respawned Actor replaces its entry
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Account {
+    ///     received: Arc<Mutex<Vec<(Option<u32>, Vec<u8>)>>>,
+    /// }
+    /// impl Actor for Account {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.received.lock().unwrap().push((msg.version, msg.content));
+    ///     }
+    /// }
+    /// struct AccountFactory {
+    ///     received: Arc<Mutex<Vec<(Option<u32>, Vec<u8>)>>>,
+    /// }
+    /// impl ActorFactory<Account> for AccountFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Account>) -> Account {
+    ///         Account { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let account = actor_system
+    ///     .builder()
+    ///     .protocol_version(3)
+    ///     .accepts_versions(1..=3)
+    ///     .add_protocol_upgrade(1, |mut content: Vec<u8>| { content.push(2); content })
+    ///     .add_protocol_upgrade(2, |mut content: Vec<u8>| { content.push(3); content })
+    ///     .spawn("account", AccountFactory { received: received.clone() })
+    ///     .unwrap();
+    /// let address = account.get_address();
+    /// assert_eq!(Some(ActorProtocol::new(3, 1..=3)), actor_system.get_protocol(address));
+    ///
+    /// // unsupported versions are rejected before they reach the Actor
+    /// let result = actor_system.try_send_to_address(address, SerializedMessage::new(vec![4]).with_version(4));
+    /// assert_eq!(Err(ProtocolError::UnsupportedVersion { version: 4, min: 1, max: 3 }), result);
+    ///
+    /// // older versions are upgraded step by step, messages without a version are passed on unchanged
+    /// actor_system.try_send_to_address(address, SerializedMessage::new(vec![1]).with_version(1)).unwrap();
+    /// actor_system.try_send_to_address(address, SerializedMessage::new(vec![3]).with_version(3)).unwrap();
+    /// actor_system.try_send_to_address(address, SerializedMessage::new(vec![0])).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(
+    ///     vec![(Some(3), vec![1, 2, 3]), (Some(3), vec![3]), (None, vec![0])],
+    ///     *received.lock().unwrap()
+    /// );
+    ///
+    /// // a peer checks its sends against the protocol table of this system
+    /// let remote = RemoteProtocols::new(actor_system.protocol_table());
+    /// assert_eq!(Ok(()), remote.check(address, 2));
+    /// assert_eq!(Err(ProtocolError::UnsupportedVersion { version: 0, min: 1, max: 3 }), remote.check(address, 0));
+    /// ```
+    pub fn protocol_version(mut self, version: u32) -> ActorBuilder<A> {
+        self.protocol_version = Some(version);
+        self
+    }
+
+    /// Sets the versions the Actor accepts, defaults to the [protocol_version](#method.protocol_version), which in turn defaults to the end of the range
+    pub fn accepts_versions(mut self, versions: RangeInclusive<u32>) -> ActorBuilder<A> {
+        self.accepted_versions = Some(versions);
+        self
+    }
+
+    /// Converts the content of a [SerializedMessage](../prelude/struct.SerializedMessage.html) from version `from` to `from + 1`
+    pub fn add_protocol_upgrade<F>(mut self, from: u32, upgrade: F) -> ActorBuilder<A>
+    where
+        F: Fn(Vec<u8>) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.protocol_upgrades.insert(from, Arc::new(upgrade));
+        self
+    }
+
+    /// Deserializes [SerializedMessage](../prelude/struct.SerializedMessage.html)s with the type tag and sends them to the [Handler] of `M`, see [ActorSystem.send_to_address](../prelude/struct.ActorSystem.html#method.send_to_address)
+    ///
+    /// Messages without a type tag are handed to [Actor.handle_serialized_message](../prelude/trait.Actor.html#method.handle_serialized_message) as before, messages with an unknown one to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message).
+    /// Content that can not be deserialized is handed to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message) as well and counted through [ActorWrapper.undecodable_count](../prelude/struct.ActorWrapper.html#method.undecodable_count),
+    /// [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address) fails with [ProtocolError::Undecodable](../prelude/enum.ProtocolError.html#variant.Undecodable).
+    /// Headers of typed serialized messages are not attached to the message.
+    /// The deserializer runs on the thread that calls `send_to_address`, after the content has been upgraded to the protocol version of the Actor
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// struct Order { id: u64, note: String }
+    /// impl ActorMessage for Order {}
+    ///
+    /// const ORDER: u16 = 1;
+    ///
+    /// struct Shop { orders: Arc<Mutex<Vec<Order>>>, raw: Arc<Mutex<Vec<Vec<u8>>>> }
+    /// impl Actor for Shop {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.raw.lock().unwrap().push(msg.content);
+    ///     }
+    /// }
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, msg: Order, _context: &ActorContext<Self>) {
+    ///         self.orders.lock().unwrap().push(msg);
+    ///     }
+    /// }
+    /// struct ShopFactory { orders: Arc<Mutex<Vec<Order>>>, raw: Arc<Mutex<Vec<Vec<u8>>>> }
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+    ///         Shop { orders: self.orders.clone(), raw: self.raw.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let orders = Arc::new(Mutex::new(Vec::new()));
+    /// let raw = Arc::new(Mutex::new(Vec::new()));
+    /// let shop = actor_system
+    ///     .builder()
+    ///     .register_serialized(ORDER, |content: &[u8]| {
+    ///         serde_json::from_slice::<Order>(content).map_err(|error| error.to_string())
+    ///     })
+    ///     .spawn("shop", ShopFactory { orders: orders.clone(), raw: raw.clone() })
+    ///     .unwrap();
+    ///
+    /// let order = Order { id: 7, note: String::from("gift") };
+    /// let content = serde_json::to_vec(&order).unwrap();
+    /// actor_system.send_to_address(shop.get_address(), SerializedMessage::new(content.clone()).with_type_tag(ORDER));
+    /// // untagged messages fall through to handle_serialized_message, unknown tags are unhandled
+    /// actor_system.send_to_address(shop.get_address(), SerializedMessage::new(content.clone()));
+    /// actor_system.send_to_address(shop.get_address(), SerializedMessage::new(content).with_type_tag(2));
+    /// let broken = SerializedMessage::new(b"{".to_vec()).with_type_tag(ORDER);
+    /// let result = actor_system.try_send_to_address(shop.get_address(), broken);
+    /// assert_eq!(Err(ProtocolError::Undecodable { type_tag: ORDER }), result);
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// assert_eq!(vec![order], *orders.lock().unwrap());
+    /// assert_eq!(1, raw.lock().unwrap().len());
+    /// assert_eq!(1, shop.undecodable_count());
+    /// assert_eq!(2, shop.unhandled_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn register_serialized<M, F>(mut self, type_tag: u16, deserializer: F) -> ActorBuilder<A>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        F: Fn(&[u8]) -> Result<M, String> + Send + Sync + 'static,
+    {
+        let decoder: Decoder<A> = Arc::new(move |wrapper, content| {
+            let msg = deserializer(content)?;
+            // sends to a stopped Actor are reported as dead letters
+            let _ = wrapper.send(msg);
+            Ok(())
+        });
+        self.serialized.insert(type_tag, decoder);
         self
     }
 
+    fn get_protocol(&self) -> Option<ActorProtocol> {
+        match (self.protocol_version, self.accepted_versions.clone()) {
+            (None, None) => None,
+            (Some(version), None) => Some(ActorProtocol::new(version, version..=version)),
+            (None, Some(accepts)) => Some(ActorProtocol::new(*accepts.end(), accepts)),
+            (Some(version), Some(accepts)) => Some(ActorProtocol::new(version, accepts)),
+        }
+    }
+
     pub fn set_mailbox_unbounded(self) -> ActorBuilder<A> {
         self.set_mailbox_size(0)
     }
 
+    /// Capacity of the mailbox, `0` is unbounded, overrides `general.default_mailbox_size` and the mailbox size of the pool
+    ///
+    /// Sends to a full mailbox wait for room, see [ActorWrapper.try_send](../prelude/struct.ActorWrapper.html#method.try_send) and [ActorWrapper.send_timeout](../prelude/struct.ActorWrapper.html#method.send_timeout)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Pong {}
+    /// impl Actor for Pong {}
+    /// impl Handler<Ping> for Pong {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct PongFactory {}
+    /// impl ActorFactory<Pong> for PongFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Pong>) -> Pong {
+    ///         Pong {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// // the Actor does not receive anything until it is released
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .start_suspended(true)
+    ///     .spawn("pong", PongFactory {})
+    ///     .unwrap();
+    /// assert!(actor.try_send(Ping {}).is_ok());
+    /// assert!(matches!(actor.try_send(Ping {}), Err(SendError::Full(_))));
+    /// assert!(matches!(actor.send_timeout(Ping {}, Duration::from_millis(50)), Err(SendError::Timeout(_))));
+    ///
+    /// assert!(actor.release());
+    /// assert!(actor.send_timeout(Ping {}, Duration::from_secs(3)).is_ok());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
     pub fn set_mailbox_size(mut self, mailbox_size: usize) -> ActorBuilder<A> {
-        self.actor_config.mailbox_size = mailbox_size;
+        self.overrides.mailbox_size = Some(mailbox_size);
         self
     }
 
+    /// Returns the config the next spawned Actor will use, together with the origin of each setting
+    ///
+    /// Settings are resolved in the order `general.default_*` < [ActorDefaults](../prelude/struct.ActorDefaults.html) of the pool < settings of this builder.
+    /// The pool is resolved when the Actor is spawned, so the order of [set_pool_name](#method.set_pool_name) and the other setters does not matter
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Fragile { handled: Arc<AtomicUsize> }
+    /// impl Actor for Fragile {}
+    /// impl Handler<Crash> for Fragile {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::Relaxed);
+    ///         panic!("crash");
+    ///     }
+    /// }
+    /// struct FragileFactory { handled: Arc<AtomicUsize> }
+    /// impl ActorFactory<Fragile> for FragileFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Fragile>) -> Fragile {
+    ///         Fragile { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let io = ThreadPoolConfig::new(0, 1, 2, 1.0).with_actor_defaults(ActorDefaults {
+    ///     mailbox_size: Some(0),
+    ///     message_throughput: Some(100),
+    ///     ..ActorDefaults::default()
+    /// });
+    /// let control = ThreadPoolConfig::new(0, 1, 2, 1.0).with_actor_defaults(ActorDefaults {
+    ///     mailbox_size: Some(4),
+    ///     restart_policy: Some(RestartPolicy::Never),
+    ///     ..ActorDefaults::default()
+    /// });
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.default_restart_policy = RestartPolicy::Always;
+    /// config.thread_pool.config.insert(String::from("io"), io);
+    /// config.thread_pool.config.insert(String::from("control"), control);
+    /// let actor_system = ActorSystem::new(config);
+    ///
+    /// let io_builder = actor_system.builder::<Fragile>().set_pool_name("io").unwrap();
+    /// let resolved = io_builder.get_resolved_config();
+    /// assert_eq!(0, resolved.config.mailbox_size);
+    /// assert_eq!(ConfigSource::Pool, resolved.sources.mailbox_size);
+    /// assert_eq!(ConfigSource::Global, resolved.sources.restart_policy);
+    ///
+    /// // the pool is selected after the other settings
+    /// let control_builder = actor_system.builder::<Fragile>().set_message_throughput(1).set_pool_name("control").unwrap();
+    /// let resolved = control_builder.get_resolved_config();
+    /// assert_eq!(4, resolved.config.mailbox_size);
+    /// assert_eq!(RestartPolicy::Never, resolved.config.restart_policy);
+    /// assert_eq!(ConfigSource::Pool, resolved.sources.restart_policy);
+    /// assert_eq!((1, ConfigSource::Builder), (resolved.config.message_throughput, resolved.sources.message_throughput));
+    ///
+    /// // explicit settings beat the pool defaults
+    /// let overridden = actor_system.builder::<Fragile>().set_pool_name("control").unwrap().set_mailbox_size(8);
+    /// assert_eq!(8, overridden.get_resolved_config().config.mailbox_size);
+    ///
+    /// let io_handled = Arc::new(AtomicUsize::new(0));
+    /// let control_handled = Arc::new(AtomicUsize::new(0));
+    /// let io_actor = io_builder.spawn("io", FragileFactory { handled: io_handled.clone() }).unwrap();
+    /// let control_actor = control_builder.spawn("control", FragileFactory { handled: control_handled.clone() }).unwrap();
+    /// for _ in 0..3 {
+    ///     io_actor.send(Crash {});
+    ///     control_actor.send(Crash {});
+    ///     sleep(Duration::from_millis(50));
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// // the io Actor is restarted after every panic, the control Actor stops after the first one
+    /// assert_eq!(3, io_handled.load(Ordering::Relaxed));
+    /// assert_eq!(1, control_handled.load(Ordering::Relaxed));
+    /// ```
+    pub fn get_resolved_config(&self) -> ResolvedActorConfig {
+        self.resolve_config(&self.overrides)
+    }
+
+    fn resolve_config(&self, overrides: &ActorDefaults) -> ResolvedActorConfig {
+        let general = &self.system.get_config().general;
+        let pool = self.system.get_pool_actor_defaults(&self.pool_name).unwrap_or_default();
+        let (mailbox_size, mailbox_size_source) =
+            actor_config::resolve(general.default_mailbox_size, pool.mailbox_size, overrides.mailbox_size);
+        let (message_throughput, message_throughput_source) = actor_config::resolve(
+            general.default_message_throughput,
+            pool.message_throughput,
+            overrides.message_throughput,
+        );
+        let (restart_policy, restart_policy_source) =
+            actor_config::resolve(general.default_restart_policy, pool.restart_policy, overrides.restart_policy);
+        let (max_suspension, max_suspension_source) =
+            actor_config::resolve(general.default_max_suspension, pool.max_suspension, overrides.max_suspension);
+        let (priority, priority_source) =
+            actor_config::resolve(general.default_priority, pool.priority, overrides.priority);
+        ResolvedActorConfig {
+            config: ActorConfig {
+                pool_name: self.pool_name.clone(),
+                mailbox_size,
+                message_throughput,
+                adaptive_throughput: self.adaptive_throughput,
+                restart_policy,
+                max_suspension,
+                priority,
+                capture_last_n_messages: self.capture_last_n_messages,
+                latency_slo: self.latency_slo,
+                watermarks: self.watermarks,
+                start_suspended: self.start_suspended,
+                sheddable: self.sheddable,
+                protocol: self.get_protocol(),
+                journal_capacity: self.journal_capacity,
+                rng_reseed: self.rng_reseed,
+                cycle_exempt: self.cycle_exempt,
+                state_capture_capacity: self.state_capture_capacity,
+                snapshot_format: self.snapshot_format,
+                capture_around: self.capture_around.clone(),
+                max_message_bytes: self.max_message_bytes.or(general.max_message_bytes),
+                snapshot_interval: self.snapshot_interval,
+                recover_after_stop: self.recover_after_stop,
+                dedicated_thread: self.dedicated_thread,
+                lazy: self.lazy,
+                overflow_policy: self.overflow_policy,
+                overflow_dead_letters: self.overflow_dead_letters,
+                max_mailbox_bytes: self.max_mailbox_bytes,
+                failed_message_strategy: self.failed_message_strategy,
+                restart_mailbox_policy: self.restart_mailbox_policy,
+                rate_limit: self.rate_limit,
+                stash_capacity: self.stash_capacity,
+                shutdown_phase: self.shutdown_phase,
+                system_stop_order: self.system_stop_order,
+                idle_timeout: self.idle_timeout,
+                message_ttl: self.message_ttl,
+                daemon: self.daemon,
+                tick_interval: self.tick_interval,
+                strict_messages: self.strict_messages,
+                exempt_from_shedding: self.exempt_from_shedding,
+                slow_handler_threshold: self.slow_handler_threshold,
+                panic_escalation: self.panic_escalation.unwrap_or(general.default_panic_escalation),
+            },
+            sources: ActorConfigSources {
+                mailbox_size: mailbox_size_source,
+                message_throughput: message_throughput_source,
+                restart_policy: restart_policy_source,
+                max_suspension: max_suspension_source,
+                priority: priority_source,
+            },
+        }
+    }
+
     /// Creates the defined [Actor] on the [ActorSystem]
     ///
     /// # Returns
@@ -92,65 +3187,515 @@ impl<A> ActorBuilder<A>
     ///
     /// `None` if actor is running on the system AND actor was not created by the same builder or a clone of it
     ///
+    /// `None` if another spawn of the same address is still running, only one of two concurrent spawns of the same address creates the Actor
+    ///
+    /// `None` once [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) has been called, use [try_spawn](#method.try_spawn) to tell the cases apart
+    ///
+    /// `None` if the [TryActorFactory](../prelude/trait.TryActorFactory.html) failed, [try_spawn](#method.try_spawn) returns its error
+    ///
     pub fn spawn<P>(&self, name: impl Into<String>, props: P) -> Option<ActorWrapper<A>>
     where
-        P: ActorFactory<A> + 'static,
+        P: TryActorFactory<A> + 'static,
+    {
+        self.spawn_or_existing(name, props, self.get_resolved_config().config).ok()
+    }
+
+    /// Same as [spawn](#method.spawn), but the Actor is created by a closure instead of an [ActorFactory](../prelude/trait.ActorFactory.html)
+    ///
+    /// The closure is kept for the lifetime of the Actor and called again with every restart. It has to be `Send` and `Sync`,
+    /// because it is called on whichever thread executes the Actor, and `UnwindSafe`, because it is called again after a panic,
+    /// so that state it captures has to stay consistent across an unwind, e.g. behind a `Mutex` or an atomic.
+    /// Any closure with these bounds is an [ActorFactory](../prelude/trait.ActorFactory.html) as well, `spawn_fn` only saves naming the type of its argument
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work { fail: bool }
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Worker {
+    ///     done: Arc<Mutex<Vec<usize>>>,
+    ///     generation: usize,
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+    ///         if msg.fail {
+    ///             panic!("work failed");
+    ///         }
+    ///         self.done.lock().unwrap().push(self.generation);
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let created = Arc::new(AtomicUsize::new(0));
+    /// let done = Arc::new(Mutex::new(Vec::new()));
+    /// let (counter, log) = (created.clone(), done.clone());
+    /// let worker = actor_system
+    ///     .builder()
+    ///     .set_restart_policy(RestartPolicy::Always)
+    ///     .spawn_fn("worker", move |context| Worker {
+    ///         done: log.clone(),
+    ///         generation: counter.fetch_add(1, Ordering::SeqCst),
+    ///         context,
+    ///     })
+    ///     .unwrap();
+    ///
+    /// // the restart calls the closure again
+    /// worker.send(Work { fail: false }).unwrap();
+    /// worker.send(Work { fail: true }).unwrap();
+    /// worker.send(Work { fail: false }).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(2, created.load(Ordering::SeqCst));
+    /// assert_eq!(vec![0, 1], *done.lock().unwrap());
+    ///
+    /// // closures can be passed to every spawn, if the type of their argument is named
+    /// let log = done.clone();
+    /// let other = actor_system
+    ///     .builder()
+    ///     .spawn("other", move |context: ActorContext<Worker>| Worker { done: log.clone(), generation: 7, context })
+    ///     .unwrap();
+    /// other.send(Work { fail: false }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![0, 1, 7], *done.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// ```
+    ///
+    /// Closures that capture state which is not `UnwindSafe` are rejected:
+    ///
+    /// ```rust,compile_fail
+    /// use tyractorsaur::prelude::*;
+    /// use std::cell::UnsafeCell;
+    /// use std::sync::Arc;
+    ///
+    /// struct Shared(UnsafeCell<usize>);
+    /// unsafe impl Sync for Shared {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let shared = Arc::new(Shared(UnsafeCell::new(0)));
+    /// actor_system.builder().spawn_fn("worker", move |_context| {
+    ///     let _shared = &shared;
+    ///     Worker {}
+    /// });
+    /// ```
+    pub fn spawn_fn<F>(&self, name: impl Into<String>, factory: F) -> Option<ActorWrapper<A>>
+    where
+        F: Fn(ActorContext<A>) -> A + Send + Sync + UnwindSafe + 'static,
+    {
+        self.spawn(name, factory)
+    }
+
+    /// Same as [spawn](#method.spawn), but fails with [SpawnError::NameTaken](../prelude/enum.SpawnError.html#variant.NameTaken) if an Actor with the same address is running or being spawned, regardless of the builder that created it
+    ///
+    /// Fails with [SpawnError::SystemStopping](../prelude/enum.SpawnError.html#variant.SystemStopping) once the shutdown phase of the Actor completed, see [set_shutdown_phase](#method.set_shutdown_phase),
+    /// and with [SpawnError::FactoryFailed](../prelude/enum.SpawnError.html#variant.FactoryFailed) if the [TryActorFactory](../prelude/trait.TryActorFactory.html) failed
+    ///
+    /// The name is free again once the Actor has stopped
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Barrier};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// #[derive(Clone)]
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         // widens the window between the check of the name and the registration of the Actor
+    ///         sleep(Duration::from_millis(20));
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let barrier = Arc::new(Barrier::new(2));
+    /// let racers: Vec<_> = (0..2)
+    ///     .map(|_| {
+    ///         let builder = actor_system.builder();
+    ///         let barrier = barrier.clone();
+    ///         std::thread::spawn(move || {
+    ///             barrier.wait();
+    ///             builder.try_spawn("worker", WorkerFactory {})
+    ///         })
+    ///     })
+    ///     .collect();
+    /// let results: Vec<_> = racers.into_iter().map(|racer| racer.join().unwrap()).collect();
+    /// assert_eq!(1, results.iter().filter(|result| result.is_ok()).count());
+    /// assert!(results.iter().any(|result| matches!(result, Err(SpawnError::NameTaken))));
+    ///
+    /// // the same builder does not hand out the running Actor either
+    /// let builder = actor_system.builder();
+    /// assert!(matches!(builder.try_spawn("worker", WorkerFactory {}), Err(SpawnError::NameTaken)));
+    ///
+    /// let worker = results.into_iter().find_map(|result| result.ok()).unwrap();
+    /// worker.stop();
+    /// sleep(Duration::from_millis(200));
+    /// let respawned = builder.try_spawn("worker", WorkerFactory {}).unwrap();
+    /// assert_eq!(worker.get_address(), respawned.get_address());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn try_spawn<P>(&self, name: impl Into<String>, props: P) -> Result<ActorWrapper<A>, SpawnError>
+    where
+        P: TryActorFactory<A> + 'static,
+    {
+        self.spawn_with_config(name, props, self.get_resolved_config().config)
+            .map_err(|rejected| match rejected {
+                Rejected::Taken(_) => SpawnError::NameTaken,
+                Rejected::SystemStopping => SpawnError::SystemStopping,
+                Rejected::UnknownPool => SpawnError::UnknownPool,
+                Rejected::FactoryFailed(error) => SpawnError::FactoryFailed(error),
+                Rejected::DependencyCycle(cycle) => SpawnError::DependencyCycle(cycle),
+            })
+    }
+
+    /// Same as [ActorSystem.entity_region](../prelude/struct.ActorSystem.html#method.entity_region), but every entity is spawned with the settings of this builder
+    ///
+    /// Combined with [set_idle_timeout](#method.set_idle_timeout), idle entities stop and are spawned again by their next message
+    pub fn entity_region<F>(self, name: impl Into<String>, factory_fn: F) -> EntityRegion<A>
+    where
+        F: Fn(&str, ActorContext<A>) -> A + Send + Sync + 'static,
+    {
+        EntityRegion::new(self.system.clone(), self, name.into(), factory_fn)
+    }
+
+    /// Same as [spawn](#method.spawn), but the settings of `overrides` that are set replace the ones of this builder
+    pub(crate) fn spawn_with_overrides<P>(&self, name: impl Into<String>, props: P, overrides: ActorDefaults) -> Result<ActorWrapper<A>, SpawnError>
+    where
+        P: TryActorFactory<A> + 'static,
+    {
+        let overrides = ActorDefaults {
+            mailbox_size: overrides.mailbox_size.or(self.overrides.mailbox_size),
+            message_throughput: overrides.message_throughput.or(self.overrides.message_throughput),
+            restart_policy: overrides.restart_policy.or(self.overrides.restart_policy),
+            max_suspension: overrides.max_suspension.or(self.overrides.max_suspension),
+            priority: overrides.priority.or(self.overrides.priority),
+        };
+        self.spawn_or_existing(name, props, self.resolve_config(&overrides).config)
+    }
+
+    /// Hands out the running Actor if the address is taken by an Actor of this builder
+    fn spawn_or_existing<P>(&self, name: impl Into<String>, props: P, actor_config: ActorConfig) -> Result<ActorWrapper<A>, SpawnError>
+    where
+        P: TryActorFactory<A> + 'static,
+    {
+        match self.spawn_with_config(name, props, actor_config) {
+            Ok(actor) => Ok(actor),
+            Err(Rejected::SystemStopping) => Err(SpawnError::SystemStopping),
+            Err(Rejected::UnknownPool) => Err(SpawnError::UnknownPool),
+            Err(Rejected::FactoryFailed(error)) => Err(SpawnError::FactoryFailed(error)),
+            Err(Rejected::DependencyCycle(cycle)) => Err(SpawnError::DependencyCycle(cycle)),
+            Err(Rejected::Taken(address)) => {
+                if !self.system_state.is_actor_active(&address) {
+                    return Err(SpawnError::NameTaken);
+                }
+                self.existing
+                    .get(&address)
+                    .map(|existing| existing.value().clone())
+                    .ok_or(SpawnError::NameTaken)
+            }
+        }
+    }
+
+    fn spawn_with_config<P>(&self, name: impl Into<String>, props: P, mut actor_config: ActorConfig) -> Result<ActorWrapper<A>, Rejected>
+    where
+        P: TryActorFactory<A> + 'static,
     {
+        // the pool may have been set through a handle of another system
+        if self.system.get_pool(&actor_config.pool_name).is_none() {
+            return Err(Rejected::UnknownPool);
+        }
         let actor_address = ActorAddress {
             actor: name.into(),
             system: String::from(self.system.get_name()),
-            pool: self.actor_config.pool_name.clone(),
-            remote: String::from("local"),
+            pool: actor_config.pool_name.clone(),
+            remote: self.system.get_local_remote(),
         };
 
-        if self.system_state.is_actor_active(&actor_address) {
-            if !self.existing.contains_key(&actor_address) {
-                return None
-            }
-            let to_return = self.existing.get(&actor_address).unwrap().value().clone();
-            return Some(to_return)
+        // held until the Actor has been added
+        let _reservation = match self.system_state.reserve_address(&actor_address) {
+            Some(reservation) => reservation,
+            None => return Err(Rejected::Taken(actor_address)),
+        };
+        // the shutdown waits for the Actor to be added, so that it is either rejected here or stopped like all others
+        let registration = match self.system_state.begin_registration(actor_config.shutdown_phase) {
+            Some(registration) => registration,
+            None => return Err(Rejected::SystemStopping),
+        };
+        let startup_order = self.system_state.get_startup_order();
+        let has_dependencies = !self.dependencies.is_empty();
+        if has_dependencies {
+            startup_order
+                .declare(&actor_address, &self.dependencies)
+                .map_err(Rejected::DependencyCycle)?;
+        }
+
+        // systems that do not execute Actors on threads execute it like any other Actor of its pool
+        if actor_config.dedicated_thread {
+            actor_config.dedicated_thread = self.system.get_thread_pool_manager().add_dedicated(&actor_address);
         }
+        // an earlier Actor at the same address might have been moved to another pool
+        self.system.get_thread_pool_manager().remove_actor_pool(&actor_address);
+        let is_dedicated = actor_config.dedicated_thread;
+        let is_lazy = actor_config.lazy;
+        let is_suspended = actor_config.start_suspended;
+        // an Actor with dependencies is held back like a suspended one
+        let is_held = is_suspended || has_dependencies;
 
-        let (sender, receiver) = if self.actor_config.mailbox_size == 0 {
+        let (sender, receiver) = if actor_config.mailbox_size == 0 {
             unbounded()
         } else {
-            bounded(self.actor_config.mailbox_size)
+            bounded(actor_config.mailbox_size)
         };
 
+        let (urgent_in, urgent_out) = unbounded();
+        let (continuation_in, continuation_out) = if actor_config.mailbox_size == 0 {
+            unbounded()
+        } else {
+            bounded(actor_config.mailbox_size)
+        };
         let mailbox = Mailbox {
-            is_stopped: Arc::new(AtomicBool::new(false)),
-            is_sleeping: Arc::new(AtomicBool::new(true)),
+            flags: Arc::new(MailboxFlags {
+                is_stopped: AtomicBool::new(false),
+                stop_request: AtomicU8::new(STOP_NONE),
+                // sends must not wake up an Actor that awaits its release, only the release itself does
+                is_sleeping: AtomicBool::new(!is_held),
+                is_awaiting_release: AtomicBool::new(is_held),
+                // a lazy Actor is only created by its first message, it does not hold anyone up
+                is_ready: AtomicBool::new(is_lazy),
+                is_redirected: AtomicBool::new(false),
+            }),
+            is_system_stopped: self.system_state.get_stopped_flag(),
             msg_in: sender,
+            msg_out: receiver.clone(),
+            urgent_in,
+            urgent_out,
+            continuation_in,
+            continuation_out,
+            overflow_policy: actor_config.overflow_policy,
+            overflow_dead_letters: actor_config.overflow_dead_letters,
+            bytes: actor_config.max_mailbox_bytes.map(|max_bytes| Arc::new(MailboxBytes::new(max_bytes))),
+            handoff: Arc::new(Mutex::new(HandoffState::new())),
+            priority: Arc::new(PriorityState::new(actor_config.priority)),
+            slo_status: actor_config.latency_slo.map(|_| Arc::new(Mutex::new(SloStatus::default()))),
+            pressure: actor_config
+                .watermarks
+                .map(|watermarks| Arc::new(PressureState::new(actor_address.clone(), watermarks))),
+            memory_pressure: if actor_config.sheddable {
+                Some(self.system_state.get_memory_pressure().clone())
+            } else {
+                None
+            },
+            final_state: Arc::new(Mutex::new(FinalState::new())),
+            dead_letters: self.system_state.get_dead_letters().clone(),
+            instrumentation: self.system_state.get_instrumentation().clone(),
+            message_ttl: actor_config.message_ttl.filter(|message_ttl| !message_ttl.is_zero()),
+            actor_registry: self.system_state.get_actor_registry(),
+            emitter: Arc::new(Emitter::new()),
+            supersession: if self.superseding.is_empty() {
+                None
+            } else {
+                Some(Arc::new(Supersession::new(self.superseding.clone())))
+            },
+            batching: if self.batching.is_empty() {
+                None
+            } else {
+                Some(Arc::new(self.batching.clone()))
+            },
+            classes: if self.message_classes.is_empty() {
+                None
+            } else {
+                Some(Arc::new(ClassedQueues::new(&self.message_classes, actor_config.mailbox_size)))
+            },
+            middlewares: if self.middlewares.is_empty() {
+                None
+            } else {
+                Some(Arc::new(self.middlewares.clone()))
+            },
+            message_budget: match actor_config.exempt_from_shedding {
+                true => None,
+                false => self.system.get_message_budget().cloned(),
+            },
+            accepted_messages: Arc::new(MessageRegistry::new(&self.registered_messages, actor_config.strict_messages)),
+            retrying: if self.retrying.is_empty() {
+                None
+            } else {
+                Some(Arc::new(self.retrying.clone()))
+            },
+            recovery: self.recovery.clone().map(Arc::new),
+            debug_snapshot: self.debug_snapshot,
+            stash: Arc::new(Stash::new(actor_config.stash_capacity)),
+            #[cfg(feature = "testing")]
+            interactions: self.system.get_interaction_log().cloned(),
+            #[cfg(feature = "async")]
+            send_waiters: Arc::new(Default::default()),
+            effect_ledger: self.effect_ledger.clone().unwrap_or_else(|| {
+                let general = &self.system.get_config().general;
+                Arc::new(MemoryEffectLedger::new(general.effect_ledger_capacity, general.effect_ledger_ttl))
+            }),
+            journal: if actor_config.journal_capacity > 0 {
+                let journal = Journal::new(actor_address.clone(), actor_config.journal_capacity, self.journal_sink.clone());
+                Some(Arc::new(journal))
+            } else {
+                None
+            },
+            size_limit: self.system.get_message_sizes().limit(actor_config.max_message_bytes),
+            captures: if actor_config.state_capture_capacity > 0 {
+                Some(Arc::new(StateCaptures::new(
+                    actor_address.clone(),
+                    actor_config.snapshot_format,
+                    actor_config.state_capture_capacity,
+                    actor_config.capture_around.clone(),
+                )))
+            } else {
+                None
+            },
         };
 
+        let queue = mailbox.clone();
+        let actor_metrics = ActorMetrics::new(
+            Box::new(move || queue.len()),
+            mailbox.flags.clone(),
+            self.system.get_config().general.metrics_history,
+        );
+
         let actor_ref = ActorWrapper::new(
             mailbox.clone(),
             actor_address.clone(),
             self.wakeup_manager.clone(),
+            actor_metrics.clone(),
         );
 
-        let context = ActorContext {
-            system: self.system.clone(),
-            actor_ref: actor_ref.clone(),
+        let rng = self
+            .rng
+            .clone()
+            .unwrap_or_else(|| ActorRng::for_actor(self.system.get_config().general.rng_seed, &actor_address));
+        let context = ActorContext::new(actor_ref.clone(), self.system.clone(), rng);
+        let created = if is_dedicated || is_lazy {
+            None
+        } else {
+            let created = props.try_new_actor(context.clone()).map_err(|error| {
+                startup_order.forget(&actor_address);
+                Rejected::FactoryFailed(error)
+            });
+            Some(created?)
+        };
+        let protocol = actor_config.protocol.clone();
+        let serialized = if self.serialized.is_empty() {
+            None
+        } else {
+            Some(SerializedDispatch::new(actor_ref.clone(), self.serialized.clone(), actor_metrics.clone()))
         };
-        let actor = props.new_actor(context);
         let actor_handler = Executor::new(
+            created,
             props,
-            actor_address.clone(),
-            self.actor_config.clone(),
+            actor_config,
             mailbox.clone(),
             receiver,
-            self.system.clone(),
-            actor_ref.clone(),
+            context,
+            actor_metrics.clone(),
         );
 
-        self.system_state.add_actor(actor_address.clone(), Arc::new(actor));
-        self.wakeup_manager.add_sleeping_actor(
-            actor_handler.get_address(),
-            Arc::new(RwLock::new(actor_handler)),
+        if let Some(protocol) = protocol {
+            self.system_state
+                .add_protocol(actor_address.clone(), ProtocolEntry::new(protocol, self.protocol_upgrades.clone()));
+        }
+        let executor: Arc<RwLock<dyn ExecutorTrait>> = Arc::new(RwLock::new(actor_handler));
+        self.system_state.add_actor(
+            actor_address.clone(),
+            actor_ref.clone(),
+            Arc::downgrade(&executor),
+            serialized,
+            actor_metrics,
         );
+        // only the release of a suspended Actor is up to the user
+        if is_suspended {
+            let flags = mailbox.flags.clone();
+            let wakeup_manager = self.wakeup_manager.clone();
+            let address = actor_address.clone();
+            self.system_state.add_awaiting_release(
+                actor_address.clone(),
+                Box::new(move || {
+                    if flags.is_stopped.load(Ordering::SeqCst) || !flags.is_awaiting_release.swap(false, Ordering::SeqCst) {
+                        return false;
+                    }
+                    wakeup_manager.wakeup(address.clone());
+                    true
+                }),
+            );
+        }
+        self.wakeup_manager.add_spawned_actor(actor_address.clone(), executor, &registration);
+        // creates the Actor on its thread right away, Actors only get ready once they have been executed
+        if !mailbox.is_awaiting_release() && ((is_dedicated && !is_lazy) || startup_order.is_awaited(&actor_address)) {
+            self.wakeup_manager.wakeup(actor_address.clone());
+        }
+        if has_dependencies {
+            let (released, stopped) = (actor_ref.clone(), actor_ref.clone());
+            startup_order.hold(
+                &actor_address,
+                &self.dependencies,
+                |dependency| self.system_state.is_actor_ready(dependency),
+                self.dependency_failure_policy,
+                Box::new(move || {
+                    released.release();
+                }),
+                Box::new(move || {
+                    stopped.stop();
+                }),
+            );
+            // dependencies that are running already but have not been executed yet
+            for dependency in &self.dependencies {
+                if self.system_state.is_actor_active(dependency) && !self.system_state.is_actor_ready(dependency) {
+                    self.wakeup_manager.wakeup(dependency.clone());
+                }
+            }
+        }
 
         self.existing.insert(actor_address, actor_ref.clone());
-        Some(actor_ref)
+        Ok(actor_ref)
+    }
+
+    /// Spawns all `specs` with the settings of this builder, see [ActorSystem.spawn_many](../prelude/struct.ActorSystem.html#method.spawn_many)
+    pub fn spawn_many<P, I>(&self, specs: I, config: BulkSpawnConfig) -> BulkSpawnReport<A>
+    where
+        P: TryActorFactory<A> + Send + 'static,
+        I: IntoIterator,
+        I::Item: Into<SpawnSpec<P>>,
+    {
+        bulk_spawn::spawn_many(self, specs.into_iter().map(Into::into).collect(), config)
     }
 }
+
+/// Why [ActorBuilder.spawn_with_config](./struct.ActorBuilder.html#method.spawn_with_config) did not create the Actor
+enum Rejected {
+    /// an Actor with the address is running or being spawned
+    Taken(ActorAddress),
+    SystemStopping,
+    /// the pool has neither been added nor configured
+    UnknownPool,
+    FactoryFailed(ActorInitError),
+    DependencyCycle(Vec<ActorAddress>),
+}
@@ -1,4 +1,20 @@
+use crate::actor::pressure::Watermarks;
+use crate::actor::priority::ActorPriority;
+use crate::actor::protocol::ActorProtocol;
+use crate::actor::rate_limit::RateLimit;
+use crate::actor::rng::RngReseed;
+use crate::actor::slo::SloConfig;
+use crate::actor::state_capture::SnapshotFormat;
+use crate::actor::throughput::{AdaptiveThroughput, Throughput};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Shutdown phase of Actors that did not declare one, see [ActorBuilder.set_shutdown_phase](../prelude/struct.ActorBuilder.html#method.set_shutdown_phase)
+pub const DEFAULT_SHUTDOWN_PHASE: u8 = 128;
+
+fn default_shutdown_phase() -> u8 {
+    DEFAULT_SHUTDOWN_PHASE
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ActorConfig {
@@ -6,12 +22,660 @@ pub struct ActorConfig {
     pub pool_name: String,
     pub mailbox_size: usize,
     pub message_throughput: usize,
+    /// replaces `message_throughput` if set
+    #[serde(default)]
+    pub adaptive_throughput: Option<AdaptiveThroughput>,
     pub restart_policy: RestartPolicy,
+    pub max_suspension: Duration,
+    pub priority: ActorPriority,
+    pub capture_last_n_messages: Option<usize>,
+    pub latency_slo: Option<SloConfig>,
+    pub watermarks: Option<Watermarks>,
+    #[serde(default)]
+    pub start_suspended: bool,
+    #[serde(default)]
+    pub sheddable: bool,
+    #[serde(default)]
+    pub protocol: Option<ActorProtocol>,
+    /// `0` disables the journal
+    #[serde(default)]
+    pub journal_capacity: usize,
+    #[serde(default)]
+    pub rng_reseed: RngReseed,
+    /// sends of the Actor never continue a causal chain of the cycle detection
+    #[serde(default)]
+    pub cycle_exempt: bool,
+    /// `0` disables state captures
+    #[serde(default)]
+    pub state_capture_capacity: usize,
+    #[serde(default)]
+    pub snapshot_format: SnapshotFormat,
+    /// message types that are handled between two state captures, matched against the end of their type name
+    #[serde(default)]
+    pub capture_around: Vec<String>,
+    /// sends whose approximate size exceeds the limit are rejected
+    #[serde(default)]
+    pub max_message_bytes: Option<usize>,
+    /// handled messages between two recovery snapshots, `0` only takes them on stop
+    #[serde(default)]
+    pub snapshot_interval: usize,
+    /// Actors spawned again after a graceful stop recover from the snapshot of their previous run
+    #[serde(default)]
+    pub recover_after_stop: bool,
+    /// the Actor is created and executed on a thread of its own instead of the threads of its pool
+    #[serde(default)]
+    pub dedicated_thread: bool,
+    /// the Actor is created by its first slot instead of the spawn, see [ActorBuilder.set_lazy](../prelude/struct.ActorBuilder.html#method.set_lazy)
+    #[serde(default)]
+    pub lazy: bool,
+    /// behavior of sends to the full bounded mailbox, has no effect on unbounded mailboxes
+    #[serde(default)]
+    pub overflow_policy: MailboxOverflowPolicy,
+    /// messages dropped by the overflow policy are reported as dead letters
+    #[serde(default)]
+    pub overflow_dead_letters: bool,
+    /// approximate bytes that can be queued at the same time, sends beyond it are treated like sends to a full mailbox
+    #[serde(default)]
+    pub max_mailbox_bytes: Option<usize>,
+    /// what happens to the message whose handler panicked or failed
+    #[serde(default)]
+    pub failed_message_strategy: FailedMessageStrategy,
+    /// what happens to the queued messages when the Actor restarts
+    #[serde(default)]
+    pub restart_mailbox_policy: RestartMailboxPolicy,
+    /// further limits the message budget of every scheduling slot
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+    /// messages that can be stashed at the same time, `0` allows an unbounded stash
+    #[serde(default)]
+    pub stash_capacity: usize,
+    /// Actors of lower phases are stopped first when the system stops
+    #[serde(default = "default_shutdown_phase")]
+    pub shutdown_phase: u8,
+    /// when the system stop is handled relative to the messages that are still queued
+    #[serde(default)]
+    pub system_stop_order: SystemStopOrder,
+    /// the Actor stops itself once it has not handled any message for this long
+    #[serde(default)]
+    pub idle_timeout: Option<Duration>,
+    /// messages that have not been handled within this long after they have been sent are dead letters
+    #[serde(default)]
+    pub message_ttl: Option<Duration>,
+    /// not part of the graceful drain of the system, stopped after all other Actors
+    #[serde(default)]
+    pub daemon: bool,
+    /// interval of [Actor.on_tick](../prelude/trait.Actor.html#method.on_tick), no ticks without it
+    #[serde(default)]
+    pub tick_interval: Option<Duration>,
+    /// messages of types that have not been registered are dead letters, see [ActorBuilder.register_message](../prelude/struct.ActorBuilder.html#method.register_message)
+    #[serde(default)]
+    pub strict_messages: bool,
+    /// the mailbox neither counts towards the message budget of the system nor is it shed, see [MessageBudgetConfig](../prelude/struct.MessageBudgetConfig.html)
+    #[serde(default)]
+    pub exempt_from_shedding: bool,
+    /// handler invocations that take longer are counted and handed to the [slow handler hook](../prelude/struct.ActorSystem.html#method.set_slow_handler_hook)
+    #[serde(default)]
+    pub slow_handler_threshold: Option<Duration>,
+    /// what a panic of the Actor means beyond the Actor itself, overrides `general.default_panic_escalation`
+    #[serde(default)]
+    pub panic_escalation: PanicEscalation,
+}
+
+impl ActorConfig {
+    pub fn get_throughput(&self) -> Throughput {
+        match self.adaptive_throughput {
+            Some(adaptive) => Throughput::Adaptive(adaptive),
+            None => Throughput::Fixed(self.message_throughput),
+        }
+    }
 }
 
 /// Defines behavior of [Actor](../prelude/trait.Actor.html) in case of a panic when handling a message
+///
+/// # Examples
+///
+/// Limited restarts with exponential backoff:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::{Duration, Instant};
+///
+/// struct Job {}
+/// impl ActorMessage for Job {}
+///
+/// struct Fragile { starts: Arc<Mutex<Vec<Instant>>> }
+/// impl Actor for Fragile {
+///     fn pre_start(&mut self) {
+///         self.starts.lock().unwrap().push(Instant::now());
+///     }
+/// }
+/// impl Handler<Job> for Fragile {
+///     fn handle(&mut self, _msg: Job, _context: &ActorContext<Self>) {
+///         panic!("always fails");
+///     }
+/// }
+/// struct FragileFactory { starts: Arc<Mutex<Vec<Instant>>> }
+/// impl ActorFactory<Fragile> for FragileFactory {
+///     fn new_actor(&self, _context: ActorContext<Fragile>) -> Fragile {
+///         Fragile { starts: self.starts.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let starts = Arc::new(Mutex::new(Vec::new()));
+/// let policy = RestartPolicy::Limited {
+///     max_restarts: 3,
+///     within: Duration::from_secs(60),
+///     backoff: BackoffStrategy::Exponential { initial: Duration::from_millis(100), max: Duration::from_secs(1) },
+/// };
+/// let actor = actor_system
+///     .builder()
+///     .set_restart_policy(policy)
+///     .spawn("fragile", FragileFactory { starts: starts.clone() })
+///     .unwrap();
+/// for _ in 0..10 {
+///     actor.send(Job {}).unwrap();
+/// }
+/// sleep(Duration::from_millis(1500));
+///
+/// // the initial start and three restarts, the fourth panic stops the Actor
+/// let starts = starts.lock().unwrap();
+/// assert_eq!(4, starts.len());
+/// assert!(actor.send(Job {}).is_err());
+/// for (n, expected) in [100, 200, 400].iter().enumerate() {
+///     let delay = starts[n + 1].duration_since(starts[n]);
+///     assert!(delay >= Duration::from_millis(*expected));
+///     assert!(delay < Duration::from_millis(*expected + 250));
+/// }
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum RestartPolicy {
     Never,
     Always,
+    /// restarts at most `max_restarts` times within `within` and stops with the next panic, the parent is notified through [ChildTerminated](../prelude/struct.ChildTerminated.html)
+    ///
+    /// Restarted Actors wait for the backoff before `pre_start` is executed, without blocking a thread of the pool
+    Limited {
+        max_restarts: usize,
+        within: Duration,
+        backoff: BackoffStrategy,
+    },
+}
+
+/// Consequence of a panic or a failed [TryHandler](../prelude/trait.TryHandler.html) beyond the [RestartPolicy](./enum.RestartPolicy.html), see [ActorBuilder.set_panic_escalation](../prelude/struct.ActorBuilder.html#method.set_panic_escalation)
+///
+/// [Actor.on_panic](../prelude/trait.Actor.html#method.on_panic), the [panic handler](../prelude/struct.ActorSystem.html#method.set_panic_handler) and the
+/// [instrumentation](../prelude/struct.ActorSystem.html#method.set_instrumentation) are executed before the escalation, whatever it is
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::time::Duration;
+///
+/// struct Crash {}
+/// impl ActorMessage for Crash {}
+///
+/// struct Ledger { context: ActorContext<Self> }
+/// impl Actor for Ledger {
+///     fn on_system_stop(&mut self) {
+///         self.context.actor_ref.stop();
+///     }
+/// }
+/// impl Handler<Crash> for Ledger {
+///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+///         panic!("corrupted balance");
+///     }
+/// }
+/// struct LedgerFactory {}
+/// impl ActorFactory<Ledger> for LedgerFactory {
+///     fn new_actor(&self, context: ActorContext<Ledger>) -> Ledger {
+///         Ledger { context }
+///     }
+/// }
+///
+/// // the Actor stops for good despite its restart policy, the system keeps running
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// actor_system.set_panic_handler(|_info: PanicInfoContext| {});
+/// let ledger = actor_system
+///     .builder()
+///     .set_restart_policy(RestartPolicy::Always)
+///     .set_panic_escalation(PanicEscalation::StopActor)
+///     .spawn("ledger", LedgerFactory {})
+///     .unwrap();
+/// actor_system.builder().spawn("other", LedgerFactory {}).unwrap();
+/// ledger.send(Crash {}).unwrap();
+/// assert!(ledger.await_stop(Duration::from_secs(1)));
+/// assert!(matches!(ledger.send(Crash {}), Err(SendError::Stopped(_))));
+/// assert_eq!(1, actor_system.get_actor_count());
+/// actor_system.stop(Duration::from_secs(1));
+/// assert_eq!(0, actor_system.await_shutdown());
+///
+/// // a panic stops the whole system, the exit code tells it apart from a clean or a forced stop
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.default_panic_escalation = PanicEscalation::StopSystem { graceful: Duration::from_secs(1) };
+/// let actor_system = ActorSystem::new(config);
+/// actor_system.set_panic_handler(|_info: PanicInfoContext| {});
+/// let ledger = actor_system.builder().spawn("ledger", LedgerFactory {}).unwrap();
+/// let other = actor_system.builder().spawn("other", LedgerFactory {}).unwrap();
+/// ledger.send(Crash {}).unwrap();
+/// assert_eq!(Some(3), actor_system.await_shutdown_timeout(Duration::from_secs(1)));
+/// assert!(matches!(other.send(Crash {}), Err(SendError::Stopped(_))));
+///
+/// // an Actor that ignores the system stop forces it once the timeout elapsed
+/// struct Idle {}
+/// impl Actor for Idle {}
+/// struct IdleFactory {}
+/// impl ActorFactory<Idle> for IdleFactory {
+///     fn new_actor(&self, _context: ActorContext<Idle>) -> Idle {
+///         Idle {}
+///     }
+/// }
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// actor_system.builder().spawn("idle", IdleFactory {}).unwrap();
+/// actor_system.stop(Duration::from_millis(100));
+/// assert_eq!(1, actor_system.await_shutdown());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PanicEscalation {
+    #[default]
+    /// the [RestartPolicy](./enum.RestartPolicy.html) decides whether the Actor is restarted
+    Restart,
+    /// the Actor stops regardless of its restart policy, the parent is notified through [ChildTerminated](../prelude/struct.ChildTerminated.html)
+    StopActor,
+    /// the Actor stops and the system is stopped through [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) with `graceful` as termination timeout
+    ///
+    /// [ActorSystem.await_shutdown](../prelude/struct.ActorSystem.html#method.await_shutdown) returns `3` afterwards, even if the stop had to be forced
+    StopSystem { graceful: Duration },
+    /// the shutdown hooks are executed and the process is aborted right away, without stopping any other Actor
+    Abort,
+}
+
+/// Behavior of a send to the full bounded mailbox of an [Actor](../prelude/trait.Actor.html), see [ActorBuilder.set_overflow_policy](../prelude/struct.ActorBuilder.html#method.set_overflow_policy)
+///
+/// Dropped messages are counted by [ActorWrapper.dropped_count](../prelude/struct.ActorWrapper.html#method.dropped_count).
+/// Messages sent through [send_priority](../prelude/struct.ActorWrapper.html#method.send_priority) and control messages are never dropped
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// struct Sample { id: usize }
+/// impl ActorMessage for Sample {}
+///
+/// struct Sink { seen: Arc<Mutex<Vec<usize>>> }
+/// impl Actor for Sink {}
+/// impl Handler<Sample> for Sink {
+///     fn handle(&mut self, msg: Sample, _context: &ActorContext<Self>) {
+///         self.seen.lock().unwrap().push(msg.id);
+///     }
+/// }
+/// struct SinkFactory { seen: Arc<Mutex<Vec<usize>>> }
+/// impl ActorFactory<Sink> for SinkFactory {
+///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+///         Sink { seen: self.seen.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let run = |name: &str, policy: MailboxOverflowPolicy| {
+///     let seen = Arc::new(Mutex::new(Vec::new()));
+///     // the suspended Actor does not make room before it is released
+///     let sink = actor_system
+///         .builder()
+///         .set_mailbox_size(2)
+///         .set_overflow_policy(policy)
+///         .start_suspended(true)
+///         .spawn(name, SinkFactory { seen: seen.clone() })
+///         .unwrap();
+///     // a blocking send would wait for the release forever
+///     let accepted: Vec<bool> = (0..4)
+///         .map(|id| match policy {
+///             MailboxOverflowPolicy::Block => sink.try_send(Sample { id }).is_ok(),
+///             _ => sink.send(Sample { id }).is_ok(),
+///         })
+///         .collect();
+///     sink.release();
+///     sink.stop();
+///     assert!(sink.await_stop(Duration::from_secs(3)));
+///     let seen = seen.lock().unwrap().clone();
+///     (accepted, seen, sink.dropped_count())
+/// };
+///
+/// assert_eq!((vec![true, true, false, false], vec![0, 1], 0), run("block", MailboxOverflowPolicy::Block));
+/// assert_eq!((vec![true, true, false, false], vec![0, 1], 0), run("fail", MailboxOverflowPolicy::Fail));
+/// assert_eq!((vec![true, true, true, true], vec![0, 1], 2), run("newest", MailboxOverflowPolicy::DropNewest));
+/// assert_eq!((vec![true, true, true, true], vec![2, 3], 2), run("oldest", MailboxOverflowPolicy::DropOldest));
+///
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum MailboxOverflowPolicy {
+    #[default]
+    /// [send](../prelude/struct.ActorWrapper.html#method.send) waits for room, [try_send](../prelude/struct.ActorWrapper.html#method.try_send) hands the message back
+    Block,
+    /// the new message is dropped and the send succeeds
+    DropNewest,
+    /// the oldest unprocessed message is dropped to make room, the order of the remaining messages is kept
+    DropOldest,
+    /// every send hands the message back with [SendError::Full](../prelude/enum.SendError.html#variant.Full) without waiting
+    Fail,
+}
+
+/// Handling of the message whose handler panicked or failed, see [ActorBuilder.set_failed_message_strategy](../prelude/struct.ActorBuilder.html#method.set_failed_message_strategy)
+///
+/// Messages that are still queued are not lost by a restart and keep their order, unless the [RestartMailboxPolicy](../prelude/enum.RestartMailboxPolicy.html) flushes them.
+/// The restarted Actor handles nothing before the factory created it and `pre_start` finished
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// #[derive(Clone)]
+/// struct Number { n: usize }
+/// impl ActorMessage for Number {}
+///
+/// struct Counter { instance: usize, starts: Arc<AtomicUsize>, seen: Arc<Mutex<Vec<(usize, usize)>>> }
+/// impl Actor for Counter {
+///     fn pre_start(&mut self) {
+///         self.instance = self.starts.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+/// impl Handler<Number> for Counter {
+///     fn handle(&mut self, msg: Number, _context: &ActorContext<Self>) {
+///         // only the first instance fails
+///         if msg.n == 50 && self.instance == 0 {
+///             panic!("fails on 50");
+///         }
+///         self.seen.lock().unwrap().push((self.instance, msg.n));
+///     }
+/// }
+/// struct CounterFactory { starts: Arc<AtomicUsize>, seen: Arc<Mutex<Vec<(usize, usize)>>> }
+/// impl ActorFactory<Counter> for CounterFactory {
+///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+///         Counter { instance: 0, starts: self.starts.clone(), seen: self.seen.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let run = |name: &str, strategy: FailedMessageStrategy| {
+///     let seen = Arc::new(Mutex::new(Vec::new()));
+///     let counter = actor_system
+///         .builder()
+///         .set_restart_policy(RestartPolicy::Always)
+///         .set_failed_message_strategy(strategy)
+///         .add_retryable::<Number>()
+///         .start_suspended(true)
+///         .spawn(name, CounterFactory { starts: Arc::new(AtomicUsize::new(0)), seen: seen.clone() })
+///         .unwrap();
+///     for n in 1..=100 {
+///         counter.send(Number { n }).unwrap();
+///     }
+///     counter.release();
+///     // a panic while the Actor drains its queue for a stop ends it instead of restarting it
+///     sleep(Duration::from_millis(200));
+///     counter.stop();
+///     assert!(counter.await_stop(Duration::from_secs(3)));
+///     let seen = seen.lock().unwrap().clone();
+///     assert_eq!((1..50).map(|n| (0, n)).collect::<Vec<_>>(), seen[..49]);
+///     seen[49..].iter().map(|(instance, n)| {
+///         assert_eq!(1, *instance);
+///         *n
+///     }).collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!((51..=100).collect::<Vec<_>>(), run("drop", FailedMessageStrategy::Drop));
+/// assert_eq!((50..=100).collect::<Vec<_>>(), run("retry", FailedMessageStrategy::RetryOnce));
+/// assert_eq!(0, actor_system.dead_letter_count());
+/// assert_eq!((51..=100).collect::<Vec<_>>(), run("dead", FailedMessageStrategy::SendToDeadLetters));
+/// assert_eq!(1, actor_system.dead_letter_count());
+///
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum FailedMessageStrategy {
+    #[default]
+    /// the message is lost together with the failed Actor
+    Drop,
+    /// the restarted Actor handles the message again before anything else, a message that fails twice is dropped
+    ///
+    /// Only applies to message types registered through [ActorBuilder.add_retryable](../prelude/struct.ActorBuilder.html#method.add_retryable), all others are dropped
+    RetryOnce,
+    /// the message is reported as a dead letter, see [ActorSystem.subscribe_dead_letters](../prelude/struct.ActorSystem.html#method.subscribe_dead_letters)
+    SendToDeadLetters,
+}
+
+/// Messages that are queued while the Actor restarts after a panic or failure, see [ActorBuilder.set_restart_mailbox_policy](../prelude/struct.ActorBuilder.html#method.set_restart_mailbox_policy)
+///
+/// Flushed messages are [FlushedOnRestart](../prelude/enum.DeadLetterReason.html#variant.FlushedOnRestart) dead letters.
+/// The flush takes place together with the decision to restart, messages that are sent afterwards are handled by the restarted Actor.
+/// Stop requests and other internal messages are never flushed
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Line { n: usize }
+/// impl ActorMessage for Line {}
+///
+/// struct Parser { seen: Arc<Mutex<Vec<usize>>> }
+/// impl Actor for Parser {}
+/// impl Handler<Line> for Parser {
+///     fn handle(&mut self, msg: Line, _context: &ActorContext<Self>) {
+///         if msg.n == 5 {
+///             panic!("malformed line");
+///         }
+///         self.seen.lock().unwrap().push(msg.n);
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let run = |name: &str, policy: RestartMailboxPolicy| {
+///     let seen = Arc::new(Mutex::new(Vec::new()));
+///     let shared = seen.clone();
+///     let parser = actor_system
+///         .builder()
+///         .set_restart_policy(RestartPolicy::Always)
+///         .set_restart_mailbox_policy(policy)
+///         .start_suspended(true)
+///         .spawn_fn(name, move |_context| Parser { seen: shared.clone() })
+///         .unwrap();
+///     for n in 1..=10 {
+///         parser.send(Line { n }).unwrap();
+///     }
+///     parser.release();
+///     sleep(Duration::from_millis(100));
+///     // sent after the restart
+///     parser.send(Line { n: 11 }).unwrap();
+///     sleep(Duration::from_millis(100));
+///     let seen = seen.lock().unwrap().clone();
+///     (seen, actor_system.drop_stats().get(DeadLetterReason::FlushedOnRestart, parser.get_address()))
+/// };
+///
+/// assert_eq!((vec![1, 2, 3, 4, 6, 7, 8, 9, 10, 11], 0), run("keep", RestartMailboxPolicy::Keep));
+/// assert_eq!((vec![1, 2, 3, 4, 11], 5), run("flush", RestartMailboxPolicy::Flush));
+/// assert_eq!((vec![1, 2, 3, 4, 9, 10, 11], 3), run("keep-last", RestartMailboxPolicy::KeepLast(2)));
+///
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum RestartMailboxPolicy {
+    #[default]
+    /// the restarted Actor handles every queued message
+    Keep,
+    /// every queued message is flushed
+    Flush,
+    /// the `n` most recently queued messages are kept, all older ones are flushed
+    KeepLast(usize),
+}
+
+/// Position of the system stop within the mailbox of an [Actor](../prelude/trait.Actor.html), see [ActorBuilder.set_system_stop_order](../prelude/struct.ActorBuilder.html#method.set_system_stop_order)
+///
+/// Either way [Actor.on_system_stop](../prelude/trait.Actor.html#method.on_system_stop) is executed by a thread of the pool like every handler,
+/// the Actor is only removed once it stopped or the graceful termination timeout of [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) elapsed
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Record {}
+/// impl ActorMessage for Record {}
+///
+/// struct Buffer { context: ActorContext<Self>, delay: Duration, handled: Arc<AtomicUsize>, flushed: Arc<Mutex<Option<usize>>> }
+/// impl Actor for Buffer {
+///     fn on_system_stop(&mut self) {
+///         *self.flushed.lock().unwrap() = Some(self.handled.load(Ordering::SeqCst));
+///         // whatever is still queued is dropped
+///         self.context.actor_ref.stop_now();
+///     }
+/// }
+/// impl Handler<Record> for Buffer {
+///     fn handle(&mut self, _msg: Record, _context: &ActorContext<Self>) {
+///         sleep(self.delay);
+///         self.handled.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+/// struct BufferFactory { delay: Duration, handled: Arc<AtomicUsize>, flushed: Arc<Mutex<Option<usize>>> }
+/// impl ActorFactory<Buffer> for BufferFactory {
+///     fn new_actor(&self, context: ActorContext<Buffer>) -> Buffer {
+///         Buffer { context, delay: self.delay, handled: self.handled.clone(), flushed: self.flushed.clone() }
+///     }
+/// }
+///
+/// let run = |order: SystemStopOrder, delay: Duration| {
+///     let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+///     let handled = Arc::new(AtomicUsize::new(0));
+///     let flushed = Arc::new(Mutex::new(None));
+///     let factory = BufferFactory { delay, handled: handled.clone(), flushed: flushed.clone() };
+///     let buffer = actor_system
+///         .builder()
+///         .set_mailbox_unbounded()
+///         .set_system_stop_order(order)
+///         .spawn("buffer", factory)
+///         .unwrap();
+///     for _ in 0..10000 {
+///         buffer.send(Record {}).unwrap();
+///     }
+///     actor_system.stop(Duration::from_secs(2));
+///     assert_eq!(0, actor_system.await_shutdown());
+///     let flushed = flushed.lock().unwrap().unwrap();
+///     (flushed, handled.load(Ordering::SeqCst))
+/// };
+///
+/// // the backlog would take 10 seconds, the hook runs right after the current message
+/// let (flushed, handled) = run(SystemStopOrder::BeforeQueued, Duration::from_millis(1));
+/// assert!(flushed < 10000);
+/// assert_eq!(flushed, handled);
+///
+/// // the hook runs once the backlog has been handled
+/// assert_eq!((10000, 10000), run(SystemStopOrder::AfterQueued, Duration::from_secs(0)));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SystemStopOrder {
+    #[default]
+    /// the system stop overtakes all queued messages and is handled right after the current message
+    BeforeQueued,
+    /// the system stop is handled once the mailbox ran empty, messages that keep arriving delay it until the graceful termination timeout
+    AfterQueued,
+}
+
+/// Delay between a panic and the restart of the Actor, see [RestartPolicy::Limited](./enum.RestartPolicy.html#variant.Limited)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum BackoffStrategy {
+    None,
+    Fixed(Duration),
+    /// starts with `initial` and doubles with every restart within the window, up to `max`
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl BackoffStrategy {
+    /// Delay of the restart that follows `previous` restarts within the window
+    pub fn delay(&self, previous: usize) -> Duration {
+        match *self {
+            BackoffStrategy::None => Duration::from_secs(0),
+            BackoffStrategy::Fixed(delay) => delay,
+            BackoffStrategy::Exponential { initial, max } => {
+                let factor = 1u32 << previous.min(31);
+                initial.checked_mul(factor).map_or(max, |delay| delay.min(max))
+            }
+        }
+    }
+}
+
+/// Actor settings that override the `general.default_*` settings for all Actors of a pool
+///
+/// Unset fields fall back to the general defaults, see [ThreadPoolConfig.with_actor_defaults](../prelude/struct.ThreadPoolConfig.html#method.with_actor_defaults)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct ActorDefaults {
+    pub mailbox_size: Option<usize>,
+    pub message_throughput: Option<usize>,
+    pub restart_policy: Option<RestartPolicy>,
+    pub max_suspension: Option<Duration>,
+    pub priority: Option<ActorPriority>,
+}
+
+/// Origin of a single setting of a [ResolvedActorConfig](./struct.ResolvedActorConfig.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigSource {
+    /// `general.default_*`
+    Global,
+    /// [ActorDefaults](./struct.ActorDefaults.html) of the pool
+    Pool,
+    /// set explicitly on the [ActorBuilder](./struct.ActorBuilder.html)
+    Builder,
+}
+
+/// Origin of every setting that has a general default
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActorConfigSources {
+    pub mailbox_size: ConfigSource,
+    pub message_throughput: ConfigSource,
+    pub restart_policy: ConfigSource,
+    pub max_suspension: ConfigSource,
+    pub priority: ConfigSource,
+}
+
+/// See [ActorBuilder.get_resolved_config](../prelude/struct.ActorBuilder.html#method.get_resolved_config)
+#[derive(Debug, Clone)]
+pub struct ResolvedActorConfig {
+    pub config: ActorConfig,
+    pub sources: ActorConfigSources,
+}
+
+pub(crate) fn resolve<T>(global: T, pool: Option<T>, builder: Option<T>) -> (T, ConfigSource) {
+    match (builder, pool) {
+        (Some(value), _) => (value, ConfigSource::Builder),
+        (None, Some(value)) => (value, ConfigSource::Pool),
+        (None, None) => (global, ConfigSource::Global),
+    }
 }
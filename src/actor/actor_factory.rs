@@ -1,11 +1,13 @@
 use crate::actor::actor::Actor;
 use crate::actor::context::ActorContext;
+use std::error::Error;
 use std::panic::UnwindSafe;
 
 /// [Actor] can only be created from a Factory
 ///
 /// This factory approach is necessary because of the restart behavior.
 /// Without this factory we'd need to keep a `.clone()` of the initial Actor, which would force all Actor implementations to implement `Clone`.
+/// Closures that take the [ActorContext] and return the Actor are factories as well, see [ActorBuilder.spawn_fn](../prelude/struct.ActorBuilder.html#method.spawn_fn)
 ///
 /// # Examples
 ///
@@ -41,3 +43,124 @@ where
     /// through [Actor.on_system_stop]
     fn new_actor(&self, context: ActorContext<A>) -> A;
 }
+
+/// Closures that create the Actor, see [ActorBuilder.spawn_fn](../prelude/struct.ActorBuilder.html#method.spawn_fn) for why the bounds are needed
+impl<A, F> ActorFactory<A> for F
+where
+    A: Actor + UnwindSafe + 'static,
+    F: Fn(ActorContext<A>) -> A + Send + Sync + UnwindSafe,
+{
+    fn new_actor(&self, context: ActorContext<A>) -> A {
+        self(context)
+    }
+}
+
+/// Returned by a [TryActorFactory] that could not create the Actor
+///
+/// Any [std::error::Error] converts into an `ActorInitError`, so that `?` can be used within [TryActorFactory.try_new_actor](./trait.TryActorFactory.html#tymethod.try_new_actor)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorInitError {
+    pub message: String,
+}
+
+impl ActorInitError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl<E> From<E> for ActorInitError
+where
+    E: Error,
+{
+    fn from(error: E) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+/// Fallible alternative to [ActorFactory], implemented by every [ActorFactory]
+///
+/// A failure when the Actor is spawned is returned by [ActorBuilder.try_spawn](../prelude/struct.ActorBuilder.html#method.try_spawn) as [SpawnError::FactoryFailed](../prelude/enum.SpawnError.html#variant.FactoryFailed).
+/// A failure when the Actor is restarted is handled like a panic of the Actor: it counts towards the [RestartPolicy](../prelude/enum.RestartPolicy.html),
+/// so the creation is retried with the next restart or the Actor stops, and parents are notified with the error as usual.
+/// Actors on a dedicated thread are created on that thread, a failure there is handled like a failure during a restart
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Crash {}
+/// impl ActorMessage for Crash {}
+///
+/// struct Connection {}
+/// impl Actor for Connection {}
+/// impl Handler<Crash> for Connection {
+///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+///         panic!("connection lost");
+///     }
+/// }
+/// struct ConnectionFactory { port: &'static str, attempts: Arc<AtomicUsize>, available: usize }
+/// impl TryActorFactory<Connection> for ConnectionFactory {
+///     fn try_new_actor(&self, _context: ActorContext<Connection>) -> Result<Connection, ActorInitError> {
+///         let _port: u16 = self.port.parse()?;
+///         // the first creations succeed, every later one fails
+///         if self.attempts.fetch_add(1, Ordering::Relaxed) >= self.available {
+///             return Err(ActorInitError::new("connection refused"));
+///         }
+///         Ok(Connection {})
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let attempts = Arc::new(AtomicUsize::new(0));
+/// let invalid = ConnectionFactory { port: "http", attempts: attempts.clone(), available: 10 };
+/// let result = actor_system.builder().try_spawn("invalid", invalid);
+/// assert_eq!(Some(SpawnError::FactoryFailed(ActorInitError::new("invalid digit found in string"))), result.err());
+/// // nothing is left of the failed spawn
+/// assert_eq!(0, actor_system.get_actor_count());
+/// let valid = ConnectionFactory { port: "8080", attempts: attempts.clone(), available: 10 };
+/// assert!(actor_system.builder().try_spawn("invalid", valid).is_ok());
+///
+/// // the spawn creates the Actor once, one restart succeeds
+/// let attempts = Arc::new(AtomicUsize::new(0));
+/// let policy = RestartPolicy::Limited { max_restarts: 3, within: Duration::from_secs(60), backoff: BackoffStrategy::None };
+/// let connection = actor_system
+///     .builder()
+///     .set_restart_policy(policy)
+///     .try_spawn("connection", ConnectionFactory { port: "8080", attempts: attempts.clone(), available: 2 })
+///     .unwrap();
+/// connection.send(Crash {}).unwrap();
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(2, attempts.load(Ordering::Relaxed));
+/// assert_eq!(1, actor_system.actor_stats(connection.get_address()).unwrap().restarts);
+///
+/// // failed creations count as restarts as well, the Actor stops once the limit is exhausted
+/// connection.send(Crash {}).unwrap();
+/// assert!(connection.await_stop(Duration::from_secs(1)));
+/// assert_eq!(4, attempts.load(Ordering::Relaxed));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+pub trait TryActorFactory<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Same as [ActorFactory.new_actor](./trait.ActorFactory.html#tymethod.new_actor), but the Actor is not created if it returns an `Err`
+    fn try_new_actor(&self, context: ActorContext<A>) -> Result<A, ActorInitError>;
+}
+
+impl<A, F> TryActorFactory<A> for F
+where
+    A: Actor + UnwindSafe + 'static,
+    F: ActorFactory<A>,
+{
+    fn try_new_actor(&self, context: ActorContext<A>) -> Result<A, ActorInitError> {
+        Ok(self.new_actor(context))
+    }
+}
@@ -0,0 +1,355 @@
+use crate::actor::mailbox::MailboxFlags;
+use crate::actor::metric_stripes::{Counter, StripedCounters};
+use crate::config::global_config::HistoryConfig;
+use crate::system::metrics_history::{ActorMetricsHistory, ActorMetricsSample, RingBuffer};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Amount of worker ids kept per Actor
+const RECENT_WORKERS: usize = 4;
+
+struct ActorMetricsInner {
+    /// processed messages, handler and construction time, accumulated per worker thread
+    striped: Arc<StripedCounters>,
+    restarts: AtomicUsize,
+    panics: AtomicUsize,
+    /// nanoseconds since the unix epoch, `0` until the first message has been handled
+    last_activity: AtomicU64,
+    shed: AtomicUsize,
+    undecodable: AtomicUsize,
+    superseded: AtomicUsize,
+    dropped: AtomicUsize,
+    expired: AtomicUsize,
+    unhandled: AtomicUsize,
+    slow_handlers: AtomicUsize,
+    empty_wakeups: AtomicUsize,
+    wakeup_requests: AtomicUsize,
+    migrations: AtomicUsize,
+    recent_workers: Mutex<VecDeque<usize>>,
+    suspended_nanos: AtomicU64,
+    boosted_nanos: AtomicU64,
+    active_request_chains: AtomicUsize,
+    message_budget: AtomicUsize,
+    mailbox_len: Box<dyn Fn() -> usize + Send + Sync>,
+    /// shared with the mailbox
+    mailbox_flags: Arc<MailboxFlags>,
+    history: Option<Mutex<ActorHistoryState>>,
+}
+
+/// Snapshot of the counters of a running Actor, see [ActorSystem.actor_stats](../prelude/struct.ActorSystem.html#method.actor_stats)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorStats {
+    /// messages waiting in the regular mailbox
+    pub mailbox_len: usize,
+    /// handled messages, including internal ones like the stop message
+    pub processed: usize,
+    /// panics while handling a message or within `pre_start`, whether the Actor has been restarted or not
+    pub panics: usize,
+    pub restarts: usize,
+    /// when the Actor finished handling its last message, `None` if it has not handled any message yet
+    pub last_activity: Option<SystemTime>,
+    /// wall time spent handling messages, including the construction of lazily sent messages
+    ///
+    /// Never reset, the difference between two snapshots is the busy time within that window
+    pub busy_time: Duration,
+    /// handler invocations that exceeded the [slow handler threshold](../prelude/struct.ActorBuilder.html#method.set_slow_handler_threshold)
+    pub slow_handlers: usize,
+}
+
+struct ActorHistoryState {
+    samples: RingBuffer<ActorMetricsSample>,
+    last_processed: usize,
+}
+
+/// Counters of a single Actor, shared between the Executor, the [ActorWrapper](../prelude/struct.ActorWrapper.html) and the system
+///
+/// The processed count, the handler time and the construction time are written to a stripe of the worker thread and merged when they are read.
+/// Values that are being flushed at the end of a scheduling slot might be missed by a concurrent read, so they can lag behind by at most one slot.
+/// All other counters and the mailbox length are exact at any time
+#[derive(Clone)]
+pub struct ActorMetrics {
+    inner: Arc<ActorMetricsInner>,
+}
+
+impl ActorMetrics {
+    pub(crate) fn new(
+        mailbox_len: Box<dyn Fn() -> usize + Send + Sync>,
+        mailbox_flags: Arc<MailboxFlags>,
+        history_config: Option<HistoryConfig>,
+    ) -> Self {
+        let history = history_config.map(|config| {
+            Mutex::new(ActorHistoryState {
+                samples: RingBuffer::new(config.capacity()),
+                last_processed: 0,
+            })
+        });
+        Self {
+            inner: Arc::new(ActorMetricsInner {
+                striped: StripedCounters::new(),
+                restarts: AtomicUsize::new(0),
+                panics: AtomicUsize::new(0),
+                last_activity: AtomicU64::new(0),
+                shed: AtomicUsize::new(0),
+                undecodable: AtomicUsize::new(0),
+                superseded: AtomicUsize::new(0),
+                dropped: AtomicUsize::new(0),
+                expired: AtomicUsize::new(0),
+                unhandled: AtomicUsize::new(0),
+                slow_handlers: AtomicUsize::new(0),
+                empty_wakeups: AtomicUsize::new(0),
+                wakeup_requests: AtomicUsize::new(0),
+                migrations: AtomicUsize::new(0),
+                recent_workers: Mutex::new(VecDeque::with_capacity(RECENT_WORKERS)),
+                suspended_nanos: AtomicU64::new(0),
+                boosted_nanos: AtomicU64::new(0),
+                active_request_chains: AtomicUsize::new(0),
+                message_budget: AtomicUsize::new(0),
+                mailbox_len,
+                mailbox_flags,
+                history,
+            }),
+        }
+    }
+
+    pub fn increment_processed(&self) {
+        self.inner.striped.add(Counter::Processed, 1);
+    }
+
+    /// Moves the unflushed counts of the current thread into the shared counters, called once the Actor stopped
+    pub fn flush_local(&self) {
+        self.inner.striped.flush_local();
+    }
+
+    pub fn increment_restarts(&self) {
+        self.inner.restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn increment_panics(&self) {
+        self.inner.panics.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_panics(&self) -> usize {
+        self.inner.panics.load(Ordering::Relaxed)
+    }
+
+    pub fn set_last_activity(&self, timestamp: SystemTime) {
+        let nanos = timestamp.duration_since(UNIX_EPOCH).map_or(1, |since| since.as_nanos() as u64);
+        self.inner.last_activity.store(nanos.max(1), Ordering::Relaxed);
+    }
+
+    pub fn get_last_activity(&self) -> Option<SystemTime> {
+        match self.inner.last_activity.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(UNIX_EPOCH + Duration::from_nanos(nanos)),
+        }
+    }
+
+    pub fn get_stats(&self) -> ActorStats {
+        ActorStats {
+            mailbox_len: self.get_mailbox_len(),
+            processed: self.get_processed(),
+            panics: self.get_panics(),
+            restarts: self.get_restarts(),
+            last_activity: self.get_last_activity(),
+            busy_time: self.get_handler_time() + self.get_construction_time(),
+            slow_handlers: self.get_slow_handlers(),
+        }
+    }
+
+    pub fn increment_shed(&self) {
+        self.inner.shed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_shed(&self) -> usize {
+        self.inner.shed.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_undecodable(&self) {
+        self.inner.undecodable.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_undecodable(&self) -> usize {
+        self.inner.undecodable.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_superseded(&self) {
+        self.inner.superseded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_superseded(&self) -> usize {
+        self.inner.superseded.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_dropped(&self) {
+        self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_dropped(&self) -> usize {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_expired(&self) {
+        self.inner.expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_expired(&self) -> usize {
+        self.inner.expired.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_unhandled(&self) {
+        self.inner.unhandled.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_unhandled(&self) -> usize {
+        self.inner.unhandled.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_slow_handlers(&self) {
+        self.inner.slow_handlers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_slow_handlers(&self) -> usize {
+        self.inner.slow_handlers.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_empty_wakeups(&self) {
+        self.inner.empty_wakeups.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_empty_wakeups(&self) -> usize {
+        self.inner.empty_wakeups.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_wakeup_requests(&self) {
+        self.inner.wakeup_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_wakeup_requests(&self) -> usize {
+        self.inner.wakeup_requests.load(Ordering::Relaxed)
+    }
+
+    /// Only called if the worker changed, so consecutive slots on the same worker never lock
+    pub fn record_worker(&self, worker: usize, migrated: bool) {
+        if migrated {
+            self.inner.migrations.fetch_add(1, Ordering::Relaxed);
+        }
+        let mut recent_workers = self.inner.recent_workers.lock().unwrap();
+        if recent_workers.len() == RECENT_WORKERS {
+            recent_workers.pop_front();
+        }
+        recent_workers.push_back(worker);
+    }
+
+    pub fn get_migrations(&self) -> usize {
+        self.inner.migrations.load(Ordering::Relaxed)
+    }
+
+    /// Workers the Actor ran on, from oldest to newest, consecutive slots on the same worker are listed once
+    pub fn get_recent_workers(&self) -> Vec<usize> {
+        self.inner.recent_workers.lock().unwrap().iter().copied().collect()
+    }
+
+    pub fn add_suspended_time(&self, duration: Duration) {
+        self.inner
+            .suspended_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_suspended_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.suspended_nanos.load(Ordering::Relaxed))
+    }
+
+    pub fn add_boosted_time(&self, duration: Duration) {
+        self.inner
+            .boosted_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn get_boosted_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.boosted_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Time spent in handlers, including handlers that panicked
+    pub fn add_handler_time(&self, duration: Duration) {
+        self.inner.striped.add(Counter::HandlerNanos, duration.as_nanos() as u64);
+    }
+
+    pub fn get_handler_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.striped.get(Counter::HandlerNanos))
+    }
+
+    pub fn set_active_request_chains(&self, active: usize) {
+        self.inner.active_request_chains.store(active, Ordering::Relaxed);
+    }
+
+    pub fn get_active_request_chains(&self) -> usize {
+        self.inner.active_request_chains.load(Ordering::Relaxed)
+    }
+
+    pub fn set_message_budget(&self, budget: usize) {
+        self.inner.message_budget.store(budget, Ordering::Relaxed);
+    }
+
+    /// Message budget of the next scheduling slot
+    pub fn get_message_budget(&self) -> usize {
+        self.inner.message_budget.load(Ordering::Relaxed)
+    }
+
+    /// Time spent constructing lazily sent messages, not included in the handler time
+    pub fn add_construction_time(&self, duration: Duration) {
+        if duration.is_zero() {
+            return;
+        }
+        self.inner.striped.add(Counter::ConstructionNanos, duration.as_nanos() as u64);
+    }
+
+    pub fn get_construction_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.striped.get(Counter::ConstructionNanos))
+    }
+
+    pub fn get_processed(&self) -> usize {
+        self.inner.striped.get(Counter::Processed) as usize
+    }
+
+    pub fn get_restarts(&self) -> usize {
+        self.inner.restarts.load(Ordering::Relaxed)
+    }
+
+    pub fn get_mailbox_len(&self) -> usize {
+        (self.inner.mailbox_len)()
+    }
+
+    pub fn is_awaiting_release(&self) -> bool {
+        self.inner.mailbox_flags.is_awaiting_release.load(Ordering::SeqCst)
+    }
+
+    pub fn has_history(&self) -> bool {
+        self.inner.history.is_some()
+    }
+
+    pub fn sample_history(&self, timestamp: SystemTime) {
+        if let Some(history) = &self.inner.history {
+            let mut history = history.lock().unwrap();
+            // a read during a flush may lag behind the previous one
+            let processed = self.get_processed().max(history.last_processed);
+            let sample = ActorMetricsSample {
+                timestamp,
+                mailbox_len: self.get_mailbox_len(),
+                processed: processed - history.last_processed,
+                restarts: self.get_restarts(),
+            };
+            history.last_processed = processed;
+            history.samples.push(sample);
+        }
+    }
+
+    pub fn get_history(&self) -> Option<ActorMetricsHistory> {
+        let history = self.inner.history.as_ref()?;
+        let history = history.lock().unwrap();
+        Some(ActorMetricsHistory {
+            samples: history.samples.to_vec(),
+        })
+    }
+}
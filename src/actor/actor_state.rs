@@ -2,5 +2,6 @@
 pub enum ActorState {
     Running,
     Sleeping,
+    Suspended,
     Stopped,
 }
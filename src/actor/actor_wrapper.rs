@@ -1,13 +1,107 @@
 use crate::actor::actor::Actor;
 use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_config::MailboxOverflowPolicy;
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::context::ActorContext;
+use crate::actor::execute_fn::ExecuteFn;
+use crate::actor::extraction::ExtractError;
 use crate::actor::handler::Handler;
-use crate::actor::mailbox::Mailbox;
+use crate::actor::handoff::{self, Redirect};
+use crate::actor::journal::JournalEntry;
+use crate::actor::panic_report::ActorPanicReport;
+use crate::actor::message_stream::{LagPolicy, MessageStream};
+use crate::actor::mailbox_bytes::MailboxBytes;
+use crate::actor::mailbox::{Mailbox, PushError, Pushed, SendWait, STOP_DRAIN, STOP_NONE, STOP_NOW};
+use crate::actor::pressure::{MailboxPressure, MailboxRelieved, PressureLevel, PressureToken};
+use crate::actor::priority::{self, ActorPriority};
+use crate::actor::reply::{ReplyPath, ReplyTo};
+#[cfg(feature = "async")]
+use crate::actor::send_async::SendAsync;
+use crate::actor::send_batch::BatchSendStatus;
+use crate::actor::slo::SloStatus;
+use crate::actor::state_capture::{CaptureError, CaptureStateMessage, StateCapture};
+use crate::actor::weak_actor_wrapper::WeakActorWrapper;
 use crate::message::actor_message::ActorMessage;
 use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::envelope::{Constructor, MessageEnvelope, MessageEnvelopeTrait};
+use crate::message::headers::{self, Headers};
+use crate::message::receipt::{self, Receipt, ReceiptHandle};
+use crate::message::send_error::SendError;
+use crate::message::trace::{self, TraceId, TRACE_HEADER};
+use crate::system::cycle_detection;
+use crate::system::dead_letters::DeadLetterReason;
+use crate::system::memory_pressure::{MemoryMode, SystemMemoryPressure};
+use crate::system::message_budget::{BudgetToken, SheddingPolicy};
+use crate::system::metrics_history::ActorMetricsHistory;
 use crate::system::wakeup_manager::WakeupManager;
+use crate::system::yield_point::yield_point;
+use crossbeam_channel::bounded;
+use std::any::TypeId;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
 use std::panic::UnwindSafe;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Outcome of queueing an envelope, envelopes that have not been queued are handed back
+enum Queued<A> {
+    Done(BatchSendStatus),
+    Stopped(MessageEnvelope<A>),
+    Full(MessageEnvelope<A>),
+    /// rejected by the message budget of the system, see [SheddingPolicy::Reject]
+    Overloaded(MessageEnvelope<A>),
+    /// sent by the Actor itself to its own full mailbox
+    WouldDeadlock(MessageEnvelope<A>),
+}
 
 /// Wrapper used to interact with [Actor]
+///
+/// Wrappers are `Send + Sync + Clone`, clones are cheap and can be stored within other Actors.
+/// Wrappers are equal and hash the same if they point to the same [ActorAddress](../prelude/struct.ActorAddress.html), regardless of how they have been obtained.
+/// `Debug` only shows the address
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::collections::HashSet;
+/// use std::hash::{Hash, Hasher};
+/// use std::time::Duration;
+///
+/// struct Subscriber {}
+/// impl Actor for Subscriber {}
+///
+/// fn hash_of<T: Hash>(value: &T) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     value.hash(&mut hasher);
+///     hasher.finish()
+/// }
+/// fn shareable<T: Send + Sync + Clone>(_value: &T) {}
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.name = String::from("shop");
+/// let actor_system = ActorSystem::new(config);
+/// let first = actor_system.builder().spawn_fn("first", |_context| Subscriber {}).unwrap();
+/// let second = actor_system.builder().spawn_fn("second", |_context| Subscriber {}).unwrap();
+/// shareable(&first);
+///
+/// // a wrapper that has been looked up separately is the same subscriber
+/// let looked_up = actor_system.resolve::<Subscriber>(first.get_address()).unwrap();
+/// let subscribers: HashSet<ActorWrapper<Subscriber>> = vec![first.clone(), second.clone(), looked_up.clone()].into_iter().collect();
+/// assert_eq!(2, subscribers.len());
+/// assert_eq!(first, looked_up);
+/// assert_ne!(first, second);
+/// assert_eq!(hash_of(&first), hash_of(&looked_up));
+/// assert_ne!(hash_of(&first), hash_of(&second));
+///
+/// assert_eq!("shop://default/first", first.get_address().to_string());
+/// assert_eq!(format!("ActorWrapper {{ address: {:?} }}", first.get_address()), format!("{:?}", first));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
 pub struct ActorWrapper<A>
 where
     A: Actor + 'static,
@@ -15,6 +109,7 @@ where
     mailbox: Mailbox<A>,
     address: ActorAddress,
     wakeup_manager: WakeupManager,
+    metrics: ActorMetrics,
 }
 
 impl<A> UnwindSafe for ActorWrapper<A> where A: Actor + 'static {}
@@ -24,37 +119,2930 @@ where
     A: Actor + UnwindSafe,
 {
     /// Automatically called by the [ActorBuilder.build](../prelude/struct.ActorBuilder.html#method.build)
-    pub fn new(mailbox: Mailbox<A>, address: ActorAddress, wakeup_manager: WakeupManager) -> Self {
+    pub fn new(
+        mailbox: Mailbox<A>,
+        address: ActorAddress,
+        wakeup_manager: WakeupManager,
+        metrics: ActorMetrics,
+    ) -> Self {
         Self {
             mailbox,
             address,
             wakeup_manager,
+            metrics,
         }
     }
 
-    pub fn send<M>(&self, msg: M)
+    /// Queues the message, waits for room if the bounded mailbox of the Actor is full
+    ///
+    /// The message is handed back if the Actor has stopped or if it exceeds the message size limit of the Actor, see [try_send](#method.try_send).
+    /// Messages dropped by a sheddable Actor under memory pressure or by the cycle detection count as sent
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping { id: usize }
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Pong {}
+    /// impl Actor for Pong {}
+    /// impl Handler<Ping> for Pong {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct PongFactory {}
+    /// impl ActorFactory<Pong> for PongFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Pong>) -> Pong {
+    ///         Pong {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system.builder().spawn("pong", PongFactory {}).unwrap();
+    /// assert!(actor.send(Ping { id: 1 }).is_ok());
+    /// actor.stop();
+    /// sleep(Duration::from_millis(200));
+    /// match actor.send(Ping { id: 2 }) {
+    ///     Err(SendError::Stopped(ping)) => assert_eq!(2, ping.id),
+    ///     _ => panic!("the Actor does not accept messages after the stop"),
+    /// }
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send<M>(&self, msg: M) -> Result<(), SendError<M>>
     where
         A: Handler<M>,
         M: ActorMessage + 'static,
     {
-        if self.mailbox.is_stopped() {
+        self.send_message(msg, SendWait::Block, false)
+    }
+
+    /// Same as [send](#method.send) for a message that is shared instead of copied, e.g. to hand the same large buffer to several Actors
+    ///
+    /// `Arc<M>` is an [ActorMessage](../prelude/trait.ActorMessage.html) and `Clone` for every message `M`, so it works for everything that requires `Clone` as well,
+    /// e.g. [EventBus.broadcast](../prelude/struct.EventBus.html#method.broadcast) or [ActorContext.schedule_repeating](../prelude/struct.ActorContext.html#method.schedule_repeating)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// struct Blob { bytes: Vec<u8> }
+    /// impl ActorMessage for Blob {}
+    ///
+    /// struct Checksum { sums: Sender<usize> }
+    /// impl Actor for Checksum {}
+    /// impl Handler<Arc<Blob>> for Checksum {
+    ///     fn handle(&mut self, msg: Arc<Blob>, _context: &ActorContext<Self>) {
+    ///         let sum: usize = msg.bytes.iter().map(|byte| *byte as usize).sum();
+    ///         drop(msg);
+    ///         self.sums.send(sum).unwrap();
+    ///     }
+    /// }
+    /// struct ChecksumFactory { sums: Sender<usize> }
+    /// impl ActorFactory<Checksum> for ChecksumFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Checksum>) -> Checksum {
+    ///         Checksum { sums: self.sums.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (sender, sums) = unbounded();
+    /// let workers: Vec<ActorWrapper<Checksum>> = (0..3)
+    ///     .map(|i| actor_system.builder().spawn(format!("checksum-{}", i), ChecksumFactory { sums: sender.clone() }).unwrap())
+    ///     .collect();
+    /// let blob = Arc::new(Blob { bytes: vec![1; 1024 * 1024] });
+    /// for worker in &workers {
+    ///     worker.send_shared(blob.clone()).unwrap();
+    /// }
+    /// for _ in 0..3 {
+    ///     assert_eq!(1024 * 1024, sums.recv_timeout(Duration::from_secs(10)).unwrap());
+    /// }
+    /// // the handlers dropped their references
+    /// assert_eq!(1, Arc::strong_count(&blob));
+    /// ```
+    pub fn send_shared<M>(&self, msg: Arc<M>) -> Result<(), SendError<Arc<M>>>
+    where
+        A: Handler<Arc<M>>,
+        M: ActorMessage + 'static,
+    {
+        self.send(msg)
+    }
+
+    /// Executes `function` with the state of the Actor, without a dedicated message type and [Handler](../prelude/trait.Handler.html)
+    ///
+    /// Same as [send](#method.send) with an [ExecuteFn](../prelude/struct.ExecuteFn.html): the closure is handled in order with all other messages
+    /// and a panic is handled like the panic of any other handler
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Push(u32);
+    /// impl ActorMessage for Push {}
+    ///
+    /// struct Stack {
+    ///     values: Vec<u32>,
+    ///     observed: Arc<Mutex<Vec<Vec<u32>>>>,
+    /// }
+    /// impl Actor for Stack {}
+    /// impl Handler<Push> for Stack {
+    ///     fn handle(&mut self, msg: Push, _context: &ActorContext<Self>) {
+    ///         self.values.push(msg.0);
+    ///     }
+    /// }
+    /// struct StackFactory {
+    ///     observed: Arc<Mutex<Vec<Vec<u32>>>>,
+    /// }
+    /// impl ActorFactory<Stack> for StackFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stack>) -> Stack {
+    ///         Stack { values: Vec::new(), observed: self.observed.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let observed = Arc::new(Mutex::new(Vec::new()));
+    /// let stack = actor_system.builder().spawn("stack", StackFactory { observed: observed.clone() }).unwrap();
+    /// for i in 0..3 {
+    ///     stack.send(Push(i)).unwrap();
+    ///     stack.exec(|stack: &mut Stack, _context| stack.observed.lock().unwrap().push(stack.values.clone())).unwrap();
+    /// }
+    /// stack.exec(|stack: &mut Stack, _context| {
+    ///     stack.values.pop();
+    /// })
+    /// .unwrap();
+    /// stack.exec(|stack: &mut Stack, _context| stack.observed.lock().unwrap().push(stack.values.clone())).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![vec![0], vec![0, 1], vec![0, 1, 2], vec![0, 1]], *observed.lock().unwrap());
+    ///
+    /// // a panicking closure restarts the Actor like any other handler
+    /// stack.exec(|_stack: &mut Stack, _context| panic!("failed")).unwrap();
+    /// stack.exec(|stack: &mut Stack, _context| stack.observed.lock().unwrap().push(stack.values.clone())).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(Some(&vec![]), observed.lock().unwrap().last());
+    /// assert_eq!(1, actor_system.actor_stats(stack.get_address()).unwrap().restarts);
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn exec<F>(&self, function: F) -> Result<(), SendError<ExecuteFn<A>>>
+    where
+        F: FnOnce(&mut A, &ActorContext<A>) + Send + 'static,
+    {
+        self.send(ExecuteFn::new(function))
+    }
+
+    /// Same as [send](#method.send), but the message is skipped if it has not been handled within `ttl`
+    ///
+    /// Skipped messages are reported as [expired dead letters](../prelude/enum.DeadLetterReason.html#variant.Expired) and counted in [expired_count](#method.expired_count).
+    /// Takes precedence over [ActorBuilder.set_message_ttl](../prelude/struct.ActorBuilder.html#method.set_message_ttl)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Heartbeat(u32);
+    /// impl ActorMessage for Heartbeat {}
+    ///
+    /// struct Monitor {
+    ///     seen: Arc<Mutex<Vec<u32>>>,
+    /// }
+    /// impl Actor for Monitor {}
+    /// impl Handler<Heartbeat> for Monitor {
+    ///     fn handle(&mut self, msg: Heartbeat, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(100));
+    ///         self.seen.lock().unwrap().push(msg.0);
+    ///     }
+    /// }
+    /// struct MonitorFactory {
+    ///     seen: Arc<Mutex<Vec<u32>>>,
+    /// }
+    /// impl ActorFactory<Monitor> for MonitorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Monitor>) -> Monitor {
+    ///         Monitor { seen: self.seen.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Letters {
+    ///     expired: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl Actor for Letters {}
+    /// impl Handler<DeadLetter> for Letters {
+    ///     fn handle(&mut self, msg: DeadLetter, _context: &ActorContext<Self>) {
+    ///         if msg.reason == DeadLetterReason::Expired {
+    ///             self.expired.lock().unwrap().push(msg.type_name);
+    ///         }
+    ///     }
+    /// }
+    /// struct LettersFactory {
+    ///     expired: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl ActorFactory<Letters> for LettersFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Letters>) -> Letters {
+    ///         Letters { expired: self.expired.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let expired = Arc::new(Mutex::new(Vec::new()));
+    /// let letters = actor_system.builder().spawn("letters", LettersFactory { expired: expired.clone() }).unwrap();
+    /// actor_system.subscribe_dead_letters(&letters);
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let monitor = actor_system.builder().spawn("monitor", MonitorFactory { seen: seen.clone() }).unwrap();
+    ///
+    /// // the first heartbeat keeps the monitor busy, so that the second one expires while the third one is still in time
+    /// monitor.send(Heartbeat(1)).unwrap();
+    /// monitor.send_with_ttl(Heartbeat(2), Duration::from_millis(20)).unwrap();
+    /// monitor.send_with_ttl(Heartbeat(3), Duration::from_secs(10)).unwrap();
+    /// sleep(Duration::from_millis(500));
+    ///
+    /// assert_eq!(vec![1, 3], *seen.lock().unwrap());
+    /// assert_eq!(1, monitor.expired_count());
+    /// assert_eq!(vec![std::any::type_name::<Heartbeat>()], *expired.lock().unwrap());
+    /// ```
+    pub fn send_with_ttl<M>(&self, msg: M, ttl: Duration) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_message_from(msg, SendWait::Block, false, None, Some(ttl))
+    }
+
+    /// Same as [send](#method.send), but the returned [ReceiptHandle](../prelude/struct.ReceiptHandle.html) tells whether the handler completed, panicked or the message has been dropped
+    ///
+    /// Every receipt is settled eventually, messages discarded by an overflow policy, their time to live, a middleware or the stop of the Actor are settled as [Receipt::Dropped](../prelude/enum.Receipt.html#variant.Dropped) with the reason.
+    /// Messages with a receipt are never part of a batch of a [BatchHandler](../prelude/trait.BatchHandler.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job {
+    ///     fail: bool,
+    /// }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(100));
+    ///         if msg.fail {
+    ///             panic!("job failed");
+    ///         }
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let worker = actor_system
+    ///     .builder()
+    ///     .set_restart_policy(RestartPolicy::Always)
+    ///     .spawn("worker", WorkerFactory {})
+    ///     .unwrap();
+    /// let timeout = Duration::from_secs(3);
+    ///
+    /// // the receipt is only settled once the handler returned, not once the message has been queued
+    /// let handled = worker.send_with_receipt(Job { fail: false }).unwrap();
+    /// assert_eq!(None, handled.poll());
+    /// assert_eq!(Some(Receipt::Handled), handled.wait(timeout));
+    /// assert_eq!(Some(Receipt::Handled), handled.poll());
+    ///
+    /// // the restarted Actor handles the following messages
+    /// let panicked = worker.send_with_receipt(Job { fail: true }).unwrap();
+    /// let after_restart = worker.send_with_receipt(Job { fail: false }).unwrap();
+    /// assert_eq!(Some(Receipt::Panicked), panicked.wait(timeout));
+    /// assert_eq!(Some(Receipt::Handled), after_restart.wait(timeout));
+    ///
+    /// // messages still queued when the Actor stops immediately are dropped
+    /// let running = worker.send_with_receipt(Job { fail: false }).unwrap();
+    /// let queued = worker.send_with_receipt(Job { fail: false }).unwrap();
+    /// sleep(Duration::from_millis(25));
+    /// worker.stop_now();
+    /// assert_eq!(Some(Receipt::Handled), running.wait(timeout));
+    /// assert_eq!(Some(Receipt::Dropped(DeadLetterReason::Undeliverable)), queued.wait(timeout));
+    /// assert!(worker.send_with_receipt(Job { fail: false }).is_err());
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// ```
+    pub fn send_with_receipt<M>(&self, msg: M) -> Result<ReceiptHandle, SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let (slot, handle) = receipt::channel();
+        let mut envelope = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        envelope.set_receipt(slot);
+        self.send_typed_envelope(envelope, SendWait::Block).map(|_| handle)
+    }
+
+    /// Same as [send](#method.send), but the handler can answer through [ActorContext.reply](../prelude/struct.ActorContext.html#method.reply) without knowing the sender
+    ///
+    /// See [ActorContext.sender](../prelude/struct.ActorContext.html#method.sender)
+    pub fn send_from<M, R>(&self, msg: M, reply_to: ReplyTo<R>) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        R: ActorMessage + 'static,
+    {
+        self.send_message_from(msg, SendWait::Block, false, Some(ReplyPath::new(reply_to)), None)
+    }
+
+    /// Same as [send](#method.send), but returns immediately with [SendError::Full](../prelude/enum.SendError.html#variant.Full) if the bounded mailbox of the Actor is full
+    ///
+    /// Unbounded mailboxes are never full. Messages are never rejected for their size if neither `general.max_message_bytes` nor [ActorBuilder.set_max_message_bytes](../prelude/struct.ActorBuilder.html#method.set_max_message_bytes) is set,
+    /// messages are measured through [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size), see [oversized_count](#method.oversized_count)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Receiver, Sender};
+    /// use std::time::Duration;
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { started: Sender<usize>, gate: Receiver<()> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         self.started.send(msg.id).unwrap();
+    ///         let _ = self.gate.recv();
+    ///     }
+    /// }
+    /// struct WorkerFactory { started: Sender<usize>, gate: Receiver<()> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { started: self.started.clone(), gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (started, handling) = unbounded();
+    /// let (open, gate) = unbounded();
+    /// let (open_unbounded, unbounded_gate) = unbounded();
+    /// let factory = WorkerFactory { started: started.clone(), gate };
+    /// let bounded = actor_system.builder().set_mailbox_size(2).spawn("bounded", factory).unwrap();
+    /// let factory = WorkerFactory { started, gate: unbounded_gate };
+    /// let unbounded = actor_system.builder().set_mailbox_size(0).spawn("unbounded", factory).unwrap();
+    ///
+    /// // the first job blocks the handler, the next two fill the mailbox
+    /// bounded.try_send(Job { id: 0 }).unwrap();
+    /// assert_eq!(0, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// bounded.try_send(Job { id: 1 }).unwrap();
+    /// bounded.try_send(Job { id: 2 }).unwrap();
+    /// let error = bounded.try_send(Job { id: 3 }).unwrap_err();
+    /// assert!(matches!(error, SendError::Full(_)));
+    /// assert!(error.is_retryable());
+    /// let job = error.into_inner();
+    /// assert_eq!(3, job.id);
+    ///
+    /// // the handler is blocked as well, but the mailbox takes every message
+    /// unbounded.try_send(Job { id: 0 }).unwrap();
+    /// assert_eq!(0, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// for id in 1..1000 {
+    ///     assert!(unbounded.try_send(Job { id }).is_ok());
+    /// }
+    ///
+    /// // room is made once the handler continues, the rejected job can be retried
+    /// open.send(()).unwrap();
+    /// assert_eq!(1, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// assert!(bounded.try_send(job).is_ok());
+    ///
+    /// for _ in 0..1000 {
+    ///     open.send(()).unwrap();
+    ///     open_unbounded.send(()).unwrap();
+    /// }
+    /// bounded.stop();
+    /// unbounded.stop();
+    /// actor_system.stop(Duration::from_secs(3));
+    /// actor_system.await_shutdown();
+    /// assert!(matches!(bounded.try_send(Job { id: 4 }), Err(SendError::Stopped(Job { id: 4 }))));
+    /// assert!(matches!(unbounded.try_send(Job { id: 5 }), Err(SendError::Stopped(_))));
+    /// ```
+    ///
+    /// Message size limits:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Upload { bytes: Vec<u8> }
+    /// impl ActorMessage for Upload {
+    ///     fn approx_size(&self) -> usize {
+    ///         std::mem::size_of::<Self>() + self.bytes.capacity()
+    ///     }
+    /// }
+    /// struct Relay { upload: Upload, target: ActorWrapper<Store> }
+    /// impl ActorMessage for Relay {}
+    /// struct Shared { bytes: Arc<Vec<u8>> }
+    /// impl ActorMessage for Shared {}
+    /// impl SizeUnchecked for Shared {}
+    ///
+    /// struct Store { received: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Store {}
+    /// impl Handler<Upload> for Store {
+    ///     fn handle(&mut self, msg: Upload, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(msg.bytes.len());
+    ///     }
+    /// }
+    /// impl Handler<Relay> for Store {
+    ///     fn handle(&mut self, msg: Relay, _context: &ActorContext<Self>) {
+    ///         msg.target.send(msg.upload);
+    ///     }
+    /// }
+    /// impl Handler<Shared> for Store {
+    ///     fn handle(&mut self, msg: Shared, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(msg.bytes.len());
+    ///     }
+    /// }
+    /// struct StoreFactory { received: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Store> for StoreFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Store>) -> Store {
+    ///         Store { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Monitor { events: Arc<Mutex<Vec<OversizedMessage>>> }
+    /// impl Actor for Monitor {}
+    /// impl Handler<OversizedMessage> for Monitor {
+    ///     fn handle(&mut self, msg: OversizedMessage, _context: &ActorContext<Self>) {
+    ///         self.events.lock().unwrap().push(msg);
+    ///     }
+    /// }
+    /// struct MonitorFactory { events: Arc<Mutex<Vec<OversizedMessage>>> }
+    /// impl ActorFactory<Monitor> for MonitorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Monitor>) -> Monitor {
+    ///         Monitor { events: self.events.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.warn_message_bytes = Some(1024);
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.register_size_unchecked::<Shared>();
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let monitor = actor_system.builder().spawn("monitor", MonitorFactory { events: events.clone() }).unwrap();
+    /// actor_system.get_event_bus().subscribe_pattern::<OversizedMessage, _>(OVERSIZED_TOPIC, &monitor).unwrap();
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let store = actor_system
+    ///     .builder()
+    ///     .set_max_message_bytes(64 * 1024)
+    ///     .spawn("store", StoreFactory { received: received.clone() })
+    ///     .unwrap();
+    /// let relay = actor_system.builder().spawn("relay", StoreFactory { received: Arc::new(Mutex::new(Vec::new())) }).unwrap();
+    ///
+    /// // the oversized message never reaches the mailbox
+    /// let upload = Upload { bytes: vec![0; 100 * 1024] };
+    /// let size = upload.approx_size();
+    /// match store.try_send(upload) {
+    ///     Err(SendError::MessageTooLarge { msg, size: rejected, limit, type_name }) => {
+    ///         assert_eq!(size, rejected);
+    ///         assert_eq!(64 * 1024, limit);
+    ///         assert_eq!(std::any::type_name::<Upload>(), type_name);
+    ///         assert_eq!(100 * 1024, msg.bytes.len());
+    ///     }
+    ///     _ => panic!("the upload exceeds the limit"),
+    /// }
+    /// assert!(store.try_send(Upload { bytes: vec![0; 100] }).is_ok());
+    ///
+    /// // sends above the warning threshold are queued, but reported once with the sending Actor
+    /// for _ in 0..5 {
+    ///     relay.send(Relay { upload: Upload { bytes: vec![0; 2048] }, target: store.clone() });
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// // a regular send is dropped as well, types that opted out are never checked
+    /// store.send(Upload { bytes: vec![0; 100 * 1024] });
+    /// store.send(Shared { bytes: Arc::new(vec![0; 100 * 1024]) });
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// assert_eq!(vec![100, 2048, 2048, 2048, 2048, 2048, 100 * 1024], *received.lock().unwrap());
+    /// assert_eq!(2, store.oversized_count());
+    /// let events = events.lock().unwrap().clone();
+    /// assert_eq!(2, events.len());
+    /// assert!(events[0].is_rejected);
+    /// assert_eq!(None, events[0].sender);
+    /// let warning = &events[1];
+    /// assert!(!warning.is_rejected);
+    /// assert_eq!(Some(relay.get_address().clone()), warning.sender);
+    /// assert_eq!(store.get_address(), &warning.target);
+    /// assert_eq!(std::any::type_name::<Upload>(), warning.type_name);
+    /// assert_eq!(1024, warning.threshold);
+    /// ```
+    pub fn try_send<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_message(msg, SendWait::Never, false)
+    }
+
+    /// Queues all messages in order and asks the wakeup manager at most once to wake up the Actor, instead of once per send
+    ///
+    /// Every message is sent like through [try_send](#method.try_send), with the headers that are propagated at this point.
+    /// The first message that is not accepted ends the batch, the error hands it back together with all messages that have not been sent yet.
+    /// Messages dropped by the overflow policy, by memory pressure or by the cycle detection count as accepted, like for a single send.
+    /// Returns the amount of accepted messages. Messages of the batch stay in order with sends of the same thread before and after it
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Sample { id: usize }
+    /// impl ActorMessage for Sample {}
+    /// struct Pause {}
+    /// impl ActorMessage for Pause {}
+    ///
+    /// struct Recorder { received: Arc<Mutex<Vec<usize>>>, paused: Sender<SuspensionToken> }
+    /// impl Actor for Recorder {}
+    /// impl Handler<Sample> for Recorder {
+    ///     fn handle(&mut self, msg: Sample, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(msg.id);
+    ///     }
+    /// }
+    /// impl Handler<Pause> for Recorder {
+    ///     fn handle(&mut self, _msg: Pause, context: &ActorContext<Self>) {
+    ///         self.paused.send(context.suspend_until(SuspendCondition::External)).unwrap();
+    ///     }
+    /// }
+    /// struct RecorderFactory { received: Arc<Mutex<Vec<usize>>>, paused: Sender<SuspensionToken> }
+    /// impl ActorFactory<Recorder> for RecorderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Recorder>) -> Recorder {
+    ///         Recorder { received: self.received.clone(), paused: self.paused.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (paused, tokens) = unbounded();
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = RecorderFactory { received: received.clone(), paused };
+    /// let recorder = actor_system.builder().set_mailbox_unbounded().spawn("recorder", factory).unwrap();
+    ///
+    /// // the suspended Actor sleeps, every single send has to ask for a wakeup
+    /// recorder.send(Pause {}).unwrap();
+    /// let token = tokens.recv_timeout(Duration::from_secs(3)).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// let before = recorder.wakeup_request_count();
+    /// for id in 0..10_000 {
+    ///     recorder.send(Sample { id }).unwrap();
+    /// }
+    /// let single = recorder.wakeup_request_count() - before;
+    /// sleep(Duration::from_millis(100));
+    /// let before = recorder.wakeup_request_count();
+    /// assert_eq!(10_000, recorder.send_batch((10_000..20_000).map(|id| Sample { id })).unwrap());
+    /// let batched = recorder.wakeup_request_count() - before;
+    /// assert!(batched <= 1);
+    /// assert!(single > 100 * batched);
+    /// token.resume();
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!((0..20_000).collect::<Vec<usize>>(), *received.lock().unwrap());
+    ///
+    /// // a bounded mailbox that is too small hands back the rest of the batch
+    /// received.lock().unwrap().clear();
+    /// let small = RecorderFactory { received: received.clone(), paused: unbounded().0 };
+    /// let small = actor_system.builder().set_mailbox_size(4).start_suspended(true).spawn("small", small).unwrap();
+    /// let rest = match small.send_batch((0..10).map(|id| Sample { id })) {
+    ///     Err(SendError::Full(rest)) => rest,
+    ///     _ => panic!("the mailbox only holds 4 messages"),
+    /// };
+    /// assert_eq!((4..10).collect::<Vec<usize>>(), rest.iter().map(|sample| sample.id).collect::<Vec<usize>>());
+    /// small.release();
+    /// sleep(Duration::from_millis(200));
+    /// // regular sends wait for room
+    /// for sample in rest {
+    ///     small.send(sample).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!((0..10).collect::<Vec<usize>>(), *received.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send_batch<M, I>(&self, msgs: I) -> Result<usize, SendError<Vec<M>>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        I: IntoIterator<Item = M>,
+    {
+        let mut msgs = msgs.into_iter();
+        let mut accepted = 0;
+        let mut rejected = None;
+        for msg in msgs.by_ref() {
+            match self.queue_message(msg) {
+                Ok(()) => accepted += 1,
+                Err(error) => {
+                    rejected = Some(error);
+                    break;
+                }
+            }
+        }
+        if accepted > 0 {
+            yield_point();
+            self.wakeup_if_sleeping();
+        }
+        match rejected {
+            None => Ok(accepted),
+            Some(error) => Err(error.map(|msg| {
+                let mut rest = Vec::with_capacity(msgs.size_hint().0 + 1);
+                rest.push(msg);
+                rest.extend(msgs);
+                rest
+            })),
+        }
+    }
+
+    /// Same as [send](#method.send), but waits at most `timeout` for room in the bounded mailbox of the Actor and returns [SendError::Timeout](../prelude/enum.SendError.html#variant.Timeout) otherwise
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Receiver, Sender};
+    /// use std::thread::{sleep, spawn};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { started: Sender<usize>, gate: Receiver<()> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         self.started.send(msg.id).unwrap();
+    ///         let _ = self.gate.recv();
+    ///     }
+    /// }
+    /// struct WorkerFactory { started: Sender<usize>, gate: Receiver<()> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { started: self.started.clone(), gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (started, handling) = unbounded();
+    /// let (open, gate) = unbounded();
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .spawn("worker", WorkerFactory { started, gate })
+    ///     .unwrap();
+    /// actor.send(Job { id: 0 }).unwrap();
+    /// assert_eq!(0, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// actor.send(Job { id: 1 }).unwrap();
+    ///
+    /// let started = Instant::now();
+    /// let error = actor.send_timeout(Job { id: 2 }, Duration::from_millis(100)).unwrap_err();
+    /// assert!(started.elapsed() >= Duration::from_millis(100));
+    /// assert!(matches!(error, SendError::Timeout(Job { id: 2 })));
+    ///
+    /// // the mailbox has room again before the timeout elapses
+    /// let opener = spawn(move || {
+    ///     sleep(Duration::from_millis(50));
+    ///     open.send(()).unwrap();
+    ///     open
+    /// });
+    /// assert!(actor.send_timeout(error.into_inner(), Duration::from_secs(3)).is_ok());
+    /// let open = opener.join().unwrap();
+    /// assert_eq!(1, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// open.send(()).unwrap();
+    /// assert_eq!(2, handling.recv_timeout(Duration::from_secs(3)).unwrap());
+    /// open.send(()).unwrap();
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send_timeout<M>(&self, msg: M, timeout: Duration) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_message(msg, SendWait::Until(Instant::now() + timeout), false)
+    }
+
+    /// Same as [send](#method.send), but yields instead of blocking the thread while the bounded mailbox of the Actor is full
+    ///
+    /// Completes once the message has been queued. Waiting sends are woken whenever the Actor made room in its mailbox or stopped, no thread is spawned for them
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { context: ActorContext<Self>, handled: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(1));
+    ///         self.handled.lock().unwrap().push(msg.id);
+    ///     }
+    /// }
+    /// struct WorkerFactory { handled: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context, handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let worker = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .spawn("worker", WorkerFactory { handled: handled.clone() })
+    ///     .unwrap();
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let ticks = Arc::new(AtomicUsize::new(0));
+    /// runtime.block_on(async {
+    ///     let ticker = {
+    ///         let ticks = ticks.clone();
+    ///         tokio::spawn(async move {
+    ///             loop {
+    ///                 ticks.fetch_add(1, Ordering::Relaxed);
+    ///                 tokio::task::yield_now().await;
+    ///             }
+    ///         })
+    ///     };
+    ///     for id in 0..100 {
+    ///         worker.send_async(Job { id }).await.unwrap();
+    ///     }
+    ///     ticker.abort();
+    /// });
+    /// // the only thread of the runtime kept executing other tasks while the sends waited for room
+    /// assert!(ticks.load(Ordering::Relaxed) > 0);
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, runtime.block_on(actor_system.await_shutdown_async()));
+    /// assert_eq!((0..100).collect::<Vec<usize>>(), *handled.lock().unwrap());
+    /// assert!(matches!(runtime.block_on(worker.send_async(Job { id: 100 })), Err(SendError::Stopped(_))));
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn send_async<M>(&self, msg: M) -> SendAsync<'_, A, M>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        SendAsync::new(self, msg)
+    }
+
+    /// Same as [send](#method.send), but the message is queued in the priority lane of the mailbox and handled before all messages of the regular lane
+    ///
+    /// The priority lane is unbounded and never waits for room, stop messages and supervision notifications are queued in it as well.
+    /// Messages of the priority lane are handled in the order they have been sent and count towards the message throughput of the Actor like any other message,
+    /// so a steady stream of them does not keep the Actor from giving its thread back to the pool
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Receiver};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    /// struct Urgent {}
+    /// impl ActorMessage for Urgent {}
+    ///
+    /// struct Worker { handled: Arc<Mutex<Vec<String>>>, gate: Receiver<()> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         let _ = self.gate.recv();
+    ///         self.handled.lock().unwrap().push(format!("job {}", msg.id));
+    ///     }
+    /// }
+    /// impl Handler<Urgent> for Worker {
+    ///     fn handle(&mut self, _msg: Urgent, _context: &ActorContext<Self>) {
+    ///         self.handled.lock().unwrap().push(String::from("urgent"));
+    ///     }
+    /// }
+    /// struct WorkerFactory { handled: Arc<Mutex<Vec<String>>>, gate: Receiver<()> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { handled: self.handled.clone(), gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let (open, gate) = unbounded();
+    /// let factory = WorkerFactory { handled: handled.clone(), gate };
+    /// let worker = actor_system.builder().set_mailbox_size(3).spawn("worker", factory).unwrap();
+    /// worker.send(Job { id: 0 }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// for id in 1..4 {
+    ///     worker.send(Job { id }).unwrap();
+    /// }
+    /// // the regular lane is full, the priority lane is not
+    /// assert!(worker.try_send(Job { id: 4 }).is_err());
+    /// worker.send_priority(Urgent {}).unwrap();
+    ///
+    /// for _ in 0..4 {
+    ///     open.send(()).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(300));
+    /// // the first job was already in progress
+    /// assert_eq!(vec!["job 0", "urgent", "job 1", "job 2", "job 3"], *handled.lock().unwrap());
+    /// ```
+    pub fn send_priority<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_message(msg, SendWait::Never, true)
+    }
+
+    /// Sends a message with additional [Headers](../prelude/struct.Headers.html)
+    ///
+    /// Headers propagated from the message that is currently handled are sent as well, explicit headers take precedence
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Hop {}
+    /// impl ActorMessage for Hop {}
+    ///
+    /// struct Relay {
+    ///     next: Option<ActorWrapper<Relay>>,
+    ///     seen: Arc<Mutex<Vec<Headers>>>,
+    /// }
+    /// impl Actor for Relay {}
+    /// impl Handler<Hop> for Relay {
+    ///     fn handle(&mut self, _msg: Hop, context: &ActorContext<Self>) {
+    ///         self.seen.lock().unwrap().push(context.headers());
+    ///         if let Some(next) = &self.next {
+    ///             next.send(Hop {});
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct RelayFactory {
+    ///     next: Option<ActorWrapper<Relay>>,
+    ///     seen: Arc<Mutex<Vec<Headers>>>,
+    /// }
+    /// impl ActorFactory<Relay> for RelayFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Relay>) -> Relay {
+    ///         Relay { next: self.next.clone(), seen: self.seen.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let tenant = actor_system.register_header("tenant", Propagate::Always);
+    /// let auth = actor_system.register_header("auth", Propagate::Never);
+    /// let hops = actor_system.register_header("hops", Propagate::Decrement);
+    ///
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let mut next = None;
+    /// for name in ["d", "c", "b", "a"].iter() {
+    ///     let factory = RelayFactory { next: next.clone(), seen: seen.clone() };
+    ///     next = Some(actor_system.builder().spawn(*name, factory).unwrap());
+    /// }
+    /// let headers = Headers::new()
+    ///     .with(tenant, HeaderValue::Str("acme".into()))
+    ///     .with(auth, HeaderValue::U64(42))
+    ///     .with(hops, HeaderValue::U64(2));
+    /// next.unwrap().send_with_headers(Hop {}, headers);
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let seen = seen.lock().unwrap();
+    /// assert_eq!(4, seen.len());
+    /// for headers in seen.iter() {
+    ///     assert_eq!(Some(&HeaderValue::Str("acme".into())), headers.get(tenant));
+    /// }
+    /// assert_eq!(Some(&HeaderValue::U64(42)), seen[0].get(auth));
+    /// assert_eq!(None, seen[1].get(auth));
+    /// let hops: Vec<Option<&HeaderValue>> = seen.iter().map(|h| h.get(hops)).collect();
+    /// assert_eq!(vec![Some(&HeaderValue::U64(2)), Some(&HeaderValue::U64(1)), Some(&HeaderValue::U64(0)), None], hops);
+    /// ```
+    pub fn send_with_headers<M>(&self, msg: M, headers: Headers)
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if size_limit.admit(&msg, &self.address).is_err() {
+                return;
+            }
+        }
+        let mut merged = headers::get_propagated().unwrap_or_default();
+        merged.merge(headers);
+        let _ = self.send_envelope(MessageEnvelope::with_headers(msg, Some(merged)), SendWait::Block);
+    }
+
+    /// Sends a message that carries the given [TraceId](../prelude/struct.TraceId.html)
+    ///
+    /// Every message sent while a traced message is handled inherits its trace id, see [ActorContext.trace_id](../prelude/struct.ActorContext.html#method.trace_id).
+    /// The trace id is part of the [Headers](../prelude/struct.Headers.html) of the message, untraced messages do not carry any
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Step { flow: u64 }
+    /// impl ActorMessage for Step {}
+    ///
+    /// type Log = Arc<Mutex<Vec<(&'static str, u64, Option<TraceId>)>>>;
+    ///
+    /// struct Stage { name: &'static str, next: Option<ActorWrapper<Stage>>, log: Log }
+    /// impl Actor for Stage {}
+    /// impl Handler<Step> for Stage {
+    ///     fn handle(&mut self, msg: Step, context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push((self.name, msg.flow, context.trace_id()));
+    ///         if let Some(next) = &self.next {
+    ///             next.send(Step { flow: msg.flow });
+    ///         }
+    ///     }
+    /// }
+    /// struct StageFactory { name: &'static str, next: Option<ActorWrapper<Stage>>, log: Log }
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stage>) -> Stage {
+    ///         Stage { name: self.name, next: self.next.clone(), log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Log::default();
+    /// let mut next = None;
+    /// for name in ["c", "b", "a"].iter() {
+    ///     let factory = StageFactory { name: *name, next: next.clone(), log: log.clone() };
+    ///     next = Some(actor_system.builder().spawn(*name, factory).unwrap());
+    /// }
+    /// let first = next.unwrap();
+    ///
+    /// // interleaved flows keep their own trace id on every hop, untraced flows stay untraced
+    /// for flow in 0..20 {
+    ///     match flow % 4 {
+    ///         0 => first.send(Step { flow }).unwrap(),
+    ///         _ => first.send_traced(Step { flow }, TraceId(1000 + flow)),
+    ///     }
+    /// }
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// let log = log.lock().unwrap();
+    /// assert_eq!(60, log.len());
+    /// for (_, flow, trace_id) in log.iter() {
+    ///     let expected = if flow % 4 == 0 { None } else { Some(TraceId(1000 + flow)) };
+    ///     assert_eq!(expected, *trace_id);
+    /// }
+    /// assert_eq!(3, log.iter().filter(|(_, flow, _)| *flow == 5).count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send_traced<M>(&self, msg: M, trace_id: TraceId)
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_with_headers(msg, Headers::new().with(TRACE_HEADER.1, trace_id.to_header()));
+    }
+
+    /// Sends a message that is only constructed by `f` right before it is handled
+    ///
+    /// Messages that are never handled never pay for their construction, i.e. sends to stopped or shedding Actors and messages dropped by stopping unreleased Actors.
+    /// Until then the message reports the type name of `M`, payloads of lazy messages are never captured.
+    /// A panic in `f` is treated like a panic of the handler, see [ActorWrapper.construction_time](#method.construction_time) for the time spent in `f`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Snapshot { bytes: Vec<u8> }
+    /// impl ActorMessage for Snapshot {}
+    ///
+    /// struct Store { handled: Arc<AtomicUsize>, panics: Arc<Mutex<Vec<&'static str>>> }
+    /// impl Actor for Store {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         self.panics.lock().unwrap().push(report.message_type);
+    ///     }
+    /// }
+    /// impl Handler<Snapshot> for Store {
+    ///     fn handle(&mut self, msg: Snapshot, _context: &ActorContext<Self>) {
+    ///         assert_eq!(1024, msg.bytes.len());
+    ///         self.handled.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    /// struct StoreFactory { handled: Arc<AtomicUsize>, panics: Arc<Mutex<Vec<&'static str>>> }
+    /// impl ActorFactory<Store> for StoreFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Store>) -> Store {
+    ///         Store { handled: self.handled.clone(), panics: self.panics.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(AtomicUsize::new(0));
+    /// let panics = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = || StoreFactory { handled: handled.clone(), panics: panics.clone() };
+    /// let constructed = Arc::new(AtomicUsize::new(0));
+    /// let snapshot = || {
+    ///     let constructed = constructed.clone();
+    ///     move || {
+    ///         constructed.fetch_add(1, Ordering::SeqCst);
+    ///         sleep(Duration::from_millis(20));
+    ///         Snapshot { bytes: vec![0; 1024] }
+    ///     }
+    /// };
+    ///
+    /// let store = actor_system.builder().spawn("store", factory()).unwrap();
+    /// for _ in 0..5 {
+    ///     store.send_lazy(snapshot());
+    /// }
+    /// sleep(Duration::from_millis(400));
+    /// assert_eq!(5, constructed.load(Ordering::SeqCst));
+    /// assert_eq!(5, handled.load(Ordering::SeqCst));
+    /// assert!(store.construction_time() >= Duration::from_millis(100));
+    /// assert!(store.handler_time() < store.construction_time());
+    ///
+    /// // neither a stopped Actor nor an Actor that is stopped before its release construct anything
+    /// store.stop();
+    /// let unreleased = actor_system.builder().start_suspended(true).spawn("unreleased", factory()).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// for _ in 0..3 {
+    ///     store.send_lazy(snapshot());
+    ///     unreleased.send_lazy(snapshot());
+    /// }
+    /// unreleased.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(5, constructed.load(Ordering::SeqCst));
+    ///
+    /// // a panicking constructor restarts the Actor like a panicking handler
+    /// let fragile = actor_system.builder().spawn("fragile", factory()).unwrap();
+    /// fragile.send_lazy(|| -> Snapshot { panic!("snapshot failed") });
+    /// fragile.send_lazy(snapshot());
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(vec![std::any::type_name::<Snapshot>()], *panics.lock().unwrap());
+    /// assert_eq!(6, handled.load(Ordering::SeqCst));
+    /// ```
+    pub fn send_lazy<M, F>(&self, f: F)
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        F: FnOnce() -> M + Send + 'static,
+    {
+        let _ = self.send_envelope(MessageEnvelope::lazy(Constructor::new(f), headers::get_propagated()), SendWait::Block);
+    }
+
+    pub(crate) fn send_constructor<M>(&self, constructor: Constructor<M>)
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        let _ = self.send_envelope(MessageEnvelope::lazy(constructor, headers::get_propagated()), SendWait::Block);
+    }
+
+    fn send_envelope(&self, msg: MessageEnvelope<A>, wait: SendWait) -> Queued<A> {
+        if cycle_detection::is_cut(&self.address, msg.get_type_id(), msg.get_type_name()) {
+            return Queued::Done(BatchSendStatus::CycleCut);
+        }
+        #[cfg(feature = "testing")]
+        if let Some(interactions) = &self.mailbox.interactions {
+            interactions.record_send(&self.address, &msg, self.mailbox.is_stopped());
+        }
+        let queued = self.deliver_envelope(msg, wait);
+        if let Queued::Stopped(msg) = &queued {
+            self.report_dead_letter(msg);
+        }
+        queued
+    }
+
+    /// Same as [try_send](#method.try_send), without waking up the Actor, see [send_batch](#method.send_batch)
+    fn queue_message<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let envelope = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        if cycle_detection::is_cut(&self.address, envelope.get_type_id(), envelope.get_type_name()) {
+            return Ok(());
+        }
+        #[cfg(feature = "testing")]
+        if let Some(interactions) = &self.mailbox.interactions {
+            interactions.record_send(&self.address, &envelope, self.mailbox.is_stopped());
+        }
+        let rejected = |msg: MessageEnvelope<A>| -> M {
+            msg.into_message().expect("envelopes of regular sends hold their message until they are handled")
+        };
+        match self.queue_envelope(envelope, SendWait::Never) {
+            Queued::Done(_) => Ok(()),
+            Queued::Stopped(msg) => {
+                self.report_dead_letter(&msg);
+                Err(SendError::Stopped(rejected(msg)))
+            }
+            Queued::Full(msg) => Err(SendError::Full(rejected(msg))),
+            Queued::Overloaded(msg) => Err(SendError::Overloaded(rejected(msg))),
+            Queued::WouldDeadlock(msg) => Err(SendError::WouldDeadlock(rejected(msg))),
+        }
+    }
+
+    /// Same as [try_send](#method.try_send), with exactly the given headers instead of the propagated ones
+    pub(crate) fn try_send_with_headers<M>(&self, msg: M, headers: Headers) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        self.send_typed_envelope(MessageEnvelope::with_headers(msg, Some(headers)), SendWait::Never)
+    }
+
+    /// Wraps the message into an envelope and hands it back if it has not been queued, `urgent` envelopes are queued in the priority lane
+    fn send_message<M>(&self, msg: M, wait: SendWait, urgent: bool) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.send_message_from(msg, wait, urgent, None, None)
+    }
+
+    fn send_message_from<M>(
+        &self,
+        msg: M,
+        wait: SendWait,
+        urgent: bool,
+        sender: Option<ReplyPath>,
+        ttl: Option<Duration>,
+    ) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let mut envelope = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        if urgent {
+            envelope.set_urgent();
+        }
+        if let Some(sender) = sender {
+            envelope.set_reply_path(sender);
+        }
+        if let Some(ttl) = ttl {
+            envelope.set_expires_at(Instant::now() + ttl);
+        }
+        self.send_typed_envelope(envelope, wait)
+    }
+
+    /// Same as [try_send](#method.try_send), but tells a queued message apart from one the target dropped right away, e.g. through its overflow policy
+    pub(crate) fn try_send_with_status<M>(&self, msg: M) -> Result<BatchSendStatus, SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let envelope = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        self.send_typed_envelope_with_status(envelope, SendWait::Never)
+    }
+
+    /// Queues `msg` in the continuation lane without waiting for room, see [ActorContext.send_to_self](../prelude/struct.ActorContext.html#method.send_to_self)
+    pub(crate) fn send_continuation<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let mut envelope = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        envelope.set_continuation();
+        self.send_typed_envelope(envelope, SendWait::Never)
+    }
+
+    /// Sends an envelope of a regular send, hands the message back if it has not been queued
+    fn send_typed_envelope<M>(&self, envelope: MessageEnvelope<A>, wait: SendWait) -> Result<(), SendError<M>>
+    where
+        M: ActorMessage + 'static,
+    {
+        self.send_typed_envelope_with_status(envelope, wait).map(|_| ())
+    }
+
+    fn send_typed_envelope_with_status<M>(&self, envelope: MessageEnvelope<A>, wait: SendWait) -> Result<BatchSendStatus, SendError<M>>
+    where
+        M: ActorMessage + 'static,
+    {
+        let rejected = |msg: MessageEnvelope<A>| -> M {
+            msg.into_message().expect("envelopes of regular sends hold their message until they are handled")
+        };
+        match self.send_envelope(envelope, wait) {
+            Queued::Done(status) => Ok(status),
+            Queued::Stopped(msg) => Err(SendError::Stopped(rejected(msg))),
+            Queued::Full(msg) if wait == SendWait::Never || self.mailbox.overflow_policy == MailboxOverflowPolicy::Fail => {
+                Err(SendError::Full(rejected(msg)))
+            }
+            Queued::Full(msg) => Err(SendError::Timeout(rejected(msg))),
+            Queued::Overloaded(msg) => Err(SendError::Overloaded(rejected(msg))),
+            Queued::WouldDeadlock(msg) => Err(SendError::WouldDeadlock(rejected(msg))),
+        }
+    }
+
+    /// Queues `msg` with the metadata of the message it has been forwarded from, see [ActorContext.forward](../prelude/struct.ActorContext.html#method.forward)
+    pub(crate) fn forward_message<M>(
+        &self,
+        msg: M,
+        headers: Option<Headers>,
+        sender: Option<ReplyPath>,
+        expires_at: Option<Instant>,
+    ) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &self.mailbox.size_limit {
+            if let Err(oversized) = size_limit.admit(&msg, &self.address) {
+                return Err(oversized.into_error(msg));
+            }
+        }
+        let mut envelope = MessageEnvelope::with_headers(msg, headers);
+        if let Some(sender) = sender {
+            envelope.set_reply_path(sender);
+        }
+        if let Some(expires_at) = expires_at {
+            envelope.set_expires_at(expires_at);
+        }
+        self.send_typed_envelope(envelope, SendWait::Block)
+    }
+
+    /// Same as a regular send, but the caller is responsible for waking up the Actor, see [SendBatch](../prelude/struct.SendBatch.html)
+    pub(crate) fn send_staged(&self, msg: MessageEnvelope<A>) -> BatchSendStatus {
+        if cycle_detection::is_cut(&self.address, msg.get_type_id(), msg.get_type_name()) {
+            return BatchSendStatus::CycleCut;
+        }
+        #[cfg(feature = "testing")]
+        if let Some(interactions) = &self.mailbox.interactions {
+            interactions.record_send(&self.address, &msg, self.mailbox.is_stopped());
+        }
+        match self.queue_envelope(msg, SendWait::Block) {
+            Queued::Done(status) => status,
+            // staged sends wait for room unless the overflow policy fails them, there is no sender to hand them back to
+            Queued::Full(msg) | Queued::WouldDeadlock(msg) => {
+                self.drop_overflow(msg);
+                BatchSendStatus::Dropped
+            }
+            Queued::Stopped(msg) => {
+                self.report_dead_letter(&msg);
+                BatchSendStatus::Stopped
+            }
+            Queued::Overloaded(_) => BatchSendStatus::Shed,
+        }
+    }
+
+    fn deliver_envelope(&self, msg: MessageEnvelope<A>, wait: SendWait) -> Queued<A> {
+        let queued = self.queue_envelope(msg, wait);
+        if let Queued::Done(BatchSendStatus::Delivered | BatchSendStatus::DeliveredAfterWait) = queued {
+            yield_point();
+            self.wakeup_if_sleeping();
+        }
+        queued
+    }
+
+    /// Queues the envelope without waking up the Actor, unless the mailbox is full and the send waits for room
+    fn queue_envelope(&self, mut msg: MessageEnvelope<A>, wait: SendWait) -> Queued<A> {
+        let instrumentation = self.mailbox.instrumentation.get();
+        if (self.mailbox.slo_status.is_some() || self.mailbox.middlewares.is_some() || instrumentation.is_some()) && msg.get_enqueued().is_none() {
+            msg.set_enqueued(Instant::now());
+        }
+        if let Some(message_ttl) = self.mailbox.message_ttl {
+            if msg.get_expires_at().is_none() && !msg.is_urgent() && !handoff::is_control_message(&msg) {
+                msg.set_expires_at(Instant::now() + message_ttl);
+            }
+        }
+        if self.mailbox.is_redirected() {
+            let mut handoff = self.mailbox.handoff.lock().unwrap();
+            match &handoff.redirect {
+                Redirect::Park => {
+                    handoff.parked.push_back(msg);
+                    return Queued::Done(BatchSendStatus::Redirected);
+                }
+                Redirect::Target(target) => {
+                    let target = target.as_ref().clone();
+                    drop(handoff);
+                    return match target.deliver_envelope(msg, wait) {
+                        Queued::Done(_) => Queued::Done(BatchSendStatus::Redirected),
+                        rejected => rejected,
+                    };
+                }
+                Redirect::None => {}
+            }
+        }
+
+        if self.mailbox.is_stopped() || self.mailbox.is_system_stopped() {
+            return Queued::Stopped(msg);
+        }
+        if self.is_shed(&msg) {
+            msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Overloaded));
+            self.metrics.increment_shed();
+            self.mailbox
+                .dead_letters
+                .report_drop(&self.address, msg.get_type_name(), DeadLetterReason::Overloaded);
+            return Queued::Done(BatchSendStatus::Shed);
+        }
+        if let Some(budget) = &self.mailbox.message_budget {
+            if budget.is_exceeded() && !handoff::is_control_message(&msg) {
+                budget.increment_shed();
+                match budget.get_policy() {
+                    SheddingPolicy::Reject => return Queued::Overloaded(msg),
+                    SheddingPolicy::DeadLetter => {
+                        msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Overloaded));
+                        let trace_id = trace::of(msg.get_headers());
+                        self.mailbox.dead_letters.report_traced(
+                            &self.address,
+                            msg.get_type_name(),
+                            DeadLetterReason::Overloaded,
+                            trace_id,
+                        );
+                    }
+                    SheddingPolicy::Drop => {
+                        msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Overloaded));
+                        self.mailbox
+                            .dead_letters
+                            .report_drop(&self.address, msg.get_type_name(), DeadLetterReason::Overloaded);
+                    }
+                }
+                return Queued::Done(BatchSendStatus::Shed);
+            }
+        }
+
+        if let Some(bytes) = &self.mailbox.bytes {
+            if !msg.is_urgent() && !msg.is_continuation() && !handoff::is_control_message(&msg) {
+                msg = match self.reserve_bytes(bytes, msg, wait) {
+                    Ok(msg) => msg,
+                    Err(queued) => return queued,
+                };
+            }
+        }
+
+        // reported before the message is queued, so that its handling is never reported first
+        if let Some(hook) = &instrumentation {
+            hook.on_send(&self.address, msg.get_type_name());
+        }
+        let superseding = self.stamp(&mut msg);
+        self.attach_pressure(&mut msg);
+        self.attach_budget(&mut msg);
+        let inherited = match msg.is_urgent() || msg.is_continuation() {
+            true => None,
+            false => priority::inherited(msg.get_headers(), self.mailbox.priority.get_own()),
+        };
+        let status = match inherited {
+            Some((priority, chain)) => {
+                self.mailbox.priority.push(priority, chain, msg);
+                BatchSendStatus::Delivered
+            }
+            // the Actor has to be awake to make room in its mailbox
+            None => match self.mailbox.push(msg, wait, || self.wakeup_if_sleeping(), |dropped| self.drop_overflow(dropped)) {
+                Ok(Pushed::Queued) => BatchSendStatus::Delivered,
+                Ok(Pushed::QueuedAfterWait) => BatchSendStatus::DeliveredAfterWait,
+                Ok(Pushed::Dropped) => BatchSendStatus::Dropped,
+                Err(PushError::Full(msg)) => return Queued::Full(msg),
+                Err(PushError::WouldDeadlock(msg)) => return Queued::WouldDeadlock(msg),
+                Err(PushError::Disconnected(msg)) => return Queued::Stopped(msg),
+            },
+        };
+        self.supersede(superseding);
+        Queued::Done(status)
+    }
+
+    /// Queues an envelope the system already accepted, e.g. one that is moved by a handoff, ignoring stop and handoff state
+    ///
+    /// Never waits for room: the envelope follows the overflow policy of the mailbox like a [try_send](#method.try_send), an envelope that can not be queued is reported as dead letter.
+    /// Control messages that do not fit are queued as urgent instead, so that a stop queued by a handoff always arrives
+    pub(crate) fn enqueue(&self, msg: MessageEnvelope<A>) {
+        if handoff::is_control_message(&msg) {
+            if let Err(msg) = self.try_enqueue(msg) {
+                let _ = self.mailbox.urgent_in.send(msg);
+                self.wakeup_if_sleeping();
+            }
             return;
         }
+        let mut msg = msg;
+        let superseding = self.stamp(&mut msg);
+        self.attach_pressure(&mut msg);
+        self.attach_budget(&mut msg);
+        match self.mailbox.push(msg, SendWait::Never, || {}, |dropped| self.drop_overflow(dropped)) {
+            Ok(_) => self.supersede(superseding),
+            Err(PushError::Full(mut msg)) | Err(PushError::WouldDeadlock(mut msg)) => {
+                msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Overflow));
+                self.metrics.increment_dropped();
+                self.report_dead_letter_with_reason(&msg, DeadLetterReason::Overflow);
+            }
+            Err(PushError::Disconnected(msg)) => self.report_dead_letter(&msg),
+        }
+        self.wakeup_if_sleeping();
+    }
+
+    /// Queues the envelope behind everything that is queued, ignoring stop and handoff state, hands it back if the mailbox is full
+    pub(crate) fn try_enqueue(&self, mut msg: MessageEnvelope<A>) -> Result<(), MessageEnvelope<A>> {
+        let superseding = self.stamp(&mut msg);
+        self.attach_pressure(&mut msg);
+        self.attach_budget(&mut msg);
+        if let Err(error) = self.mailbox.get_lane(msg.get_type_id()).0.try_send(msg) {
+            return Err(error.into_inner());
+        }
+        self.supersede(superseding);
+        self.wakeup_if_sleeping();
+        Ok(())
+    }
+
+    /// Counts the message towards the bytes of the mailbox, messages beyond `max_mailbox_bytes` are treated like sends to a full mailbox
+    ///
+    /// [DropOldest](../prelude/enum.MailboxOverflowPolicy.html#variant.DropOldest) drops the oldest messages of the lane of the message until it fits,
+    /// [Block](../prelude/enum.MailboxOverflowPolicy.html#variant.Block) waits until the Actor handled enough messages
+    fn reserve_bytes(&self, bytes: &Arc<MailboxBytes>, mut msg: MessageEnvelope<A>, wait: SendWait) -> Result<MessageEnvelope<A>, Queued<A>> {
+        let size = msg.approx_size();
+        let mut is_waiting = false;
+        loop {
+            if let Some(token) = bytes.try_reserve(size) {
+                msg.set_bytes(Some(token));
+                return Ok(msg);
+            }
+            if self.mailbox.is_stopped() || self.mailbox.is_system_stopped() {
+                return Err(Queued::Stopped(msg));
+            }
+            match self.mailbox.overflow_policy {
+                MailboxOverflowPolicy::Block => {}
+                MailboxOverflowPolicy::Fail => return Err(Queued::Full(msg)),
+                MailboxOverflowPolicy::DropNewest => {
+                    self.drop_overflow(msg);
+                    return Err(Queued::Done(BatchSendStatus::Dropped));
+                }
+                MailboxOverflowPolicy::DropOldest => {
+                    let msg_out = self.mailbox.get_lane(msg.get_type_id()).1;
+                    if self.mailbox.drop_oldest(msg_out, &mut |dropped| self.drop_overflow(dropped)) {
+                        continue;
+                    }
+                }
+            }
+            if wait == SendWait::Never {
+                return Err(Queued::Full(msg));
+            }
+            // only the waiting Actor could release bytes
+            if self.mailbox.is_executing() {
+                return Err(Queued::WouldDeadlock(msg));
+            }
+            if !is_waiting {
+                is_waiting = true;
+                self.wakeup_if_sleeping();
+            }
+            let deadline = match wait {
+                SendWait::Until(deadline) => Some(deadline),
+                _ => None,
+            };
+            if !bytes.wait(size, deadline) {
+                return Err(Queued::Full(msg));
+            }
+        }
+    }
+
+    /// Envelope dropped by the overflow policy of the mailbox, see [ActorBuilder.set_overflow_policy](../prelude/struct.ActorBuilder.html#method.set_overflow_policy)
+    pub(crate) fn drop_overflow(&self, mut msg: MessageEnvelope<A>) {
+        msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Overflow));
+        self.metrics.increment_dropped();
+        if self.mailbox.overflow_dead_letters {
+            self.report_dead_letter_with_reason(&msg, DeadLetterReason::Overflow);
+        } else {
+            self.mailbox
+                .dead_letters
+                .report_drop(&self.address, msg.get_type_name(), DeadLetterReason::Overflow);
+        }
+    }
+
+    fn report_dead_letter(&self, msg: &MessageEnvelope<A>) {
+        self.report_dead_letter_with_reason(msg, DeadLetterReason::Undeliverable);
+    }
+
+    fn report_dead_letter_with_reason(&self, msg: &MessageEnvelope<A>, reason: DeadLetterReason) {
+        let trace_id = trace::of(msg.get_headers());
+        self.mailbox
+            .dead_letters
+            .report_traced(&self.address, msg.get_type_name(), reason, trace_id);
+    }
+
+    /// Counts the message towards the pressure of this mailbox instead of the one it was previously queued in
+    ///
+    /// Control messages and pressure notifications are never counted, so that notifications cannot cause further crossings
+    pub(crate) fn attach_pressure(&self, msg: &mut MessageEnvelope<A>) {
+        let pressure = match &self.mailbox.pressure {
+            Some(pressure)
+                if !handoff::is_control_message(msg) && msg.get_type_id() != TypeId::of::<MailboxPressure>() =>
+            {
+                Some(PressureToken::new(pressure))
+            }
+            _ => None,
+        };
+        msg.set_pressure(pressure);
+    }
+
+    /// Control messages are never counted, so that stopping an Actor never depends on the budget
+    fn attach_budget(&self, msg: &mut MessageEnvelope<A>) {
+        if let Some(budget) = &self.mailbox.message_budget {
+            if !handoff::is_control_message(msg) {
+                msg.set_budget(Some(BudgetToken::new(budget)));
+            }
+        }
+    }
+
+    /// Sequence number the executor compares against superseding messages, see [ActorBuilder.add_superseding](../prelude/struct.ActorBuilder.html#method.add_superseding)
+    ///
+    /// Returns the type and sequence number of superseding messages, they supersede earlier messages once they have been queued
+    fn stamp(&self, msg: &mut MessageEnvelope<A>) -> Option<(TypeId, u64)> {
+        let supersession = self.mailbox.supersession.as_ref()?;
+        let sequence = supersession.stamp();
+        msg.set_sequence(sequence);
+        let type_id = msg.get_type_id();
+        if supersession.is_superseding(type_id) {
+            return Some((type_id, sequence));
+        }
+        None
+    }
+
+    fn supersede(&self, superseding: Option<(TypeId, u64)>) {
+        if let (Some(supersession), Some((type_id, sequence))) = (&self.mailbox.supersession, superseding) {
+            supersession.supersede(type_id, sequence);
+        }
+    }
 
-        self.mailbox.send(msg);
+    /// Sheddable Actors drop all messages under memory pressure, except for control messages and the pressure notification itself
+    fn is_shed(&self, msg: &MessageEnvelope<A>) -> bool {
+        self.mailbox.get_memory_mode() != MemoryMode::Normal
+            && !handoff::is_control_message(msg)
+            && msg.get_type_id() != TypeId::of::<SystemMemoryPressure>()
+    }
 
+    pub(crate) fn wakeup_if_sleeping(&self) {
         if self.mailbox.is_sleeping() {
+            self.metrics.increment_wakeup_requests();
+            self.wakeup_manager.wakeup(self.address.clone());
+        }
+    }
+
+    pub(crate) fn get_mailbox(&self) -> &Mailbox<A> {
+        &self.mailbox
+    }
+
+    /// Stops the Actor gracefully once it handled the stop message, see [Actor.on_actor_stop](../prelude/trait.Actor.html#method.on_actor_stop)
+    ///
+    /// The stop message is queued in the priority lane and handled right after the current message, see [send_priority](#method.send_priority).
+    /// Afterwards no new messages are accepted, but all messages that have been queued before are handled before [Actor.post_stop](../prelude/trait.Actor.html#method.post_stop) is executed.
+    /// The Actor counts towards [ActorSystem.get_actor_count](../prelude/struct.ActorSystem.html#method.get_actor_count) until then
+    ///
+    /// Returns `false` if the Actor is already stopping, see [stop_now](#method.stop_now) to discard the queue instead
+    pub fn stop(&self) -> bool {
+        if self.mailbox.request_stop(STOP_DRAIN) != STOP_NONE || self.mailbox.is_stopped() {
+            return false;
+        }
+        self.send_stop();
+        true
+    }
+
+    /// Stops the Actor right after the message it is currently handling, all messages that are still queued are dropped
+    ///
+    /// [Actor.on_actor_stop](../prelude/trait.Actor.html#method.on_actor_stop) and [Actor.post_stop](../prelude/trait.Actor.html#method.post_stop) are executed as for [stop](#method.stop).
+    /// A graceful stop that is still draining the queue is turned into an immediate one
+    ///
+    /// Returns `false` if it has been called before
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job {}
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { handled: Arc<Mutex<usize>> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, _msg: Job, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(10));
+    ///         *self.handled.lock().unwrap() += 1;
+    ///     }
+    /// }
+    /// struct WorkerFactory { handled: Arc<Mutex<usize>> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let spawn = |name: &str, handled: &Arc<Mutex<usize>>| {
+    ///     let worker = actor_system.builder().spawn(name, WorkerFactory { handled: handled.clone() }).unwrap();
+    ///     for _ in 0..20 {
+    ///         worker.send(Job {});
+    ///     }
+    ///     worker
+    /// };
+    ///
+    /// // a graceful stop handles everything that has been queued before
+    /// let drained = Arc::new(Mutex::new(0));
+    /// let worker = spawn("drained", &drained);
+    /// assert!(worker.stop());
+    /// assert!(!worker.stop());
+    /// sleep(Duration::from_millis(25));
+    /// assert!(worker.send(Job {}).is_err());
+    /// sleep(Duration::from_millis(500));
+    /// assert_eq!(20, *drained.lock().unwrap());
+    ///
+    /// // an immediate stop drops the queue
+    /// let dropped = Arc::new(Mutex::new(0));
+    /// let worker = spawn("dropped", &dropped);
+    /// sleep(Duration::from_millis(25));
+    /// assert!(worker.stop_now());
+    /// assert!(!worker.stop_now());
+    /// assert!(!worker.stop());
+    /// sleep(Duration::from_millis(500));
+    /// assert!(*dropped.lock().unwrap() < 20);
+    /// assert_eq!(0, worker.mailbox_len());
+    /// assert_eq!(0, actor_system.get_actor_count());
+    /// ```
+    pub fn stop_now(&self) -> bool {
+        let previous = self.mailbox.request_stop(STOP_NOW);
+        if previous == STOP_NOW {
+            return false;
+        }
+        if previous == STOP_NONE && !self.mailbox.is_stopped() {
+            self.send_stop();
+        } else {
+            // the stop message has already been sent, the Executor drops the queue once it handled it
+            self.wakeup_if_sleeping();
+        }
+        true
+    }
+
+    fn send_stop(&self) {
+        if self.mailbox.is_awaiting_release() {
+            // an Actor that has never been released cannot handle the stop message, it stops without ever starting
+            self.mailbox.flags.is_stopped.store(true, Ordering::SeqCst);
             self.wakeup_manager.wakeup(self.address.clone());
+            return;
+        }
+        // a full bounded mailbox does not hold the stop back, it is handled before all queued messages
+        let _ = self.send_message(ActorStopMessage {}, SendWait::Block, true);
+    }
+
+    /// Stops the Actor gracefully and returns it once all queued messages have been handled and `post_stop` has been executed
+    ///
+    /// Once requested, a panic ends the Actor regardless of its [RestartPolicy](../prelude/enum.RestartPolicy.html) and is returned as [ExtractError::Panicked](../prelude/enum.ExtractError.html#variant.Panicked).
+    /// After a timeout the Actor keeps stopping, but its final state is dropped. The final state can only be extracted once
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Default)]
+    /// struct Aggregate { count: u64, sum: u64, max: u64 }
+    ///
+    /// struct Value(u64);
+    /// impl ActorMessage for Value {}
+    /// struct Stall {}
+    /// impl ActorMessage for Stall {}
+    ///
+    /// struct Aggregator {
+    ///     aggregate: Aggregate,
+    ///     panic_on_stop: bool,
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Aggregator {
+    ///     fn post_stop(&mut self) {
+    ///         if self.panic_on_stop {
+    ///             panic!("flush failed");
+    ///         }
+    ///     }
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Value> for Aggregator {
+    ///     fn handle(&mut self, msg: Value, _context: &ActorContext<Self>) {
+    ///         self.aggregate.count += 1;
+    ///         self.aggregate.sum += msg.0;
+    ///         self.aggregate.max = self.aggregate.max.max(msg.0);
+    ///     }
+    /// }
+    /// impl Handler<Stall> for Aggregator {
+    ///     fn handle(&mut self, _msg: Stall, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(200));
+    ///     }
+    /// }
+    ///
+    /// struct AggregatorFactory { panic_on_stop: bool }
+    /// impl ActorFactory<Aggregator> for AggregatorFactory {
+    ///     fn new_actor(&self, context: ActorContext<Aggregator>) -> Aggregator {
+    ///         Aggregator { aggregate: Aggregate::default(), panic_on_stop: self.panic_on_stop, context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let aggregator = actor_system.builder().spawn("aggregator", AggregatorFactory { panic_on_stop: false }).unwrap();
+    /// let observed = Arc::new(Mutex::new(None));
+    /// let observer = observed.clone();
+    /// aggregator.with_final_state(move |actor: &Aggregator| {
+    ///     *observer.lock().unwrap() = Some(actor.aggregate.clone());
+    /// }).unwrap();
+    /// for value in 1..=10_000 {
+    ///     aggregator.send(Value(value));
+    /// }
+    /// let extracted = aggregator.stop_and_extract(Duration::from_secs(10)).unwrap();
+    /// let expected = Aggregate { count: 10_000, sum: 50_005_000, max: 10_000 };
+    /// assert_eq!(expected, extracted.aggregate);
+    /// assert_eq!(Some(expected), *observed.lock().unwrap());
+    /// assert_eq!(Some(ExtractError::Stopped), aggregator.stop_and_extract(Duration::from_secs(1)).err());
+    ///
+    /// // a panic in `post_stop` is returned instead of the Actor
+    /// let failing = actor_system.builder().spawn("failing", AggregatorFactory { panic_on_stop: true }).unwrap();
+    /// match failing.stop_and_extract(Duration::from_secs(1)) {
+    ///     Err(ExtractError::Panicked(report)) => {
+    ///         assert_eq!("post_stop", report.message_type);
+    ///         assert_eq!(Some(String::from("flush failed")), report.panic_message);
+    ///     }
+    ///     _ => panic!("expected a panic report"),
+    /// }
+    ///
+    /// // the final state of a timed out extraction is dropped, it can not be requested again
+    /// let stalled = actor_system.builder().spawn("stalled", AggregatorFactory { panic_on_stop: false }).unwrap();
+    /// stalled.send(Stall {});
+    /// assert_eq!(Some(ExtractError::Timeout), stalled.stop_and_extract(Duration::from_millis(10)).err());
+    /// assert_eq!(Some(ExtractError::AlreadyRequested), stalled.stop_and_extract(Duration::from_secs(1)).err());
+    /// ```
+    pub fn stop_and_extract(&self, timeout: Duration) -> Result<A, ExtractError> {
+        let (sender, receiver) = bounded(1);
+        self.mailbox.final_state.lock().unwrap().request_extraction(sender)?;
+        self.stop();
+        receiver.recv_timeout(timeout).map_err(|_| ExtractError::Timeout)?
+    }
+
+    /// Executes `f` on the thread of the Actor once it reached its final state, right before it is dropped
+    ///
+    /// `f` is skipped if the Actor ends with a panic and fails with [ExtractError::Stopped](../prelude/enum.ExtractError.html#variant.Stopped) if the Actor has already stopped.
+    /// Unlike [stop_and_extract](#method.stop_and_extract) it does not stop the Actor and the state never leaves the thread of the Actor
+    pub fn with_final_state<F>(&self, f: F) -> Result<(), ExtractError>
+    where
+        F: FnOnce(&A) + Send + 'static,
+    {
+        self.mailbox.final_state.lock().unwrap().add_callback(Box::new(f))
+    }
+
+    /// Executes `watcher` once the Actor stopped for good, right away if it has already stopped
+    ///
+    /// Unlike [with_final_state](#method.with_final_state) it is executed after a panic as well, together with the report of that panic.
+    /// The report is always `None` if the Actor had already stopped
+    pub(crate) fn on_termination<F>(&self, watcher: F)
+    where
+        F: FnOnce(Option<&ActorPanicReport>) + Send + 'static,
+    {
+        let result = self.mailbox.final_state.lock().unwrap().add_watcher(Box::new(watcher));
+        if let Err(watcher) = result {
+            watcher(None);
+        }
+    }
+
+    /// Executes `watcher` once the Actor has been removed from the system after it stopped, right away if it has already been removed
+    pub(crate) fn on_removal<F>(&self, watcher: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let result = self.mailbox.final_state.lock().unwrap().add_removal_watcher(Box::new(watcher));
+        if let Err(watcher) = result {
+            watcher();
+        }
+    }
+
+    /// Executed by the system once it removed the Actor
+    pub(crate) fn notify_removed(&self) {
+        let watchers = self.mailbox.final_state.lock().unwrap().take_removal_watchers();
+        for watcher in watchers {
+            watcher();
         }
     }
 
-    pub fn stop(&self) {
-        self.send(ActorStopMessage {});
+    /// Blocks until the Actor stopped for good, returns `false` if `timeout` elapsed before
+    ///
+    /// Returns once the last message has been handled, `post_stop` has been executed and the Actor has been removed from the system, right away if that already happened.
+    /// Does not stop the Actor, see [stop](#method.stop) and [ActorContext.watch](../prelude/struct.ActorContext.html#method.watch)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Record {}
+    /// impl ActorMessage for Record {}
+    ///
+    /// struct Stage {
+    ///     written: Arc<AtomicUsize>,
+    ///     is_flushed: Arc<AtomicBool>,
+    /// }
+    /// impl Actor for Stage {
+    ///     fn post_stop(&mut self) {
+    ///         self.is_flushed.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// impl Handler<Record> for Stage {
+    ///     fn handle(&mut self, _msg: Record, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(10));
+    ///         self.written.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// struct StageFactory {
+    ///     written: Arc<AtomicUsize>,
+    ///     is_flushed: Arc<AtomicBool>,
+    /// }
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stage>) -> Stage {
+    ///         Stage { written: self.written.clone(), is_flushed: self.is_flushed.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let written = Arc::new(AtomicUsize::new(0));
+    /// let is_flushed = Arc::new(AtomicBool::new(false));
+    /// let factory = StageFactory { written: written.clone(), is_flushed: is_flushed.clone() };
+    /// let stage = actor_system.builder().spawn("stage", factory).unwrap();
+    /// for _ in 0..20 {
+    ///     stage.send(Record {}).unwrap();
+    /// }
+    /// // running Actors are not awaited
+    /// assert!(!stage.await_stop(Duration::from_millis(10)));
+    ///
+    /// stage.stop();
+    /// assert!(stage.await_stop(Duration::from_secs(5)));
+    /// assert_eq!(20, written.load(Ordering::Relaxed));
+    /// assert!(is_flushed.load(Ordering::Relaxed));
+    /// assert!(actor_system.actor_stats(stage.get_address()).is_none());
+    /// // the Actor has already been removed
+    /// assert!(stage.await_stop(Duration::from_secs(0)));
+    /// ```
+    pub fn await_stop(&self, timeout: Duration) -> bool {
+        let (sender, receiver) = bounded(1);
+        self.on_removal(move || {
+            let _ = sender.send(());
+        });
+        receiver.recv_timeout(timeout).is_ok()
+    }
+
+    /// Starts an Actor spawned through [ActorBuilder.start_suspended](../prelude/struct.ActorBuilder.html#method.start_suspended)
+    ///
+    /// Returns `false` if the Actor has already been released or has not been spawned suspended
+    pub fn release(&self) -> bool {
+        if self.mailbox.is_stopped() || !self.mailbox.flags.is_awaiting_release.swap(false, Ordering::SeqCst) {
+            return false;
+        }
+        self.wakeup_manager.wakeup(self.address.clone());
+        true
+    }
+
+    pub fn is_awaiting_release(&self) -> bool {
+        self.mailbox.is_awaiting_release()
     }
 
     pub fn get_address(&self) -> &ActorAddress {
         &self.address
     }
+
+    /// Reference to the Actor that neither keeps its mailbox alive nor delays its removal, see [WeakActorWrapper.upgrade](../prelude/struct.WeakActorWrapper.html#method.upgrade)
+    ///
+    /// Clones of an [ActorWrapper] share the mailbox of the Actor, which is only dropped once the last clone is gone
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Session {
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Session {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Session {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct SessionFactory {}
+    /// impl ActorFactory<Session> for SessionFactory {
+    ///     fn new_actor(&self, context: ActorContext<Session>) -> Session {
+    ///         Session { context }
+    ///     }
+    /// }
+    ///
+    /// struct Register(WeakActorWrapper<Session>);
+    /// impl ActorMessage for Register {}
+    /// struct PingAll {}
+    /// impl ActorMessage for PingAll {}
+    ///
+    /// struct Registry {
+    ///     context: ActorContext<Self>,
+    ///     sessions: Vec<WeakActorWrapper<Session>>,
+    ///     alive: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Registry {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Register> for Registry {
+    ///     fn handle(&mut self, msg: Register, _context: &ActorContext<Self>) {
+    ///         self.sessions.push(msg.0);
+    ///     }
+    /// }
+    /// impl Handler<PingAll> for Registry {
+    ///     fn handle(&mut self, _msg: PingAll, _context: &ActorContext<Self>) {
+    ///         self.sessions.retain(|session| session.upgrade().is_some_and(|session| session.send(Ping {}).is_ok()));
+    ///         self.alive.store(self.sessions.len(), Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct RegistryFactory {
+    ///     alive: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Registry> for RegistryFactory {
+    ///     fn new_actor(&self, context: ActorContext<Registry>) -> Registry {
+    ///         Registry { context, sessions: Vec::new(), alive: self.alive.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let alive = Arc::new(AtomicUsize::new(0));
+    /// let registry = actor_system.builder().spawn("registry", RegistryFactory { alive: alive.clone() }).unwrap();
+    /// let sessions: Vec<_> = (0..3)
+    ///     .map(|i| actor_system.builder().spawn(format!("session-{}", i), SessionFactory {}).unwrap())
+    ///     .collect();
+    /// sessions.iter().for_each(|session| registry.send(Register(session.downgrade())).unwrap());
+    ///
+    /// sessions[0].stop();
+    /// assert!(sessions[0].await_stop(Duration::from_secs(1)));
+    /// registry.send(PingAll {}).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(alive.load(Ordering::Relaxed), 2);
+    ///
+    /// // the weak references of the registry do not keep any session from stopping
+    /// actor_system.stop(Duration::from_secs(3));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert_eq!(0, actor_system.get_actor_count());
+    /// ```
+    pub fn downgrade(&self) -> WeakActorWrapper<A> {
+        WeakActorWrapper::new(
+            self.address.clone(),
+            Arc::downgrade(&self.mailbox.flags),
+            self.mailbox.actor_registry.clone(),
+        )
+    }
+
+    /// Number of messages waiting in the regular mailbox
+    pub fn mailbox_len(&self) -> usize {
+        self.metrics.get_mailbox_len()
+    }
+
+    /// Approximate bytes of the messages that have been queued and not yet handled or dropped, see [ActorBuilder.set_max_mailbox_bytes](../prelude/struct.ActorBuilder.html#method.set_max_mailbox_bytes)
+    ///
+    /// Always `0` if the Actor has no `max_mailbox_bytes`
+    pub fn mailbox_bytes(&self) -> usize {
+        self.mailbox.bytes.as_ref().map_or(0, |bytes| bytes.get_queued())
+    }
+
+    /// Current pressure of the mailbox, a single atomic read that can be polled before every send
+    ///
+    /// Always `Normal` if the Actor has no [Watermarks](../prelude/struct.Watermarks.html) or has stopped
+    pub fn pressure(&self) -> PressureLevel {
+        self.mailbox
+            .pressure
+            .as_ref()
+            .map_or(PressureLevel::Normal, |pressure| pressure.level())
+    }
+
+    /// `true` while the [pressure](#method.pressure) is `High`, i.e. from the moment the high watermark has been reached until the queue shrank to the low watermark
+    pub fn is_backpressured(&self) -> bool {
+        self.pressure() == PressureLevel::High
+    }
+
+    /// Sends a [MailboxRelieved](../prelude/struct.MailboxRelieved.html) to `watcher` whenever the queue of the Actor returns from `High` pressure to the low watermark
+    ///
+    /// Registering the same watcher again has no effect, the registration ends when this Actor stops
+    ///
+    /// # Returns
+    ///
+    /// `false` if the Actor has no [Watermarks](../prelude/struct.Watermarks.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Receiver, Sender};
+    /// use std::time::Duration;
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// // every message waits for the gate, dropping the sender opens it for good
+    /// struct Consumer { gate: Receiver<()> }
+    /// impl Actor for Consumer {}
+    /// impl Handler<Work> for Consumer {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         let _ = self.gate.recv();
+    ///     }
+    /// }
+    /// struct ConsumerFactory { gate: Receiver<()> }
+    /// impl ActorFactory<Consumer> for ConsumerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Consumer>) -> Consumer {
+    ///         Consumer { gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Producer { relieved: Sender<String> }
+    /// impl Actor for Producer {}
+    /// impl Handler<MailboxRelieved> for Producer {
+    ///     fn handle(&mut self, msg: MailboxRelieved, _context: &ActorContext<Self>) {
+    ///         self.relieved.send(msg.address.actor).unwrap();
+    ///     }
+    /// }
+    /// struct ProducerFactory { relieved: Sender<String> }
+    /// impl ActorFactory<Producer> for ProducerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Producer>) -> Producer {
+    ///         Producer { relieved: self.relieved.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (gate_sender, gate) = unbounded();
+    /// let consumer = actor_system
+    ///     .builder()
+    ///     .set_watermarks(Watermarks::new(50, 10))
+    ///     .spawn("consumer", ConsumerFactory { gate })
+    ///     .unwrap();
+    /// let (relieved_sender, relieved) = unbounded();
+    /// let producer = actor_system.builder().spawn("producer", ProducerFactory { relieved: relieved_sender }).unwrap();
+    /// assert!(consumer.notify_when_below_watermark(&producer));
+    /// assert!(consumer.notify_when_below_watermark(&producer));
+    ///
+    /// for _ in 0..100 {
+    ///     consumer.send(Work {}).unwrap();
+    /// }
+    /// // the consumer is held at the gate, the queue cannot shrink
+    /// assert!(consumer.is_backpressured());
+    /// assert!(relieved.try_recv().is_err());
+    ///
+    /// // drained below the low watermark, the queue never returned to the high watermark
+    /// drop(gate_sender);
+    /// assert_eq!("consumer", relieved.recv_timeout(Duration::from_secs(5)).unwrap());
+    /// assert!(!consumer.is_backpressured());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn notify_when_below_watermark<B>(&self, watcher: &ActorWrapper<B>) -> bool
+    where
+        B: Actor + Handler<MailboxRelieved> + UnwindSafe + 'static,
+    {
+        let pressure = match &self.mailbox.pressure {
+            Some(pressure) => pressure,
+            None => return false,
+        };
+        let address = watcher.get_address().clone();
+        let watcher = watcher.clone();
+        pressure.watch::<MailboxRelieved>(&address, Box::new(move |msg| {
+            if msg.level == PressureLevel::Normal {
+                let _ = watcher.send(MailboxRelieved { address: msg.address });
+            }
+        }));
+        true
+    }
+
+    /// Returns the recent metrics history of the Actor
+    ///
+    /// `None` if [GeneralConfig.metrics_history](../prelude/struct.HistoryConfig.html) is not configured
+    ///
+    /// Actors spawned after the history window started simply have shorter series
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Burst {}
+    /// impl ActorMessage for Burst {}
+    ///
+    /// struct HelloWorld {}
+    /// impl Actor for HelloWorld {}
+    /// impl Handler<Burst> for HelloWorld {
+    ///     fn handle(&mut self, _msg: Burst, _context: &ActorContext<Self>) {}
+    /// }
+    ///
+    /// struct HelloWorldFactory {}
+    /// impl ActorFactory<HelloWorld> for HelloWorldFactory {
+    ///     fn new_actor(&self, _context: ActorContext<HelloWorld>) -> HelloWorld {
+    ///         HelloWorld {}
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.metrics_history = Some(HistoryConfig::new(Duration::from_millis(20), Duration::from_millis(200)));
+    /// let actor_system = ActorSystem::new(config);
+    /// let actor = actor_system.builder().spawn("history", HelloWorldFactory {}).unwrap();
+    ///
+    /// sleep(Duration::from_millis(60));
+    /// for _ in 0..100 {
+    ///     actor.send(Burst {});
+    /// }
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// let history = actor.metrics_history().unwrap();
+    /// assert!(history.samples.len() <= 10);
+    /// assert_eq!(100, history.samples.iter().map(|s| s.processed).sum::<usize>());
+    /// assert_eq!(0, history.summary().unwrap().processed.min);
+    /// ```
+    pub fn metrics_history(&self) -> Option<ActorMetricsHistory> {
+        self.metrics.get_history()
+    }
+
+    /// Returns the total time the Actor spent suspended through [ActorContext.suspend_until](../prelude/struct.ActorContext.html#method.suspend_until)
+    ///
+    /// Suspended time is tracked separately and not considered idle time
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Waiter {
+    ///     stopped: Arc<AtomicBool>,
+    /// }
+    /// impl Actor for Waiter {
+    ///     fn on_suspension_ended(&mut self, _reason: SuspensionEndReason) {
+    ///         panic!("stopping cancels the suspension");
+    ///     }
+    ///     fn post_stop(&mut self) {
+    ///         self.stopped.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// impl Handler<Work> for Waiter {
+    ///     fn handle(&mut self, _msg: Work, context: &ActorContext<Self>) {
+    ///         context.suspend_until(SuspendCondition::external());
+    ///     }
+    /// }
+    ///
+    /// struct WaiterFactory {
+    ///     stopped: Arc<AtomicBool>,
+    /// }
+    /// impl ActorFactory<Waiter> for WaiterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Waiter>) -> Waiter {
+    ///         Waiter { stopped: self.stopped.clone() }
+    ///     }
+    /// }
+    ///
+    /// let stopped = Arc::new(AtomicBool::new(false));
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .spawn("waiter", WaiterFactory { stopped: stopped.clone() })
+    ///     .unwrap();
+    /// actor.send(Work {});
+    /// sleep(Duration::from_millis(100));
+    /// actor.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert!(stopped.load(Ordering::Relaxed));
+    /// assert!(actor.suspended_time() > Duration::from_millis(0));
+    /// ```
+    pub fn suspended_time(&self) -> Duration {
+        self.metrics.get_suspended_time()
+    }
+
+    /// Number of messages the Actor handled, including messages whose handler panicked and its stop message
+    ///
+    /// Workers count into a stripe of their own that is merged when the count is read, a read may miss the messages of a slot that is being flushed at the same time
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::{sleep, spawn};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Counter { handled: Arc<AtomicUsize> }
+    /// impl Actor for Counter {}
+    /// impl Handler<Work> for Counter {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    /// struct CounterFactory { handled: Arc<AtomicUsize> }
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.thread_pool.config.insert(String::from("alpha"), ThreadPoolConfig::new(0, 2, 2, 1.0));
+    /// config.thread_pool.config.insert(String::from("beta"), ThreadPoolConfig::new(0, 3, 3, 1.0));
+    /// let actor_system = ActorSystem::new(config);
+    /// let pools = ["default", "alpha", "beta"];
+    /// let actors: Vec<(ActorWrapper<Counter>, Arc<AtomicUsize>)> = (0..12)
+    ///     .map(|index| {
+    ///         let handled = Arc::new(AtomicUsize::new(0));
+    ///         let actor = actor_system
+    ///             .builder()
+    ///             .set_pool_name(pools[index % pools.len()])
+    ///             .unwrap()
+    ///             .spawn(format!("counter-{}", index), CounterFactory { handled: handled.clone() })
+    ///             .unwrap();
+    ///         (actor, handled)
+    ///     })
+    ///     .collect();
+    /// sleep(Duration::from_millis(1500));
+    ///
+    /// // every third Actor is stopped while the senders are still running
+    /// let senders: Vec<_> = (0..4)
+    ///     .map(|sender| {
+    ///         let actors: Vec<ActorWrapper<Counter>> = actors.iter().map(|(actor, _)| actor.clone()).collect();
+    ///         spawn(move || {
+    ///             for round in 0..500 {
+    ///                 if sender == 0 && round == 250 {
+    ///                     actors.iter().step_by(3).for_each(|actor| {
+    ///                         actor.stop();
+    ///                     });
+    ///                 }
+    ///                 for actor in actors.iter() {
+    ///                     actor.send(Work {});
+    ///                 }
+    ///             }
+    ///         })
+    ///     })
+    ///     .collect();
+    /// senders.into_iter().for_each(|sender| sender.join().unwrap());
+    ///
+    /// let started = Instant::now();
+    /// while actors.iter().skip(1).step_by(3).any(|(_, handled)| handled.load(Ordering::SeqCst) < 2000) {
+    ///     assert!(started.elapsed() < Duration::from_secs(10));
+    ///     sleep(Duration::from_millis(10));
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// for (index, (actor, handled)) in actors.iter().enumerate() {
+    ///     if index % 3 == 0 {
+    ///         // the stop message is counted as well
+    ///         assert_eq!(handled.load(Ordering::SeqCst) + 1, actor.processed_count());
+    ///     } else {
+    ///         assert_eq!(2000, actor.processed_count());
+    ///     }
+    /// }
+    ///
+    /// // workers flush their stripes before they exit
+    /// let before: Vec<usize> = actors.iter().map(|(actor, _)| actor.processed_count()).collect();
+    /// actor_system.stop(Duration::from_secs(3));
+    /// actor_system.await_shutdown();
+    /// for (index, ((actor, _), before)) in actors.iter().zip(before).enumerate() {
+    ///     // Actors that were still running handled the system stop message
+    ///     let system_stop = if index % 3 == 0 { 0 } else { 1 };
+    ///     assert_eq!(before + system_stop, actor.processed_count());
+    /// }
+    /// let handled: usize = actors.iter().map(|(_, handled)| handled.load(Ordering::SeqCst)).sum();
+    /// let processed: usize = actors.iter().map(|(actor, _)| actor.processed_count()).sum();
+    /// assert_eq!(handled + actors.len(), processed);
+    /// ```
+    pub fn processed_count(&self) -> usize {
+        self.metrics.get_processed()
+    }
+
+    /// Number of messages that have been dropped because the Actor is sheddable and the system was under memory pressure
+    pub fn shed_count(&self) -> usize {
+        self.metrics.get_shed()
+    }
+
+    /// Number of messages that have been skipped because a later message superseded them, see [ActorBuilder.add_superseding](../prelude/struct.ActorBuilder.html#method.add_superseding)
+    pub fn superseded_count(&self) -> usize {
+        self.metrics.get_superseded()
+    }
+
+    /// Number of messages that have been dropped because the bounded mailbox was full, see [MailboxOverflowPolicy](../prelude/enum.MailboxOverflowPolicy.html)
+    pub fn dropped_count(&self) -> usize {
+        self.metrics.get_dropped()
+    }
+
+    /// Number of messages that have been skipped because their time to live passed, see [send_with_ttl](#method.send_with_ttl)
+    pub fn expired_count(&self) -> usize {
+        self.metrics.get_expired()
+    }
+
+    /// Number of messages that have been rejected because they exceeded the message size limit of the Actor, see [try_send](#method.try_send)
+    pub fn oversized_count(&self) -> usize {
+        self.mailbox.size_limit.as_ref().map_or(0, |size_limit| size_limit.get_rejected())
+    }
+
+    /// Number of serialized messages whose content could not be deserialized, see [ActorBuilder.register_serialized](../prelude/struct.ActorBuilder.html#method.register_serialized)
+    pub fn undecodable_count(&self) -> usize {
+        self.metrics.get_undecodable()
+    }
+
+    /// Number of messages that arrived but could not be handled, see [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+    pub fn unhandled_count(&self) -> usize {
+        self.metrics.get_unhandled()
+    }
+
+    /// Number of wakeups after which the Actor found its mailbox empty and went back to sleep without handling a message
+    ///
+    /// A restart keeps the Actor in place, the wakeups scheduled by the crashed Actor are dropped and do not show up here
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work(usize);
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Flaky { handled: Arc<AtomicUsize> }
+    /// impl Actor for Flaky {}
+    /// impl Handler<Work> for Flaky {
+    ///     fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+    ///         self.handled.fetch_add(1, Ordering::SeqCst);
+    ///         if msg.0 % 10 == 0 {
+    ///             panic!("every tenth message fails");
+    ///         }
+    ///     }
+    /// }
+    /// struct FlakyFactory { handled: Arc<AtomicUsize> }
+    /// impl ActorFactory<Flaky> for FlakyFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Flaky>) -> Flaky {
+    ///         Flaky { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(AtomicUsize::new(0));
+    /// let policy = RestartPolicy::Limited {
+    ///     max_restarts: 100,
+    ///     within: Duration::from_secs(60),
+    ///     backoff: BackoffStrategy::Fixed(Duration::from_millis(2)),
+    /// };
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .set_restart_policy(policy)
+    ///     .spawn("flaky", FlakyFactory { handled: handled.clone() })
+    ///     .unwrap();
+    /// std::thread::scope(|scope| {
+    ///     for producer in 0..4 {
+    ///         let actor = actor.clone();
+    ///         scope.spawn(move || {
+    ///             for n in 0..50 {
+    ///                 actor.send(Work(producer * 50 + n)).unwrap();
+    ///                 if n % 10 == 5 {
+    ///                     sleep(Duration::from_millis(3));
+    ///                 }
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    /// sleep(Duration::from_millis(500));
+    ///
+    /// // the failed messages count as handled, nothing got lost across the restarts
+    /// assert_eq!(200, handled.load(Ordering::SeqCst));
+    /// assert_eq!(20, actor_system.actor_stats(actor.get_address()).unwrap().restarts);
+    /// assert!(actor.empty_wakeup_count() <= 2);
+    /// assert_eq!(0, actor_system.integrity_duplicate_entries());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn empty_wakeup_count(&self) -> usize {
+        self.metrics.get_empty_wakeups()
+    }
+
+    /// Number of sends that found the Actor sleeping and asked the wakeup manager to wake it up, see [send_batch](#method.send_batch)
+    pub fn wakeup_request_count(&self) -> usize {
+        self.metrics.get_wakeup_requests()
+    }
+
+    /// Time spent in handlers, excluding the construction of lazily sent messages
+    pub fn handler_time(&self) -> Duration {
+        self.metrics.get_handler_time()
+    }
+
+    /// Time spent constructing messages sent through [ActorWrapper.send_lazy](#method.send_lazy)
+    pub fn construction_time(&self) -> Duration {
+        self.metrics.get_construction_time()
+    }
+
+    /// Number of chains started through [ActorContext.request_chain](../prelude/struct.ActorContext.html#method.request_chain) that have neither completed nor failed yet
+    pub fn active_request_chains(&self) -> usize {
+        self.metrics.get_active_request_chains()
+    }
+
+    /// Number of scheduling slots that ran on another worker thread than the previous slot, see [ActorSystem.pool_worker_stats](../prelude/struct.ActorSystem.html#method.pool_worker_stats)
+    pub fn migration_count(&self) -> usize {
+        self.metrics.get_migrations()
+    }
+
+    /// Ids of the last worker threads of the pool the Actor ran on, from oldest to newest
+    pub fn recent_workers(&self) -> Vec<usize> {
+        self.metrics.get_recent_workers()
+    }
+
+    /// Returns the scheduling priority of the Actor, including the priority inherited from request chains
+    ///
+    /// See [ActorBuilder.set_priority](../prelude/struct.ActorBuilder.html#method.set_priority)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Hop {}
+    /// impl ActorMessage for Hop {}
+    ///
+    /// struct Relay {
+    ///     next: Option<ActorWrapper<Relay>>,
+    ///     seen: Arc<Mutex<Vec<ActorPriority>>>,
+    /// }
+    /// impl Actor for Relay {}
+    /// impl Handler<Hop> for Relay {
+    ///     fn handle(&mut self, _msg: Hop, context: &ActorContext<Self>) {
+    ///         self.seen.lock().unwrap().push(context.actor_ref.effective_priority());
+    ///         if let Some(next) = &self.next {
+    ///             next.send(Hop {});
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct RelayFactory {
+    ///     next: Option<ActorWrapper<Relay>>,
+    ///     seen: Arc<Mutex<Vec<ActorPriority>>>,
+    /// }
+    /// impl ActorFactory<Relay> for RelayFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Relay>) -> Relay {
+    ///         Relay { next: self.next.clone(), seen: self.seen.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_priority_inheritance_depth = 2;
+    /// let actor_system = ActorSystem::new(config);
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let mut next = None;
+    /// for name in ["d", "c", "b"].iter() {
+    ///     let factory = RelayFactory { next: next.clone(), seen: seen.clone() };
+    ///     next = Some(actor_system.builder().spawn(*name, factory).unwrap());
+    /// }
+    /// let first = actor_system
+    ///     .builder()
+    ///     .set_priority(ActorPriority::High)
+    ///     .spawn("a", RelayFactory { next: next.clone(), seen: seen.clone() })
+    ///     .unwrap();
+    /// first.send(Hop {});
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// // "b" and "c" inherit the priority of "a", "d" is beyond the maximum depth
+    /// let expected = vec![ActorPriority::High, ActorPriority::High, ActorPriority::High, ActorPriority::Normal];
+    /// assert_eq!(expected, *seen.lock().unwrap());
+    /// assert_eq!(ActorPriority::Normal, next.unwrap().effective_priority());
+    /// ```
+    pub fn effective_priority(&self) -> ActorPriority {
+        self.mailbox.priority.get_effective()
+    }
+
+    /// Returns the total time the Actor ran with a priority inherited from a request chain
+    ///
+    /// A high value indicates a priority inversion between this Actor and its callers
+    pub fn boosted_time(&self) -> Duration {
+        self.metrics.get_boosted_time()
+    }
+
+    /// Returns the state of the latency SLO as of the last completed window
+    ///
+    /// `None` if the Actor has no SLO, see [ActorBuilder.set_latency_slo](../prelude/struct.ActorBuilder.html#method.set_latency_slo)
+    pub fn slo_status(&self) -> Option<SloStatus> {
+        self.mailbox.slo_status.as_ref().map(|status| *status.lock().unwrap())
+    }
+
+    /// Maximum amount of messages the Actor handles in its next scheduling slot, see [ActorBuilder.set_throughput](../prelude/struct.ActorBuilder.html#method.set_throughput)
+    pub fn message_budget(&self) -> usize {
+        self.metrics.get_message_budget()
+    }
+
+    /// Amount of effect outcomes kept for the Actor, see [ActorContext.effect_guard](../prelude/struct.ActorContext.html#method.effect_guard)
+    pub fn effect_ledger_len(&self) -> usize {
+        self.mailbox.effect_ledger.len()
+    }
+
+    /// Attaches a stream that receives every item of type `M` the Actor emits through [ActorContext.emit](../prelude/struct.ActorContext.html#method.emit)
+    ///
+    /// Buffers up to `buffer` items and drops the oldest ones if the consumer falls behind, see [subscribe_stream_with_policy](#method.subscribe_stream_with_policy).
+    /// All streams end with [StreamEndReason::Stopped](../prelude/enum.StreamEndReason.html#variant.Stopped) once the Actor has stopped
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Publish { count: u32 }
+    /// impl ActorMessage for Publish {}
+    ///
+    /// struct Feed {}
+    /// impl Actor for Feed {}
+    /// impl Handler<Publish> for Feed {
+    ///     fn handle(&mut self, msg: Publish, context: &ActorContext<Self>) {
+    ///         for i in 0..msg.count {
+    ///             context.emit(i);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// struct FeedFactory {}
+    /// impl ActorFactory<Feed> for FeedFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Feed>) -> Feed {
+    ///         Feed {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let feed = actor_system.builder().spawn("feed", FeedFactory {}).unwrap();
+    ///
+    /// // dropping a stream detaches it, emitting is back to a single atomic load
+    /// let stream = feed.subscribe_stream::<u32>(10);
+    /// feed.send(Publish { count: 5 });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(5, feed.stream_emissions());
+    /// drop(stream);
+    /// assert_eq!(0, feed.attached_streams());
+    /// feed.send(Publish { count: 5 });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(5, feed.stream_emissions());
+    ///
+    /// // slow consumers either skip the oldest items or are detached
+    /// let mut lagging = feed.subscribe_stream::<u32>(10);
+    /// let mut closing = feed.subscribe_stream_with_policy::<u32>(10, LagPolicy::Close);
+    /// feed.send(Publish { count: 100 });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(Some(StreamItem::Lagged(90)), lagging.try_next());
+    /// for i in 90..100 {
+    ///     assert_eq!(Some(StreamItem::Item(i)), lagging.try_next());
+    /// }
+    /// assert_eq!(None, lagging.try_next());
+    /// let closed: Vec<StreamItem<u32>> = closing.iter().collect();
+    /// assert_eq!(11, closed.len());
+    /// assert_eq!(StreamItem::End(StreamEndReason::Overflowed), closed[10]);
+    /// assert_eq!(1, feed.attached_streams());
+    ///
+    /// // stopping the Actor ends all attached streams
+    /// let mut other = feed.subscribe_stream::<String>(10);
+    /// feed.stop();
+    /// assert_eq!(vec![StreamItem::End(StreamEndReason::Stopped)], lagging.iter().collect::<Vec<_>>());
+    /// assert_eq!(vec![StreamItem::End(StreamEndReason::Stopped)], other.iter().collect::<Vec<_>>());
+    /// assert_eq!(0, feed.attached_streams());
+    /// ```
+    pub fn subscribe_stream<M>(&self, buffer: usize) -> MessageStream<M>
+    where
+        M: Send + 'static,
+    {
+        self.subscribe_stream_with_policy(buffer, LagPolicy::DropOldest)
+    }
+
+    /// Same as [subscribe_stream](#method.subscribe_stream), but with a custom [LagPolicy](../prelude/enum.LagPolicy.html)
+    pub fn subscribe_stream_with_policy<M>(&self, buffer: usize, lag_policy: LagPolicy) -> MessageStream<M>
+    where
+        M: Send + 'static,
+    {
+        self.mailbox.emitter.subscribe(buffer, lag_policy)
+    }
+
+    /// Amount of streams attached to the Actor, see [subscribe_stream](#method.subscribe_stream)
+    pub fn attached_streams(&self) -> usize {
+        self.mailbox.emitter.get_attached()
+    }
+
+    /// Amount of emissions while at least one stream was attached, emissions without any attached stream are not counted
+    pub fn stream_emissions(&self) -> u64 {
+        self.mailbox.emitter.get_emissions()
+    }
+
+    /// Returns the journal of the Actor, oldest entry first
+    ///
+    /// Empty if the journal is disabled or the Actor has stopped, see [ActorBuilder.set_journal_capacity](../prelude/struct.ActorBuilder.html#method.set_journal_capacity)
+    pub fn journal(&self) -> Vec<JournalEntry> {
+        self.mailbox.journal.as_ref().map_or_else(Vec::new, |journal| journal.entries())
+    }
+
+    /// Captures the state of the Actor through [Actor.snapshot](../prelude/trait.Actor.html#method.snapshot) without stopping it
+    ///
+    /// The snapshot is taken on the thread of the Actor once it handled all messages that were sent before, so this blocks until then and must not be called by a handler of the same Actor.
+    /// Every capture is kept in a ring of the last [state_capture_capacity](../prelude/struct.ActorBuilder.html#method.set_state_capture_capacity) captures, together with the ones of [ActorBuilder.add_capture_around](../prelude/struct.ActorBuilder.html#method.add_capture_around).
+    /// Two captures are compared through [ActorSystem.diff_captures](../prelude/struct.ActorSystem.html#method.diff_captures)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// struct Deposit(u64);
+    /// impl ActorMessage for Deposit {}
+    /// struct Withdraw(u64);
+    /// impl ActorMessage for Withdraw {}
+    ///
+    /// struct Account { owner: String, balance: u64, deposits: u64 }
+    /// impl Actor for Account {
+    ///     fn snapshot(&self) -> Vec<u8> {
+    ///         format!(r#"{{"owner":"{}","balance":{},"deposits":{}}}"#, self.owner, self.balance, self.deposits).into_bytes()
+    ///     }
+    /// }
+    /// impl Handler<Deposit> for Account {
+    ///     fn handle(&mut self, msg: Deposit, _context: &ActorContext<Self>) {
+    ///         self.balance += msg.0;
+    ///         self.deposits += 1;
+    ///     }
+    /// }
+    /// impl Handler<Withdraw> for Account {
+    ///     fn handle(&mut self, msg: Withdraw, _context: &ActorContext<Self>) {
+    ///         self.balance -= msg.0;
+    ///     }
+    /// }
+    /// struct AccountFactory {}
+    /// impl ActorFactory<Account> for AccountFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Account>) -> Account {
+    ///         Account { owner: String::from("alice"), balance: 100, deposits: 0 }
+    ///     }
+    /// }
+    ///
+    /// struct Counter { count: u64 }
+    /// impl Actor for Counter {
+    ///     fn snapshot(&self) -> Vec<u8> {
+    ///         self.count.to_le_bytes().to_vec()
+    ///     }
+    /// }
+    /// impl Handler<Deposit> for Counter {
+    ///     fn handle(&mut self, msg: Deposit, _context: &ActorContext<Self>) {
+    ///         self.count += msg.0;
+    ///     }
+    /// }
+    /// struct CounterFactory {}
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { count: 0 }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let account = actor_system
+    ///     .builder()
+    ///     .set_state_capture_capacity(4)
+    ///     .set_snapshot_format(SnapshotFormat::Json)
+    ///     .add_capture_around("Withdraw")
+    ///     .spawn("account", AccountFactory {})
+    ///     .unwrap();
+    ///
+    /// // two manual captures name exactly the mutated fields
+    /// let first = account.capture_state("first").unwrap();
+    /// account.send(Deposit(50));
+    /// let second = account.capture_state("second").unwrap();
+    /// let expected = vec![
+    ///     FieldChange { path: String::from("balance"), before: Some(String::from("100")), after: Some(String::from("150")) },
+    ///     FieldChange { path: String::from("deposits"), before: Some(String::from("0")), after: Some(String::from("1")) },
+    /// ];
+    /// assert_eq!(CaptureDiff::Structural(expected), ActorSystem::diff_captures(&first, &second));
+    ///
+    /// // the suspected message is bracketed automatically, the oldest capture is evicted
+    /// account.send(Withdraw(30));
+    /// account.capture_state("third").unwrap();
+    /// let captures = account.list_captures();
+    /// let labels: Vec<&str> = captures.iter().map(|capture| capture.label.as_str()).collect();
+    /// assert_eq!(vec!["second", "before Withdraw", "after Withdraw", "third"], labels);
+    /// assert!(captures.windows(2).all(|pair| pair[0].sequence < pair[1].sequence));
+    /// let expected = vec![
+    ///     FieldChange { path: String::from("balance"), before: Some(String::from("150")), after: Some(String::from("120")) },
+    /// ];
+    /// assert_eq!(CaptureDiff::Structural(expected), ActorSystem::diff_captures(&captures[1], &captures[2]));
+    ///
+    /// // opaque snapshots are only compared byte by byte
+    /// let counter = actor_system.builder().set_state_capture_capacity(2).spawn("counter", CounterFactory {}).unwrap();
+    /// let before = counter.capture_state("before").unwrap();
+    /// counter.send(Deposit(3));
+    /// let after = counter.capture_state("after").unwrap();
+    /// match ActorSystem::diff_captures(&before, &after) {
+    ///     CaptureDiff::Bytes(summary) => {
+    ///         assert_eq!((8, 8), (summary.before_len, summary.after_len));
+    ///         assert_eq!(Some(0), summary.first_difference);
+    ///         assert_ne!(summary.before_hash, summary.after_hash);
+    ///     }
+    ///     CaptureDiff::Structural(_) => panic!("expected a byte summary"),
+    /// }
+    ///
+    /// let uncaptured = actor_system.builder().spawn("uncaptured", CounterFactory {}).unwrap();
+    /// assert_eq!(Err(CaptureError::Disabled), uncaptured.capture_state("never"));
+    /// ```
+    pub fn capture_state(&self, label: &str) -> Result<StateCapture, CaptureError> {
+        if self.mailbox.captures.is_none() {
+            return Err(CaptureError::Disabled);
+        }
+        if self.mailbox.is_awaiting_release() {
+            return Err(CaptureError::AwaitingRelease);
+        }
+        if self.mailbox.is_stopped() {
+            return Err(CaptureError::Stopped);
+        }
+        let (sender, receiver) = bounded(1);
+        let _ = self.send(CaptureStateMessage {
+            label: String::from(label),
+            result: sender,
+        });
+        // the message is dropped together with the sender if the Actor stops before it is handled
+        receiver.recv().unwrap_or(Err(CaptureError::Stopped))
+    }
+
+    /// Returns the kept state captures of the Actor, oldest first, see [capture_state](#method.capture_state)
+    pub fn list_captures(&self) -> Vec<StateCapture> {
+        self.mailbox.captures.as_ref().map_or_else(Vec::new, |captures| captures.list())
+    }
+
+    pub(crate) fn get_metrics(&self) -> &ActorMetrics {
+        &self.metrics
+    }
+
+    pub(crate) fn get_wakeup_manager(&self) -> &WakeupManager {
+        &self.wakeup_manager
+    }
+}
+
+impl<A> PartialEq for ActorWrapper<A>
+where
+    A: Actor + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl<A> Eq for ActorWrapper<A> where A: Actor + 'static {}
+
+impl<A> Hash for ActorWrapper<A>
+where
+    A: Actor + 'static,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+    }
+}
+
+impl<A> Debug for ActorWrapper<A>
+where
+    A: Actor + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActorWrapper").field("address", &self.address).finish()
+    }
 }
 
 impl<A> Clone for ActorWrapper<A>
@@ -66,6 +3054,7 @@ where
             wakeup_manager: self.wakeup_manager.clone(),
             mailbox: self.mailbox.clone(),
             address: self.address.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -0,0 +1,154 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::unhandled::{self, UnhandledInfo};
+use crate::message::actor_message::ActorMessage;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Handles consecutive queued messages of type `M` within a single call, see [ActorBuilder.add_batching](../prelude/struct.ActorBuilder.html#method.add_batching)
+///
+/// [Handler](../prelude/trait.Handler.html) is still required to send `M`, it is only used for Actors that have been spawned without batching `M`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Sample { value: u64 }
+/// impl ActorMessage for Sample {}
+///
+/// struct Flush {}
+/// impl ActorMessage for Flush {}
+///
+/// struct Writer {
+///     log: Arc<Mutex<Vec<String>>>,
+/// }
+/// impl Actor for Writer {}
+/// impl Handler<Sample> for Writer {
+///     fn handle(&mut self, msg: Sample, context: &ActorContext<Self>) {
+///         self.handle_batch(vec![msg], context);
+///     }
+/// }
+/// impl BatchHandler<Sample> for Writer {
+///     fn handle_batch(&mut self, msgs: Vec<Sample>, _context: &ActorContext<Self>) {
+///         let values: Vec<String> = msgs.iter().map(|msg| msg.value.to_string()).collect();
+///         self.log.lock().unwrap().push(values.join(","));
+///     }
+/// }
+/// impl Handler<Flush> for Writer {
+///     fn handle(&mut self, _msg: Flush, _context: &ActorContext<Self>) {
+///         self.log.lock().unwrap().push(String::from("flush"));
+///     }
+/// }
+///
+/// struct WriterFactory {
+///     log: Arc<Mutex<Vec<String>>>,
+/// }
+/// impl ActorFactory<Writer> for WriterFactory {
+///     fn new_actor(&self, _context: ActorContext<Writer>) -> Writer {
+///         Writer { log: self.log.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let log = Arc::new(Mutex::new(Vec::new()));
+/// let writer = actor_system
+///     .builder()
+///     .set_message_throughput(4)
+///     .add_batching::<Sample>()
+///     .start_suspended(true)
+///     .spawn("writer", WriterFactory { log: log.clone() })
+///     .unwrap();
+///
+/// for value in 0..6 {
+///     writer.send(Sample { value }).unwrap();
+/// }
+/// writer.send(Flush {}).unwrap();
+/// writer.send(Sample { value: 6 }).unwrap();
+/// writer.release();
+/// sleep(Duration::from_millis(200));
+///
+/// // batches stop at messages of other types and never exceed the message throughput
+/// assert_eq!(vec!["0,1,2,3", "4,5", "flush", "6"], *log.lock().unwrap());
+/// let stats = actor_system.actor_stats(writer.get_address()).unwrap();
+/// assert_eq!(8, stats.processed);
+/// ```
+pub trait BatchHandler<M>: Actor + Handler<M> + Sized
+where
+    M: ActorMessage,
+{
+    /// `msgs` holds at least one message, in the order they have been sent
+    fn handle_batch(&mut self, msgs: Vec<M>, context: &ActorContext<Self>);
+}
+
+type Dispatch<A> = Arc<dyn Fn(&mut A, Vec<Box<dyn Any + Send>>, &ActorContext<A>) + Send + Sync>;
+
+/// Batched message types of an Actor, registered through [ActorBuilder.add_batching](../prelude/struct.ActorBuilder.html#method.add_batching)
+pub(crate) struct Batching<A>
+where
+    A: Actor + 'static,
+{
+    dispatchers: HashMap<TypeId, Dispatch<A>>,
+}
+
+impl<A> Clone for Batching<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            dispatchers: self.dispatchers.clone(),
+        }
+    }
+}
+
+impl<A> Batching<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            dispatchers: HashMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dispatchers.is_empty()
+    }
+
+    pub fn add<M>(&mut self)
+    where
+        A: BatchHandler<M>,
+        M: ActorMessage + 'static,
+    {
+        let dispatch: Dispatch<A> = Arc::new(|actor, msgs, context| {
+            let mut batch = Vec::with_capacity(msgs.len());
+            for msg in msgs {
+                match msg.downcast::<M>() {
+                    Ok(msg) => batch.push(*msg),
+                    Err(_) => {
+                        let info = UnhandledInfo::Typed {
+                            type_name: std::any::type_name::<M>(),
+                        };
+                        unhandled::dispatch(actor, info, context);
+                    }
+                }
+            }
+            if !batch.is_empty() {
+                actor.handle_batch(batch, context);
+            }
+        });
+        self.dispatchers.insert(TypeId::of::<M>(), dispatch);
+    }
+
+    pub fn get(&self, type_id: TypeId) -> Option<Dispatch<A>> {
+        self.dispatchers.get(&type_id).cloned()
+    }
+}
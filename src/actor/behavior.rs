@@ -0,0 +1,144 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::message_registry;
+use crate::message::actor_message::ActorMessage;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+type BehaviorHandler<A> = Arc<dyn Fn(&mut A, Box<dyn Any + Send>, &ActorContext<A>) + Send + Sync>;
+
+/// Handler overrides that take precedence over the [Handler] implementations of an Actor, see [ActorContext.become_behavior](../prelude/struct.ActorContext.html#method.become_behavior)
+pub struct Behavior<A>
+where
+    A: Actor + 'static,
+{
+    handlers: HashMap<TypeId, BehaviorHandler<A>>,
+    /// messages without an override are handed to the unhandled hook instead of the [Handler]
+    is_fallback_unhandled: bool,
+}
+
+impl<A> Behavior<A>
+where
+    A: Actor + 'static,
+{
+    /// Creates a behavior without overrides, every message is handled by the [Handler] of the Actor
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            is_fallback_unhandled: false,
+        }
+    }
+
+    /// Handles messages of type `M` with `handler` instead of the [Handler] of the Actor, replaces a previous override of `M`
+    pub fn on<M, F>(mut self, handler: F) -> Self
+    where
+        M: ActorMessage + 'static,
+        A: Handler<M>,
+        F: Fn(&mut A, M, &ActorContext<A>) + Send + Sync + 'static,
+    {
+        let handler: BehaviorHandler<A> = Arc::new(move |actor, msg, context| {
+            if let Ok(msg) = msg.downcast::<M>() {
+                handler(actor, *msg, context);
+            }
+        });
+        self.handlers.insert(TypeId::of::<M>(), handler);
+        self
+    }
+
+    /// Messages without an override are handed to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message) instead of the [Handler] of the Actor
+    pub fn fallback_to_unhandled(mut self) -> Self {
+        self.is_fallback_unhandled = true;
+        self
+    }
+}
+
+impl<A> Default for Behavior<A>
+where
+    A: Actor + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returned by [ActorContext.become_behavior](../prelude/struct.ActorContext.html#method.become_behavior)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BehaviorError {
+    /// the stack already holds `general.max_behavior_depth` behaviors, the behavior has been dropped
+    DepthExceeded { max_depth: usize },
+}
+
+/// How the current behavior handles a message
+pub(crate) enum BehaviorDispatch<A>
+where
+    A: Actor + 'static,
+{
+    Override(BehaviorHandler<A>),
+    Unhandled,
+    Static,
+}
+
+/// Behaviors of an Actor, the last one is active
+pub(crate) struct BehaviorStack<A>
+where
+    A: Actor + 'static,
+{
+    max_depth: usize,
+    behaviors: Mutex<Vec<Behavior<A>>>,
+}
+
+impl<A> BehaviorStack<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            behaviors: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn push(&self, behavior: Behavior<A>) -> Result<(), BehaviorError> {
+        let mut behaviors = self.behaviors.lock().unwrap();
+        if behaviors.len() >= self.max_depth {
+            return Err(BehaviorError::DepthExceeded {
+                max_depth: self.max_depth,
+            });
+        }
+        behaviors.push(behavior);
+        Ok(())
+    }
+
+    pub fn pop(&self) -> bool {
+        self.behaviors.lock().unwrap().pop().is_some()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.behaviors.lock().unwrap().len()
+    }
+
+    pub fn clear(&self) {
+        self.behaviors.lock().unwrap().clear();
+    }
+
+    /// Messages of the library are always handled by their [Handler]
+    ///
+    /// The override is cloned out of the stack, so that it can switch behaviors itself
+    pub fn dispatch(&self, type_id: TypeId) -> BehaviorDispatch<A> {
+        if message_registry::is_builtin::<A>(type_id) {
+            return BehaviorDispatch::Static;
+        }
+        let behaviors = self.behaviors.lock().unwrap();
+        let behavior = match behaviors.last() {
+            Some(behavior) => behavior,
+            None => return BehaviorDispatch::Static,
+        };
+        match behavior.handlers.get(&type_id) {
+            Some(handler) => BehaviorDispatch::Override(handler.clone()),
+            None if behavior.is_fallback_unhandled => BehaviorDispatch::Unhandled,
+            None => BehaviorDispatch::Static,
+        }
+    }
+}
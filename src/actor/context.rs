@@ -1,7 +1,44 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::TryActorFactory;
 use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::behavior::{Behavior, BehaviorDispatch, BehaviorError, BehaviorStack};
+use crate::actor::effect_guard::EffectGuard;
+use crate::actor::handler::Handler;
+use crate::actor::journal::JournalEvent;
+use crate::actor::pressure::MailboxPressure;
+use crate::actor::relocation::MoveError;
+use crate::actor::replacement::Replacement;
+use crate::actor::protocol::ProtocolError;
+use crate::actor::reply::{ActorAddressHandle, ReplyPath, ReplyTo};
+use crate::actor::request_chain::{RequestChain, RequestChains};
+use crate::actor::retry_policy::{self, RetryPolicy, ATTEMPT_HEADER};
+use crate::actor::rng::{ActorRng, RngReseed};
+use crate::actor::send_batch::{self, SendBatch, SendBatchReport};
+use crate::actor::supervision::{ChildTerminated, Terminated};
+use crate::actor::timer::{Deliver, Delivery, ScheduledHandle, Timer, MIN_INTERVAL};
+use crate::actor::suspension::{SuspendCondition, SuspensionRequest, SuspensionToken};
+use crate::actor::control_flow::ActorResult;
+use crate::actor::try_handler::ActorError;
+use crate::actor::unhandled::UnhandledInfo;
+use crate::message::actor_message::ActorMessage;
+use crate::message::child_terminated_message::ChildTerminatedMessage;
+use crate::message::envelope::MessageEnvelope;
+use crate::message::headers::{self, HeaderValue, Headers};
+use crate::message::provenance::{Provenance, ProvenanceAction, ProvenanceHop, PROVENANCE_HEADER};
+use crate::message::serialized_message::SerializedMessage;
+use crate::message::send_error::SendError;
+use crate::message::trace::{self, TraceId};
 use crate::prelude::Actor;
 use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::SpawnError;
+use crate::system::dead_letters::DeadLetterReason;
+use crate::system::event_bus::EventBus;
+use std::any::TypeId;
 use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
 
 /// Enables access to [ActorSystem] and [Actor] within [Handler](./trait.Handler.html) implementations
 ///
@@ -13,10 +50,2441 @@ where
 {
     pub actor_ref: ActorWrapper<A>,
     pub system: ActorSystem,
+    suspension: Arc<Mutex<Option<SuspensionRequest>>>,
+    /// error returned by the [TryHandler](../prelude/trait.TryHandler.html) of the current message
+    failure: Arc<Mutex<Option<ActorError>>>,
+    /// returned by the [ControlFlowHandler](../prelude/trait.ControlFlowHandler.html) of the current message
+    result: Arc<Mutex<Option<ActorResult>>>,
+    headers: Arc<Mutex<Option<Box<Headers>>>>,
+    /// sender of the current message, only set for messages sent through [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from)
+    sender: Arc<Mutex<Option<Box<ReplyPath>>>>,
+    /// expiry of the current message, only set for messages sent with a time to live
+    expires_at: Arc<Mutex<Option<Instant>>>,
+    request_chains: RequestChains<A>,
+    behaviors: Arc<BehaviorStack<A>>,
+    /// set once the Actor has been moved to another pool, until the Executor let go of the worker of the previous pool
+    is_relocating: Arc<AtomicBool>,
+    /// set by [ActorSystem.replace_actor](../prelude/struct.ActorSystem.html#method.replace_actor), until the Executor replaced the Actor
+    replacement: Arc<Mutex<Option<Replacement<A>>>>,
+    rng: Arc<Mutex<ActorRng>>,
+    /// generator as it was at spawn, restarts are derived from it
+    initial_rng: ActorRng,
 }
 
 impl<A> UnwindSafe for ActorContext<A> where A: Actor + 'static {}
 
+impl<A> ActorContext<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub(crate) fn new(actor_ref: ActorWrapper<A>, system: ActorSystem, rng: ActorRng) -> Self {
+        let max_request_chains = system.get_config().general.max_request_chains;
+        let request_chains = RequestChains::new(actor_ref.get_metrics().clone(), max_request_chains);
+        let behaviors = Arc::new(BehaviorStack::new(system.get_config().general.max_behavior_depth));
+        Self {
+            actor_ref,
+            system,
+            suspension: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
+            result: Arc::new(Mutex::new(None)),
+            headers: Arc::new(Mutex::new(None)),
+            sender: Arc::new(Mutex::new(None)),
+            expires_at: Arc::new(Mutex::new(None)),
+            request_chains,
+            behaviors,
+            is_relocating: Arc::new(AtomicBool::new(false)),
+            replacement: Arc::new(Mutex::new(None)),
+            rng: Arc::new(Mutex::new(rng.clone())),
+            initial_rng: rng,
+        }
+    }
+
+    /// Returns the [Headers](../prelude/struct.Headers.html) of the message that is currently handled
+    ///
+    /// See [ActorWrapper.send_with_headers](../prelude/struct.ActorWrapper.html#method.send_with_headers)
+    pub fn headers(&self) -> Headers {
+        match self.headers.lock().unwrap().as_ref() {
+            Some(headers) => headers.as_ref().clone(),
+            None => Headers::new(),
+        }
+    }
+
+    /// Returns the [TraceId](../prelude/struct.TraceId.html) of the message that is currently handled, `None` if it is not traced
+    ///
+    /// See [ActorWrapper.send_traced](../prelude/struct.ActorWrapper.html#method.send_traced)
+    pub fn trace_id(&self) -> Option<TraceId> {
+        trace::of(self.headers.lock().unwrap().as_deref())
+    }
+
+    /// Delivery attempt of the message that is currently handled, `1` unless it has been redelivered through [retry](#method.retry)
+    pub fn delivery_attempt(&self) -> u32 {
+        retry_policy::attempt_of(self.headers.lock().unwrap().as_deref())
+    }
+
+    pub(crate) fn set_headers(&self, headers: Option<Box<Headers>>) {
+        *self.headers.lock().unwrap() = headers;
+    }
+
+    /// Returns the sender of the message that is currently handled, `None` if it has not been sent through [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping { id: usize }
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Pong { id: usize }
+    /// impl ActorMessage for Pong {}
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    ///
+    /// // neither the messages nor the responder know who asks
+    /// struct Responder {
+    ///     unanswered: Arc<Mutex<usize>>,
+    /// }
+    /// impl Actor for Responder {}
+    /// impl Handler<Ping> for Responder {
+    ///     fn handle(&mut self, msg: Ping, context: &ActorContext<Self>) {
+    ///         if !context.reply(Pong { id: msg.id }) {
+    ///             *self.unanswered.lock().unwrap() += 1;
+    ///         }
+    ///     }
+    /// }
+    /// struct ResponderFactory {
+    ///     unanswered: Arc<Mutex<usize>>,
+    /// }
+    /// impl ActorFactory<Responder> for ResponderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Responder>) -> Responder {
+    ///         Responder { unanswered: self.unanswered.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Requester {
+    ///     responder: ActorWrapper<Responder>,
+    ///     answers: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl Actor for Requester {}
+    /// impl Handler<Start> for Requester {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         for id in 0..3 {
+    ///             self.responder.send_from(Ping { id }, context.reply_to::<Pong>()).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Pong> for Requester {
+    ///     fn handle(&mut self, msg: Pong, _context: &ActorContext<Self>) {
+    ///         self.answers.lock().unwrap().push(msg.id);
+    ///     }
+    /// }
+    /// struct RequesterFactory {
+    ///     responder: ActorWrapper<Responder>,
+    ///     answers: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl ActorFactory<Requester> for RequesterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Requester>) -> Requester {
+    ///         Requester { responder: self.responder.clone(), answers: self.answers.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let unanswered = Arc::new(Mutex::new(0));
+    /// let answers = Arc::new(Mutex::new(Vec::new()));
+    /// let responder = actor_system.builder().spawn("responder", ResponderFactory { unanswered: unanswered.clone() }).unwrap();
+    /// let requester = actor_system
+    ///     .builder()
+    ///     .spawn("requester", RequesterFactory { responder: responder.clone(), answers: answers.clone() })
+    ///     .unwrap();
+    /// requester.send(Start {}).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![0, 1, 2], *answers.lock().unwrap());
+    ///
+    /// // without a sender the reply is a dead letter
+    /// let dead_letters = actor_system.dead_letter_count();
+    /// responder.send(Ping { id: 3 }).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(1, *unanswered.lock().unwrap());
+    /// assert_eq!(dead_letters + 1, actor_system.dead_letter_count());
+    /// ```
+    pub fn sender(&self) -> Option<ActorAddressHandle> {
+        self.sender
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|path| ActorAddressHandle::new(path, self.system.clone()))
+    }
+
+    pub(crate) fn set_sender(&self, sender: Option<Box<ReplyPath>>) {
+        *self.sender.lock().unwrap() = sender;
+    }
+
+    pub(crate) fn set_expires_at(&self, expires_at: Option<Instant>) {
+        *self.expires_at.lock().unwrap() = expires_at;
+    }
+
+    /// Marks this Actor ready, independent of what [Actor.is_ready](../prelude/trait.Actor.html#method.is_ready) returns, see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    ///
+    /// Can be called from any thread through a clone of the context, e.g. once a background initialization finished
+    pub fn notify_ready(&self) {
+        if self.actor_ref.get_mailbox().mark_ready() {
+            self.system.get_state().notify_actor_ready(self.actor_ref.get_address());
+        }
+    }
+
+    /// Typed path back to this Actor, see [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from)
+    pub fn reply_to<M>(&self) -> ReplyTo<M>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        ReplyTo::new(&self.actor_ref)
+    }
+
+    /// Same as [ActorAddressHandle.reply](../prelude/struct.ActorAddressHandle.html#method.reply) of the [sender](#method.sender), the reply is a dead letter if there is no sender
+    ///
+    /// Without a sender the dead letter is addressed to this Actor
+    pub fn reply<M>(&self, msg: M) -> bool
+    where
+        M: ActorMessage + 'static,
+    {
+        match self.sender() {
+            Some(sender) => sender.reply(msg),
+            None => {
+                self.system
+                    .report_dead_letter(self.actor_ref.get_address(), std::any::type_name::<M>());
+                false
+            }
+        }
+    }
+
+    /// Sends `response` to the `reply_to` address of `original` with the same `correlation_id`, see [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address)
+    ///
+    /// Fails with [ProtocolError::MissingReplyTo](../prelude/enum.ProtocolError.html#variant.MissingReplyTo) if `original` has no `reply_to` address
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Responder {
+    ///     context: ActorContext<Self>,
+    ///     errors: Arc<Mutex<Vec<ProtocolError>>>,
+    /// }
+    /// impl Actor for Responder {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         let response = [b"re: ".as_slice(), &msg.content].concat();
+    ///         if let Err(error) = self.context.reply_serialized(&msg, response) {
+    ///             self.errors.lock().unwrap().push(error);
+    ///         }
+    ///     }
+    /// }
+    /// struct ResponderFactory {
+    ///     errors: Arc<Mutex<Vec<ProtocolError>>>,
+    /// }
+    /// impl ActorFactory<Responder> for ResponderFactory {
+    ///     fn new_actor(&self, context: ActorContext<Responder>) -> Responder {
+    ///         Responder { context, errors: self.errors.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Requester {
+    ///     responses: Arc<Mutex<Vec<SerializedMessage>>>,
+    /// }
+    /// impl Actor for Requester {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.responses.lock().unwrap().push(msg);
+    ///     }
+    /// }
+    /// struct RequesterFactory {
+    ///     responses: Arc<Mutex<Vec<SerializedMessage>>>,
+    /// }
+    /// impl ActorFactory<Requester> for RequesterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Requester>) -> Requester {
+    ///         Requester { responses: self.responses.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let errors = Arc::new(Mutex::new(Vec::new()));
+    /// let responses = Arc::new(Mutex::new(Vec::new()));
+    /// let responder = actor_system.builder().spawn("responder", ResponderFactory { errors: errors.clone() }).unwrap();
+    /// let requester = actor_system.builder().spawn("requester", RequesterFactory { responses: responses.clone() }).unwrap();
+    ///
+    /// let request = SerializedMessage::new(b"ping".to_vec())
+    ///     .with_correlation_id(42)
+    ///     .with_reply_to(requester.get_address().clone())
+    ///     .with_content_type("text/plain");
+    /// // the metadata survives the serialization to a peer
+    /// let request: SerializedMessage = serde_json::from_str(&serde_json::to_string(&request).unwrap()).unwrap();
+    /// actor_system.try_send_to_address(responder.get_address(), request).unwrap();
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// let responses = responses.lock().unwrap();
+    /// assert_eq!(1, responses.len());
+    /// assert_eq!(Some(42), responses[0].correlation_id);
+    /// assert_eq!(b"re: ping".to_vec(), responses[0].content);
+    ///
+    /// // nobody to answer to
+    /// actor_system.send_to_address(responder.get_address(), SerializedMessage::new(b"ping".to_vec()));
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![ProtocolError::MissingReplyTo], *errors.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn reply_serialized(&self, original: &SerializedMessage, response: Vec<u8>) -> Result<(), ProtocolError> {
+        let reply_to = original.reply_to.as_ref().ok_or(ProtocolError::MissingReplyTo)?;
+        let mut response = SerializedMessage::new(response);
+        response.correlation_id = original.correlation_id;
+        self.system.try_send_to_address(reply_to, response)
+    }
+
+    /// Hands `item` to every [MessageStream](../prelude/struct.MessageStream.html) of type `M` that is attached to the Actor
+    ///
+    /// Only a single atomic load if no stream is attached, see [ActorWrapper.subscribe_stream](../prelude/struct.ActorWrapper.html#method.subscribe_stream)
+    pub fn emit<M>(&self, item: M)
+    where
+        M: Clone + Send + 'static,
+    {
+        self.actor_ref.get_mailbox().emitter.emit(item);
+    }
+
+    /// Returns the random number generator of the Actor
+    ///
+    /// Seeded from `general.rng_seed` and the address of the Actor, so that every Actor of a seeded system draws the same sequence in every run, regardless of the scheduling.
+    /// Without a seed every Actor is seeded from OS entropy. See [ActorBuilder.set_rng_reseed](../prelude/struct.ActorBuilder.html#method.set_rng_reseed) for restarts
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Tick {}
+    /// impl ActorMessage for Tick {}
+    ///
+    /// struct Agent { wealth: Arc<Mutex<u64>> }
+    /// impl Actor for Agent {}
+    /// impl Handler<Tick> for Agent {
+    ///     fn handle(&mut self, _msg: Tick, context: &ActorContext<Self>) {
+    ///         let mut rng = context.rng();
+    ///         if rng.gen_bool(0.3) {
+    ///             *self.wealth.lock().unwrap() += rng.gen_range(1..100);
+    ///         }
+    ///     }
+    /// }
+    /// struct AgentFactory { wealth: Arc<Mutex<u64>> }
+    /// impl ActorFactory<Agent> for AgentFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Agent>) -> Agent {
+    ///         Agent { wealth: self.wealth.clone() }
+    ///     }
+    /// }
+    ///
+    /// let simulate = |seed: u64| -> Vec<u64> {
+    ///     let mut config = TyractorsaurConfig::new().unwrap();
+    ///     config.general.rng_seed = Some(seed);
+    ///     let actor_system = ActorSystem::new(config);
+    ///     let wealth: Vec<Arc<Mutex<u64>>> = (0..10).map(|_| Arc::new(Mutex::new(0))).collect();
+    ///     let agents: Vec<ActorWrapper<Agent>> = wealth
+    ///         .iter()
+    ///         .enumerate()
+    ///         .map(|(i, wealth)| {
+    ///             let factory = AgentFactory { wealth: wealth.clone() };
+    ///             actor_system.builder().spawn(format!("agent-{}", i), factory).unwrap()
+    ///         })
+    ///         .collect();
+    ///     for _ in 0..50 {
+    ///         for agent in agents.iter() {
+    ///             agent.send(Tick {});
+    ///         }
+    ///     }
+    ///     sleep(Duration::from_millis(200));
+    ///     actor_system.stop(Duration::from_secs(1));
+    ///     actor_system.await_shutdown();
+    ///     wealth.iter().map(|wealth| *wealth.lock().unwrap()).collect()
+    /// };
+    ///
+    /// let first = simulate(7);
+    /// assert_eq!(first, simulate(7));
+    /// assert_ne!(first, simulate(8));
+    /// ```
+    pub fn rng(&self) -> MutexGuard<'_, ActorRng> {
+        self.rng.lock().unwrap()
+    }
+
+    pub(crate) fn reseed_rng(&self, reseed: RngReseed, restarts: usize) {
+        *self.rng.lock().unwrap() = self.initial_rng.reseed(reseed, restarts);
+    }
+
+    /// Executes external side effects at most once per key, see [EffectGuard](../prelude/struct.EffectGuard.html)
+    pub fn effect_guard(&self) -> EffectGuard<'_, A> {
+        EffectGuard::new(self, self.actor_ref.get_mailbox().effect_ledger.as_ref())
+    }
+
+    /// Appends a note or key-value fields to the journal of the Actor, does nothing if the journal is disabled
+    ///
+    /// Notes given as `&'static str` do not allocate, see [ActorBuilder.set_journal_capacity](../prelude/struct.ActorBuilder.html#method.set_journal_capacity)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// enum Order { Place(u32), Corrupt }
+    /// impl ActorMessage for Order {}
+    ///
+    /// struct Shop { report: Arc<Mutex<Option<ActorPanicReport>>>, context: ActorContext<Self> }
+    /// impl Actor for Shop {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         *self.report.lock().unwrap() = Some(report);
+    ///     }
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, msg: Order, context: &ActorContext<Self>) {
+    ///         match msg {
+    ///             Order::Place(id) => context.journal(vec![("order", id.to_string())]),
+    ///             Order::Corrupt => {
+    ///                 context.journal("rejecting corrupt order");
+    ///                 panic!("corrupt order");
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// struct ShopFactory { report: Arc<Mutex<Option<ActorPanicReport>>> }
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, context: ActorContext<Shop>) -> Shop {
+    ///         Shop { report: self.report.clone(), context }
+    ///     }
+    /// }
+    ///
+    /// struct Archive { entries: Arc<Mutex<Vec<JournalEntry>>> }
+    /// impl JournalSink for Archive {
+    ///     fn write(&self, _address: &ActorAddress, entry: JournalEntry) {
+    ///         self.entries.lock().unwrap().push(entry);
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let report = Arc::new(Mutex::new(None));
+    /// let archived = Arc::new(Mutex::new(Vec::new()));
+    /// let shop = actor_system
+    ///     .builder()
+    ///     .set_restart_policy(RestartPolicy::Always)
+    ///     .set_journal_capacity(8)
+    ///     .set_journal_sink(Archive { entries: archived.clone() })
+    ///     .spawn("shop", ShopFactory { report: report.clone() })
+    ///     .unwrap();
+    /// for id in 0..10 {
+    ///     shop.send(Order::Place(id));
+    /// }
+    /// shop.send(Order::Corrupt);
+    /// for id in 10..15 {
+    ///     shop.send(Order::Place(id));
+    /// }
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// let order = |id: u32| JournalEvent::Fields(vec![("order", id.to_string())]);
+    /// let note = JournalEvent::from("rejecting corrupt order");
+    ///
+    /// // spawned, 10 orders, the note and the restart, then 5 more orders; only the last 8 are kept
+    /// let journal = shop.journal();
+    /// let sequences: Vec<u64> = journal.iter().map(|entry| entry.sequence).collect();
+    /// assert_eq!((10..18).collect::<Vec<u64>>(), sequences);
+    /// assert_eq!(order(9), journal[0].event);
+    /// assert_eq!(note, journal[1].event);
+    /// assert!(matches!(&journal[2].event, JournalEvent::Restarted { reason: Some(reason), .. } if reason == "corrupt order"));
+    /// let events: Vec<JournalEvent> = journal[3..].iter().map(|entry| entry.event.clone()).collect();
+    /// assert_eq!((10..15).map(order).collect::<Vec<JournalEvent>>(), events);
+    ///
+    /// // the report ends with the note written right before the panic
+    /// let report = report.lock().unwrap().clone().unwrap();
+    /// let sequences: Vec<u64> = report.journal.iter().map(|entry| entry.sequence).collect();
+    /// assert_eq!((4..12).collect::<Vec<u64>>(), sequences);
+    /// assert_eq!(note, report.journal.last().unwrap().event);
+    ///
+    /// // evicted entries are archived in order
+    /// let evicted = archived.lock().unwrap().clone();
+    /// let sequences: Vec<u64> = evicted.iter().map(|entry| entry.sequence).collect();
+    /// assert_eq!((0..10).collect::<Vec<u64>>(), sequences);
+    /// assert_eq!(JournalEvent::Spawned, evicted[0].event);
+    /// assert_eq!(order(8), evicted[9].event);
+    ///
+    /// // the journal does not outlive the Actor, its remaining entries are archived once it stopped
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert!(shop.journal().is_empty());
+    /// let archived = archived.lock().unwrap();
+    /// assert_eq!(19, archived.len());
+    /// assert_eq!(JournalEvent::Stopped, archived.last().unwrap().event);
+    /// ```
+    pub fn journal(&self, event: impl Into<JournalEvent>) {
+        if let Some(journal) = &self.actor_ref.get_mailbox().journal {
+            journal.append(event.into());
+        }
+    }
+
+    /// Returns the chain of the message that is currently handled, `None` unless its type is registered through [ActorSystem.register_audited](../prelude/struct.ActorSystem.html#method.register_audited)
+    ///
+    /// The chain already contains the hop of this Actor
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::prelude::ProvenanceAction::{DerivedFrom, Forwarded, Handled};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Transfer { amount: u64 }
+    /// impl ActorMessage for Transfer {}
+    /// impl Audited for Transfer {}
+    /// struct Receipt { amount: u64 }
+    /// impl ActorMessage for Receipt {}
+    /// impl Audited for Receipt {}
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// type Seen = Arc<Mutex<Vec<(&'static str, &'static str, Option<Provenance>)>>>;
+    ///
+    /// #[derive(Clone)]
+    /// struct Node { name: &'static str, next: Option<ActorWrapper<Node>>, ledger: Option<ActorWrapper<Node>>, seen: Seen }
+    /// impl Actor for Node {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         self.seen.lock().unwrap().push((self.name, "panic", report.provenance));
+    ///     }
+    /// }
+    /// impl Handler<Transfer> for Node {
+    ///     fn handle(&mut self, msg: Transfer, context: &ActorContext<Self>) {
+    ///         self.seen.lock().unwrap().push((self.name, "transfer", context.provenance()));
+    ///         if let Some(ledger) = &self.ledger {
+    ///             ledger.send(Receipt { amount: msg.amount });
+    ///         }
+    ///         match &self.next {
+    ///             Some(next) => context.forward(next, msg),
+    ///             None if msg.amount == 0 => panic!("rejected"),
+    ///             None => {}
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Receipt> for Node {
+    ///     fn handle(&mut self, _msg: Receipt, context: &ActorContext<Self>) {
+    ///         self.seen.lock().unwrap().push((self.name, "receipt", context.provenance()));
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Node {
+    ///     fn handle(&mut self, _msg: Ping, context: &ActorContext<Self>) {
+    ///         self.seen.lock().unwrap().push((self.name, "ping", context.provenance()));
+    ///     }
+    /// }
+    /// struct NodeFactory(Node);
+    /// impl ActorFactory<Node> for NodeFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Node>) -> Node {
+    ///         self.0.clone()
+    ///     }
+    /// }
+    ///
+    /// // a -> b -> c -> d, b derives a receipt for the ledger
+    /// fn run(max_provenance_hops: usize) -> Vec<(&'static str, &'static str, Option<Provenance>)> {
+    ///     let mut config = TyractorsaurConfig::new().unwrap();
+    ///     config.general.max_provenance_hops = max_provenance_hops;
+    ///     let actor_system = ActorSystem::new(config);
+    ///     actor_system.register_audited::<Transfer>();
+    ///     actor_system.register_audited::<Receipt>();
+    ///     let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    ///     let spawn = |name, next, ledger| {
+    ///         let node = Node { name, next, ledger, seen: seen.clone() };
+    ///         actor_system.builder().spawn(name, NodeFactory(node)).unwrap()
+    ///     };
+    ///     let ledger = spawn("ledger", None, None);
+    ///     let d = spawn("d", None, None);
+    ///     let c = spawn("c", Some(d), None);
+    ///     let b = spawn("b", Some(c), Some(ledger.clone()));
+    ///     let a = spawn("a", Some(b), None);
+    ///     a.send(Transfer { amount: 10 });
+    ///     a.send(Transfer { amount: 0 });
+    ///     ledger.send(Ping {});
+    ///     sleep(Duration::from_millis(300));
+    ///     let seen = seen.lock().unwrap().clone();
+    ///     seen
+    /// }
+    /// fn find(seen: &[(&str, &str, Option<Provenance>)], name: &str, kind: &str) -> Option<Provenance> {
+    ///     seen.iter().find(|(n, k, _)| *n == name && *k == kind).unwrap().2.clone()
+    /// }
+    /// fn path(provenance: &Provenance) -> Vec<(&str, ProvenanceAction)> {
+    ///     provenance.hops.iter().map(|hop| (hop.actor.actor.as_str(), hop.action)).collect()
+    /// }
+    ///
+    /// let seen = run(64);
+    /// let transfer = find(&seen, "d", "transfer").unwrap();
+    /// let expected = vec![
+    ///     ("a", Handled), ("a", Forwarded),
+    ///     ("b", Handled), ("b", Forwarded),
+    ///     ("c", Handled), ("c", Forwarded),
+    ///     ("d", Handled),
+    /// ];
+    /// assert_eq!(expected, path(&transfer));
+    /// assert_eq!(path(&transfer)[..3], path(&find(&seen, "b", "transfer").unwrap())[..]);
+    ///
+    /// let receipt = find(&seen, "ledger", "receipt").unwrap();
+    /// assert_ne!(transfer.message_id, receipt.message_id);
+    /// assert_eq!(vec![("b", DerivedFrom(transfer.message_id)), ("ledger", Handled)], path(&receipt));
+    /// let link = format!("\"action\":\"DerivedFrom\",\"parent\":{}", transfer.message_id);
+    /// assert!(receipt.to_json().contains(&link));
+    ///
+    /// // panic reports carry the chain of the message that was handled
+    /// let rejected = find(&seen, "d", "panic").unwrap();
+    /// assert_ne!(transfer.message_id, rejected.message_id);
+    /// assert_eq!(expected, path(&rejected));
+    ///
+    /// // messages that are not audited never carry a chain
+    /// assert_eq!(None, find(&seen, "ledger", "ping"));
+    ///
+    /// // hops beyond the limit are counted
+    /// let truncated = find(&run(4), "d", "transfer").unwrap();
+    /// assert_eq!(expected[..4], path(&truncated)[..]);
+    /// assert_eq!(3, truncated.truncated_hops);
+    /// assert!(truncated.is_truncated());
+    /// ```
+    pub fn provenance(&self) -> Option<Provenance> {
+        Provenance::from_headers(self.headers.lock().unwrap().as_ref()?)
+    }
+
+    /// Passes `msg` on to `target` as if it had been sent by the sender of the message that is currently handled
+    ///
+    /// The [sender](#method.sender), the [headers](#method.headers) and the time to live of the current message travel along, so the reply of `target` reaches the original sender.
+    /// The message is queued like any other send of this Actor and keeps its order relative to them, if it is not delivered it is a dead letter.
+    /// An audited message keeps its [Provenance](../prelude/struct.Provenance.html) chain, a plain send would start a new chain that is derived from the current message instead,
+    /// see [ActorContext.provenance](#method.provenance)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Lookup { key: &'static str }
+    /// impl ActorMessage for Lookup {}
+    ///
+    /// struct Found { value: usize }
+    /// impl ActorMessage for Found {}
+    ///
+    /// struct Audit { key: &'static str }
+    /// impl ActorMessage for Audit {}
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    ///
+    /// struct Store { log: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Store {}
+    /// impl Handler<Audit> for Store {
+    ///     fn handle(&mut self, msg: Audit, _context: &ActorContext<Self>) {
+    ///         if msg.key == "slow" {
+    ///             sleep(Duration::from_millis(300));
+    ///         }
+    ///         self.log.lock().unwrap().push(format!("audit {}", msg.key));
+    ///     }
+    /// }
+    /// impl Handler<Lookup> for Store {
+    ///     fn handle(&mut self, msg: Lookup, context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("lookup {}", msg.key));
+    ///         context.reply(Found { value: msg.key.len() });
+    ///     }
+    /// }
+    /// struct StoreFactory { log: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Store> for StoreFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Store>) -> Store {
+    ///         Store { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// // audits every lookup and hands it on without ever seeing the answer
+    /// struct Gate { store: ActorWrapper<Store>, misrouted: Arc<Mutex<usize>> }
+    /// impl Actor for Gate {}
+    /// impl Handler<Lookup> for Gate {
+    ///     fn handle(&mut self, msg: Lookup, context: &ActorContext<Self>) {
+    ///         self.store.send(Audit { key: msg.key }).unwrap();
+    ///         context.forward(&self.store, msg);
+    ///     }
+    /// }
+    /// impl Handler<Found> for Gate {
+    ///     fn handle(&mut self, _msg: Found, _context: &ActorContext<Self>) {
+    ///         *self.misrouted.lock().unwrap() += 1;
+    ///     }
+    /// }
+    /// struct GateFactory { store: ActorWrapper<Store>, misrouted: Arc<Mutex<usize>> }
+    /// impl ActorFactory<Gate> for GateFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Gate>) -> Gate {
+    ///         Gate { store: self.store.clone(), misrouted: self.misrouted.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Client { gate: ActorWrapper<Gate>, found: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Client {}
+    /// impl Handler<Start> for Client {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         for key in ["a", "bb"] {
+    ///             self.gate.send_from(Lookup { key }, context.reply_to::<Found>()).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Found> for Client {
+    ///     fn handle(&mut self, msg: Found, _context: &ActorContext<Self>) {
+    ///         self.found.lock().unwrap().push(msg.value);
+    ///     }
+    /// }
+    /// struct ClientFactory { gate: ActorWrapper<Gate>, found: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Client> for ClientFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Client>) -> Client {
+    ///         Client { gate: self.gate.clone(), found: self.found.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let misrouted = Arc::new(Mutex::new(0));
+    /// let found = Arc::new(Mutex::new(Vec::new()));
+    /// let store = actor_system.builder().spawn("store", StoreFactory { log: log.clone() }).unwrap();
+    /// let gate = actor_system
+    ///     .builder()
+    ///     .spawn("gate", GateFactory { store: store.clone(), misrouted: misrouted.clone() })
+    ///     .unwrap();
+    /// let client = actor_system
+    ///     .builder()
+    ///     .spawn("client", ClientFactory { gate: gate.clone(), found: found.clone() })
+    ///     .unwrap();
+    /// client.send(Start {}).unwrap();
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// assert_eq!(vec![1, 2], *found.lock().unwrap());
+    /// assert_eq!(0, *misrouted.lock().unwrap());
+    /// assert_eq!(vec!["audit a", "lookup a", "audit bb", "lookup bb"], *log.lock().unwrap());
+    ///
+    /// // the time to live keeps counting from the original send
+    /// store.send(Audit { key: "slow" }).unwrap();
+    /// gate.send_with_ttl(Lookup { key: "ccc" }, Duration::from_millis(100)).unwrap();
+    /// sleep(Duration::from_millis(500));
+    /// assert_eq!(vec!["audit a", "lookup a", "audit bb", "lookup bb", "audit slow", "audit ccc"], *log.lock().unwrap());
+    /// assert_eq!(1, store.expired_count());
+    /// assert_eq!(vec![1, 2], *found.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn forward<B, M>(&self, target: &ActorWrapper<B>, msg: M)
+    where
+        B: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        let mut headers = headers::get_propagated().unwrap_or_default();
+        if let Some(original) = self.headers.lock().unwrap().as_deref() {
+            headers.merge(original.clone());
+        }
+        if let Some(mut provenance) = self.provenance() {
+            let hop = ProvenanceHop {
+                actor: self.actor_ref.get_address().clone(),
+                at: SystemTime::now(),
+                action: ProvenanceAction::Forwarded,
+            };
+            provenance.push(hop, self.system.get_config().general.max_provenance_hops);
+            headers.insert(PROVENANCE_HEADER.1, provenance.to_header());
+        }
+        let sender = self.sender.lock().unwrap().as_deref().cloned();
+        let expires_at = *self.expires_at.lock().unwrap();
+        let _ = target.forward_message(msg, Some(headers), sender, expires_at);
+    }
+
+    /// Stages all sends of `f` and delivers them once `f` returns, so that every target is woken up only once
+    ///
+    /// Sleeping targets are handed to the scheduler with a single wakeup per pool instead of one per send.
+    /// Staged sends are delivered in the order they were staged, so the order per target is kept, but they are delivered after all sends that `f` makes directly.
+    /// A target with a full bounded mailbox is woken up right away and the batch waits for room like a regular send.
+    /// The [SendBatchReport] contains the outcome of every staged send
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Update { sequence: usize }
+    /// impl ActorMessage for Update {}
+    /// struct Fanout {}
+    /// impl ActorMessage for Fanout {}
+    ///
+    /// struct Leaf { received: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Leaf {}
+    /// impl Handler<Update> for Leaf {
+    ///     fn handle(&mut self, msg: Update, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(msg.sequence);
+    ///         sleep(Duration::from_millis(1));
+    ///     }
+    /// }
+    /// struct LeafFactory { received: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Leaf> for LeafFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Leaf>) -> Leaf {
+    ///         Leaf { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Hub { leaves: Vec<ActorWrapper<Leaf>>, report: Arc<Mutex<Option<SendBatchReport>>> }
+    /// impl Actor for Hub {}
+    /// impl Handler<Fanout> for Hub {
+    ///     fn handle(&mut self, _msg: Fanout, context: &ActorContext<Self>) {
+    ///         let leaves = &self.leaves;
+    ///         let report = context.batch_sends(|batch| {
+    ///             for sequence in 0..5 {
+    ///                 for leaf in leaves.iter() {
+    ///                     batch.send(leaf, Update { sequence });
+    ///                 }
+    ///             }
+    ///         });
+    ///         *self.report.lock().unwrap() = Some(report);
+    ///     }
+    /// }
+    /// struct HubFactory { leaves: Vec<ActorWrapper<Leaf>>, report: Arc<Mutex<Option<SendBatchReport>>> }
+    /// impl ActorFactory<Hub> for HubFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Hub>) -> Hub {
+    ///         Hub { leaves: self.leaves.clone(), report: self.report.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.thread_pool.config.insert(String::from("other"), ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let actor_system = ActorSystem::new(config);
+    /// let mut leaves = Vec::new();
+    /// let mut received = Vec::new();
+    /// for index in 0..10 {
+    ///     let log = Arc::new(Mutex::new(Vec::new()));
+    ///     let builder = match index {
+    ///         // a bounded mailbox that can not hold all updates of the batch
+    ///         0 => actor_system.builder().set_mailbox_size(2),
+    ///         1..=4 => actor_system.builder().set_pool_name("other").unwrap(),
+    ///         _ => actor_system.builder(),
+    ///     };
+    ///     leaves.push(builder.spawn(format!("leaf-{}", index), LeafFactory { received: log.clone() }).unwrap());
+    ///     received.push(log);
+    /// }
+    /// let stopped = leaves.pop().unwrap();
+    /// stopped.stop();
+    /// sleep(Duration::from_millis(100));
+    /// leaves.push(stopped);
+    ///
+    /// let report = Arc::new(Mutex::new(None));
+    /// let hub = actor_system.builder().spawn("hub", HubFactory { leaves: leaves.clone(), report: report.clone() }).unwrap();
+    /// hub.send(Fanout {});
+    /// let is_delivered = |count: usize| received[..9].iter().all(|log| log.lock().unwrap().len() == count);
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while !is_delivered(5) && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(10));
+    /// }
+    ///
+    /// let report = report.lock().unwrap().take().unwrap();
+    /// assert_eq!(50, report.sends.len());
+    /// assert_eq!(45, report.delivered());
+    /// // the stopped leaf is the last target of every round
+    /// let dropped: Vec<(usize, BatchSendStatus)> = (0..5).map(|round| (round * 10 + 9, BatchSendStatus::Stopped)).collect();
+    /// assert_eq!(dropped, report.dropped());
+    /// assert!(report.sends.contains(&BatchSendStatus::DeliveredAfterWait));
+    /// assert!(report.woken > 0);
+    /// for log in received[..9].iter() {
+    ///     assert_eq!(vec![0, 1, 2, 3, 4], *log.lock().unwrap());
+    /// }
+    /// assert!(received[9].lock().unwrap().is_empty());
+    ///
+    /// // external injection works the same way
+    /// let report = actor_system.batch_sends(|batch| {
+    ///     for leaf in leaves[..9].iter() {
+    ///         batch.send(leaf, Update { sequence: 5 });
+    ///     }
+    /// });
+    /// assert_eq!(9, report.delivered());
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while !is_delivered(6) && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(10));
+    /// }
+    /// for log in received[..9].iter() {
+    ///     assert_eq!(vec![0, 1, 2, 3, 4, 5], *log.lock().unwrap());
+    /// }
+    /// ```
+    pub fn batch_sends<'t, F>(&self, f: F) -> SendBatchReport
+    where
+        F: FnOnce(&mut SendBatch<'t>),
+    {
+        send_batch::run(f)
+    }
+
+    /// Suspends the Actor after the current handler returns
+    ///
+    /// No further messages are dispatched until the [SuspendCondition] is met or the configured `max_suspension` elapses.
+    /// Messages sent in the meantime are queued and the Actor does not occupy a thread of its pool.
+    /// Afterwards [Actor.on_suspension_ended](../prelude/trait.Actor.html#method.on_suspension_ended) is executed before normal processing continues.
+    ///
+    /// Stopping the Actor cancels the suspension. Internal control messages are still delivered while the Actor is suspended.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work { id: usize }
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Waiter {
+    ///     token: Arc<Mutex<Option<SuspensionToken>>>,
+    ///     handled: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl Actor for Waiter {
+    ///     fn on_suspension_ended(&mut self, reason: SuspensionEndReason) {
+    ///         assert_eq!(SuspensionEndReason::Resumed, reason);
+    ///     }
+    /// }
+    /// impl Handler<Work> for Waiter {
+    ///     fn handle(&mut self, msg: Work, context: &ActorContext<Self>) {
+    ///         if msg.id == 0 {
+    ///             let token = context.suspend_until(SuspendCondition::external());
+    ///             *self.token.lock().unwrap() = Some(token);
+    ///         }
+    ///         self.handled.lock().unwrap().push(msg.id);
+    ///     }
+    /// }
+    ///
+    /// struct WaiterFactory {
+    ///     token: Arc<Mutex<Option<SuspensionToken>>>,
+    ///     handled: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl ActorFactory<Waiter> for WaiterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Waiter>) -> Waiter {
+    ///         Waiter { token: self.token.clone(), handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let token = Arc::new(Mutex::new(None));
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .spawn("waiter", WaiterFactory { token: token.clone(), handled: handled.clone() })
+    ///     .unwrap();
+    /// for id in 0..4 {
+    ///     actor.send(Work { id });
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![0], *handled.lock().unwrap());
+    ///
+    /// let external = token.lock().unwrap().clone().unwrap();
+    /// std::thread::spawn(move || external.resume());
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![0, 1, 2, 3], *handled.lock().unwrap());
+    /// ```
+    pub fn suspend_until(&self, condition: SuspendCondition) -> SuspensionToken {
+        let token = SuspensionToken::new(
+            self.actor_ref.get_address().clone(),
+            self.actor_ref.get_wakeup_manager().clone(),
+        );
+        *self.suspension.lock().unwrap() = Some(SuspensionRequest {
+            condition,
+            token: token.clone(),
+        });
+        token
+    }
+
+    pub(crate) fn take_suspension_request(&self) -> Option<SuspensionRequest> {
+        self.suspension.lock().unwrap().take()
+    }
+
+    pub(crate) fn fail(&self, error: ActorError) {
+        *self.failure.lock().unwrap() = Some(error);
+    }
+
+    pub(crate) fn has_failure(&self) -> bool {
+        self.failure.lock().unwrap().is_some()
+    }
+
+    pub(crate) fn take_failure(&self) -> Option<ActorError> {
+        self.failure.lock().unwrap().take()
+    }
+
+    pub(crate) fn set_result(&self, result: ActorResult) {
+        *self.result.lock().unwrap() = Some(result);
+    }
+
+    pub(crate) fn take_result(&self) -> Option<ActorResult> {
+        self.result.lock().unwrap().take()
+    }
+
+    /// Default of [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+    pub(crate) fn report_unhandled(&self, info: &UnhandledInfo) {
+        self.system.get_state().get_dead_letters().report_traced(
+            self.actor_ref.get_address(),
+            info.type_name(),
+            DeadLetterReason::Unhandled,
+            self.trace_id(),
+        );
+    }
+
+    /// Sets `msg` aside until [unstash_all](#method.unstash_all), the stashed message keeps the headers and the sender of the current message
+    ///
+    /// A full stash, see [ActorBuilder.set_stash_capacity](../prelude/struct.ActorBuilder.html#method.set_stash_capacity), applies the overflow policy of the mailbox.
+    /// `Block` hands the message back just like `Fail`, the Actor cannot wait for room in its own stash.
+    /// A restart unstashes all messages, messages that are still stashed once the Actor stops are dead letters
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Query { id: usize }
+    /// impl ActorMessage for Query {}
+    /// struct Loaded {}
+    /// impl ActorMessage for Loaded {}
+    ///
+    /// struct Index { is_loaded: bool, answered: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Index {}
+    /// impl Handler<Query> for Index {
+    ///     fn handle(&mut self, msg: Query, context: &ActorContext<Self>) {
+    ///         if !self.is_loaded {
+    ///             context.stash(msg).unwrap();
+    ///             return;
+    ///         }
+    ///         self.answered.lock().unwrap().push(msg.id);
+    ///     }
+    /// }
+    /// impl Handler<Loaded> for Index {
+    ///     fn handle(&mut self, _msg: Loaded, context: &ActorContext<Self>) {
+    ///         self.is_loaded = true;
+    ///         assert_eq!(3, context.unstash_all());
+    ///     }
+    /// }
+    /// struct IndexFactory { answered: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Index> for IndexFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Index>) -> Index {
+    ///         Index { is_loaded: false, answered: self.answered.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let answered = Arc::new(Mutex::new(Vec::new()));
+    /// let index = actor_system
+    ///     .builder()
+    ///     .start_suspended(true)
+    ///     .spawn("index", IndexFactory { answered: answered.clone() })
+    ///     .unwrap();
+    /// for id in 0..3 {
+    ///     index.send(Query { id }).unwrap();
+    /// }
+    /// index.send(Loaded {}).unwrap();
+    /// for id in 3..6 {
+    ///     index.send(Query { id }).unwrap();
+    /// }
+    /// index.release();
+    /// sleep(Duration::from_millis(100));
+    /// // stashed messages are handled before the ones that arrived after them, in their original order
+    /// assert_eq!(vec![0, 1, 2, 3, 4, 5], *answered.lock().unwrap());
+    ///
+    /// // a stop without unstashing turns the stash into dead letters
+    /// let other = actor_system
+    ///     .builder()
+    ///     .spawn("other", IndexFactory { answered: answered.clone() })
+    ///     .unwrap();
+    /// other.send(Query { id: 6 }).unwrap();
+    /// other.send(Query { id: 7 }).unwrap();
+    /// other.stop();
+    /// assert!(other.await_stop(Duration::from_secs(1)));
+    /// assert_eq!(2, actor_system.dead_letter_count());
+    /// ```
+    pub fn stash<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        let headers = self.headers.lock().unwrap().as_deref().cloned();
+        let mut envelope = MessageEnvelope::with_headers(msg, headers);
+        if let Some(sender) = self.sender.lock().unwrap().as_deref() {
+            envelope.set_reply_path(sender.clone());
+        }
+        let mailbox = self.actor_ref.get_mailbox();
+        match mailbox.stash.push(envelope, mailbox.overflow_policy) {
+            Ok(Some(dropped)) => self.actor_ref.drop_overflow(dropped),
+            Ok(None) => {}
+            Err(envelope) => return Err(SendError::Full(envelope.into_message().unwrap())),
+        }
+        Ok(())
+    }
+
+    /// Moves all stashed messages ahead of everything that is queued once the current handler returned, returns the amount of stashed messages
+    ///
+    /// The stashed messages keep their order, see [stash](#method.stash)
+    pub fn unstash_all(&self) -> usize {
+        let stash = &self.actor_ref.get_mailbox().stash;
+        stash.request_unstash();
+        stash.len()
+    }
+
+    /// Sends `msg` to this Actor without ever waiting for room in its mailbox, e.g. to continue a long task in steps
+    ///
+    /// Self-sends are handled right after the current handler, in the order they have been sent and before every message of the regular mailbox,
+    /// even before messages other senders queued earlier. Only the priority lane, see [ActorWrapper.send_priority](../prelude/struct.ActorWrapper.html#method.send_priority),
+    /// and stashed messages that are unstashed by the current handler go first. A bounded mailbox reserves as many slots again for self-sends,
+    /// once those are taken the send fails with [SendError::Full](../prelude/enum.SendError.html#variant.Full).
+    /// A regular send of the Actor to its own full mailbox fails with [SendError::WouldDeadlock](../prelude/enum.SendError.html#variant.WouldDeadlock) instead of waiting for room
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Step { remaining: usize }
+    /// impl ActorMessage for Step {}
+    /// struct Note { text: &'static str }
+    /// impl ActorMessage for Note {}
+    ///
+    /// struct Importer { log: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Importer {}
+    /// impl Handler<Step> for Importer {
+    ///     fn handle(&mut self, msg: Step, context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("step {}", msg.remaining));
+    ///         if msg.remaining > 0 {
+    ///             context.send_to_self(Step { remaining: msg.remaining - 1 }).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Note> for Importer {
+    ///     fn handle(&mut self, msg: Note, context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(String::from(msg.text));
+    ///         if msg.text == "fill" {
+    ///             // the first regular send takes the only slot, the second one could only wait for this handler
+    ///             context.actor_ref.send(Note { text: "filled" }).unwrap();
+    ///             if let Err(SendError::WouldDeadlock(_)) = context.actor_ref.send(Note { text: "lost" }) {
+    ///                 self.log.lock().unwrap().push(String::from("would deadlock"));
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// struct ImporterFactory { log: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Importer> for ImporterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Importer>) -> Importer {
+    ///         Importer { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let importer = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .spawn("importer", ImporterFactory { log: log.clone() })
+    ///     .unwrap();
+    ///
+    /// // a chain of continuations runs to completion with a mailbox of size 1
+    /// importer.send(Step { remaining: 100 }).unwrap();
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(101, log.lock().unwrap().len());
+    /// assert_eq!("step 0", log.lock().unwrap()[100]);
+    ///
+    /// importer.send(Note { text: "fill" }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec!["fill", "would deadlock", "filled"], log.lock().unwrap()[101..]);
+    ///
+    /// // continuations overtake messages that are already queued
+    /// log.lock().unwrap().clear();
+    /// let ordered = actor_system
+    ///     .builder()
+    ///     .start_suspended(true)
+    ///     .spawn("ordered", ImporterFactory { log: log.clone() })
+    ///     .unwrap();
+    /// ordered.send(Step { remaining: 2 }).unwrap();
+    /// ordered.send(Note { text: "first" }).unwrap();
+    /// ordered.send(Note { text: "second" }).unwrap();
+    /// ordered.release();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec!["step 2", "step 1", "step 0", "first", "second"], *log.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send_to_self<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.actor_ref.send_continuation(msg)
+    }
+
+    /// Handles messages with the overrides of `behavior` instead of the [Handler]s of the Actor, until the next `become_behavior` or [unbecome](#method.unbecome)
+    ///
+    /// Behaviors are stacked, `unbecome` returns to the previous one. Messages without an override in the active behavior are handled by the [Handler],
+    /// or by [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message) if the behavior [falls back to it](../prelude/struct.Behavior.html#method.fallback_to_unhandled).
+    /// Messages of the library, like the stop messages, always reach their [Handler]. A restart drops all behaviors.
+    ///
+    /// Fails once the stack holds `general.max_behavior_depth` behaviors, which usually means a `become_behavior` lacks its `unbecome`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Packet { seq: usize }
+    /// impl ActorMessage for Packet {}
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    /// struct Advance {}
+    /// impl ActorMessage for Advance {}
+    /// struct Back {}
+    /// impl ActorMessage for Back {}
+    ///
+    /// struct Connection { log: Arc<Mutex<Vec<String>>> }
+    /// impl Connection {
+    ///     fn log(&self, entry: String) {
+    ///         self.log.lock().unwrap().push(entry);
+    ///     }
+    /// }
+    /// impl Actor for Connection {
+    ///     fn on_unhandled_message(&mut self, info: UnhandledInfo, _context: &ActorContext<Self>) {
+    ///         self.log(format!("unhandled {}", info.type_name().rsplit("::").next().unwrap()));
+    ///     }
+    /// }
+    /// impl Handler<Packet> for Connection {
+    ///     fn handle(&mut self, msg: Packet, _context: &ActorContext<Self>) {
+    ///         self.log(format!("idle {}", msg.seq));
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Connection {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.log(String::from("pong"));
+    ///     }
+    /// }
+    /// fn phase(name: &'static str) -> Behavior<Connection> {
+    ///     Behavior::<Connection>::new().on::<Packet, _>(move |actor, msg, _context| actor.log(format!("{} {}", name, msg.seq)))
+    /// }
+    /// impl Handler<Advance> for Connection {
+    ///     fn handle(&mut self, _msg: Advance, context: &ActorContext<Self>) {
+    ///         let behavior = match context.behavior_depth() {
+    ///             0 => phase("handshaking"),
+    ///             1 => phase("streaming"),
+    ///             _ => phase("closing")
+    ///                 .on::<Back, _>(|_actor, _msg, context| {
+    ///                     context.unbecome();
+    ///                 })
+    ///                 .on::<Advance, _>(|actor, _msg, context| {
+    ///                     let error = context.become_behavior(Behavior::new()).unwrap_err();
+    ///                     actor.log(format!("{:?}", error));
+    ///                 })
+    ///                 .fallback_to_unhandled(),
+    ///         };
+    ///         context.become_behavior(behavior).unwrap();
+    ///     }
+    /// }
+    /// impl Handler<Back> for Connection {
+    ///     fn handle(&mut self, _msg: Back, context: &ActorContext<Self>) {
+    ///         if !context.unbecome() {
+    ///             self.log(String::from("no behavior"));
+    ///         }
+    ///     }
+    /// }
+    /// struct ConnectionFactory { log: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Connection> for ConnectionFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Connection>) -> Connection {
+    ///         Connection { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_behavior_depth = 3;
+    /// let actor_system = ActorSystem::new(config);
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let connection = actor_system
+    ///     .builder()
+    ///     .start_suspended(true)
+    ///     .spawn("connection", ConnectionFactory { log: log.clone() })
+    ///     .unwrap();
+    /// connection.send(Packet { seq: 0 }).unwrap();
+    /// for seq in 1..4 {
+    ///     connection.send(Advance {}).unwrap();
+    ///     connection.send(Packet { seq }).unwrap();
+    /// }
+    /// // closing hands everything it does not override to the unhandled hook
+    /// connection.send(Ping {}).unwrap();
+    /// connection.send(Advance {}).unwrap();
+    /// // unbecome pops back to streaming, which keeps the Handler of Ping
+    /// connection.send(Back {}).unwrap();
+    /// connection.send(Packet { seq: 4 }).unwrap();
+    /// connection.send(Ping {}).unwrap();
+    /// connection.send(Back {}).unwrap();
+    /// connection.send(Back {}).unwrap();
+    /// connection.send(Packet { seq: 5 }).unwrap();
+    /// connection.send(Back {}).unwrap();
+    /// connection.release();
+    /// connection.stop();
+    /// assert!(connection.await_stop(Duration::from_secs(1)));
+    ///
+    /// let expected = vec![
+    ///     "idle 0", "handshaking 1", "streaming 2", "closing 3", "unhandled Ping", "DepthExceeded { max_depth: 3 }",
+    ///     "streaming 4", "pong", "idle 5", "no behavior",
+    /// ];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    /// ```
+    pub fn become_behavior(&self, behavior: Behavior<A>) -> Result<(), BehaviorError> {
+        self.behaviors.push(behavior)
+    }
+
+    /// Returns to the behavior that was active before the last [become_behavior](#method.become_behavior), `false` if no behavior is active
+    pub fn unbecome(&self) -> bool {
+        self.behaviors.pop()
+    }
+
+    /// Amount of behaviors on the stack, `0` while the [Handler]s of the Actor are active
+    pub fn behavior_depth(&self) -> usize {
+        self.behaviors.depth()
+    }
+
+    pub(crate) fn dispatch_behavior(&self, type_id: TypeId) -> BehaviorDispatch<A> {
+        self.behaviors.dispatch(type_id)
+    }
+
+    pub(crate) fn clear_behaviors(&self) {
+        self.behaviors.clear();
+    }
+
+    /// Moves the Actor to the pool `pool` once the current handler returned, see [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Analyze { rows: usize }
+    /// impl ActorMessage for Analyze {}
+    ///
+    /// struct Analytics { pools: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Analytics {}
+    /// impl Handler<Analyze> for Analytics {
+    ///     fn handle(&mut self, msg: Analyze, context: &ActorContext<Self>) {
+    ///         let thread = String::from(std::thread::current().name().unwrap());
+    ///         self.pools.lock().unwrap().push(thread.split('-').nth(1).unwrap().to_string());
+    ///         // heavy work is not done on the shared pool
+    ///         if msg.rows > 1000 {
+    ///             context.move_to_pool("heavy").unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// struct AnalyticsFactory { pools: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Analytics> for AnalyticsFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Analytics>) -> Analytics {
+    ///         Analytics { pools: self.pools.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("reports");
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.add_pool("heavy");
+    /// let pools = Arc::new(Mutex::new(Vec::new()));
+    /// let analytics = actor_system.builder().spawn("analytics", AnalyticsFactory { pools: pools.clone() }).unwrap();
+    /// for rows in [10, 5000, 10].iter() {
+    ///     analytics.send(Analyze { rows: *rows }).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec!["default", "default", "heavy"], *pools.lock().unwrap());
+    /// // the address keeps the pool the Actor has been spawned on
+    /// assert_eq!("default", analytics.get_address().pool);
+    /// assert_eq!("heavy", actor_system.list_actors()[0].pool);
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn move_to_pool(&self, pool: &str) -> Result<(), MoveError> {
+        let thread_pool_manager = self.system.get_thread_pool_manager();
+        if !thread_pool_manager.has_pool(pool) {
+            return Err(MoveError::UnknownPool);
+        }
+        let address = self.actor_ref.get_address();
+        if thread_pool_manager.is_dedicated(address) {
+            return Err(MoveError::DedicatedThread);
+        }
+        let mailbox = self.actor_ref.get_mailbox();
+        if mailbox.is_stopped() || mailbox.is_stop_requested() {
+            return Err(MoveError::Stopping);
+        }
+        if thread_pool_manager.get_actor_pool(address) != pool {
+            thread_pool_manager.set_actor_pool(address, pool);
+            self.is_relocating.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_relocation(&self) -> bool {
+        self.is_relocating.swap(false, Ordering::Relaxed)
+    }
+
+    pub(crate) fn request_replacement(&self, factory: Replacement<A>) {
+        *self.replacement.lock().unwrap() = Some(factory);
+    }
+
+    pub(crate) fn take_replacement(&self) -> Option<Replacement<A>> {
+        self.replacement.lock().unwrap().take()
+    }
+
+    /// Sequences dependent requests to [RequestHandler](../prelude/trait.RequestHandler.html)s without blocking the Actor
+    ///
+    /// Every stage is sent once the previous stage has been answered, answers are delivered through the mailbox of this Actor,
+    /// so that `on_complete` and `on_error` get access to it. The closures themselves only capture data, never the Actor.
+    /// Multiple chains can run at the same time, chains are dropped without calling any closure once the Actor stops.
+    ///
+    /// See [ActorWrapper.active_request_chains](../prelude/struct.ActorWrapper.html#method.active_request_chains)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Lookup(u64);
+    /// impl ActorMessage for Lookup {}
+    /// struct Scale(u64);
+    /// impl ActorMessage for Scale {}
+    /// struct Render(u64);
+    /// impl ActorMessage for Render {}
+    ///
+    /// // answers every request, renders slowly for 70
+    /// struct Service { rendered: Arc<Mutex<usize>> }
+    /// impl Actor for Service {}
+    /// impl RequestHandler<Lookup> for Service {
+    ///     type Response = u64;
+    ///     fn handle_request(&mut self, request: Lookup, _context: &ActorContext<Self>) -> Result<u64, String> {
+    ///         Ok(request.0 + 1)
+    ///     }
+    /// }
+    /// impl RequestHandler<Scale> for Service {
+    ///     type Response = u64;
+    ///     fn handle_request(&mut self, request: Scale, _context: &ActorContext<Self>) -> Result<u64, String> {
+    ///         if request.0 > 1000 {
+    ///             return Err(String::from("too large"));
+    ///         }
+    ///         Ok(request.0 * 10)
+    ///     }
+    /// }
+    /// impl RequestHandler<Render> for Service {
+    ///     type Response = String;
+    ///     fn handle_request(&mut self, request: Render, _context: &ActorContext<Self>) -> Result<String, String> {
+    ///         if request.0 == 70 {
+    ///             sleep(Duration::from_millis(300));
+    ///         }
+    ///         *self.rendered.lock().unwrap() += 1;
+    ///         Ok(format!("#{}", request.0))
+    ///     }
+    /// }
+    /// struct ServiceFactory { rendered: Arc<Mutex<usize>> }
+    /// impl ActorFactory<Service> for ServiceFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Service>) -> Service {
+    ///         Service { rendered: self.rendered.clone() }
+    ///     }
+    /// }
+    ///
+    /// type Results = Arc<Mutex<Vec<(u64, Result<String, (usize, ChainError)>)>>>;
+    ///
+    /// struct Start { id: u64, timeout: Duration }
+    /// impl ActorMessage for Start {}
+    /// struct Client { services: Vec<ActorWrapper<Service>>, results: Results }
+    /// impl Actor for Client {}
+    /// impl Handler<Start> for Client {
+    ///     fn handle(&mut self, msg: Start, context: &ActorContext<Self>) {
+    ///         let id = msg.id;
+    ///         context
+    ///             .request_chain()
+    ///             .then(&self.services[0], Lookup(id))
+    ///             .then_with(&self.services[1], |looked_up| Scale(looked_up))
+    ///             .then_with(&self.services[2], |scaled| Render(scaled))
+    ///             .on_complete(move |rendered, client: &mut Client, _context| {
+    ///                 client.results.lock().unwrap().push((id, Ok(rendered)));
+    ///             })
+    ///             .on_error(move |stage, error, client: &mut Client, _context| {
+    ///                 client.results.lock().unwrap().push((id, Err((stage, error))));
+    ///             })
+    ///             .execute(msg.timeout)
+    ///             .unwrap();
+    ///     }
+    /// }
+    /// struct ClientFactory { services: Vec<ActorWrapper<Service>>, results: Results }
+    /// impl ActorFactory<Client> for ClientFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Client>) -> Client {
+    ///         Client { services: self.services.clone(), results: self.results.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let rendered = Arc::new(Mutex::new(0));
+    /// let services: Vec<ActorWrapper<Service>> = ["lookup", "scale", "render"]
+    ///     .iter()
+    ///     .map(|name| actor_system.builder().spawn(*name, ServiceFactory { rendered: rendered.clone() }).unwrap())
+    ///     .collect();
+    /// let results: Results = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = ClientFactory { services: services.clone(), results: results.clone() };
+    /// let client = actor_system.builder().spawn("client", factory).unwrap();
+    /// let take = || std::mem::take(&mut *results.lock().unwrap());
+    /// let timeout = Duration::from_secs(5);
+    ///
+    /// client.send(Start { id: 4, timeout });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![(4, Ok(String::from("#50")))], take());
+    ///
+    /// // the third stage is never sent
+    /// client.send(Start { id: 2000, timeout });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![(2000, Err((2, ChainError::Rejected(String::from("too large")))))], take());
+    /// assert_eq!(1, *rendered.lock().unwrap());
+    ///
+    /// for id in 0..100 {
+    ///     client.send(Start { id, timeout });
+    /// }
+    /// sleep(Duration::from_millis(500));
+    /// let mut completed = take();
+    /// completed.sort_by_key(|(id, _)| *id);
+    /// let expected: Vec<(u64, Result<String, (usize, ChainError)>)> = (0..100)
+    ///     .map(|id| (id, Ok(format!("#{}", (id + 1) * 10))))
+    ///     .collect();
+    /// assert_eq!(expected, completed);
+    /// assert_eq!(0, client.active_request_chains());
+    ///
+    /// client.send(Start { id: 6, timeout: Duration::from_millis(100) });
+    /// sleep(Duration::from_millis(500));
+    /// assert_eq!(vec![(6, Err((3, ChainError::Timeout)))], take());
+    /// assert_eq!(0, client.active_request_chains());
+    ///
+    /// // stopping the client drops its chains
+    /// client.send(Start { id: 6, timeout });
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(1, client.active_request_chains());
+    /// client.stop();
+    /// sleep(Duration::from_millis(500));
+    /// assert_eq!(0, client.active_request_chains());
+    /// assert!(take().is_empty());
+    /// ```
+    pub fn request_chain(&self) -> RequestChain<A, ()> {
+        RequestChain::<A, ()>::new(self.clone())
+    }
+
+    pub(crate) fn get_request_chains(&self) -> &RequestChains<A> {
+        &self.request_chains
+    }
+
+    /// Delivers a [MailboxPressure](../prelude/struct.MailboxPressure.html) message to this Actor whenever the pressure of `target` changes
+    ///
+    /// Watching the same target again has no effect, so that it can be done when the Actor is created
+    ///
+    /// Returns `false` if `target` has no [Watermarks](../prelude/struct.Watermarks.html)
+    ///
+    /// See [ActorBuilder.set_watermarks](../prelude/struct.ActorBuilder.html#method.set_watermarks) for an example
+    pub fn watch_pressure<B>(&self, target: &ActorWrapper<B>) -> bool
+    where
+        A: Handler<MailboxPressure>,
+        B: Actor + UnwindSafe + 'static,
+    {
+        let pressure = match &target.get_mailbox().pressure {
+            Some(pressure) => pressure,
+            None => return false,
+        };
+        let watcher = self.actor_ref.clone();
+        pressure.watch::<MailboxPressure>(self.actor_ref.get_address(), Box::new(move |msg| {
+            let _ = watcher.send(msg);
+        }));
+        true
+    }
+
+    /// Delivers a [Terminated](../prelude/struct.Terminated.html) message to this Actor once `target` stopped for good and has been removed from the system
+    ///
+    /// The message is delivered right away if `target` already stopped, it is dropped if this Actor stopped by then.
+    /// A `target` that panics and is restarted has not terminated. Watching the same target twice delivers two messages
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Idle {}
+    /// impl Actor for Idle {}
+    /// struct IdleFactory {}
+    /// impl ActorFactory<Idle> for IdleFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Idle>) -> Idle {
+    ///         Idle {}
+    ///     }
+    /// }
+    ///
+    /// struct Watch { target: ActorWrapper<Idle> }
+    /// impl ActorMessage for Watch {}
+    ///
+    /// struct Monitor { terminated: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Monitor {}
+    /// impl Handler<Watch> for Monitor {
+    ///     fn handle(&mut self, msg: Watch, context: &ActorContext<Self>) {
+    ///         context.watch(&msg.target);
+    ///     }
+    /// }
+    /// impl Handler<Terminated> for Monitor {
+    ///     fn handle(&mut self, msg: Terminated, _context: &ActorContext<Self>) {
+    ///         self.terminated.lock().unwrap().push(msg.address.actor);
+    ///     }
+    /// }
+    /// struct MonitorFactory { terminated: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Monitor> for MonitorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Monitor>) -> Monitor {
+    ///         Monitor { terminated: self.terminated.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let terminated = Arc::new(Mutex::new(Vec::new()));
+    /// let monitor = actor_system.builder().spawn("monitor", MonitorFactory { terminated: terminated.clone() }).unwrap();
+    /// let worker = actor_system.builder().spawn("worker", IdleFactory {}).unwrap();
+    /// let stopped = actor_system.builder().spawn("stopped", IdleFactory {}).unwrap();
+    /// stopped.stop();
+    /// assert!(stopped.await_stop(Duration::from_secs(1)));
+    ///
+    /// monitor.send(Watch { target: worker.clone() }).unwrap();
+    /// monitor.send(Watch { target: stopped }).unwrap();
+    /// sleep(Duration::from_millis(200));
+    /// // watching an Actor that already stopped is notified right away
+    /// assert_eq!(vec!["stopped"], *terminated.lock().unwrap());
+    ///
+    /// worker.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec!["stopped", "worker"], *terminated.lock().unwrap());
+    /// ```
+    pub fn watch<B>(&self, target: &ActorWrapper<B>)
+    where
+        A: Handler<Terminated>,
+        B: Actor + UnwindSafe + 'static,
+    {
+        let watcher = self.actor_ref.clone();
+        let address = target.get_address().clone();
+        target.on_removal(move || {
+            let _ = watcher.send_priority(Terminated { address });
+        });
+    }
+
+    /// Spawns a child of this Actor with the settings of [ActorSystem.builder](../prelude/struct.ActorSystem.html#method.builder), see [spawn_child_with](#method.spawn_child_with)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Crash> for Worker {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         panic!("crashed");
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// struct Parent {
+    ///     context: ActorContext<Self>,
+    ///     spawned: usize,
+    ///     worker: Arc<Mutex<Option<ActorWrapper<Worker>>>>,
+    ///     terminated: Arc<Mutex<Vec<ChildTerminated>>>,
+    /// }
+    /// impl Parent {
+    ///     fn spawn_worker(&mut self) {
+    ///         self.spawned += 1;
+    ///         // the system removes a terminated child only after the parent has been notified, so the replacement needs a new name
+    ///         let builder = self.context.system.builder().set_restart_policy(RestartPolicy::Never);
+    ///         let worker = self.context.spawn_child_with(&builder, format!("worker-{}", self.spawned), WorkerFactory {});
+    ///         *self.worker.lock().unwrap() = worker;
+    ///     }
+    /// }
+    /// impl Actor for Parent {
+    ///     fn on_child_terminated(&mut self, child: ChildTerminated) {
+    ///         let panicked = matches!(child.reason, ChildTerminationReason::Panicked(_));
+    ///         self.terminated.lock().unwrap().push(child);
+    ///         if panicked {
+    ///             self.spawn_worker();
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Start> for Parent {
+    ///     fn handle(&mut self, _msg: Start, _context: &ActorContext<Self>) {
+    ///         self.spawn_worker();
+    ///     }
+    /// }
+    /// struct ParentFactory {
+    ///     worker: Arc<Mutex<Option<ActorWrapper<Worker>>>>,
+    ///     terminated: Arc<Mutex<Vec<ChildTerminated>>>,
+    /// }
+    /// impl ActorFactory<Parent> for ParentFactory {
+    ///     fn new_actor(&self, context: ActorContext<Parent>) -> Parent {
+    ///         Parent {
+    ///             context,
+    ///             spawned: 0,
+    ///             worker: self.worker.clone(),
+    ///             terminated: self.terminated.clone(),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let worker = Arc::new(Mutex::new(None));
+    /// let terminated = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = ParentFactory { worker: worker.clone(), terminated: terminated.clone() };
+    /// let parent = actor_system.builder().spawn("parent", factory).unwrap();
+    /// parent.send(Start {}).unwrap();
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let first = worker.lock().unwrap().clone().unwrap();
+    /// assert_eq!("worker-1", first.get_address().actor);
+    /// first.send(Crash {}).unwrap();
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// // the parent has been notified about the panic and respawned its child
+    /// let reports = terminated.lock().unwrap().clone();
+    /// assert_eq!(1, reports.len());
+    /// assert_eq!(first.get_address(), &reports[0].address);
+    /// match &reports[0].reason {
+    ///     ChildTerminationReason::Panicked(report) => assert_eq!(Some(String::from("crashed")), report.panic_message),
+    ///     reason => panic!("unexpected reason {:?}", reason),
+    /// }
+    /// let second = worker.lock().unwrap().clone().unwrap();
+    /// assert_eq!("worker-2", second.get_address().actor);
+    /// assert_eq!(2, actor_system.get_actor_count());
+    ///
+    /// // stopping the parent stops its children as well
+    /// parent.stop();
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(0, actor_system.get_actor_count());
+    /// assert!(second.send(Crash {}).is_err());
+    /// ```
+    pub fn spawn_child<B, P>(&self, name: impl Into<String>, props: P) -> Option<ActorWrapper<B>>
+    where
+        B: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<B> + 'static,
+    {
+        self.spawn_child_with(&self.system.builder(), name, props)
+    }
+
+    /// Spawns an Actor with the default settings of its pool, see [ActorBuilder.try_spawn](../prelude/struct.ActorBuilder.html#method.try_spawn)
+    ///
+    /// Unlike [spawn_child](#method.spawn_child) the Actor is independent of this Actor.
+    /// Fails with [SpawnError::SystemStopping](../prelude/enum.SpawnError.html#variant.SystemStopping) instead of waiting once the shutdown phase of the spawned Actor completed,
+    /// see [ActorBuilder.set_shutdown_phase](../prelude/struct.ActorBuilder.html#method.set_shutdown_phase)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    /// struct Work {
+    ///     controller: ActorWrapper<Controller>,
+    /// }
+    /// impl ActorMessage for Work {}
+    /// struct Done {}
+    /// impl ActorMessage for Done {}
+    ///
+    /// struct Worker {
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+    ///         msg.controller.send(Done {}).unwrap();
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context }
+    ///     }
+    /// }
+    ///
+    /// struct Controller {
+    ///     context: ActorContext<Self>,
+    ///     late_spawn: Arc<Mutex<Option<SpawnError>>>,
+    /// }
+    /// impl Actor for Controller {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Start> for Controller {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         let worker = context.spawn("worker", WorkerFactory {}).unwrap();
+    ///         worker.send(Work { controller: context.actor_ref.clone() }).unwrap();
+    ///     }
+    /// }
+    /// impl Handler<Done> for Controller {
+    ///     fn handle(&mut self, _msg: Done, context: &ActorContext<Self>) {
+    ///         context.stop_system(Duration::from_secs(1));
+    ///         // the phase of the system stop has completed before the Controller is stopped
+    ///         let early = context.system.builder().set_shutdown_phase(0);
+    ///         *self.late_spawn.lock().unwrap() = early.try_spawn("late", WorkerFactory {}).err();
+    ///         // joins the phase of this Actor, which is still running
+    ///         context.spawn::<Worker, _>("joining", WorkerFactory {}).unwrap();
+    ///     }
+    /// }
+    /// struct ControllerFactory {
+    ///     late_spawn: Arc<Mutex<Option<SpawnError>>>,
+    /// }
+    /// impl ActorFactory<Controller> for ControllerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Controller>) -> Controller {
+    ///         Controller { context, late_spawn: self.late_spawn.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let late_spawn = Arc::new(Mutex::new(None));
+    /// let controller = actor_system
+    ///     .builder()
+    ///     .spawn("controller", ControllerFactory { late_spawn: late_spawn.clone() })
+    ///     .unwrap();
+    /// controller.send(Start {}).unwrap();
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert_eq!(Some(SpawnError::SystemStopping), *late_spawn.lock().unwrap());
+    /// ```
+    pub fn spawn<B, P>(&self, name: impl Into<String>, props: P) -> Result<ActorWrapper<B>, SpawnError>
+    where
+        B: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<B> + 'static,
+    {
+        self.system.builder().try_spawn(name, props)
+    }
+
+    /// Stops the whole system from within a handler, see [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop)
+    ///
+    /// Returns right away, this Actor receives the system stop like every other Actor once its current message has been handled
+    pub fn stop_system(&self, graceful_termination_timeout: Duration) {
+        self.system.stop(graceful_termination_timeout);
+    }
+
+    /// Keeps the shutdown phase of this Actor open for `grace` from now, so that Actors of later phases wait for its cleanup
+    ///
+    /// Meant to be called from [Actor.on_system_stop](../prelude/trait.Actor.html#method.on_system_stop) by Actors that clean up over several messages.
+    /// The phase ends as soon as all of its Actors stopped, the graceful termination timeout of [ActorSystem.stop](../prelude/struct.ActorSystem.html#method.stop) is never extended.
+    /// Has no effect while the system is not stopping, a later call replaces the extension
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Flush {}
+    /// impl ActorMessage for Flush {}
+    ///
+    /// type Log = Arc<Mutex<Vec<(&'static str, Instant)>>>;
+    ///
+    /// struct Stage { context: ActorContext<Self>, name: &'static str, flush_after: Option<Duration>, log: Log }
+    /// impl Actor for Stage {
+    ///     fn on_system_stop(&mut self) {
+    ///         match self.flush_after {
+    ///             // flushes asynchronously, longer than the share of its phase of the timeout
+    ///             Some(flush_after) => {
+    ///                 self.context.extend_system_stop(flush_after + Duration::from_millis(300));
+    ///                 self.context.schedule_once(flush_after, Flush {});
+    ///             }
+    ///             None => {
+    ///                 self.log.lock().unwrap().push((self.name, Instant::now()));
+    ///                 self.context.actor_ref.stop();
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Flush> for Stage {
+    ///     fn handle(&mut self, _msg: Flush, context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push((self.name, Instant::now()));
+    ///         context.actor_ref.stop();
+    ///     }
+    /// }
+    /// struct StageFactory { name: &'static str, flush_after: Option<Duration>, log: Log }
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, context: ActorContext<Stage>) -> Stage {
+    ///         Stage { context, name: self.name, flush_after: self.flush_after, log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Log::default();
+    /// let writer = StageFactory { name: "writer", flush_after: Some(Duration::from_millis(1200)), log: log.clone() };
+    /// actor_system.builder().set_shutdown_phase(0).spawn("writer", writer).unwrap();
+    /// let storage = StageFactory { name: "storage", flush_after: None, log: log.clone() };
+    /// actor_system.builder().set_shutdown_phase(1).spawn("storage", storage).unwrap();
+    ///
+    /// // each of the two phases gets a second of the timeout, the writer keeps its phase open for longer
+    /// let stopped = Instant::now();
+    /// actor_system.stop(Duration::from_secs(2));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// let log = log.lock().unwrap();
+    /// assert_eq!(vec!["writer", "storage"], log.iter().map(|(name, _)| *name).collect::<Vec<_>>());
+    /// assert!(log[1].1.duration_since(stopped) >= Duration::from_millis(1200));
+    /// ```
+    pub fn extend_system_stop(&self, grace: Duration) {
+        if let Some(until) = Instant::now().checked_add(grace) {
+            self.system.get_state().extend_stop(self.actor_ref.get_address(), until);
+        }
+    }
+
+    /// Name of the system this Actor runs in
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// struct Named {}
+    /// impl Actor for Named {}
+    /// struct NamedFactory {
+    ///     system_name: Arc<Mutex<String>>,
+    /// }
+    /// impl ActorFactory<Named> for NamedFactory {
+    ///     fn new_actor(&self, context: ActorContext<Named>) -> Named {
+    ///         *self.system_name.lock().unwrap() = String::from(context.system_name());
+    ///         Named {}
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("billing");
+    /// let actor_system = ActorSystem::new(config);
+    /// let system_name = Arc::new(Mutex::new(String::new()));
+    /// actor_system.builder().spawn("named", NamedFactory { system_name: system_name.clone() }).unwrap();
+    /// assert_eq!("billing", *system_name.lock().unwrap());
+    /// ```
+    pub fn system_name(&self) -> &str {
+        self.system.get_name()
+    }
+
+    /// Returns the [EventBus](../prelude/struct.EventBus.html) of the system, see [ActorSystem.get_event_bus](../prelude/struct.ActorSystem.html#method.get_event_bus)
+    pub fn get_event_bus(&self) -> &EventBus {
+        self.system.get_event_bus()
+    }
+
+    /// Spawns a child of this Actor through `builder`, `None` if the builder does not spawn it
+    ///
+    /// [Actor.on_child_terminated](../prelude/trait.Actor.html#method.on_child_terminated) is executed once the child stopped for good, panicked or not.
+    /// The child is stopped as soon as this Actor stopped, unless it terminated before. A restart of this Actor keeps its children running.
+    /// A child that has already been spawned by the same builder is adopted, unless it already is a child of this Actor
+    pub fn spawn_child_with<B, P>(&self, builder: &ActorBuilder<B>, name: impl Into<String>, props: P) -> Option<ActorWrapper<B>>
+    where
+        B: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<B> + 'static,
+    {
+        let child = builder.spawn(name, props)?;
+        let state = self.system.get_state().clone();
+        let parent = self.actor_ref.clone();
+        let to_stop = child.clone();
+        let stop = Box::new(move || {
+            to_stop.stop();
+        });
+        if !state.add_child(parent.get_address(), child.get_address().clone(), stop) {
+            return Some(child);
+        }
+        let address = child.get_address().clone();
+        child.on_termination(move |report| {
+            state.remove_child(parent.get_address(), &address);
+            let _ = parent.send_priority(ChildTerminatedMessage {
+                child: ChildTerminated::new(address, report),
+            });
+        });
+        Some(child)
+    }
+
+    /// Addresses of the children of this Actor that have not terminated yet, see [spawn_child](#method.spawn_child)
+    pub fn children(&self) -> Vec<ActorAddress> {
+        self.system.get_state().get_children(self.actor_ref.get_address())
+    }
+
+    /// Address of an Actor of this system, see [ActorSystem.address_of](../prelude/struct.ActorSystem.html#method.address_of)
+    pub fn address_of(&self, pool: &str, name: &str) -> ActorAddress {
+        self.system.address_of(pool, name)
+    }
+
+    /// Sends `msg` to the Actor at `address`, see [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address)
+    ///
+    /// Addresses can be passed around within typed messages through [ActorWrapper.get_address](../prelude/struct.ActorWrapper.html#method.get_address) or built through [address_of](#method.address_of).
+    /// A message to an address without a running Actor is reported as dead letter and fails with [ProtocolError::ActorNotFound](../prelude/enum.ProtocolError.html#variant.ActorNotFound)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Introduce { address: ActorAddress }
+    /// impl ActorMessage for Introduce {}
+    ///
+    /// struct Greeter { errors: Arc<Mutex<Vec<ProtocolError>>> }
+    /// impl Actor for Greeter {}
+    /// impl Handler<Introduce> for Greeter {
+    ///     fn handle(&mut self, msg: Introduce, context: &ActorContext<Self>) {
+    ///         let hello = SerializedMessage::new(b"hello".to_vec());
+    ///         context.send_serialized(&msg.address, hello).unwrap();
+    ///         let nobody = context.address_of("default", "nobody");
+    ///         let error = context.send_serialized(&nobody, SerializedMessage::new(Vec::new())).unwrap_err();
+    ///         self.errors.lock().unwrap().push(error);
+    ///     }
+    /// }
+    /// struct GreeterFactory { errors: Arc<Mutex<Vec<ProtocolError>>> }
+    /// impl ActorFactory<Greeter> for GreeterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Greeter>) -> Greeter {
+    ///         Greeter { errors: self.errors.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Listener { received: Arc<Mutex<Vec<Vec<u8>>>> }
+    /// impl Actor for Listener {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.received.lock().unwrap().push(msg.content);
+    ///     }
+    /// }
+    /// struct ListenerFactory { received: Arc<Mutex<Vec<Vec<u8>>>> }
+    /// impl ActorFactory<Listener> for ListenerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Listener>) -> Listener {
+    ///         Listener { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let errors = Arc::new(Mutex::new(Vec::new()));
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let greeter = actor_system.builder().spawn("greeter", GreeterFactory { errors: errors.clone() }).unwrap();
+    /// let listener = actor_system.builder().spawn("listener", ListenerFactory { received: received.clone() }).unwrap();
+    /// assert_eq!(*listener.get_address(), actor_system.address_of("default", "listener"));
+    ///
+    /// greeter.send(Introduce { address: listener.get_address().clone() }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![b"hello".to_vec()], *received.lock().unwrap());
+    /// assert_eq!(vec![ProtocolError::ActorNotFound], *errors.lock().unwrap());
+    /// assert_eq!(1, actor_system.dead_letter_count());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn send_serialized(&self, address: &ActorAddress, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        self.system.try_send_to_address(address, msg)
+    }
+
+    /// Looks up a running Actor by pool and name, see [ActorSystem.get_actor](../prelude/struct.ActorSystem.html#method.get_actor)
+    pub fn lookup<B>(&self, pool: &str, name: &str) -> Option<ActorWrapper<B>>
+    where
+        B: Actor + UnwindSafe + 'static,
+    {
+        self.system.get_actor(pool, name)
+    }
+
+    /// Sends `msg` to this Actor once `delay` passed
+    ///
+    /// Messages that are due at the same time are delivered in the order they have been scheduled in.
+    /// The delivery does not wait for room in a full bounded mailbox, it is attempted again shortly after.
+    /// The message is dropped if the Actor stopped by then, the schedule is cancelled as soon as the Actor stopped and survives restarts
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    /// struct Reminder { label: &'static str }
+    /// impl ActorMessage for Reminder {}
+    ///
+    /// struct Planner {
+    ///     received: Arc<Mutex<Vec<&'static str>>>,
+    /// }
+    /// impl Actor for Planner {}
+    /// impl Handler<Start> for Planner {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         context.schedule_once(Duration::from_millis(300), Reminder { label: "late" });
+    ///         context.schedule_once(Duration::from_millis(100), Reminder { label: "early" });
+    ///         context.schedule_once(Duration::from_millis(100), Reminder { label: "early, scheduled second" });
+    ///         let cancelled = context.schedule_once(Duration::from_millis(200), Reminder { label: "cancelled" });
+    ///         assert!(cancelled.cancel());
+    ///         assert!(!cancelled.is_scheduled());
+    ///     }
+    /// }
+    /// impl Handler<Reminder> for Planner {
+    ///     fn handle(&mut self, msg: Reminder, _context: &ActorContext<Self>) {
+    ///         self.received.lock().unwrap().push(msg.label);
+    ///     }
+    /// }
+    /// struct PlannerFactory { received: Arc<Mutex<Vec<&'static str>>> }
+    /// impl ActorFactory<Planner> for PlannerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Planner>) -> Planner {
+    ///         Planner { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let planner = actor_system.builder().spawn("planner", PlannerFactory { received: received.clone() }).unwrap();
+    /// planner.send(Start {}).unwrap();
+    ///
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec!["early", "early, scheduled second"], *received.lock().unwrap());
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(vec!["early", "early, scheduled second", "late"], *received.lock().unwrap());
+    /// ```
+    pub fn schedule_once<M>(&self, delay: Duration, msg: M) -> ScheduledHandle
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        let target = self.actor_ref.clone();
+        let mut pending = Some(msg);
+        let deliver = Box::new(move || {
+            let msg = match pending.take() {
+                Some(msg) => msg,
+                None => return Delivery::Stopped,
+            };
+            match target.try_send(msg) {
+                Ok(()) => Delivery::Delivered,
+                Err(SendError::Full(msg)) => {
+                    pending = Some(msg);
+                    Delivery::Full
+                }
+                Err(_) => Delivery::Stopped,
+            }
+        });
+        self.schedule(Instant::now() + delay, None, deliver)
+    }
+
+    /// Sends a clone of `msg` to this Actor once `initial_delay` passed and then every `interval`, until the handle is cancelled or the Actor stopped
+    ///
+    /// Deliveries that are missed, e.g. because the mailbox stayed full, are skipped instead of being caught up with.
+    /// Intervals shorter than a millisecond are raised to a millisecond, see [schedule_once](#method.schedule_once) for everything else
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    /// #[derive(Clone)]
+    /// struct Tick {}
+    /// impl ActorMessage for Tick {}
+    ///
+    /// struct Ticker {
+    ///     ticks: Arc<AtomicUsize>,
+    ///     handle: Arc<Mutex<Option<ScheduledHandle>>>,
+    /// }
+    /// impl Actor for Ticker {}
+    /// impl Handler<Start> for Ticker {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         let handle = context.schedule_repeating(Duration::from_millis(50), Duration::from_millis(50), Tick {});
+    ///         *self.handle.lock().unwrap() = Some(handle);
+    ///     }
+    /// }
+    /// impl Handler<Tick> for Ticker {
+    ///     fn handle(&mut self, _msg: Tick, _context: &ActorContext<Self>) {
+    ///         self.ticks.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    /// struct TickerFactory {
+    ///     ticks: Arc<AtomicUsize>,
+    ///     handle: Arc<Mutex<Option<ScheduledHandle>>>,
+    /// }
+    /// impl ActorFactory<Ticker> for TickerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Ticker>) -> Ticker {
+    ///         Ticker { ticks: self.ticks.clone(), handle: self.handle.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let ticks = Arc::new(AtomicUsize::new(0));
+    /// let handle = Arc::new(Mutex::new(None));
+    /// let factory = TickerFactory { ticks: ticks.clone(), handle: handle.clone() };
+    /// let ticker = actor_system.builder().spawn("ticker", factory).unwrap();
+    /// ticker.send(Start {}).unwrap();
+    ///
+    /// sleep(Duration::from_millis(500));
+    /// assert!(ticks.load(Ordering::SeqCst) >= 5);
+    /// let handle = handle.lock().unwrap().clone().unwrap();
+    /// assert!(handle.is_scheduled());
+    ///
+    /// // the timer stops firing once its Actor stopped
+    /// ticker.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert!(!handle.is_scheduled());
+    /// let stopped_at = ticks.load(Ordering::SeqCst);
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(stopped_at, ticks.load(Ordering::SeqCst));
+    /// assert!(!handle.cancel());
+    /// ```
+    pub fn schedule_repeating<M>(&self, initial_delay: Duration, interval: Duration, msg: M) -> ScheduledHandle
+    where
+        A: Handler<M>,
+        M: ActorMessage + Clone + 'static,
+    {
+        let target = self.actor_ref.clone();
+        let deliver = Box::new(move || match target.try_send(msg.clone()) {
+            Ok(()) => Delivery::Delivered,
+            Err(SendError::Full(_)) => Delivery::Full,
+            Err(_) => Delivery::Stopped,
+        });
+        self.schedule(Instant::now() + initial_delay, Some(interval.max(MIN_INTERVAL)), deliver)
+    }
+
+    /// Redelivers `msg` to this Actor after the backoff of `policy`, as the next [delivery_attempt](#method.delivery_attempt) of the message that is currently handled
+    ///
+    /// The redelivery keeps the headers of the current message and is queued behind everything that arrived in the meantime, other messages are handled while it waits.
+    /// Once `max_attempts` deliveries failed the message is dropped as [DeadLetter](../prelude/struct.DeadLetter.html) with [DeadLetterReason::ExhaustedRetries](../prelude/enum.DeadLetterReason.html#variant.ExhaustedRetries),
+    /// see [retry_or_forward](#method.retry_or_forward) to hand it to another Actor instead
+    ///
+    /// # Returns
+    ///
+    /// `false` if the attempts are exhausted
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Fetch { id: u32, failures: u32 }
+    /// impl ActorMessage for Fetch {}
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// type Log = Arc<Mutex<Vec<String>>>;
+    ///
+    /// struct Client { log: Log }
+    /// impl Actor for Client {}
+    /// impl Handler<Fetch> for Client {
+    ///     fn handle(&mut self, msg: Fetch, context: &ActorContext<Self>) {
+    ///         let attempt = context.delivery_attempt();
+    ///         self.log.lock().unwrap().push(format!("fetch {} attempt {}", msg.id, attempt));
+    ///         if attempt <= msg.failures {
+    ///             // the flaky service failed, try again later
+    ///             let policy = RetryPolicy::new(3, BackoffStrategy::Fixed(Duration::from_millis(100)));
+    ///             context.retry(msg, &policy);
+    ///         }
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Client {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(String::from("ping"));
+    ///     }
+    /// }
+    /// struct ClientFactory { log: Log }
+    /// impl ActorFactory<Client> for ClientFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Client>) -> Client {
+    ///         Client { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Collector { log: Log }
+    /// impl Actor for Collector {}
+    /// impl Handler<DeadLetter> for Collector {
+    ///     fn handle(&mut self, msg: DeadLetter, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(format!("dead letter {:?}", msg.reason));
+    ///     }
+    /// }
+    /// struct CollectorFactory { log: Log }
+    /// impl ActorFactory<Collector> for CollectorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Collector>) -> Collector {
+    ///         Collector { log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Log::default();
+    /// let client = actor_system.builder().spawn("client", ClientFactory { log: log.clone() }).unwrap();
+    /// let collector = actor_system.builder().spawn("collector", CollectorFactory { log: log.clone() }).unwrap();
+    /// actor_system.subscribe_dead_letters(&collector);
+    ///
+    /// // fails twice and succeeds with the third attempt, other messages are handled in between
+    /// client.send(Fetch { id: 1, failures: 2 }).unwrap();
+    /// sleep(Duration::from_millis(50));
+    /// client.send(Ping {}).unwrap();
+    /// sleep(Duration::from_millis(350));
+    /// let expected = vec!["fetch 1 attempt 1", "ping", "fetch 1 attempt 2", "fetch 1 attempt 3"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    ///
+    /// // never succeeds, gives up after the third attempt
+    /// log.lock().unwrap().clear();
+    /// client.send(Fetch { id: 2, failures: u32::MAX }).unwrap();
+    /// sleep(Duration::from_millis(400));
+    /// let expected = vec!["fetch 2 attempt 1", "fetch 2 attempt 2", "fetch 2 attempt 3", "dead letter ExhaustedRetries"];
+    /// assert_eq!(expected, *log.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn retry<M>(&self, msg: M, policy: &RetryPolicy) -> bool
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        match self.schedule_retry(msg, policy) {
+            Ok(()) => true,
+            Err(_) => {
+                self.system.get_state().get_dead_letters().report_traced(
+                    self.actor_ref.get_address(),
+                    std::any::type_name::<M>(),
+                    DeadLetterReason::ExhaustedRetries,
+                    self.trace_id(),
+                );
+                false
+            }
+        }
+    }
+
+    /// Same as [retry](#method.retry), but the message is sent to `fallback` once the attempts are exhausted
+    pub fn retry_or_forward<M>(&self, msg: M, policy: &RetryPolicy, fallback: &ReplyTo<M>) -> bool
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        match self.schedule_retry(msg, policy) {
+            Ok(()) => true,
+            Err(msg) => {
+                let _ = fallback.send(msg);
+                false
+            }
+        }
+    }
+
+    /// Hands the message back if the attempts are exhausted
+    fn schedule_retry<M>(&self, msg: M, policy: &RetryPolicy) -> Result<(), M>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        let attempt = self.delivery_attempt();
+        if attempt >= policy.max_attempts {
+            return Err(msg);
+        }
+        let headers = self.headers().with(ATTEMPT_HEADER.1, HeaderValue::U64(u64::from(attempt) + 1));
+        let target = self.actor_ref.clone();
+        let mut pending = Some(msg);
+        let deliver = Box::new(move || {
+            let msg = match pending.take() {
+                Some(msg) => msg,
+                None => return Delivery::Stopped,
+            };
+            match target.try_send_with_headers(msg, headers.clone()) {
+                Ok(()) => Delivery::Delivered,
+                Err(SendError::Full(msg)) => {
+                    pending = Some(msg);
+                    Delivery::Full
+                }
+                Err(_) => Delivery::Stopped,
+            }
+        });
+        self.schedule(Instant::now() + policy.delay(attempt), None, deliver);
+        Ok(())
+    }
+
+    fn schedule(&self, at: Instant, interval: Option<Duration>, deliver: Deliver) -> ScheduledHandle {
+        let (timer, handle) = Timer::new(Some(self.actor_ref.get_address().clone()), interval, deliver);
+        self.actor_ref.get_wakeup_manager().schedule_timer(at, timer);
+        handle
+    }
+}
+
 impl<A> Clone for ActorContext<A>
 where
     A: Actor + 'static,
@@ -25,6 +2493,18 @@ where
         Self {
             system: self.system.clone(),
             actor_ref: self.actor_ref.clone(),
+            suspension: self.suspension.clone(),
+            failure: self.failure.clone(),
+            result: self.result.clone(),
+            headers: self.headers.clone(),
+            sender: self.sender.clone(),
+            expires_at: self.expires_at.clone(),
+            request_chains: self.request_chains.clone(),
+            behaviors: self.behaviors.clone(),
+            is_relocating: self.is_relocating.clone(),
+            replacement: self.replacement.clone(),
+            rng: self.rng.clone(),
+            initial_rng: self.initial_rng.clone(),
         }
     }
 }
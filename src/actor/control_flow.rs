@@ -0,0 +1,123 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::try_handler::{ActorError, FallibleMessage, TryHandler};
+use std::panic::UnwindSafe;
+use std::time::Duration;
+
+/// Returned by a [ControlFlowHandler], the Executor acts on it once the handler returned
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActorResult {
+    /// keeps handling messages
+    Ok,
+    /// handles all messages that have been queued before and stops afterwards, see [ActorWrapper.stop](../prelude/struct.ActorWrapper.html#method.stop)
+    Stop,
+    /// stops right away and drops all queued messages, see [ActorWrapper.stop_now](../prelude/struct.ActorWrapper.html#method.stop_now)
+    StopImmediately,
+    /// handles no further message until the duration elapsed, the Actor sleeps in the meantime and does not hold a worker of its pool
+    ///
+    /// Messages that are sent in the meantime are queued and handled afterwards in order, a stop request ends the sleep right away
+    Sleep(Duration),
+}
+
+/// Marks messages that are handled by a [ControlFlowHandler] instead of a [Handler](../prelude/trait.Handler.html)
+///
+/// Requires [FallibleMessage], the [ControlFlowHandler] is executed as [TryHandler] that never fails.
+/// Implemented by `#[derive(ActorMessage)]` with `#[message(control_flow)]`
+pub trait ControlFlowMessage: FallibleMessage {}
+
+/// Alternative to [Handler](../prelude/trait.Handler.html), whose returned [ActorResult] lets an Actor stop or sleep after the current message
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::{Duration, Instant};
+///
+/// struct Step(u32, ActorResult);
+/// impl ActorMessage for Step {}
+/// impl FallibleMessage for Step {}
+/// impl ControlFlowMessage for Step {}
+///
+/// struct Stage { handled: Arc<Mutex<Vec<(u32, Instant)>>> }
+/// impl Actor for Stage {}
+/// impl ControlFlowHandler<Step> for Stage {
+///     fn handle_flow(&mut self, msg: Step, _context: &ActorContext<Self>) -> ActorResult {
+///         self.handled.lock().unwrap().push((msg.0, Instant::now()));
+///         msg.1
+///     }
+/// }
+/// struct StageFactory { handled: Arc<Mutex<Vec<(u32, Instant)>>> }
+/// impl ActorFactory<Stage> for StageFactory {
+///     fn new_actor(&self, _context: ActorContext<Stage>) -> Stage {
+///         Stage { handled: self.handled.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// // a single worker, which is not blocked by a sleeping Actor
+/// actor_system.add_pool_with_config("single", ThreadPoolConfig::new(0, 1, 1, 1.0));
+/// let spawn = |name: &str, handled: &Arc<Mutex<Vec<(u32, Instant)>>>| {
+///     let factory = StageFactory { handled: handled.clone() };
+///     actor_system.builder().set_pool_name("single").unwrap().spawn(name, factory).unwrap()
+/// };
+/// let steps = |handled: &Arc<Mutex<Vec<(u32, Instant)>>>| -> Vec<u32> {
+///     handled.lock().unwrap().iter().map(|(step, _)| *step).collect()
+/// };
+///
+/// // Stop handles everything that has been queued before
+/// let handled = Arc::new(Mutex::new(Vec::new()));
+/// let stage = spawn("drained", &handled);
+/// stage.send(Step(1, ActorResult::Stop)).unwrap();
+/// stage.send(Step(2, ActorResult::Ok)).unwrap();
+/// assert!(stage.await_stop(Duration::from_secs(1)));
+/// assert_eq!(vec![1, 2], steps(&handled));
+///
+/// // StopImmediately drops the queue
+/// let handled = Arc::new(Mutex::new(Vec::new()));
+/// let stage = spawn("dropped", &handled);
+/// stage.send(Step(1, ActorResult::StopImmediately)).unwrap();
+/// stage.send(Step(2, ActorResult::Ok)).unwrap();
+/// assert!(stage.await_stop(Duration::from_secs(1)));
+/// assert_eq!(vec![1], steps(&handled));
+///
+/// // Sleep holds back queued and later messages without blocking the pool
+/// let handled = Arc::new(Mutex::new(Vec::new()));
+/// let stage = spawn("sleeping", &handled);
+/// let sent = Instant::now();
+/// stage.send(Step(1, ActorResult::Sleep(Duration::from_millis(500)))).unwrap();
+/// stage.send(Step(2, ActorResult::Ok)).unwrap();
+/// let other = Arc::new(Mutex::new(Vec::new()));
+/// let neighbour = spawn("neighbour", &other);
+/// sleep(Duration::from_millis(100));
+/// stage.send(Step(3, ActorResult::Ok)).unwrap();
+/// neighbour.send(Step(1, ActorResult::Ok)).unwrap();
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(vec![1], steps(&handled));
+/// assert_eq!(vec![1], steps(&other));
+/// sleep(Duration::from_millis(600));
+/// assert_eq!(vec![1, 2, 3], steps(&handled));
+/// assert!(handled.lock().unwrap()[1].1.duration_since(sent) >= Duration::from_millis(500));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+pub trait ControlFlowHandler<M>: Actor + Sized
+where
+    M: ControlFlowMessage,
+{
+    fn handle_flow(&mut self, msg: M, context: &ActorContext<Self>) -> ActorResult;
+}
+
+impl<A, M> TryHandler<M> for A
+where
+    A: ControlFlowHandler<M> + UnwindSafe + 'static,
+    M: ControlFlowMessage,
+{
+    fn try_handle(&mut self, msg: M, context: &ActorContext<A>) -> Result<(), ActorError> {
+        let result = self.handle_flow(msg, context);
+        context.set_result(result);
+        Ok(())
+    }
+}
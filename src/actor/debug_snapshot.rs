@@ -0,0 +1,46 @@
+use crate::actor::actor::Actor;
+use crate::actor::panic_report;
+use crate::message::actor_message::ActorMessage;
+use crossbeam_channel::Sender;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Actor whose state can be inspected while it runs, see [ActorSystem.debug_snapshot](../prelude/struct.ActorSystem.html#method.debug_snapshot)
+///
+/// Has to be enabled per Actor through [ActorBuilder.set_debuggable](../prelude/struct.ActorBuilder.html#method.set_debuggable)
+pub trait DebuggableActor: Actor {
+    /// human readable state of the Actor, executed on the thread of the Actor between two messages
+    fn debug_snapshot(&self) -> String;
+}
+
+/// Reason why [ActorSystem.debug_snapshot](../prelude/struct.ActorSystem.html#method.debug_snapshot) did not return a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    /// no running Actor has the address
+    NotFound,
+    /// the Actor has not been spawned through [ActorBuilder.set_debuggable](../prelude/struct.ActorBuilder.html#method.set_debuggable)
+    NotSupported,
+    /// the Actor did not get to the request within the timeout, e.g. because it is busy or its bounded mailbox stayed full
+    Timeout,
+    /// the Actor stopped before it handled the request
+    Stopped,
+    /// [DebuggableActor.debug_snapshot](./trait.DebuggableActor.html#tymethod.debug_snapshot) panicked, `reason` is `None` if the panic payload is not a string
+    Panicked { reason: Option<String> },
+}
+
+pub(crate) type DebugSnapshotFn<A> = fn(&A) -> String;
+
+pub(crate) struct DebugSnapshotMessage {
+    pub result: Sender<Result<String, SnapshotError>>,
+}
+
+impl ActorMessage for DebugSnapshotMessage {}
+
+/// Takes the snapshot, a panic is reported to the caller instead of restarting the Actor
+pub(crate) fn take<A>(actor: &A, snapshot: DebugSnapshotFn<A>) -> Result<String, SnapshotError>
+where
+    A: Actor,
+{
+    catch_unwind(AssertUnwindSafe(|| snapshot(actor))).map_err(|panic| SnapshotError::Panicked {
+        reason: panic_report::panic_message(panic.as_ref()),
+    })
+}
@@ -0,0 +1,345 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::context::ActorContext;
+use std::collections::{HashMap, VecDeque};
+use std::panic::UnwindSafe;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Result of an effect executed through an [EffectGuard](./struct.EffectGuard.html)
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectOutcome {
+    /// the effect has been executed and its outcome recorded
+    Performed(String),
+    /// the effect had already completed, it has been skipped
+    Replayed(String),
+}
+
+impl EffectOutcome {
+    pub fn get_value(&self) -> &str {
+        match self {
+            EffectOutcome::Performed(value) | EffectOutcome::Replayed(value) => value,
+        }
+    }
+
+    pub fn is_replayed(&self) -> bool {
+        matches!(self, EffectOutcome::Replayed(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectError<E> {
+    /// the effect failed, nothing has been recorded so it is executed again on the next attempt
+    Failed(E),
+    /// [EffectGuard.once_per_message](./struct.EffectGuard.html#method.once_per_message) has been called for a message that is not [Audited](./trait.Audited.html)
+    NoMessageId,
+}
+
+/// Stores the outcome of completed effects, see [ActorBuilder.set_effect_ledger](../prelude/struct.ActorBuilder.html#method.set_effect_ledger)
+///
+/// An implementation backed by durable storage keeps effects from being repeated across process restarts.
+/// Entries may be evicted, an evicted effect is executed again
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::collections::HashMap;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// // stands in for a database table that outlives the process
+/// #[derive(Clone, Default)]
+/// struct DurableLedger { rows: Arc<Mutex<HashMap<(String, String), String>>> }
+/// impl EffectLedger for DurableLedger {
+///     fn get(&self, address: &ActorAddress, key: &str) -> Option<String> {
+///         self.rows.lock().unwrap().get(&(address.actor.clone(), String::from(key))).cloned()
+///     }
+///     fn record(&self, address: &ActorAddress, key: &str, outcome: &str) {
+///         self.rows.lock().unwrap().insert((address.actor.clone(), String::from(key)), String::from(outcome));
+///     }
+///     fn len(&self) -> usize {
+///         self.rows.lock().unwrap().len()
+///     }
+/// }
+///
+/// struct Ship { order: u64 }
+/// impl ActorMessage for Ship {}
+///
+/// struct Shipping { shipped: Arc<AtomicUsize>, outcomes: Arc<Mutex<Vec<EffectOutcome>>> }
+/// impl Actor for Shipping {}
+/// impl Handler<Ship> for Shipping {
+///     fn handle(&mut self, msg: Ship, context: &ActorContext<Self>) {
+///         let outcome = context.effect_guard().once(&format!("ship-{}", msg.order), || -> Result<String, ()> {
+///             self.shipped.fetch_add(1, Ordering::Relaxed);
+///             Ok(format!("parcel-{}", msg.order))
+///         });
+///         self.outcomes.lock().unwrap().push(outcome.unwrap());
+///     }
+/// }
+/// struct ShippingFactory { shipped: Arc<AtomicUsize>, outcomes: Arc<Mutex<Vec<EffectOutcome>>> }
+/// impl ActorFactory<Shipping> for ShippingFactory {
+///     fn new_actor(&self, _context: ActorContext<Shipping>) -> Shipping {
+///         Shipping { shipped: self.shipped.clone(), outcomes: self.outcomes.clone() }
+///     }
+/// }
+///
+/// let ledger = DurableLedger::default();
+/// let shipped = Arc::new(AtomicUsize::new(0));
+/// let outcomes = Arc::new(Mutex::new(Vec::new()));
+/// let run = || {
+///     let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+///     let factory = ShippingFactory { shipped: shipped.clone(), outcomes: outcomes.clone() };
+///     let shipping = actor_system.builder().set_effect_ledger(ledger.clone()).spawn("shipping", factory).unwrap();
+///     shipping.send(Ship { order: 7 });
+///     sleep(Duration::from_millis(100));
+///     actor_system.stop(Duration::from_millis(100));
+///     actor_system.await_shutdown();
+/// };
+///
+/// // the second process receives the same order again, the ledger remembers that it has been shipped
+/// run();
+/// run();
+/// assert_eq!(1, shipped.load(Ordering::Relaxed));
+/// let outcomes = outcomes.lock().unwrap();
+/// assert_eq!(EffectOutcome::Performed(String::from("parcel-7")), outcomes[0]);
+/// assert_eq!(EffectOutcome::Replayed(String::from("parcel-7")), outcomes[1]);
+/// ```
+pub trait EffectLedger: Send + Sync {
+    /// Returns the outcome recorded for `key` of the given Actor
+    fn get(&self, address: &ActorAddress, key: &str) -> Option<String>;
+    fn record(&self, address: &ActorAddress, key: &str, outcome: &str);
+    /// Amount of recorded outcomes
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+type EffectKey = (ActorAddress, String);
+
+struct MemoryLedgerState {
+    outcomes: HashMap<EffectKey, (String, Instant)>,
+    /// insertion order, all entries share the same ttl
+    order: VecDeque<(EffectKey, Instant)>,
+}
+
+/// Default [EffectLedger](./trait.EffectLedger.html), does not survive the process
+///
+/// Keeps at most `capacity` outcomes, each for at most `ttl`.
+/// Once evicted, an effect is executed again; this is the consistency boundary of the ledger
+pub struct MemoryEffectLedger {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<MemoryLedgerState>,
+}
+
+impl MemoryEffectLedger {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            state: Mutex::new(MemoryLedgerState {
+                outcomes: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn evict(&self, state: &mut MemoryLedgerState, now: Instant) {
+        while let Some((key, recorded)) = state.order.front() {
+            let is_expired = now.saturating_duration_since(*recorded) >= self.ttl;
+            if !is_expired && state.order.len() <= self.capacity {
+                break;
+            }
+            // an outcome recorded again is only removed together with its latest entry
+            if state.outcomes.get(key).map(|(_, at)| at) == Some(recorded) {
+                state.outcomes.remove(key);
+            }
+            state.order.pop_front();
+        }
+    }
+}
+
+impl EffectLedger for MemoryEffectLedger {
+    fn get(&self, address: &ActorAddress, key: &str) -> Option<String> {
+        let mut state = self.state.lock().unwrap();
+        self.evict(&mut state, Instant::now());
+        let key = (address.clone(), String::from(key));
+        state.outcomes.get(&key).map(|(outcome, _)| outcome.clone())
+    }
+
+    fn record(&self, address: &ActorAddress, key: &str, outcome: &str) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let key = (address.clone(), String::from(key));
+        state.outcomes.insert(key.clone(), (String::from(outcome), now));
+        state.order.push_back((key, now));
+        self.evict(&mut state, now);
+    }
+
+    fn len(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        self.evict(&mut state, Instant::now());
+        state.outcomes.len()
+    }
+}
+
+/// Executes side effects of a handler at most once per key, see [ActorContext.effect_guard](../prelude/struct.ActorContext.html#method.effect_guard)
+///
+/// Only completed effects are recorded: an effect that fails or panics is executed again on the next attempt.
+/// Exactly-once therefore only holds for effects that succeeded and whose outcome is still kept by the [EffectLedger](./trait.EffectLedger.html)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// // redelivered by the gateway itself until the third attempt, keeping its provenance and therefore its message id
+/// struct Charge { order: u64, attempt: u32 }
+/// impl ActorMessage for Charge {}
+/// impl Audited for Charge {}
+/// struct Refund { order: u64 }
+/// impl ActorMessage for Refund {}
+///
+/// struct Gateway { calls: Arc<AtomicUsize>, outcomes: Arc<Mutex<Vec<Result<EffectOutcome, EffectError<String>>>>> }
+/// impl Actor for Gateway {}
+/// impl Handler<Charge> for Gateway {
+///     fn handle(&mut self, msg: Charge, context: &ActorContext<Self>) {
+///         let calls = self.calls.clone();
+///         let outcome = context.effect_guard().once_per_message("charge", || {
+///             // the payment provider is down for the first attempt of order 2
+///             if calls.fetch_add(1, Ordering::Relaxed) == 1 && msg.order == 2 {
+///                 return Err(String::from("provider unavailable"));
+///             }
+///             Ok(format!("receipt-{}", msg.order))
+///         });
+///         self.outcomes.lock().unwrap().push(outcome);
+///         if msg.attempt < 3 {
+///             context.actor_ref.send_with_headers(Charge { order: msg.order, attempt: msg.attempt + 1 }, context.headers());
+///         }
+///     }
+/// }
+/// impl Handler<Refund> for Gateway {
+///     fn handle(&mut self, msg: Refund, context: &ActorContext<Self>) {
+///         let calls = self.calls.clone();
+///         let outcome = context.effect_guard().once(&format!("refund-{}", msg.order), || -> Result<String, String> {
+///             calls.fetch_add(1, Ordering::Relaxed);
+///             Ok(String::from("refunded"))
+///         });
+///         self.outcomes.lock().unwrap().push(outcome);
+///     }
+/// }
+/// struct GatewayFactory { calls: Arc<AtomicUsize>, outcomes: Arc<Mutex<Vec<Result<EffectOutcome, EffectError<String>>>>> }
+/// impl ActorFactory<Gateway> for GatewayFactory {
+///     fn new_actor(&self, _context: ActorContext<Gateway>) -> Gateway {
+///         Gateway { calls: self.calls.clone(), outcomes: self.outcomes.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// actor_system.register_audited::<Charge>();
+/// let calls = Arc::new(AtomicUsize::new(0));
+/// let outcomes = Arc::new(Mutex::new(Vec::new()));
+/// let gateway = actor_system
+///     .builder()
+///     .set_effect_ledger(MemoryEffectLedger::new(100, Duration::from_secs(1)))
+///     .spawn("gateway", GatewayFactory { calls: calls.clone(), outcomes: outcomes.clone() })
+///     .unwrap();
+///
+/// // three deliveries of the same charge call the provider once
+/// gateway.send(Charge { order: 1, attempt: 1 });
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(1, calls.load(Ordering::Relaxed));
+/// let performed = EffectOutcome::Performed(String::from("receipt-1"));
+/// let replayed = EffectOutcome::Replayed(String::from("receipt-1"));
+/// assert_eq!(vec![Ok(performed), Ok(replayed.clone()), Ok(replayed)], outcomes.lock().unwrap().drain(..).collect::<Vec<_>>());
+///
+/// // a failed effect is not recorded, the next delivery tries again
+/// gateway.send(Charge { order: 2, attempt: 1 });
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(3, calls.load(Ordering::Relaxed));
+/// let outcomes_2: Vec<_> = outcomes.lock().unwrap().drain(..).collect();
+/// assert_eq!(Err(EffectError::Failed(String::from("provider unavailable"))), outcomes_2[0]);
+/// assert_eq!(Ok(EffectOutcome::Performed(String::from("receipt-2"))), outcomes_2[1]);
+/// assert!(outcomes_2[2].as_ref().unwrap().is_replayed());
+/// assert_eq!(2, gateway.effect_ledger_len());
+///
+/// // effects are only deduplicated within the ttl of the ledger
+/// gateway.send(Refund { order: 1 });
+/// gateway.send(Refund { order: 1 });
+/// sleep(Duration::from_millis(1100));
+/// assert_eq!(0, gateway.effect_ledger_len());
+/// gateway.send(Refund { order: 1 });
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(5, calls.load(Ordering::Relaxed));
+///
+/// // messages that are not audited have no id
+/// struct Plain {}
+/// impl ActorMessage for Plain {}
+/// impl Handler<Plain> for Gateway {
+///     fn handle(&mut self, _msg: Plain, context: &ActorContext<Self>) {
+///         let outcome = context.effect_guard().once_per_message("plain", || Ok(String::new()));
+///         self.outcomes.lock().unwrap().push(outcome);
+///     }
+/// }
+/// outcomes.lock().unwrap().clear();
+/// gateway.send(Plain {});
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(vec![Err(EffectError::NoMessageId)], *outcomes.lock().unwrap());
+/// ```
+pub struct EffectGuard<'a, A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    context: &'a ActorContext<A>,
+    ledger: &'a dyn EffectLedger,
+}
+
+impl<'a, A> EffectGuard<'a, A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub(crate) fn new(context: &'a ActorContext<A>, ledger: &'a dyn EffectLedger) -> Self {
+        Self { context, ledger }
+    }
+
+    /// Executes `effect` unless an outcome is recorded for `key`, in which case the recorded outcome is returned
+    pub fn once<F, E>(&self, key: &str, effect: F) -> Result<EffectOutcome, EffectError<E>>
+    where
+        F: FnOnce() -> Result<String, E>,
+    {
+        let address = self.context.actor_ref.get_address();
+        if let Some(outcome) = self.ledger.get(address, key) {
+            return Ok(EffectOutcome::Replayed(outcome));
+        }
+        let outcome = effect().map_err(EffectError::Failed)?;
+        self.ledger.record(address, key, &outcome);
+        Ok(EffectOutcome::Performed(outcome))
+    }
+
+    /// Same as [once](#method.once), keyed by the id of the handled [Audited](./trait.Audited.html) message and `discriminator`
+    ///
+    /// Redeliveries keep the id as long as they are sent with the headers of the original message
+    pub fn once_per_message<F, E>(&self, discriminator: &str, effect: F) -> Result<EffectOutcome, EffectError<E>>
+    where
+        F: FnOnce() -> Result<String, E>,
+    {
+        let message_id = match self.context.provenance() {
+            Some(provenance) => provenance.message_id,
+            None => return Err(EffectError::NoMessageId),
+        };
+        self.once(&format!("{}/{}", message_id, discriminator), effect)
+    }
+}
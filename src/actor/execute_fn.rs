@@ -0,0 +1,45 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::sync::Mutex;
+
+type Function<A> = Box<dyn FnOnce(&mut A, &ActorContext<A>) + Send>;
+
+/// Closure that is executed by the Actor in the order it has been sent, like any other message, see [ActorWrapper.exec](../prelude/struct.ActorWrapper.html#method.exec)
+pub struct ExecuteFn<A>
+where
+    A: Actor + 'static,
+{
+    /// only locked once the message is handled, it makes the closure `Sync` without requiring it to be
+    function: Mutex<Function<A>>,
+}
+
+impl<A> ExecuteFn<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new<F>(function: F) -> Self
+    where
+        F: FnOnce(&mut A, &ActorContext<A>) + Send + 'static,
+    {
+        Self {
+            function: Mutex::new(Box::new(function)),
+        }
+    }
+}
+
+impl<A> ActorMessage for ExecuteFn<A> where A: Actor + 'static {}
+
+impl<A> Handler<ExecuteFn<A>> for A
+where
+    A: Actor + Sized + 'static,
+{
+    fn handle(&mut self, msg: ExecuteFn<A>, context: &ActorContext<A>) {
+        let function = match msg.function.into_inner() {
+            Ok(function) => function,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        function(self, context);
+    }
+}
@@ -1,38 +1,99 @@
 use crate::actor::actor::Actor;
 use crate::actor::actor_address::ActorAddress;
-use crate::actor::actor_config::{ActorConfig, RestartPolicy};
-use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_config::{
+    ActorConfig, BackoffStrategy, FailedMessageStrategy, PanicEscalation, RestartMailboxPolicy, RestartPolicy, SystemStopOrder,
+};
+use crate::actor::actor_factory::{ActorInitError, TryActorFactory};
+use crate::actor::actor_metrics::ActorMetrics;
 use crate::actor::actor_state::ActorState;
-use crate::actor::actor_wrapper::ActorWrapper;
 use crate::actor::context::ActorContext;
+use crate::actor::extraction;
 use crate::actor::handler::Handler;
-use crate::actor::mailbox::Mailbox;
+use crate::actor::handoff::{is_control_message, Redirect};
+use crate::actor::message_registry;
+use crate::actor::journal::{JournalEntry, JournalEvent, PANIC_REPORT_JOURNAL_ENTRIES};
+use crate::actor::mailbox::{Executing, Mailbox};
+use crate::actor::message_class::ClassSchedule;
+use crate::actor::middleware::{HandleOutcome, MessageMeta, MiddlewareDecision};
+use crate::actor::panic_report::{self, ActorPanicReport, CaptureRing};
+use crate::actor::priority::{self, ActorPriority};
+use crate::actor::rate_limit::RateLimiter;
+use crate::actor::replacement::Replacement;
+use crate::actor::request_chain;
+use crate::actor::slo::SloTracker;
+use crate::actor::suspension::{ActiveSuspension, SuspendCondition, SuspensionEndReason};
+use crate::actor::throughput::ThroughputController;
+use crate::actor::control_flow::ActorResult;
+use crate::actor::try_handler::ActorError;
 use crate::message::actor_message::ActorMessage;
+use crate::message::actor_stop_message::ActorStopMessage;
 use crate::message::envelope::{MessageEnvelope, MessageEnvelopeTrait};
+use crate::message::headers::{self, Headers};
+use crate::message::message_size;
+use crate::message::provenance::{self, ProvenanceAction, ProvenanceHop, DERIVED_HEADER, PROVENANCE_HEADER};
+use crate::message::receipt::Receipt;
+use crate::system::cycle_detection::CycleDetector;
+use crate::system::dead_letters::DeadLetterReason;
+#[cfg(feature = "replay")]
+use crate::system::execution_recorder::ExecutionRecorder;
 use crate::message::message_type::MessageType;
+use crate::message::suspension_ended_message::SuspensionEndedMessage;
 use crate::message::system_stop_message::SystemStopMessage;
-use crate::system::actor_system::ActorSystem;
+use crate::message::trace;
+use crate::system::memory_pressure::MemoryMode;
+use crate::system::panic_handler::{self, PanicInfoContext};
+use crate::system::slow_handler::SlowHandler;
+use crate::system::yield_point::yield_point;
 use crossbeam_channel::Receiver;
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
+use std::io::Write;
 use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
 use std::sync::atomic::Ordering;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 pub trait ExecutorTrait: Send + Sync {
     fn handle(&mut self, is_system_stopping: bool) -> ActorState;
+    /// Ends the Actor without handling anything that is still queued, see [Actor.post_stop](../prelude/trait.Actor.html#method.post_stop)
+    fn force_stop(&mut self);
     fn get_config(&self) -> &ActorConfig;
     fn get_address(&self) -> ActorAddress;
     fn is_sleeping(&self) -> bool;
     fn is_stopped(&self) -> bool;
+    fn is_wakeup_pending(&self) -> bool;
     fn wakeup(&mut self);
+    fn get_effective_priority(&self) -> ActorPriority;
+    fn get_last_worker(&self) -> Option<usize>;
+    /// Returns `true` if the previous scheduling slot ran on another worker
+    fn record_worker(&mut self, worker: usize) -> bool;
+    /// Maximum amount of `handle` calls of the current scheduling slot
+    fn get_message_budget(&self) -> usize;
+    /// Called once the worker switches to the next Actor
+    fn end_slot(&mut self);
+    #[cfg(feature = "replay")]
+    fn last_handled(&self) -> Option<&'static str>;
+    /// Same as `handle`, but a message of the priority lane is only taken if it is of the recorded type
+    #[cfg(feature = "replay")]
+    fn replay_next(&mut self, type_name: &str) -> ActorState;
 }
 
 pub struct Executor<A, P>
 where
     A: Actor + 'static,
-    P: ActorFactory<A>,
+    P: TryActorFactory<A>,
 {
-    actor: A,
+    /// `None` once the Actor reached its final state, or until an Actor on a dedicated thread or a restarted Actor has been created
+    actor: Option<A>,
+    /// `false` until the Actor on a dedicated thread or the restarted Actor has been created
+    is_created: bool,
+    /// `true` until a lazy Actor has been created for the first time, see [ActorBuilder.set_lazy](../prelude/struct.ActorBuilder.html#method.set_lazy)
+    is_lazy: bool,
     actor_props: P,
+    /// replaces `actor_props` once the Actor has been replaced, see [ActorSystem.replace_actor](../prelude/struct.ActorSystem.html#method.replace_actor)
+    replacement: Option<Replacement<A>>,
+    /// handed over by the replaced Actor, received by the next one before `pre_start`
+    handover: Option<Vec<u8>>,
     actor_config: ActorConfig,
     mailbox: Mailbox<A>,
     queue: Receiver<MessageEnvelope<A>>,
@@ -40,75 +101,260 @@ where
     is_startup: bool,
     system_triggered_stop: bool,
     last_wakeup: Instant,
+    /// `true` from a wakeup until the next handled message
+    is_wakeup_unused: bool,
+    last_worker: Option<usize>,
     context: ActorContext<A>,
+    metrics: ActorMetrics,
+    pending: VecDeque<MessageEnvelope<A>>,
+    /// only used if the Actor has message classes
+    class_schedule: ClassSchedule,
+    /// copy of the failed message, handled by the restarted Actor before anything else, see [FailedMessageStrategy::RetryOnce]
+    retry: Option<MessageEnvelope<A>>,
+    /// whether the message that has been taken last is a retry
+    is_retry: bool,
+    suspension: Option<ActiveSuspension>,
+    /// restarts within the window of [RestartPolicy::Limited], oldest first
+    recent_restarts: VecDeque<Instant>,
+    /// set while the restarted Actor waits for the backoff of [RestartPolicy::Limited]
+    restart_backoff: Option<Instant>,
+    /// set while the Actor sleeps because of [ActorResult::Sleep]
+    asleep_until: Option<Instant>,
+    has_headers: bool,
+    /// whether the context holds the sender of the previous message
+    has_sender: bool,
+    /// whether the context holds the expiry of the previous message
+    has_expiry: bool,
+    max_inheritance_depth: u64,
+    boosted_chain: Option<u64>,
+    idle_sleep_after: Duration,
+    slo: Option<SloTracker>,
+    max_captured_bytes: usize,
+    max_provenance_hops: usize,
+    cycle_detector: Option<Arc<CycleDetector>>,
+    capture_ring: Option<CaptureRing>,
+    /// only set if the Actor adapts its throughput
+    throughput: Option<ThroughputController>,
+    slot_handled: usize,
+    slot_handler_time: Duration,
+    /// only set if the Actor has a rate limit, kept across restarts
+    rate_limiter: Option<RateLimiter>,
+    /// end of the window the Actor waits for, its wakeup has already been scheduled
+    rate_limited_until: Option<Instant>,
+    /// last handled message, only tracked if the Actor has an idle timeout
+    last_received: Instant,
+    /// idle deadline whose wakeup has already been scheduled
+    idle_wakeup: Option<Instant>,
+    /// due time of the next tick, its wakeup has already been scheduled
+    next_tick: Option<Instant>,
+    /// handled messages since the last recovery snapshot
+    since_snapshot: usize,
+    /// whether the Actor recovers from its snapshot before `pre_start`
+    is_recovering: bool,
+    #[cfg(feature = "replay")]
+    recorder: Option<(ExecutionRecorder, Arc<str>)>,
+    #[cfg(feature = "replay")]
+    last_handled: Option<&'static str>,
+    /// type of the message a replay expects next, see [replay_next](#method.replay_next)
+    #[cfg(feature = "replay")]
+    replay_expected: Option<String>,
+    /// priority lane messages taken from the mailbox by a replay that expected another message
+    #[cfg(feature = "replay")]
+    held_urgent: VecDeque<MessageEnvelope<A>>,
 }
 
 unsafe impl<A, P> Send for Executor<A, P>
 where
     A: Actor + UnwindSafe + 'static,
-    P: ActorFactory<A>,
+    P: TryActorFactory<A>,
 {
 }
 unsafe impl<A, P> Sync for Executor<A, P>
 where
     A: Actor + UnwindSafe + 'static,
-    P: ActorFactory<A>,
+    P: TryActorFactory<A>,
 {
 }
 
 impl<A, P> ExecutorTrait for Executor<A, P>
 where
     A: Actor + UnwindSafe + 'static,
-    P: ActorFactory<A>,
+    P: TryActorFactory<A>,
 {
     fn handle(&mut self, system_is_stopping: bool) -> ActorState {
-        if system_is_stopping && !self.system_triggered_stop {
+        let _executing = Executing::enter(&self.mailbox.flags);
+        if self.is_lazy {
+            if let Some(state) = self.defer_creation(system_is_stopping) {
+                return state;
+            }
+            self.is_lazy = false;
+        }
+        if !self.is_created && (system_is_stopping || !self.is_backing_off()) {
+            // first slot on the dedicated thread, see [ActorBuilder.set_dedicated_thread](../prelude/struct.ActorBuilder.html#method.set_dedicated_thread), or the first one after a restart
+            self.is_created = true;
+            if let Some(state) = self.create_actor() {
+                return state;
+            }
+        }
+        if self.is_created && self.actor.is_none() {
+            return ActorState::Stopped;
+        }
+        if self.context.system.get_state().is_force_stopped() {
+            self.force_stop();
+            return ActorState::Stopped;
+        }
+        if self.mailbox.is_awaiting_release() {
+            if system_is_stopping || self.is_stopped() {
+                return self.stop_unreleased();
+            }
+            return ActorState::Sleeping;
+        }
+        if self.is_paused(system_is_stopping) {
+            // sends after the recovery wake the Actor up again
+            self.mailbox.flags.is_sleeping.store(true, Ordering::Relaxed);
+            return ActorState::Sleeping;
+        }
+        if !system_is_stopping && self.is_backing_off() {
+            // woken up by the wakeup scheduled with the restart, or by a stop request
+            self.mailbox.flags.is_sleeping.store(true, Ordering::Relaxed);
+            return ActorState::Sleeping;
+        }
+        self.restart_backoff = None;
+        if !system_is_stopping && self.is_asleep() {
+            // woken up by the wakeup scheduled with the sleep, or by a stop request
+            self.mailbox.flags.is_sleeping.store(true, Ordering::Relaxed);
+            return ActorState::Sleeping;
+        }
+        self.asleep_until = None;
+        if system_is_stopping && !self.system_triggered_stop && self.actor_config.system_stop_order == SystemStopOrder::BeforeQueued {
             self.system_triggered_stop = true;
-            self.send(SystemStopMessage {});
+            // handled before anything that is still queued
+            self.mailbox.urgent_in.send(MessageEnvelope::new(SystemStopMessage {})).unwrap();
+            self.stop_daemon();
         }
         if self.is_startup {
             self.is_startup = false;
-            self.actor.pre_start();
+            let recovery = self.mailbox.recovery.as_ref().filter(|_| self.is_recovering);
+            let address = &self.actor_address;
+            let actor = self.actor.as_mut().unwrap();
+            let mut message_type = "recover";
+            let result = panic_handler::catch(|| {
+                if let Some(recovery) = recovery {
+                    recovery.recover(address, actor);
+                }
+                message_type = "pre_start";
+                actor.pre_start()
+            });
+            self.is_recovering = false;
+            if result.is_ok() {
+                self.update_readiness();
+            }
+            if let Err(panic) = result {
+                let panic_message = panic_report::panic_message(panic.as_ref());
+                self.notify_panic(message_type, &panic_message);
+                let report = ActorPanicReport {
+                    address: self.actor_address.clone(),
+                    message_type,
+                    panic_message,
+                    error: None,
+                    payload: None,
+                    recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+                    provenance: None,
+                    trace_id: None,
+                    journal: self.journal_tail(),
+                };
+                return self.restart_or_stop(report);
+            }
+        }
+        if self.suspension.is_some() {
+            if let Some(state) = self.handle_suspension() {
+                return state;
+            }
+        }
+        for (chain_id, stage, deadline) in self.context.get_request_chains().expire(Instant::now()) {
+            self.send(request_chain::timeout(chain_id, stage));
+            self.context
+                .actor_ref
+                .get_wakeup_manager()
+                .schedule_wakeup(self.actor_address.clone(), deadline);
         }
-        let m = self.queue.try_recv();
+        if let Some(state) = self.tick() {
+            return state;
+        }
+        if self.mailbox.is_stop_now_requested() && self.is_stopped() {
+            self.drop_queued();
+        }
+        if self.is_rate_limited() {
+            self.mailbox.flags.is_sleeping.store(true, Ordering::Relaxed);
+            return ActorState::Sleeping;
+        }
+        let m = self.next_message();
 
-        if m.is_err() {
+        if m.is_none() {
             if self.is_stopped() {
-                self.actor.post_stop();
+                self.stop_actor();
                 return ActorState::Stopped;
             }
-            self.mailbox.is_sleeping.store(true, Ordering::Relaxed);
-            let duration = self.last_wakeup.elapsed();
-            if duration >= Duration::from_secs(5) {
-                return ActorState::Sleeping;
+            if system_is_stopping && !self.system_triggered_stop {
+                self.system_triggered_stop = true;
+                // the mailbox has been drained, see SystemStopOrder::AfterQueued
+                let state = self.handle_envelope(MessageEnvelope::new(SystemStopMessage {}));
+                self.stop_daemon();
+                return state;
             }
-            self.mailbox.is_sleeping.store(false, Ordering::Relaxed);
-            return ActorState::Running;
+            if self.is_idle() {
+                self.stop_idle();
+                return ActorState::Running;
+            }
+            yield_point();
+            if self.last_wakeup.elapsed() < self.idle_sleep_after {
+                return ActorState::Running;
+            }
+            // only an Actor that is actually going to sleep is marked, a sender that sees the mark has to wake it up
+            if !self.mailbox.mark_sleeping() {
+                return ActorState::Running;
+            }
+            if self.is_wakeup_unused {
+                self.metrics.increment_empty_wakeups();
+            }
+            return ActorState::Sleeping;
         }
 
-        let mut msg = m.unwrap();
-        let result = catch_unwind(AssertUnwindSafe(|| {
-            msg.handle(&mut self.actor, &self.context)
-        }));
-        if result.is_err() {
-            println!("ACTOR PANIC");
-            self.actor.post_stop();
-
-            if self.actor_config.restart_policy == RestartPolicy::Never || self.is_stopped() {
-                self.mailbox.is_stopped.store(true, Ordering::Relaxed);
-                return ActorState::Stopped;
-            }
-            self.actor = self.actor_props.new_actor(self.context.clone());
-            self.is_startup = true;
-            return ActorState::Running;
+        self.is_wakeup_unused = false;
+        let state = self.handle_envelope(m.unwrap());
+        self.update_readiness();
+        if let Some(chain) = self.boosted_chain.take() {
+            self.release_boost(chain);
         }
-        let message_type = result.unwrap();
-        if message_type == MessageType::ActorStopMessage {
-            self.mailbox.is_stopped.store(true, Ordering::Relaxed);
-            return ActorState::Running;
+        if state == ActorState::Running {
+            self.start_requested_suspension();
         }
+        state
+    }
 
-        ActorState::Running
+    fn force_stop(&mut self) {
+        if !self.is_created {
+            self.is_created = true;
+            self.mailbox.flags.is_stopped.store(true, Ordering::Relaxed);
+            self.drop_queued();
+            self.clear_pressure();
+            self.finish_uncreated();
+            self.mailbox.wake_senders();
+            return;
+        }
+        if self.actor.is_none() {
+            return;
+        }
+        // an Actor that never executed pre_start does not execute post_stop either
+        if self.mailbox.is_awaiting_release() || self.is_startup {
+            self.stop_unreleased();
+            self.mailbox.wake_senders();
+            return;
+        }
+        self.mailbox.flags.is_stopped.store(true, Ordering::Relaxed);
+        self.drop_queued();
+        self.stop_actor();
+        self.mailbox.wake_senders();
     }
 
     fn get_config(&self) -> &ActorConfig {
@@ -120,42 +366,177 @@ where
     }
 
     fn is_sleeping(&self) -> bool {
-        self.mailbox.is_sleeping.load(Ordering::Relaxed)
+        self.mailbox.flags.is_sleeping.load(Ordering::Relaxed)
     }
 
     fn is_stopped(&self) -> bool {
-        self.mailbox.is_stopped.load(Ordering::Relaxed)
+        self.mailbox.flags.is_stopped.load(Ordering::Relaxed)
+    }
+
+    fn is_wakeup_pending(&self) -> bool {
+        if self.mailbox.is_awaiting_release() {
+            return self.is_stopped();
+        }
+        if self.is_paused(false) || self.is_backing_off() || self.is_asleep() {
+            return false;
+        }
+        if !self.mailbox.is_empty() {
+            return true;
+        }
+        if self.suspension.is_none() && self.is_tick_due() {
+            return true;
+        }
+        #[cfg(feature = "replay")]
+        if !self.held_urgent.is_empty() {
+            return true;
+        }
+        match &self.suspension {
+            None => {
+                !self.pending.is_empty()
+                    || self.mailbox.priority.has_boosted()
+                    || !self.mailbox.handoff.lock().unwrap().front.is_empty()
+            }
+            Some(suspension) => self.is_suspension_resolved(suspension),
+        }
     }
 
     fn wakeup(&mut self) {
-        self.mailbox.is_sleeping.store(false, Ordering::Relaxed);
+        self.mailbox.flags.is_sleeping.store(false, Ordering::Relaxed);
         self.last_wakeup = Instant::now();
+        self.is_wakeup_unused = true;
+    }
+
+    fn get_effective_priority(&self) -> ActorPriority {
+        self.mailbox.priority.get_effective()
+    }
+
+    fn get_last_worker(&self) -> Option<usize> {
+        self.last_worker
+    }
+
+    fn record_worker(&mut self, worker: usize) -> bool {
+        if self.last_worker == Some(worker) {
+            return false;
+        }
+        let migrated = self.last_worker.is_some();
+        self.last_worker = Some(worker);
+        self.metrics.record_worker(worker, migrated);
+        migrated
+    }
+
+    fn get_message_budget(&self) -> usize {
+        let budget = match &self.throughput {
+            Some(throughput) => throughput.get_budget(),
+            None => self.actor_config.message_throughput,
+        };
+        match &self.rate_limiter {
+            // an exhausted window still takes one call, which puts the Actor to sleep
+            Some(rate_limiter) => budget.min(rate_limiter.get_remaining(Instant::now())).max(1),
+            None => budget,
+        }
+    }
+
+    fn end_slot(&mut self) {
+        if let Some(throughput) = self.throughput.as_mut() {
+            let budget = throughput.record(self.slot_handled, self.slot_handler_time);
+            self.metrics.set_message_budget(budget);
+            self.slot_handled = 0;
+            self.slot_handler_time = Duration::from_secs(0);
+        }
+        // the slot made room in the mailbox or stopped the Actor
+        self.mailbox.wake_senders();
+    }
+
+    #[cfg(feature = "replay")]
+    fn last_handled(&self) -> Option<&'static str> {
+        self.last_handled
+    }
+
+    #[cfg(feature = "replay")]
+    fn replay_next(&mut self, type_name: &str) -> ActorState {
+        self.replay_expected = Some(String::from(type_name));
+        let state = self.handle(false);
+        self.replay_expected = None;
+        state
     }
 }
 
 impl<A, P> Executor<A, P>
 where
     A: Actor,
-    P: ActorFactory<A>,
+    P: TryActorFactory<A>,
 {
     pub fn new(
+        actor: Option<A>,
         actor_props: P,
-        actor_address: ActorAddress,
         actor_config: ActorConfig,
         mailbox: Mailbox<A>,
         receiver: Receiver<MessageEnvelope<A>>,
-        system: ActorSystem,
-        actor_ref: ActorWrapper<A>,
+        context: ActorContext<A>,
+        metrics: ActorMetrics,
     ) -> Self {
-
-        let context = ActorContext {
-            actor_ref,
-            system: system.clone(),
+        let actor_address = context.actor_ref.get_address().clone();
+        #[cfg(feature = "replay")]
+        let recorder = context
+            .system
+            .get_recorder()
+            .map(|recorder| (recorder.clone(), ExecutionRecorder::actor_key(&actor_address)));
+        let idle_sleep_after = context.system.get_idle_sleep_after();
+        let max_inheritance_depth = context.system.get_config().general.max_priority_inheritance_depth;
+        let max_captured_bytes = context.system.get_config().general.max_captured_payload_bytes;
+        let max_provenance_hops = context.system.get_config().general.max_provenance_hops;
+        let cycle_detector = match actor_config.cycle_exempt {
+            true => None,
+            false => context.system.get_cycle_detector().cloned(),
         };
-
+        let capture_ring = actor_config.capture_last_n_messages.map(CaptureRing::new);
+        let throughput = actor_config.adaptive_throughput.map(ThroughputController::new);
+        let rate_limiter = actor_config.rate_limit.map(RateLimiter::new);
+        let last_received = Instant::now();
+        // a spawned Actor sleeps until its first message
+        let idle_wakeup = actor_config.idle_timeout.map(|idle_timeout| last_received + idle_timeout);
+        if let Some(deadline) = idle_wakeup {
+            context
+                .actor_ref
+                .get_wakeup_manager()
+                .schedule_wakeup(actor_address.clone(), deadline);
+        }
+        let next_tick = actor_config.tick_interval.map(|tick_interval| last_received + tick_interval);
+        if let Some(deadline) = next_tick {
+            context
+                .actor_ref
+                .get_wakeup_manager()
+                .schedule_wakeup(actor_address.clone(), deadline);
+        }
+        metrics.set_message_budget(
+            throughput
+                .as_ref()
+                .map_or(actor_config.message_throughput, |throughput| throughput.get_budget()),
+        );
+        if let Some(journal) = &mailbox.journal {
+            journal.append(JournalEvent::Spawned);
+        }
+        let slo = actor_config.latency_slo.zip(mailbox.slo_status.clone()).map(|(slo, status)| {
+            SloTracker::new(slo, actor_address.clone(), status, context.system.get_slo_events().clone())
+        });
+        // the snapshot of a previous run is only kept if the user opted in
+        let is_recovering = match &mailbox.recovery {
+            Some(_) if actor_config.recover_after_stop => true,
+            Some(recovery) => {
+                recovery.store.remove(&actor_address);
+                false
+            }
+            None => false,
+        };
+        let is_created = actor.is_some();
+        let is_lazy = !is_created && actor_config.lazy;
         Self {
-            actor: actor_props.new_actor(context.clone()),
+            actor,
+            is_created,
+            is_lazy,
             actor_props,
+            replacement: None,
+            handover: None,
             actor_config,
             mailbox,
             queue: receiver,
@@ -163,7 +544,47 @@ where
             is_startup: true,
             system_triggered_stop: false,
             last_wakeup: Instant::now(),
+            is_wakeup_unused: false,
+            last_worker: None,
             context,
+            metrics,
+            pending: VecDeque::new(),
+            class_schedule: ClassSchedule::new(),
+            retry: None,
+            is_retry: false,
+            suspension: None,
+            recent_restarts: VecDeque::new(),
+            restart_backoff: None,
+            asleep_until: None,
+            has_headers: false,
+            has_sender: false,
+            has_expiry: false,
+            max_inheritance_depth,
+            boosted_chain: None,
+            idle_sleep_after,
+            slo,
+            max_captured_bytes,
+            max_provenance_hops,
+            cycle_detector,
+            capture_ring,
+            throughput,
+            slot_handled: 0,
+            slot_handler_time: Duration::from_secs(0),
+            rate_limiter,
+            rate_limited_until: None,
+            last_received,
+            idle_wakeup,
+            next_tick,
+            since_snapshot: 0,
+            is_recovering,
+            #[cfg(feature = "replay")]
+            recorder,
+            #[cfg(feature = "replay")]
+            last_handled: None,
+            #[cfg(feature = "replay")]
+            replay_expected: None,
+            #[cfg(feature = "replay")]
+            held_urgent: VecDeque::new(),
         }
     }
     pub fn send<M>(&self, msg: M)
@@ -174,3 +595,1119 @@ where
         self.mailbox.msg_in.send(MessageEnvelope::new(msg)).unwrap();
     }
 }
+
+impl<A, P> Executor<A, P>
+where
+    A: Actor + UnwindSafe + 'static,
+    P: TryActorFactory<A>,
+{
+    /// Drops superseded and expired messages on the way, see [ActorBuilder.add_superseding](../prelude/struct.ActorBuilder.html#method.add_superseding) and [ActorWrapper.send_with_ttl](../prelude/struct.ActorWrapper.html#method.send_with_ttl)
+    fn next_message(&mut self) -> Option<MessageEnvelope<A>> {
+        loop {
+            let mut msg = self.next_queued_message()?;
+            if self.is_superseded(&msg) {
+                msg.settle_receipt(Receipt::Superseded);
+                self.metrics.increment_superseded();
+            } else if !self.is_retry && msg.is_expired() {
+                self.expire(msg);
+            } else {
+                return Some(msg);
+            }
+            if let Some(chain) = self.boosted_chain.take() {
+                self.release_boost(chain);
+            }
+        }
+    }
+
+    /// Next message of the priority lane
+    fn next_urgent(&mut self) -> Option<MessageEnvelope<A>> {
+        #[cfg(feature = "replay")]
+        {
+            if let Some(expected) = self.replay_expected.as_deref() {
+                // both lanes interleave by timing while recording, the log decides which lane the message is taken from
+                self.held_urgent.extend(self.mailbox.urgent_out.try_iter());
+                if self.held_urgent.front().is_some_and(|msg| msg.get_type_name() == expected) {
+                    return self.held_urgent.pop_front();
+                }
+                return None;
+            }
+            if let Some(msg) = self.held_urgent.pop_front() {
+                return Some(msg);
+            }
+        }
+        self.mailbox.urgent_out.try_recv().ok()
+    }
+
+    fn expire(&self, mut msg: MessageEnvelope<A>) {
+        msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Expired));
+        self.metrics.increment_expired();
+        let trace_id = trace::of(msg.get_headers());
+        self.mailbox
+            .dead_letters
+            .report_traced(&self.actor_address, msg.get_type_name(), DeadLetterReason::Expired, trace_id);
+    }
+
+    fn is_superseded(&self, msg: &MessageEnvelope<A>) -> bool {
+        self.mailbox
+            .supersession
+            .as_ref()
+            .is_some_and(|supersession| supersession.is_superseded(msg.get_type_id(), msg.get_sequence()))
+    }
+
+    fn next_queued_message(&mut self) -> Option<MessageEnvelope<A>> {
+        self.is_retry = self.retry.is_some();
+        if let Some(msg) = self.retry.take() {
+            return Some(msg);
+        }
+        if let Some(msg) = self.pending.pop_front() {
+            return Some(msg);
+        }
+        let handoff = self.mailbox.handoff.clone();
+        let mut handoff = handoff.lock().unwrap();
+        if !handoff.closed {
+            if let Some(msg) = self.next_urgent() {
+                return Some(msg);
+            }
+            // messages of a request chain with a higher priority overtake the regular mailbox
+            if let Some((chain, msg)) = self.mailbox.priority.pop() {
+                self.boosted_chain = Some(chain);
+                return Some(msg);
+            }
+            if let Ok(msg) = self.mailbox.continuation_out.try_recv() {
+                return Some(msg);
+            }
+            if let Some(msg) = handoff.front.pop_front() {
+                return Some(msg);
+            }
+            return match &self.mailbox.classes {
+                Some(classes) => self.class_schedule.next(classes, &self.queue),
+                None => self.queue.try_recv().ok(),
+            };
+        }
+        // messages that were still sent to this Actor after it has been handed off are forwarded
+        let target = match &handoff.redirect {
+            Redirect::Target(target) => Some(target.as_ref().clone()),
+            _ => None,
+        };
+        drop(handoff);
+        for (chain, msg) in self.mailbox.priority.drain() {
+            if let Some(target) = &target {
+                target.enqueue(msg);
+            }
+            self.release_boost(chain);
+        }
+        while let Some(msg) = self.mailbox.try_recv() {
+            match &target {
+                Some(target) if !is_control_message(&msg) => target.enqueue(msg),
+                _ => return Some(msg),
+            }
+        }
+        None
+    }
+
+    /// Takes `msg` and the messages of the same type that directly follow it out of the regular mailbox, see [ActorBuilder.add_batching](../prelude/struct.ActorBuilder.html#method.add_batching)
+    ///
+    /// The first message that does not belong to the batch stays the next message. Returns an empty batch if `msg` is handled on its own,
+    /// its headers and sender have already been handed to the context
+    fn collect_batch(&mut self, msg: &mut MessageEnvelope<A>) -> Vec<Box<dyn Any + Send>> {
+        let mut batch = Vec::new();
+        // both rely on every envelope being handled on its own
+        #[cfg(feature = "replay")]
+        if self.recorder.is_some() || self.replay_expected.is_some() {
+            return batch;
+        }
+        #[cfg(feature = "testing")]
+        if self.mailbox.interactions.is_some() {
+            return batch;
+        }
+        // every message passes the middlewares on its own
+        if self.mailbox.middlewares.is_some() {
+            return batch;
+        }
+        let first = match msg.take_message() {
+            Some(first) => first,
+            // lazily constructed
+            None => return batch,
+        };
+        batch.push(first);
+        let type_id = msg.get_type_id();
+        // a batch only holds messages of one class, whose queue is the one of the type
+        let queue = self.mailbox.get_lane(type_id).1.clone();
+        let budget = self.get_message_budget();
+        let is_handed_off = {
+            let handoff = self.mailbox.handoff.lock().unwrap();
+            handoff.closed || !handoff.front.is_empty()
+        };
+        if !self.pending.is_empty() || is_handed_off {
+            return batch;
+        }
+        while batch.len() < budget && self.mailbox.urgent_out.is_empty() && !self.mailbox.priority.has_boosted() {
+            let mut next = match queue.try_recv() {
+                Ok(next) => next,
+                Err(_) => break,
+            };
+            if self.is_superseded(&next) {
+                next.settle_receipt(Receipt::Superseded);
+                self.metrics.increment_superseded();
+                continue;
+            }
+            if next.is_expired() {
+                self.expire(next);
+                continue;
+            }
+            let is_batched = next.get_type_id() == type_id && next.get_headers().is_none() && !next.has_reply_path() && !next.has_receipt();
+            if !is_batched {
+                self.pending.push_back(next);
+                break;
+            }
+            match next.take_message() {
+                Some(message) => batch.push(message),
+                None => {
+                    self.pending.push_back(next);
+                    break;
+                }
+            }
+        }
+        batch
+    }
+
+    fn handle_envelope(&mut self, mut msg: MessageEnvelope<A>) -> ActorState {
+        // the message left the mailbox
+        msg.set_pressure(None);
+        msg.set_budget(None);
+        if self.actor_config.idle_timeout.is_some() {
+            self.last_received = Instant::now();
+        }
+        let is_builtin = message_registry::is_builtin::<A>(msg.get_type_id());
+        if !is_builtin && !self.mailbox.accepted_messages.accepts(msg.get_type_id()) {
+            msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Unregistered));
+            self.mailbox
+                .dead_letters
+                .report_with_reason(&self.actor_address, msg.get_type_name(), DeadLetterReason::Unregistered);
+            return ActorState::Running;
+        }
+        let retry = match (&self.mailbox.retrying, self.actor_config.failed_message_strategy) {
+            // a retry that fails again is dropped
+            (Some(retrying), FailedMessageStrategy::RetryOnce) if !self.is_retry => retrying.copy(&msg),
+            _ => None,
+        };
+        #[cfg(feature = "replay")]
+        {
+            let type_name = msg.get_type_name();
+            // recorded before the message is handled, so that the log order respects all sends of the handler
+            if let Some((recorder, key)) = &self.recorder {
+                recorder.record_handled(key, type_name);
+            }
+            self.last_handled = Some(type_name);
+        }
+        let mut msg_headers = msg.take_headers();
+        let mut propagated = None;
+        if msg_headers.is_some() {
+            let registry = self.context.system.get_header_registry();
+            propagated = msg_headers.as_ref().and_then(|h| registry.propagate(h));
+        }
+        let mut propagated = priority::originate(propagated, self.actor_config.priority, self.max_inheritance_depth);
+        let is_cycle_suspected = match &self.cycle_detector {
+            Some(detector) => detector.begin_handle(msg_headers.as_deref(), &self.actor_address, &mut propagated),
+            None => false,
+        };
+        let is_audited = self.context.system.get_audit_registry().is_audited(msg.get_type_id());
+        if is_audited {
+            let headers = msg_headers.get_or_insert_with(Default::default);
+            let mut provenance = provenance::receive(headers);
+            let hop = ProvenanceHop {
+                actor: self.actor_address.clone(),
+                at: SystemTime::now(),
+                action: ProvenanceAction::Handled,
+            };
+            provenance.push(hop, self.max_provenance_hops);
+            headers.insert(PROVENANCE_HEADER.1, provenance.to_header());
+            let derivation = provenance::derivation(provenance.message_id, &self.actor_address);
+            propagated.get_or_insert_with(Headers::new).insert(DERIVED_HEADER.1, derivation);
+        }
+        let has_headers = msg_headers.is_some();
+        let is_propagating = propagated.is_some();
+        let is_size_checking = self.context.system.get_message_sizes().is_checking();
+        if is_size_checking {
+            message_size::set_sender(Some(self.actor_address.clone()));
+        }
+        if is_propagating {
+            headers::set_propagated(propagated);
+        }
+        // the headers of the context can not be borrowed while the middlewares are executed
+        let meta_headers = if self.mailbox.middlewares.is_some() { msg_headers.clone() } else { None };
+        if has_headers || self.has_headers {
+            self.context.set_headers(msg_headers);
+            self.has_headers = has_headers;
+        }
+        let sender = msg.take_reply_path();
+        if sender.is_some() || self.has_sender {
+            self.has_sender = sender.is_some();
+            self.context.set_sender(sender);
+        }
+        let expires_at = msg.get_expires_at();
+        if expires_at.is_some() || self.has_expiry {
+            self.has_expiry = expires_at.is_some();
+            self.context.set_expires_at(expires_at);
+        }
+        let middlewares = self.mailbox.middlewares.clone().filter(|_| !is_control_message(&msg));
+        let meta = MessageMeta {
+            message_type: msg.get_type_name(),
+            enqueued: msg.get_enqueued(),
+            expires_at,
+            headers: meta_headers.as_deref(),
+        };
+        let mut passed = 0;
+        let mut middleware_panic = None;
+        if let Some(middlewares) = &middlewares {
+            match panic_handler::catch(|| middlewares.before(&meta, &self.context, &mut passed)) {
+                Ok(MiddlewareDecision::Continue) => {}
+                Ok(decision) => {
+                    msg.settle_receipt(Receipt::Dropped(DeadLetterReason::Rejected));
+                    if is_cycle_suspected {
+                        CycleDetector::end_handle();
+                    }
+                    if is_propagating {
+                        headers::set_propagated(None);
+                    }
+                    if is_size_checking {
+                        message_size::set_sender(None);
+                    }
+                    if decision == MiddlewareDecision::DeadLetter {
+                        let trace_id = self.context.trace_id();
+                        self.mailbox.dead_letters.report_traced(
+                            &self.actor_address,
+                            meta.message_type,
+                            DeadLetterReason::Rejected,
+                            trace_id,
+                        );
+                    }
+                    return ActorState::Running;
+                }
+                Err(panic) => middleware_panic = Some(panic),
+            }
+        }
+        let captured = if self.max_captured_bytes > 0 {
+            msg.capture_payload(self.context.system.get_payload_capture(), self.max_captured_bytes)
+        } else {
+            None
+        };
+        #[cfg(feature = "testing")]
+        if let Some(interactions) = &self.mailbox.interactions {
+            interactions.begin_handle(&self.actor_address, &msg);
+        }
+        let bracket = self.mailbox.captures.as_ref().and_then(|captures| {
+            captures
+                .around(msg.get_type_name())
+                .map(|message_type| (captures.clone(), message_type))
+        });
+        if let Some((captures, message_type)) = &bracket {
+            let _ = captures.capture(self.get_actor(), format!("before {}", message_type));
+        }
+        let dispatch = self.mailbox.batching.as_ref().and_then(|batching| batching.get(msg.get_type_id()));
+        let batch = match &dispatch {
+            Some(_) if !has_headers && !self.has_sender => self.collect_batch(&mut msg),
+            _ => Vec::new(),
+        };
+        let batch_len = batch.len();
+        let instrumentation = self.mailbox.instrumentation.get();
+        if let Some(hook) = &instrumentation {
+            let queue_latency = msg.get_enqueued().map(|enqueued| enqueued.elapsed());
+            hook.on_handle_start(&self.actor_address, msg.get_type_name(), queue_latency);
+        }
+        let started = Instant::now();
+        let mut result = match middleware_panic {
+            // handled like a panic of the handler
+            Some(panic) => Err(panic),
+            None => panic_handler::catch(|| match dispatch {
+                Some(dispatch) if batch_len > 0 => {
+                    dispatch(self.actor.as_mut().unwrap(), batch, &self.context);
+                    MessageType::Other
+                }
+                _ => msg.handle(self.actor.as_mut().unwrap(), &self.context),
+            }),
+        };
+        if is_cycle_suspected {
+            CycleDetector::end_handle();
+        }
+        if let (Some((captures, message_type)), Ok(_)) = (&bracket, &result) {
+            let _ = captures.capture(self.get_actor(), format!("after {}", message_type));
+        }
+        #[cfg(feature = "testing")]
+        if let Some(interactions) = &self.mailbox.interactions {
+            interactions.end_handle();
+        }
+        let now = Instant::now();
+        self.metrics.set_last_activity(SystemTime::now());
+        let construction_time = msg.get_construction_time();
+        self.metrics.add_construction_time(construction_time);
+        let handler_time = now.saturating_duration_since(started).saturating_sub(construction_time);
+        self.metrics.add_handler_time(handler_time);
+        if let Some(threshold) = self.actor_config.slow_handler_threshold {
+            if handler_time > threshold {
+                self.metrics.increment_slow_handlers();
+                self.context.system.handle_slow_handler(SlowHandler {
+                    address: self.actor_address.clone(),
+                    message_type: msg.get_type_name(),
+                    duration: handler_time,
+                    threshold,
+                });
+            }
+        }
+        if let Some(middlewares) = &middlewares {
+            let outcome = match &result {
+                Err(_) => HandleOutcome::Panicked,
+                Ok(_) if self.context.has_failure() => HandleOutcome::Failed,
+                Ok(_) => HandleOutcome::Handled,
+            };
+            let after = panic_handler::catch(|| middlewares.after(passed, &meta, handler_time, outcome));
+            if let (Err(panic), Ok(_)) = (after, &result) {
+                result = Err(panic);
+            }
+        }
+        msg.settle_receipt(match &result {
+            Ok(_) => Receipt::Handled,
+            Err(_) => Receipt::Panicked,
+        });
+        if result.is_ok() && !is_builtin {
+            self.mailbox.accepted_messages.record(msg.get_type_id(), msg.get_type_name());
+        }
+        if let Some(hook) = &instrumentation {
+            match &result {
+                Ok(_) => hook.on_handle_end(&self.actor_address, msg.get_type_name(), handler_time),
+                Err(_) => hook.on_panic(&self.actor_address, msg.get_type_name()),
+            }
+        }
+        if self.throughput.is_some() {
+            self.slot_handled += batch_len.max(1);
+            self.slot_handler_time += handler_time;
+        }
+        if let Some(rate_limiter) = self.rate_limiter.as_mut() {
+            rate_limiter.record(batch_len.max(1), started);
+        }
+        if let Some(slo) = self.slo.as_mut() {
+            let since = msg.get_enqueued().unwrap_or(started);
+            slo.record(now.saturating_duration_since(since), msg.get_type_name(), now);
+        }
+        if is_propagating {
+            headers::set_propagated(None);
+        }
+        if is_size_checking {
+            message_size::set_sender(None);
+        }
+        if let Err(panic) = &result {
+            let panic_message = panic_report::panic_message(panic.as_ref());
+            self.notify_panic(msg.get_type_name(), &panic_message);
+            let report = ActorPanicReport {
+                address: self.actor_address.clone(),
+                message_type: msg.get_type_name(),
+                panic_message,
+                error: None,
+                payload: captured,
+                recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+                provenance: if is_audited { self.context.provenance() } else { None },
+                trace_id: self.context.trace_id(),
+                journal: self.journal_tail(),
+            };
+            let state = self.restart_or_stop(report);
+            return self.apply_failed_message_strategy(msg.get_type_name(), batch_len, retry, state);
+        }
+        if let Some(error) = self.context.take_failure() {
+            println!("ACTOR FAILURE: {}", error.message);
+            let report = ActorPanicReport {
+                address: self.actor_address.clone(),
+                message_type: msg.get_type_name(),
+                panic_message: None,
+                error: Some(error),
+                payload: captured,
+                recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+                provenance: if is_audited { self.context.provenance() } else { None },
+                trace_id: self.context.trace_id(),
+                journal: self.journal_tail(),
+            };
+            let state = self.restart_or_stop(report);
+            return self.apply_failed_message_strategy(msg.get_type_name(), batch_len, retry, state);
+        }
+        let message_type = result.unwrap();
+        match self.context.take_result() {
+            Some(ActorResult::Stop) => {
+                self.context.actor_ref.stop();
+            }
+            Some(ActorResult::StopImmediately) => {
+                self.context.actor_ref.stop_now();
+            }
+            Some(ActorResult::Sleep(duration)) => {
+                let until = Instant::now() + duration;
+                self.asleep_until = Some(until);
+                self.context
+                    .actor_ref
+                    .get_wakeup_manager()
+                    .schedule_wakeup(self.actor_address.clone(), until);
+            }
+            Some(ActorResult::Ok) | None => {}
+        }
+        if self.mailbox.stash.take_unstash_request() {
+            self.unstash();
+        }
+        if let (Some(ring), Some(payload)) = (self.capture_ring.as_mut(), captured) {
+            ring.push(payload);
+        }
+        for _ in 0..batch_len.max(1) {
+            self.metrics.increment_processed();
+        }
+        if self.mailbox.recovery.is_some() && self.actor_config.snapshot_interval > 0 {
+            self.since_snapshot += batch_len.max(1);
+            if self.since_snapshot >= self.actor_config.snapshot_interval {
+                self.since_snapshot = 0;
+                self.save_snapshot();
+            }
+        }
+        if message_type == MessageType::ActorStopMessage {
+            self.mailbox.flags.is_stopped.store(true, Ordering::Relaxed);
+            return ActorState::Running;
+        }
+        if let Some(factory) = self.context.take_replacement() {
+            self.replace_actor(factory);
+        }
+        // the worker of the previous pool lets go of the Actor, the wakeup queues it in the new one
+        if self.context.take_relocation() {
+            self.mailbox.flags.is_sleeping.store(true, Ordering::Relaxed);
+            return ActorState::Sleeping;
+        }
+
+        ActorState::Running
+    }
+
+    /// Replaces the Actor after a panic according to its [RestartPolicy] and [PanicEscalation], or ends it
+    fn restart_or_stop(&mut self, report: ActorPanicReport) -> ActorState {
+        self.metrics.increment_panics();
+        // the factory might have failed to create the Actor
+        if let Some(actor) = self.actor.as_mut() {
+            actor.on_panic(report.clone());
+            actor.post_stop();
+        }
+        // a suspension requested by the crashed handler is dropped together with the Actor
+        self.context.take_suspension_request();
+        // the replacement starts with its Handlers
+        self.context.clear_behaviors();
+        if self.actor_config.panic_escalation == PanicEscalation::Abort {
+            self.abort();
+        }
+
+        // a requested extraction ends the Actor regardless of its restart policy
+        let is_extraction_requested = self.mailbox.final_state.lock().unwrap().is_extraction_requested();
+        let backoff = match (self.actor_config.panic_escalation, self.actor_config.restart_policy) {
+            (PanicEscalation::Restart, RestartPolicy::Never) => None,
+            (PanicEscalation::Restart, RestartPolicy::Always) => Some(Duration::from_secs(0)),
+            (
+                PanicEscalation::Restart,
+                RestartPolicy::Limited {
+                    max_restarts,
+                    within,
+                    backoff,
+                },
+            ) => self.limit_restart(max_restarts, within, backoff),
+            _ => None,
+        };
+        if backoff.is_none() || self.is_stopped() || is_extraction_requested {
+            self.mailbox.flags.is_stopped.store(true, Ordering::Relaxed);
+            self.clear_pressure();
+            self.actor = None;
+            self.finish(Err(report));
+            if let PanicEscalation::StopSystem { graceful } = self.actor_config.panic_escalation {
+                self.context.system.get_state().set_panic_stopped();
+                self.context.system.stop(graceful);
+            }
+            return ActorState::Stopped;
+        }
+        if let Some(actor) = self.actor.as_mut() {
+            actor.pre_restart();
+        }
+        self.context.reseed_rng(self.actor_config.rng_reseed, self.metrics.get_restarts() + 1);
+        // the restarted Actor cannot know about the messages the crashed one stashed
+        self.mailbox.stash.take_unstash_request();
+        self.unstash();
+        self.flush_queued();
+        // created by the next slot, once the backoff passed
+        self.actor = None;
+        self.is_created = false;
+        self.is_recovering = self.mailbox.recovery.is_some();
+        self.since_snapshot = 0;
+        self.journal(JournalEvent::Restarted {
+            message_type: report.message_type,
+            reason: report.panic_message.or(report.error.map(|error| error.message)),
+        });
+        self.metrics.increment_restarts();
+        self.asleep_until = None;
+        if let Some(backoff) = backoff.filter(|backoff| *backoff > Duration::from_secs(0)) {
+            self.restart_backoff = Some(Instant::now() + backoff);
+        }
+        // the Executor stays the single entry of the Actor in the wakeup loop, only the wakeups the crashed Actor left behind are dropped
+        let now = Instant::now();
+        // ticks pause during the backoff
+        self.next_tick = self
+            .actor_config
+            .tick_interval
+            .map(|tick_interval| self.restart_backoff.unwrap_or(now) + tick_interval);
+        let deadlines = [
+            self.restart_backoff,
+            self.idle_wakeup,
+            self.next_tick,
+            self.rate_limited_until,
+            self.suspension.as_ref().map(|suspension| suspension.deadline),
+            self.context.get_request_chains().next_deadline(),
+        ];
+        self.context.actor_ref.get_wakeup_manager().replace_scheduled_wakeups(
+            self.actor_address.clone(),
+            deadlines.iter().flatten().copied().filter(|deadline| *deadline > now).collect(),
+        );
+        if let Some(throughput) = self.throughput.as_mut() {
+            throughput.reset();
+            self.metrics.set_message_budget(throughput.get_budget());
+            self.slot_handled = 0;
+            self.slot_handler_time = Duration::from_secs(0);
+        }
+        self.is_startup = true;
+        ActorState::Running
+    }
+
+    /// Handles the message whose handler panicked or failed, every message of a failed batch counts
+    fn apply_failed_message_strategy(
+        &mut self,
+        type_name: &'static str,
+        batch_len: usize,
+        retry: Option<MessageEnvelope<A>>,
+        state: ActorState,
+    ) -> ActorState {
+        match self.actor_config.failed_message_strategy {
+            FailedMessageStrategy::Drop => {}
+            // the batch has been handed over as a whole, its messages cannot be copied
+            FailedMessageStrategy::RetryOnce if batch_len > 0 => {}
+            FailedMessageStrategy::RetryOnce => {
+                if state == ActorState::Running {
+                    self.retry = retry;
+                }
+            }
+            FailedMessageStrategy::SendToDeadLetters => {
+                let trace_id = self.context.trace_id();
+                for _ in 0..batch_len.max(1) {
+                    self.mailbox.dead_letters.report_traced(
+                        &self.actor_address,
+                        type_name,
+                        DeadLetterReason::Undeliverable,
+                        trace_id,
+                    );
+                }
+            }
+        }
+        state
+    }
+
+    /// Ends the current Actor without a panic, the next slot creates its replacement through `factory` and keeps handling the same mailbox
+    fn replace_actor(&mut self, factory: Replacement<A>) {
+        if let Some(actor) = self.actor.as_mut() {
+            self.handover = actor.handover();
+            actor.post_stop();
+        }
+        self.context.clear_behaviors();
+        self.replacement = Some(factory);
+        self.actor = None;
+        self.is_created = false;
+        self.is_startup = true;
+    }
+
+    /// Flushes the messages that are queued when the Actor restarts according to its [RestartMailboxPolicy], internal messages are kept
+    fn flush_queued(&mut self) {
+        let keep_last = match self.actor_config.restart_mailbox_policy {
+            RestartMailboxPolicy::Keep => return,
+            RestartMailboxPolicy::Flush => 0,
+            RestartMailboxPolicy::KeepLast(keep_last) => keep_last,
+        };
+        // messages sent from here on are left for the restarted Actor
+        let queued = self.mailbox.len();
+        let mut taken: Vec<MessageEnvelope<A>> = self.pending.drain(..).collect();
+        for (chain, msg) in self.mailbox.priority.drain() {
+            self.release_boost(chain);
+            taken.push(msg);
+        }
+        taken.extend(self.mailbox.handoff.lock().unwrap().front.drain(..));
+        taken.extend((0..queued).map_while(|_| self.mailbox.try_recv()));
+
+        let is_builtin = |msg: &MessageEnvelope<A>| message_registry::is_builtin::<A>(msg.get_type_id());
+        let mut to_flush = taken.iter().filter(|msg| !is_builtin(msg)).count().saturating_sub(keep_last);
+        for mut msg in taken {
+            if to_flush == 0 || is_builtin(&msg) {
+                self.pending.push_back(msg);
+                continue;
+            }
+            to_flush -= 1;
+            msg.settle_receipt(Receipt::Dropped(DeadLetterReason::FlushedOnRestart));
+            let trace_id = trace::of(msg.get_headers());
+            self.mailbox
+                .dead_letters
+                .report_traced(&self.actor_address, msg.get_type_name(), DeadLetterReason::FlushedOnRestart, trace_id);
+        }
+    }
+
+    /// Records the restart and returns its backoff, `None` if the Actor already restarted `max_restarts` times within the window
+    fn limit_restart(&mut self, max_restarts: usize, within: Duration, backoff: BackoffStrategy) -> Option<Duration> {
+        let now = Instant::now();
+        while self
+            .recent_restarts
+            .front()
+            .is_some_and(|restart| now.duration_since(*restart) >= within)
+        {
+            self.recent_restarts.pop_front();
+        }
+        if self.recent_restarts.len() >= max_restarts {
+            return None;
+        }
+        let delay = backoff.delay(self.recent_restarts.len());
+        self.recent_restarts.push_back(now);
+        Some(delay)
+    }
+
+    /// Whether the Actor used all permits of the current window of its rate limit, schedules its wakeup for the next one
+    fn is_rate_limited(&mut self) -> bool {
+        let until = match self.rate_limiter.as_ref().and_then(|limiter| limiter.get_exhausted_until(Instant::now())) {
+            Some(until) => until,
+            None => return false,
+        };
+        // sends wake the Actor up in the meantime as well
+        if self.rate_limited_until != Some(until) {
+            self.rate_limited_until = Some(until);
+            self.context
+                .actor_ref
+                .get_wakeup_manager()
+                .schedule_wakeup(self.actor_address.clone(), until);
+        }
+        true
+    }
+
+    /// `true` once the idle timeout of the Actor passed, schedules the wakeup for the deadline otherwise
+    fn is_idle(&mut self) -> bool {
+        let deadline = match self.actor_config.idle_timeout {
+            Some(idle_timeout) => self.last_received + idle_timeout,
+            None => return false,
+        };
+        if Instant::now() >= deadline {
+            return !self.mailbox.is_stop_requested();
+        }
+        // sends wake the Actor up in the meantime and move the deadline, the earlier wakeup only finds a later deadline
+        if self.idle_wakeup != Some(deadline) {
+            self.idle_wakeup = Some(deadline);
+            self.context
+                .actor_ref
+                .get_wakeup_manager()
+                .schedule_wakeup(self.actor_address.clone(), deadline);
+        }
+        false
+    }
+
+    /// Executes `on_idle_stop` and stops the Actor gracefully, a message sent in the meantime is handled before the stop message
+    fn stop_idle(&mut self) {
+        let actor = self.actor.as_mut().unwrap();
+        if let Err(panic) = panic_handler::catch(|| actor.on_idle_stop()) {
+            let panic_message = panic_report::panic_message(panic.as_ref());
+            self.notify_panic("on_idle_stop", &panic_message);
+        }
+        self.context.actor_ref.stop();
+    }
+
+    fn is_tick_due(&self) -> bool {
+        self.next_tick.is_some_and(|due| Instant::now() >= due) && !self.mailbox.is_stop_requested()
+    }
+
+    /// Executes `on_tick` if it is due and schedules the wakeup for the next one, returns the state of the Actor if the tick panicked
+    fn tick(&mut self) -> Option<ActorState> {
+        if !self.is_tick_due() {
+            return None;
+        }
+        let (due, tick_interval) = self.next_tick.zip(self.actor_config.tick_interval)?;
+        let now = Instant::now();
+        // missed ticks are skipped
+        let mut next_tick = due + tick_interval;
+        if next_tick <= now {
+            next_tick = now + tick_interval;
+        }
+        self.next_tick = Some(next_tick);
+        self.context
+            .actor_ref
+            .get_wakeup_manager()
+            .schedule_wakeup(self.actor_address.clone(), next_tick);
+        let actor = self.actor.as_mut().unwrap();
+        let context = &self.context;
+        let panic = panic_handler::catch(|| actor.on_tick(context)).err()?;
+        let panic_message = panic_report::panic_message(panic.as_ref());
+        self.notify_panic("on_tick", &panic_message);
+        let report = ActorPanicReport {
+            address: self.actor_address.clone(),
+            message_type: "on_tick",
+            panic_message,
+            error: None,
+            payload: None,
+            recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+            provenance: None,
+            trace_id: None,
+            journal: self.journal_tail(),
+        };
+        Some(self.restart_or_stop(report))
+    }
+
+    /// A daemon is not stopped by anyone else once the system stop has been delivered, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon)
+    fn stop_daemon(&self) {
+        if self.actor_config.daemon {
+            self.context.actor_ref.stop();
+        }
+    }
+
+    /// A stop request ends the backoff early, the restarted Actor executes `pre_start` and handles the stop
+    fn is_asleep(&self) -> bool {
+        self.asleep_until.is_some_and(|until| Instant::now() < until) && !self.mailbox.is_stop_requested()
+    }
+
+    fn is_backing_off(&self) -> bool {
+        self.restart_backoff.is_some_and(|until| Instant::now() < until) && !self.mailbox.is_stop_requested()
+    }
+
+    /// Executes `post_stop` and ends the Actor, a panic within `post_stop` ends up in the [ActorPanicReport] of the termination
+    fn stop_actor(&mut self) {
+        self.save_snapshot();
+        let mut actor = self.actor.take().unwrap();
+        let result = match panic_handler::catch(|| actor.post_stop()) {
+            Ok(()) => Ok(actor),
+            Err(panic) => {
+                let panic_message = panic_report::panic_message(panic.as_ref());
+                self.notify_panic("post_stop", &panic_message);
+                Err(ActorPanicReport {
+                    address: self.actor_address.clone(),
+                    message_type: "post_stop",
+                    panic_message,
+                    error: None,
+                    payload: None,
+                    recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+                    provenance: None,
+                    trace_id: None,
+                    journal: self.journal_tail(),
+                })
+            }
+        };
+        self.clear_pressure();
+        self.finish(result);
+    }
+
+    /// Takes a recovery snapshot, a panic keeps the previous snapshot, see [ActorBuilder.set_recovery](../prelude/struct.ActorBuilder.html#method.set_recovery)
+    fn save_snapshot(&mut self) {
+        if let (Some(recovery), Some(actor)) = (&self.mailbox.recovery, &self.actor) {
+            if catch_unwind(AssertUnwindSafe(|| recovery.save(&self.actor_address, actor))).is_err() {
+                println!("ACTOR SNAPSHOT PANIC");
+            }
+        }
+    }
+
+    /// Stops an Actor that has never been released, `pre_start` and `post_stop` are not executed and queued messages are dropped
+    fn stop_unreleased(&mut self) -> ActorState {
+        self.mailbox.flags.is_stopped.store(true, Ordering::Relaxed);
+        self.drop_queued();
+        self.clear_pressure();
+        match self.actor.take() {
+            Some(actor) => self.finish(Ok(actor)),
+            None => self.finish_uncreated(),
+        }
+        ActorState::Stopped
+    }
+
+    fn drop_queued(&mut self) {
+        self.retry = None;
+        self.pending.clear();
+        for (chain, _) in self.mailbox.priority.drain() {
+            self.release_boost(chain);
+        }
+        self.mailbox.handoff.lock().unwrap().front.clear();
+        while self.mailbox.try_recv().is_some() {}
+    }
+
+    /// Keeps a lazy Actor uncreated until its first message, returns `None` once it is to be created
+    ///
+    /// An Actor that has not received anything but the stop message is stopped without creating it
+    fn defer_creation(&mut self, system_is_stopping: bool) -> Option<ActorState> {
+        let stop_messages = usize::from(self.mailbox.is_stop_requested());
+        let is_untouched = self.pending.is_empty()
+            && self.mailbox.is_regular_empty()
+            && self.mailbox.urgent_in.len() <= stop_messages;
+        if !is_untouched {
+            return None;
+        }
+        if system_is_stopping || self.is_stopped() || self.mailbox.is_stop_requested() {
+            self.is_created = true;
+            return Some(self.stop_unreleased());
+        }
+        // a sender that sees the mark wakes the Actor up again
+        if !self.mailbox.mark_sleeping() {
+            return None;
+        }
+        Some(ActorState::Sleeping)
+    }
+
+    /// Marks the Actor ready once [Actor.is_ready](../prelude/trait.Actor.html#method.is_ready) returned `true`, see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    fn update_readiness(&self) {
+        if self.mailbox.is_ready() || !self.actor.as_ref().is_some_and(|actor| actor.is_ready()) {
+            return;
+        }
+        if self.mailbox.mark_ready() {
+            self.context.system.get_state().notify_actor_ready(&self.actor_address);
+        }
+    }
+
+    /// Creates the Actor through its factory, returns `None` once it has been created
+    ///
+    /// A panic of the factory is handled like a returned error, the Actor is restarted or stopped according to its [RestartPolicy]
+    fn create_actor(&mut self) -> Option<ActorState> {
+        let props: &dyn TryActorFactory<A> = match &self.replacement {
+            Some(replacement) => replacement.as_ref(),
+            None => &self.actor_props,
+        };
+        let context = self.context.clone();
+        let handover = self.handover.take();
+        let (panic_message, error) = match panic_handler::catch(|| {
+            let mut actor = props.try_new_actor(context)?;
+            if let Some(state) = handover {
+                actor.receive_handover(state);
+            }
+            Ok::<A, ActorInitError>(actor)
+        }) {
+            Ok(Ok(actor)) => {
+                self.actor = Some(actor);
+                return None;
+            }
+            Ok(Err(error)) => {
+                println!("ACTOR INIT FAILURE: {}", error.message);
+                (None, Some(ActorError::new(error.message)))
+            }
+            Err(panic) => {
+                let panic_message = panic_report::panic_message(panic.as_ref());
+                self.notify_panic("new_actor", &panic_message);
+                (panic_message, None)
+            }
+        };
+        let report = ActorPanicReport {
+            address: self.actor_address.clone(),
+            message_type: "new_actor",
+            panic_message,
+            error,
+            payload: None,
+            recent: self.capture_ring.as_mut().map_or_else(Vec::new, |ring| ring.take()),
+            provenance: None,
+            trace_id: None,
+            journal: self.journal_tail(),
+        };
+        Some(self.restart_or_stop(report))
+    }
+
+    /// Ends the process after a panic with [PanicEscalation::Abort], the journal, the metrics and the shutdown hooks are flushed first
+    fn abort(&mut self) -> ! {
+        println!("ACTOR PANIC ABORT: {:?}", self.actor_address);
+        self.close();
+        self.context.system.get_state().execute_shutdown_hooks();
+        let _ = std::io::stdout().flush();
+        std::process::abort();
+    }
+
+    /// Hands a caught panic to the panic handler of the system, see [ActorSystem.set_panic_handler](../prelude/struct.ActorSystem.html#method.set_panic_handler)
+    fn notify_panic(&self, message_type: &'static str, panic_message: &Option<String>) {
+        self.context.system.handle_panic(PanicInfoContext {
+            address: self.actor_address.clone(),
+            pool_name: self.actor_config.pool_name.clone(),
+            restarts: self.metrics.get_restarts(),
+            message_type,
+            payload: panic_message.clone(),
+            location: panic_handler::take_location(),
+        });
+    }
+
+    fn get_actor(&mut self) -> &mut A {
+        self.actor.as_mut().unwrap()
+    }
+
+    /// Hands the final state to everything that waits for it, see [ActorWrapper.stop_and_extract](../prelude/struct.ActorWrapper.html#method.stop_and_extract)
+    fn finish(&mut self, result: Result<A, ActorPanicReport>) {
+        self.close();
+        let (callbacks, extraction, watchers) = self.mailbox.final_state.lock().unwrap().take();
+        extraction::deliver(result, callbacks, extraction, watchers);
+    }
+
+    /// Same as [finish](#method.finish) for an Actor on a dedicated thread that has never been created
+    fn finish_uncreated(&mut self) {
+        self.close();
+        let (_, extraction, watchers) = self.mailbox.final_state.lock().unwrap().take();
+        extraction::deliver_uncreated(extraction, watchers);
+    }
+
+    /// Moves all stashed messages ahead of everything that is queued, keeping their order
+    fn unstash(&mut self) {
+        for msg in self.mailbox.stash.take().into_iter().rev() {
+            self.pending.push_front(msg);
+        }
+    }
+
+    fn close(&mut self) {
+        // messages that have never been unstashed are not lost silently
+        for msg in self.mailbox.stash.take() {
+            let trace_id = trace::of(msg.get_headers());
+            self.mailbox.dead_letters.report_traced(
+                &self.actor_address,
+                msg.get_type_name(),
+                DeadLetterReason::Undeliverable,
+                trace_id,
+            );
+        }
+        self.metrics.flush_local();
+        self.context.get_request_chains().clear();
+        if let Some(journal) = &self.mailbox.journal {
+            journal.close();
+        }
+        self.mailbox.emitter.stop();
+    }
+
+    fn journal(&self, event: JournalEvent) {
+        if let Some(journal) = &self.mailbox.journal {
+            journal.append(event);
+        }
+    }
+
+    fn journal_tail(&self) -> Vec<JournalEntry> {
+        self.mailbox
+            .journal
+            .as_ref()
+            .map_or_else(Vec::new, |journal| journal.tail(PANIC_REPORT_JOURNAL_ENTRIES))
+    }
+
+    /// Sheddable Actors do not handle messages while the system is in the Critical memory mode, unless it is stopping
+    fn is_paused(&self, system_is_stopping: bool) -> bool {
+        !system_is_stopping && self.mailbox.get_memory_mode() == MemoryMode::Critical
+    }
+
+    fn clear_pressure(&self) {
+        if let Some(pressure) = &self.mailbox.pressure {
+            pressure.clear();
+        }
+    }
+
+    fn release_boost(&mut self, chain: u64) {
+        if let Some(boosted) = self.mailbox.priority.release(chain) {
+            self.metrics.add_boosted_time(boosted);
+        }
+    }
+
+    fn start_requested_suspension(&mut self) {
+        let request = match self.context.take_suspension_request() {
+            Some(request) => request,
+            None => return,
+        };
+        if self.is_stopped() {
+            return;
+        }
+        let now = Instant::now();
+        let deadline = now + self.actor_config.max_suspension;
+        let wakeup_manager = self.context.actor_ref.get_wakeup_manager();
+        wakeup_manager.schedule_wakeup(self.actor_address.clone(), deadline);
+        if let SuspendCondition::UntilActorStarted(target) = &request.condition {
+            let wakeup_manager = wakeup_manager.clone();
+            let address = self.actor_address.clone();
+            self.context.system.get_state().notify_watcher_when_active(
+                target,
+                self.actor_address.clone(),
+                Box::new(move || wakeup_manager.wakeup(address)),
+            );
+        }
+        self.journal(JournalEvent::Suspended);
+        self.suspension = Some(ActiveSuspension {
+            condition: request.condition,
+            token: request.token,
+            started: now,
+            deadline,
+        });
+    }
+
+    /// The suspension no longer waits for its target, e.g. because it timed out
+    fn cancel_activation_watch(&self, suspension: &ActiveSuspension) {
+        if let SuspendCondition::UntilActorStarted(target) = &suspension.condition {
+            self.context
+                .system
+                .get_state()
+                .cancel_activation_watch(target, &self.actor_address);
+        }
+    }
+
+    fn is_condition_met(&self, suspension: &ActiveSuspension) -> bool {
+        if suspension.token.is_resumed() {
+            return true;
+        }
+        match &suspension.condition {
+            SuspendCondition::UntilActorStarted(target) => {
+                self.context.system.get_state().is_actor_active(target)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_suspension_resolved(&self, suspension: &ActiveSuspension) -> bool {
+        self.is_condition_met(suspension) || suspension.deadline <= Instant::now()
+    }
+
+    /// Buffers the mailbox while suspended, returns `None` once the suspension has ended
+    fn handle_suspension(&mut self) -> Option<ActorState> {
+        let awaited = match &self.suspension.as_ref().unwrap().condition {
+            SuspendCondition::UntilMessage(type_id) => Some(*type_id),
+            _ => None,
+        };
+        let mut awaited_msg = None;
+        let mut stop_requested = false;
+        while let Some(msg) = self.mailbox.try_recv() {
+            let type_id = msg.get_type_id();
+            if type_id == TypeId::of::<SystemStopMessage>() {
+                let state = self.handle_envelope(msg);
+                if state != ActorState::Running {
+                    if let Some(suspension) = self.suspension.as_ref() {
+                        self.cancel_activation_watch(suspension);
+                    }
+                    return Some(state);
+                }
+                continue;
+            }
+            if type_id == TypeId::of::<ActorStopMessage>() {
+                stop_requested = true;
+            } else if awaited_msg.is_none() && awaited == Some(type_id) {
+                awaited_msg = Some(msg);
+                continue;
+            }
+            self.pending.push_back(msg);
+        }
+
+        let suspension = self.suspension.as_ref().unwrap();
+        let reason = if stop_requested || self.is_stopped() {
+            None
+        } else if awaited_msg.is_some() || self.is_condition_met(suspension) {
+            Some(SuspensionEndReason::Resumed)
+        } else if suspension.deadline <= Instant::now() {
+            Some(SuspensionEndReason::TimedOut)
+        } else {
+            if !self.mailbox.mark_sleeping() {
+                return Some(ActorState::Running);
+            }
+            return Some(ActorState::Suspended);
+        };
+
+        let suspension = self.suspension.take().unwrap();
+        self.cancel_activation_watch(&suspension);
+        self.metrics.add_suspended_time(suspension.started.elapsed());
+        if let Some(reason) = reason {
+            self.journal(JournalEvent::SuspensionEnded(reason));
+            self.pending.push_front(MessageEnvelope::new(SuspensionEndedMessage { reason }));
+        }
+        if let Some(msg) = awaited_msg {
+            self.pending.push_front(msg);
+        }
+        None
+    }
+}
@@ -0,0 +1,141 @@
+use crate::actor::panic_report::ActorPanicReport;
+use crossbeam_channel::Sender;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtractError {
+    /// the Actor panicked after the extraction has been requested, either while handling a message or in `post_stop`
+    Panicked(Box<ActorPanicReport>),
+    /// the Actor did not stop in time, it keeps stopping but its final state is dropped
+    Timeout,
+    /// the final state has already been requested through another call
+    AlreadyRequested,
+    /// the Actor stopped before the request
+    Stopped,
+}
+
+pub(crate) type FinalStateCallback<A> = Box<dyn FnOnce(&A) + Send>;
+pub(crate) type Extraction<A> = Sender<Result<A, ExtractError>>;
+/// receives the report of the panic that ended the Actor, `None` if it stopped
+pub(crate) type TerminationWatcher = Box<dyn FnOnce(Option<&ActorPanicReport>) + Send>;
+/// executed once the Actor has been removed from the system
+pub(crate) type RemovalWatcher = Box<dyn FnOnce() + Send>;
+
+/// One-shot slot that receives the Actor once it reached its final state, shared between the Executor and all [ActorWrapper](../prelude/struct.ActorWrapper.html)s
+pub(crate) struct FinalState<A> {
+    is_finished: bool,
+    extraction: Option<Extraction<A>>,
+    is_extraction_requested: bool,
+    callbacks: Vec<FinalStateCallback<A>>,
+    watchers: Vec<TerminationWatcher>,
+    is_removed: bool,
+    removal_watchers: Vec<RemovalWatcher>,
+}
+
+impl<A> FinalState<A> {
+    pub fn new() -> Self {
+        Self {
+            is_finished: false,
+            extraction: None,
+            is_extraction_requested: false,
+            callbacks: Vec::new(),
+            watchers: Vec::new(),
+            is_removed: false,
+            removal_watchers: Vec::new(),
+        }
+    }
+
+    pub fn is_extraction_requested(&self) -> bool {
+        self.is_extraction_requested
+    }
+
+    pub fn request_extraction(&mut self, sender: Extraction<A>) -> Result<(), ExtractError> {
+        if self.is_finished {
+            return Err(ExtractError::Stopped);
+        }
+        if self.is_extraction_requested {
+            return Err(ExtractError::AlreadyRequested);
+        }
+        self.is_extraction_requested = true;
+        self.extraction = Some(sender);
+        Ok(())
+    }
+
+    pub fn add_callback(&mut self, callback: FinalStateCallback<A>) -> Result<(), ExtractError> {
+        if self.is_finished {
+            return Err(ExtractError::Stopped);
+        }
+        self.callbacks.push(callback);
+        Ok(())
+    }
+
+    /// Returns the watcher if the Actor has already stopped, so that the caller can execute it right away
+    pub fn add_watcher(&mut self, watcher: TerminationWatcher) -> Result<(), TerminationWatcher> {
+        if self.is_finished {
+            return Err(watcher);
+        }
+        self.watchers.push(watcher);
+        Ok(())
+    }
+
+    /// Returns the watcher if the Actor has already been removed, so that the caller can execute it right away
+    pub fn add_removal_watcher(&mut self, watcher: RemovalWatcher) -> Result<(), RemovalWatcher> {
+        if self.is_removed {
+            return Err(watcher);
+        }
+        self.removal_watchers.push(watcher);
+        Ok(())
+    }
+
+    /// Marks the Actor as removed and returns everything that waits for the removal
+    pub fn take_removal_watchers(&mut self) -> Vec<RemovalWatcher> {
+        self.is_removed = true;
+        std::mem::take(&mut self.removal_watchers)
+    }
+
+    /// Marks the Actor as finished and returns everything that waits for its final state
+    pub fn take(&mut self) -> (Vec<FinalStateCallback<A>>, Option<Extraction<A>>, Vec<TerminationWatcher>) {
+        self.is_finished = true;
+        (
+            std::mem::take(&mut self.callbacks),
+            self.extraction.take(),
+            std::mem::take(&mut self.watchers),
+        )
+    }
+}
+
+/// Executes all callbacks and hands the Actor to a pending extraction, otherwise it is dropped
+///
+/// Callbacks are skipped if the Actor panicked, watchers are notified last in any case
+pub(crate) fn deliver<A>(
+    result: Result<A, ActorPanicReport>,
+    callbacks: Vec<FinalStateCallback<A>>,
+    extraction: Option<Extraction<A>>,
+    watchers: Vec<TerminationWatcher>,
+) {
+    if let Ok(actor) = &result {
+        for callback in callbacks {
+            callback(actor);
+        }
+    }
+    let report = match &result {
+        Err(report) if !watchers.is_empty() => Some(report.clone()),
+        _ => None,
+    };
+    if let Some(extraction) = extraction {
+        // fails if the extraction timed out, the Actor is dropped in that case
+        let _ = extraction.send(result.map_err(|report| ExtractError::Panicked(Box::new(report))));
+    }
+    for watcher in watchers {
+        watcher(report.as_ref());
+    }
+}
+
+/// Same as [deliver] for an Actor that has never been created, a pending extraction receives [ExtractError::Stopped]
+pub(crate) fn deliver_uncreated<A>(extraction: Option<Extraction<A>>, watchers: Vec<TerminationWatcher>) {
+    if let Some(extraction) = extraction {
+        let _ = extraction.send(Err(ExtractError::Stopped));
+    }
+    for watcher in watchers {
+        watcher(None);
+    }
+}
@@ -1,7 +1,12 @@
 use crate::actor::actor::Actor;
 use crate::actor::context::ActorContext;
+use crate::actor::debug_snapshot::{self, DebugSnapshotMessage, SnapshotError};
+use crate::actor::handoff::HandoffDrainedMessage;
+use crate::actor::state_capture::{CaptureError, CaptureStateMessage};
 use crate::message::actor_message::ActorMessage;
 use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::child_terminated_message::ChildTerminatedMessage;
+use crate::message::suspension_ended_message::SuspensionEndedMessage;
 use crate::message::system_stop_message::SystemStopMessage;
 
 /// Defines which [ActorMessage] is supported per [Actor]
@@ -49,3 +54,56 @@ where
         self.on_system_stop();
     }
 }
+
+impl<A> Handler<SuspensionEndedMessage> for A
+where
+    A: Actor + Sized,
+{
+    fn handle(&mut self, msg: SuspensionEndedMessage, _context: &ActorContext<A>) {
+        self.on_suspension_ended(msg.reason);
+    }
+}
+
+impl<A> Handler<ChildTerminatedMessage> for A
+where
+    A: Actor + Sized,
+{
+    fn handle(&mut self, msg: ChildTerminatedMessage, _context: &ActorContext<A>) {
+        self.on_child_terminated(msg.child);
+    }
+}
+
+impl<A> Handler<HandoffDrainedMessage> for A
+where
+    A: Actor + Sized,
+{
+    fn handle(&mut self, msg: HandoffDrainedMessage, _context: &ActorContext<A>) {
+        let _ = msg.signal.send(());
+    }
+}
+
+impl<A> Handler<DebugSnapshotMessage> for A
+where
+    A: Actor + Sized,
+{
+    fn handle(&mut self, msg: DebugSnapshotMessage, context: &ActorContext<A>) {
+        let result = match context.actor_ref.get_mailbox().debug_snapshot {
+            Some(snapshot) => debug_snapshot::take(self, snapshot),
+            None => Err(SnapshotError::NotSupported),
+        };
+        let _ = msg.result.send(result);
+    }
+}
+
+impl<A> Handler<CaptureStateMessage> for A
+where
+    A: Actor + Sized,
+{
+    fn handle(&mut self, msg: CaptureStateMessage, context: &ActorContext<A>) {
+        let result = match &context.actor_ref.get_mailbox().captures {
+            Some(captures) => captures.capture(self, msg.label),
+            None => Err(CaptureError::Disabled),
+        };
+        let _ = msg.result.send(result);
+    }
+}
@@ -0,0 +1,282 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::mailbox::STOP_DRAIN;
+use crate::message::actor_message::ActorMessage;
+use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::envelope::{MessageEnvelope, MessageEnvelopeTrait};
+use crate::message::system_stop_message::SystemStopMessage;
+use crate::system::system_state::SystemState;
+use crossbeam_channel::{bounded, Sender};
+use std::any::TypeId;
+use std::collections::VecDeque;
+use std::panic::UnwindSafe;
+use std::sync::atomic::Ordering;
+use std::sync::MutexGuard;
+use std::time::Duration;
+
+/// Defines how [ActorSystem.handoff](../prelude/struct.ActorSystem.html#method.handoff) moves pending work
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HandoffMode {
+    /// the source Actor finishes its queue and stops, messages sent in the meantime are held back and handled by the target afterwards
+    DrainThenRedirect,
+    /// queued messages of the source Actor are moved to the front of the target mailbox and the source Actor stops immediately
+    TransferQueue,
+}
+
+/// Result of a successful handoff
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HandoffReport {
+    pub mode: HandoffMode,
+    /// messages that were still handled by the source Actor
+    pub drained: usize,
+    /// messages that were moved to the target Actor
+    pub moved: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HandoffError {
+    SameActor,
+    SourceNotActive,
+    TargetNotActive,
+    /// the source Actor is already being handed off
+    HandoffInProgress,
+    /// the target Actor stopped during the handoff, the source Actor keeps running and no message is lost
+    TargetStopped,
+}
+
+pub(crate) enum Redirect<A>
+where
+    A: Actor + 'static,
+{
+    None,
+    Park,
+    Target(Box<ActorWrapper<A>>),
+}
+
+/// Handoff related state of a [Mailbox](./struct.Mailbox.html), shared between all clones of it
+pub struct HandoffState<A>
+where
+    A: Actor + 'static,
+{
+    pub(crate) front: VecDeque<MessageEnvelope<A>>,
+    pub(crate) parked: VecDeque<MessageEnvelope<A>>,
+    pub(crate) redirect: Redirect<A>,
+    pub(crate) closed: bool,
+}
+
+impl<A> HandoffState<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            front: VecDeque::new(),
+            parked: VecDeque::new(),
+            redirect: Redirect::None,
+            closed: false,
+        }
+    }
+}
+
+impl<A> Default for HandoffState<A>
+where
+    A: Actor + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handled by the source Actor of a [HandoffMode::DrainThenRedirect](./enum.HandoffMode.html#variant.DrainThenRedirect) once its queue is drained
+pub struct HandoffDrainedMessage {
+    pub(crate) signal: Sender<()>,
+}
+
+impl ActorMessage for HandoffDrainedMessage {}
+
+pub(crate) fn is_control_message<A>(msg: &MessageEnvelope<A>) -> bool
+where
+    A: Actor,
+{
+    let type_id = msg.get_type_id();
+    type_id == TypeId::of::<ActorStopMessage>() || type_id == TypeId::of::<SystemStopMessage>()
+}
+
+fn lock_both<'a, A>(
+    from: &'a ActorWrapper<A>,
+    to: &'a ActorWrapper<A>,
+) -> (MutexGuard<'a, HandoffState<A>>, MutexGuard<'a, HandoffState<A>>)
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    // always lock in address order, so that concurrent handoffs between the same Actors can not deadlock
+    if from.get_address() < to.get_address() {
+        let source = from.get_mailbox().handoff.lock().unwrap();
+        let target = to.get_mailbox().handoff.lock().unwrap();
+        (source, target)
+    } else {
+        let target = to.get_mailbox().handoff.lock().unwrap();
+        let source = from.get_mailbox().handoff.lock().unwrap();
+        (source, target)
+    }
+}
+
+fn is_running<A>(state: &SystemState, actor: &ActorWrapper<A>) -> bool
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    state.is_actor_active(actor.get_address()) && !actor.get_mailbox().is_stopped()
+}
+
+pub(crate) fn handoff<A>(
+    state: &SystemState,
+    from: &ActorWrapper<A>,
+    to: &ActorWrapper<A>,
+    mode: HandoffMode,
+) -> Result<HandoffReport, HandoffError>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    if from.get_address() == to.get_address() {
+        return Err(HandoffError::SameActor);
+    }
+    if !is_running(state, from) {
+        return Err(HandoffError::SourceNotActive);
+    }
+    if !is_running(state, to) {
+        return Err(HandoffError::TargetNotActive);
+    }
+    match mode {
+        HandoffMode::TransferQueue => transfer_queue(state, from, to),
+        HandoffMode::DrainThenRedirect => drain_then_redirect(state, from, to),
+    }
+}
+
+fn transfer_queue<A>(
+    state: &SystemState,
+    from: &ActorWrapper<A>,
+    to: &ActorWrapper<A>,
+) -> Result<HandoffReport, HandoffError>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let moved;
+    {
+        let (mut source, mut target) = lock_both(from, to);
+        if !matches!(source.redirect, Redirect::None) || !matches!(target.redirect, Redirect::None) {
+            return Err(HandoffError::HandoffInProgress);
+        }
+        if !is_running(state, to) {
+            return Err(HandoffError::TargetStopped);
+        }
+        let mut queued: VecDeque<MessageEnvelope<A>> = source.front.drain(..).collect();
+        while let Some(msg) = from.get_mailbox().try_recv() {
+            // the source Actor is stopped by the handoff anyway
+            if !is_control_message(&msg) {
+                queued.push_back(msg);
+            }
+        }
+        moved = queued.len();
+        while let Some(mut msg) = queued.pop_back() {
+            to.attach_pressure(&mut msg);
+            target.front.push_front(msg);
+        }
+        source.redirect = Redirect::Target(Box::new(to.clone()));
+        source.closed = true;
+        from.get_mailbox().flags.is_redirected.store(true, Ordering::Relaxed);
+    }
+    from.get_mailbox().request_stop(STOP_DRAIN);
+    from.enqueue(MessageEnvelope::new(ActorStopMessage {}));
+    to.wakeup_if_sleeping();
+    Ok(HandoffReport {
+        mode: HandoffMode::TransferQueue,
+        drained: 0,
+        moved,
+    })
+}
+
+fn drain_then_redirect<A>(
+    state: &SystemState,
+    from: &ActorWrapper<A>,
+    to: &ActorWrapper<A>,
+) -> Result<HandoffReport, HandoffError>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let drained;
+    {
+        let (mut source, target) = lock_both(from, to);
+        if !matches!(source.redirect, Redirect::None) || !matches!(target.redirect, Redirect::None) {
+            return Err(HandoffError::HandoffInProgress);
+        }
+        drained = source.front.len() + from.get_mailbox().len();
+        source.redirect = Redirect::Park;
+        from.get_mailbox().flags.is_redirected.store(true, Ordering::Relaxed);
+    }
+    let (signal_in, signal_out) = bounded(1);
+    // queued behind everything that is left, so it waits for room in a full mailbox instead of overtaking
+    let mut drained_signal = Some(MessageEnvelope::new(HandoffDrainedMessage { signal: signal_in }));
+    loop {
+        if let Some(msg) = drained_signal.take() {
+            drained_signal = from.try_enqueue(msg).err();
+        }
+        if signal_out.recv_timeout(Duration::from_millis(10)).is_ok() {
+            break;
+        }
+        if !is_running(state, to) {
+            rollback(from);
+            return Err(HandoffError::TargetStopped);
+        }
+        if !state.is_actor_active(from.get_address()) {
+            break;
+        }
+    }
+
+    let moved;
+    {
+        let (mut source, mut target) = lock_both(from, to);
+        if !is_running(state, to) {
+            drop(target);
+            drop(source);
+            rollback(from);
+            return Err(HandoffError::TargetStopped);
+        }
+        moved = source.parked.len();
+        while let Some(mut msg) = source.parked.pop_back() {
+            to.attach_pressure(&mut msg);
+            target.front.push_front(msg);
+        }
+        source.redirect = Redirect::Target(Box::new(to.clone()));
+        source.closed = true;
+    }
+    from.get_mailbox().request_stop(STOP_DRAIN);
+    from.enqueue(MessageEnvelope::new(ActorStopMessage {}));
+    to.wakeup_if_sleeping();
+    Ok(HandoffReport {
+        mode: HandoffMode::DrainThenRedirect,
+        drained,
+        moved,
+    })
+}
+
+fn rollback<A>(from: &ActorWrapper<A>)
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    // parked messages are newer than anything still queued, so they are appended
+    // new messages keep being parked until all previously parked ones have been appended
+    loop {
+        let parked: Vec<MessageEnvelope<A>> = {
+            let mut source = from.get_mailbox().handoff.lock().unwrap();
+            if source.parked.is_empty() {
+                source.redirect = Redirect::None;
+                from.get_mailbox().flags.is_redirected.store(false, Ordering::Relaxed);
+                return;
+            }
+            source.parked.drain(..).collect()
+        };
+        for msg in parked {
+            from.enqueue(msg);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::suspension::SuspensionEndReason;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Amount of journal entries attached to an [ActorPanicReport](./struct.ActorPanicReport.html)
+pub const PANIC_REPORT_JOURNAL_ENTRIES: usize = 16;
+
+/// Content of a [JournalEntry](./struct.JournalEntry.html)
+///
+/// `Note` and `Fields` are appended through [ActorContext.journal](../prelude/struct.ActorContext.html#method.journal), all other events are appended by the framework
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalEvent {
+    /// does not allocate for a `&'static str`
+    Note(Cow<'static, str>),
+    Fields(Vec<(&'static str, String)>),
+    Spawned,
+    /// the Actor has been recreated after its handler of `message_type` panicked
+    Restarted {
+        message_type: &'static str,
+        reason: Option<String>,
+    },
+    Suspended,
+    SuspensionEnded(SuspensionEndReason),
+    /// last entry of the Actor, only received by the [JournalSink](./trait.JournalSink.html)
+    Stopped,
+}
+
+impl From<&'static str> for JournalEvent {
+    fn from(note: &'static str) -> Self {
+        JournalEvent::Note(Cow::Borrowed(note))
+    }
+}
+
+impl From<String> for JournalEvent {
+    fn from(note: String) -> Self {
+        JournalEvent::Note(Cow::Owned(note))
+    }
+}
+
+impl From<Vec<(&'static str, String)>> for JournalEvent {
+    fn from(fields: Vec<(&'static str, String)>) -> Self {
+        JournalEvent::Fields(fields)
+    }
+}
+
+impl Display for JournalEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalEvent::Note(note) => write!(f, "{}", note),
+            JournalEvent::Fields(fields) => {
+                let fields: Vec<String> = fields.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+                write!(f, "{}", fields.join(" "))
+            }
+            JournalEvent::Spawned => write!(f, "spawned"),
+            JournalEvent::Restarted { message_type, reason } => match reason {
+                Some(reason) => write!(f, "restarted after {} panicked: {}", message_type, reason),
+                None => write!(f, "restarted after {} panicked", message_type),
+            },
+            JournalEvent::Suspended => write!(f, "suspended"),
+            JournalEvent::SuspensionEnded(reason) => write!(f, "suspension ended: {:?}", reason),
+            JournalEvent::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
+/// A single entry of the journal of an Actor, see [ActorBuilder.set_journal_capacity](../prelude/struct.ActorBuilder.html#method.set_journal_capacity)
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// starts at `0` for every Actor and is continued across restarts
+    pub sequence: u64,
+    pub at: SystemTime,
+    pub event: JournalEvent,
+}
+
+/// Receives the entries that are evicted from the journal of an Actor, oldest first
+///
+/// Executed on the thread that appends the entry, so it should hand them off instead of doing slow I/O.
+/// The remaining entries are written once the Actor stopped, ending with [JournalEvent::Stopped](./enum.JournalEvent.html#variant.Stopped)
+pub trait JournalSink: Send + Sync {
+    fn write(&self, address: &ActorAddress, entry: JournalEntry);
+}
+
+struct JournalRing {
+    entries: VecDeque<JournalEntry>,
+    next_sequence: u64,
+}
+
+/// Bounded journal of a single Actor, shared by its executor, context and wrappers
+pub(crate) struct Journal {
+    address: ActorAddress,
+    capacity: usize,
+    sink: Option<Arc<dyn JournalSink>>,
+    ring: Mutex<JournalRing>,
+}
+
+impl Journal {
+    pub fn new(address: ActorAddress, capacity: usize, sink: Option<Arc<dyn JournalSink>>) -> Self {
+        Self {
+            address,
+            capacity,
+            sink,
+            ring: Mutex::new(JournalRing {
+                entries: VecDeque::with_capacity(capacity),
+                next_sequence: 0,
+            }),
+        }
+    }
+
+    pub fn append(&self, event: JournalEvent) {
+        let mut ring = self.ring.lock().unwrap();
+        let evicted = if ring.entries.len() == self.capacity {
+            ring.entries.pop_front()
+        } else {
+            None
+        };
+        let entry = JournalEntry {
+            sequence: ring.next_sequence,
+            at: SystemTime::now(),
+            event,
+        };
+        ring.next_sequence += 1;
+        ring.entries.push_back(entry);
+        drop(ring);
+        if let (Some(sink), Some(evicted)) = (&self.sink, evicted) {
+            sink.write(&self.address, evicted);
+        }
+    }
+
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.ring.lock().unwrap().entries.iter().cloned().collect()
+    }
+
+    /// Returns the last `n` entries, oldest first
+    pub fn tail(&self, n: usize) -> Vec<JournalEntry> {
+        let ring = self.ring.lock().unwrap();
+        let skip = ring.entries.len().saturating_sub(n);
+        ring.entries.iter().skip(skip).cloned().collect()
+    }
+
+    /// Appends [JournalEvent::Stopped] and hands all entries to the sink, the journal is empty afterwards
+    pub fn close(&self) {
+        self.append(JournalEvent::Stopped);
+        let entries: Vec<JournalEntry> = self.ring.lock().unwrap().entries.drain(..).collect();
+        if let Some(sink) = &self.sink {
+            for entry in entries {
+                sink.write(&self.address, entry);
+            }
+        }
+    }
+}
@@ -1,16 +1,180 @@
 use crate::actor::actor::Actor;
+use crate::actor::actor_config::MailboxOverflowPolicy;
+use crate::actor::extraction::FinalState;
+use crate::actor::handoff;
 use crate::actor::handler::Handler;
+use crate::actor::effect_guard::EffectLedger;
+use crate::actor::handoff::HandoffState;
+use crate::actor::journal::Journal;
+use crate::actor::mailbox_bytes::MailboxBytes;
+use crate::actor::message_stream::Emitter;
+use crate::actor::pressure::PressureState;
+use crate::actor::priority::PriorityState;
+use crate::actor::slo::SloStatus;
+use crate::actor::stash::Stash;
+use crate::actor::state_capture::StateCaptures;
+use crate::actor::supersession::Supersession;
+use crate::actor::batch_handler::Batching;
+use crate::actor::middleware::Middlewares;
+use crate::actor::message_class::{ClassedQueues, Lane};
+use crate::actor::message_registry::MessageRegistry;
+use crate::system::message_budget::MessageBudget;
+use crate::actor::debug_snapshot::DebugSnapshotFn;
+use crate::actor::recovery::Recovery;
+use crate::actor::retry::Retrying;
+#[cfg(feature = "testing")]
+use crate::harness::interaction_log::InteractionLog;
+#[cfg(feature = "async")]
+use crate::actor::send_async::SendWaiters;
 use crate::message::actor_message::ActorMessage;
-use crate::message::envelope::MessageEnvelope;
-use crossbeam_channel::Sender;
+use crate::message::envelope::{MessageEnvelope, MessageEnvelopeTrait};
+use crate::message::message_size::SizeLimit;
+use crate::system::dead_letters::DeadLetterOffice;
+use crate::system::instrumentation::Instrumentation;
+use crate::system::system_state::ActorRegistry;
+use crate::system::memory_pressure::{MemoryMode, MemoryPressureState};
+use crossbeam_channel::{Receiver, SendTimeoutError, Sender, TrySendError};
+use crate::system::yield_point::yield_point;
+use std::any::TypeId;
+use std::cell::Cell;
 use std::panic::UnwindSafe;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{fence, AtomicBool, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-pub struct Mailbox<A> {
-    pub is_stopped: Arc<AtomicBool>,
-    pub is_sleeping: Arc<AtomicBool>,
+/// Stop requests, a later request may only escalate an earlier one
+pub(crate) const STOP_NONE: u8 = 0;
+pub(crate) const STOP_DRAIN: u8 = 1;
+pub(crate) const STOP_NOW: u8 = 2;
+
+/// How long a send waits for room in a full bounded mailbox, unbounded mailboxes are never full
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SendWait {
+    Block,
+    Never,
+    Until(Instant),
+}
+
+/// Outcome of an envelope that has been accepted by the mailbox
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Pushed {
+    Queued,
+    /// the send had to wait for room
+    QueuedAfterWait,
+    /// dropped by [MailboxOverflowPolicy::DropNewest](../prelude/enum.MailboxOverflowPolicy.html#variant.DropNewest)
+    Dropped,
+}
+
+/// Envelope that could not be queued, handed back to the sender
+pub(crate) enum PushError<A> {
+    /// the mailbox was full for as long as the send was allowed to wait
+    Full(MessageEnvelope<A>),
+    /// the Executor is gone
+    Disconnected(MessageEnvelope<A>),
+    /// the mailbox is full and the send has been issued by the Actor itself, waiting for room would never end
+    WouldDeadlock(MessageEnvelope<A>),
+}
+
+thread_local! {
+    /// flags of the mailbox whose Actor is executed by the current thread, `0` if none
+    static EXECUTING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Marks the current thread as executing the Actor of a mailbox until it is dropped
+pub(crate) struct Executing(usize);
+
+impl Executing {
+    pub fn enter(flags: &Arc<MailboxFlags>) -> Self {
+        Self(EXECUTING.with(|executing| executing.replace(Arc::as_ptr(flags) as usize)))
+    }
+}
+
+impl Drop for Executing {
+    fn drop(&mut self) {
+        EXECUTING.with(|executing| executing.set(self.0));
+    }
+}
+
+/// State flags of a mailbox, held in one allocation that is shared by all clones of the mailbox
+#[derive(Default)]
+pub(crate) struct MailboxFlags {
+    pub(crate) is_stopped: AtomicBool,
+    /// strongest stop requested through [ActorWrapper.stop](../prelude/struct.ActorWrapper.html#method.stop) or [ActorWrapper.stop_now](../prelude/struct.ActorWrapper.html#method.stop_now)
+    pub(crate) stop_request: AtomicU8,
+    pub(crate) is_sleeping: AtomicBool,
+    /// set while an Actor spawned through [ActorBuilder.start_suspended](../prelude/struct.ActorBuilder.html#method.start_suspended) has not been released
+    pub(crate) is_awaiting_release: AtomicBool,
+    /// set once the Actor is ready, see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    pub(crate) is_ready: AtomicBool,
+    pub(crate) is_redirected: AtomicBool,
+}
+
+pub struct Mailbox<A>
+where
+    A: Actor + 'static,
+{
+    pub(crate) flags: Arc<MailboxFlags>,
+    /// shared with the system, set once its stop has been finalized
+    pub(crate) is_system_stopped: Arc<AtomicBool>,
     pub msg_in: Sender<MessageEnvelope<A>>,
+    pub msg_out: Receiver<MessageEnvelope<A>>,
+    /// unbounded lane for control messages and [ActorWrapper.send_priority](../prelude/struct.ActorWrapper.html#method.send_priority), drained before `msg_out`
+    pub(crate) urgent_in: Sender<MessageEnvelope<A>>,
+    pub(crate) urgent_out: Receiver<MessageEnvelope<A>>,
+    /// lane for [ActorContext.send_to_self](../prelude/struct.ActorContext.html#method.send_to_self), drained after `urgent_out` and before the regular lane
+    ///
+    /// A bounded mailbox reserves as many slots again for it, so that a self-send never waits for room
+    pub(crate) continuation_in: Sender<MessageEnvelope<A>>,
+    pub(crate) continuation_out: Receiver<MessageEnvelope<A>>,
+    pub(crate) overflow_policy: MailboxOverflowPolicy,
+    pub(crate) overflow_dead_letters: bool,
+    /// only set if the Actor has `max_mailbox_bytes`, see [ActorBuilder.set_max_mailbox_bytes](../prelude/struct.ActorBuilder.html#method.set_max_mailbox_bytes)
+    pub(crate) bytes: Option<Arc<MailboxBytes>>,
+    pub handoff: Arc<Mutex<HandoffState<A>>>,
+    pub priority: Arc<PriorityState<A>>,
+    /// only set if the Actor has a latency SLO, messages are timestamped when sent
+    pub slo_status: Option<Arc<Mutex<SloStatus>>>,
+    /// only set if the Actor has watermarks
+    pub(crate) pressure: Option<Arc<PressureState>>,
+    /// only set if the Actor is sheddable
+    pub(crate) memory_pressure: Option<MemoryPressureState>,
+    pub(crate) final_state: Arc<Mutex<FinalState<A>>>,
+    /// only set if the Actor has a journal capacity
+    pub(crate) journal: Option<Arc<Journal>>,
+    /// only set if the Actor has a state capture capacity
+    pub(crate) captures: Option<Arc<StateCaptures>>,
+    /// only set if the Actor has a message size limit or the system a warning threshold
+    pub(crate) size_limit: Option<Arc<SizeLimit>>,
+    pub(crate) effect_ledger: Arc<dyn EffectLedger>,
+    pub(crate) dead_letters: DeadLetterOffice,
+    pub(crate) instrumentation: Instrumentation,
+    pub(crate) actor_registry: ActorRegistry,
+    /// default time to live of the messages of the regular lane, see [ActorBuilder.set_message_ttl](../prelude/struct.ActorBuilder.html#method.set_message_ttl)
+    pub(crate) message_ttl: Option<Duration>,
+    pub(crate) emitter: Arc<Emitter>,
+    /// only set if the Actor has superseding message types
+    pub(crate) supersession: Option<Arc<Supersession>>,
+    /// only set if the Actor has batched message types
+    pub(crate) batching: Option<Arc<Batching<A>>>,
+    /// only set if the Actor has message classes, messages of other types are queued in `msg_in`
+    pub(crate) classes: Option<Arc<ClassedQueues<A>>>,
+    /// only set if the Actor has middlewares
+    pub(crate) middlewares: Option<Arc<Middlewares<A>>>,
+    pub(crate) accepted_messages: Arc<MessageRegistry>,
+    /// only set if the system has a message budget and the Actor is not exempt
+    pub(crate) message_budget: Option<Arc<MessageBudget>>,
+    /// only set if the Actor has retryable message types
+    pub(crate) retrying: Option<Arc<Retrying<A>>>,
+    /// only set if the Actor recovers its state after a panic
+    pub(crate) recovery: Option<Arc<Recovery<A>>>,
+    /// only set if the Actor is debuggable, see [ActorBuilder.set_debuggable](../prelude/struct.ActorBuilder.html#method.set_debuggable)
+    pub(crate) debug_snapshot: Option<DebugSnapshotFn<A>>,
+    pub(crate) stash: Arc<Stash<A>>,
+    /// only set for Actors of a [TestActorSystem](../testing/struct.TestActorSystem.html)
+    #[cfg(feature = "testing")]
+    pub(crate) interactions: Option<Arc<InteractionLog>>,
+    #[cfg(feature = "async")]
+    pub(crate) send_waiters: Arc<SendWaiters>,
 }
 
 impl<A> Clone for Mailbox<A>
@@ -20,8 +184,45 @@ where
     fn clone(&self) -> Self {
         Self {
             msg_in: self.msg_in.clone(),
-            is_stopped: self.is_stopped.clone(),
-            is_sleeping: self.is_sleeping.clone(),
+            msg_out: self.msg_out.clone(),
+            urgent_in: self.urgent_in.clone(),
+            urgent_out: self.urgent_out.clone(),
+            continuation_in: self.continuation_in.clone(),
+            continuation_out: self.continuation_out.clone(),
+            flags: self.flags.clone(),
+            is_system_stopped: self.is_system_stopped.clone(),
+            overflow_policy: self.overflow_policy,
+            overflow_dead_letters: self.overflow_dead_letters,
+            bytes: self.bytes.clone(),
+            handoff: self.handoff.clone(),
+            priority: self.priority.clone(),
+            slo_status: self.slo_status.clone(),
+            pressure: self.pressure.clone(),
+            memory_pressure: self.memory_pressure.clone(),
+            final_state: self.final_state.clone(),
+            journal: self.journal.clone(),
+            captures: self.captures.clone(),
+            size_limit: self.size_limit.clone(),
+            effect_ledger: self.effect_ledger.clone(),
+            dead_letters: self.dead_letters.clone(),
+            instrumentation: self.instrumentation.clone(),
+            actor_registry: self.actor_registry.clone(),
+            message_ttl: self.message_ttl,
+            emitter: self.emitter.clone(),
+            supersession: self.supersession.clone(),
+            batching: self.batching.clone(),
+            classes: self.classes.clone(),
+            middlewares: self.middlewares.clone(),
+            accepted_messages: self.accepted_messages.clone(),
+            message_budget: self.message_budget.clone(),
+            retrying: self.retrying.clone(),
+            recovery: self.recovery.clone(),
+            debug_snapshot: self.debug_snapshot,
+            stash: self.stash.clone(),
+            #[cfg(feature = "testing")]
+            interactions: self.interactions.clone(),
+            #[cfg(feature = "async")]
+            send_waiters: self.send_waiters.clone(),
         }
     }
 }
@@ -38,11 +239,202 @@ where
         self.msg_in.send(MessageEnvelope::new(msg)).unwrap();
     }
 
+    /// Queues the envelope, `on_full` is called once before the send starts to wait for room
+    ///
+    /// Envelopes dropped by the overflow policy are passed to `on_dropped`, urgent envelopes never wait and are never dropped
+    pub(crate) fn push<F, D>(
+        &self,
+        msg: MessageEnvelope<A>,
+        wait: SendWait,
+        on_full: F,
+        mut on_dropped: D,
+    ) -> Result<Pushed, PushError<A>>
+    where
+        F: FnOnce(),
+        D: FnMut(MessageEnvelope<A>),
+    {
+        if msg.is_urgent() {
+            return match self.urgent_in.send(msg) {
+                Ok(()) => Ok(Pushed::Queued),
+                Err(error) => Err(PushError::Disconnected(error.into_inner())),
+            };
+        }
+        if msg.is_continuation() {
+            return match self.continuation_in.try_send(msg) {
+                Ok(()) => Ok(Pushed::Queued),
+                Err(TrySendError::Full(msg)) => Err(PushError::Full(msg)),
+                Err(TrySendError::Disconnected(msg)) => Err(PushError::Disconnected(msg)),
+            };
+        }
+        let (msg_in, msg_out) = self.get_lane(msg.get_type_id());
+        let mut msg = match msg_in.try_send(msg) {
+            Ok(()) => return Ok(Pushed::Queued),
+            Err(TrySendError::Full(msg)) => msg,
+            Err(TrySendError::Disconnected(msg)) => return Err(PushError::Disconnected(msg)),
+        };
+        match self.overflow_policy {
+            MailboxOverflowPolicy::Block => {}
+            MailboxOverflowPolicy::Fail => return Err(PushError::Full(msg)),
+            MailboxOverflowPolicy::DropNewest => {
+                on_dropped(msg);
+                return Ok(Pushed::Dropped);
+            }
+            // the Executor receives from the same channel, so whichever receives first takes the oldest message
+            // and the message that is dropped here is always the oldest one that is left
+            MailboxOverflowPolicy::DropOldest => loop {
+                self.drop_oldest(msg_out, &mut on_dropped);
+                msg = match msg_in.try_send(msg) {
+                    Ok(()) => return Ok(Pushed::Queued),
+                    Err(TrySendError::Full(msg)) => msg,
+                    Err(TrySendError::Disconnected(msg)) => return Err(PushError::Disconnected(msg)),
+                };
+            },
+        }
+        if wait == SendWait::Never {
+            return Err(PushError::Full(msg));
+        }
+        // only the waiting Actor could make room
+        if self.is_executing() {
+            return Err(PushError::WouldDeadlock(msg));
+        }
+        on_full();
+        let result = match wait {
+            SendWait::Until(deadline) => msg_in.send_deadline(msg, deadline),
+            _ => msg_in.send(msg).map_err(|error| SendTimeoutError::Disconnected(error.into_inner())),
+        };
+        match result {
+            Ok(()) => Ok(Pushed::QueuedAfterWait),
+            Err(SendTimeoutError::Timeout(msg)) => Err(PushError::Full(msg)),
+            Err(SendTimeoutError::Disconnected(msg)) => Err(PushError::Disconnected(msg)),
+        }
+    }
+
+    /// Passes the oldest message of the lane to `on_dropped`, returns `false` if the lane is empty
+    pub(crate) fn drop_oldest<D>(&self, msg_out: &Receiver<MessageEnvelope<A>>, on_dropped: &mut D) -> bool
+    where
+        D: FnMut(MessageEnvelope<A>),
+    {
+        match msg_out.try_recv() {
+            // a stop queued by a handoff still has to stop the Actor
+            Ok(oldest) if handoff::is_control_message(&oldest) => {
+                let _ = self.urgent_in.send(oldest);
+                true
+            }
+            Ok(oldest) => {
+                on_dropped(oldest);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Pairs with the check of the mailbox after an Executor marked itself as sleeping, either the sender sees the mark or the Executor sees the message
     pub fn is_sleeping(&self) -> bool {
-        self.is_sleeping.load(Ordering::Relaxed)
+        // the message has been queued before, without the fence the load could be ordered before it
+        fence(Ordering::SeqCst);
+        self.flags.is_sleeping.load(Ordering::SeqCst)
+    }
+
+    /// Marks the Executor as sleeping, returns `false` and takes the mark back if a message has been queued in the meantime
+    pub(crate) fn mark_sleeping(&self) -> bool {
+        self.flags.is_sleeping.store(true, Ordering::SeqCst);
+        yield_point();
+        fence(Ordering::SeqCst);
+        if self.is_empty() {
+            return true;
+        }
+        self.flags.is_sleeping.store(false, Ordering::SeqCst);
+        false
+    }
+
+    /// `true` if the current thread executes the Actor of this mailbox
+    pub(crate) fn is_executing(&self) -> bool {
+        EXECUTING.with(|executing| executing.get() == Arc::as_ptr(&self.flags) as usize)
     }
 
     pub fn is_stopped(&self) -> bool {
-        self.is_stopped.load(Ordering::Relaxed)
+        self.flags.is_stopped.load(Ordering::Relaxed)
+    }
+
+    /// Sends are still delivered while the system is stopping, only the end of the graceful termination turns them into dead letters
+    pub(crate) fn is_system_stopped(&self) -> bool {
+        self.is_system_stopped.load(Ordering::Relaxed)
+    }
+
+    /// Lets sends that wait for room try again, see [ActorWrapper.send_async](../prelude/struct.ActorWrapper.html#method.send_async)
+    #[cfg(feature = "async")]
+    pub(crate) fn wake_senders(&self) {
+        self.send_waiters.wake();
+    }
+
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn wake_senders(&self) {}
+
+    pub fn is_awaiting_release(&self) -> bool {
+        self.flags.is_awaiting_release.load(Ordering::SeqCst)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.flags.is_ready.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if the Actor has not been ready before
+    pub(crate) fn mark_ready(&self) -> bool {
+        !self.flags.is_ready.swap(true, Ordering::SeqCst)
+    }
+
+    /// Records the stop request and returns the previous one
+    pub(crate) fn request_stop(&self, request: u8) -> u8 {
+        self.flags.stop_request.fetch_max(request, Ordering::SeqCst)
+    }
+
+    pub(crate) fn is_stop_requested(&self) -> bool {
+        self.flags.stop_request.load(Ordering::Relaxed) != STOP_NONE
+    }
+
+    pub(crate) fn is_stop_now_requested(&self) -> bool {
+        self.flags.stop_request.load(Ordering::Relaxed) == STOP_NOW
+    }
+
+    /// Current memory mode if the Actor is sheddable, always `Normal` otherwise
+    pub(crate) fn get_memory_mode(&self) -> MemoryMode {
+        self.memory_pressure
+            .as_ref()
+            .map_or(MemoryMode::Normal, |memory_pressure| memory_pressure.get_mode())
+    }
+
+    pub fn is_redirected(&self) -> bool {
+        self.flags.is_redirected.load(Ordering::Relaxed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.msg_in.len() + self.urgent_in.len() + self.continuation_in.len() + self.classes.as_ref().map_or(0, |classes| classes.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urgent_in.is_empty() && self.is_regular_empty()
+    }
+
+    /// `true` if neither the continuation lane, the regular lane nor any message class holds a message
+    pub(crate) fn is_regular_empty(&self) -> bool {
+        self.continuation_in.is_empty() && self.msg_in.is_empty() && self.classes.as_ref().is_none_or(|classes| classes.is_empty())
+    }
+
+    /// Queue of the message class of the type, the regular lane for types without a class, see [ActorBuilder.add_message_class](../prelude/struct.ActorBuilder.html#method.add_message_class)
+    pub(crate) fn get_lane(&self, type_id: TypeId) -> Lane<'_, A> {
+        self.classes
+            .as_ref()
+            .and_then(|classes| classes.get_lane(type_id))
+            .unwrap_or((&self.msg_in, &self.msg_out))
+    }
+
+    /// Next message of the priority lane, otherwise of the continuation lane, the regular lane or any message class
+    pub(crate) fn try_recv(&self) -> Option<MessageEnvelope<A>> {
+        self.urgent_out
+            .try_recv()
+            .or_else(|_| self.continuation_out.try_recv())
+            .or_else(|_| self.msg_out.try_recv())
+            .ok()
+            .or_else(|| self.classes.as_ref().and_then(|classes| classes.try_recv()))
     }
 }
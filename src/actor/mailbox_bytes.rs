@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Longest wait between two checks of a blocked send, in case the Actor stopped in the meantime
+const MAX_WAIT: Duration = Duration::from_millis(10);
+
+/// Approximate bytes queued in the mailbox of an Actor with `max_mailbox_bytes`, see [ActorBuilder.set_max_mailbox_bytes](../prelude/struct.ActorBuilder.html#method.set_max_mailbox_bytes)
+pub(crate) struct MailboxBytes {
+    max_bytes: usize,
+    queued: AtomicUsize,
+    /// sends that wait for bytes to be released, only those make a release take the lock
+    waiting: AtomicUsize,
+    lock: Mutex<()>,
+    released: Condvar,
+}
+
+impl MailboxBytes {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            queued: AtomicUsize::new(0),
+            waiting: AtomicUsize::new(0),
+            lock: Mutex::new(()),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn get_queued(&self) -> usize {
+        self.queued.load(Ordering::SeqCst)
+    }
+
+    /// Counts `size` bytes towards the mailbox until the token is dropped, `None` if they exceed the limit
+    ///
+    /// A message that exceeds the limit on its own is only accepted by an empty mailbox, so that it never waits forever
+    pub fn try_reserve(self: &Arc<Self>, size: usize) -> Option<BytesToken> {
+        let mut queued = self.queued.load(Ordering::SeqCst);
+        loop {
+            if queued > 0 && queued.saturating_add(size) > self.max_bytes {
+                return None;
+            }
+            match self.queued.compare_exchange_weak(queued, queued + size, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return Some(BytesToken { bytes: self.clone(), size }),
+                Err(current) => queued = current,
+            }
+        }
+    }
+
+    /// Waits until bytes have been released or the deadline passed, returns `false` once it passed
+    pub fn wait(&self, size: usize, deadline: Option<Instant>) -> bool {
+        let now = Instant::now();
+        let timeout = match deadline {
+            Some(deadline) if deadline <= now => return false,
+            Some(deadline) => (deadline - now).min(MAX_WAIT),
+            None => MAX_WAIT,
+        };
+        let guard = self.lock.lock().unwrap();
+        self.waiting.fetch_add(1, Ordering::SeqCst);
+        // checked under the lock, a release in between notifies only after the wait started
+        let queued = self.queued.load(Ordering::SeqCst);
+        if queued == 0 || queued.saturating_add(size) <= self.max_bytes {
+            self.waiting.fetch_sub(1, Ordering::SeqCst);
+            return true;
+        }
+        let _ = self.released.wait_timeout(guard, timeout).unwrap();
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+        true
+    }
+
+    fn release(&self, size: usize) {
+        self.queued.fetch_sub(size, Ordering::SeqCst);
+        if self.waiting.load(Ordering::SeqCst) > 0 {
+            let _guard = self.lock.lock().unwrap();
+            self.released.notify_all();
+        }
+    }
+}
+
+/// Counts a queued message towards the bytes of its mailbox until it is dropped
+///
+/// The envelope holds the token, so the bytes are released no matter how the message leaves the mailbox:
+/// handled, dropped by the overflow policy, expired, discarded by a stop or dropped together with the mailbox
+pub(crate) struct BytesToken {
+    bytes: Arc<MailboxBytes>,
+    size: usize,
+}
+
+impl Drop for BytesToken {
+    fn drop(&mut self) {
+        self.bytes.release(self.size);
+    }
+}
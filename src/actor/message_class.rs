@@ -0,0 +1,137 @@
+use crate::actor::actor::Actor;
+use crate::message::envelope::MessageEnvelope;
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// Weight of the regular mailbox, which holds all messages that do not belong to a class
+pub(crate) const DEFAULT_CLASS_WEIGHT: usize = 1;
+
+/// Sending and receiving end of a queue of a mailbox
+pub(crate) type Lane<'a, A> = (&'a Sender<MessageEnvelope<A>>, &'a Receiver<MessageEnvelope<A>>);
+
+/// Message classes of an Actor, registered through [ActorBuilder.add_message_class](../prelude/struct.ActorBuilder.html#method.add_message_class)
+#[derive(Clone, Default)]
+pub(crate) struct MessageClasses {
+    /// in the order they have been added, which is also the order within a round
+    weights: Vec<(TypeId, usize)>,
+}
+
+impl MessageClasses {
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Adding the same type again replaces its weight
+    pub fn add(&mut self, type_id: TypeId, weight: usize) {
+        let weight = weight.max(1);
+        match self.weights.iter_mut().find(|(existing, _)| *existing == type_id) {
+            Some(class) => class.1 = weight,
+            None => self.weights.push((type_id, weight)),
+        }
+    }
+}
+
+struct ClassLane<A> {
+    weight: usize,
+    lane_in: Sender<MessageEnvelope<A>>,
+    lane_out: Receiver<MessageEnvelope<A>>,
+}
+
+/// Sub-queue per message class, the type of a message decides its queue when it is sent
+pub(crate) struct ClassedQueues<A> {
+    lanes: Vec<ClassLane<A>>,
+    by_type: HashMap<TypeId, usize>,
+}
+
+impl<A> ClassedQueues<A>
+where
+    A: Actor,
+{
+    /// Every queue has the capacity of the mailbox, `0` is unbounded
+    pub fn new(classes: &MessageClasses, capacity: usize) -> Self {
+        let lanes = classes
+            .weights
+            .iter()
+            .map(|(_, weight)| {
+                let (lane_in, lane_out) = if capacity == 0 { unbounded() } else { bounded(capacity) };
+                ClassLane {
+                    weight: *weight,
+                    lane_in,
+                    lane_out,
+                }
+            })
+            .collect();
+        let by_type = classes
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(index, (type_id, _))| (*type_id, index))
+            .collect();
+        Self { lanes, by_type }
+    }
+
+    /// `None` for messages of the regular mailbox
+    pub fn get_lane(&self, type_id: TypeId) -> Option<Lane<'_, A>> {
+        let lane = &self.lanes[*self.by_type.get(&type_id)?];
+        Some((&lane.lane_in, &lane.lane_out))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lanes.iter().map(|lane| lane.lane_in.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(|lane| lane.lane_in.is_empty())
+    }
+
+    /// Next message of any class, without regard to the weights
+    pub fn try_recv(&self) -> Option<MessageEnvelope<A>> {
+        self.lanes.iter().find_map(|lane| lane.lane_out.try_recv().ok())
+    }
+}
+
+/// Weighted round over the classes and the regular mailbox, kept by the Executor
+///
+/// Within a round each queue hands out at most as many messages as its weight, empty queues pass their turn.
+/// A new round starts once every queue that still holds messages used up its weight
+pub(crate) struct ClassSchedule {
+    /// remaining messages per class within the current round, the regular mailbox is last
+    credits: Vec<usize>,
+}
+
+impl ClassSchedule {
+    pub fn new() -> Self {
+        Self { credits: Vec::new() }
+    }
+
+    pub fn next<A>(&mut self, classes: &ClassedQueues<A>, regular: &Receiver<MessageEnvelope<A>>) -> Option<MessageEnvelope<A>>
+    where
+        A: Actor,
+    {
+        if self.credits.len() != classes.lanes.len() + 1 {
+            self.refill(classes);
+        }
+        for _ in 0..2 {
+            for (index, credits) in self.credits.iter_mut().enumerate() {
+                if *credits == 0 {
+                    continue;
+                }
+                let lane_out = classes.lanes.get(index).map_or(regular, |lane| &lane.lane_out);
+                if let Ok(msg) = lane_out.try_recv() {
+                    *credits -= 1;
+                    return Some(msg);
+                }
+            }
+            // all queues with weight left are empty
+            self.refill(classes);
+        }
+        None
+    }
+
+    fn refill<A>(&mut self, classes: &ClassedQueues<A>) {
+        self.credits.clear();
+        self.credits.extend(classes.lanes.iter().map(|lane| lane.weight));
+        self.credits.push(DEFAULT_CLASS_WEIGHT);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::actor::actor::Actor;
+use crate::actor::debug_snapshot::DebugSnapshotMessage;
+use crate::actor::execute_fn::ExecuteFn;
+use crate::actor::handoff::HandoffDrainedMessage;
+use crate::actor::relocation::MoveToPoolMessage;
+use crate::actor::replacement::ReplaceActorMessage;
+use crate::actor::request_chain::ChainReply;
+use crate::actor::state_capture::CaptureStateMessage;
+use crate::actor::unhandled::UnhandledMessage;
+use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::child_terminated_message::ChildTerminatedMessage;
+use crate::message::serialized_dispatch::SerializedRequest;
+use crate::message::suspension_ended_message::SuspensionEndedMessage;
+use crate::message::system_stop_message::SystemStopMessage;
+use dashmap::DashMap;
+use std::any::TypeId;
+
+/// Message types an Actor accepts, see [ActorBuilder.register_message](../prelude/struct.ActorBuilder.html#method.register_message)
+///
+/// Kept across restarts, messages handled by every Actor are never part of it
+pub(crate) struct MessageRegistry {
+    accepted: DashMap<TypeId, &'static str>,
+    /// only registered types are accepted, nothing is added on dispatch
+    strict: bool,
+}
+
+impl MessageRegistry {
+    pub fn new(registered: &[(TypeId, &'static str)], strict: bool) -> Self {
+        Self {
+            accepted: registered.iter().copied().collect(),
+            strict,
+        }
+    }
+
+    /// `false` if the Actor is strict and the type has not been registered
+    pub fn accepts(&self, type_id: TypeId) -> bool {
+        !self.strict || self.accepted.contains_key(&type_id)
+    }
+
+    /// Adds a type that has been handled successfully
+    pub fn record(&self, type_id: TypeId, type_name: &'static str) {
+        if !self.strict && !self.accepted.contains_key(&type_id) {
+            self.accepted.insert(type_id, type_name);
+        }
+    }
+
+    /// Sorted by name
+    pub fn get_accepted(&self) -> Vec<&'static str> {
+        let mut accepted: Vec<&'static str> = self.accepted.iter().map(|entry| *entry.value()).collect();
+        accepted.sort_unstable();
+        accepted
+    }
+}
+
+/// Messages of the library that every Actor handles, they bypass the registry
+pub(crate) fn is_builtin<A>(type_id: TypeId) -> bool
+where
+    A: Actor + 'static,
+{
+    [
+        TypeId::of::<ActorStopMessage>(),
+        TypeId::of::<SystemStopMessage>(),
+        TypeId::of::<SuspensionEndedMessage>(),
+        TypeId::of::<ChildTerminatedMessage>(),
+        TypeId::of::<HandoffDrainedMessage>(),
+        TypeId::of::<CaptureStateMessage>(),
+        TypeId::of::<DebugSnapshotMessage>(),
+        TypeId::of::<UnhandledMessage>(),
+        TypeId::of::<ChainReply>(),
+        TypeId::of::<SerializedRequest>(),
+        TypeId::of::<MoveToPoolMessage>(),
+        TypeId::of::<ReplaceActorMessage<A>>(),
+        TypeId::of::<ExecuteFn<A>>(),
+    ]
+    .contains(&type_id)
+}
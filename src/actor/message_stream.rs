@@ -0,0 +1,448 @@
+use std::any::{Any, TypeId};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::{Context, Poll, Waker};
+
+/// Defines what happens once the buffer of a [MessageStream](./struct.MessageStream.html) is full
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LagPolicy {
+    /// drops the oldest buffered item, the consumer receives a [StreamItem::Lagged](./enum.StreamItem.html#variant.Lagged) marker in its place
+    DropOldest,
+    /// detaches the stream, the consumer receives the buffered items followed by [StreamEndReason::Overflowed](./enum.StreamEndReason.html#variant.Overflowed)
+    Close,
+}
+
+/// Reason why a [MessageStream](./struct.MessageStream.html) has ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamEndReason {
+    /// the Actor has stopped
+    Stopped,
+    /// the consumer did not keep up, see [LagPolicy::Close](./enum.LagPolicy.html#variant.Close)
+    Overflowed,
+}
+
+/// Item yielded by a [MessageStream](./struct.MessageStream.html)
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem<M> {
+    Item(M),
+    /// amount of items that have been dropped, see [LagPolicy::DropOldest](./enum.LagPolicy.html#variant.DropOldest)
+    Lagged(u64),
+    /// last item of the stream
+    End(StreamEndReason),
+}
+
+struct ChannelState<M> {
+    items: VecDeque<M>,
+    lagged: u64,
+    end: Option<StreamEndReason>,
+    is_finished: bool,
+    #[cfg(feature = "async")]
+    waker: Option<Waker>,
+}
+
+/// Buffer shared by a single [MessageStream] and the emitter of its Actor
+struct Channel<M> {
+    buffer: usize,
+    lag_policy: LagPolicy,
+    state: Mutex<ChannelState<M>>,
+    is_ready: Condvar,
+}
+
+impl<M> ChannelState<M> {
+    #[cfg(feature = "async")]
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    #[cfg(not(feature = "async"))]
+    fn wake(&mut self) {}
+}
+
+impl<M> Channel<M> {
+    fn notify(&self, state: &mut ChannelState<M>) {
+        state.wake();
+        self.is_ready.notify_one();
+    }
+
+    /// Returns `false` if the stream has to be detached
+    fn push(&self, item: M) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.end.is_some() {
+            return false;
+        }
+        let is_attached = if state.items.len() < self.buffer {
+            state.items.push_back(item);
+            true
+        } else {
+            match self.lag_policy {
+                LagPolicy::DropOldest => {
+                    state.items.pop_front();
+                    state.lagged += 1;
+                    state.items.push_back(item);
+                    true
+                }
+                LagPolicy::Close => {
+                    state.end = Some(StreamEndReason::Overflowed);
+                    false
+                }
+            }
+        };
+        self.notify(&mut state);
+        is_attached
+    }
+
+    fn pop(state: &mut ChannelState<M>) -> Option<StreamItem<M>> {
+        if state.is_finished {
+            return None;
+        }
+        // the marker takes the place of the dropped items, which were older than all buffered ones
+        if state.lagged > 0 {
+            let lagged = state.lagged;
+            state.lagged = 0;
+            return Some(StreamItem::Lagged(lagged));
+        }
+        if let Some(item) = state.items.pop_front() {
+            return Some(StreamItem::Item(item));
+        }
+        state.end.map(|reason| {
+            state.is_finished = true;
+            StreamItem::End(reason)
+        })
+    }
+}
+
+/// Type erased [Channel] as seen by the emitter
+trait AttachedStream: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn end(&self, reason: StreamEndReason);
+}
+
+impl<M> AttachedStream for Channel<M>
+where
+    M: Send + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn end(&self, reason: StreamEndReason) {
+        let mut state = self.state.lock().unwrap();
+        if state.end.is_none() {
+            state.end = Some(reason);
+        }
+        self.notify(&mut state);
+    }
+}
+
+struct Attachment {
+    id: u64,
+    type_id: TypeId,
+    channel: Arc<dyn AttachedStream>,
+}
+
+#[derive(Default)]
+struct Attachments {
+    streams: Vec<Attachment>,
+    next_id: u64,
+    is_stopped: bool,
+}
+
+/// Streams attached to a single Actor, shared by its executor, context and wrappers
+#[derive(Default)]
+pub(crate) struct Emitter {
+    /// checked by every emission, so that emitting without attached streams is a single atomic load
+    attached: AtomicUsize,
+    emissions: AtomicU64,
+    attachments: Mutex<Attachments>,
+}
+
+impl Emitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe<M>(self: &Arc<Self>, buffer: usize, lag_policy: LagPolicy) -> MessageStream<M>
+    where
+        M: Send + 'static,
+    {
+        let channel = Arc::new(Channel {
+            buffer: buffer.max(1),
+            lag_policy,
+            state: Mutex::new(ChannelState {
+                items: VecDeque::new(),
+                lagged: 0,
+                end: None,
+                is_finished: false,
+                #[cfg(feature = "async")]
+                waker: None,
+            }),
+            is_ready: Condvar::new(),
+        });
+        let mut attachments = self.attachments.lock().unwrap();
+        let id = attachments.next_id;
+        attachments.next_id += 1;
+        if attachments.is_stopped {
+            channel.end(StreamEndReason::Stopped);
+        } else {
+            attachments.streams.push(Attachment {
+                id,
+                type_id: TypeId::of::<M>(),
+                channel: channel.clone(),
+            });
+            self.attached.store(attachments.streams.len(), Ordering::SeqCst);
+        }
+        MessageStream {
+            id,
+            channel,
+            emitter: self.clone(),
+        }
+    }
+
+    pub fn emit<M>(&self, item: M)
+    where
+        M: Clone + Send + 'static,
+    {
+        if self.attached.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        self.emissions.fetch_add(1, Ordering::Relaxed);
+        let mut attachments = self.attachments.lock().unwrap();
+        let type_id = TypeId::of::<M>();
+        let channels: Vec<(u64, Arc<dyn AttachedStream>)> = attachments
+            .streams
+            .iter()
+            .filter(|attachment| attachment.type_id == type_id)
+            .map(|attachment| (attachment.id, attachment.channel.clone()))
+            .collect();
+        let mut overflowed = Vec::new();
+        if let Some(((last_id, last), others)) = channels.split_last() {
+            for (id, channel) in others {
+                if !Self::downcast::<M>(channel).push(item.clone()) {
+                    overflowed.push(*id);
+                }
+            }
+            if !Self::downcast::<M>(last).push(item) {
+                overflowed.push(*last_id);
+            }
+        }
+        if !overflowed.is_empty() {
+            attachments.streams.retain(|attachment| !overflowed.contains(&attachment.id));
+            self.attached.store(attachments.streams.len(), Ordering::SeqCst);
+        }
+    }
+
+    fn downcast<M>(channel: &Arc<dyn AttachedStream>) -> &Channel<M>
+    where
+        M: Send + 'static,
+    {
+        channel.as_any().downcast_ref::<Channel<M>>().unwrap()
+    }
+
+    fn detach(&self, id: u64) {
+        let mut attachments = self.attachments.lock().unwrap();
+        attachments.streams.retain(|attachment| attachment.id != id);
+        self.attached.store(attachments.streams.len(), Ordering::SeqCst);
+    }
+
+    /// Ends all attached streams and every stream that is attached afterwards
+    pub fn stop(&self) {
+        let mut attachments = self.attachments.lock().unwrap();
+        attachments.is_stopped = true;
+        self.attached.store(0, Ordering::SeqCst);
+        for attachment in attachments.streams.drain(..) {
+            attachment.channel.end(StreamEndReason::Stopped);
+        }
+    }
+
+    pub fn get_attached(&self) -> usize {
+        self.attached.load(Ordering::Relaxed)
+    }
+
+    pub fn get_emissions(&self) -> u64 {
+        self.emissions.load(Ordering::Relaxed)
+    }
+}
+
+/// Items of type `M` emitted by an Actor through [ActorContext.emit](../prelude/struct.ActorContext.html#method.emit)
+///
+/// Created by [ActorWrapper.subscribe_stream](../prelude/struct.ActorWrapper.html#method.subscribe_stream), dropping it detaches it from the Actor.
+/// Implements `futures_core::Stream` if the `async` feature is enabled, [iter](#method.iter) blocks the current thread instead
+pub struct MessageStream<M>
+where
+    M: Send + 'static,
+{
+    id: u64,
+    channel: Arc<Channel<M>>,
+    emitter: Arc<Emitter>,
+}
+
+impl<M> MessageStream<M>
+where
+    M: Send + 'static,
+{
+    /// Blocking iterator over the stream, ends after [StreamItem::End](./enum.StreamItem.html#variant.End)
+    pub fn iter(&mut self) -> MessageStreamIter<'_, M> {
+        MessageStreamIter { stream: self }
+    }
+
+    /// Returns the next item without blocking, `None` if nothing is buffered or the stream has ended
+    pub fn try_next(&mut self) -> Option<StreamItem<M>> {
+        Channel::pop(&mut self.channel.state.lock().unwrap())
+    }
+
+    /// Returns `true` once [StreamItem::End](./enum.StreamItem.html#variant.End) has been yielded
+    pub fn is_finished(&self) -> bool {
+        self.channel.state.lock().unwrap().is_finished
+    }
+}
+
+impl<M> Drop for MessageStream<M>
+where
+    M: Send + 'static,
+{
+    fn drop(&mut self) {
+        self.emitter.detach(self.id);
+    }
+}
+
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use futures_core::Stream;
+/// use tyractorsaur::prelude::*;
+/// use std::pin::Pin;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll, Wake, Waker};
+/// use std::thread::{self, Thread};
+///
+/// struct Produce { value: u32 }
+/// impl ActorMessage for Produce {}
+///
+/// struct Ping {}
+/// impl ActorMessage for Ping {}
+///
+/// struct Producer { pings: Arc<AtomicUsize> }
+/// impl Actor for Producer {}
+/// impl Handler<Produce> for Producer {
+///     fn handle(&mut self, msg: Produce, context: &ActorContext<Self>) {
+///         context.emit(msg.value);
+///     }
+/// }
+/// impl Handler<Ping> for Producer {
+///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+///         self.pings.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// struct ProducerFactory { pings: Arc<AtomicUsize> }
+/// impl ActorFactory<Producer> for ProducerFactory {
+///     fn new_actor(&self, _context: ActorContext<Producer>) -> Producer {
+///         Producer { pings: self.pings.clone() }
+///     }
+/// }
+///
+/// // minimal executor that parks the current thread until the stream wakes it up
+/// struct Unpark(Thread);
+/// impl Wake for Unpark {
+///     fn wake(self: Arc<Self>) {
+///         self.0.unpark();
+///     }
+/// }
+/// fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+///     let waker = Waker::from(Arc::new(Unpark(thread::current())));
+///     let mut context = Context::from_waker(&waker);
+///     loop {
+///         match Pin::new(&mut *stream).poll_next(&mut context) {
+///             Poll::Ready(item) => return item,
+///             Poll::Pending => thread::park(),
+///         }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let pings = Arc::new(AtomicUsize::new(0));
+/// let producer = actor_system
+///     .builder()
+///     .spawn("producer", ProducerFactory { pings: pings.clone() })
+///     .unwrap();
+/// let mut stream = producer.subscribe_stream::<u32>(1000);
+/// let sender = producer.clone();
+/// let sending = thread::spawn(move || {
+///     for value in 0..1000 {
+///         sender.send(Produce { value });
+///         sender.send(Ping {});
+///     }
+/// });
+///
+/// let mut received = Vec::new();
+/// while received.len() < 1000 {
+///     match next(&mut stream) {
+///         Some(StreamItem::Item(value)) => received.push(value),
+///         item => panic!("unexpected {:?}", item),
+///     }
+/// }
+/// sending.join().unwrap();
+/// assert_eq!((0..1000).collect::<Vec<u32>>(), received);
+///
+/// producer.stop();
+/// assert_eq!(Some(StreamItem::End(StreamEndReason::Stopped)), next(&mut stream));
+/// assert_eq!(None, next(&mut stream));
+/// assert_eq!(1000, pings.load(Ordering::Relaxed));
+/// ```
+#[cfg(feature = "async")]
+impl<M> futures_core::Stream for MessageStream<M>
+where
+    M: Send + 'static,
+{
+    type Item = StreamItem<M>;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.channel.state.lock().unwrap();
+        match Channel::pop(&mut state) {
+            Some(item) => Poll::Ready(Some(item)),
+            None if state.is_finished => Poll::Ready(None),
+            None => {
+                state.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Blocking iterator returned by [MessageStream.iter](./struct.MessageStream.html#method.iter)
+pub struct MessageStreamIter<'a, M>
+where
+    M: Send + 'static,
+{
+    stream: &'a mut MessageStream<M>,
+}
+
+impl<'a, M> Iterator for MessageStreamIter<'a, M>
+where
+    M: Send + 'static,
+{
+    type Item = StreamItem<M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let channel = &self.stream.channel;
+        let mut state = channel.state.lock().unwrap();
+        loop {
+            if state.is_finished {
+                return None;
+            }
+            if let Some(item) = Channel::pop(&mut state) {
+                return Some(item);
+            }
+            state = channel.is_ready.wait(state).unwrap();
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Actors per thread, a full table is flushed before another Actor is added
+const SLOTS: usize = 16;
+
+/// Stripes of all threads that ever counted, dead entries are dropped whenever a thread registers
+static STRIPES: Mutex<Vec<Weak<Stripe>>> = Mutex::new(Vec::new());
+
+/// 0 marks an unused slot
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Hot counters of an Actor that are accumulated per thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Counter {
+    Processed = 0,
+    HandlerNanos = 1,
+    ConstructionNanos = 2,
+}
+
+const COUNTERS: usize = 3;
+
+/// Counters of a single Actor, holds everything that has been flushed from the stripes
+///
+/// A read merges the flushed values with the unflushed values of every stripe, see [get](#method.get)
+pub(crate) struct StripedCounters {
+    id: u64,
+    flushed: [AtomicU64; COUNTERS],
+}
+
+impl StripedCounters {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            flushed: Default::default(),
+        })
+    }
+
+    /// Adds to the stripe of the current thread, a plain load and store unless the Actor is new to this thread
+    pub fn add(self: &Arc<Self>, counter: Counter, value: u64) {
+        let added = LOCAL.try_with(|local| {
+            local
+                .borrow_mut()
+                .get_or_insert_with(LocalStripe::register)
+                .add(self, counter, value);
+        });
+        // the thread is exiting and its stripe has already been flushed
+        if added.is_err() {
+            self.flushed[counter as usize].fetch_add(value, Ordering::SeqCst);
+        }
+    }
+
+    /// Flushed value plus the unflushed values of all stripes
+    ///
+    /// Never counts a value twice: stripes are cleared before their values are added to the flushed value and are read after it.
+    /// A value that is being flushed concurrently may be missed, so the result lags behind by at most one flush of a single thread
+    pub fn get(&self, counter: Counter) -> u64 {
+        let mut value = self.flushed[counter as usize].load(Ordering::SeqCst);
+        let stripes: Vec<Arc<Stripe>> = STRIPES.lock().unwrap().iter().filter_map(Weak::upgrade).collect();
+        for stripe in stripes {
+            value += stripe.get(self.id, counter);
+        }
+        value
+    }
+
+    /// Flushes the entry of this Actor from the stripe of the current thread
+    pub fn flush_local(&self) {
+        let _ = LOCAL.try_with(|local| {
+            if let Some(local) = local.borrow_mut().as_mut() {
+                local.flush_id(self.id);
+            }
+        });
+    }
+}
+
+/// Flushes the stripe of the current thread, called by workers at the end of every scheduling slot
+pub(crate) fn flush() {
+    let _ = LOCAL.try_with(|local| {
+        if let Some(local) = local.borrow_mut().as_mut() {
+            local.flush_all();
+        }
+    });
+}
+
+/// Unflushed values of one Actor, a cache line of its own so that threads never share a line
+#[repr(align(64))]
+#[derive(Default)]
+struct Slot {
+    id: AtomicU64,
+    values: [AtomicU64; COUNTERS],
+}
+
+/// Slots of a single thread, only written by that thread
+#[derive(Default)]
+struct Stripe {
+    slots: [Slot; SLOTS],
+}
+
+impl Stripe {
+    fn get(&self, id: u64, counter: Counter) -> u64 {
+        self.slots
+            .iter()
+            .filter(|slot| slot.id.load(Ordering::SeqCst) == id)
+            .map(|slot| {
+                let value = slot.values[counter as usize].load(Ordering::SeqCst);
+                // the slot has been handed to another Actor in between
+                if slot.id.load(Ordering::SeqCst) == id {
+                    value
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Owner side of the stripe of a thread, flushes everything when the thread exits
+struct LocalStripe {
+    stripe: Arc<Stripe>,
+    owners: [Option<Arc<StripedCounters>>; SLOTS],
+    /// id and slot of the Actor that counted last, consecutive adds of a slot skip the lookup
+    last: (u64, usize),
+}
+
+impl LocalStripe {
+    fn register() -> Self {
+        let stripe = Arc::new(Stripe::default());
+        let mut stripes = STRIPES.lock().unwrap();
+        stripes.retain(|stripe| stripe.strong_count() > 0);
+        stripes.push(Arc::downgrade(&stripe));
+        Self {
+            stripe,
+            owners: Default::default(),
+            last: (0, 0),
+        }
+    }
+
+    fn add(&mut self, counters: &Arc<StripedCounters>, counter: Counter, value: u64) {
+        let index = if self.last.0 == counters.id {
+            self.last.1
+        } else {
+            let index = match self.find(counters.id) {
+                Some(index) => index,
+                None => self.claim(counters),
+            };
+            self.last = (counters.id, index);
+            index
+        };
+        let value_ref = &self.stripe.slots[index].values[counter as usize];
+        // only this thread writes the slot, release pairs with the id check of readers
+        value_ref.store(value_ref.load(Ordering::Relaxed) + value, Ordering::Release);
+    }
+
+    fn find(&self, id: u64) -> Option<usize> {
+        let start = id as usize % SLOTS;
+        (0..SLOTS)
+            .map(|probe| (start + probe) % SLOTS)
+            .take_while(|index| self.owners[*index].is_some())
+            .find(|index| self.owners[*index].as_ref().is_some_and(|owner| owner.id == id))
+    }
+
+    fn claim(&mut self, counters: &Arc<StripedCounters>) -> usize {
+        let start = counters.id as usize % SLOTS;
+        let free = (0..SLOTS)
+            .map(|probe| (start + probe) % SLOTS)
+            .find(|index| self.owners[*index].is_none());
+        let index = match free {
+            Some(index) => index,
+            None => {
+                self.flush_all();
+                start
+            }
+        };
+        self.stripe.slots[index].id.store(counters.id, Ordering::SeqCst);
+        self.owners[index] = Some(counters.clone());
+        index
+    }
+
+    fn flush_id(&mut self, id: u64) {
+        if self.find(id).is_some() {
+            // removing a single entry would break the probe sequences of the others
+            self.flush_all();
+        }
+    }
+
+    fn flush_all(&mut self) {
+        for (slot, owner) in self.stripe.slots.iter().zip(self.owners.iter_mut()) {
+            let owner = match owner.take() {
+                Some(owner) => owner,
+                None => continue,
+            };
+            for (value, flushed) in slot.values.iter().zip(owner.flushed.iter()) {
+                let value = value.swap(0, Ordering::SeqCst);
+                if value > 0 {
+                    flushed.fetch_add(value, Ordering::SeqCst);
+                }
+            }
+            slot.id.store(0, Ordering::SeqCst);
+        }
+        self.last = (0, 0);
+    }
+}
+
+impl Drop for LocalStripe {
+    fn drop(&mut self) {
+        self.flush_all();
+    }
+}
+
+thread_local! {
+    static LOCAL: RefCell<Option<LocalStripe>> = const { RefCell::new(None) };
+}
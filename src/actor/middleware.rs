@@ -0,0 +1,124 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::message::headers::Headers;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Everything a middleware can see of a message, the payload stays hidden, see [ActorMiddleware]
+#[derive(Debug, Clone, Copy)]
+pub struct MessageMeta<'a> {
+    pub message_type: &'static str,
+    /// `None` for messages that have not been queued through an [ActorWrapper](../prelude/struct.ActorWrapper.html)
+    pub enqueued: Option<Instant>,
+    /// only set for messages with a time to live
+    pub expires_at: Option<Instant>,
+    pub headers: Option<&'a Headers>,
+}
+
+/// Returned by [ActorMiddleware.before_handle](./trait.ActorMiddleware.html#method.before_handle)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiddlewareDecision {
+    /// hands the message to the next middleware, or to the handler after the last one
+    Continue,
+    /// the message is discarded silently
+    Drop,
+    /// the message is discarded and reported as dead letter with [DeadLetterReason::Rejected](../prelude/enum.DeadLetterReason.html#variant.Rejected)
+    DeadLetter,
+}
+
+/// Passed to [ActorMiddleware.after_handle](./trait.ActorMiddleware.html#method.after_handle)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleOutcome {
+    Handled,
+    /// the handler returned an [ActorError](../prelude/struct.ActorError.html), see [TryHandler](../prelude/trait.TryHandler.html)
+    Failed,
+    Panicked,
+    /// a later middleware did not let the message through, the handler has not been executed
+    Rejected,
+}
+
+/// Cross-cutting logic executed around every message an Actor handles, see [ActorBuilder.add_middleware](../prelude/struct.ActorBuilder.html#method.add_middleware)
+///
+/// Middlewares are executed on the thread of the Actor, in the order they have been added before the handler and in reverse order afterwards.
+/// Stop messages bypass them. A panicking middleware is treated like a panicking handler, the [RestartPolicy](../prelude/enum.RestartPolicy.html) of the Actor applies
+pub trait ActorMiddleware<A>: Send
+where
+    A: Actor,
+{
+    /// executed before the handler, the context already belongs to the message
+    fn before_handle(&mut self, _meta: &MessageMeta, _context: &ActorContext<A>) -> MiddlewareDecision {
+        MiddlewareDecision::Continue
+    }
+    /// executed once the handler returned or panicked, only for middlewares that let the message through
+    ///
+    /// `duration` is the time spent in the handler, zero for rejected messages
+    fn after_handle(&mut self, _meta: &MessageMeta, _duration: Duration, _outcome: HandleOutcome) {}
+}
+
+type SharedMiddleware<A> = Arc<Mutex<Box<dyn ActorMiddleware<A>>>>;
+
+/// Middlewares of an Actor, shared by all Actors spawned through the same builder
+pub(crate) struct Middlewares<A>
+where
+    A: Actor + 'static,
+{
+    chain: Vec<SharedMiddleware<A>>,
+}
+
+impl<A> Clone for Middlewares<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            chain: self.chain.clone(),
+        }
+    }
+}
+
+impl<A> Middlewares<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new() -> Self {
+        Self { chain: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chain.is_empty()
+    }
+
+    pub fn add(&mut self, middleware: Box<dyn ActorMiddleware<A>>) {
+        self.chain.push(Arc::new(Mutex::new(middleware)));
+    }
+
+    /// Executes `before_handle` until a middleware rejects the message, `passed` counts the middlewares that let it through
+    ///
+    /// Those are notified right away about a rejection, otherwise through [after](#method.after) once the handler returned
+    pub fn before(&self, meta: &MessageMeta, context: &ActorContext<A>, passed: &mut usize) -> MiddlewareDecision {
+        for middleware in &self.chain {
+            let decision = lock(middleware).before_handle(meta, context);
+            if decision != MiddlewareDecision::Continue {
+                self.after(*passed, meta, Duration::ZERO, HandleOutcome::Rejected);
+                return decision;
+            }
+            *passed += 1;
+        }
+        MiddlewareDecision::Continue
+    }
+
+    /// Executes `after_handle` of the first `passed` middlewares in reverse order
+    pub fn after(&self, passed: usize, meta: &MessageMeta, duration: Duration, outcome: HandleOutcome) {
+        for middleware in self.chain[..passed].iter().rev() {
+            lock(middleware).after_handle(meta, duration, outcome);
+        }
+    }
+}
+
+/// A middleware that panicked is used again by the restarted Actor
+fn lock<A>(middleware: &SharedMiddleware<A>) -> MutexGuard<'_, Box<dyn ActorMiddleware<A>>>
+where
+    A: Actor,
+{
+    middleware.lock().unwrap_or_else(PoisonError::into_inner)
+}
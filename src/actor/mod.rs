@@ -1,21 +1,112 @@
+#[allow(clippy::module_inception)]
 pub mod actor;
 pub mod actor_address;
 pub mod actor_builder;
 pub mod actor_config;
 pub mod actor_factory;
+pub mod actor_metrics;
 pub mod actor_state;
 pub mod actor_wrapper;
+pub mod batch_handler;
+pub mod behavior;
 pub mod context;
+pub mod control_flow;
+pub mod debug_snapshot;
+pub mod effect_guard;
+pub mod execute_fn;
 pub mod executor;
+pub mod extraction;
 pub mod handler;
+pub mod handoff;
+pub mod journal;
 pub mod mailbox;
+pub mod mailbox_bytes;
+pub(crate) mod message_class;
+pub(crate) mod message_registry;
+pub mod message_stream;
+pub(crate) mod metric_stripes;
+pub mod middleware;
+pub mod panic_report;
+pub mod pressure;
+pub mod priority;
+pub mod protocol;
+pub mod rate_limit;
+pub mod recovery;
+pub mod relocation;
+pub mod replacement;
+pub mod reply;
+pub mod request_chain;
+pub(crate) mod retry;
+pub mod retry_policy;
+pub mod rng;
+#[cfg(feature = "async")]
+pub mod send_async;
+pub mod send_batch;
+pub mod slo;
+pub(crate) mod stash;
+pub mod state_capture;
+pub mod supersession;
+pub mod supervision;
+pub mod suspension;
+pub mod throughput;
+pub mod timer;
+pub mod transaction;
+pub mod try_handler;
+pub mod unhandled;
+pub mod weak_actor_wrapper;
 
 pub mod prelude {
     pub use crate::actor::actor::Actor;
-    pub use crate::actor::actor_config::RestartPolicy;
+    pub use crate::actor::actor_address::{ActorAddress, ResolveError};
+    pub use crate::actor::actor_metrics::ActorStats;
+    pub use crate::actor::actor_config::{
+        ActorConfig, ActorConfigSources, ActorDefaults, BackoffStrategy, ConfigSource, FailedMessageStrategy,
+        MailboxOverflowPolicy, PanicEscalation, ResolvedActorConfig, RestartMailboxPolicy, RestartPolicy, SystemStopOrder,
+        DEFAULT_SHUTDOWN_PHASE,
+    };
     pub use crate::actor::actor_wrapper::ActorWrapper;
     pub use crate::actor::actor_builder::ActorBuilder;
-    pub use crate::actor::actor_factory::ActorFactory;
+    pub use crate::actor::actor_factory::{ActorFactory, ActorInitError, TryActorFactory};
+    pub use crate::actor::batch_handler::BatchHandler;
+    pub use crate::actor::behavior::{Behavior, BehaviorError};
     pub use crate::actor::context::ActorContext;
+    pub use crate::actor::effect_guard::{EffectError, EffectGuard, EffectLedger, EffectOutcome, MemoryEffectLedger};
+    pub use crate::actor::execute_fn::ExecuteFn;
+    pub use crate::actor::extraction::ExtractError;
     pub use crate::actor::handler::Handler;
+    pub use crate::actor::handoff::{HandoffError, HandoffMode, HandoffReport};
+    pub use crate::actor::journal::{JournalEntry, JournalEvent, JournalSink, PANIC_REPORT_JOURNAL_ENTRIES};
+    pub use crate::actor::debug_snapshot::{DebuggableActor, SnapshotError};
+    pub use crate::actor::middleware::{ActorMiddleware, HandleOutcome, MessageMeta, MiddlewareDecision};
+    pub use crate::actor::message_stream::{LagPolicy, MessageStream, MessageStreamIter, StreamEndReason, StreamItem};
+    pub use crate::actor::panic_report::{ActorPanicReport, CapturedPayload, DebugCapture, PayloadFormat};
+    pub use crate::actor::pressure::{MailboxPressure, MailboxRelieved, PressureLevel, Watermarks};
+    pub use crate::actor::priority::ActorPriority;
+    pub use crate::actor::protocol::{ActorProtocol, ProtocolError, RemoteProtocols};
+    pub use crate::actor::rate_limit::RateLimit;
+    pub use crate::actor::recovery::{MemorySnapshotStore, RecoverableActor, SnapshotStore};
+    pub use crate::actor::relocation::MoveError;
+    pub use crate::actor::replacement::ReplaceError;
+    pub use crate::actor::reply::{ActorAddressHandle, ReplyTo};
+    pub use crate::actor::request_chain::{ChainError, CompletedRequestChain, RequestChain, RequestHandler};
+    pub use crate::actor::retry_policy::RetryPolicy;
+    pub use crate::actor::rng::{ActorRng, RngReseed};
+    #[cfg(feature = "async")]
+    pub use crate::actor::send_async::SendAsync;
+    pub use crate::actor::send_batch::{BatchSendStatus, SendBatch, SendBatchReport};
+    pub use crate::actor::slo::{LatencyHistogram, SloConfig, SloEvent, SloReport, SloStatus, SloWindowStats};
+    pub use crate::actor::state_capture::{ByteSummary, CaptureDiff, CaptureError, FieldChange, SnapshotFormat, StateCapture};
+    pub use crate::actor::supersession::Supersedes;
+    pub use crate::actor::supervision::{ChildTerminated, ChildTerminationReason, Terminated};
+    pub use crate::actor::suspension::{SuspendCondition, SuspensionEndReason, SuspensionToken};
+    pub use crate::actor::throughput::{AdaptiveThroughput, Throughput};
+    pub use crate::actor::timer::ScheduledHandle;
+    pub use crate::actor::transaction::{
+        PrepareVote, Transaction, TransactionConverged, TransactionalHandler, TxnDecision, TxnOutcome,
+        TRANSACTION_TOPIC,
+    };
+    pub use crate::actor::control_flow::{ActorResult, ControlFlowHandler, ControlFlowMessage};
+    pub use crate::actor::try_handler::{ActorError, FallibleMessage, TryHandler};
+    pub use crate::actor::unhandled::UnhandledInfo;
+    pub use crate::actor::weak_actor_wrapper::WeakActorWrapper;
 }
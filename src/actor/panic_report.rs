@@ -0,0 +1,231 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::journal::JournalEntry;
+use crate::actor::try_handler::ActorError;
+use crate::message::actor_message::ActorMessage;
+use crate::message::provenance::Provenance;
+use crate::message::trace::TraceId;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+/// Marks messages whose `Debug` rendering may be attached to an [ActorPanicReport](./struct.ActorPanicReport.html)
+///
+/// See [ActorSystem.register_debug_capture](../prelude/struct.ActorSystem.html#method.register_debug_capture)
+pub trait DebugCapture: ActorMessage + Debug {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PayloadFormat {
+    /// produced by the serializer registered through [ActorSystem.register_payload_serializer](../prelude/struct.ActorSystem.html#method.register_payload_serializer)
+    Serialized,
+    /// UTF-8 `Debug` rendering of a [DebugCapture](./trait.DebugCapture.html) message
+    Debug,
+}
+
+/// A single message captured for an [ActorPanicReport](./struct.ActorPanicReport.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedPayload {
+    pub message_type: &'static str,
+    pub format: PayloadFormat,
+    pub bytes: Vec<u8>,
+    /// `bytes` has been cut at `general.max_captured_payload_bytes`
+    pub truncated: bool,
+}
+
+/// Handed to [Actor.on_panic](../prelude/trait.Actor.html#method.on_panic) when a handler panics
+///
+/// Payloads are only captured if `general.max_captured_payload_bytes` is greater than `0` and the message type is registered, otherwise the report only contains the type name
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::alloc::{GlobalAlloc, Layout, System};
+/// use std::cell::Cell;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::{sleep, ThreadId};
+/// use std::time::Duration;
+///
+/// thread_local! {
+///     static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+/// }
+/// struct CountingAllocator;
+/// unsafe impl GlobalAlloc for CountingAllocator {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+///         System.alloc(layout)
+///     }
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         System.dealloc(ptr, layout)
+///     }
+/// }
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// struct Order { id: u64 }
+/// impl ActorMessage for Order {}
+///
+/// // counts allocations of the executing thread between two consecutive handled messages
+/// struct Shop { last: Option<(ThreadId, usize)>, between: Arc<Mutex<usize>> }
+/// impl Actor for Shop {}
+/// impl Handler<Order> for Shop {
+///     fn handle(&mut self, _msg: Order, _context: &ActorContext<Self>) {
+///         let now = ALLOCATIONS.with(|count| count.get());
+///         let thread = std::thread::current().id();
+///         if let Some((last_thread, last)) = self.last {
+///             if last_thread == thread {
+///                 *self.between.lock().unwrap() += now - last;
+///             }
+///         }
+///         self.last = Some((thread, ALLOCATIONS.with(|count| count.get())));
+///     }
+/// }
+/// struct ShopFactory { between: Arc<Mutex<usize>> }
+/// impl ActorFactory<Shop> for ShopFactory {
+///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+///         Shop { last: None, between: self.between.clone() }
+///     }
+/// }
+///
+/// fn allocations_between_messages(max_captured_payload_bytes: usize) -> usize {
+///     let mut config = TyractorsaurConfig::new().unwrap();
+///     config.general.max_captured_payload_bytes = max_captured_payload_bytes;
+///     let actor_system = ActorSystem::new(config);
+///     actor_system.register_payload_serializer(|msg: &Order| msg.id.to_le_bytes().to_vec());
+///     let between = Arc::new(Mutex::new(0));
+///     let shop = actor_system.builder().spawn("shop", ShopFactory { between: between.clone() }).unwrap();
+///     for id in 0..1000 {
+///         shop.send(Order { id });
+///     }
+///     sleep(Duration::from_millis(200));
+///     let between = *between.lock().unwrap();
+///     between
+/// }
+///
+/// // disabled capture does not allocate per message, every captured message needs at least one allocation
+/// assert!(allocations_between_messages(0) < 100);
+/// assert!(allocations_between_messages(64) >= 900);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorPanicReport {
+    pub address: ActorAddress,
+    pub message_type: &'static str,
+    /// the panic message, if the panic was raised with a string
+    pub panic_message: Option<String>,
+    /// returned by a [TryHandler](../prelude/trait.TryHandler.html) instead of a panic
+    pub error: Option<ActorError>,
+    /// the message that was handled when the panic occurred
+    pub payload: Option<CapturedPayload>,
+    /// the messages handled before, oldest first, see [ActorBuilder.set_capture_last_n_messages](../prelude/struct.ActorBuilder.html#method.set_capture_last_n_messages)
+    pub recent: Vec<CapturedPayload>,
+    /// chain of the message that was handled, if it is [Audited](../prelude/trait.Audited.html)
+    pub provenance: Option<Provenance>,
+    /// trace id of the message that was handled, see [ActorWrapper.send_traced](../prelude/struct.ActorWrapper.html#method.send_traced)
+    pub trace_id: Option<TraceId>,
+    /// the last [PANIC_REPORT_JOURNAL_ENTRIES](./constant.PANIC_REPORT_JOURNAL_ENTRIES.html) entries of the journal, oldest first, see [ActorBuilder.set_journal_capacity](../prelude/struct.ActorBuilder.html#method.set_journal_capacity)
+    pub journal: Vec<JournalEntry>,
+}
+
+type Capturer = Arc<dyn Fn(&dyn Any) -> (PayloadFormat, Vec<u8>) + Send + Sync>;
+
+/// Serializers and `Debug` renderings used to capture payloads, shared by all Actors of a system
+#[derive(Clone)]
+pub struct PayloadCaptureRegistry {
+    capturers: Arc<RwLock<HashMap<TypeId, Capturer>>>,
+}
+
+impl PayloadCaptureRegistry {
+    pub fn new() -> Self {
+        Self {
+            capturers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn register_serializer<M, F>(&self, serializer: F)
+    where
+        M: ActorMessage + 'static,
+        F: Fn(&M) -> Vec<u8> + Send + Sync + 'static,
+    {
+        let capturer: Capturer = Arc::new(move |msg: &dyn Any| {
+            (PayloadFormat::Serialized, serializer(msg.downcast_ref::<M>().unwrap()))
+        });
+        self.capturers.write().unwrap().insert(TypeId::of::<M>(), capturer);
+    }
+
+    pub fn register_debug<M>(&self)
+    where
+        M: DebugCapture + 'static,
+    {
+        let capturer: Capturer = Arc::new(|msg: &dyn Any| {
+            let msg = msg.downcast_ref::<M>().unwrap();
+            (PayloadFormat::Debug, format!("{:?}", msg).into_bytes())
+        });
+        // a registered serializer takes precedence
+        self.capturers
+            .write()
+            .unwrap()
+            .entry(TypeId::of::<M>())
+            .or_insert(capturer);
+    }
+
+    /// Returns `None` if the type of the message is not registered
+    pub fn capture<M>(&self, msg: &M, max_bytes: usize) -> Option<CapturedPayload>
+    where
+        M: 'static,
+    {
+        let capturer = self.capturers.read().unwrap().get(&TypeId::of::<M>())?.clone();
+        let (format, mut bytes) = capturer(msg);
+        let truncated = bytes.len() > max_bytes;
+        bytes.truncate(max_bytes);
+        Some(CapturedPayload {
+            message_type: std::any::type_name::<M>(),
+            format,
+            bytes,
+            truncated,
+        })
+    }
+}
+
+impl Default for PayloadCaptureRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps the payloads of the last handled messages of an Actor
+pub(crate) struct CaptureRing {
+    capacity: usize,
+    payloads: VecDeque<CapturedPayload>,
+}
+
+impl CaptureRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            payloads: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, payload: CapturedPayload) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.payloads.len() == self.capacity {
+            self.payloads.pop_front();
+        }
+        self.payloads.push_back(payload);
+    }
+
+    pub fn take(&mut self) -> Vec<CapturedPayload> {
+        self.payloads.drain(..).collect()
+    }
+}
+
+pub(crate) fn panic_message(panic: &(dyn Any + Send)) -> Option<String> {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return Some(String::from(*message));
+    }
+    panic.downcast_ref::<String>().cloned()
+}
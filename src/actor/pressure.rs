@@ -0,0 +1,167 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Queue lengths at which the pressure of a mailbox changes, see [ActorBuilder.set_watermarks](../prelude/struct.ActorBuilder.html#method.set_watermarks)
+///
+/// Pressure becomes [PressureLevel::High](./enum.PressureLevel.html#variant.High) once `high` messages are queued and returns to `Normal` once the queue shrank to `low`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Watermarks {
+    pub high: usize,
+    pub low: usize,
+}
+
+impl Watermarks {
+    /// Panics if `low` is not below `high`
+    pub fn new(high: usize, low: usize) -> Self {
+        assert!(low < high, "the low watermark must be below the high watermark");
+        Self { high, low }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureLevel {
+    Normal,
+    High,
+}
+
+/// Sent to Actors that watch the pressure of another Actor through [ActorContext.watch_pressure](../prelude/struct.ActorContext.html#method.watch_pressure)
+///
+/// Levels strictly alternate, starting with `High`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailboxPressure {
+    pub address: ActorAddress,
+    pub level: PressureLevel,
+}
+
+impl ActorMessage for MailboxPressure {}
+
+/// Sent to Actors registered through [ActorWrapper.notify_when_below_watermark](../prelude/struct.ActorWrapper.html#method.notify_when_below_watermark) once the queue of the Actor at `address` shrank to its low watermark
+///
+/// Sent exactly once for every return from `High` to `Normal`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailboxRelieved {
+    pub address: ActorAddress,
+}
+
+impl ActorMessage for MailboxRelieved {}
+
+type PressureWatcher = Box<dyn Fn(MailboxPressure) + Send + Sync>;
+
+/// Queue length and pressure level of a mailbox with watermarks
+///
+/// Every counted message holds a `PressureToken`, so that the length stays correct no matter how a message leaves the mailbox
+pub(crate) struct PressureState {
+    address: ActorAddress,
+    watermarks: Watermarks,
+    queued: AtomicUsize,
+    is_high: AtomicBool,
+    /// serializes level changes and their notifications, so that watchers receive them in order
+    watchers: Mutex<Watchers>,
+}
+
+struct Watchers {
+    /// keyed by the watching Actor and the message it is notified with
+    watchers: Vec<((ActorAddress, TypeId), PressureWatcher)>,
+    is_cleared: bool,
+}
+
+impl PressureState {
+    pub fn new(address: ActorAddress, watermarks: Watermarks) -> Self {
+        Self {
+            address,
+            watermarks,
+            queued: AtomicUsize::new(0),
+            is_high: AtomicBool::new(false),
+            watchers: Mutex::new(Watchers {
+                watchers: Vec::new(),
+                is_cleared: false,
+            }),
+        }
+    }
+
+    pub fn level(&self) -> PressureLevel {
+        if self.is_high.load(Ordering::SeqCst) {
+            PressureLevel::High
+        } else {
+            PressureLevel::Normal
+        }
+    }
+
+    /// Registers every watching Actor only once per notification message `N`, even if it watches again after a restart
+    pub fn watch<N>(&self, address: &ActorAddress, watcher: PressureWatcher)
+    where
+        N: 'static,
+    {
+        let key = (address.clone(), TypeId::of::<N>());
+        let mut watchers = self.watchers.lock().unwrap();
+        if !watchers.is_cleared && !watchers.watchers.iter().any(|(watching, _)| *watching == key) {
+            watchers.watchers.push((key, watcher));
+        }
+    }
+
+    /// The Actor stopped, its pressure stays `Normal` from now on
+    pub fn clear(&self) {
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers.is_cleared = true;
+        watchers.watchers.clear();
+        self.is_high.store(false, Ordering::SeqCst);
+    }
+
+    fn on_enqueue(&self) {
+        if self.queued.fetch_add(1, Ordering::SeqCst) + 1 == self.watermarks.high {
+            self.evaluate();
+        }
+    }
+
+    fn on_dequeue(&self) {
+        if self.queued.fetch_sub(1, Ordering::SeqCst) - 1 == self.watermarks.low {
+            self.evaluate();
+        }
+    }
+
+    /// Decides on the current length instead of the crossing that triggered it, so that racing crossings cannot leave a stale level behind
+    fn evaluate(&self) {
+        let watchers = self.watchers.lock().unwrap();
+        if watchers.is_cleared {
+            return;
+        }
+        let queued = self.queued.load(Ordering::SeqCst);
+        let level = if queued >= self.watermarks.high {
+            PressureLevel::High
+        } else if queued <= self.watermarks.low {
+            PressureLevel::Normal
+        } else {
+            return;
+        };
+        if level == self.level() {
+            return;
+        }
+        self.is_high.store(level == PressureLevel::High, Ordering::SeqCst);
+        for (_, watcher) in watchers.watchers.iter() {
+            watcher(MailboxPressure {
+                address: self.address.clone(),
+                level,
+            });
+        }
+    }
+}
+
+/// Counts a queued message towards the pressure of a mailbox until it is dropped
+pub(crate) struct PressureToken(Arc<PressureState>);
+
+impl PressureToken {
+    pub fn new(state: &Arc<PressureState>) -> Self {
+        state.on_enqueue();
+        Self(state.clone())
+    }
+}
+
+impl Drop for PressureToken {
+    fn drop(&mut self) {
+        self.0.on_dequeue();
+    }
+}
@@ -0,0 +1,156 @@
+use crate::message::envelope::MessageEnvelope;
+use crate::message::headers::{HeaderKey, HeaderValue, Headers};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Scheduling priority of an [Actor](../prelude/trait.Actor.html) within its thread pool
+///
+/// Runnable Actors with a higher priority are always picked first
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ActorPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl ActorPriority {
+    pub(crate) const COUNT: usize = 3;
+
+    pub(crate) fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn from_u64(value: u64) -> Option<Self> {
+        match value {
+            0 => Some(ActorPriority::Low),
+            1 => Some(ActorPriority::Normal),
+            2 => Some(ActorPriority::High),
+            _ => None,
+        }
+    }
+}
+
+/// Built-in headers, registered by every system before any user defined header
+pub(crate) const PRIORITY_HEADER: (&str, HeaderKey) = ("tyractorsaur.priority", HeaderKey(0));
+pub(crate) const CHAIN_HEADER: (&str, HeaderKey) = ("tyractorsaur.chain", HeaderKey(1));
+pub(crate) const DEPTH_HEADER: (&str, HeaderKey) = ("tyractorsaur.inheritance_depth", HeaderKey(2));
+
+static NEXT_CHAIN: AtomicU64 = AtomicU64::new(1);
+
+/// Starts a new request chain on the headers propagated by an Actor with a raised priority
+///
+/// Existing chains are continued instead
+pub(crate) fn originate(propagated: Option<Headers>, priority: ActorPriority, max_depth: u64) -> Option<Headers> {
+    if priority <= ActorPriority::Normal || max_depth == 0 {
+        return propagated;
+    }
+    let mut headers = propagated.unwrap_or_default();
+    if headers.get(PRIORITY_HEADER.1).is_none() {
+        let chain = NEXT_CHAIN.fetch_add(1, Ordering::Relaxed);
+        headers.insert(PRIORITY_HEADER.1, HeaderValue::U64(priority as u64));
+        headers.insert(CHAIN_HEADER.1, HeaderValue::U64(chain));
+        headers.insert(DEPTH_HEADER.1, HeaderValue::U64(max_depth));
+    }
+    Some(headers)
+}
+
+/// Returns the inherited priority and the chain of a message, if it boosts an Actor with the given priority
+pub(crate) fn inherited(headers: Option<&Headers>, own: ActorPriority) -> Option<(ActorPriority, u64)> {
+    let headers = headers?;
+    let priority = match headers.get(PRIORITY_HEADER.1)? {
+        HeaderValue::U64(value) => ActorPriority::from_u64(*value)?,
+        _ => return None,
+    };
+    let chain = match headers.get(CHAIN_HEADER.1)? {
+        HeaderValue::U64(chain) => *chain,
+        _ => return None,
+    };
+    match headers.get(DEPTH_HEADER.1)? {
+        HeaderValue::U64(depth) if *depth > 0 && priority > own => Some((priority, chain)),
+        _ => None,
+    }
+}
+
+struct Boosts<A> {
+    queue: VecDeque<(u64, MessageEnvelope<A>)>,
+    chains: HashMap<u64, (ActorPriority, usize)>,
+    since: Option<Instant>,
+}
+
+/// Own and inherited priority of an Actor, shared between all clones of its [Mailbox](./struct.Mailbox.html)
+pub struct PriorityState<A> {
+    own: ActorPriority,
+    boosts: Mutex<Boosts<A>>,
+}
+
+impl<A> PriorityState<A> {
+    pub fn new(own: ActorPriority) -> Self {
+        Self {
+            own,
+            boosts: Mutex::new(Boosts {
+                queue: VecDeque::new(),
+                chains: HashMap::new(),
+                since: None,
+            }),
+        }
+    }
+
+    pub fn get_own(&self) -> ActorPriority {
+        self.own
+    }
+
+    /// Own priority raised by all chains that still have boosted messages in the mailbox or in progress
+    pub fn get_effective(&self) -> ActorPriority {
+        let boosts = self.boosts.lock().unwrap();
+        boosts
+            .chains
+            .values()
+            .map(|(priority, _)| *priority)
+            .fold(self.own, |effective, priority| effective.max(priority))
+    }
+
+    pub fn has_boosted(&self) -> bool {
+        !self.boosts.lock().unwrap().queue.is_empty()
+    }
+
+    /// Queues a message ahead of the regular mailbox and raises the priority until it has been handled
+    pub(crate) fn push(&self, priority: ActorPriority, chain: u64, msg: MessageEnvelope<A>) {
+        let mut boosts = self.boosts.lock().unwrap();
+        let entry = boosts.chains.entry(chain).or_insert((priority, 0));
+        entry.0 = entry.0.max(priority);
+        entry.1 += 1;
+        if boosts.since.is_none() {
+            boosts.since = Some(Instant::now());
+        }
+        boosts.queue.push_back((chain, msg));
+    }
+
+    pub(crate) fn pop(&self) -> Option<(u64, MessageEnvelope<A>)> {
+        self.boosts.lock().unwrap().queue.pop_front()
+    }
+
+    /// Removes all queued boosted messages, the boost itself is released through [release](#method.release)
+    pub(crate) fn drain(&self) -> Vec<(u64, MessageEnvelope<A>)> {
+        self.boosts.lock().unwrap().queue.drain(..).collect()
+    }
+
+    /// Called once a boosted message of the chain has been handled
+    ///
+    /// Returns the time spent boosted, once no chain boosts the Actor anymore
+    pub(crate) fn release(&self, chain: u64) -> Option<Duration> {
+        let mut boosts = self.boosts.lock().unwrap();
+        if let Some(entry) = boosts.chains.get_mut(&chain) {
+            entry.1 -= 1;
+            if entry.1 == 0 {
+                boosts.chains.remove(&chain);
+            }
+        }
+        if !boosts.chains.is_empty() {
+            return None;
+        }
+        boosts.since.take().map(|since| since.elapsed())
+    }
+}
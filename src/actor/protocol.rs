@@ -0,0 +1,193 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::serialized_message::SerializedMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// Converts the content of a [SerializedMessage](../prelude/struct.SerializedMessage.html) from one protocol version to the next
+pub(crate) type ProtocolUpgrade = Arc<dyn Fn(Vec<u8>) -> Vec<u8> + Send + Sync>;
+
+/// Protocol version an Actor speaks and the versions of [SerializedMessage](../prelude/struct.SerializedMessage.html)s it accepts, see [ActorBuilder.protocol_version](../prelude/struct.ActorBuilder.html#method.protocol_version)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActorProtocol {
+    pub version: u32,
+    pub accepts: RangeInclusive<u32>,
+}
+
+impl ActorProtocol {
+    /// Panics if `version` is not part of `accepts`
+    pub fn new(version: u32, accepts: RangeInclusive<u32>) -> Self {
+        assert!(accepts.contains(&version), "an Actor must accept its own protocol version");
+        Self { version, accepts }
+    }
+
+    /// Fails with the accepted range if the version is not accepted
+    pub fn check(&self, version: u32) -> Result<(), ProtocolError> {
+        if self.accepts.contains(&version) {
+            return Ok(());
+        }
+        Err(ProtocolError::UnsupportedVersion {
+            version,
+            min: *self.accepts.start(),
+            max: *self.accepts.end(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProtocolError {
+    /// the Actor only accepts versions from `min` to `max`
+    UnsupportedVersion { version: u32, min: u32, max: u32 },
+    /// the Actor accepts the version, but has no upgrade from `from` to the next version
+    MissingUpgrade { from: u32 },
+    /// no Actor is running at the address
+    ActorNotFound,
+    /// the address belongs to another system that is neither a peer nor reachable through a transport, see [ActorSystem.register_peer](../prelude/struct.ActorSystem.html#method.register_peer)
+    ForeignSystem,
+    /// the content and headers of the message exceed the message size limit of the receiving system
+    PayloadTooLarge { size: usize, limit: usize },
+    /// the content could not be deserialized into the message type registered for the tag, see [ActorBuilder.register_serialized](../prelude/struct.ActorBuilder.html#method.register_serialized)
+    Undecodable { type_tag: u16 },
+    /// the message has no `reply_to` address to send the response to, see [ActorContext.reply_serialized](../prelude/struct.ActorContext.html#method.reply_serialized)
+    MissingReplyTo,
+}
+
+/// Protocol of a running Actor together with its upgrade chain
+pub(crate) struct ProtocolEntry {
+    protocol: ActorProtocol,
+    upgrades: BTreeMap<u32, ProtocolUpgrade>,
+}
+
+impl ProtocolEntry {
+    pub fn new(protocol: ActorProtocol, upgrades: BTreeMap<u32, ProtocolUpgrade>) -> Self {
+        Self { protocol, upgrades }
+    }
+
+    pub fn get_protocol(&self) -> &ActorProtocol {
+        &self.protocol
+    }
+
+    /// Rejects unaccepted versions and upgrades older versions step by step to the version of the Actor
+    ///
+    /// Messages without a version are passed on unchanged
+    pub fn upgrade(&self, mut msg: SerializedMessage) -> Result<SerializedMessage, ProtocolError> {
+        let mut version = match msg.version {
+            Some(version) => version,
+            None => return Ok(msg),
+        };
+        self.protocol.check(version)?;
+        while version < self.protocol.version {
+            let upgrade = self
+                .upgrades
+                .get(&version)
+                .ok_or(ProtocolError::MissingUpgrade { from: version })?;
+            msg.content = upgrade(msg.content);
+            version += 1;
+        }
+        msg.version = Some(version);
+        Ok(msg)
+    }
+}
+
+/// Protocols of the Actors of a remote system, built from its [ActorSystem.protocol_table](../prelude/struct.ActorSystem.html#method.protocol_table)
+///
+/// Allows to reject a send of an unsupported version before it leaves the local system
+#[derive(Debug, Clone, Default)]
+pub struct RemoteProtocols {
+    protocols: HashMap<ActorAddress, ActorProtocol>,
+    max_message_bytes: Option<usize>,
+}
+
+impl RemoteProtocols {
+    pub fn new(protocol_table: Vec<(ActorAddress, ActorProtocol)>) -> Self {
+        Self {
+            protocols: protocol_table.into_iter().collect(),
+            max_message_bytes: None,
+        }
+    }
+
+    /// Message size limit of the remote system, its `general.max_message_bytes`
+    pub fn with_max_message_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_message_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn get(&self, address: &ActorAddress) -> Option<&ActorProtocol> {
+        self.protocols.get(address)
+    }
+
+    /// Actors without a declared protocol accept every version
+    pub fn check(&self, address: &ActorAddress, version: u32) -> Result<(), ProtocolError> {
+        match self.protocols.get(address) {
+            Some(protocol) => protocol.check(version),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the payload size against the limit of the remote system and the version, if set, against the protocol of the Actor
+    ///
+    /// The receiving system checks the payload size again, see [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Inbox { received: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Inbox {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.received.lock().unwrap().push(msg.content.len());
+    ///     }
+    /// }
+    /// struct InboxFactory { received: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Inbox> for InboxFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Inbox>) -> Inbox {
+    ///         Inbox { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_message_bytes = Some(1024);
+    /// let receiving = ActorSystem::new(config);
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let inbox = receiving.builder().spawn("inbox", InboxFactory { received: received.clone() }).unwrap();
+    /// let address = inbox.get_address().clone();
+    ///
+    /// // the transport of the receiving system answers every refused frame with the error
+    /// let peer_errors = Arc::new(Mutex::new(Vec::new()));
+    /// let transport = |msg: SerializedMessage| {
+    ///     if let Err(error) = receiving.try_send_to_address(&address, msg) {
+    ///         peer_errors.lock().unwrap().push(error);
+    ///     }
+    /// };
+    /// transport(SerializedMessage::new(vec![0; 2000]));
+    /// transport(SerializedMessage::new(vec![0; 1000]));
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![ProtocolError::PayloadTooLarge { size: 2000, limit: 1024 }], *peer_errors.lock().unwrap());
+    /// assert_eq!(vec![1000], *received.lock().unwrap());
+    ///
+    /// // the peer rejects oversized sends before they leave its system
+    /// let remote = RemoteProtocols::new(receiving.protocol_table()).with_max_message_bytes(1024);
+    /// let msg = SerializedMessage::with_headers(vec![0; 1000], vec![0; 100]);
+    /// assert_eq!(Err(ProtocolError::PayloadTooLarge { size: 1100, limit: 1024 }), remote.check_message(&address, &msg));
+    /// assert_eq!(Ok(()), remote.check_message(&address, &SerializedMessage::new(vec![0; 1000])));
+    /// ```
+    pub fn check_message(&self, address: &ActorAddress, msg: &SerializedMessage) -> Result<(), ProtocolError> {
+        if let Some(limit) = self.max_message_bytes {
+            let size = msg.payload_size();
+            if size > limit {
+                return Err(ProtocolError::PayloadTooLarge { size, limit });
+            }
+        }
+        match msg.version {
+            Some(version) => self.check(address, version),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Maximum amount of messages an Actor handles within every window of length `per`
+///
+/// See [ActorBuilder.set_rate_limit](../prelude/struct.ActorBuilder.html#method.set_rate_limit)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub max_messages: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(max_messages: u32, per: Duration) -> Self {
+        Self { max_messages, per }
+    }
+}
+
+/// Permits of an Actor with a rate limit, a window starts with the first message after the previous one ended
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    /// `None` until the first message has been handled
+    window_start: Option<Instant>,
+    used: usize,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            window_start: None,
+            used: 0,
+        }
+    }
+
+    fn is_window_over(&self, now: Instant) -> bool {
+        self.window_start
+            .is_none_or(|window_start| now.saturating_duration_since(window_start) >= self.limit.per)
+    }
+
+    /// Messages that can still be handled within the current window
+    pub fn get_remaining(&self, now: Instant) -> usize {
+        let max_messages = self.limit.max_messages.max(1) as usize;
+        match self.is_window_over(now) {
+            true => max_messages,
+            false => max_messages.saturating_sub(self.used),
+        }
+    }
+
+    /// End of the current window if all of its permits have been used
+    pub fn get_exhausted_until(&self, now: Instant) -> Option<Instant> {
+        match self.get_remaining(now) {
+            0 => self.window_start.map(|window_start| window_start + self.limit.per),
+            _ => None,
+        }
+    }
+
+    pub fn record(&mut self, handled: usize, now: Instant) {
+        if self.is_window_over(now) {
+            self.window_start = Some(now);
+            self.used = 0;
+        }
+        self.used += handled;
+    }
+}
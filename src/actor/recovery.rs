@@ -0,0 +1,183 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Keeps the state of an Actor across restarts, see [ActorBuilder.set_recovery](../prelude/struct.ActorBuilder.html#method.set_recovery)
+///
+/// Snapshots are taken on the thread of the Actor between two messages, every `snapshot_interval` handled messages and when the Actor stops gracefully.
+/// After a panic the restarted Actor recovers from the latest snapshot before `pre_start`, messages handled after that snapshot are lost
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Add { crash: bool }
+/// impl ActorMessage for Add {}
+///
+/// struct Counter {
+///     count: u64,
+///     seen: Arc<AtomicUsize>,
+/// }
+/// impl Actor for Counter {}
+/// impl Handler<Add> for Counter {
+///     fn handle(&mut self, msg: Add, _context: &ActorContext<Self>) {
+///         if msg.crash {
+///             panic!("crash");
+///         }
+///         self.count += 1;
+///         self.seen.store(self.count as usize, Ordering::Relaxed);
+///     }
+/// }
+/// impl RecoverableActor for Counter {
+///     fn recovery_snapshot(&self) -> Option<Vec<u8>> {
+///         Some(self.count.to_be_bytes().to_vec())
+///     }
+///     fn recover(&mut self, snapshot: Vec<u8>) {
+///         let mut bytes = [0; 8];
+///         bytes.copy_from_slice(&snapshot);
+///         self.count = u64::from_be_bytes(bytes);
+///     }
+/// }
+///
+/// struct CounterFactory {
+///     seen: Arc<AtomicUsize>,
+/// }
+/// impl ActorFactory<Counter> for CounterFactory {
+///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+///         Counter { count: 0, seen: self.seen.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let seen = Arc::new(AtomicUsize::new(0));
+/// let counter = actor_system
+///     .builder()
+///     .set_restart_policy(RestartPolicy::Always)
+///     .set_recovery(1)
+///     .spawn("counter", CounterFactory { seen: seen.clone() })
+///     .unwrap();
+///
+/// for i in 0..10 {
+///     counter.send(Add { crash: i == 5 }).unwrap();
+/// }
+/// sleep(Duration::from_millis(200));
+///
+/// // the restarted Actor continues counting where the crashed one stopped
+/// assert_eq!(9, seen.load(Ordering::Relaxed));
+/// assert_eq!(1, actor_system.actor_stats(counter.get_address()).unwrap().restarts);
+/// ```
+pub trait RecoverableActor: Actor {
+    /// Serialized state to recover from, `None` keeps the previous snapshot
+    fn recovery_snapshot(&self) -> Option<Vec<u8>>;
+    /// Executed on the Actor that has just been created by its factory, before `pre_start`
+    fn recover(&mut self, snapshot: Vec<u8>);
+}
+
+/// Storage of the snapshots of [RecoverableActor]s, keyed by [ActorAddress]
+///
+/// Stores are shared by all Actors of a builder and may outlive them, see [ActorBuilder.set_snapshot_store](../prelude/struct.ActorBuilder.html#method.set_snapshot_store)
+pub trait SnapshotStore: Send + Sync {
+    fn save(&self, address: &ActorAddress, snapshot: Vec<u8>);
+    fn load(&self, address: &ActorAddress) -> Option<Vec<u8>>;
+    fn remove(&self, address: &ActorAddress);
+}
+
+/// Default [SnapshotStore](./trait.SnapshotStore.html), does not survive the process
+#[derive(Default)]
+pub struct MemorySnapshotStore {
+    snapshots: Mutex<HashMap<ActorAddress, Vec<u8>>>,
+}
+
+impl MemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Amount of stored snapshots
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn save(&self, address: &ActorAddress, snapshot: Vec<u8>) {
+        self.snapshots.lock().unwrap().insert(address.clone(), snapshot);
+    }
+
+    fn load(&self, address: &ActorAddress) -> Option<Vec<u8>> {
+        self.snapshots.lock().unwrap().get(address).cloned()
+    }
+
+    fn remove(&self, address: &ActorAddress) {
+        self.snapshots.lock().unwrap().remove(address);
+    }
+}
+
+/// Recovery hooks of an Actor, registered through [ActorBuilder.set_recovery](../prelude/struct.ActorBuilder.html#method.set_recovery)
+pub(crate) struct Recovery<A>
+where
+    A: Actor + 'static,
+{
+    snapshot: fn(&A) -> Option<Vec<u8>>,
+    recover: fn(&mut A, Vec<u8>),
+    pub store: Arc<dyn SnapshotStore>,
+}
+
+impl<A> Clone for Recovery<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            snapshot: self.snapshot,
+            recover: self.recover,
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<A> Recovery<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new(store: Arc<dyn SnapshotStore>) -> Self
+    where
+        A: RecoverableActor,
+    {
+        Self {
+            snapshot: A::recovery_snapshot,
+            recover: A::recover,
+            store,
+        }
+    }
+
+    /// Replaces the current snapshot of the Actor, if it provides one
+    pub fn save(&self, address: &ActorAddress, actor: &A) {
+        if let Some(snapshot) = (self.snapshot)(actor) {
+            self.store.save(address, snapshot);
+        }
+    }
+
+    /// Returns whether the Actor recovered from a snapshot
+    pub fn recover(&self, address: &ActorAddress, actor: &mut A) -> bool {
+        match self.store.load(address) {
+            Some(snapshot) => {
+                (self.recover)(actor, snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+}
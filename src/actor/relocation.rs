@@ -0,0 +1,41 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::panic::UnwindSafe;
+
+/// Returned by [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor) and [ActorContext.move_to_pool](../prelude/struct.ActorContext.html#method.move_to_pool)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveError {
+    /// the target pool does not exist, see [ActorSystem.add_pool](../prelude/struct.ActorSystem.html#method.add_pool)
+    UnknownPool,
+    /// no Actor is running at the address
+    NotFound,
+    /// the Actor has been asked to stop
+    Stopping,
+    /// the Actor runs on its own thread, see [ActorBuilder.set_dedicated_thread](../prelude/struct.ActorBuilder.html#method.set_dedicated_thread)
+    DedicatedThread,
+}
+
+/// Moves the Actor between two of its handler invocations, see [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor)
+pub(crate) struct MoveToPoolMessage {
+    pool: String,
+}
+
+impl MoveToPoolMessage {
+    pub fn new(pool: String) -> Self {
+        Self { pool }
+    }
+}
+
+impl ActorMessage for MoveToPoolMessage {}
+
+impl<A> Handler<MoveToPoolMessage> for A
+where
+    A: Actor + UnwindSafe + Sized + 'static,
+{
+    fn handle(&mut self, msg: MoveToPoolMessage, context: &ActorContext<A>) {
+        // a stop requested in the meantime keeps the Actor where it is
+        let _ = context.move_to_pool(&msg.pool);
+    }
+}
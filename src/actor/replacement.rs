@@ -0,0 +1,54 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_factory::TryActorFactory;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::panic::UnwindSafe;
+use std::sync::Mutex;
+
+/// Returned by [ActorSystem.replace_actor](../prelude/struct.ActorSystem.html#method.replace_actor)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaceError {
+    /// no Actor is running at the address
+    NotFound,
+    /// the Actor at the address is of another type
+    TypeMismatch,
+}
+
+/// Factory that creates the Actor from the replacement on, including its restarts
+pub(crate) type Replacement<A> = Box<dyn TryActorFactory<A> + Send>;
+
+/// Replaces the Actor between two of its handler invocations, see [ActorSystem.replace_actor](../prelude/struct.ActorSystem.html#method.replace_actor)
+pub(crate) struct ReplaceActorMessage<A>
+where
+    A: Actor + 'static,
+{
+    /// only locked once the message is handled, it makes the factory `Sync` without requiring it to be
+    factory: Mutex<Replacement<A>>,
+}
+
+impl<A> ReplaceActorMessage<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new(factory: Replacement<A>) -> Self {
+        Self {
+            factory: Mutex::new(factory),
+        }
+    }
+}
+
+impl<A> ActorMessage for ReplaceActorMessage<A> where A: Actor + 'static {}
+
+impl<A> Handler<ReplaceActorMessage<A>> for A
+where
+    A: Actor + UnwindSafe + Sized + 'static,
+{
+    fn handle(&mut self, msg: ReplaceActorMessage<A>, context: &ActorContext<A>) {
+        let factory = match msg.factory.into_inner() {
+            Ok(factory) => factory,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        context.request_replacement(factory);
+    }
+}
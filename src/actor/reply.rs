@@ -0,0 +1,119 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::actor::protocol::ProtocolError;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::message::serialized_message::SerializedMessage;
+use crate::system::actor_system::ActorSystem;
+use std::any::Any;
+use std::panic::UnwindSafe;
+use std::sync::Arc;
+
+type Deliver<M> = Arc<dyn Fn(M) -> Result<(), SendError<M>> + Send + Sync>;
+
+/// Typed path back to an Actor that handles `M`, without knowing the type of that Actor
+///
+/// See [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from) and [ActorContext.reply_to](../prelude/struct.ActorContext.html#method.reply_to)
+pub struct ReplyTo<M> {
+    address: ActorAddress,
+    deliver: Deliver<M>,
+}
+
+impl<M> Clone for ReplyTo<M> {
+    fn clone(&self) -> Self {
+        Self {
+            address: self.address.clone(),
+            deliver: self.deliver.clone(),
+        }
+    }
+}
+
+impl<M> ReplyTo<M>
+where
+    M: ActorMessage + 'static,
+{
+    pub fn new<A>(actor: &ActorWrapper<A>) -> Self
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+    {
+        let target = actor.clone();
+        Self {
+            address: actor.get_address().clone(),
+            deliver: Arc::new(move |msg| target.send(msg)),
+        }
+    }
+
+    pub fn get_address(&self) -> &ActorAddress {
+        &self.address
+    }
+
+    /// Same as [ActorWrapper.send](../prelude/struct.ActorWrapper.html#method.send) of the Actor
+    pub fn send(&self, msg: M) -> Result<(), SendError<M>> {
+        (self.deliver)(msg)
+    }
+}
+
+/// Travels with a message sent through [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from), holds a `ReplyTo<R>`
+#[derive(Clone)]
+pub(crate) struct ReplyPath {
+    pub address: ActorAddress,
+    pub reply_to: Arc<dyn Any + Send + Sync>,
+}
+
+impl ReplyPath {
+    pub fn new<R>(reply_to: ReplyTo<R>) -> Self
+    where
+        R: ActorMessage + 'static,
+    {
+        Self {
+            address: reply_to.get_address().clone(),
+            reply_to: Arc::new(reply_to),
+        }
+    }
+}
+
+/// Sender of the message that is currently handled, see [ActorContext.sender](../prelude/struct.ActorContext.html#method.sender)
+#[derive(Clone)]
+pub struct ActorAddressHandle {
+    address: ActorAddress,
+    reply_to: Arc<dyn Any + Send + Sync>,
+    system: ActorSystem,
+}
+
+impl ActorAddressHandle {
+    pub(crate) fn new(path: &ReplyPath, system: ActorSystem) -> Self {
+        Self {
+            address: path.address.clone(),
+            reply_to: path.reply_to.clone(),
+            system,
+        }
+    }
+
+    pub fn get_address(&self) -> &ActorAddress {
+        &self.address
+    }
+
+    /// Same as [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address) with the address of the sender
+    pub fn send_serialized(&self, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        self.system.try_send_to_address(&self.address, msg)
+    }
+
+    /// Sends `msg` to the sender, returns `false` if it has not been delivered
+    ///
+    /// Replies are dead letters if the sender stopped, or if it did not send the message with a [ReplyTo](../prelude/struct.ReplyTo.html) of type `M`
+    pub fn reply<M>(&self, msg: M) -> bool
+    where
+        M: ActorMessage + 'static,
+    {
+        match self.reply_to.downcast_ref::<ReplyTo<M>>() {
+            // stopped senders are reported by the send itself
+            Some(reply_to) => reply_to.send(msg).is_ok(),
+            None => {
+                self.system.report_dead_letter(&self.address, std::any::type_name::<M>());
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Answers requests of type `R`, see [ActorContext.request_chain](../prelude/struct.ActorContext.html#method.request_chain)
+pub trait RequestHandler<R>: Actor + Sized
+where
+    R: ActorMessage,
+{
+    type Response: Send + Sync + 'static;
+    /// `Err` fails the chain with [ChainError::Rejected](./enum.ChainError.html#variant.Rejected)
+    fn handle_request(&mut self, request: R, context: &ActorContext<Self>) -> Result<Self::Response, String>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainError {
+    /// the responder returned an error
+    Rejected(String),
+    /// the stage has not been answered within its timeout
+    Timeout,
+    /// the request has been dropped without an answer, because the responder stopped or panicked
+    Dropped,
+    /// the Actor already runs `general.max_request_chains` chains
+    TooManyChains,
+}
+
+type Response = Box<dyn Any + Send + Sync>;
+type Stage = Box<dyn FnOnce(Response, ReplyTo) + Send>;
+type Completion<A> = Box<dyn FnOnce(Response, &mut A, &ActorContext<A>) + Send>;
+type ErrorHandler<A> = Box<dyn FnOnce(usize, ChainError, &mut A, &ActorContext<A>) + Send>;
+
+/// Answer of a single stage, delivered through the mailbox of the Actor that runs the chain
+pub struct ChainReply {
+    chain_id: u64,
+    stage: usize,
+    result: Result<Response, ChainError>,
+}
+
+impl ActorMessage for ChainReply {}
+
+impl<A> Handler<ChainReply> for A
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn handle(&mut self, msg: ChainReply, context: &ActorContext<A>) {
+        drive(self, context, msg);
+    }
+}
+
+/// Answers with [ChainError::Dropped](./enum.ChainError.html#variant.Dropped) if it is dropped without an answer
+struct ReplyTo {
+    requester: Arc<dyn Fn(ChainReply) + Send + Sync>,
+    chain_id: u64,
+    stage: usize,
+    is_answered: bool,
+}
+
+impl ReplyTo {
+    fn answer(mut self, result: Result<Response, ChainError>) {
+        self.is_answered = true;
+        (self.requester)(ChainReply {
+            chain_id: self.chain_id,
+            stage: self.stage,
+            result,
+        });
+    }
+}
+
+impl Drop for ReplyTo {
+    fn drop(&mut self) {
+        if !self.is_answered {
+            (self.requester)(ChainReply {
+                chain_id: self.chain_id,
+                stage: self.stage,
+                result: Err(ChainError::Dropped),
+            });
+        }
+    }
+}
+
+/// Sent to a [RequestHandler](./trait.RequestHandler.html) by a request chain
+pub struct RequestMessage<R> {
+    request: R,
+    reply: ReplyTo,
+}
+
+impl<R> ActorMessage for RequestMessage<R> where R: ActorMessage {}
+
+impl<A, R> Handler<RequestMessage<R>> for A
+where
+    A: RequestHandler<R>,
+    R: ActorMessage,
+{
+    fn handle(&mut self, msg: RequestMessage<R>, context: &ActorContext<A>) {
+        let result = self.handle_request(msg.request, context);
+        msg.reply
+            .answer(result.map(|response| Box::new(response) as Response).map_err(ChainError::Rejected));
+    }
+}
+
+struct ChainState<A>
+where
+    A: Actor + 'static,
+{
+    /// stage that waits for its answer, `0` while a chain without stages completes
+    stage: usize,
+    deadline: Instant,
+    timeout: Duration,
+    is_expired: bool,
+    stages: VecDeque<Stage>,
+    on_complete: Completion<A>,
+    on_error: Option<ErrorHandler<A>>,
+}
+
+/// Chains of a single Actor, shared between its Executor and its [ActorContext](../prelude/struct.ActorContext.html)
+pub(crate) struct RequestChains<A>
+where
+    A: Actor + 'static,
+{
+    chains: Arc<Mutex<HashMap<u64, ChainState<A>>>>,
+    active: Arc<AtomicUsize>,
+    next_id: Arc<AtomicU64>,
+    metrics: ActorMetrics,
+    max_chains: usize,
+}
+
+impl<A> Clone for RequestChains<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            chains: self.chains.clone(),
+            active: self.active.clone(),
+            next_id: self.next_id.clone(),
+            metrics: self.metrics.clone(),
+            max_chains: self.max_chains,
+        }
+    }
+}
+
+impl<A> RequestChains<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new(metrics: ActorMetrics, max_chains: usize) -> Self {
+        Self {
+            chains: Arc::new(Mutex::new(HashMap::new())),
+            active: Arc::new(AtomicUsize::new(0)),
+            next_id: Arc::new(AtomicU64::new(1)),
+            metrics,
+            max_chains,
+        }
+    }
+
+    fn update(&self, chains: &HashMap<u64, ChainState<A>>) {
+        self.active.store(chains.len(), Ordering::Relaxed);
+        self.metrics.set_active_request_chains(chains.len());
+    }
+
+    fn insert(&self, chain_id: u64, state: ChainState<A>) {
+        let mut chains = self.chains.lock().unwrap();
+        chains.insert(chain_id, state);
+        self.update(&chains);
+    }
+
+    /// Removes the chain if it waits for the given stage, a timed out stage only accepts its timeout
+    fn take(&self, chain_id: u64, stage: usize, is_timeout: bool) -> Option<ChainState<A>> {
+        let mut chains = self.chains.lock().unwrap();
+        let state = chains.get(&chain_id)?;
+        if state.stage != stage || state.is_expired != is_timeout {
+            return None;
+        }
+        let state = chains.remove(&chain_id);
+        self.update(&chains);
+        state
+    }
+
+    /// Returns all stages whose deadline passed together with their next deadline
+    ///
+    /// An expired stage is returned again after another timeout, in case its timeout got lost before it reached the mailbox
+    pub fn expire(&self, now: Instant) -> Vec<(u64, usize, Instant)> {
+        if self.active.load(Ordering::Relaxed) == 0 {
+            return Vec::new();
+        }
+        let mut chains = self.chains.lock().unwrap();
+        chains
+            .iter_mut()
+            .filter(|(_, state)| state.deadline <= now)
+            .map(|(chain_id, state)| {
+                state.is_expired = true;
+                state.deadline = now + state.timeout;
+                (*chain_id, state.stage, state.deadline)
+            })
+            .collect()
+    }
+
+    /// Earliest deadline of all running chains
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if self.active.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        self.chains.lock().unwrap().values().map(|state| state.deadline).min()
+    }
+
+    /// Drops all chains without calling any of their closures
+    pub fn clear(&self) {
+        let mut chains = self.chains.lock().unwrap();
+        chains.clear();
+        self.update(&chains);
+    }
+}
+
+/// Expired stages are answered through the mailbox, so that `on_error` runs like any other handler
+pub(crate) fn timeout(chain_id: u64, stage: usize) -> ChainReply {
+    ChainReply {
+        chain_id,
+        stage,
+        result: Err(ChainError::Timeout),
+    }
+}
+
+fn reply_to<A>(context: &ActorContext<A>, chain_id: u64, stage: usize) -> ReplyTo
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let requester = context.actor_ref.clone();
+    ReplyTo {
+        requester: Arc::new(move |reply| {
+            let _ = requester.send(reply);
+        }),
+        chain_id,
+        stage,
+        is_answered: false,
+    }
+}
+
+/// Sends the next stage of `state` and keeps the chain until that stage is answered
+fn advance<A>(context: &ActorContext<A>, chain_id: u64, mut state: ChainState<A>, response: Response)
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let stage = state.stages.pop_front().unwrap();
+    state.stage += 1;
+    state.deadline = Instant::now() + state.timeout;
+    let deadline = state.deadline;
+    let reply = reply_to(context, chain_id, state.stage);
+    context.get_request_chains().insert(chain_id, state);
+    let address = context.actor_ref.get_address().clone();
+    context.actor_ref.get_wakeup_manager().schedule_wakeup(address, deadline);
+    stage(response, reply);
+}
+
+fn drive<A>(actor: &mut A, context: &ActorContext<A>, reply: ChainReply)
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let is_timeout = reply.result.as_ref().err() == Some(&ChainError::Timeout);
+    // stale answers of timed out or dropped chains are ignored
+    let mut state = match context.get_request_chains().take(reply.chain_id, reply.stage, is_timeout) {
+        Some(state) => state,
+        None => return,
+    };
+    match reply.result {
+        Err(error) => {
+            if let Some(on_error) = state.on_error.take() {
+                on_error(reply.stage, error, actor, context);
+            }
+        }
+        Ok(response) if state.stages.is_empty() => (state.on_complete)(response, actor, context),
+        Ok(response) => advance(context, reply.chain_id, state, response),
+    }
+}
+
+/// Sequences dependent requests, see [ActorContext.request_chain](../prelude/struct.ActorContext.html#method.request_chain)
+///
+/// `T` is the response of the last stage
+pub struct RequestChain<A, T>
+where
+    A: Actor + 'static,
+{
+    context: ActorContext<A>,
+    stages: VecDeque<Stage>,
+    on_error: Option<ErrorHandler<A>>,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<A, T> RequestChain<A, T>
+where
+    A: Actor + UnwindSafe + 'static,
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new(context: ActorContext<A>) -> RequestChain<A, ()> {
+        RequestChain {
+            context,
+            stages: VecDeque::new(),
+            on_error: None,
+            response: PhantomData,
+        }
+    }
+
+    /// Adds a stage that sends `request` to `target`
+    pub fn then<B, R>(self, target: &ActorWrapper<B>, request: R) -> RequestChain<A, B::Response>
+    where
+        B: RequestHandler<R> + UnwindSafe + 'static,
+        R: ActorMessage + 'static,
+    {
+        self.then_with(target, move |_| request)
+    }
+
+    /// Adds a stage that sends the request built from the response of the previous stage to `target`
+    pub fn then_with<B, R, F>(mut self, target: &ActorWrapper<B>, f: F) -> RequestChain<A, B::Response>
+    where
+        B: RequestHandler<R> + UnwindSafe + 'static,
+        R: ActorMessage + 'static,
+        F: FnOnce(T) -> R + Send + 'static,
+    {
+        let target = target.clone();
+        self.stages.push_back(Box::new(move |previous: Response, reply: ReplyTo| {
+            let previous = *previous.downcast::<T>().unwrap();
+            let _ = target.send(RequestMessage {
+                request: f(previous),
+                reply,
+            });
+        }));
+        RequestChain {
+            context: self.context,
+            stages: self.stages,
+            on_error: self.on_error,
+            response: PhantomData,
+        }
+    }
+
+    /// Executed with the stage that failed, starting at `1`, remaining stages are not sent
+    pub fn on_error<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(usize, ChainError, &mut A, &ActorContext<A>) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    /// Executed with the response of the last stage
+    pub fn on_complete<F>(self, f: F) -> CompletedRequestChain<A>
+    where
+        F: FnOnce(T, &mut A, &ActorContext<A>) + Send + 'static,
+    {
+        CompletedRequestChain {
+            context: self.context,
+            stages: self.stages,
+            on_complete: Box::new(move |response: Response, actor: &mut A, context: &ActorContext<A>| {
+                f(*response.downcast::<T>().unwrap(), actor, context)
+            }),
+            on_error: self.on_error,
+        }
+    }
+}
+
+/// [RequestChain](./struct.RequestChain.html) with a completion, ready to be executed
+pub struct CompletedRequestChain<A>
+where
+    A: Actor + 'static,
+{
+    context: ActorContext<A>,
+    stages: VecDeque<Stage>,
+    on_complete: Completion<A>,
+    on_error: Option<ErrorHandler<A>>,
+}
+
+impl<A> CompletedRequestChain<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Executed with the stage that failed, starting at `1`, remaining stages are not sent
+    pub fn on_error<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(usize, ChainError, &mut A, &ActorContext<A>) + Send + 'static,
+    {
+        self.on_error = Some(Box::new(f));
+        self
+    }
+
+    /// Sends the first stage, every stage fails with [ChainError::Timeout](./enum.ChainError.html#variant.Timeout) unless it is answered within `timeout_per_stage`
+    ///
+    /// Returns the id of the chain
+    pub fn execute(self, timeout_per_stage: Duration) -> Result<u64, ChainError> {
+        let chains = self.context.get_request_chains();
+        if chains.active.load(Ordering::Relaxed) >= chains.max_chains {
+            return Err(ChainError::TooManyChains);
+        }
+        let chain_id = chains.next_id.fetch_add(1, Ordering::Relaxed);
+        let state = ChainState {
+            stage: 0,
+            deadline: Instant::now() + timeout_per_stage,
+            timeout: timeout_per_stage,
+            is_expired: false,
+            stages: self.stages,
+            on_complete: self.on_complete,
+            on_error: self.on_error,
+        };
+        if state.stages.is_empty() {
+            // completes through the mailbox, so that the completion gets access to the Actor
+            chains.insert(chain_id, state);
+            let _ = self.context.actor_ref.send(ChainReply {
+                chain_id,
+                stage: 0,
+                result: Ok(Box::new(())),
+            });
+        } else {
+            advance(&self.context, chain_id, state, Box::new(()));
+        }
+        Ok(chain_id)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::actor::actor::Actor;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::envelope::{MessageEnvelope, MessageEnvelopeTrait};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type Duplicate<A> = Arc<dyn Fn(&dyn Any) -> Option<MessageEnvelope<A>> + Send + Sync>;
+
+/// Message types of an Actor that are retried after a panic, registered through [ActorBuilder.add_retryable](../prelude/struct.ActorBuilder.html#method.add_retryable)
+///
+/// Handlers take ownership of their message, so the retry is a copy taken right before the message is handled
+pub(crate) struct Retrying<A>
+where
+    A: Actor + 'static,
+{
+    copies: HashMap<TypeId, Duplicate<A>>,
+}
+
+impl<A> Clone for Retrying<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            copies: self.copies.clone(),
+        }
+    }
+}
+
+impl<A> Retrying<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new() -> Self {
+        Self { copies: HashMap::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.copies.is_empty()
+    }
+
+    pub fn add<M>(&mut self)
+    where
+        A: Handler<M>,
+        M: ActorMessage + Clone + 'static,
+    {
+        let copy: Duplicate<A> = Arc::new(|msg| msg.downcast_ref::<M>().map(|msg| MessageEnvelope::new(msg.clone())));
+        self.copies.insert(TypeId::of::<M>(), copy);
+    }
+
+    /// Returns `None` for types that are not retried and for lazily constructed messages
+    pub fn copy(&self, msg: &MessageEnvelope<A>) -> Option<MessageEnvelope<A>> {
+        let copy = self.copies.get(&msg.get_type_id())?;
+        let retry = copy(msg.get_message()?)?;
+        Some(retry.with_metadata_of(msg))
+    }
+}
@@ -0,0 +1,52 @@
+use crate::actor::actor_config::BackoffStrategy;
+use crate::actor::rng::ActorRng;
+use crate::message::headers::{HeaderKey, HeaderValue, Headers};
+use std::time::Duration;
+
+/// Delivery attempt of a message redelivered through [ActorContext.retry](../prelude/struct.ActorContext.html#method.retry), only visible to the Actor itself
+pub(crate) const ATTEMPT_HEADER: (&str, HeaderKey) = ("tyractorsaur.attempt", HeaderKey(7));
+
+/// Redelivery of a failed message, see [ActorContext.retry](../prelude/struct.ActorContext.html#method.retry)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// deliveries including the first one, a message is never redelivered with less than 2
+    pub max_attempts: u32,
+    /// delay before the next attempt, `previous` is the number of failed attempts minus one
+    pub backoff: BackoffStrategy,
+    /// picks every delay at random between half of the backoff and the full backoff, so that many failing messages do not all come back at once
+    pub jitter: bool,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, backoff: BackoffStrategy) -> Self {
+        Self {
+            max_attempts,
+            backoff,
+            jitter: false,
+        }
+    }
+
+    pub fn with_jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Delay before the attempt that follows the failed `attempt`
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.backoff.delay(attempt.saturating_sub(1) as usize);
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+        let half = delay / 2;
+        let nanos = (delay - half).as_nanos().min(u64::MAX as u128) as u64;
+        half + Duration::from_nanos(ActorRng::from_entropy().gen_range(0..nanos.max(1)))
+    }
+}
+
+/// Delivery attempt carried by the given headers, `1` for messages that have not been redelivered
+pub(crate) fn attempt_of(headers: Option<&Headers>) -> u32 {
+    match headers.and_then(|headers| headers.get(ATTEMPT_HEADER.1)) {
+        Some(HeaderValue::U64(attempt)) => *attempt as u32,
+        _ => 1,
+    }
+}
@@ -0,0 +1,193 @@
+use crate::actor::actor_address::ActorAddress;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+static ENTROPY_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Defines how the [ActorRng](./struct.ActorRng.html) of an Actor is re-seeded when it is restarted after a panic
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum RngReseed {
+    /// the restarted Actor draws the same sequence as the initial one
+    #[default]
+    Repeat,
+    /// the seed is mixed with the amount of restarts, every restart draws a different but reproducible sequence
+    PerRestart,
+}
+
+/// Random number generator of a single Actor, see [ActorContext.rng](../prelude/struct.ActorContext.html#method.rng)
+///
+/// xoshiro256** seeded through SplitMix64, it is fast and reproducible but not cryptographically secure.
+/// The state is serializable, so that it can be stored within a snapshot of a simulation and restored through [ActorBuilder.set_rng](../prelude/struct.ActorBuilder.html#method.set_rng)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+///
+/// let mut rng = ActorRng::from_seed(42);
+/// let roll = rng.gen_range(1..7);
+/// assert!((1..7).contains(&roll));
+/// assert!(rng.next_f64() < 1.0);
+///
+/// let snapshot = rng.clone();
+/// let expected: Vec<u64> = (0..10).map(|_| rng.next_u64()).collect();
+/// let mut resumed = ActorRng::from_state(snapshot.get_state());
+/// let resumed: Vec<u64> = (0..10).map(|_| resumed.next_u64()).collect();
+/// assert_eq!(expected, resumed);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActorRng {
+    state: [u64; 4],
+}
+
+impl ActorRng {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut state = [0; 4];
+        for word in state.iter_mut() {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            *word = splitmix64(seed);
+        }
+        Self { state }
+    }
+
+    /// Seeded from the randomly keyed hasher of the standard library and the current time
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        SystemTime::now().hash(&mut hasher);
+        ENTROPY_COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+        Self::from_seed(hasher.finish())
+    }
+
+    /// Continues exactly where the generator of [get_state](#method.get_state) stopped
+    pub fn from_state(state: [u64; 4]) -> Self {
+        // the all-zero state would only ever produce zeros
+        if state == [0; 4] {
+            return Self::from_seed(0);
+        }
+        Self { state }
+    }
+
+    /// Derived from `seed` and the address of the Actor, [from_entropy](#method.from_entropy) without a seed
+    pub(crate) fn for_actor(seed: Option<u64>, address: &ActorAddress) -> Self {
+        match seed {
+            Some(seed) => Self::from_seed(mix(seed, address_id(address))),
+            None => Self::from_entropy(),
+        }
+    }
+
+    /// Generator of a restarted Actor, derived from its initial generator
+    pub(crate) fn reseed(&self, reseed: RngReseed, restarts: usize) -> Self {
+        match reseed {
+            RngReseed::Repeat => self.clone(),
+            RngReseed::PerRestart => {
+                let folded = self.state.iter().fold(0, |folded, word| mix(folded, *word));
+                Self::from_seed(mix(folded, restarts as u64))
+            }
+        }
+    }
+
+    pub fn get_state(&self) -> [u64; 4] {
+        self.state
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniformly distributed within `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniformly distributed within `range`, panics if it is empty
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "empty range {:?}", range);
+        let span = range.end - range.start;
+        // rejects the low products that would favour some values, see Lemire's nearly divisionless method
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let product = self.next_u64() as u128 * span as u128;
+            if product as u64 >= threshold {
+                return range.start + (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Returns `true` with probability `p`
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Generator for another Actor, independent of this one but reproducible as long as this one is
+    ///
+    /// Advances this generator by a single draw, hand the result to [ActorBuilder.set_rng](../prelude/struct.ActorBuilder.html#method.set_rng) of the child
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// let child = ActorAddress {
+    ///     remote: String::from("local"),
+    ///     system: String::from("simulation"),
+    ///     pool: String::from("default"),
+    ///     actor: String::from("child-1"),
+    /// };
+    /// let sibling = ActorAddress { actor: String::from("child-2"), ..child.clone() };
+    ///
+    /// let mut first = ActorRng::from_seed(5).derive_for(&child);
+    /// let mut second = ActorRng::from_seed(5).derive_for(&child);
+    /// let mut other = ActorRng::from_seed(5).derive_for(&sibling);
+    /// let expected = first.next_u64();
+    /// assert_eq!(expected, second.next_u64());
+    /// assert_ne!(expected, other.next_u64());
+    /// ```
+    pub fn derive_for(&mut self, address: &ActorAddress) -> ActorRng {
+        let seed = self.next_u64();
+        Self::from_seed(mix(seed, address_id(address)))
+    }
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn mix(a: u64, b: u64) -> u64 {
+    splitmix64(a ^ splitmix64(b))
+}
+
+/// FNV-1a of the system, pool and Actor name, the remote part differs between hosts
+fn address_id(address: &ActorAddress) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in [&address.system, &address.pool, &address.actor].iter() {
+        for byte in part.as_bytes().iter().chain(b"/".iter()) {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
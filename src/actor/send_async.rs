@@ -0,0 +1,100 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_config::MailboxOverflowPolicy;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use std::future::Future;
+use std::panic::UnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+/// Futures of [ActorWrapper.send_async](../prelude/struct.ActorWrapper.html#method.send_async) waiting for room in a full mailbox
+///
+/// Woken by the Executor after every slot and once the Actor stopped, a woken send simply tries again
+#[derive(Default)]
+pub(crate) struct SendWaiters {
+    /// checked before the lock is taken, so that Actors without waiting sends never lock
+    waiting: AtomicUsize,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl SendWaiters {
+    fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+        if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+        self.waiting.store(wakers.len(), Ordering::SeqCst);
+        drop(wakers);
+        // pairs with the fence in wake, either the retry of the send sees the room or the Executor sees the waker
+        fence(Ordering::SeqCst);
+    }
+
+    pub(crate) fn wake(&self) {
+        fence(Ordering::SeqCst);
+        if self.waiting.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let wakers = {
+            let mut wakers = self.wakers.lock().unwrap();
+            self.waiting.store(0, Ordering::SeqCst);
+            std::mem::take(&mut *wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [ActorWrapper.send_async](../prelude/struct.ActorWrapper.html#method.send_async)
+///
+/// Completes as soon as the message has been queued, the result is the same as the one of [ActorWrapper.send](../prelude/struct.ActorWrapper.html#method.send)
+pub struct SendAsync<'a, A, M>
+where
+    A: Actor + 'static,
+{
+    actor: &'a ActorWrapper<A>,
+    msg: Option<M>,
+}
+
+// the message is only ever moved out of the future, never pinned
+impl<A, M> Unpin for SendAsync<'_, A, M> where A: Actor + 'static {}
+
+impl<'a, A, M> SendAsync<'a, A, M>
+where
+    A: Actor + 'static,
+{
+    pub(crate) fn new(actor: &'a ActorWrapper<A>, msg: M) -> Self {
+        Self { actor, msg: Some(msg) }
+    }
+}
+
+impl<A, M> Future for SendAsync<'_, A, M>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    type Output = Result<(), SendError<M>>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let msg = self.msg.take().expect("SendAsync polled after completion");
+        let msg = match self.actor.try_send(msg) {
+            Err(SendError::Full(msg)) if self.actor.get_mailbox().overflow_policy != MailboxOverflowPolicy::Fail => msg,
+            // a full mailbox that fails sends does not let a regular send wait either
+            result => return Poll::Ready(result),
+        };
+        self.actor.get_mailbox().send_waiters.register(context.waker());
+        match self.actor.try_send(msg) {
+            Err(SendError::Full(msg)) => {
+                // the Actor has to be awake to make room in its mailbox
+                self.actor.wakeup_if_sleeping();
+                self.msg = Some(msg);
+                Poll::Pending
+            }
+            result => Poll::Ready(result),
+        }
+    }
+}
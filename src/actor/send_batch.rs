@@ -0,0 +1,166 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::envelope::MessageEnvelope;
+use crate::message::headers;
+use crate::system::wakeup_manager::WakeupManager;
+use crate::system::yield_point::yield_point;
+use std::collections::HashSet;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{fence, AtomicBool, Ordering};
+
+/// Outcome of a single send of a [SendBatch](./struct.SendBatch.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchSendStatus {
+    /// queued in the mailbox of the target
+    Delivered,
+    /// the bounded mailbox of the target was full, the send waited for room like a regular send
+    DeliveredAfterWait,
+    /// the target is being handed off, the message has been parked or forwarded to the new Actor
+    Redirected,
+    /// dropped, the target has stopped
+    Stopped,
+    /// dropped by a sheddable target under memory pressure, or because the system exceeded its message budget
+    Shed,
+    /// dropped by the cycle detection
+    CycleCut,
+    /// dropped by the overflow policy of the full bounded mailbox of the target
+    Dropped,
+    /// dropped while staging, the message exceeds the message size limit of the target
+    TooLarge,
+}
+
+impl BatchSendStatus {
+    pub fn is_delivered(&self) -> bool {
+        matches!(
+            self,
+            BatchSendStatus::Delivered | BatchSendStatus::DeliveredAfterWait | BatchSendStatus::Redirected
+        )
+    }
+}
+
+/// Returned by [ActorContext.batch_sends](../prelude/struct.ActorContext.html#method.batch_sends) and [ActorSystem.batch_sends](../prelude/struct.ActorSystem.html#method.batch_sends)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendBatchReport {
+    /// status of every staged send, in the order they were staged
+    pub sends: Vec<BatchSendStatus>,
+    /// amount of sleeping Actors that have been woken up at the end of the batch
+    pub woken: usize,
+}
+
+impl SendBatchReport {
+    pub fn delivered(&self) -> usize {
+        self.sends.iter().filter(|status| status.is_delivered()).count()
+    }
+
+    /// Index and status of every send whose message has been dropped
+    pub fn dropped(&self) -> Vec<(usize, BatchSendStatus)> {
+        self.sends
+            .iter()
+            .enumerate()
+            .filter(|(_, status)| !status.is_delivered())
+            .map(|(index, status)| (index, *status))
+            .collect()
+    }
+}
+
+type StagedSend<'t> = Box<dyn FnOnce(&mut Wakeups<'t>) -> BatchSendStatus + 't>;
+
+/// Sends that are staged while the batch is open and delivered once it is closed
+///
+/// See [ActorSystem.batch_sends](../prelude/struct.ActorSystem.html#method.batch_sends)
+pub struct SendBatch<'t> {
+    staged: Vec<StagedSend<'t>>,
+}
+
+impl<'t> SendBatch<'t> {
+    /// Stages `msg` for `target`, headers that are propagated at this point are sent as well
+    pub fn send<A, M>(&mut self, target: &'t ActorWrapper<A>, msg: M)
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        if let Some(size_limit) = &target.get_mailbox().size_limit {
+            if size_limit.admit(&msg, target.get_address()).is_err() {
+                self.staged.push(Box::new(|_: &mut Wakeups<'t>| BatchSendStatus::TooLarge));
+                return;
+            }
+        }
+        let msg = MessageEnvelope::with_headers(msg, headers::get_propagated());
+        self.staged.push(Box::new(move |wakeups: &mut Wakeups<'t>| {
+            let status = target.send_staged(msg);
+            if status == BatchSendStatus::Delivered {
+                wakeups.add(target);
+            }
+            status
+        }));
+    }
+
+    pub fn len(&self) -> usize {
+        self.staged.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+}
+
+/// Targets that may have to be woken up once all sends are queued
+#[derive(Default)]
+struct Wakeups<'t> {
+    targets: Vec<(&'t AtomicBool, &'t ActorAddress, &'t WakeupManager)>,
+}
+
+impl<'t> Wakeups<'t> {
+    fn add<A>(&mut self, target: &'t ActorWrapper<A>)
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        // consecutive sends to the same target are only recorded once
+        let is_sleeping: &AtomicBool = &target.get_mailbox().flags.is_sleeping;
+        if !self.targets.last().is_some_and(|(last, _, _)| std::ptr::eq(*last, is_sleeping)) {
+            self.targets.push((is_sleeping, target.get_address(), target.get_wakeup_manager()));
+        }
+    }
+
+    /// Hands the sleeping targets to the wakeup manager with one wakeup per pool, every target is woken up only once
+    fn wake(self) -> usize {
+        let mut pools: Vec<(&WakeupManager, &str, Vec<ActorAddress>)> = Vec::new();
+        let mut woken: HashSet<*const AtomicBool> = HashSet::new();
+        // pairs with Mailbox.mark_sleeping, all sends have been queued before
+        fence(Ordering::SeqCst);
+        for (is_sleeping, address, wakeup_manager) in self.targets {
+            if !is_sleeping.load(Ordering::SeqCst) || !woken.insert(is_sleeping) {
+                continue;
+            }
+            let pool = pools
+                .iter_mut()
+                .find(|(manager, pool, _)| manager.is_same(wakeup_manager) && *pool == address.pool);
+            match pool {
+                Some((_, _, addresses)) => addresses.push(address.clone()),
+                None => pools.push((wakeup_manager, &address.pool, vec![address.clone()])),
+            }
+        }
+        for (wakeup_manager, _, addresses) in pools {
+            wakeup_manager.wakeup_batch(addresses);
+        }
+        woken.len()
+    }
+}
+
+pub(crate) fn run<'t, F>(f: F) -> SendBatchReport
+where
+    F: FnOnce(&mut SendBatch<'t>),
+{
+    let mut batch = SendBatch { staged: Vec::new() };
+    f(&mut batch);
+    let mut wakeups = Wakeups::default();
+    let sends = batch.staged.into_iter().map(|send| send(&mut wakeups)).collect();
+    yield_point();
+    SendBatchReport {
+        sends,
+        woken: wakeups.wake(),
+    }
+}
@@ -0,0 +1,364 @@
+use crate::actor::actor_address::ActorAddress;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Latency objective of an [Actor](../prelude/trait.Actor.html), see [ActorBuilder.set_latency_slo](../prelude/struct.ActorBuilder.html#method.set_latency_slo)
+///
+/// Latency is measured from the moment a message is sent until its handler returns
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct SloConfig {
+    pub target: Duration,
+    /// between `0.0` and `100.0`, i.e. `99.0` for the p99 latency
+    pub percentile: f32,
+    pub window: Duration,
+    /// fraction of the evaluated windows that have to miss the target, before the SLO counts as breached
+    ///
+    /// A breached SLO recovers once at most half of this fraction misses the target
+    pub breach_threshold: f32,
+    /// number of most recent windows that are evaluated
+    pub evaluated_windows: usize,
+}
+
+impl SloConfig {
+    /// Evaluates 10 windows of 1 second each, breached once more than half of them miss the target
+    pub fn new(target: Duration, percentile: f32) -> Self {
+        Self {
+            target,
+            percentile,
+            window: Duration::from_secs(1),
+            breach_threshold: 0.5,
+            evaluated_windows: 10,
+        }
+    }
+
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn with_breach_threshold(mut self, breach_threshold: f32) -> Self {
+        self.breach_threshold = breach_threshold;
+        self
+    }
+
+    pub fn with_evaluated_windows(mut self, evaluated_windows: usize) -> Self {
+        self.evaluated_windows = evaluated_windows;
+        self
+    }
+}
+
+const SUB_BUCKET_BITS: u32 = 4;
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+const BUCKETS: usize = ((64 - SUB_BUCKET_BITS as usize) << SUB_BUCKET_BITS) + SUB_BUCKETS as usize;
+
+/// Fixed size log-linear histogram of latencies, used to estimate the percentiles of an [SloConfig](./struct.SloConfig.html)
+///
+/// Every power of two is split into 16 buckets, so that estimated values are at most about 3% off
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::time::Duration;
+///
+/// let mut samples: Vec<u64> = Vec::new();
+/// let mut state: u64 = 42;
+/// for _ in 0..10_000 {
+///     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+///     samples.push(1_000 + (state >> 40) % 5_000_000);
+/// }
+///
+/// let mut histogram = LatencyHistogram::new();
+/// for sample in samples.iter() {
+///     histogram.record(Duration::from_nanos(*sample));
+/// }
+///
+/// samples.sort_unstable();
+/// for percentile in [50.0, 90.0, 99.0, 99.9] {
+///     let rank = ((percentile / 100.0) * samples.len() as f64).ceil() as usize;
+///     let exact = samples[rank - 1] as f64;
+///     let estimated = histogram.percentile(percentile as f32).as_nanos() as f64;
+///     assert!((estimated - exact).abs() / exact < 0.04);
+/// }
+/// assert_eq!(histogram.count(), 10_000);
+/// assert_eq!(histogram.max(), Duration::from_nanos(*samples.last().unwrap()));
+/// ```
+#[derive(Clone)]
+pub struct LatencyHistogram {
+    buckets: Box<[u32]>,
+    count: u64,
+    max: u64,
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; BUCKETS].into_boxed_slice(),
+            count: 0,
+            max: 0,
+        }
+    }
+
+    fn index(nanos: u64) -> usize {
+        if nanos < SUB_BUCKETS {
+            return nanos as usize;
+        }
+        let exponent = 63 - nanos.leading_zeros();
+        let group = (exponent - SUB_BUCKET_BITS + 1) as usize;
+        let sub_bucket = ((nanos >> (exponent - SUB_BUCKET_BITS)) & (SUB_BUCKETS - 1)) as usize;
+        (group << SUB_BUCKET_BITS) | sub_bucket
+    }
+
+    /// Middle of the bucket
+    fn value(index: usize) -> u64 {
+        let group = index >> SUB_BUCKET_BITS;
+        if group == 0 {
+            return index as u64;
+        }
+        let sub_bucket = (index as u64) & (SUB_BUCKETS - 1);
+        let lower = (SUB_BUCKETS + sub_bucket) << (group - 1);
+        lower + ((1u64 << (group - 1)) >> 1)
+    }
+
+    pub fn record(&mut self, latency: Duration) {
+        let nanos = latency.as_nanos().min(u64::MAX as u128) as u64;
+        self.buckets[Self::index(nanos)] += 1;
+        self.count += 1;
+        self.max = self.max.max(nanos);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max)
+    }
+
+    /// Estimated nearest-rank percentile between `0.0` and `100.0`, `Duration::ZERO` if nothing was recorded
+    pub fn percentile(&self, percentile: f32) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((percentile.clamp(0.0, 100.0) as f64 / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            seen += *bucket as u64;
+            if seen >= rank {
+                return Duration::from_nanos(Self::value(index).min(self.max));
+            }
+        }
+        Duration::from_nanos(self.max)
+    }
+
+    pub fn clear(&mut self) {
+        if self.count > 0 {
+            self.buckets.iter_mut().for_each(|bucket| *bucket = 0);
+        }
+        self.count = 0;
+        self.max = 0;
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Latency of a single completed window of an [SloConfig](./struct.SloConfig.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SloWindowStats {
+    pub samples: u64,
+    /// estimated latency at the configured percentile
+    pub measured: Duration,
+    pub max: Duration,
+    /// `measured` exceeded the target, windows without samples never miss it
+    pub missed: bool,
+}
+
+/// Current state of the SLO of an Actor, see [ActorWrapper.slo_status](../prelude/struct.ActorWrapper.html#method.slo_status)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SloStatus {
+    pub breached: bool,
+    pub missed_windows: usize,
+    pub evaluated_windows: usize,
+    pub last_window: Option<SloWindowStats>,
+}
+
+/// Carried by an [SloEvent](./enum.SloEvent.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloReport {
+    pub address: ActorAddress,
+    pub config: SloConfig,
+    /// estimated latency at the configured percentile of the last completed window
+    pub measured: Duration,
+    /// evaluated windows, oldest first
+    pub windows: Vec<SloWindowStats>,
+    /// message types with the most messages above the target within the evaluated windows
+    pub top_message_types: Vec<(&'static str, u64)>,
+}
+
+/// Emitted whenever the SLO of an Actor changes its state, see [ActorSystem.subscribe_slo_events](../prelude/struct.ActorSystem.html#method.subscribe_slo_events)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SloEvent {
+    Breached(SloReport),
+    Recovered(SloReport),
+}
+
+/// Fans [SloEvent](./enum.SloEvent.html)s out to all subscribers of a system
+#[derive(Clone)]
+pub(crate) struct SloEventBus {
+    subscribers: Arc<Mutex<Vec<Sender<SloEvent>>>>,
+}
+
+impl SloEventBus {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn subscribe(&self) -> Receiver<SloEvent> {
+        let (sender, receiver) = unbounded();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    fn publish(&self, event: SloEvent) {
+        // dropped receivers unsubscribe
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+const TOP_MESSAGE_TYPES: usize = 3;
+
+struct CompletedWindow {
+    stats: SloWindowStats,
+    above_target: Vec<(&'static str, u64)>,
+}
+
+/// Owned by the Executor, windows are only evaluated when the first message after the end of a window is recorded
+pub(crate) struct SloTracker {
+    config: SloConfig,
+    address: ActorAddress,
+    histogram: LatencyHistogram,
+    above_target: Vec<(&'static str, u64)>,
+    window_start: Instant,
+    windows: VecDeque<CompletedWindow>,
+    breached: bool,
+    status: Arc<Mutex<SloStatus>>,
+    events: SloEventBus,
+}
+
+impl SloTracker {
+    pub fn new(config: SloConfig, address: ActorAddress, status: Arc<Mutex<SloStatus>>, events: SloEventBus) -> Self {
+        Self {
+            config,
+            address,
+            histogram: LatencyHistogram::new(),
+            above_target: Vec::new(),
+            window_start: Instant::now(),
+            windows: VecDeque::with_capacity(config.evaluated_windows),
+            breached: false,
+            status,
+            events,
+        }
+    }
+
+    pub fn record(&mut self, latency: Duration, message_type: &'static str, now: Instant) {
+        if now.saturating_duration_since(self.window_start) >= self.config.window {
+            self.rollover(now);
+        }
+        self.histogram.record(latency);
+        if latency > self.config.target {
+            match self.above_target.iter_mut().find(|(name, _)| *name == message_type) {
+                Some((_, count)) => *count += 1,
+                None => self.above_target.push((message_type, 1)),
+            }
+        }
+    }
+
+    fn rollover(&mut self, now: Instant) {
+        let window = self.config.window.as_nanos().max(1);
+        let elapsed = (now.saturating_duration_since(self.window_start).as_nanos() / window) as u32;
+        let measured = self.histogram.percentile(self.config.percentile);
+        let completed = CompletedWindow {
+            stats: SloWindowStats {
+                samples: self.histogram.count(),
+                measured,
+                max: self.histogram.max(),
+                missed: self.histogram.count() > 0 && measured > self.config.target,
+            },
+            above_target: std::mem::take(&mut self.above_target),
+        };
+        self.push_window(completed);
+        // windows without any message in between
+        for _ in 1..(elapsed as usize).min(self.config.evaluated_windows + 1) {
+            self.push_window(CompletedWindow {
+                stats: SloWindowStats {
+                    samples: 0,
+                    measured: Duration::ZERO,
+                    max: Duration::ZERO,
+                    missed: false,
+                },
+                above_target: Vec::new(),
+            });
+        }
+        self.histogram.clear();
+        self.window_start += self.config.window * elapsed.max(1);
+        self.evaluate();
+    }
+
+    fn push_window(&mut self, window: CompletedWindow) {
+        if self.windows.len() == self.config.evaluated_windows.max(1) {
+            self.windows.pop_front();
+        }
+        self.windows.push_back(window);
+    }
+
+    fn evaluate(&mut self) {
+        let missed = self.windows.iter().filter(|window| window.stats.missed).count();
+        let fraction = missed as f32 / self.config.evaluated_windows.max(1) as f32;
+        if !self.breached && fraction > self.config.breach_threshold {
+            self.breached = true;
+            self.events.publish(SloEvent::Breached(self.report()));
+        } else if self.breached && fraction <= self.config.breach_threshold / 2.0 {
+            self.breached = false;
+            self.events.publish(SloEvent::Recovered(self.report()));
+        }
+        *self.status.lock().unwrap() = SloStatus {
+            breached: self.breached,
+            missed_windows: missed,
+            evaluated_windows: self.windows.len(),
+            last_window: self.windows.back().map(|window| window.stats),
+        };
+    }
+
+    fn report(&self) -> SloReport {
+        let mut top_message_types: Vec<(&'static str, u64)> = Vec::new();
+        for (name, count) in self.windows.iter().flat_map(|window| window.above_target.iter()) {
+            match top_message_types.iter_mut().find(|(top, _)| top == name) {
+                Some((_, total)) => *total += count,
+                None => top_message_types.push((name, *count)),
+            }
+        }
+        top_message_types.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        top_message_types.truncate(TOP_MESSAGE_TYPES);
+        SloReport {
+            address: self.address.clone(),
+            config: self.config,
+            measured: self.windows.back().map_or(Duration::ZERO, |window| window.stats.measured),
+            windows: self.windows.iter().map(|window| window.stats).collect(),
+            top_message_types,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_config::MailboxOverflowPolicy;
+use crate::message::envelope::MessageEnvelope;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Messages set aside through [ActorContext.stash](../prelude/struct.ActorContext.html#method.stash), shared between all clones of a [Mailbox](./struct.Mailbox.html)
+pub(crate) struct Stash<A>
+where
+    A: Actor + 'static,
+{
+    /// `0` allows an unbounded stash
+    capacity: usize,
+    messages: Mutex<VecDeque<MessageEnvelope<A>>>,
+    /// the Executor moves the stash ahead of everything that is queued once the current handler returned
+    is_unstash_requested: AtomicBool,
+}
+
+impl<A> Stash<A>
+where
+    A: Actor + 'static,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            messages: Mutex::new(VecDeque::new()),
+            is_unstash_requested: AtomicBool::new(false),
+        }
+    }
+
+    /// Stashes the envelope and returns the one dropped by the overflow policy, a full stash that does not drop hands the envelope back
+    pub fn push(
+        &self,
+        msg: MessageEnvelope<A>,
+        overflow_policy: MailboxOverflowPolicy,
+    ) -> Result<Option<MessageEnvelope<A>>, MessageEnvelope<A>> {
+        let mut messages = self.messages.lock().unwrap();
+        if self.capacity == 0 || messages.len() < self.capacity {
+            messages.push_back(msg);
+            return Ok(None);
+        }
+        match overflow_policy {
+            // the Actor cannot wait for room in its own stash
+            MailboxOverflowPolicy::Block | MailboxOverflowPolicy::Fail => Err(msg),
+            MailboxOverflowPolicy::DropNewest => Ok(Some(msg)),
+            MailboxOverflowPolicy::DropOldest => {
+                let oldest = messages.pop_front();
+                messages.push_back(msg);
+                Ok(oldest)
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    pub fn request_unstash(&self) {
+        self.is_unstash_requested.store(true, Ordering::Relaxed);
+    }
+
+    pub fn take_unstash_request(&self) -> bool {
+        self.is_unstash_requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Takes all stashed messages, oldest first
+    pub fn take(&self) -> VecDeque<MessageEnvelope<A>> {
+        std::mem::take(&mut *self.messages.lock().unwrap())
+    }
+}
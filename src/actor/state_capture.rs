@@ -0,0 +1,449 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::panic_report;
+use crate::message::actor_message::ActorMessage;
+use crossbeam_channel::Sender;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Format of the bytes returned by [Actor.snapshot](../prelude/trait.Actor.html#method.snapshot), see [ActorBuilder.set_snapshot_format](../prelude/struct.ActorBuilder.html#method.set_snapshot_format)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SnapshotFormat {
+    /// only compared byte by byte
+    #[default]
+    Opaque,
+    /// UTF-8 encoded JSON, for example written by `serde_json::to_vec`, compared field by field
+    Json,
+}
+
+/// Snapshot of the state of an Actor, taken on its own thread between two messages
+///
+/// See [ActorWrapper.capture_state](../prelude/struct.ActorWrapper.html#method.capture_state)
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateCapture {
+    pub label: String,
+    pub address: ActorAddress,
+    /// starts at `0` for every Actor and is continued across restarts
+    pub sequence: u64,
+    pub at: SystemTime,
+    pub format: SnapshotFormat,
+    pub state: Vec<u8>,
+}
+
+/// Reason why [ActorWrapper.capture_state](../prelude/struct.ActorWrapper.html#method.capture_state) did not capture the state
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptureError {
+    /// the state capture capacity of the Actor is `0`
+    Disabled,
+    /// the Actor stopped before it handled the capture
+    Stopped,
+    /// the Actor has been spawned suspended and has not been released yet
+    AwaitingRelease,
+    /// [Actor.snapshot](../prelude/trait.Actor.html#method.snapshot) panicked, `reason` is `None` if the panic payload is not a string
+    Panicked { reason: Option<String> },
+}
+
+/// A single value that differs between two captures
+///
+/// `path` addresses the value with `.` for fields and `[n]` for array elements, it is empty for the root value
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub path: String,
+    /// compact JSON, `None` if the value did not exist before
+    pub before: Option<String>,
+    /// compact JSON, `None` if the value has been removed
+    pub after: Option<String>,
+}
+
+/// Byte level comparison of two captures that can not be compared field by field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteSummary {
+    pub before_len: usize,
+    pub after_len: usize,
+    /// `None` if both captures are equal
+    pub first_difference: Option<usize>,
+    pub before_hash: u64,
+    pub after_hash: u64,
+}
+
+/// Returned by [ActorSystem.diff_captures](../prelude/struct.ActorSystem.html#method.diff_captures)
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptureDiff {
+    /// both captures are valid [SnapshotFormat::Json](./enum.SnapshotFormat.html#variant.Json), changes are ordered by their first appearance
+    Structural(Vec<FieldChange>),
+    Bytes(ByteSummary),
+}
+
+/// Captures the state of the Actor once it is handled
+pub(crate) struct CaptureStateMessage {
+    pub label: String,
+    pub result: Sender<Result<StateCapture, CaptureError>>,
+}
+
+impl ActorMessage for CaptureStateMessage {}
+
+struct CaptureRing {
+    captures: VecDeque<StateCapture>,
+    next_sequence: u64,
+}
+
+/// Bounded captures of a single Actor, shared by its executor and wrappers
+pub(crate) struct StateCaptures {
+    address: ActorAddress,
+    format: SnapshotFormat,
+    capacity: usize,
+    /// message types that are bracketed by captures
+    around: Vec<String>,
+    ring: Mutex<CaptureRing>,
+}
+
+impl StateCaptures {
+    pub fn new(address: ActorAddress, format: SnapshotFormat, capacity: usize, around: Vec<String>) -> Self {
+        Self {
+            address,
+            format,
+            capacity,
+            around,
+            ring: Mutex::new(CaptureRing {
+                captures: VecDeque::with_capacity(capacity),
+                next_sequence: 0,
+            }),
+        }
+    }
+
+    /// Must be executed on the thread of the Actor, a panicking snapshot is not stored
+    pub fn capture<A>(&self, actor: &A, label: String) -> Result<StateCapture, CaptureError>
+    where
+        A: Actor,
+    {
+        let state = catch_unwind(AssertUnwindSafe(|| actor.snapshot())).map_err(|panic| CaptureError::Panicked {
+            reason: panic_report::panic_message(panic.as_ref()),
+        })?;
+        let mut ring = self.ring.lock().unwrap();
+        let capture = StateCapture {
+            label,
+            address: self.address.clone(),
+            sequence: ring.next_sequence,
+            at: SystemTime::now(),
+            format: self.format,
+            state,
+        };
+        ring.next_sequence += 1;
+        if ring.captures.len() == self.capacity {
+            ring.captures.pop_front();
+        }
+        ring.captures.push_back(capture.clone());
+        Ok(capture)
+    }
+
+    pub fn list(&self) -> Vec<StateCapture> {
+        self.ring.lock().unwrap().captures.iter().cloned().collect()
+    }
+
+    /// Returns the configured name that matches the full type name or its last path segments
+    pub fn around(&self, type_name: &str) -> Option<String> {
+        self.around
+            .iter()
+            .find(|name| {
+                type_name
+                    .strip_suffix(name.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.ends_with("::"))
+            })
+            .cloned()
+    }
+}
+
+pub(crate) fn diff(before: &StateCapture, after: &StateCapture) -> CaptureDiff {
+    if before.format == SnapshotFormat::Json && after.format == SnapshotFormat::Json {
+        if let (Some(before), Some(after)) = (JsonParser::parse(&before.state), JsonParser::parse(&after.state)) {
+            let mut changes = Vec::new();
+            diff_values(String::new(), Some(&before), Some(&after), &mut changes);
+            return CaptureDiff::Structural(changes);
+        }
+    }
+    let first_difference = before
+        .state
+        .iter()
+        .zip(after.state.iter())
+        .position(|(before, after)| before != after)
+        .or_else(|| {
+            let shorter = before.state.len().min(after.state.len());
+            (before.state.len() != after.state.len()).then_some(shorter)
+        });
+    CaptureDiff::Bytes(ByteSummary {
+        before_len: before.state.len(),
+        after_len: after.state.len(),
+        first_difference,
+        before_hash: hash(&before.state),
+        after_hash: hash(&after.state),
+    })
+}
+
+fn hash(state: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn diff_values(path: String, before: Option<&JsonValue>, after: Option<&JsonValue>, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Some(JsonValue::Object(before)), Some(JsonValue::Object(after))) => {
+            for (key, value) in before.iter() {
+                diff_values(join(&path, key), Some(value), field(after, key), changes);
+            }
+            for (key, value) in after.iter().filter(|(key, _)| field(before, key).is_none()) {
+                diff_values(join(&path, key), None, Some(value), changes);
+            }
+        }
+        (Some(JsonValue::Array(before)), Some(JsonValue::Array(after))) => {
+            for index in 0..before.len().max(after.len()) {
+                diff_values(format!("{}[{}]", path, index), before.get(index), after.get(index), changes);
+            }
+        }
+        (before, after) if before != after => changes.push(FieldChange {
+            path,
+            before: before.map(JsonValue::render),
+            after: after.map(JsonValue::render),
+        }),
+        _ => {}
+    }
+}
+
+fn field<'a>(fields: &'a [(String, JsonValue)], key: &str) -> Option<&'a JsonValue> {
+    fields.iter().find(|(field, _)| field == key).map(|(_, value)| value)
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path.is_empty() {
+        true => String::from(key),
+        false => format!("{}.{}", path, key),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JsonValue {
+    Null,
+    Bool(bool),
+    /// compared by its literal
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// fields in the order of the document
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    fn render(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            JsonValue::Number(value) => out.push_str(value),
+            JsonValue::String(value) => write_string(value, out),
+            JsonValue::Array(values) => {
+                out.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    value.write(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(fields) => {
+                out.push('{');
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    write_string(key, out);
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Minimal JSON reader, so that structural diffs do not depend on `serde_json`
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(bytes: &'a [u8]) -> Option<JsonValue> {
+        let mut parser = Self { bytes, position: 0 };
+        let value = parser.value()?;
+        parser.whitespace();
+        (parser.position == bytes.len()).then_some(value)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\n' | b'\r' | b'\t')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.whitespace();
+        (self.next()? == byte).then_some(())
+    }
+
+    fn value(&mut self) -> Option<JsonValue> {
+        self.whitespace();
+        match self.peek()? {
+            b'n' => self.literal("null", JsonValue::Null),
+            b't' => self.literal("true", JsonValue::Bool(true)),
+            b'f' => self.literal("false", JsonValue::Bool(false)),
+            b'"' => self.string().map(JsonValue::String),
+            b'[' => self.array(),
+            b'{' => self.object(),
+            b'-' | b'0'..=b'9' => self.number(),
+            _ => None,
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: JsonValue) -> Option<JsonValue> {
+        let end = self.position + literal.len();
+        (self.bytes.get(self.position..end)? == literal.as_bytes()).then(|| {
+            self.position = end;
+            value
+        })
+    }
+
+    fn number(&mut self) -> Option<JsonValue> {
+        let start = self.position;
+        while matches!(self.peek(), Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+            self.position += 1;
+        }
+        let literal = std::str::from_utf8(&self.bytes[start..self.position]).ok()?;
+        literal.parse::<f64>().ok()?;
+        Some(JsonValue::Number(String::from(literal)))
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect(b'"')?;
+        let mut out = Vec::new();
+        loop {
+            match self.next()? {
+                b'"' => return String::from_utf8(out).ok(),
+                b'\\' => {
+                    let escaped = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode()?,
+                        _ => return None,
+                    };
+                    let mut buffer = [0; 4];
+                    out.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte => out.push(byte),
+            }
+        }
+    }
+
+    fn unicode(&mut self) -> Option<char> {
+        let high = self.hex()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high);
+        }
+        // characters outside of the basic plane are escaped as surrogate pair
+        if self.next()? != b'\\' || self.next()? != b'u' {
+            return None;
+        }
+        let low = self.hex()?;
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + low.checked_sub(0xDC00)?)
+    }
+
+    fn hex(&mut self) -> Option<u32> {
+        let end = self.position + 4;
+        let digits = std::str::from_utf8(self.bytes.get(self.position..end)?).ok()?;
+        self.position = end;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn array(&mut self) -> Option<JsonValue> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.whitespace();
+        if self.peek()? == b']' {
+            self.position += 1;
+            return Some(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.whitespace();
+            match self.next()? {
+                b',' => continue,
+                b']' => return Some(JsonValue::Array(values)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn object(&mut self) -> Option<JsonValue> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.whitespace();
+        if self.peek()? == b'}' {
+            self.position += 1;
+            return Some(JsonValue::Object(fields));
+        }
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            self.whitespace();
+            match self.next()? {
+                b',' => continue,
+                b'}' => return Some(JsonValue::Object(fields)),
+                _ => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::message::actor_message::ActorMessage;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Message that makes all earlier queued messages of the listed types irrelevant, see [ActorBuilder.add_superseding](../prelude/struct.ActorBuilder.html#method.add_superseding)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::any::TypeId;
+///
+/// struct Update { value: u64 }
+/// impl ActorMessage for Update {}
+///
+/// struct ResetAll {}
+/// impl ActorMessage for ResetAll {}
+/// impl Supersedes for ResetAll {
+///     fn superseded_types() -> Vec<TypeId> {
+///         vec![TypeId::of::<Update>()]
+///     }
+/// }
+/// ```
+pub trait Supersedes: ActorMessage + 'static {
+    /// Evaluated once per Actor when it is spawned
+    fn superseded_types() -> Vec<TypeId>;
+}
+
+/// Skips messages that have been superseded while they were queued
+///
+/// Every message is stamped with a sequence number when it is sent, a queued superseding message only records its own number per superseded type.
+/// The executor compares both when the message is dispatched, so that neither side ever scans the queue
+pub(crate) struct Supersession {
+    /// superseded types per superseding type
+    superseding: HashMap<TypeId, Vec<TypeId>>,
+    sequence: AtomicU64,
+    /// sequence number of the latest superseding message per superseded type
+    cutoffs: Mutex<HashMap<TypeId, u64>>,
+    has_cutoffs: AtomicBool,
+}
+
+impl Supersession {
+    pub fn new(superseding: HashMap<TypeId, Vec<TypeId>>) -> Self {
+        Self {
+            superseding,
+            sequence: AtomicU64::new(0),
+            cutoffs: Mutex::new(HashMap::new()),
+            has_cutoffs: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the sequence number of the sent message
+    pub fn stamp(&self) -> u64 {
+        self.sequence.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn is_superseding(&self, type_id: TypeId) -> bool {
+        self.superseding.contains_key(&type_id)
+    }
+
+    /// Moves the cutoffs of the types superseded by a message once it has been queued, so that a rejected message never supersedes anything
+    pub fn supersede(&self, type_id: TypeId, sequence: u64) {
+        if let Some(superseded) = self.superseding.get(&type_id) {
+            let mut cutoffs = self.cutoffs.lock().unwrap();
+            for superseded in superseded.iter() {
+                let cutoff = cutoffs.entry(*superseded).or_insert(0);
+                *cutoff = (*cutoff).max(sequence);
+            }
+            self.has_cutoffs.store(true, Ordering::Release);
+        }
+    }
+
+    pub fn is_superseded(&self, type_id: TypeId, sequence: u64) -> bool {
+        if !self.has_cutoffs.load(Ordering::Acquire) {
+            return false;
+        }
+        self.cutoffs
+            .lock()
+            .unwrap()
+            .get(&type_id)
+            .is_some_and(|cutoff| sequence < *cutoff)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::panic_report::ActorPanicReport;
+use crate::message::actor_message::ActorMessage;
+
+/// Why a child spawned through [ActorContext.spawn_child](../prelude/struct.ActorContext.html#method.spawn_child) ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChildTerminationReason {
+    /// the child stopped, either on its own, through its parent or through the system
+    Stopped,
+    /// the child panicked or a [TryHandler](../prelude/trait.TryHandler.html) of it returned an error, and it has not been restarted, because of [RestartPolicy::Never](../prelude/enum.RestartPolicy.html#variant.Never), an exceeded [RestartPolicy::Limited](../prelude/enum.RestartPolicy.html#variant.Limited) or because it was already stopping
+    Panicked(Box<ActorPanicReport>),
+}
+
+/// Delivered through [Actor.on_child_terminated](../prelude/trait.Actor.html#method.on_child_terminated) once a child stopped for good
+///
+/// A child that panics and is restarted according to its [RestartPolicy](../prelude/enum.RestartPolicy.html) has not terminated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildTerminated {
+    pub address: ActorAddress,
+    pub reason: ChildTerminationReason,
+}
+
+impl ChildTerminated {
+    pub(crate) fn new(address: ActorAddress, report: Option<&ActorPanicReport>) -> Self {
+        let reason = match report {
+            Some(report) => ChildTerminationReason::Panicked(Box::new(report.clone())),
+            None => ChildTerminationReason::Stopped,
+        };
+        Self { address, reason }
+    }
+}
+
+/// Delivered to Actors that watch another Actor once it stopped for good, see [ActorContext.watch](../prelude/struct.ActorContext.html#method.watch)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terminated {
+    pub address: ActorAddress,
+}
+
+impl ActorMessage for Terminated {}
@@ -0,0 +1,148 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::system::wakeup_manager::WakeupManager;
+use std::any::TypeId;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Defines when a suspended [Actor](../prelude/trait.Actor.html) continues processing its mailbox
+///
+/// See [ActorContext.suspend_until](../prelude/struct.ActorContext.html#method.suspend_until)
+#[derive(Clone)]
+pub enum SuspendCondition {
+    /// resumes once [SuspensionToken.resume](../prelude/struct.SuspensionToken.html#method.resume) is called
+    External,
+    /// resumes once a message of the given type arrives, the message is handled before any older queued message
+    UntilMessage(TypeId),
+    /// resumes once an Actor with the given address is running in the system
+    UntilActorStarted(ActorAddress),
+}
+
+impl SuspendCondition {
+    pub fn external() -> Self {
+        SuspendCondition::External
+    }
+
+    /// Resumes once a message of type `M` arrives, which is handled before any older queued message
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Work { id: usize }
+    /// impl ActorMessage for Work {}
+    /// struct Ready {}
+    /// impl ActorMessage for Ready {}
+    ///
+    /// struct Waiter {
+    ///     handled: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl Actor for Waiter {
+    ///     fn on_suspension_ended(&mut self, _reason: SuspensionEndReason) {
+    ///         self.handled.lock().unwrap().push(String::from("ended"));
+    ///     }
+    /// }
+    /// impl Handler<Work> for Waiter {
+    ///     fn handle(&mut self, msg: Work, context: &ActorContext<Self>) {
+    ///         if msg.id == 0 {
+    ///             context.suspend_until(SuspendCondition::until_message::<Ready>());
+    ///         }
+    ///         self.handled.lock().unwrap().push(format!("work {}", msg.id));
+    ///     }
+    /// }
+    /// impl Handler<Ready> for Waiter {
+    ///     fn handle(&mut self, _msg: Ready, _context: &ActorContext<Self>) {
+    ///         self.handled.lock().unwrap().push(String::from("ready"));
+    ///     }
+    /// }
+    ///
+    /// struct WaiterFactory {
+    ///     handled: Arc<Mutex<Vec<String>>>,
+    /// }
+    /// impl ActorFactory<Waiter> for WaiterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Waiter>) -> Waiter {
+    ///         Waiter { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system
+    ///     .builder()
+    ///     .spawn("waiter", WaiterFactory { handled: handled.clone() })
+    ///     .unwrap();
+    /// actor.send(Work { id: 0 });
+    /// actor.send(Work { id: 1 });
+    /// sleep(Duration::from_millis(100));
+    /// actor.send(Ready {});
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec!["work 0", "ready", "ended", "work 1"], *handled.lock().unwrap());
+    /// ```
+    pub fn until_message<M>() -> Self
+    where
+        M: ActorMessage + 'static,
+    {
+        SuspendCondition::UntilMessage(TypeId::of::<M>())
+    }
+
+    pub fn until_actor_started(address: ActorAddress) -> Self {
+        SuspendCondition::UntilActorStarted(address)
+    }
+}
+
+/// Reason delivered through [Actor.on_suspension_ended](../prelude/trait.Actor.html#method.on_suspension_ended)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SuspensionEndReason {
+    Resumed,
+    TimedOut,
+}
+
+/// Handle to resume a suspended [Actor](../prelude/trait.Actor.html) from any thread
+#[derive(Clone)]
+pub struct SuspensionToken {
+    resumed: Arc<AtomicBool>,
+    address: ActorAddress,
+    wakeup_manager: WakeupManager,
+}
+
+impl SuspensionToken {
+    pub fn new(address: ActorAddress, wakeup_manager: WakeupManager) -> Self {
+        Self {
+            resumed: Arc::new(AtomicBool::new(false)),
+            address,
+            wakeup_manager,
+        }
+    }
+
+    /// Resumes the suspended Actor. Calling this more than once has no further effect
+    pub fn resume(&self) {
+        if !self.resumed.swap(true, Ordering::AcqRel) {
+            self.wakeup_manager.wakeup(self.address.clone());
+        }
+    }
+
+    pub fn is_resumed(&self) -> bool {
+        self.resumed.load(Ordering::Acquire)
+    }
+}
+
+/// Suspension that has been requested through the [ActorContext](../prelude/struct.ActorContext.html), but not yet picked up by the Executor
+pub struct SuspensionRequest {
+    pub condition: SuspendCondition,
+    pub token: SuspensionToken,
+}
+
+/// Suspension that is currently enforced by the Executor
+pub struct ActiveSuspension {
+    pub condition: SuspendCondition,
+    pub token: SuspensionToken,
+    pub started: Instant,
+    pub deadline: Instant,
+}
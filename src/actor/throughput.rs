@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Weight of the latest slot in the smoothed cost per message
+const SMOOTHING: f64 = 0.25;
+
+/// Maximum amount of consecutive messages an Actor handles before its worker switches to the next Actor
+///
+/// See [ActorBuilder.set_throughput](../prelude/struct.ActorBuilder.html#method.set_throughput)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Throughput {
+    Fixed(usize),
+    Adaptive(AdaptiveThroughput),
+}
+
+/// Adapts the message budget of every scheduling slot, so that slots take about `target_slot_duration`
+///
+/// The budget is derived from a smoothed cost per message and clamped to `[min, max]`.
+/// It shrinks at once if slots take too long and at most doubles per slot otherwise, starting at `min`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct AdaptiveThroughput {
+    pub target_slot_duration: Duration,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl AdaptiveThroughput {
+    pub fn new(target_slot_duration: Duration, min: usize, max: usize) -> Self {
+        Self {
+            target_slot_duration,
+            min,
+            max,
+        }
+    }
+}
+
+/// Message budget of an Actor with adaptive throughput
+pub(crate) struct ThroughputController {
+    config: AdaptiveThroughput,
+    budget: usize,
+    /// smoothed handler time per message in nanoseconds
+    cost: Option<f64>,
+}
+
+impl ThroughputController {
+    pub fn new(config: AdaptiveThroughput) -> Self {
+        let mut controller = Self {
+            config,
+            budget: 0,
+            cost: None,
+        };
+        controller.reset();
+        controller
+    }
+
+    pub fn get_budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn reset(&mut self) {
+        self.budget = self.config.min.max(1);
+        self.cost = None;
+    }
+
+    /// Adapts the budget to the handler time of the last slot, returns the budget of the next one
+    pub fn record(&mut self, handled: usize, handler_time: Duration) -> usize {
+        if handled == 0 {
+            return self.budget;
+        }
+        let sample = handler_time.as_nanos() as f64 / handled as f64;
+        let cost = match self.cost {
+            Some(cost) => cost + SMOOTHING * (sample - cost),
+            None => sample,
+        };
+        self.cost = Some(cost);
+        let target = self.config.target_slot_duration.as_nanos() as f64;
+        let max = self.config.max.max(self.config.min).max(1);
+        let ideal = ((target / cost.max(1.0)) as usize).clamp(self.config.min.max(1), max);
+        self.budget = if ideal <= self.budget {
+            ideal
+        } else {
+            ideal.min(self.budget.saturating_mul(2))
+        };
+        self.budget
+    }
+}
@@ -0,0 +1,101 @@
+use crate::actor::actor_address::ActorAddress;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const SCHEDULED: u8 = 0;
+const DONE: u8 = 1;
+const CANCELLED: u8 = 2;
+
+/// Shortest interval of a repeating timer, shorter intervals are raised to it
+pub(crate) const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Handle of a message scheduled through [ActorContext.schedule_once](../prelude/struct.ActorContext.html#method.schedule_once) or [ActorContext.schedule_repeating](../prelude/struct.ActorContext.html#method.schedule_repeating)
+///
+/// Dropping the handle does not cancel the schedule
+#[derive(Debug, Clone)]
+pub struct ScheduledHandle {
+    state: Arc<AtomicU8>,
+}
+
+impl ScheduledHandle {
+    /// Prevents all further deliveries, returns `false` if there are none left, because it has already been cancelled, delivered or its Actor stopped
+    ///
+    /// A delivery that is already in the mailbox is not taken back
+    pub fn cancel(&self) -> bool {
+        self.state.compare_exchange(SCHEDULED, CANCELLED, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    /// `true` as long as there are deliveries left
+    pub fn is_scheduled(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == SCHEDULED
+    }
+}
+
+/// Outcome of a single delivery of a [Timer]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Delivery {
+    Delivered,
+    /// the bounded mailbox is full, the message has been kept for another attempt
+    Full,
+    /// the Actor stopped, the timer is dropped
+    Stopped,
+}
+
+pub(crate) type Deliver = Box<dyn FnMut() -> Delivery + Send>;
+
+/// A scheduled message, owned by the wakeup loop until it is done
+pub(crate) struct Timer {
+    /// `None` for timers of the system itself, they are only dropped once they are done or cancelled
+    pub owner: Option<ActorAddress>,
+    pub interval: Option<Duration>,
+    state: Arc<AtomicU8>,
+    deliver: Deliver,
+}
+
+impl Timer {
+    pub fn new(owner: Option<ActorAddress>, interval: Option<Duration>, deliver: Deliver) -> (Self, ScheduledHandle) {
+        let state = Arc::new(AtomicU8::new(SCHEDULED));
+        let handle = ScheduledHandle { state: state.clone() };
+        let timer = Self {
+            owner,
+            interval,
+            state,
+            deliver,
+        };
+        (timer, handle)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == CANCELLED
+    }
+
+    /// Delivers the message that was due at `due` and returns when it is due next, `None` once the timer is done
+    ///
+    /// A repeating timer that fell behind skips the deliveries it missed instead of catching up
+    pub fn fire(&mut self, due: Instant, now: Instant, retry_after: Duration) -> Option<Instant> {
+        if self.is_cancelled() {
+            return None;
+        }
+        let next = match ((self.deliver)(), self.interval) {
+            (Delivery::Full, _) => return Some(now + retry_after),
+            (Delivery::Stopped, _) | (Delivery::Delivered, None) => None,
+            (Delivery::Delivered, Some(interval)) => Some(next_tick(due, now, interval)),
+        };
+        if next.is_none() {
+            self.finish();
+        }
+        next
+    }
+
+    /// Marks the timer as done, unless it has been cancelled
+    pub fn finish(&self) {
+        let _ = self.state.compare_exchange(SCHEDULED, DONE, Ordering::SeqCst, Ordering::SeqCst);
+    }
+}
+
+/// First point in time after `now` that is a whole number of intervals after `due`, `interval` must not be zero
+fn next_tick(due: Instant, now: Instant, interval: Duration) -> Instant {
+    let behind = now.saturating_duration_since(due).as_nanos() % interval.as_nanos();
+    now + interval - Duration::from_nanos(behind as u64)
+}
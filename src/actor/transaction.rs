@@ -0,0 +1,386 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::timer::{Delivery, Timer, MIN_INTERVAL};
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::system::system_state::SystemState;
+use crate::system::wakeup_manager::WakeupManager;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::marker::PhantomData;
+use std::panic::UnwindSafe;
+use std::time::{Duration, Instant};
+
+/// Topic of the [EventBus](../prelude/struct.EventBus.html) on which every [TxnOutcome](./struct.TxnOutcome.html) and [TransactionConverged](./struct.TransactionConverged.html) is published
+pub const TRANSACTION_TOPIC: &str = "system.transaction";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrepareVote {
+    Yes,
+    No,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxnDecision {
+    Commit,
+    Abort,
+}
+
+/// Participant of a [Transaction](./struct.Transaction.html) for messages of type `M`
+///
+/// All calls go through the mailbox of the Actor, so a participant never handles two phases at the same time.
+/// `commit` and `abort` are redelivered until they return, reservations therefore have to survive restarts of the Actor
+/// and both have to ignore unknown or already resolved transactions
+pub trait TransactionalHandler<M>: Actor + Sized
+where
+    M: ActorMessage,
+{
+    /// Validates and reserves the effect of `msg` without applying it
+    fn prepare(&mut self, txn_id: u64, msg: M, context: &ActorContext<Self>) -> PrepareVote;
+    /// Applies the reservation of the transaction
+    fn commit(&mut self, txn_id: u64);
+    /// Drops the reservation of the transaction, also received by participants whose vote arrived too late
+    fn abort(&mut self, txn_id: u64);
+}
+
+/// Sent to a [TransactionalHandler](./trait.TransactionalHandler.html) by the coordinator
+pub struct PrepareMessage<M> {
+    txn_id: u64,
+    participant: usize,
+    msg: M,
+    reply: Sender<(usize, PrepareVote)>,
+}
+
+impl<M> ActorMessage for PrepareMessage<M> where M: ActorMessage {}
+
+/// Sent to a [TransactionalHandler](./trait.TransactionalHandler.html) by the coordinator until it is acknowledged
+pub struct DecisionMessage<M> {
+    txn_id: u64,
+    participant: usize,
+    decision: TxnDecision,
+    ack: Sender<usize>,
+    message: PhantomData<fn() -> M>,
+}
+
+impl<M> ActorMessage for DecisionMessage<M> where M: ActorMessage {}
+
+impl<A, M> Handler<PrepareMessage<M>> for A
+where
+    A: TransactionalHandler<M>,
+    M: ActorMessage,
+{
+    fn handle(&mut self, msg: PrepareMessage<M>, context: &ActorContext<A>) {
+        let vote = self.prepare(msg.txn_id, msg.msg, context);
+        // fails if the transaction has already been decided
+        let _ = msg.reply.send((msg.participant, vote));
+    }
+}
+
+impl<A, M> Handler<DecisionMessage<M>> for A
+where
+    A: TransactionalHandler<M>,
+    M: ActorMessage,
+{
+    fn handle(&mut self, msg: DecisionMessage<M>, _context: &ActorContext<A>) {
+        match msg.decision {
+            TxnDecision::Commit => self.commit(msg.txn_id),
+            TxnDecision::Abort => self.abort(msg.txn_id),
+        }
+        let _ = msg.ack.send(msg.participant);
+    }
+}
+
+/// Result of [Transaction.execute](./struct.Transaction.html#method.execute)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxnOutcome {
+    pub txn_id: u64,
+    pub decision: TxnDecision,
+    /// in the order of the participants, `None` if the participant did not vote in time
+    pub votes: Vec<(ActorAddress, Option<PrepareVote>)>,
+    /// participants that did not acknowledge the decision in time, it is redelivered in the background until they do, they stop, the system stops or `max_retries` is reached
+    pub stuck: Vec<ActorAddress>,
+    /// participants that stopped before they acknowledged the decision, it is not delivered to them anymore
+    pub abandoned: Vec<ActorAddress>,
+}
+
+impl TxnOutcome {
+    pub fn is_committed(&self) -> bool {
+        self.decision == TxnDecision::Commit
+    }
+}
+
+impl ActorMessage for TxnOutcome {}
+
+/// Published once all stuck participants of a [TxnOutcome](./struct.TxnOutcome.html) acknowledged the decision or stopped
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionConverged {
+    pub txn_id: u64,
+    pub decision: TxnDecision,
+    /// stuck participants that stopped before they acknowledged the decision
+    pub abandoned: Vec<ActorAddress>,
+}
+
+impl ActorMessage for TransactionConverged {}
+
+trait Participant: Send {
+    fn get_address(&self) -> &ActorAddress;
+    fn prepare(&mut self, txn_id: u64, participant: usize, reply: Sender<(usize, PrepareVote)>);
+    /// Returns `false` once the participant stopped
+    fn decide(&self, txn_id: u64, participant: usize, decision: TxnDecision, ack: Sender<usize>) -> bool;
+}
+
+struct TypedParticipant<A, M>
+where
+    A: Actor + 'static,
+{
+    wrapper: ActorWrapper<A>,
+    msg: Option<M>,
+}
+
+impl<A, M> Participant for TypedParticipant<A, M>
+where
+    A: TransactionalHandler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    fn get_address(&self) -> &ActorAddress {
+        self.wrapper.get_address()
+    }
+
+    fn prepare(&mut self, txn_id: u64, participant: usize, reply: Sender<(usize, PrepareVote)>) {
+        if let Some(msg) = self.msg.take() {
+            let _ = self.wrapper.send(PrepareMessage {
+                txn_id,
+                participant,
+                msg,
+                reply,
+            });
+        }
+    }
+
+    fn decide(&self, txn_id: u64, participant: usize, decision: TxnDecision, ack: Sender<usize>) -> bool {
+        // does not wait for room, redeliveries run on the wakeup loop
+        let result = self.wrapper.try_send(DecisionMessage::<M> {
+            txn_id,
+            participant,
+            decision,
+            ack,
+            message: PhantomData,
+        });
+        !matches!(result, Err(SendError::Stopped(_)))
+    }
+}
+
+/// Redelivers a decision to all participants that did not acknowledge it yet
+struct Redelivery {
+    txn_id: u64,
+    decision: TxnDecision,
+    participants: Vec<Box<dyn Participant>>,
+    unacked: Vec<usize>,
+    /// participants that stopped before they acknowledged the decision
+    abandoned: Vec<usize>,
+    ack: Sender<usize>,
+    acks: Receiver<usize>,
+}
+
+impl Redelivery {
+    /// Sends the decision to all unacknowledged participants, those that stopped are abandoned
+    fn send(&mut self) {
+        let participants = &self.participants;
+        let abandoned = &mut self.abandoned;
+        let (txn_id, decision, ack) = (self.txn_id, self.decision, &self.ack);
+        self.unacked.retain(|index| {
+            let is_alive = participants[*index].decide(txn_id, *index, decision, ack.clone());
+            if !is_alive {
+                abandoned.push(*index);
+            }
+            is_alive
+        });
+    }
+
+    /// Collects acknowledgements until `until` or, without it, those that already arrived, returns `true` once none are left
+    fn collect(&mut self, until: Option<Instant>) -> bool {
+        while !self.unacked.is_empty() {
+            let participant = match until {
+                Some(until) => self.acks.recv_deadline(until).ok(),
+                None => self.acks.try_recv().ok(),
+            };
+            match participant {
+                Some(participant) => self.unacked.retain(|unacked| *unacked != participant),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Redelivers every `retry_interval` until `deadline`, returns `true` once no participant is left
+    fn run(&mut self, deadline: Instant, retry_interval: Duration, state: &SystemState) -> bool {
+        loop {
+            self.send();
+            if self.collect(Some((Instant::now() + retry_interval).min(deadline))) {
+                return true;
+            }
+            if Instant::now() >= deadline || state.is_stopping() {
+                return false;
+            }
+        }
+    }
+
+    fn addresses(&self, indices: &[usize]) -> Vec<ActorAddress> {
+        indices.iter().map(|index| self.participants[*index].get_address().clone()).collect()
+    }
+}
+
+/// Two-phase commit across multiple Actors, see [ActorSystem.transaction](./struct.ActorSystem.html#method.transaction)
+///
+/// No locks are held, isolation per participant is given by the mailbox of its Actor
+pub struct Transaction {
+    state: SystemState,
+    wakeup_manager: WakeupManager,
+    participants: Vec<Box<dyn Participant>>,
+    retry_interval: Duration,
+    max_retries: usize,
+}
+
+impl Transaction {
+    pub(crate) fn new(state: SystemState, wakeup_manager: WakeupManager) -> Self {
+        Self {
+            state,
+            wakeup_manager,
+            participants: Vec::new(),
+            retry_interval: Duration::from_millis(50),
+            max_retries: 100,
+        }
+    }
+
+    /// Adds a participant that prepares `msg`
+    pub fn participant<A, M>(mut self, wrapper: &ActorWrapper<A>, msg: M) -> Self
+    where
+        A: TransactionalHandler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        self.participants.push(Box::new(TypedParticipant {
+            wrapper: wrapper.clone(),
+            msg: Some(msg),
+        }));
+        self
+    }
+
+    /// Interval in which an unacknowledged decision is redelivered, defaults to `50ms`
+    pub fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    /// Redeliveries to stuck participants after `execute` returned, defaults to `100`
+    ///
+    /// Once they are used up the decision is no longer delivered and a warning is printed
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Prepares all participants and commits only if all of them voted [PrepareVote::Yes](./enum.PrepareVote.html#variant.Yes) within `timeout`
+    ///
+    /// Blocks for at most `timeout` while collecting the votes and another `timeout` while delivering the decision
+    pub fn execute(mut self, timeout: Duration) -> TxnOutcome {
+        let txn_id = self.state.next_transaction_id();
+        let (reply, replies) = unbounded();
+        for (index, participant) in self.participants.iter_mut().enumerate() {
+            participant.prepare(txn_id, index, reply.clone());
+        }
+        drop(reply);
+
+        let deadline = Instant::now() + timeout;
+        let mut votes = vec![None; self.participants.len()];
+        let mut pending = votes.len();
+        while pending > 0 {
+            match replies.recv_deadline(deadline) {
+                Ok((index, vote)) => {
+                    votes[index] = Some(vote);
+                    pending -= 1;
+                }
+                Err(_) => break,
+            }
+        }
+        let decision = if votes.iter().all(|vote| *vote == Some(PrepareVote::Yes)) {
+            TxnDecision::Commit
+        } else {
+            TxnDecision::Abort
+        };
+
+        // a participant that voted no did not reserve anything
+        let unacked = (0..votes.len())
+            .filter(|index| votes[*index] != Some(PrepareVote::No))
+            .collect();
+        let (ack, acks) = unbounded();
+        let mut redelivery = Redelivery {
+            txn_id,
+            decision,
+            participants: std::mem::take(&mut self.participants),
+            unacked,
+            abandoned: Vec::new(),
+            ack,
+            acks,
+        };
+        let is_delivered = redelivery.run(Instant::now() + timeout, self.retry_interval, &self.state);
+
+        let outcome = TxnOutcome {
+            txn_id,
+            decision,
+            votes: redelivery
+                .participants
+                .iter()
+                .zip(votes)
+                .map(|(participant, vote)| (participant.get_address().clone(), vote))
+                .collect(),
+            stuck: redelivery.addresses(&redelivery.unacked),
+            abandoned: redelivery.addresses(&redelivery.abandoned),
+        };
+        let _ = self.state.get_event_bus().publish(TRANSACTION_TOPIC, outcome.clone());
+        if !is_delivered {
+            self.redeliver(redelivery);
+        }
+        outcome
+    }
+
+    /// Keeps redelivering on the wakeup loop, a timer of the system that ends with the system
+    fn redeliver(&self, mut redelivery: Redelivery) {
+        // abandoned participants have already been reported by the outcome
+        redelivery.abandoned.clear();
+        let state = self.state.clone();
+        let mut retries_left = self.max_retries;
+        let deliver = Box::new(move || {
+            if !redelivery.collect(None) {
+                if state.is_stopping() {
+                    return Delivery::Stopped;
+                }
+                if retries_left == 0 {
+                    println!(
+                        "TRANSACTION UNACKNOWLEDGED: {:?} of transaction {} by {:?}, redelivery gave up",
+                        redelivery.decision,
+                        redelivery.txn_id,
+                        redelivery.addresses(&redelivery.unacked)
+                    );
+                    return Delivery::Stopped;
+                }
+                retries_left -= 1;
+                redelivery.send();
+                if !redelivery.collect(None) {
+                    return Delivery::Delivered;
+                }
+            }
+            let converged = TransactionConverged {
+                txn_id: redelivery.txn_id,
+                decision: redelivery.decision,
+                abandoned: redelivery.addresses(&redelivery.abandoned),
+            };
+            let _ = state.get_event_bus().publish(TRANSACTION_TOPIC, converged);
+            Delivery::Stopped
+        });
+        let interval = self.retry_interval.max(MIN_INTERVAL);
+        let (timer, _) = Timer::new(None, Some(interval), deliver);
+        self.wakeup_manager.schedule_timer(Instant::now() + interval, timer);
+    }
+}
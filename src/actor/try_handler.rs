@@ -0,0 +1,116 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::error::Error;
+use std::panic::UnwindSafe;
+
+/// Returned by a [TryHandler], the Actor is restarted or stopped according to its [RestartPolicy](../prelude/enum.RestartPolicy.html) as if it had panicked
+///
+/// Any [std::error::Error] converts into an `ActorError`, so that `?` can be used within [TryHandler.try_handle](./trait.TryHandler.html#tymethod.try_handle)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorError {
+    pub message: String,
+}
+
+impl ActorError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+}
+
+impl<E> From<E> for ActorError
+where
+    E: Error,
+{
+    fn from(error: E) -> Self {
+        Self::new(error.to_string())
+    }
+}
+
+/// Marks messages that are handled by a [TryHandler] instead of a [Handler]
+///
+/// Implemented by `#[derive(ActorMessage)]` with `#[message(fallible)]`
+pub trait FallibleMessage: ActorMessage {}
+
+/// Alternative to [Handler] for messages that can fail without a panic
+///
+/// An `Err` is routed through the same machinery as a panic: it counts towards [ActorStats.panics](../prelude/struct.ActorStats.html#structfield.panics)
+/// and the [RestartPolicy](../prelude/enum.RestartPolicy.html), [Actor.on_panic](../prelude/trait.Actor.html#method.on_panic) receives it as [ActorPanicReport.error](../prelude/struct.ActorPanicReport.html#structfield.error)
+/// right before `post_stop` and `pre_restart`, and a parent is notified with it through [ChildTerminationReason::Panicked](../prelude/enum.ChildTerminationReason.html#variant.Panicked).
+/// Panics within `try_handle` are still caught as for every other handler
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Parse(String);
+/// impl ActorMessage for Parse {}
+/// impl FallibleMessage for Parse {}
+///
+/// struct Parser {
+///     errors: Arc<Mutex<Vec<ActorError>>>,
+///     total: u32,
+/// }
+/// impl Actor for Parser {
+///     fn on_panic(&mut self, report: ActorPanicReport) {
+///         assert!(report.panic_message.is_none());
+///         self.errors.lock().unwrap().push(report.error.unwrap());
+///     }
+/// }
+/// impl TryHandler<Parse> for Parser {
+///     fn try_handle(&mut self, msg: Parse, _context: &ActorContext<Self>) -> Result<(), ActorError> {
+///         self.total += msg.0.parse::<u32>()?;
+///         Ok(())
+///     }
+/// }
+/// struct ParserFactory {
+///     errors: Arc<Mutex<Vec<ActorError>>>,
+/// }
+/// impl ActorFactory<Parser> for ParserFactory {
+///     fn new_actor(&self, _context: ActorContext<Parser>) -> Parser {
+///         Parser { errors: self.errors.clone(), total: 0 }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let errors = Arc::new(Mutex::new(Vec::new()));
+/// let parser = actor_system
+///     .builder()
+///     .set_restart_policy(RestartPolicy::Always)
+///     .spawn("parser", ParserFactory { errors: errors.clone() })
+///     .unwrap();
+/// parser.send(Parse(String::from("1"))).unwrap();
+/// parser.send(Parse(String::from("one"))).unwrap();
+/// parser.send(Parse(String::from("2"))).unwrap();
+/// sleep(Duration::from_millis(300));
+///
+/// assert_eq!(*errors.lock().unwrap(), vec![ActorError::new("invalid digit found in string")]);
+/// let stats = actor_system.actor_stats(parser.get_address()).unwrap();
+/// assert_eq!((stats.panics, stats.restarts, stats.processed), (1, 1, 2));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+pub trait TryHandler<M>: Actor + Sized
+where
+    M: FallibleMessage,
+{
+    fn try_handle(&mut self, msg: M, context: &ActorContext<Self>) -> Result<(), ActorError>;
+}
+
+impl<A, M> Handler<M> for A
+where
+    A: TryHandler<M> + UnwindSafe + 'static,
+    M: FallibleMessage,
+{
+    fn handle(&mut self, msg: M, context: &ActorContext<A>) {
+        if let Err(error) = self.try_handle(msg, context) {
+            context.fail(error);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::actor::actor::Actor;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::serialized_message::SerializedMessage;
+
+/// Message an Actor has not been able to handle, see [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnhandledInfo {
+    /// typed message that could not be dispatched to a handler of the Actor
+    Typed { type_name: &'static str },
+    /// serialized message with a type tag the Actor did not register, or whose content could not be deserialized
+    Serialized(Box<SerializedMessage>),
+}
+
+impl UnhandledInfo {
+    /// Type name of the message, [SerializedMessage] for serialized messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            UnhandledInfo::Typed { type_name } => type_name,
+            UnhandledInfo::Serialized(_) => std::any::type_name::<SerializedMessage>(),
+        }
+    }
+}
+
+/// Hands an [UnhandledInfo] to the Actor, so the hook is executed within the mailbox order like any other message
+pub(crate) struct UnhandledMessage {
+    info: UnhandledInfo,
+}
+
+impl UnhandledMessage {
+    pub fn new(info: UnhandledInfo) -> Self {
+        Self { info }
+    }
+}
+
+impl ActorMessage for UnhandledMessage {}
+
+impl<A> Handler<UnhandledMessage> for A
+where
+    A: Actor + Sized + 'static,
+{
+    fn handle(&mut self, msg: UnhandledMessage, context: &ActorContext<A>) {
+        dispatch(self, msg.info, context);
+    }
+}
+
+/// Counts the message and executes the hook of the Actor
+pub(crate) fn dispatch<A>(actor: &mut A, info: UnhandledInfo, context: &ActorContext<A>)
+where
+    A: Actor + Sized + 'static,
+{
+    context.actor_ref.get_metrics().increment_unhandled();
+    actor.on_unhandled_message(info, context);
+}
@@ -0,0 +1,103 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::mailbox::MailboxFlags;
+use crate::system::system_state::ActorRegistry;
+use std::marker::PhantomData;
+use std::panic::UnwindSafe;
+use std::sync::{Arc, Weak};
+
+/// Reference to an Actor that does not keep its mailbox alive, see [ActorWrapper.downgrade](../prelude/struct.ActorWrapper.html#method.downgrade)
+pub struct WeakActorWrapper<A>
+where
+    A: Actor + 'static,
+{
+    address: ActorAddress,
+    /// identifies the mailbox, so that an Actor spawned later with the same address is not mistaken for the referenced one
+    flags: Weak<MailboxFlags>,
+    actor_registry: ActorRegistry,
+    phantom: PhantomData<fn() -> A>,
+}
+
+impl<A> UnwindSafe for WeakActorWrapper<A> where A: Actor + 'static {}
+
+impl<A> WeakActorWrapper<A>
+where
+    A: Actor + UnwindSafe,
+{
+    pub(crate) fn new(address: ActorAddress, flags: Weak<MailboxFlags>, actor_registry: ActorRegistry) -> Self {
+        Self {
+            address,
+            flags,
+            actor_registry,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the [ActorWrapper] as long as the Actor has not stopped
+    ///
+    /// `None` once the Actor has stopped, once it has been removed from the system or once the system is gone.
+    /// The Actor can still stop right afterwards, sending to it then ends up as dead letter like through any other [ActorWrapper]
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Session {}
+    /// impl Actor for Session {}
+    /// impl Handler<Ping> for Session {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct SessionFactory {}
+    /// impl ActorFactory<Session> for SessionFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Session>) -> Session {
+    ///         Session {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let session = actor_system.builder().spawn("session", SessionFactory {}).unwrap();
+    /// let weak = session.downgrade();
+    /// assert!(weak.upgrade().unwrap().send(Ping {}).is_ok());
+    ///
+    /// session.stop();
+    /// assert!(session.await_stop(Duration::from_secs(1)));
+    /// assert!(weak.upgrade().is_none());
+    ///
+    /// // a new Actor with the same address is a different Actor
+    /// let _respawned = actor_system.builder().spawn("session", SessionFactory {}).unwrap();
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    pub fn upgrade(&self) -> Option<ActorWrapper<A>> {
+        let wrapper = self.actor_registry.get_actor_wrapper::<A>(&self.address)?;
+        if Weak::as_ptr(&self.flags) != Arc::as_ptr(&wrapper.get_mailbox().flags) {
+            return None;
+        }
+        Some(wrapper)
+    }
+
+    pub fn get_address(&self) -> &ActorAddress {
+        &self.address
+    }
+}
+
+impl<A> Clone for WeakActorWrapper<A>
+where
+    A: Actor + UnwindSafe,
+{
+    fn clone(&self) -> Self {
+        Self {
+            address: self.address.clone(),
+            flags: self.flags.clone(),
+            actor_registry: self.actor_registry.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::metric_stripes;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Storage of the hot per-Actor counters that is measured by [CounterContention](./struct.CounterContention.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterLayout {
+    /// nothing is counted, the baseline
+    Off,
+    /// one atomic per counter next to each other, every handled message adds to all of them
+    Shared,
+    /// per-thread stripes merged on read, as written by every Executor, flushed at the end of every slot
+    Striped,
+}
+
+/// Outcome of a single [CounterContention.measure](./struct.CounterContention.html#method.measure)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentionResult {
+    pub layout: CounterLayout,
+    pub elapsed: Duration,
+    /// processed count summed over all Actors after the run, 0 for [CounterLayout::Off](./enum.CounterLayout.html#variant.Off)
+    pub counted: u64,
+}
+
+impl ContentionResult {
+    pub fn messages_per_second(&self, messages: usize) -> f64 {
+        messages as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Stands in for a handler, returns a value that is used as its handler time
+fn handle(message: u64, work: usize) -> u64 {
+    let mut state = black_box(message) | 1;
+    for _ in 0..work {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+    }
+    black_box(state) % 1000 + 1
+}
+
+/// The counters of the previous metrics storage
+#[derive(Default)]
+struct SharedCounters {
+    processed: AtomicU64,
+    handler_nanos: AtomicU64,
+    construction_nanos: AtomicU64,
+}
+
+/// Replays the counter writes of a thread pool without any Actor, so that only the cost of counting is measured
+///
+/// Every thread runs `slots` scheduling slots of `budget` messages, each slot on the next of `actors` Actors, so that all Actors are handled by all threads over time
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterContention {
+    pub threads: usize,
+    pub actors: usize,
+    pub slots: usize,
+    pub budget: usize,
+    /// rounds of a xorshift per message that stand in for the handler
+    pub handler_work: usize,
+}
+
+impl CounterContention {
+    pub fn new(threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            actors: 64,
+            slots: 20000,
+            budget: 100,
+            handler_work: 50,
+        }
+    }
+
+    pub fn with_actors(mut self, actors: usize) -> Self {
+        self.actors = actors.max(1);
+        self
+    }
+
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.slots = slots.max(1);
+        self
+    }
+
+    pub fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = budget.max(1);
+        self
+    }
+
+    pub fn with_handler_work(mut self, handler_work: usize) -> Self {
+        self.handler_work = handler_work;
+        self
+    }
+
+    /// Handled messages of a single run
+    pub fn messages(&self) -> usize {
+        self.threads * self.slots * self.budget
+    }
+
+    pub fn measure(&self, layout: CounterLayout) -> ContentionResult {
+        let shared: Arc<Vec<SharedCounters>> = Arc::new((0..self.actors).map(|_| SharedCounters::default()).collect());
+        let striped: Arc<Vec<ActorMetrics>> = Arc::new(
+            (0..self.actors)
+                .map(|_| ActorMetrics::new(Box::new(|| 0), Default::default(), None))
+                .collect(),
+        );
+        let barrier = Arc::new(Barrier::new(self.threads + 1));
+        let threads: Vec<_> = (0..self.threads)
+            .map(|thread| {
+                let config = self.clone();
+                let shared = shared.clone();
+                let striped = striped.clone();
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    for slot in 0..config.slots {
+                        let actor = (thread + slot) % config.actors;
+                        for message in 0..config.budget {
+                            let handler_nanos = handle(message as u64, config.handler_work);
+                            match layout {
+                                CounterLayout::Off => {}
+                                CounterLayout::Shared => {
+                                    let counters = &shared[actor];
+                                    counters.construction_nanos.fetch_add(0, Ordering::Relaxed);
+                                    counters.handler_nanos.fetch_add(handler_nanos, Ordering::Relaxed);
+                                    counters.processed.fetch_add(1, Ordering::Relaxed);
+                                }
+                                CounterLayout::Striped => {
+                                    let metrics = &striped[actor];
+                                    metrics.add_construction_time(Duration::from_nanos(0));
+                                    metrics.add_handler_time(Duration::from_nanos(handler_nanos));
+                                    metrics.increment_processed();
+                                }
+                            }
+                        }
+                        if layout == CounterLayout::Striped {
+                            metric_stripes::flush();
+                        }
+                    }
+                    barrier.wait();
+                })
+            })
+            .collect();
+        barrier.wait();
+        let started = Instant::now();
+        barrier.wait();
+        let elapsed = started.elapsed();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        let counted = match layout {
+            CounterLayout::Off => 0,
+            CounterLayout::Shared => shared.iter().map(|counters| counters.processed.load(Ordering::Relaxed)).sum(),
+            CounterLayout::Striped => striped.iter().map(|metrics| metrics.get_processed() as u64).sum(),
+        };
+        ContentionResult {
+            layout,
+            elapsed,
+            counted,
+        }
+    }
+}
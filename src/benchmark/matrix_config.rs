@@ -0,0 +1,111 @@
+/// Optional feature that is toggled at runtime for a cell of the [OverheadReport](./struct.OverheadReport.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feature {
+    /// `general.metrics_history` with a resolution of 10ms
+    MetricsHistory,
+    /// a header that is propagated on every send
+    Headers,
+    /// the message type is registered through `ActorSystem.register_audited`
+    Provenance,
+    /// payload serializer, `general.max_captured_payload_bytes` and the last 16 messages of every Actor
+    PayloadCapture,
+    /// high priority Actors, so that the priority is inherited on every send
+    Priority,
+    /// a latency SLO on every Actor
+    LatencySlo,
+    /// watermarks on every Actor
+    Watermarks,
+    /// `general.record_execution`, requires the `replay` feature
+    #[cfg(feature = "replay")]
+    ExecutionRecording,
+}
+
+impl Feature {
+    /// All features that are compiled in
+    pub fn all() -> Vec<Feature> {
+        vec![
+            Feature::MetricsHistory,
+            Feature::Headers,
+            Feature::Provenance,
+            Feature::PayloadCapture,
+            Feature::Priority,
+            Feature::LatencySlo,
+            Feature::Watermarks,
+            #[cfg(feature = "replay")]
+            Feature::ExecutionRecording,
+        ]
+    }
+}
+
+/// Scenario and cells of [OverheadReport.measure](./struct.OverheadReport.html#method.measure)
+///
+/// Every cell bounces a single message `messages` times between two Actors of a new [ActorSystem](../prelude/struct.ActorSystem.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixConfig {
+    /// handled messages per run
+    pub messages: usize,
+    /// handled messages before the first run, also covers the startup of the thread pool
+    pub warmup: usize,
+    /// size of the payload that is moved along with the message
+    pub payload_bytes: usize,
+    /// runs per cell, the median is reported
+    pub runs: usize,
+    /// feature combinations that are measured in addition to the baseline
+    pub cells: Vec<Vec<Feature>>,
+}
+
+impl MatrixConfig {
+    /// Measures every feature on its own, metrics history together with headers and all features together
+    pub fn new() -> Self {
+        let mut cells: Vec<Vec<Feature>> = Feature::all()
+            .into_iter()
+            .map(|feature| vec![feature])
+            .collect();
+        cells.push(vec![Feature::MetricsHistory, Feature::Headers]);
+        cells.push(Feature::all());
+        Self {
+            messages: 10000,
+            warmup: 1000,
+            payload_bytes: 0,
+            runs: 3,
+            cells,
+        }
+    }
+
+    pub fn with_messages(mut self, messages: usize) -> Self {
+        self.messages = messages.max(1);
+        self
+    }
+
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    pub fn with_payload_bytes(mut self, payload_bytes: usize) -> Self {
+        self.payload_bytes = payload_bytes;
+        self
+    }
+
+    pub fn with_runs(mut self, runs: usize) -> Self {
+        self.runs = runs.max(1);
+        self
+    }
+
+    /// Replaces all cells
+    pub fn with_cells(mut self, cells: Vec<Vec<Feature>>) -> Self {
+        self.cells = cells;
+        self
+    }
+
+    pub fn with_cell(mut self, features: Vec<Feature>) -> Self {
+        self.cells.push(features);
+        self
+    }
+}
+
+impl Default for MatrixConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,14 @@
+pub mod counter_contention;
+pub mod counting_allocator;
+pub mod matrix_config;
+pub mod overhead_report;
+mod scenario;
+
+pub mod prelude {
+    pub use crate::benchmark::counter_contention::{ContentionResult, CounterContention, CounterLayout};
+    pub use crate::benchmark::counting_allocator::CountingAllocator;
+    pub use crate::benchmark::matrix_config::{Feature, MatrixConfig};
+    pub use crate::benchmark::overhead_report::{
+        CellResult, CompileTimeFeature, Environment, OverheadReport,
+    };
+}
@@ -0,0 +1,344 @@
+use crate::benchmark::counting_allocator::CountingAllocator;
+use crate::benchmark::matrix_config::{Feature, MatrixConfig};
+use crate::benchmark::scenario;
+use crate::config::tyractorsaur_config::{TyractorsaurConfig, DEFAULT_POOL};
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Machine and configuration the numbers of an [OverheadReport](./struct.OverheadReport.html) have been measured with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Environment {
+    pub version: &'static str,
+    pub cpus: usize,
+    pub pool: String,
+    pub pool_threads: usize,
+    pub message_throughput: usize,
+    /// `false` unless the [CountingAllocator](./struct.CountingAllocator.html) is installed
+    pub allocations_counted: bool,
+}
+
+/// Cargo feature that can not be toggled at runtime
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompileTimeFeature {
+    pub name: &'static str,
+    pub is_enabled: bool,
+    /// the runtime cell that measures the feature, if it is compiled in
+    pub measured_by: Option<Feature>,
+    pub note: &'static str,
+}
+
+/// Median cost of a single handled message
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellResult {
+    /// empty for the baseline
+    pub features: Vec<Feature>,
+    pub nanos_per_message: f64,
+    /// `None` unless the [CountingAllocator](./struct.CountingAllocator.html) is installed
+    pub allocations_per_message: Option<f64>,
+    /// difference to the baseline
+    pub delta_nanos: f64,
+    pub delta_allocations: Option<f64>,
+}
+
+/// Cost of optional features on the current machine
+///
+/// Every cell runs the same scenario in a new [ActorSystem](../prelude/struct.ActorSystem.html), the baseline without any of them.
+/// Cargo features are listed in `compile_time`, run `cargo bench --bench overhead --features replay` to measure execution recording.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::bench::{CountingAllocator, Feature, MatrixConfig, OverheadReport};
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// let config = MatrixConfig::new()
+///     .with_messages(2000)
+///     .with_warmup(200)
+///     .with_runs(1)
+///     .with_cells(vec![vec![Feature::MetricsHistory], vec![Feature::Headers, Feature::MetricsHistory]]);
+/// let report = OverheadReport::measure(config);
+/// println!("{}", report);
+/// assert!(report.environment.allocations_counted);
+/// assert_eq!(2, report.cells.len());
+///
+/// // generous limits, only meant to catch regressions by orders of magnitude
+/// assert!(report.baseline.nanos_per_message < 1_000_000.0);
+/// let history = report.get(&[Feature::MetricsHistory]).unwrap();
+/// assert!(history.delta_nanos < 1_000_000.0);
+/// assert!(history.allocations_per_message.unwrap() < 100.0);
+/// let combined = report.get(&[Feature::MetricsHistory, Feature::Headers]).unwrap();
+/// assert!(combined.delta_nanos < 1_000_000.0);
+/// assert!(combined.allocations_per_message.unwrap() < 100.0);
+/// assert!(report.to_json().contains("\"features\":[\"MetricsHistory\",\"Headers\"]"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverheadReport {
+    pub environment: Environment,
+    pub messages: usize,
+    pub payload_bytes: usize,
+    pub baseline: CellResult,
+    pub cells: Vec<CellResult>,
+    pub compile_time: Vec<CompileTimeFeature>,
+}
+
+impl OverheadReport {
+    /// Runs the baseline and every cell of the matrix one after another, this takes at least a few seconds per cell
+    pub fn measure(config: MatrixConfig) -> Self {
+        // the first system of the process pays for page faults and lazily started threads
+        scenario::measure(&[], &config.clone().with_runs(1));
+        let (nanos_per_message, allocations_per_message) = scenario::measure(&[], &config);
+        let baseline = CellResult {
+            features: Vec::new(),
+            nanos_per_message,
+            allocations_per_message,
+            delta_nanos: 0.0,
+            delta_allocations: allocations_per_message.map(|_| 0.0),
+        };
+        let cells = config
+            .cells
+            .iter()
+            .map(|features| {
+                let mut features = features.clone();
+                features.sort();
+                features.dedup();
+                let (nanos_per_message, allocations_per_message) =
+                    scenario::measure(&features, &config);
+                CellResult {
+                    features,
+                    nanos_per_message,
+                    allocations_per_message,
+                    delta_nanos: nanos_per_message - baseline.nanos_per_message,
+                    delta_allocations: allocations_per_message
+                        .zip(baseline.allocations_per_message)
+                        .map(|(cell, baseline)| cell - baseline),
+                }
+            })
+            .collect();
+        Self {
+            environment: environment(),
+            messages: config.messages,
+            payload_bytes: config.payload_bytes,
+            baseline,
+            cells,
+            compile_time: compile_time_features(),
+        }
+    }
+
+    /// Returns the cell with exactly the given features, regardless of their order
+    pub fn get(&self, features: &[Feature]) -> Option<&CellResult> {
+        let mut features = features.to_vec();
+        features.sort();
+        features.dedup();
+        self.cells.iter().find(|cell| cell.features == features)
+    }
+
+    pub fn to_json(&self) -> String {
+        let environment = &self.environment;
+        let mut json = format!(
+            "{{\"environment\":{{\"version\":\"{}\",\"cpus\":{},\"pool\":\"{}\",\"pool_threads\":{},\"message_throughput\":{},\"allocations_counted\":{}}},",
+            environment.version,
+            environment.cpus,
+            environment.pool,
+            environment.pool_threads,
+            environment.message_throughput,
+            environment.allocations_counted
+        );
+        let _ = write!(
+            json,
+            "\"messages\":{},\"payload_bytes\":{},\"baseline\":{},\"cells\":[",
+            self.messages,
+            self.payload_bytes,
+            cell_json(&self.baseline)
+        );
+        let cells: Vec<String> = self.cells.iter().map(cell_json).collect();
+        json.push_str(&cells.join(","));
+        json.push_str("],\"compile_time\":[");
+        let compile_time: Vec<String> = self
+            .compile_time
+            .iter()
+            .map(|feature| {
+                format!(
+                    "{{\"name\":\"{}\",\"enabled\":{},\"measured_by\":{}}}",
+                    feature.name,
+                    feature.is_enabled,
+                    feature.measured_by.map_or_else(
+                        || String::from("null"),
+                        |measured_by| format!("\"{:?}\"", measured_by)
+                    )
+                )
+            })
+            .collect();
+        json.push_str(&compile_time.join(","));
+        json.push_str("]}");
+        json
+    }
+}
+
+impl Display for OverheadReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let environment = &self.environment;
+        writeln!(
+            f,
+            "tyractorsaur {}, {} cpus, pool {} with {} threads, message throughput {}",
+            environment.version,
+            environment.cpus,
+            environment.pool,
+            environment.pool_threads,
+            environment.message_throughput
+        )?;
+        writeln!(
+            f,
+            "{} messages with {} payload bytes",
+            self.messages, self.payload_bytes
+        )?;
+        if !environment.allocations_counted {
+            writeln!(
+                f,
+                "allocations are not counted, install the CountingAllocator to count them"
+            )?;
+        }
+        writeln!(f)?;
+        let rows: Vec<(String, &CellResult)> = std::iter::once(&self.baseline)
+            .chain(self.cells.iter())
+            .map(|cell| {
+                let names: Vec<String> = cell
+                    .features
+                    .iter()
+                    .map(|feature| format!("{:?}", feature))
+                    .collect();
+                let features = if names.is_empty() {
+                    String::from("baseline")
+                } else {
+                    names.join(" + ")
+                };
+                (features, cell)
+            })
+            .collect();
+        let width = rows
+            .iter()
+            .map(|(features, _)| features.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "{:<width$} {:>10} {:>10} {:>11} {:>12}",
+            "features",
+            "ns/msg",
+            "delta ns",
+            "allocs/msg",
+            "delta allocs",
+            width = width
+        )?;
+        for (features, cell) in rows {
+            writeln!(
+                f,
+                "{:<width$} {:>10.1} {:>+10.1} {:>11} {:>12}",
+                features,
+                cell.nanos_per_message,
+                cell.delta_nanos,
+                cell.allocations_per_message
+                    .map_or_else(|| String::from("-"), |a| format!("{:.2}", a)),
+                cell.delta_allocations
+                    .map_or_else(|| String::from("-"), |a| format!("{:+.2}", a)),
+                width = width
+            )?;
+        }
+        writeln!(f)?;
+        for feature in self.compile_time.iter() {
+            let state = if feature.is_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            };
+            writeln!(
+                f,
+                "feature {:<12} {:<9} {}",
+                feature.name, state, feature.note
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn cell_json(cell: &CellResult) -> String {
+    let features: Vec<String> = cell
+        .features
+        .iter()
+        .map(|feature| format!("\"{:?}\"", feature))
+        .collect();
+    format!(
+        "{{\"features\":[{}],\"nanos_per_message\":{},\"allocations_per_message\":{},\"delta_nanos\":{},\"delta_allocations\":{}}}",
+        features.join(","),
+        number(Some(cell.nanos_per_message)),
+        number(cell.allocations_per_message),
+        number(Some(cell.delta_nanos)),
+        number(cell.delta_allocations)
+    )
+}
+
+/// JSON has no representation for failed cells
+fn number(value: Option<f64>) -> String {
+    match value {
+        Some(value) if value.is_finite() => format!("{:.3}", value),
+        _ => String::from("null"),
+    }
+}
+
+fn environment() -> Environment {
+    let config = TyractorsaurConfig::new().unwrap();
+    let cpus = num_cpus::get();
+    let pool_threads = config
+        .thread_pool
+        .config
+        .get(DEFAULT_POOL)
+        .map_or(0, |pool| pool.thread_count(cpus));
+    Environment {
+        version: env!("CARGO_PKG_VERSION"),
+        cpus,
+        pool: String::from(DEFAULT_POOL),
+        pool_threads,
+        message_throughput: config.general.default_message_throughput,
+        allocations_counted: CountingAllocator::is_installed(),
+    }
+}
+
+fn compile_time_features() -> Vec<CompileTimeFeature> {
+    vec![
+        CompileTimeFeature {
+            name: "replay",
+            is_enabled: cfg!(feature = "replay"),
+            #[cfg(feature = "replay")]
+            measured_by: Some(Feature::ExecutionRecording),
+            #[cfg(not(feature = "replay"))]
+            measured_by: None,
+            note: "costs nothing unless general.record_execution is set",
+        },
+        CompileTimeFeature {
+            name: "testing",
+            is_enabled: cfg!(feature = "testing"),
+            measured_by: None,
+            note: "not on the message path",
+        },
+        CompileTimeFeature {
+            name: "signals",
+            is_enabled: cfg!(feature = "signals"),
+            measured_by: None,
+            note: "not on the message path",
+        },
+        CompileTimeFeature {
+            name: "http-bridge",
+            is_enabled: cfg!(feature = "http-bridge"),
+            measured_by: None,
+            note: "only messages sent through the bridge pay for it",
+        },
+        CompileTimeFeature {
+            name: "ffi",
+            is_enabled: cfg!(feature = "ffi"),
+            measured_by: None,
+            note: "only messages sent through the ffi pay for it",
+        },
+    ]
+}
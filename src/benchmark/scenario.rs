@@ -0,0 +1,184 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::pressure::Watermarks;
+use crate::actor::priority::ActorPriority;
+use crate::actor::slo::SloConfig;
+use crate::benchmark::counting_allocator::CountingAllocator;
+use crate::benchmark::matrix_config::{Feature, MatrixConfig};
+use crate::config::global_config::HistoryConfig;
+use crate::config::tyractorsaur_config::TyractorsaurConfig;
+use crate::message::actor_message::ActorMessage;
+use crate::message::headers::{HeaderValue, Headers, Propagate};
+use crate::message::provenance::Audited;
+use crate::system::actor_system::ActorSystem;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+/// Upper bound of a single run, so that a broken cell does not block the whole matrix
+const RUN_TIMEOUT: Duration = Duration::from_secs(300);
+
+struct Ping {
+    remaining: usize,
+    payload: Vec<u8>,
+}
+
+impl ActorMessage for Ping {}
+impl Audited for Ping {}
+
+struct Peer(ActorWrapper<Player>);
+
+impl ActorMessage for Peer {}
+
+struct Player {
+    peer: Option<ActorWrapper<Player>>,
+    done: Sender<()>,
+    context: ActorContext<Self>,
+}
+
+impl Actor for Player {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl Handler<Peer> for Player {
+    fn handle(&mut self, msg: Peer, _context: &ActorContext<Self>) {
+        self.peer = Some(msg.0);
+    }
+}
+
+impl Handler<Ping> for Player {
+    fn handle(&mut self, msg: Ping, _context: &ActorContext<Self>) {
+        if msg.remaining == 0 {
+            let _ = self.done.send(());
+            return;
+        }
+        if let Some(peer) = &self.peer {
+            let _ = peer.send(Ping {
+                remaining: msg.remaining - 1,
+                payload: msg.payload,
+            });
+        }
+    }
+}
+
+struct PlayerFactory {
+    done: Sender<()>,
+}
+
+impl ActorFactory<Player> for PlayerFactory {
+    fn new_actor(&self, context: ActorContext<Player>) -> Player {
+        Player {
+            peer: None,
+            done: self.done.clone(),
+            context,
+        }
+    }
+}
+
+/// Measurement of a single cell, `(nanoseconds, allocations)` per handled message
+pub(crate) fn measure(features: &[Feature], config: &MatrixConfig) -> (f64, Option<f64>) {
+    let has = |feature: Feature| features.contains(&feature);
+    let mut system_config = TyractorsaurConfig::new().unwrap();
+    if has(Feature::MetricsHistory) {
+        let history = HistoryConfig::new(Duration::from_millis(10), Duration::from_secs(1));
+        system_config.general.metrics_history = Some(history);
+    }
+    if has(Feature::PayloadCapture) {
+        system_config.general.max_captured_payload_bytes = config.payload_bytes.max(1);
+    }
+    #[cfg(feature = "replay")]
+    let recording =
+        std::env::temp_dir().join(format!("tyractorsaur-bench-{}.log", std::process::id()));
+    #[cfg(feature = "replay")]
+    if has(Feature::ExecutionRecording) {
+        system_config.general.record_execution = Some(recording.to_string_lossy().into_owned());
+    }
+
+    let actor_system = ActorSystem::new(system_config);
+    if has(Feature::PayloadCapture) {
+        actor_system.register_payload_serializer(|ping: &Ping| ping.payload.clone());
+    }
+    if has(Feature::Provenance) {
+        actor_system.register_audited::<Ping>();
+    }
+    let headers = if has(Feature::Headers) {
+        let key = actor_system.register_header("bench.tenant", Propagate::Always);
+        Headers::new().with(key, HeaderValue::Str("bench".into()))
+    } else {
+        Headers::new()
+    };
+
+    let (done, finished) = unbounded();
+    let configure = |builder: ActorBuilder<Player>| {
+        let mut builder = builder;
+        if has(Feature::Priority) {
+            builder = builder.set_priority(ActorPriority::High);
+        }
+        if has(Feature::LatencySlo) {
+            builder = builder.set_latency_slo(SloConfig::new(Duration::from_millis(1), 0.99));
+        }
+        if has(Feature::Watermarks) {
+            builder = builder.set_watermarks(Watermarks::new(1000, 100));
+        }
+        if has(Feature::PayloadCapture) {
+            builder = builder.set_capture_last_n_messages(16);
+        }
+        builder
+    };
+    let first = configure(actor_system.builder())
+        .spawn("bench-first", PlayerFactory { done: done.clone() })
+        .unwrap();
+    let second = configure(actor_system.builder())
+        .spawn("bench-second", PlayerFactory { done })
+        .unwrap();
+    let _ = first.send(Peer(second.clone()));
+    let _ = second.send(Peer(first.clone()));
+
+    let run = |messages: usize| -> Option<(Duration, usize)> {
+        let allocations = CountingAllocator::allocations();
+        let started = Instant::now();
+        let ping = Ping {
+            remaining: messages - 1,
+            payload: vec![0; config.payload_bytes],
+        };
+        first.send_with_headers(ping, headers.clone());
+        wait(&finished)?;
+        Some((
+            started.elapsed(),
+            CountingAllocator::allocations() - allocations,
+        ))
+    };
+    let mut results = Vec::new();
+    if config.warmup == 0 || run(config.warmup).is_some() {
+        for _ in 0..config.runs {
+            match run(config.messages) {
+                Some(result) => results.push(result),
+                None => break,
+            }
+        }
+    }
+
+    actor_system.stop(Duration::from_secs(10));
+    actor_system.await_shutdown();
+    #[cfg(feature = "replay")]
+    let _ = std::fs::remove_file(&recording);
+
+    if results.is_empty() {
+        return (f64::NAN, None);
+    }
+    results.sort_by_key(|(elapsed, _)| *elapsed);
+    let (elapsed, allocations) = results[results.len() / 2];
+    let messages = config.messages as f64;
+    let allocations =
+        Some(allocations as f64 / messages).filter(|_| CountingAllocator::is_installed());
+    (elapsed.as_nanos() as f64 / messages, allocations)
+}
+
+fn wait(finished: &Receiver<()>) -> Option<()> {
+    finished.recv_timeout(RUN_TIMEOUT).ok()
+}
@@ -0,0 +1,48 @@
+use crate::config::tyractorsaur_config::DEFAULT_POOL;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Expected load of a group of Actors of the same type, see [CapacityPlan.actor_group](./struct.CapacityPlan.html#method.actor_group)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActorLoadSpec {
+    pub count: usize,
+    /// messages per second of a single Actor
+    pub msgs_per_sec: f64,
+    pub avg_handler_cost: Duration,
+    /// payload size of a single message, `0` if unknown
+    pub avg_msg_bytes: usize,
+    pub pool: String,
+}
+
+impl ActorLoadSpec {
+    /// Spawns the Actors into the `default` pool without a known message size
+    pub fn new(count: usize, msgs_per_sec: f64, avg_handler_cost: Duration) -> Self {
+        Self {
+            count,
+            msgs_per_sec,
+            avg_handler_cost,
+            avg_msg_bytes: 0,
+            pool: String::from(DEFAULT_POOL),
+        }
+    }
+
+    pub fn with_pool(mut self, pool: &str) -> Self {
+        self.pool = String::from(pool);
+        self
+    }
+
+    pub fn with_msg_bytes(mut self, avg_msg_bytes: usize) -> Self {
+        self.avg_msg_bytes = avg_msg_bytes;
+        self
+    }
+
+    /// Fraction of time a single Actor spends in its handlers, Actors handle their messages sequentially so they fall behind above `1.0`
+    pub fn actor_utilization(&self) -> f64 {
+        self.msgs_per_sec * self.avg_handler_cost.as_secs_f64()
+    }
+
+    /// Threads the whole group keeps busy
+    pub fn thread_demand(&self) -> f64 {
+        self.count as f64 * self.actor_utilization()
+    }
+}
@@ -0,0 +1,379 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::capacity::actor_load_spec::ActorLoadSpec;
+use crate::capacity::plan_report::{GroupPlan, PlanFinding, PlanFindingKind, PlanReport, PoolPlan};
+use crate::config::tyractorsaur_config::{TyractorsaurConfig, DEFAULT_POOL};
+use crate::system::actor_system::ActorSystem;
+use std::collections::{BTreeMap, HashMap};
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Estimates whether a set of actor groups fits into the thread pools of a [TyractorsaurConfig](../prelude/struct.TyractorsaurConfig.html) before it is deployed
+///
+/// - pool utilization is the sum of `count * msgs_per_sec * avg_handler_cost` of all groups, divided by the threads of the pool
+/// - queues of overloaded pools or Actors grow by the messages that exceed their capacity
+/// - steady-state queues are estimated as `u / (1 - u)` messages per Actor, `u` being the higher of the Actor and the pool utilization
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::{ThreadPoolConfig, TyractorsaurConfig};
+/// use tyractorsaur::planning::{ActorLoadSpec, CapacityPlan, PlanFindingKind};
+/// use std::time::Duration;
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.thread_pool.config.insert(String::from("io"), ThreadPoolConfig::new(0, 2, 2, 1.0));
+/// config.thread_pool.config.insert(String::from("compute"), ThreadPoolConfig::new(20, 8, 8, 1.0));
+///
+/// // 10 workers with 100 msg/s of 1ms keep a single thread busy
+/// let relaxed = CapacityPlan::new(config.clone())
+///     .with_cpus(4)
+///     .actor_group("workers", ActorLoadSpec::new(10, 100.0, Duration::from_millis(1)).with_pool("compute").with_msg_bytes(64))
+///     .actor_group("writers", ActorLoadSpec::new(2, 50.0, Duration::from_millis(2)).with_pool("io"))
+///     .analyze();
+/// assert!(relaxed.is_ok(), "{}", relaxed);
+/// assert!((relaxed.get_pool("compute").unwrap().utilization - 0.125).abs() < 1e-9);
+/// assert!(relaxed.get_group("workers").unwrap().mailbox_bytes.is_some());
+///
+/// // 10k msg/s of 5ms handlers need 50 threads
+/// let impossible = CapacityPlan::new(config)
+///     .with_cpus(4)
+///     .actor_group("workers", ActorLoadSpec::new(10, 100.0, Duration::from_millis(1)).with_pool("compute"))
+///     .actor_group("writers", ActorLoadSpec::new(25, 400.0, Duration::from_millis(5)).with_pool("io"))
+///     .actor_group("shards", ActorLoadSpec::new(30, 1.0, Duration::from_millis(1)).with_pool("compute"))
+///     .analyze();
+/// let overloaded = impossible.findings_of(PlanFindingKind::PoolOverloaded);
+/// assert_eq!(1, overloaded.len());
+/// assert_eq!("thread_pool.config.io", overloaded[0].path);
+/// let io = impossible.get_pool("io").unwrap();
+/// assert!((io.utilization - 25.0).abs() < 1e-9);
+/// assert!((io.queue_growth_per_sec - 9_600.0).abs() < 1e-6);
+/// assert_eq!(None, impossible.get_group("writers").unwrap().expected_queue_depth);
+///
+/// let limits = impossible.findings_of(PlanFindingKind::ActorLimitExceeded);
+/// assert_eq!(vec!["thread_pool.config.compute.actor_limit"], limits.iter().map(|finding| finding.path.as_str()).collect::<Vec<&str>>());
+/// println!("{}", impossible);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CapacityPlan {
+    config: TyractorsaurConfig,
+    groups: Vec<(String, ActorLoadSpec)>,
+    utilization_threshold: f64,
+    cpus: usize,
+}
+
+impl CapacityPlan {
+    /// Plans for the cores of the current machine with a utilization threshold of `0.8`
+    pub fn new(config: TyractorsaurConfig) -> Self {
+        Self {
+            config,
+            groups: Vec::new(),
+            utilization_threshold: 0.8,
+            cpus: num_cpus::get(),
+        }
+    }
+
+    /// Measures the load of all Actors of a running system for `window` and plans it against the config of the system, blocks the caller for `window`
+    ///
+    /// Every pool becomes a single group named after the pool, message sizes are unknown and left at `0`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::planning::CapacityPlan;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Tick {}
+    /// impl ActorMessage for Tick {}
+    ///
+    /// struct Busy {}
+    /// impl Actor for Busy {}
+    /// impl Handler<Tick> for Busy {
+    ///     fn handle(&mut self, _msg: Tick, _context: &ActorContext<Self>) {
+    ///         let started = Instant::now();
+    ///         while started.elapsed() < Duration::from_millis(2) {}
+    ///     }
+    /// }
+    /// struct BusyFactory {}
+    /// impl ActorFactory<Busy> for BusyFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Busy>) -> Busy {
+    ///         Busy {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("load", ThreadPoolConfig::new(0, 2, 2, 1.0));
+    /// let busy = actor_system.builder().set_pool_name("load").unwrap().spawn("busy", BusyFactory {}).unwrap();
+    ///
+    /// // synthetic load of 50 msg/s
+    /// let generator = std::thread::spawn(move || {
+    ///     for _ in 0..150 {
+    ///         busy.send(Tick {});
+    ///         sleep(Duration::from_millis(20));
+    ///     }
+    /// });
+    /// // the threads of a new pool are started within a second
+    /// sleep(Duration::from_millis(1500));
+    /// let plan = CapacityPlan::from_observed(&actor_system, Duration::from_secs(1));
+    /// generator.join().unwrap();
+    ///
+    /// let observed = plan.get_group("load").unwrap();
+    /// assert_eq!(1, observed.count);
+    /// assert!(observed.msgs_per_sec > 30.0 && observed.msgs_per_sec < 60.0, "{}", observed.msgs_per_sec);
+    /// assert!(observed.avg_handler_cost >= Duration::from_millis(2));
+    /// let report = plan.analyze();
+    /// assert_eq!(2, report.get_pool("load").unwrap().threads);
+    /// assert!(report.is_ok(), "{}", report);
+    /// ```
+    pub fn from_observed(actor_system: &ActorSystem, window: Duration) -> Self {
+        Self::from_observed_grouped(actor_system, window, |address| address.pool.clone())
+    }
+
+    /// Same as [CapacityPlan::from_observed](#method.from_observed), but groups Actors by the name returned by `group`
+    ///
+    /// All Actors of a group are expected to share a pool, the group uses the pool of its first Actor
+    pub fn from_observed_grouped<F>(actor_system: &ActorSystem, window: Duration, group: F) -> Self
+    where
+        F: Fn(&ActorAddress) -> String,
+    {
+        let state = actor_system.get_state();
+        let before: HashMap<ActorAddress, (usize, Duration)> = state
+            .get_actor_metrics()
+            .into_iter()
+            .map(|(address, metrics)| (address, (metrics.get_processed(), metrics.get_handler_time())))
+            .collect();
+        sleep(window);
+
+        // pool, count, processed, handler time
+        let mut observed: BTreeMap<String, (String, usize, usize, Duration)> = BTreeMap::new();
+        for (address, metrics) in state.get_actor_metrics() {
+            let (processed, handler_time) = match before.get(&address) {
+                Some(before) => *before,
+                None => continue,
+            };
+            let entry = observed
+                .entry(group(&address))
+                .or_insert_with(|| (address.pool.clone(), 0, 0, Duration::from_secs(0)));
+            entry.1 += 1;
+            entry.2 += metrics.get_processed().saturating_sub(processed);
+            entry.3 += metrics.get_handler_time().saturating_sub(handler_time);
+        }
+
+        let mut config = actor_system.get_config().clone();
+        for (name, pool_config) in actor_system.get_thread_pool_manager().get_pool_configs() {
+            config.thread_pool.config.insert(name, pool_config);
+        }
+        let mut plan = Self::new(config);
+        for (name, (pool, count, processed, handler_time)) in observed {
+            let avg_handler_cost = if processed == 0 {
+                Duration::from_secs(0)
+            } else {
+                Duration::from_secs_f64(handler_time.as_secs_f64() / processed as f64)
+            };
+            let msgs_per_sec = processed as f64 / window.as_secs_f64() / count as f64;
+            let spec = ActorLoadSpec::new(count, msgs_per_sec, avg_handler_cost).with_pool(&pool);
+            plan = plan.actor_group(&name, spec);
+        }
+        plan
+    }
+
+    /// Adds a group of Actors, replaces an existing group with the same name
+    pub fn actor_group(mut self, name: &str, spec: ActorLoadSpec) -> Self {
+        match self.groups.iter_mut().find(|(group, _)| group == name) {
+            Some((_, existing)) => *existing = spec,
+            None => self.groups.push((String::from(name), spec)),
+        }
+        self
+    }
+
+    pub fn get_group(&self, name: &str) -> Option<&ActorLoadSpec> {
+        self.groups.iter().find(|(group, _)| group == name).map(|(_, spec)| spec)
+    }
+
+    /// Pools at or above this utilization are reported as [HighUtilization](./enum.PlanFindingKind.html#variant.HighUtilization)
+    pub fn with_utilization_threshold(mut self, utilization_threshold: f64) -> Self {
+        self.utilization_threshold = utilization_threshold;
+        self
+    }
+
+    /// Plans for a machine with `cpus` cores instead of the current one
+    pub fn with_cpus(mut self, cpus: usize) -> Self {
+        self.cpus = cpus;
+        self
+    }
+
+    pub fn analyze(&self) -> PlanReport {
+        let mut findings = Vec::new();
+        let mut pools: BTreeMap<&str, PoolPlan> = BTreeMap::new();
+        let mut demands: HashMap<&str, f64> = HashMap::new();
+        for (_, spec) in &self.groups {
+            let pool = pools.entry(spec.pool.as_str()).or_insert_with(|| PoolPlan {
+                name: spec.pool.clone(),
+                threads: 0,
+                actors: 0,
+                msgs_per_sec: 0.0,
+                utilization: 0.0,
+                queue_growth_per_sec: 0.0,
+                mailbox_bytes: Some(0),
+            });
+            pool.actors += spec.count;
+            pool.msgs_per_sec += spec.count as f64 * spec.msgs_per_sec;
+            *demands.entry(spec.pool.as_str()).or_insert(0.0) += spec.thread_demand();
+        }
+
+        let mut mailbox_sizes = HashMap::new();
+        for (name, pool) in pools.iter_mut() {
+            let path = format!("thread_pool.config.{}", name);
+            let config = match self.config.thread_pool.config.get(*name) {
+                Some(config) => config,
+                None => {
+                    findings.push(PlanFinding::new(
+                        PlanFindingKind::UnknownPool,
+                        &path,
+                        String::from("pool is not configured, the default pool config is used"),
+                    ));
+                    self.config.thread_pool.config.get(DEFAULT_POOL).unwrap()
+                }
+            };
+            let mailbox_size = config
+                .actor_defaults
+                .and_then(|actor_defaults| actor_defaults.mailbox_size)
+                .unwrap_or(self.config.general.default_mailbox_size);
+            mailbox_sizes.insert(*name, mailbox_size);
+
+            let demand = demands[name];
+            pool.threads = config.thread_count(self.cpus);
+            pool.utilization = if pool.threads == 0 {
+                if demand > 0.0 {
+                    f64::INFINITY
+                } else {
+                    0.0
+                }
+            } else {
+                demand / pool.threads as f64
+            };
+            if pool.utilization > 1.0 {
+                pool.queue_growth_per_sec = pool.msgs_per_sec * (1.0 - 1.0 / pool.utilization);
+            }
+
+            if config.actor_limit > 0 && pool.actors > config.actor_limit {
+                findings.push(PlanFinding::new(
+                    PlanFindingKind::ActorLimitExceeded,
+                    &format!("{}.actor_limit", path),
+                    format!("{} Actors planned, the pool is limited to {}", pool.actors, config.actor_limit),
+                ));
+            }
+            if pool.utilization >= 1.0 {
+                findings.push(PlanFinding::new(
+                    PlanFindingKind::PoolOverloaded,
+                    &path,
+                    format!(
+                        "{:.2} busy threads needed, the pool has {}, queues grow by {:.1} msg/s",
+                        demand, pool.threads, pool.queue_growth_per_sec
+                    ),
+                ));
+            } else if pool.utilization >= self.utilization_threshold {
+                findings.push(PlanFinding::new(
+                    PlanFindingKind::HighUtilization,
+                    &path,
+                    format!(
+                        "utilization of {:.1}% reaches the threshold of {:.1}%",
+                        pool.utilization * 100.0,
+                        self.utilization_threshold * 100.0
+                    ),
+                ));
+            }
+        }
+
+        let mut groups = Vec::with_capacity(self.groups.len());
+        for (name, spec) in &self.groups {
+            let path = format!("groups.{}", name);
+            let pool = &pools[spec.pool.as_str()];
+            let mailbox_size = mailbox_sizes[spec.pool.as_str()];
+            let actor_utilization = spec.actor_utilization();
+            let group_rate = spec.count as f64 * spec.msgs_per_sec;
+
+            let share = if pool.msgs_per_sec > 0.0 {
+                group_rate / pool.msgs_per_sec
+            } else {
+                0.0
+            };
+            let actor_growth = if actor_utilization > 1.0 {
+                group_rate * (1.0 - 1.0 / actor_utilization)
+            } else {
+                0.0
+            };
+            let queue_growth_per_sec = actor_growth.max(pool.queue_growth_per_sec * share);
+
+            let utilization = actor_utilization.max(pool.utilization);
+            let expected_queue_depth = if utilization < 1.0 {
+                Some(utilization / (1.0 - utilization))
+            } else {
+                None
+            };
+            let queued = match expected_queue_depth {
+                Some(depth) if mailbox_size > 0 => Some(depth.min(mailbox_size as f64)),
+                Some(depth) => Some(depth),
+                None if mailbox_size > 0 => Some(mailbox_size as f64),
+                None => None,
+            };
+            let mailbox_bytes =
+                queued.map(|queued| (queued * spec.count as f64 * spec.avg_msg_bytes as f64).round() as u64);
+
+            if actor_utilization >= 1.0 {
+                findings.push(PlanFinding::new(
+                    PlanFindingKind::ActorOverloaded,
+                    &path,
+                    format!(
+                        "a single Actor is busy {:.1}% of the time, the queues of the group grow by {:.1} msg/s",
+                        actor_utilization * 100.0,
+                        actor_growth
+                    ),
+                ));
+            }
+            let overflows = match expected_queue_depth {
+                Some(depth) => depth > mailbox_size as f64,
+                None => true,
+            };
+            if mailbox_size > 0 && overflows {
+                findings.push(PlanFinding::new(
+                    PlanFindingKind::MailboxOverflow,
+                    &path,
+                    format!("expected queue exceeds the mailbox size of {}", mailbox_size),
+                ));
+            }
+
+            groups.push(GroupPlan {
+                name: name.clone(),
+                pool: spec.pool.clone(),
+                count: spec.count,
+                actor_utilization,
+                expected_queue_depth,
+                queue_growth_per_sec,
+                mailbox_size,
+                mailbox_bytes,
+            });
+        }
+
+        for group in &groups {
+            let pool = pools.get_mut(group.pool.as_str()).unwrap();
+            pool.mailbox_bytes = match (pool.mailbox_bytes, group.mailbox_bytes) {
+                (Some(pool), Some(group)) => Some(pool + group),
+                _ => None,
+            };
+        }
+
+        PlanReport {
+            cpus: self.cpus,
+            utilization_threshold: self.utilization_threshold,
+            pools: pools.into_values().collect(),
+            groups,
+            findings,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod actor_load_spec;
+mod capacity_plan;
+mod plan_report;
+
+pub mod prelude {
+    pub use crate::capacity::actor_load_spec::ActorLoadSpec;
+    pub use crate::capacity::capacity_plan::CapacityPlan;
+    pub use crate::capacity::plan_report::{GroupPlan, PlanFinding, PlanFindingKind, PlanReport, PoolPlan};
+}
@@ -0,0 +1,149 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum PlanFindingKind {
+    /// the pool is not configured, [ActorSystem.add_pool](../prelude/struct.ActorSystem.html#method.add_pool) falls back to the `default` pool config
+    UnknownPool,
+    /// the pool utilization reached the threshold of the plan
+    HighUtilization,
+    /// the pool needs more threads than it has, its queues grow without bounds
+    PoolOverloaded,
+    /// a single Actor receives more messages than it can handle sequentially
+    ActorOverloaded,
+    /// the pool `actor_limit` is lower than the amount of planned Actors
+    ActorLimitExceeded,
+    /// the expected queue does not fit into the mailbox, senders block once it is full
+    MailboxOverflow,
+}
+
+/// Single finding of a [PlanReport](./struct.PlanReport.html), `path` refers to the config or the actor group causing it
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PlanFinding {
+    pub kind: PlanFindingKind,
+    pub path: String,
+    pub message: String,
+}
+
+impl PlanFinding {
+    pub(crate) fn new(kind: PlanFindingKind, path: &str, message: String) -> Self {
+        Self {
+            kind,
+            path: String::from(path),
+            message,
+        }
+    }
+}
+
+/// Estimate for a single pool
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PoolPlan {
+    pub name: String,
+    pub threads: usize,
+    pub actors: usize,
+    pub msgs_per_sec: f64,
+    /// threads kept busy divided by `threads`
+    pub utilization: f64,
+    /// messages per second by which the queues of the pool grow, `0.0` for pools within capacity
+    pub queue_growth_per_sec: f64,
+    /// `None` if at least one queue of the pool grows without bounds
+    pub mailbox_bytes: Option<u64>,
+}
+
+/// Estimate for a single actor group
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct GroupPlan {
+    pub name: String,
+    pub pool: String,
+    pub count: usize,
+    pub actor_utilization: f64,
+    /// messages queued per Actor in steady state, `None` if the queues keep growing
+    pub expected_queue_depth: Option<f64>,
+    /// messages per second by which the queues of the whole group grow
+    pub queue_growth_per_sec: f64,
+    /// `0` is treated as unlimited
+    pub mailbox_size: usize,
+    /// payload bytes queued in the mailboxes of the whole group, full mailboxes for growing queues with limited mailboxes, `None` for growing queues with unlimited mailboxes
+    pub mailbox_bytes: Option<u64>,
+}
+
+/// Result of [CapacityPlan.analyze](./struct.CapacityPlan.html#method.analyze)
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PlanReport {
+    pub cpus: usize,
+    pub utilization_threshold: f64,
+    /// sorted by name
+    pub pools: Vec<PoolPlan>,
+    /// in the order of declaration
+    pub groups: Vec<GroupPlan>,
+    pub findings: Vec<PlanFinding>,
+}
+
+impl PlanReport {
+    /// `true` if the plan has no findings
+    pub fn is_ok(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn get_pool(&self, name: &str) -> Option<&PoolPlan> {
+        self.pools.iter().find(|pool| pool.name == name)
+    }
+
+    pub fn get_group(&self, name: &str) -> Option<&GroupPlan> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// Findings of the given kind
+    pub fn findings_of(&self, kind: PlanFindingKind) -> Vec<&PlanFinding> {
+        self.findings.iter().filter(|finding| finding.kind == kind).collect()
+    }
+}
+
+fn bytes(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(bytes) => format!("{} B", bytes),
+        None => String::from("unbounded"),
+    }
+}
+
+impl fmt::Display for PlanReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "capacity plan for {} cpus", self.cpus)?;
+        for pool in &self.pools {
+            writeln!(
+                f,
+                "pool {}: {} threads, {} actors, {:.1} msg/s, utilization {:.1}%, queue growth {:.1} msg/s, mailboxes {}",
+                pool.name,
+                pool.threads,
+                pool.actors,
+                pool.msgs_per_sec,
+                pool.utilization * 100.0,
+                pool.queue_growth_per_sec,
+                bytes(pool.mailbox_bytes)
+            )?;
+            for group in self.groups.iter().filter(|group| group.pool == pool.name) {
+                let depth = match group.expected_queue_depth {
+                    Some(depth) => format!("{:.2}", depth),
+                    None => String::from("growing"),
+                };
+                writeln!(
+                    f,
+                    "  group {}: {} actors, actor utilization {:.1}%, queue depth {}, mailboxes {}",
+                    group.name,
+                    group.count,
+                    group.actor_utilization * 100.0,
+                    depth,
+                    bytes(group.mailbox_bytes)
+                )?;
+            }
+        }
+        if self.findings.is_empty() {
+            return writeln!(f, "no findings");
+        }
+        writeln!(f, "findings:")?;
+        for finding in &self.findings {
+            writeln!(f, "  {:?} {}: {}", finding.kind, finding.path, finding.message)?;
+        }
+        Ok(())
+    }
+}
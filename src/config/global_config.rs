@@ -1,5 +1,11 @@
-use crate::actor::actor_config::RestartPolicy;
+use crate::actor::actor_config::{PanicEscalation, RestartPolicy};
+use crate::actor::priority::ActorPriority;
+use crate::system::cycle_detection::CycleConfig;
+use crate::system::memory_pressure::MemoryPressureConfig;
+use crate::system::message_budget::MessageBudgetConfig;
+use crate::system::name_guard::GuardConfig;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GeneralConfig {
@@ -7,4 +13,98 @@ pub struct GeneralConfig {
     pub default_mailbox_size: usize,
     pub default_message_throughput: usize,
     pub default_restart_policy: RestartPolicy,
+    /// consequence of a panic beyond the restart policy, see [PanicEscalation](../prelude/enum.PanicEscalation.html)
+    pub default_panic_escalation: PanicEscalation,
+    pub default_max_suspension: Duration,
+    pub default_priority: ActorPriority,
+    /// maximum amount of Actors a request chain boosts, see [ActorBuilder.set_priority](../prelude/struct.ActorBuilder.html#method.set_priority)
+    ///
+    /// `0` disables priority inheritance
+    pub max_priority_inheritance_depth: u64,
+    /// maximum size of a payload attached to an [ActorPanicReport](../prelude/struct.ActorPanicReport.html)
+    ///
+    /// `0` disables payload capture
+    pub max_captured_payload_bytes: usize,
+    /// maximum amount of hops recorded per [Provenance](../prelude/struct.Provenance.html) chain, further hops are only counted
+    pub max_provenance_hops: usize,
+    /// maximum amount of request chains a single Actor runs at the same time, see [ActorContext.request_chain](../prelude/struct.ActorContext.html#method.request_chain)
+    pub max_request_chains: usize,
+    /// maximum amount of behaviors a single Actor stacks, see [ActorContext.become_behavior](../prelude/struct.ActorContext.html#method.become_behavior)
+    pub max_behavior_depth: usize,
+    /// capacity of the default [MemoryEffectLedger](../prelude/struct.MemoryEffectLedger.html) of every Actor
+    pub effect_ledger_capacity: usize,
+    /// time the default [MemoryEffectLedger](../prelude/struct.MemoryEffectLedger.html) keeps an outcome, effects are executed again afterwards
+    pub effect_ledger_ttl: Duration,
+    pub metrics_history: Option<HistoryConfig>,
+    /// reporting interval of [ActorSystem.pool_worker_stats](../prelude/struct.ActorSystem.html#method.pool_worker_stats)
+    pub worker_stats_interval: Duration,
+    /// sheds load of sheddable Actors under memory pressure, see [ActorBuilder.set_sheddable](../prelude/struct.ActorBuilder.html#method.set_sheddable)
+    pub memory_pressure: Option<MemoryPressureConfig>,
+    /// records every scheduling decision to the given file, requires the `replay` feature
+    ///
+    /// See [ActorSystem.replay_execution](../prelude/struct.ActorSystem.html#method.replay_execution)
+    pub record_execution: Option<String>,
+    /// seeds the random number generators of all Actors, see [ActorContext.rng](../prelude/struct.ActorContext.html#method.rng)
+    ///
+    /// Every Actor is seeded from OS entropy if not set
+    pub rng_seed: Option<u64>,
+    /// fails [ActorSystem.try_new](../prelude/struct.ActorSystem.html#method.try_new) if another process already runs a system with the same name
+    pub unique_name_guard: Option<GuardConfig>,
+    /// drops sends of causal chains that exceed `max_hops`, see [CycleConfig](../prelude/struct.CycleConfig.html)
+    pub cycle_detection: Option<CycleConfig>,
+    /// rejects sends whose approximate size exceeds the limit, see [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size)
+    ///
+    /// Also limits the payload of [SerializedMessage](../prelude/struct.SerializedMessage.html)s, Actors override it through [ActorBuilder.set_max_message_bytes](../prelude/struct.ActorBuilder.html#method.set_max_message_bytes)
+    pub max_message_bytes: Option<usize>,
+    /// reports sends whose approximate size exceeds the threshold as [OversizedMessage](../prelude/struct.OversizedMessage.html) without rejecting them
+    pub warn_message_bytes: Option<usize>,
+    /// sheds sends once the mailboxes of all Actors together hold too many messages, see [MessageBudgetConfig](../prelude/struct.MessageBudgetConfig.html)
+    pub message_budget: Option<MessageBudgetConfig>,
+    /// interval of the integrity sweep of the internal registries, see [ActorSystem.sweep_integrity](../prelude/struct.ActorSystem.html#method.sweep_integrity)
+    ///
+    /// A zero interval disables the periodic sweep
+    pub integrity_sweep_interval: Duration,
+    /// prints every message sent to a stopped Actor, see [ActorSystem.subscribe_dead_letters](../prelude/struct.ActorSystem.html#method.subscribe_dead_letters)
+    pub log_dead_letters: bool,
+    /// prints the first and then every n-th drop of each reason and Actor, `0` only counts them, see [ActorSystem.drop_stats](../prelude/struct.ActorSystem.html#method.drop_stats)
+    pub log_drops_every: u64,
+    /// labels the Actor series of [ActorSystem.render_prometheus](../prelude/struct.ActorSystem.html#method.render_prometheus) per Actor, `false` sums them up per pool
+    pub metrics_per_actor: bool,
+}
+
+/// Enables short-term metrics history for all Actors and thread pools
+///
+/// Each Actor and each pool keeps a preallocated ring buffer with `retention / resolution` samples
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::{TyractorsaurConfig, HistoryConfig};
+/// use std::time::Duration;
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.metrics_history = Some(HistoryConfig::new(Duration::from_secs(1), Duration::from_secs(60)));
+/// assert_eq!(60, config.general.metrics_history.unwrap().capacity());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct HistoryConfig {
+    pub resolution: Duration,
+    pub retention: Duration,
+}
+
+impl HistoryConfig {
+    pub fn new(resolution: Duration, retention: Duration) -> Self {
+        Self {
+            resolution,
+            retention,
+        }
+    }
+
+    /// Amount of samples that are kept per Actor and per pool
+    pub fn capacity(&self) -> usize {
+        let resolution = self.resolution.as_nanos().max(1);
+        ((self.retention.as_nanos() / resolution) as usize).max(1)
+    }
 }
@@ -3,6 +3,7 @@ pub mod pool_config;
 pub mod tyractorsaur_config;
 
 pub mod prelude {
-    pub use crate::config::pool_config::ThreadPoolConfig;
-    pub use crate::config::tyractorsaur_config::TyractorsaurConfig;
+    pub use crate::config::global_config::HistoryConfig;
+    pub use crate::config::pool_config::{IdleStrategy, ThreadPoolConfig};
+    pub use crate::config::tyractorsaur_config::{ConfigLoadError, ConfigWarning, TyractorsaurConfig};
 }
@@ -1,6 +1,19 @@
+use crate::actor::actor_config::ActorDefaults;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// What an idle worker of a pool does before it waits for the next runnable Actor, see [ThreadPoolConfig.with_idle_strategy](../prelude/struct.ThreadPoolConfig.html#method.with_idle_strategy)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IdleStrategy {
+    /// waits right away without using any CPU
+    #[default]
+    ParkImmediately,
+    /// polls the queue for `spin_us` microseconds before it waits, for pools that need to pick up Actors with the lowest latency
+    SpinThenPark { spin_us: u64 },
+    /// never waits, the worker polls the queue and yields its thread in between
+    Yield,
+}
+
 /// See [default.toml](https://github.com/sers-dev/tyractorsaur/blob/master/src/config/default.toml) for documentation of all configurations & their defaults
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ThreadPoolConfig {
@@ -8,6 +21,20 @@ pub struct ThreadPoolConfig {
     pub threads_min: usize,
     pub threads_max: usize,
     pub threads_factor: f32,
+    /// overrides the general Actor defaults for all Actors spawned into this pool
+    #[serde(default)]
+    pub actor_defaults: Option<ActorDefaults>,
+    /// prefers the previous worker thread of an Actor when it is scheduled again, see [ActorSystem.pool_worker_stats](../prelude/struct.ActorSystem.html#method.pool_worker_stats)
+    #[serde(default)]
+    pub soft_affinity: bool,
+    /// pools whose runnable Actors are executed by the idle workers of this pool, see [ThreadPoolConfig.with_steal_from](../prelude/struct.ThreadPoolConfig.html#method.with_steal_from)
+    #[serde(default)]
+    pub steal_from: Vec<String>,
+    /// overrides `general.default_message_throughput` for all Actors spawned into this pool, `actor_defaults.message_throughput` takes precedence
+    #[serde(default)]
+    pub default_message_throughput: Option<usize>,
+    #[serde(default)]
+    pub idle_strategy: IdleStrategy,
 }
 
 impl ThreadPoolConfig {
@@ -28,8 +55,267 @@ impl ThreadPoolConfig {
             threads_min,
             threads_max,
             threads_factor,
+            actor_defaults: None,
+            soft_affinity: false,
+            steal_from: Vec::new(),
+            default_message_throughput: None,
+            idle_strategy: IdleStrategy::default(),
+        }
+    }
+
+    /// Actors spawned into the pool use these defaults unless they are overridden on the [ActorBuilder](../prelude/struct.ActorBuilder.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{ActorDefaults, RestartPolicy, ThreadPoolConfig};
+    ///
+    /// let defaults = ActorDefaults {
+    ///     mailbox_size: Some(16),
+    ///     restart_policy: Some(RestartPolicy::Never),
+    ///     ..ActorDefaults::default()
+    /// };
+    /// let config = ThreadPoolConfig::new(0, 1, 2, 1.0).with_actor_defaults(defaults);
+    /// ```
+    pub fn with_actor_defaults(mut self, actor_defaults: ActorDefaults) -> Self {
+        self.actor_defaults = Some(actor_defaults);
+        self
+    }
+
+    /// Actor defaults of the pool, including the `default_message_throughput`
+    pub fn get_actor_defaults(&self) -> Option<ActorDefaults> {
+        let message_throughput = match self.default_message_throughput {
+            Some(message_throughput) => message_throughput,
+            None => return self.actor_defaults,
+        };
+        let actor_defaults = self.actor_defaults.unwrap_or_default();
+        Some(ActorDefaults {
+            message_throughput: actor_defaults.message_throughput.or(Some(message_throughput)),
+            ..actor_defaults
+        })
+    }
+
+    /// Maximum amount of consecutive messages an Actor of this pool handles before the worker switches to the next Actor
+    ///
+    /// Overrides `general.default_message_throughput`, an Actor still overrides it through [ActorBuilder.set_message_throughput](../prelude/struct.ActorBuilder.html#method.set_message_throughput)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{bounded, Receiver, Sender};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Tick {}
+    /// impl ActorMessage for Tick {}
+    ///
+    /// struct Ticker { name: &'static str, log: Arc<Mutex<Vec<&'static str>>> }
+    /// impl Actor for Ticker {}
+    /// impl Handler<Tick> for Ticker {
+    ///     fn handle(&mut self, _msg: Tick, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push(self.name);
+    ///     }
+    /// }
+    ///
+    /// // occupies the single worker until it is opened
+    /// struct Hold { entered: Sender<()>, open: Receiver<()> }
+    /// impl ActorMessage for Hold {}
+    /// struct Gate {}
+    /// impl Actor for Gate {}
+    /// impl Handler<Hold> for Gate {
+    ///     fn handle(&mut self, msg: Hold, _context: &ActorContext<Self>) {
+    ///         msg.entered.send(()).unwrap();
+    ///         msg.open.recv().unwrap();
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let config = ThreadPoolConfig::new(0, 1, 1, 1.0).with_default_message_throughput(2);
+    /// actor_system.add_pool_with_config("pairs", config);
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let spawn = |name: &'static str| {
+    ///     let builder = actor_system.builder().set_pool_name("pairs").unwrap().start_suspended(true);
+    ///     let log = log.clone();
+    ///     builder.spawn_fn(name, move |_context| Ticker { name, log: log.clone() }).unwrap()
+    /// };
+    /// let (a, b) = (spawn("a"), spawn("b"));
+    /// assert_eq!(2, actor_system.builder::<Ticker>().set_pool_name("pairs").unwrap().get_resolved_config().config.message_throughput);
+    /// for _ in 0..4 {
+    ///     a.send(Tick {}).unwrap();
+    ///     b.send(Tick {}).unwrap();
+    /// }
+    ///
+    /// // both Actors are queued behind the gate before the worker picks up either of them
+    /// let gate = actor_system.builder().set_pool_name("pairs").unwrap().spawn_fn("gate", |_context| Gate {}).unwrap();
+    /// let (entered, has_entered) = bounded(1);
+    /// let (open, opened) = bounded(1);
+    /// gate.send(Hold { entered, open: opened }).unwrap();
+    /// has_entered.recv_timeout(Duration::from_secs(1)).unwrap();
+    /// a.release();
+    /// b.release();
+    /// open.send(()).unwrap();
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(2);
+    /// while log.lock().unwrap().len() < 8 && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(5));
+    /// }
+    /// // the single worker switches Actors after every second message
+    /// let log = log.lock().unwrap().clone();
+    /// assert_eq!(vec!["a", "a", "b", "b", "a", "a", "b", "b"], log);
+    /// ```
+    pub fn with_default_message_throughput(mut self, message_throughput: usize) -> Self {
+        self.default_message_throughput = Some(message_throughput);
+        self
+    }
+
+    /// What an idle worker does before it waits for the next runnable Actor, defaults to [IdleStrategy::ParkImmediately]
+    ///
+    /// Empty polls of the queue are counted in [PoolStats.idle_polls](../prelude/struct.PoolStats.html#structfield.idle_polls)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("bulk", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let low_latency = ThreadPoolConfig::new(0, 1, 1, 1.0).with_idle_strategy(IdleStrategy::Yield);
+    /// actor_system.add_pool_with_config("low-latency", low_latency);
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// // a parked worker only polls when its wait times out
+    /// assert!(actor_system.pool_stats("bulk").unwrap().idle_polls < 10);
+    /// assert!(actor_system.pool_stats("low-latency").unwrap().idle_polls > 100);
+    ///
+    /// // both settings can be loaded per pool
+    /// let toml = "[thread_pool.config.fast]\nactor_limit = 0\nthreads_min = 1\nthreads_max = 1\nthreads_factor = 1.0\n\
+    ///     default_message_throughput = 4\nidle_strategy = { SpinThenPark = { spin_us = 50 } }\n";
+    /// let config = TyractorsaurConfig::from_toml(toml).unwrap();
+    /// let fast = config.thread_pool.config.get("fast").unwrap();
+    /// assert_eq!(IdleStrategy::SpinThenPark { spin_us: 50 }, fast.idle_strategy);
+    /// assert_eq!(Some(4), fast.default_message_throughput);
+    /// assert_eq!(IdleStrategy::ParkImmediately, config.thread_pool.config.get("default").unwrap().idle_strategy);
+    /// ```
+    pub fn with_idle_strategy(mut self, idle_strategy: IdleStrategy) -> Self {
+        self.idle_strategy = idle_strategy;
+        self
+    }
+
+    /// Amount of threads the pool spawns on a machine with `cpus` cores
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::ThreadPoolConfig;
+    ///
+    /// let config = ThreadPoolConfig::new(0, 2, 10, 1.5);
+    /// assert_eq!(2, config.thread_count(1));
+    /// assert_eq!(6, config.thread_count(4));
+    /// assert_eq!(10, config.thread_count(64));
+    /// ```
+    pub fn thread_count(&self, cpus: usize) -> usize {
+        let thread_count = (self.threads_factor * cpus as f32).floor() as usize;
+        if thread_count < self.threads_min {
+            self.threads_min
+        } else if thread_count > self.threads_max {
+            self.threads_max
+        } else {
+            thread_count
         }
     }
+
+    /// An Actor that is scheduled again runs on its previous worker thread if that worker is idle
+    ///
+    /// A busy Actor keeps its worker as long as no other Actor of the pool is waiting
+    pub fn with_soft_affinity(mut self, soft_affinity: bool) -> Self {
+        self.soft_affinity = soft_affinity;
+        self
+    }
+
+    /// Workers of this pool that find their own queue empty execute runnable Actors of the given pools
+    ///
+    /// A stolen Actor keeps its own `message_throughput` and is queued in its own pool again after its slot.
+    /// An Actor is never executed by two workers at the same time, stolen slots are counted in [PoolStats](../prelude/struct.PoolStats.html).
+    /// Pools that do not exist yet are picked up once they have been added
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Worker {
+    ///     done: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Worker {}
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(10));
+    ///         self.done.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct WorkerFactory {
+    ///     done: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { done: self.done.clone() }
+    ///     }
+    /// }
+    ///
+    /// fn run(steal: bool) -> (Duration, usize) {
+    ///     let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    ///     actor_system.add_pool_with_config("busy", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    ///     let steal_from = if steal { vec![String::from("busy")] } else { Vec::new() };
+    ///     actor_system.add_pool_with_config("idle", ThreadPoolConfig::new(0, 2, 2, 1.0).with_steal_from(steal_from));
+    ///     let done = Arc::new(AtomicUsize::new(0));
+    ///     let workers: Vec<_> = (0..8)
+    ///         .map(|i| {
+    ///             let factory = WorkerFactory { done: done.clone() };
+    ///             actor_system.builder().set_pool_name("busy").unwrap().spawn(format!("worker-{}", i), factory).unwrap()
+    ///         })
+    ///         .collect();
+    ///     sleep(Duration::from_millis(200));
+    ///     let start = Instant::now();
+    ///     for _ in 0..5 {
+    ///         workers.iter().for_each(|worker| worker.send(Work {}).unwrap());
+    ///     }
+    ///     while done.load(Ordering::Relaxed) < 40 {
+    ///         sleep(Duration::from_millis(1));
+    ///     }
+    ///     (start.elapsed(), actor_system.pool_stats("idle").unwrap().stolen_slots)
+    /// }
+    ///
+    /// let (without_stealing, stolen) = run(false);
+    /// assert_eq!(stolen, 0);
+    /// let (with_stealing, stolen) = run(true);
+    /// assert!(stolen > 0);
+    /// assert!(with_stealing < without_stealing);
+    /// ```
+    pub fn with_steal_from(mut self, steal_from: Vec<String>) -> Self {
+        self.steal_from = steal_from;
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
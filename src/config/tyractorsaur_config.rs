@@ -1,8 +1,9 @@
 use crate::config::global_config::GeneralConfig;
-use crate::config::pool_config::PoolConfig;
+use crate::config::pool_config::{PoolConfig, ThreadPoolConfig};
 
 use config::{Config, ConfigError, Environment, File, FileFormat};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 pub const DEFAULT_POOL: &str = "default";
 
@@ -52,4 +53,279 @@ impl TyractorsaurConfig {
 
         Ok(parsed)
     }
+
+    /// Same as [TyractorsaurConfig.new](#method.new), but merges the given TOML over the defaults before the environment variables
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::TyractorsaurConfig;
+    ///
+    /// let config = TyractorsaurConfig::from_toml("[general]\nname = \"embedded\"").unwrap();
+    /// assert_eq!("embedded", config.general.name);
+    /// assert!(TyractorsaurConfig::from_toml("[general").is_err());
+    /// ```
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        let mut config = Config::new();
+
+        let default: &str = std::include_str!("default.toml");
+
+        config
+            .merge(File::from_str(default, FileFormat::Toml))
+            .expect("Could not load default Config");
+        config.merge(File::from_str(toml, FileFormat::Toml))?;
+        config.merge(Environment::with_prefix("TYRACTORSAUR").separator("_CONFIG_"))?;
+
+        let mut parsed: TyractorsaurConfig = config.try_into()?;
+        if parsed.general.name == "$HOSTNAME" {
+            parsed.general.name = String::from(hostname::get().unwrap().to_str().unwrap());
+        }
+
+        Ok(parsed)
+    }
+
+    /// Same as [TyractorsaurConfig.from_toml](#method.from_toml) with the content of a TOML file, fails if the result does not pass [check](#method.check)
+    ///
+    /// Pools that are not part of the defaults are defined entirely within the file. Environment variables take precedence over the file
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{ConfigLoadError, TyractorsaurConfig};
+    ///
+    /// let dir = std::env::temp_dir().join(format!("tyractorsaur-config-{}", std::process::id()));
+    /// std::fs::create_dir_all(&dir).unwrap();
+    ///
+    /// let path = dir.join("valid.toml");
+    /// std::fs::write(&path, "[general]\nname = \"billing\"\ndefault_message_throughput = 30\n\n[thread_pool.config.io]\nactor_limit = 0\nthreads_min = 1\nthreads_max = 4\nthreads_factor = 1.0\n").unwrap();
+    /// let config = TyractorsaurConfig::from_file(&path).unwrap();
+    /// assert_eq!(30, config.general.default_message_throughput);
+    /// assert_eq!(4, config.thread_pool.config.get("io").unwrap().threads_max);
+    /// assert!(config.thread_pool.config.contains_key("default"));
+    ///
+    /// // environment variables override the file
+    /// std::env::set_var("TYRACTORSAUR_GENERAL_CONFIG_NAME", "overridden");
+    /// assert_eq!("overridden", TyractorsaurConfig::from_file(&path).unwrap().general.name);
+    /// std::env::remove_var("TYRACTORSAUR_GENERAL_CONFIG_NAME");
+    ///
+    /// let path = dir.join("invalid.toml");
+    /// std::fs::write(&path, "[thread_pool.config.io]\nactor_limit = 0\nthreads_min = 0\nthreads_max = 0\nthreads_factor = 1.0\n").unwrap();
+    /// let expected = ConfigLoadError::InvalidValue {
+    ///     path: String::from("thread_pool.config.io.threads_max"),
+    ///     message: String::from("a pool needs at least one thread"),
+    /// };
+    /// assert_eq!(Err(expected), TyractorsaurConfig::from_file(&path).map(|_| ()));
+    ///
+    /// let path = dir.join("unparsable.toml");
+    /// std::fs::write(&path, "[general]\ndefault_message_throughput = \"many\"\n").unwrap();
+    /// assert!(matches!(TyractorsaurConfig::from_file(&path), Err(ConfigLoadError::Unparsable { .. })));
+    ///
+    /// let path = dir.join("missing.toml");
+    /// assert!(matches!(TyractorsaurConfig::from_file(&path), Err(ConfigLoadError::Unreadable { .. })));
+    /// std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn from_file(path: &Path) -> Result<Self, ConfigLoadError> {
+        let toml = std::fs::read_to_string(path).map_err(|error| ConfigLoadError::Unreadable {
+            path: PathBuf::from(path),
+            reason: error.to_string(),
+        })?;
+        let config = Self::from_toml(&toml).map_err(ConfigLoadError::unparsable)?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Same as [TyractorsaurConfig.new](#method.new) with environment variables of the given prefix instead of `TYRACTORSAUR`, fails if the result does not pass [check](#method.check)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{ConfigLoadError, TyractorsaurConfig};
+    ///
+    /// std::env::set_var("BILLING_GENERAL_CONFIG_DEFAULT_MESSAGE_THROUGHPUT", "30");
+    /// assert_eq!(30, TyractorsaurConfig::from_env("BILLING").unwrap().general.default_message_throughput);
+    ///
+    /// std::env::set_var("BILLING_THREAD_POOL_CONFIG_CONFIG_CONFIG_DEFAULT_CONFIG_THREADS_MAX", "0");
+    /// let expected = ConfigLoadError::InvalidValue {
+    ///     path: String::from("thread_pool.config.default.threads_max"),
+    ///     message: String::from("a pool needs at least one thread"),
+    /// };
+    /// assert_eq!(Err(expected), TyractorsaurConfig::from_env("BILLING").map(|_| ()));
+    /// ```
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigLoadError> {
+        let mut config = Config::new();
+
+        let default: &str = std::include_str!("default.toml");
+
+        config
+            .merge(File::from_str(default, FileFormat::Toml))
+            .expect("Could not load default Config");
+        config
+            .merge(Environment::with_prefix(prefix).separator("_CONFIG_"))
+            .map_err(ConfigLoadError::unparsable)?;
+
+        let mut parsed: TyractorsaurConfig = config.try_into().map_err(ConfigLoadError::unparsable)?;
+        if parsed.general.name == "$HOSTNAME" {
+            parsed.general.name = String::from(hostname::get().unwrap().to_str().unwrap());
+        }
+        parsed.check()?;
+
+        Ok(parsed)
+    }
+
+    /// Returns the first setting that prevents the system from starting, [ActorSystem.try_new](../prelude/struct.ActorSystem.html#method.try_new) fails with it
+    ///
+    /// Settings that only degrade the system are reported by [validate](#method.validate)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// assert_eq!(Ok(()), config.check());
+    ///
+    /// config.general.default_mailbox_size = usize::MAX;
+    /// let expected = ConfigLoadError::InvalidValue {
+    ///     path: String::from("general.default_mailbox_size"),
+    ///     message: String::from("mailbox sizes must not exceed isize::MAX"),
+    /// };
+    /// assert_eq!(Err(expected), config.check());
+    ///
+    /// config.general.default_mailbox_size = 0;
+    /// config.thread_pool.config.remove("default");
+    /// assert_eq!(Err(ConfigLoadError::MissingDefaultPool), config.check());
+    /// let expected = TyractorsaurError::InvalidConfig(ConfigLoadError::MissingDefaultPool);
+    /// assert_eq!(expected, ActorSystem::try_new(config).err().unwrap());
+    /// ```
+    pub fn check(&self) -> Result<(), ConfigLoadError> {
+        if !self.thread_pool.config.contains_key(DEFAULT_POOL) {
+            return Err(ConfigLoadError::MissingDefaultPool);
+        }
+        check_mailbox_size("general.default_mailbox_size", self.general.default_mailbox_size)?;
+        let mut pools: Vec<(&String, &ThreadPoolConfig)> = self.thread_pool.config.iter().collect();
+        pools.sort_by_key(|(name, _)| *name);
+        for (name, pool) in pools {
+            if pool.threads_max == 0 {
+                return Err(ConfigLoadError::invalid(
+                    &format!("thread_pool.config.{}.threads_max", name),
+                    "a pool needs at least one thread",
+                ));
+            }
+            if let Some(mailbox_size) = pool.actor_defaults.as_ref().and_then(|defaults| defaults.mailbox_size) {
+                check_mailbox_size(&format!("thread_pool.config.{}.actor_defaults.mailbox_size", name), mailbox_size)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns all settings that contradict each other or prevent Actors from working
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{ActorDefaults, ThreadPoolConfig, TyractorsaurConfig};
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// assert!(config.validate().is_empty());
+    ///
+    /// let stuck = ThreadPoolConfig::new(0, 4, 2, 1.0).with_actor_defaults(ActorDefaults {
+    ///     message_throughput: Some(0),
+    ///     ..ActorDefaults::default()
+    /// });
+    /// config.thread_pool.config.insert(String::from("stuck"), stuck);
+    /// let paths: Vec<String> = config.validate().into_iter().map(|warning| warning.path).collect();
+    /// assert_eq!(vec!["thread_pool.config.stuck.threads_min", "thread_pool.config.stuck.actor_defaults.message_throughput"], paths);
+    /// ```
+    pub fn validate(&self) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+        if self.general.default_message_throughput == 0 {
+            warnings.push(ConfigWarning::new(
+                "general.default_message_throughput",
+                "Actors without explicit throughput never handle a message",
+            ));
+        }
+        let mut pools: Vec<(&String, &ThreadPoolConfig)> = self.thread_pool.config.iter().collect();
+        pools.sort_by_key(|(name, _)| *name);
+        for (name, pool) in pools {
+            if pool.threads_min > pool.threads_max {
+                warnings.push(ConfigWarning::new(
+                    &format!("thread_pool.config.{}.threads_min", name),
+                    "threads_min is greater than threads_max, threads_max is used",
+                ));
+            }
+            let actor_defaults = match &pool.actor_defaults {
+                Some(actor_defaults) => actor_defaults,
+                None => continue,
+            };
+            if actor_defaults.message_throughput == Some(0) {
+                warnings.push(ConfigWarning::new(
+                    &format!("thread_pool.config.{}.actor_defaults.message_throughput", name),
+                    "Actors of this pool without explicit throughput never handle a message",
+                ));
+            }
+        }
+        warnings
+    }
+}
+
+fn check_mailbox_size(path: &str, mailbox_size: usize) -> Result<(), ConfigLoadError> {
+    if mailbox_size > isize::MAX as usize {
+        return Err(ConfigLoadError::invalid(path, "mailbox sizes must not exceed isize::MAX"));
+    }
+    Ok(())
+}
+
+/// Returned by [TyractorsaurConfig.from_file](./struct.TyractorsaurConfig.html#method.from_file), [TyractorsaurConfig.from_env](./struct.TyractorsaurConfig.html#method.from_env) and [TyractorsaurConfig.check](./struct.TyractorsaurConfig.html#method.check)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigLoadError {
+    /// the file could not be read
+    Unreadable { path: PathBuf, reason: String },
+    /// the file or an environment variable is no valid TOML or has the wrong type
+    Unparsable { reason: String },
+    /// the setting at the toml `path` prevents the system from starting
+    InvalidValue { path: String, message: String },
+    /// the pool `default` is not configured, Actors are spawned into it unless told otherwise
+    MissingDefaultPool,
+}
+
+impl ConfigLoadError {
+    fn unparsable(error: ConfigError) -> Self {
+        Self::Unparsable {
+            reason: error.to_string(),
+        }
+    }
+
+    fn invalid(path: &str, message: &str) -> Self {
+        Self::InvalidValue {
+            path: String::from(path),
+            message: String::from(message),
+        }
+    }
+}
+
+/// Single finding of [TyractorsaurConfig.validate](./struct.TyractorsaurConfig.html#method.validate)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigWarning {
+    /// toml path of the offending setting
+    pub path: String,
+    pub message: String,
+}
+
+impl ConfigWarning {
+    fn new(path: &str, message: &str) -> Self {
+        Self {
+            path: String::from(path),
+            message: String::from(message),
+        }
+    }
 }
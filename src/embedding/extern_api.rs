@@ -0,0 +1,209 @@
+use crate::config::tyractorsaur_config::TyractorsaurConfig;
+use crate::embedding::tyra_system::{TyraEventCallback, TyraShutdownCode, TyraStatus, TyraSystem};
+use crate::system::actor_system::ActorSystem;
+use crate::system::name_guard::TyractorsaurError;
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::time::Duration;
+
+thread_local! {
+    static LAST_STATUS: Cell<TyraStatus> = const { Cell::new(TyraStatus::Ok) };
+}
+
+/// handles that have not been freed yet, used to detect double frees and dangling handles
+#[cfg(debug_assertions)]
+static HANDLES: std::sync::Mutex<Vec<usize>> = std::sync::Mutex::new(Vec::new());
+
+#[cfg(debug_assertions)]
+pub(crate) fn register_handle(system: *mut TyraSystem) {
+    HANDLES.lock().unwrap().push(system as usize);
+}
+
+#[cfg(not(debug_assertions))]
+pub(crate) fn register_handle(_system: *mut TyraSystem) {}
+
+#[cfg(debug_assertions)]
+fn is_live(system: *const TyraSystem) -> bool {
+    HANDLES.lock().unwrap().contains(&(system as usize))
+}
+
+#[cfg(not(debug_assertions))]
+fn is_live(_system: *const TyraSystem) -> bool {
+    true
+}
+
+/// Catches panics at the boundary and stores the status for [tyra_last_status](./fn.tyra_last_status.html)
+fn guarded<T, F>(on_error: impl FnOnce(TyraStatus) -> T, f: F) -> T
+where
+    F: FnOnce() -> Result<T, TyraStatus>,
+{
+    let (status, result) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(result)) => (TyraStatus::Ok, result),
+        Ok(Err(status)) => (status, on_error(status)),
+        Err(_) => (TyraStatus::Panic, on_error(TyraStatus::Panic)),
+    };
+    LAST_STATUS.with(|last| last.set(status));
+    result
+}
+
+/// # Safety
+///
+/// `system` must be `NULL` or a handle that has been returned by `tyra_system_new` or `TyraSystem.into_raw`
+unsafe fn get_system<'a>(system: *const TyraSystem) -> Result<&'a TyraSystem, TyraStatus> {
+    if system.is_null() {
+        return Err(TyraStatus::NullPointer);
+    }
+    if !is_live(system) {
+        return Err(TyraStatus::InvalidHandle);
+    }
+    Ok(&*system)
+}
+
+/// # Safety
+///
+/// `value` must be `NULL` or point to a NUL-terminated string
+unsafe fn get_str<'a>(value: *const c_char) -> Result<&'a str, TyraStatus> {
+    if value.is_null() {
+        return Err(TyraStatus::NullPointer);
+    }
+    CStr::from_ptr(value).to_str().map_err(|_| TyraStatus::InvalidUtf8)
+}
+
+/// Status of the last call on the current thread, explains a `NULL` returned by `tyra_system_new` or a `Failed` stop
+#[no_mangle]
+pub extern "C" fn tyra_last_status() -> TyraStatus {
+    LAST_STATUS.with(|last| last.get())
+}
+
+/// Creates and starts a system without Actors, `NULL` uses the default configuration
+///
+/// Returns `NULL` on failure, the reason is available through `tyra_last_status`
+///
+/// # Safety
+///
+/// `config_toml` must be `NULL` or point to a NUL-terminated string that stays valid during the call
+#[no_mangle]
+pub unsafe extern "C" fn tyra_system_new(config_toml: *const c_char) -> *mut TyraSystem {
+    guarded(
+        |_| ptr::null_mut(),
+        || {
+            let config = if config_toml.is_null() {
+                TyractorsaurConfig::new()
+            } else {
+                TyractorsaurConfig::from_toml(get_str(config_toml)?)
+            };
+            let config = config.map_err(|_| TyraStatus::InvalidConfig)?;
+            let system = ActorSystem::try_new(config).map_err(|error| match error {
+                TyractorsaurError::SystemNameInUse { .. } => TyraStatus::SystemNameInUse,
+                TyractorsaurError::NameGuardFailed { .. } | TyractorsaurError::InvalidConfig(_) => TyraStatus::InvalidConfig,
+            })?;
+            Ok(TyraSystem::new(system).into_raw())
+        },
+    )
+}
+
+/// Sends a payload to the Actor with the given name, may be called from any thread
+///
+/// Payloads that cannot be delivered are reported as `dead_letter` event
+///
+/// # Safety
+///
+/// `system` must be a live handle, `actor_name` and `type_name` must point to NUL-terminated strings and `payload` must point to `payload_len` readable bytes, it may be `NULL` if `payload_len` is `0`.
+/// The payload is copied, all pointers only have to stay valid during the call
+#[no_mangle]
+pub unsafe extern "C" fn tyra_system_send(
+    system: *const TyraSystem,
+    actor_name: *const c_char,
+    type_name: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+) -> TyraStatus {
+    guarded(
+        |status| status,
+        || {
+            let system = get_system(system)?;
+            let actor_name = get_str(actor_name)?;
+            let type_name = get_str(type_name)?;
+            let payload = match (payload.is_null(), payload_len) {
+                (true, 0) => &[][..],
+                (true, _) => return Err(TyraStatus::NullPointer),
+                (false, len) => std::slice::from_raw_parts(payload, len),
+            };
+            match system.send(actor_name, type_name, payload) {
+                TyraStatus::Ok => Ok(TyraStatus::Ok),
+                status => Err(status),
+            }
+        },
+    )
+}
+
+/// Replaces the event callback, `NULL` removes it
+///
+/// Events are JSON objects with an `event` field, either `dead_letter` or `system_stopped`.
+/// The callback is executed on the thread that caused the event and must not call `tyra_system_free`
+///
+/// # Safety
+///
+/// `system` must be a live handle. `user_data` is passed to every call of the callback, the host guarantees that it may be used from any thread until the callback is replaced or the handle is freed
+#[no_mangle]
+pub unsafe extern "C" fn tyra_system_register_event_callback(
+    system: *const TyraSystem,
+    callback: Option<TyraEventCallback>,
+    user_data: *mut c_void,
+) -> TyraStatus {
+    guarded(
+        |status| status,
+        || {
+            get_system(system)?.set_event_callback(callback, user_data);
+            Ok(TyraStatus::Ok)
+        },
+    )
+}
+
+/// Stops the system gracefully and blocks until it stopped or `timeout_ms` elapsed
+///
+/// Calling it again returns the code of the first stop
+///
+/// # Safety
+///
+/// `system` must be a live handle
+#[no_mangle]
+pub unsafe extern "C" fn tyra_system_stop(system: *const TyraSystem, timeout_ms: u64) -> TyraShutdownCode {
+    guarded(
+        |_| TyraShutdownCode::Failed,
+        || Ok(get_system(system)?.stop(Duration::from_millis(timeout_ms))),
+    )
+}
+
+/// Releases the handle, it must not be used afterwards
+///
+/// Does not stop the system, Actors keep running until `tyra_system_stop` has been called before.
+/// Debug builds detect double frees and return `InvalidHandle` instead of freeing the handle again
+///
+/// # Safety
+///
+/// `system` must be a live handle that is not used by any other thread
+#[no_mangle]
+pub unsafe extern "C" fn tyra_system_free(system: *mut TyraSystem) -> TyraStatus {
+    guarded(
+        |status| status,
+        || {
+            if system.is_null() {
+                return Err(TyraStatus::NullPointer);
+            }
+            #[cfg(debug_assertions)]
+            {
+                let mut handles = HANDLES.lock().unwrap();
+                match handles.iter().position(|handle| *handle == system as usize) {
+                    Some(index) => handles.swap_remove(index),
+                    None => return Err(TyraStatus::InvalidHandle),
+                };
+            }
+            drop(Box::from_raw(system));
+            Ok(TyraStatus::Ok)
+        },
+    )
+}
@@ -0,0 +1,10 @@
+mod extern_api;
+mod tyra_system;
+
+pub mod prelude {
+    pub use crate::embedding::extern_api::{
+        tyra_last_status, tyra_system_free, tyra_system_new, tyra_system_register_event_callback, tyra_system_send,
+        tyra_system_stop,
+    };
+    pub use crate::embedding::tyra_system::{TyraEventCallback, TyraShutdownCode, TyraStatus, TyraSystem};
+}
@@ -0,0 +1,272 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::system::actor_system::ActorSystem;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_void};
+use std::panic::UnwindSafe;
+use std::sync::RwLock;
+use std::thread::sleep;
+use std::time::Duration;
+
+type MessageDecoder = Box<dyn Fn(&[u8]) -> Result<(), String> + Send + Sync>;
+
+/// Receives every event as a NUL-terminated JSON string that is only valid during the call
+pub type TyraEventCallback = extern "C" fn(event_json: *const c_char, user_data: *mut c_void);
+
+/// Result of every function of the [ffi](../ffi/index.html) module, `Ok` is `0`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TyraStatus {
+    Ok = 0,
+    /// a required pointer was `NULL`
+    NullPointer = 1,
+    /// a string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// the configuration could not be parsed
+    InvalidConfig = 3,
+    /// no Actor has been registered under the given name
+    UnknownActor = 4,
+    /// the Actor does not accept the given message type
+    UnknownMessageType = 5,
+    /// the payload could not be decoded into the message type
+    InvalidPayload = 6,
+    /// the system is stopping or has been stopped
+    SystemStopped = 7,
+    /// the handle has already been freed, only detected in debug builds
+    InvalidHandle = 8,
+    /// the call panicked, the system may still be used
+    Panic = 9,
+    /// another process runs a system with the same name, see `general.unique_name_guard`
+    SystemNameInUse = 10,
+}
+
+/// Same codes as [ActorSystem.await_shutdown](../prelude/struct.ActorSystem.html#method.await_shutdown), extended by `Failed`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TyraShutdownCode {
+    Graceful = 0,
+    Forced = 1,
+    /// the system could not be stopped through this call, see [tyra_last_status](./fn.tyra_last_status.html)
+    Failed = 2,
+    /// stopped because of a panic, see [PanicEscalation::StopSystem](../prelude/enum.PanicEscalation.html#variant.StopSystem)
+    Panicked = 3,
+}
+
+struct EventCallback {
+    callback: TyraEventCallback,
+    user_data: *mut c_void,
+}
+
+// the host guarantees that `user_data` may be used from any thread, see `tyra_system_register_event_callback`
+unsafe impl Send for EventCallback {}
+unsafe impl Sync for EventCallback {}
+
+/// An [ActorSystem](../prelude/struct.ActorSystem.html) that is driven by a non-Rust host through the [ffi](../ffi/index.html) functions
+///
+/// Actors are implemented in Rust, spawned and registered with their message types before the handle is passed to the host through [TyraSystem.into_raw](#method.into_raw).
+/// The crate that registers the Actors is built as `staticlib` or `cdylib` and exports all `tyra_*` functions to the host
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::ffi::*;
+/// use std::ffi::{CStr, CString};
+/// use std::os::raw::{c_char, c_void};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Greet { name: String }
+/// impl ActorMessage for Greet {}
+///
+/// struct Greeter {
+///     greeted: Arc<Mutex<Vec<String>>>,
+///     context: ActorContext<Self>,
+/// }
+/// impl Actor for Greeter {
+///     fn on_system_stop(&mut self) {
+///         self.context.actor_ref.stop();
+///     }
+/// }
+/// impl Handler<Greet> for Greeter {
+///     fn handle(&mut self, msg: Greet, _context: &ActorContext<Self>) {
+///         self.greeted.lock().unwrap().push(msg.name);
+///     }
+/// }
+///
+/// struct GreeterFactory {
+///     greeted: Arc<Mutex<Vec<String>>>,
+/// }
+/// impl ActorFactory<Greeter> for GreeterFactory {
+///     fn new_actor(&self, context: ActorContext<Greeter>) -> Greeter {
+///         Greeter { greeted: self.greeted.clone(), context }
+///     }
+/// }
+///
+/// extern "C" fn on_event(event_json: *const c_char, user_data: *mut c_void) {
+///     let events = unsafe { &*(user_data as *const Mutex<Vec<String>>) };
+///     let event = unsafe { CStr::from_ptr(event_json) }.to_str().unwrap();
+///     events.lock().unwrap().push(String::from(event));
+/// }
+///
+/// unsafe {
+///     // systems created by the host
+///     assert!(tyra_system_new(b"\xff\0".as_ptr() as *const c_char).is_null());
+///     assert_eq!(TyraStatus::InvalidUtf8, tyra_last_status());
+///     let broken = CString::new("[general").unwrap();
+///     assert!(tyra_system_new(broken.as_ptr()).is_null());
+///     assert_eq!(TyraStatus::InvalidConfig, tyra_last_status());
+///     let config = CString::new("[general]\nname = \"host\"").unwrap();
+///     let empty = tyra_system_new(config.as_ptr());
+///     assert!(!empty.is_null());
+///     assert_eq!(TyraShutdownCode::Graceful, tyra_system_stop(empty, 1000));
+///     assert_eq!(TyraStatus::Ok, tyra_system_free(empty));
+///     if cfg!(debug_assertions) {
+///         assert_eq!(TyraStatus::InvalidHandle, tyra_system_free(empty));
+///     }
+/// }
+///
+/// // Actors are registered in Rust before the handle is passed to the host
+/// let greeted = Arc::new(Mutex::new(Vec::new()));
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let greeter = actor_system.builder().spawn("greeter", GreeterFactory { greeted: greeted.clone() }).unwrap();
+/// let mut embedded = TyraSystem::new(actor_system);
+/// embedded.register_message(&greeter, "greet", |payload: &[u8]| {
+///     String::from_utf8(payload.to_vec()).map(|name| Greet { name }).map_err(|e| e.to_string())
+/// });
+/// let system = embedded.into_raw();
+///
+/// let events: Mutex<Vec<String>> = Mutex::new(Vec::new());
+/// unsafe {
+///     let user_data = &events as *const Mutex<Vec<String>> as *mut c_void;
+///     assert_eq!(TyraStatus::Ok, tyra_system_register_event_callback(system, Some(on_event), user_data));
+///     let greeter_name = CString::new("greeter").unwrap();
+///     let greet = CString::new("greet").unwrap();
+///     let payload = b"alice";
+///     assert_eq!(TyraStatus::Ok, tyra_system_send(system, greeter_name.as_ptr(), greet.as_ptr(), payload.as_ptr(), payload.len()));
+///
+///     let unknown = CString::new("unknown").unwrap();
+///     assert_eq!(TyraStatus::UnknownActor, tyra_system_send(system, unknown.as_ptr(), greet.as_ptr(), payload.as_ptr(), payload.len()));
+///     assert_eq!(TyraStatus::UnknownMessageType, tyra_system_send(system, greeter_name.as_ptr(), unknown.as_ptr(), payload.as_ptr(), payload.len()));
+///     let invalid = b"\xff";
+///     assert_eq!(TyraStatus::InvalidPayload, tyra_system_send(system, greeter_name.as_ptr(), greet.as_ptr(), invalid.as_ptr(), invalid.len()));
+///     sleep(Duration::from_millis(200));
+///     assert_eq!(vec![String::from("alice")], *greeted.lock().unwrap());
+///
+///     assert_eq!(TyraShutdownCode::Graceful, tyra_system_stop(system, 1000));
+///     assert_eq!(TyraStatus::SystemStopped, tyra_system_send(system, greeter_name.as_ptr(), greet.as_ptr(), payload.as_ptr(), payload.len()));
+///     assert_eq!(TyraStatus::Ok, tyra_system_free(system));
+/// }
+///
+/// let events = events.lock().unwrap();
+/// assert_eq!(5, events.len());
+/// assert!(events[0].contains("\"reason\":\"UnknownActor\""));
+/// assert!(events[3].contains("\"event\":\"system_stopped\""));
+/// assert!(events[4].contains("\"reason\":\"SystemStopped\""));
+/// ```
+pub struct TyraSystem {
+    system: ActorSystem,
+    actors: HashMap<String, HashMap<String, MessageDecoder>>,
+    event_callback: RwLock<Option<EventCallback>>,
+}
+
+impl TyraSystem {
+    pub fn new(system: ActorSystem) -> Self {
+        Self {
+            system,
+            actors: HashMap::new(),
+            event_callback: RwLock::new(None),
+        }
+    }
+
+    /// Accepts payloads of type `type_name` for the Actor, `decode` turns them into the message that is sent
+    ///
+    /// The Actor is reachable through its name
+    pub fn register_message<A, M, F>(&mut self, actor: &ActorWrapper<A>, type_name: impl Into<String>, decode: F)
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+        F: Fn(&[u8]) -> Result<M, String> + Send + Sync + 'static,
+    {
+        let wrapper = actor.clone();
+        let decoder: MessageDecoder = Box::new(move |payload| {
+            let _ = wrapper.send(decode(payload)?);
+            Ok(())
+        });
+        self.actors
+            .entry(actor.get_address().actor.clone())
+            .or_default()
+            .insert(type_name.into(), decoder);
+    }
+
+    pub fn get_system(&self) -> &ActorSystem {
+        &self.system
+    }
+
+    /// Hands the ownership over to the host, which has to release it through `tyra_system_free`
+    pub fn into_raw(self) -> *mut TyraSystem {
+        let system = Box::into_raw(Box::new(self));
+        super::extern_api::register_handle(system);
+        system
+    }
+
+    pub(crate) fn set_event_callback(&self, callback: Option<TyraEventCallback>, user_data: *mut c_void) {
+        *self.event_callback.write().unwrap() = callback.map(|callback| EventCallback { callback, user_data });
+    }
+
+    pub(crate) fn send(&self, actor: &str, type_name: &str, payload: &[u8]) -> TyraStatus {
+        let status = if self.system.get_state().is_stopping() {
+            TyraStatus::SystemStopped
+        } else {
+            match self.actors.get(actor).map(|decoders| decoders.get(type_name)) {
+                None => TyraStatus::UnknownActor,
+                Some(None) => TyraStatus::UnknownMessageType,
+                Some(Some(decoder)) => match decoder(payload) {
+                    Ok(()) => return TyraStatus::Ok,
+                    Err(_) => TyraStatus::InvalidPayload,
+                },
+            }
+        };
+        self.emit(json!({
+            "event": "dead_letter",
+            "actor": actor,
+            "type_name": type_name,
+            "reason": format!("{:?}", status),
+        }));
+        status
+    }
+
+    pub(crate) fn stop(&self, timeout: Duration) -> TyraShutdownCode {
+        self.system.stop(timeout);
+        let state = self.system.get_state();
+        while !state.is_stopped() {
+            sleep(Duration::from_millis(10));
+        }
+        let code = if state.is_panic_stopped() {
+            TyraShutdownCode::Panicked
+        } else if state.is_force_stopped() {
+            TyraShutdownCode::Forced
+        } else {
+            TyraShutdownCode::Graceful
+        };
+        self.emit(json!({
+            "event": "system_stopped",
+            "code": code as i32,
+        }));
+        code
+    }
+
+    fn emit(&self, event: Value) {
+        if let Some(event_callback) = self.event_callback.read().unwrap().as_ref() {
+            let event = CString::new(event.to_string()).unwrap();
+            (event_callback.callback)(event.as_ptr(), event_callback.user_data);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::harness::interaction_log::{short_type_name, Interaction, InteractionKind};
+use crate::harness::test_actor_system::TestActorSystem;
+use crate::message::actor_message::ActorMessage;
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::panic::UnwindSafe;
+
+type Matcher = Box<dyn Fn(&dyn Any) -> bool + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExpectedKind {
+    Send,
+    Handle,
+}
+
+/// Expectation as registered within a [TestActorSystem](./struct.TestActorSystem.html)
+pub(crate) struct ExpectationSpec {
+    kind: ExpectedKind,
+    /// sender of a send, or the Actor that handles the message
+    actor: ActorAddress,
+    target: Option<ActorAddress>,
+    message_type: Option<(TypeId, &'static str)>,
+    matcher: Option<Matcher>,
+    /// `None` requires at least one matching interaction
+    times: Option<usize>,
+    is_dead_letter: bool,
+    /// indices of the expectations that have to be met afterwards
+    before: Vec<usize>,
+}
+
+impl ExpectationSpec {
+    fn new(kind: ExpectedKind, actor: ActorAddress) -> Self {
+        Self {
+            kind,
+            actor,
+            target: None,
+            message_type: None,
+            matcher: None,
+            times: None,
+            is_dead_letter: false,
+            before: Vec::new(),
+        }
+    }
+
+    fn matches(&self, interaction: &Interaction) -> bool {
+        let is_kind = match self.kind {
+            ExpectedKind::Send if self.is_dead_letter => interaction.kind == InteractionKind::DeadLetter,
+            ExpectedKind::Send => interaction.kind != InteractionKind::Handled,
+            ExpectedKind::Handle => interaction.kind == InteractionKind::Handled,
+        };
+        let actor = match self.kind {
+            ExpectedKind::Send => interaction.sender.as_ref(),
+            ExpectedKind::Handle => Some(&interaction.target),
+        };
+        is_kind
+            && actor == Some(&self.actor)
+            && self.target.as_ref().is_none_or(|target| *target == interaction.target)
+            && self.message_type.is_none_or(|(type_id, _)| type_id == interaction.get_type_id())
+            && self.matcher.as_ref().is_none_or(|matcher| {
+                // messages of unregistered types are never cloned into the log
+                interaction.get_payload().is_some_and(matcher)
+            })
+    }
+}
+
+impl Display for ExpectationSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let message_type = self.message_type.map(|(_, type_name)| short_type_name(type_name));
+        match (self.kind, message_type) {
+            (ExpectedKind::Send, Some(message_type)) => write!(f, "{} sends {}", self.actor.actor, message_type)?,
+            (ExpectedKind::Send, None) => write!(f, "{} sends any message", self.actor.actor)?,
+            (ExpectedKind::Handle, Some(message_type)) => write!(f, "{} handles {}", self.actor.actor, message_type)?,
+            (ExpectedKind::Handle, None) => write!(f, "{} handles any message", self.actor.actor)?,
+        }
+        if let Some(target) = &self.target {
+            write!(f, " to {}", target.actor)?;
+        }
+        if self.matcher.is_some() {
+            write!(f, " matching the predicate")?;
+        }
+        if self.is_dead_letter {
+            write!(f, " as dead letter")?;
+        }
+        match self.times {
+            Some(1) => write!(f, " exactly once"),
+            Some(times) => write!(f, " exactly {} times", times),
+            None => write!(f, " at least once"),
+        }
+    }
+}
+
+/// Entry point of the expectations on a single Actor, see [TestActorSystem.expect](./struct.TestActorSystem.html#method.expect)
+pub struct ActorExpectations<'a> {
+    testkit: &'a TestActorSystem,
+    actor: ActorAddress,
+}
+
+impl<'a> ActorExpectations<'a> {
+    pub(crate) fn new(testkit: &'a TestActorSystem, actor: ActorAddress) -> Self {
+        Self { testkit, actor }
+    }
+
+    /// Expects the Actor to send `M`, including sends to Actors that have already stopped
+    pub fn to_send<M>(self) -> Expectation<'a, M>
+    where
+        M: ActorMessage + 'static,
+    {
+        let mut spec = ExpectationSpec::new(ExpectedKind::Send, self.actor);
+        spec.message_type = Some((TypeId::of::<M>(), std::any::type_name::<M>()));
+        Expectation::register(self.testkit, spec)
+    }
+
+    /// Expects the Actor to never send anything to `target`
+    pub fn to_send_nothing_to<B>(self, target: &ActorWrapper<B>) -> Expectation<'a, ()>
+    where
+        B: Actor + UnwindSafe,
+    {
+        let mut spec = ExpectationSpec::new(ExpectedKind::Send, self.actor);
+        spec.target = Some(target.get_address().clone());
+        spec.times = Some(0);
+        Expectation::register(self.testkit, spec)
+    }
+
+    /// Expects the Actor to handle `M`
+    pub fn to_handle<M>(self) -> Expectation<'a, M>
+    where
+        M: ActorMessage + 'static,
+    {
+        let mut spec = ExpectationSpec::new(ExpectedKind::Handle, self.actor);
+        spec.message_type = Some((TypeId::of::<M>(), std::any::type_name::<M>()));
+        Expectation::register(self.testkit, spec)
+    }
+}
+
+/// A registered expectation, refined through its builder methods and checked by [TestActorSystem.verify](./struct.TestActorSystem.html#method.verify)
+///
+/// Requires at least one matching interaction, unless [times](#method.times) is set
+pub struct Expectation<'a, M> {
+    testkit: &'a TestActorSystem,
+    index: usize,
+    phantom: PhantomData<fn(M)>,
+}
+
+impl<'a, M> Expectation<'a, M> {
+    fn register(testkit: &'a TestActorSystem, spec: ExpectationSpec) -> Self {
+        Self {
+            testkit,
+            index: testkit.add_expectation(spec),
+            phantom: PhantomData,
+        }
+    }
+
+    fn update(self, f: impl FnOnce(&mut ExpectationSpec)) -> Self {
+        self.testkit.update_expectation(self.index, f);
+        self
+    }
+
+    /// Only matches messages sent to `target`
+    pub fn to<B>(self, target: &ActorWrapper<B>) -> Self
+    where
+        B: Actor + UnwindSafe,
+    {
+        let target = target.get_address().clone();
+        self.update(|spec| spec.target = Some(target))
+    }
+
+    /// Requires exactly `times` matching interactions
+    pub fn times(self, times: usize) -> Self {
+        self.update(|spec| spec.times = Some(times))
+    }
+
+    /// Only matches sends to Actors that have already stopped
+    pub fn as_dead_letter(self) -> Self {
+        self.update(|spec| spec.is_dead_letter = true)
+    }
+
+    /// Requires all interactions matching this expectation to happen before the first one matching `other`
+    pub fn before<N>(self, other: &Expectation<'a, N>) -> Self {
+        let other = other.index;
+        self.update(|spec| spec.before.push(other))
+    }
+}
+
+impl<'a, M> Expectation<'a, M>
+where
+    M: ActorMessage + Clone + 'static,
+{
+    /// Only matches messages for which `matcher` returns `true`
+    ///
+    /// Messages of type `M` are cloned into the interaction log from now on, messages sent through [ActorWrapper.send_lazy](../prelude/struct.ActorWrapper.html#method.send_lazy) never match
+    pub fn matching<F>(self, matcher: F) -> Self
+    where
+        F: Fn(&M) -> bool + Send + 'static,
+    {
+        self.testkit.get_interaction_log().register::<M>();
+        let matcher: Matcher = Box::new(move |msg: &dyn Any| msg.downcast_ref::<M>().is_some_and(&matcher));
+        self.update(|spec| spec.matcher = Some(matcher))
+    }
+}
+
+/// A single failure found by [TestActorSystem.verify](./struct.TestActorSystem.html#method.verify)
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationFailure {
+    /// the expectation matched `actual` interactions
+    Unmatched { expectation: String, actual: usize },
+    /// the interaction `at` matching `expectation` happened after `other_at`, the first one matching `other`
+    OutOfOrder {
+        expectation: String,
+        other: String,
+        at: usize,
+        other_at: usize,
+    },
+    /// an Actor with send expectations sent a message that none of them covers
+    Unexpected { interaction: String },
+}
+
+impl Display for VerificationFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationFailure::Unmatched { expectation, actual } => {
+                write!(f, "expected {}, matched {} times", expectation, actual)
+            }
+            VerificationFailure::OutOfOrder {
+                expectation,
+                other,
+                at,
+                other_at,
+            } => write!(f, "expected {} before {}, but #{} came after #{}", expectation, other, at, other_at),
+            VerificationFailure::Unexpected { interaction } => write!(f, "unexpected {}", interaction),
+        }
+    }
+}
+
+/// All failures of [TestActorSystem.verify](./struct.TestActorSystem.html#method.verify) together with the complete interaction log
+///
+/// `Debug` prints the same report as `Display`, so that `verify().unwrap()` fails readably
+#[derive(Clone)]
+pub struct VerificationReport {
+    pub expectations: usize,
+    pub failures: Vec<VerificationFailure>,
+    pub interactions: Vec<Interaction>,
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} failures for {} expectations:", self.failures.len(), self.expectations)?;
+        for failure in self.failures.iter() {
+            writeln!(f, "  {}", failure)?;
+        }
+        writeln!(f, "actual interactions:")?;
+        for interaction in self.interactions.iter() {
+            writeln!(f, "  {}", interaction)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Checks all expectations against the log, failures are ordered by expectation and unexpected interactions come last
+pub(crate) fn verify(specs: &[ExpectationSpec], interactions: Vec<Interaction>) -> Result<(), VerificationReport> {
+    let mut failures = Vec::new();
+    let matches: Vec<Vec<usize>> = specs
+        .iter()
+        .map(|spec| {
+            interactions
+                .iter()
+                .filter(|interaction| spec.matches(interaction))
+                .map(|interaction| interaction.sequence)
+                .collect()
+        })
+        .collect();
+
+    for (spec, matched) in specs.iter().zip(matches.iter()) {
+        let is_met = match spec.times {
+            Some(times) => matched.len() == times,
+            None => !matched.is_empty(),
+        };
+        if !is_met {
+            failures.push(VerificationFailure::Unmatched {
+                expectation: spec.to_string(),
+                actual: matched.len(),
+            });
+        }
+    }
+
+    for (spec, matched) in specs.iter().zip(matches.iter()) {
+        for other in spec.before.iter() {
+            let (last, first) = match (matched.last(), matches[*other].first()) {
+                (Some(last), Some(first)) => (last, first),
+                // missing interactions are already reported as unmatched
+                _ => continue,
+            };
+            if last > first {
+                failures.push(VerificationFailure::OutOfOrder {
+                    expectation: spec.to_string(),
+                    other: specs[*other].to_string(),
+                    at: *last,
+                    other_at: *first,
+                });
+            }
+        }
+    }
+
+    let senders: HashSet<&ActorAddress> = specs
+        .iter()
+        .filter(|spec| spec.kind == ExpectedKind::Send)
+        .map(|spec| &spec.actor)
+        .collect();
+    // interactions matched by forbidding expectations have already been reported
+    let covered: HashSet<usize> = specs
+        .iter()
+        .zip(matches.iter())
+        .filter(|(spec, _)| spec.kind == ExpectedKind::Send)
+        .flat_map(|(_, matched)| matched.iter().copied())
+        .collect();
+    for interaction in interactions.iter() {
+        let is_from_sender = interaction.sender.as_ref().is_some_and(|sender| senders.contains(sender));
+        if interaction.kind != InteractionKind::Handled && is_from_sender && !covered.contains(&interaction.sequence) {
+            failures.push(VerificationFailure::Unexpected {
+                interaction: interaction.to_string(),
+            });
+        }
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+    Err(VerificationReport {
+        expectations: specs.len(),
+        failures,
+        interactions,
+    })
+}
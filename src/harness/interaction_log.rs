@@ -0,0 +1,202 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::child_terminated_message::ChildTerminatedMessage;
+use crate::message::envelope::{MessageEnvelope, MessageEnvelopeTrait};
+use crate::message::suspension_ended_message::SuspensionEndedMessage;
+use crate::message::system_stop_message::SystemStopMessage;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::sync::{Arc, Mutex};
+
+type Payload = Arc<dyn Any + Send + Sync>;
+type PayloadCloner = fn(&dyn Any) -> Payload;
+
+/// What happened to a message within a [TestActorSystem](./struct.TestActorSystem.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteractionKind {
+    Sent,
+    /// sent to an Actor that has already stopped, the message has been dropped
+    DeadLetter,
+    Handled,
+}
+
+/// A single entry of the interaction log of a [TestActorSystem](./struct.TestActorSystem.html)
+#[derive(Clone)]
+pub struct Interaction {
+    /// position within the log, starting at `0`
+    pub sequence: usize,
+    pub kind: InteractionKind,
+    /// Actor that handled a message while this one was sent, `None` for messages sent by the test itself and for handled messages
+    pub sender: Option<ActorAddress>,
+    /// receiver of a sent message, or the Actor that handled it
+    pub target: ActorAddress,
+    pub message_type: &'static str,
+    type_id: TypeId,
+    payload: Option<Payload>,
+}
+
+impl Interaction {
+    /// Returns the clone of the message, only kept for types that are used by [Expectation.matching](./struct.Expectation.html#method.matching)
+    pub fn payload<M>(&self) -> Option<&M>
+    where
+        M: 'static,
+    {
+        self.payload.as_ref()?.downcast_ref::<M>()
+    }
+
+    pub(crate) fn get_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub(crate) fn get_payload(&self) -> Option<&dyn Any> {
+        self.payload.as_deref().map(|payload| payload as &dyn Any)
+    }
+}
+
+impl Debug for Interaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interaction")
+            .field("sequence", &self.sequence)
+            .field("kind", &self.kind)
+            .field("sender", &self.sender)
+            .field("target", &self.target)
+            .field("message_type", &self.message_type)
+            .finish()
+    }
+}
+
+impl Display for Interaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let message_type = short_type_name(self.message_type);
+        match self.kind {
+            InteractionKind::Handled => write!(f, "#{} {} handled {}", self.sequence, self.target.actor, message_type),
+            kind => {
+                let sender = self.sender.as_ref().map_or("test", |sender| sender.actor.as_str());
+                write!(f, "#{} {} -> {} {}", self.sequence, sender, self.target.actor, message_type)?;
+                if kind == InteractionKind::DeadLetter {
+                    write!(f, " (dead letter)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Strips the module path of a type name that is not generic
+pub(crate) fn short_type_name(type_name: &'static str) -> &'static str {
+    if type_name.contains('<') {
+        return type_name;
+    }
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+fn clone_payload<M>(msg: &dyn Any) -> Payload
+where
+    M: Clone + Send + Sync + 'static,
+{
+    Arc::new(msg.downcast_ref::<M>().unwrap().clone())
+}
+
+fn is_control_message(type_id: TypeId) -> bool {
+    type_id == TypeId::of::<ActorStopMessage>()
+        || type_id == TypeId::of::<SystemStopMessage>()
+        || type_id == TypeId::of::<SuspensionEndedMessage>()
+        || type_id == TypeId::of::<ChildTerminatedMessage>()
+}
+
+#[derive(Default)]
+struct LogState {
+    interactions: Vec<Interaction>,
+    /// Actor whose handler is currently executed, the harness executes a single handler at a time
+    current: Option<ActorAddress>,
+    cloners: HashMap<TypeId, PayloadCloner>,
+}
+
+/// Records all sends and handled messages of a [TestActorSystem](./struct.TestActorSystem.html), framework control messages are left out
+#[derive(Default)]
+pub(crate) struct InteractionLog {
+    state: Mutex<LogState>,
+}
+
+impl InteractionLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps a clone of every message of type `M`, so that it can be matched afterwards
+    pub fn register<M>(&self)
+    where
+        M: Clone + Send + Sync + 'static,
+    {
+        let mut state = self.state.lock().unwrap();
+        state.cloners.insert(TypeId::of::<M>(), clone_payload::<M>);
+    }
+
+    pub fn record_send<A>(&self, target: &ActorAddress, msg: &MessageEnvelope<A>, is_dead_letter: bool)
+    where
+        A: Actor,
+    {
+        let kind = if is_dead_letter {
+            InteractionKind::DeadLetter
+        } else {
+            InteractionKind::Sent
+        };
+        let mut state = self.state.lock().unwrap();
+        let sender = state.current.clone();
+        Self::push(&mut state, kind, sender, target, msg);
+    }
+
+    /// Records the handled message, all sends until [end_handle](#method.end_handle) are attributed to `address`
+    pub fn begin_handle<A>(&self, address: &ActorAddress, msg: &MessageEnvelope<A>)
+    where
+        A: Actor,
+    {
+        let mut state = self.state.lock().unwrap();
+        state.current = Some(address.clone());
+        Self::push(&mut state, InteractionKind::Handled, None, address, msg);
+    }
+
+    pub fn end_handle(&self) {
+        self.state.lock().unwrap().current = None;
+    }
+
+    pub fn get_interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    pub fn count(&self, kind: InteractionKind) -> usize {
+        let state = self.state.lock().unwrap();
+        state.interactions.iter().filter(|interaction| interaction.kind == kind).count()
+    }
+
+    fn push<A>(
+        state: &mut LogState,
+        kind: InteractionKind,
+        sender: Option<ActorAddress>,
+        target: &ActorAddress,
+        msg: &MessageEnvelope<A>,
+    ) where
+        A: Actor,
+    {
+        let type_id = msg.get_type_id();
+        if is_control_message(type_id) {
+            return;
+        }
+        let payload = match (state.cloners.get(&type_id), msg.get_message()) {
+            (Some(cloner), Some(message)) => Some(cloner(message)),
+            _ => None,
+        };
+        let interaction = Interaction {
+            sequence: state.interactions.len(),
+            kind,
+            sender,
+            target: target.clone(),
+            message_type: msg.get_type_name(),
+            type_id,
+            payload,
+        };
+        state.interactions.push(interaction);
+    }
+}
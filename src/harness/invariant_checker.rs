@@ -0,0 +1,214 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A broken guarantee of the mailbox and wakeup protocol, found by an [InvariantChecker](./struct.InvariantChecker.html)
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvariantViolation {
+    /// a handler was entered while another handler of the same Actor was still running
+    ConcurrentHandlers { sender: usize, seq: u64 },
+    /// the message has already been handled before
+    Duplicate { sender: usize, seq: u64 },
+    /// the message overtook `expected`, which has been sent earlier by the same sender
+    OutOfOrder { sender: usize, expected: u64, seq: u64 },
+    /// the message has never been sent
+    Unknown { sender: usize, seq: u64 },
+    /// a handler was entered after the Actor stopped
+    HandledAfterStop { sender: usize, seq: u64 },
+    /// the Actor was picked up while it was already scheduled somewhere else
+    DoubleScheduled,
+    /// the Actor was picked up after it stopped
+    ScheduledAfterStop,
+    /// the system became idle while the message was neither handled nor legitimately dropped by a stopped Actor
+    Lost { sender: usize, seq: u64 },
+    /// the system became idle, but a requested stop never took effect
+    StopIgnored,
+    /// wakeups kept circulating without the system becoming idle
+    NotQuiescent,
+}
+
+/// Outcome of a run that did not violate any invariant
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HarnessReport {
+    pub sent: u64,
+    pub handled: u64,
+    /// messages that were not handled, because they were sent to or still queued in a stopped Actor
+    pub dropped: u64,
+    pub stopped: bool,
+}
+
+/// Checks the guarantees of a single Actor, while any number of threads send to and execute it
+///
+/// Every sender numbers its messages through [record_sent](#method.record_sent) and [record_enqueued](#method.record_enqueued), the Actor reports them through [begin_handle](#method.begin_handle) and [end_handle](#method.end_handle)
+///
+/// A stop only excuses messages that were not yet enqueued when it was requested, or all remaining messages if the Actor crashed
+///
+/// The first violation is kept and returned by [check](#method.check)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::testing::*;
+///
+/// let checker = InvariantChecker::new(1);
+/// let first = checker.record_sent(0);
+/// let second = checker.record_sent(0);
+/// checker.begin_handle(0, second);
+/// checker.end_handle();
+/// assert_eq!(checker.check(), Err(InvariantViolation::OutOfOrder { sender: 0, expected: first, seq: second }));
+/// ```
+pub struct InvariantChecker {
+    sent: Vec<AtomicU64>,
+    enqueued: Vec<AtomicU64>,
+    enqueued_before_stop: Mutex<Option<Vec<u64>>>,
+    handled: Vec<AtomicU64>,
+    in_handler: AtomicBool,
+    scheduled: AtomicUsize,
+    stop_requested: AtomicBool,
+    stopped: AtomicBool,
+    crashed: AtomicBool,
+    violation: Mutex<Option<InvariantViolation>>,
+}
+
+impl InvariantChecker {
+    pub fn new(senders: usize) -> Self {
+        Self {
+            sent: (0..senders).map(|_| AtomicU64::new(0)).collect(),
+            enqueued: (0..senders).map(|_| AtomicU64::new(0)).collect(),
+            enqueued_before_stop: Mutex::new(None),
+            handled: (0..senders).map(|_| AtomicU64::new(0)).collect(),
+            in_handler: AtomicBool::new(false),
+            scheduled: AtomicUsize::new(0),
+            stop_requested: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+            crashed: AtomicBool::new(false),
+            violation: Mutex::new(None),
+        }
+    }
+
+    /// Returns the sequence number of the next message of the sender, must be called before the message is sent
+    pub fn record_sent(&self, sender: usize) -> u64 {
+        self.sent[sender].fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// The send of the message returned
+    pub fn record_enqueued(&self, sender: usize, seq: u64) {
+        self.enqueued[sender].store(seq + 1, Ordering::SeqCst);
+    }
+
+    /// Must be called before the stop is sent
+    pub fn record_stop_requested(&self) {
+        self.stop_requested.store(true, Ordering::SeqCst);
+        self.enqueued_before_stop
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| self.enqueued.iter().map(|enqueued| enqueued.load(Ordering::SeqCst)).collect());
+    }
+
+    /// A handler panicked and the Actor is not restarted, so that all queued messages are dropped
+    pub fn record_crashed(&self) {
+        self.crashed.store(true, Ordering::SeqCst);
+    }
+
+    /// The Actor returned `ActorState::Stopped`
+    pub fn record_stopped(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    pub fn begin_handle(&self, sender: usize, seq: u64) {
+        if self.in_handler.swap(true, Ordering::SeqCst) {
+            self.violate(InvariantViolation::ConcurrentHandlers { sender, seq });
+        }
+        if self.is_stopped() {
+            self.violate(InvariantViolation::HandledAfterStop { sender, seq });
+        }
+        if seq >= self.sent[sender].load(Ordering::SeqCst) {
+            self.violate(InvariantViolation::Unknown { sender, seq });
+            return;
+        }
+        // messages of a sender are only ever dropped as a suffix, so the handled ones form a prefix
+        let expected = self.handled[sender].load(Ordering::SeqCst);
+        if seq < expected {
+            self.violate(InvariantViolation::Duplicate { sender, seq });
+        } else if seq > expected {
+            self.violate(InvariantViolation::OutOfOrder { sender, expected, seq });
+        } else {
+            self.handled[sender].store(seq + 1, Ordering::SeqCst);
+        }
+    }
+
+    pub fn end_handle(&self) {
+        self.in_handler.store(false, Ordering::SeqCst);
+    }
+
+    /// A thread took the Actor from the pool queue or the sleeping Actors
+    pub fn record_scheduled(&self) {
+        if self.is_stopped() {
+            self.violate(InvariantViolation::ScheduledAfterStop);
+        }
+        if self.scheduled.fetch_add(1, Ordering::SeqCst) > 0 {
+            self.violate(InvariantViolation::DoubleScheduled);
+        }
+    }
+
+    /// The thread handed the Actor back, must be called before the Actor becomes visible to other threads again
+    pub fn record_descheduled(&self) {
+        self.scheduled.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    pub fn record_not_quiescent(&self) {
+        self.violate(InvariantViolation::NotQuiescent);
+    }
+
+    /// Returns the first violation found so far
+    pub fn check(&self) -> Result<(), InvariantViolation> {
+        match self.violation.lock().unwrap().as_ref() {
+            Some(violation) => Err(violation.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Final check once no thread sends or executes anymore and all wakeups have been processed
+    ///
+    /// Every sent message must have been handled, unless the Actor stopped before it was enqueued
+    pub fn check_quiescent(&self) -> Result<HarnessReport, InvariantViolation> {
+        self.check()?;
+        let stopped = self.is_stopped();
+        if self.stop_requested.load(Ordering::SeqCst) && !stopped {
+            self.violate(InvariantViolation::StopIgnored);
+            return self.check().map(|_| HarnessReport::default());
+        }
+        let mut report = HarnessReport {
+            stopped,
+            ..HarnessReport::default()
+        };
+        let enqueued_before_stop = self.enqueued_before_stop.lock().unwrap().clone();
+        for (sender, (sent, handled)) in self.sent.iter().zip(self.handled.iter()).enumerate() {
+            let sent = sent.load(Ordering::SeqCst);
+            let handled = handled.load(Ordering::SeqCst);
+            let required = match &enqueued_before_stop {
+                _ if !stopped => sent,
+                _ if self.crashed.load(Ordering::SeqCst) => 0,
+                Some(enqueued_before_stop) => enqueued_before_stop[sender],
+                None => 0,
+            };
+            if handled < required {
+                self.violate(InvariantViolation::Lost { sender, seq: handled });
+                return self.check().map(|_| report);
+            }
+            report.sent += sent;
+            report.handled += handled;
+            report.dropped += sent - handled;
+        }
+        Ok(report)
+    }
+
+    fn violate(&self, violation: InvariantViolation) {
+        self.violation.lock().unwrap().get_or_insert(violation);
+    }
+}
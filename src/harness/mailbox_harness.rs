@@ -0,0 +1,366 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_config::RestartPolicy;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_state::ActorState;
+use crate::actor::context::ActorContext;
+use crate::actor::executor::ExecutorTrait;
+use crate::actor::handler::Handler;
+use crate::config::tyractorsaur_config::TyractorsaurConfig;
+use crate::harness::invariant_checker::{HarnessReport, InvariantChecker, InvariantViolation};
+use crate::message::actor_message::ActorMessage;
+use crate::system::actor_system::ActorSystem;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::yield_now;
+use std::time::{Duration, Instant};
+
+/// A single step of a [MailboxHarness](./struct.MailboxHarness.html) script, executed by one of its threads
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HarnessOp {
+    /// sends the next message of the thread
+    Send,
+    /// sends the next message of the thread, its handler panics afterwards
+    SendPanicking,
+    /// sends an `ActorStopMessage`
+    Stop,
+    /// takes the Actor from the pool queue and executes it like a pool worker, up to its message throughput
+    ///
+    /// If the Actor goes to sleep, it is held by the thread until [CompleteSleep](#variant.CompleteSleep)
+    Handle,
+    /// hands a held Actor over to the sleeping Actors
+    CompleteSleep,
+    /// processes a single pending wakeup, like the wakeup manager
+    Wake,
+}
+
+impl HarnessOp {
+    /// Maps a single input byte to the executing thread and the operation
+    ///
+    /// Stops are rare, so that most scripts keep the Actor alive for a while
+    pub fn decode(byte: u8, threads: usize) -> (usize, HarnessOp) {
+        let thread = (byte >> 4) as usize % threads.max(1);
+        let op = match byte & 0x0f {
+            0..=4 => HarnessOp::Send,
+            5..=8 => HarnessOp::Handle,
+            9..=11 => HarnessOp::Wake,
+            12 | 13 => HarnessOp::CompleteSleep,
+            14 => HarnessOp::SendPanicking,
+            _ if byte == 0xff => HarnessOp::Stop,
+            _ => HarnessOp::Send,
+        };
+        (thread, op)
+    }
+}
+
+/// Settings of a [MailboxHarness](./struct.MailboxHarness.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarnessConfig {
+    /// number of real threads, every thread is a separate sender
+    pub threads: usize,
+    pub message_throughput: usize,
+    /// applied when a [SendPanicking](./enum.HarnessOp.html#variant.SendPanicking) message is handled
+    pub restart_policy: RestartPolicy,
+    /// upper bound of single threaded steps after all scripts finished, before the run counts as not quiescent
+    pub max_drain_steps: usize,
+}
+
+impl Default for HarnessConfig {
+    fn default() -> Self {
+        Self {
+            threads: 4,
+            message_throughput: 3,
+            restart_policy: RestartPolicy::Always,
+            max_drain_steps: 10_000,
+        }
+    }
+}
+
+struct ProbeMessage {
+    sender: usize,
+    seq: u64,
+    panic: bool,
+}
+
+impl ActorMessage for ProbeMessage {}
+
+struct Probe {
+    checker: Arc<InvariantChecker>,
+    crashes: bool,
+}
+
+impl Actor for Probe {}
+
+impl Handler<ProbeMessage> for Probe {
+    fn handle(&mut self, msg: ProbeMessage, context: &ActorContext<Self>) {
+        self.checker.begin_handle(msg.sender, msg.seq);
+        yield_now();
+        self.checker.end_handle();
+        if msg.panic {
+            // the stop overtakes queued messages, a panic while they are drained ends the Actor regardless of its restart policy
+            if self.crashes || context.actor_ref.get_mailbox().is_stopped() {
+                self.checker.record_crashed();
+            }
+            panic!("harness probe panicked on purpose");
+        }
+    }
+}
+
+struct ProbeFactory {
+    checker: Arc<InvariantChecker>,
+    crashes: bool,
+}
+
+impl ActorFactory<Probe> for ProbeFactory {
+    fn new_actor(&self, _context: ActorContext<Probe>) -> Probe {
+        Probe {
+            checker: self.checker.clone(),
+            crashes: self.crashes,
+        }
+    }
+}
+
+type Executor = Arc<RwLock<dyn ExecutorTrait>>;
+
+/// Drives a real mailbox, Executor, wakeup manager and pool queue through a script of [HarnessOp](./enum.HarnessOp.html)s on multiple real threads
+///
+/// Nothing is executed by the system itself, the threads act as senders, pool workers and wakeup manager, with yield points in between the protocol steps.
+/// Idle Actors go to sleep immediately, so every empty mailbox exercises the sleep and wakeup path.
+///
+/// Invariants are checked by an [InvariantChecker](./struct.InvariantChecker.html) after every step and once all scripts are done and the system became idle.
+///
+/// `cargo fuzz run mailbox` feeds arbitrary scripts through [run_bytes](#method.run_bytes), see `fuzz/`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::testing::*;
+///
+/// let harness = MailboxHarness::new(HarnessConfig::default());
+/// let script = vec![
+///     vec![HarnessOp::Send, HarnessOp::Wake, HarnessOp::Handle, HarnessOp::CompleteSleep],
+///     vec![HarnessOp::Send, HarnessOp::SendPanicking, HarnessOp::Handle, HarnessOp::Wake],
+/// ];
+/// let report = harness.run(script).unwrap();
+/// assert_eq!(report.sent, 3);
+/// assert_eq!(report.handled, 3);
+/// ```
+///
+/// Bounded property test over generated scripts, short scripts keep later messages from hiding a lost wakeup:
+///
+/// ```rust
+/// use tyractorsaur::prelude::RestartPolicy;
+/// use tyractorsaur::testing::*;
+///
+/// for restart_policy in [RestartPolicy::Always, RestartPolicy::Never] {
+///     for threads in [1, 2, 4] {
+///         let harness = MailboxHarness::new(HarnessConfig { threads, restart_policy, ..HarnessConfig::default() });
+///         for seed in 0..300 {
+///             let script = harness.generate_script(seed, 8 + seed as usize % 56);
+///             if let Err(violation) = harness.run(script) {
+///                 panic!("{:?} {} threads seed {} violated {:?}", restart_policy, threads, seed, violation);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub struct MailboxHarness {
+    config: HarnessConfig,
+}
+
+impl MailboxHarness {
+    pub fn new(config: HarnessConfig) -> Self {
+        Self { config }
+    }
+
+    /// Splits arbitrary input into one script per thread, see [HarnessOp.decode](./enum.HarnessOp.html#method.decode)
+    pub fn script_from_bytes(&self, bytes: &[u8]) -> Vec<Vec<HarnessOp>> {
+        let threads = self.config.threads.max(1);
+        let mut script = vec![Vec::new(); threads];
+        for byte in bytes {
+            let (thread, op) = HarnessOp::decode(*byte, threads);
+            script[thread].push(op);
+        }
+        script
+    }
+
+    /// Deterministic script of `len` operations for the given seed
+    pub fn generate_script(&self, seed: u64, len: usize) -> Vec<Vec<HarnessOp>> {
+        // xorshift64*, the seed must not be zero
+        let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+        let bytes: Vec<u8> = (0..len)
+            .map(|_| {
+                state ^= state >> 12;
+                state ^= state << 25;
+                state ^= state >> 27;
+                (state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 56) as u8
+            })
+            .collect();
+        self.script_from_bytes(&bytes)
+    }
+
+    pub fn run_bytes(&self, bytes: &[u8]) -> Result<HarnessReport, InvariantViolation> {
+        self.run(self.script_from_bytes(bytes))
+    }
+
+    /// Executes every script on its own thread, scripts beyond the configured number of threads are ignored
+    pub fn run(&self, script: Vec<Vec<HarnessOp>>) -> Result<HarnessReport, InvariantViolation> {
+        let threads = self.config.threads.max(1);
+        let system = ActorSystem::new_for_testing(TyractorsaurConfig::new().unwrap(), Duration::from_secs(0));
+        let checker = Arc::new(InvariantChecker::new(threads));
+        let actor = system
+            .builder()
+            .set_restart_policy(self.config.restart_policy)
+            .set_message_throughput(self.config.message_throughput)
+            .set_mailbox_unbounded()
+            .spawn(
+                "probe",
+                ProbeFactory {
+                    checker: checker.clone(),
+                    crashes: self.config.restart_policy == RestartPolicy::Never,
+                },
+            )
+            .unwrap();
+        let worker = Worker {
+            system: &system,
+            checker: &checker,
+            deduplication: Mutex::new(HashMap::new()),
+        };
+        let aborted = AtomicBool::new(false);
+
+        std::thread::scope(|scope| {
+            for (sender, ops) in script.into_iter().take(threads).enumerate() {
+                let actor = actor.clone();
+                let worker = &worker;
+                let aborted = &aborted;
+                scope.spawn(move || {
+                    let mut held = None;
+                    for op in ops {
+                        if aborted.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        match op {
+                            HarnessOp::Send | HarnessOp::SendPanicking => {
+                                let seq = worker.checker.record_sent(sender);
+                                let panic = op == HarnessOp::SendPanicking;
+                                let _ = actor.send(ProbeMessage { sender, seq, panic });
+                                worker.checker.record_enqueued(sender, seq);
+                            }
+                            HarnessOp::Stop => {
+                                worker.checker.record_stop_requested();
+                                actor.stop();
+                            }
+                            HarnessOp::Handle => {
+                                if held.is_none() {
+                                    held = worker.handle();
+                                }
+                            }
+                            HarnessOp::CompleteSleep => {
+                                if let Some(executor) = held.take() {
+                                    worker.complete_sleep(executor);
+                                }
+                            }
+                            HarnessOp::Wake => {
+                                worker.wake();
+                            }
+                        }
+                        if worker.checker.check().is_err() {
+                            aborted.store(true, Ordering::Relaxed);
+                        }
+                        yield_now();
+                    }
+                    if let Some(executor) = held {
+                        worker.complete_sleep(executor);
+                    }
+                });
+            }
+        });
+        worker.checker.check()?;
+
+        // single threaded until all wakeups are processed and the Actor is either sleeping or stopped
+        let mut is_quiescent = false;
+        for _ in 0..self.config.max_drain_steps {
+            if worker.has_runnable() {
+                if let Some(executor) = worker.handle() {
+                    worker.complete_sleep(executor);
+                }
+                continue;
+            }
+            if !worker.wake() {
+                is_quiescent = true;
+                break;
+            }
+        }
+        if !is_quiescent {
+            checker.record_not_quiescent();
+        }
+        checker.check_quiescent()
+    }
+}
+
+struct Worker<'a> {
+    system: &'a ActorSystem,
+    checker: &'a InvariantChecker,
+    deduplication: Mutex<HashMap<ActorAddress, Instant>>,
+}
+
+impl Worker<'_> {
+    fn has_runnable(&self) -> bool {
+        self.system.get_thread_pool_manager().get_pool_queue("default").len() > 0
+    }
+
+    /// Mirrors a pool worker, returns the Actor if it is on its way to sleep
+    fn handle(&self) -> Option<Executor> {
+        let queue = self.system.get_thread_pool_manager().get_pool_queue("default");
+        let executor = queue.try_pop()?;
+        self.checker.record_scheduled();
+        let mut actor_state = ActorState::Running;
+        {
+            let mut actor_ref = executor.write().unwrap();
+            for _j in 0..actor_ref.get_message_budget() {
+                actor_state = actor_ref.handle(false);
+                if actor_state != ActorState::Running {
+                    break;
+                }
+            }
+            actor_ref.end_slot();
+        }
+        match actor_state {
+            ActorState::Running => {
+                self.checker.record_descheduled();
+                queue.push(executor);
+                None
+            }
+            ActorState::Sleeping | ActorState::Suspended => Some(executor),
+            ActorState::Stopped => {
+                self.checker.record_stopped();
+                self.checker.record_descheduled();
+                let address = executor.read().unwrap().get_address();
+                let pool = self.system.get_thread_pool_manager().get_actor_pool(&address);
+                self.system.get_state().remove_actor(&address, &pool);
+                None
+            }
+        }
+    }
+
+    fn complete_sleep(&self, executor: Executor) {
+        let address = executor.read().unwrap().get_address();
+        self.checker.record_descheduled();
+        self.system.get_wakeup_manager().add_sleeping_actor(address, executor);
+    }
+
+    /// Mirrors the single threaded wakeup manager
+    fn wake(&self) -> bool {
+        let mut deduplication = self.deduplication.lock().unwrap();
+        self.system
+            .get_wakeup_manager()
+            .try_process_wakeup(
+                self.system.get_state(),
+                self.system.get_thread_pool_manager(),
+                &mut deduplication,
+            )
+    }
+}
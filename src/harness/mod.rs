@@ -0,0 +1,19 @@
+pub(crate) mod expectation;
+pub(crate) mod interaction_log;
+mod invariant_checker;
+mod mailbox_harness;
+mod orphans;
+pub(crate) mod test_actor_system;
+mod test_probe;
+mod wakeup_stress;
+
+pub mod prelude {
+    pub use crate::harness::expectation::{ActorExpectations, Expectation, VerificationFailure, VerificationReport};
+    pub use crate::harness::interaction_log::{Interaction, InteractionKind};
+    pub use crate::harness::invariant_checker::{HarnessReport, InvariantChecker, InvariantViolation};
+    pub use crate::harness::mailbox_harness::{HarnessConfig, HarnessOp, MailboxHarness};
+    pub use crate::harness::orphans::inject_orphans;
+    pub use crate::harness::test_actor_system::TestActorSystem;
+    pub use crate::harness::test_probe::{ProbeActor, RecordedMessage, TestProbe};
+    pub use crate::harness::wakeup_stress::{WakeupStress, WakeupStressConfig, WakeupStressReport};
+}
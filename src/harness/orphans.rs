@@ -0,0 +1,63 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::system::actor_system::ActorSystem;
+use std::time::{Duration, Instant};
+
+/// Creates an orphaned entry for the address in every internal registry that the integrity sweep cross-checks against the registered Actors
+///
+/// Meant for a stopped Actor, whose cleanup is skipped this way.
+/// See [ActorSystem.sweep_integrity](../prelude/struct.ActorSystem.html#method.sweep_integrity)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::testing::*;
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Worker {}
+/// impl Actor for Worker {}
+/// struct WorkerFactory {}
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+///         Worker {}
+///     }
+/// }
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.integrity_sweep_interval = Duration::from_secs(0);
+/// let actor_system = ActorSystem::new(config);
+/// let worker = actor_system.builder().spawn("worker", WorkerFactory {}).unwrap();
+/// let address = worker.get_address().clone();
+/// worker.stop();
+/// sleep(Duration::from_millis(500));
+/// inject_orphans(&actor_system, &address);
+///
+/// // registries keyed by address are only reclaimed by the second sweep that finds their orphans
+/// let first = actor_system.sweep_integrity().unwrap();
+/// assert_eq!(1, first.reclaimed.scheduled_wakeups);
+/// assert_eq!(1, first.reclaimed.activation_watchers);
+/// assert_eq!(2, first.reclaimed.total());
+/// assert_eq!(5, first.suspected);
+///
+/// let second = actor_system.sweep_integrity().unwrap();
+/// assert_eq!(1, second.reclaimed.actor_metrics);
+/// assert_eq!(1, second.reclaimed.protocols);
+/// assert_eq!(1, second.reclaimed.awaiting_release);
+/// assert_eq!(1, second.reclaimed.children);
+/// assert_eq!(1, second.reclaimed.event_subscriptions);
+/// assert_eq!(5, second.reclaimed.total());
+/// assert_eq!(0, second.suspected);
+/// assert_eq!(0, second.retained.actor_metrics);
+///
+/// assert_eq!(0, actor_system.sweep_integrity().unwrap().reclaimed.total());
+/// assert_eq!(7, actor_system.integrity_reclaimed_total().total());
+/// ```
+pub fn inject_orphans(system: &ActorSystem, address: &ActorAddress) {
+    system.get_state().inject_orphans(address);
+    system
+        .get_wakeup_manager()
+        .schedule_wakeup(address.clone(), Instant::now() + Duration::from_secs(3600));
+}
@@ -0,0 +1,568 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_state::ActorState;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::executor::ExecutorTrait;
+use crate::config::tyractorsaur_config::TyractorsaurConfig;
+use crate::harness::expectation::{self, ActorExpectations, ExpectationSpec, VerificationReport};
+use crate::harness::interaction_log::{Interaction, InteractionKind, InteractionLog};
+use crate::system::actor_system::ActorSystem;
+use crate::system::thread_pool_manager::PoolQueue;
+use crate::system::timer_queue::TimerQueue;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::panic::UnwindSafe;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+type Executor = Arc<RwLock<dyn ExecutorTrait>>;
+
+/// Scheduled wakeups and timers, due once the wall clock plus `advanced` passed their deadline
+#[derive(Default)]
+struct VirtualClock {
+    advanced: Duration,
+    scheduled: BinaryHeap<Reverse<(Instant, ActorAddress)>>,
+    timers: TimerQueue,
+}
+
+/// ActorSystem that executes all Actors deterministically on the calling thread and records their interactions
+///
+/// Nothing is executed until [pump](#method.pump), which runs a single handler at a time until all mailboxes are empty.
+/// Every send is attributed to the Actor whose handler sent it, or to the test itself.
+///
+/// Expectations are registered through [expect](#method.expect) before pumping and checked all at once by [verify](#method.verify).
+/// An Actor with at least one send expectation must not send anything that none of them covers.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::testing::*;
+///
+/// #[derive(Clone)]
+/// struct PlaceOrder { amount: u32 }
+/// impl ActorMessage for PlaceOrder {}
+///
+/// #[derive(Clone)]
+/// struct ChargeCard { amount: u32 }
+/// impl ActorMessage for ChargeCard {}
+///
+/// struct CardCharged {}
+/// impl ActorMessage for CardCharged {}
+///
+/// struct Ship {}
+/// impl ActorMessage for Ship {}
+///
+/// struct Stub {
+///     orchestrator: Option<ActorWrapper<Orchestrator>>,
+/// }
+/// impl Actor for Stub {}
+/// impl Handler<ChargeCard> for Stub {
+///     fn handle(&mut self, _msg: ChargeCard, _context: &ActorContext<Self>) {
+///         if let Some(orchestrator) = &self.orchestrator {
+///             orchestrator.send(CardCharged {});
+///         }
+///     }
+/// }
+/// impl Handler<Ship> for Stub {
+///     fn handle(&mut self, _msg: Ship, _context: &ActorContext<Self>) {}
+/// }
+///
+/// struct StubFactory {
+///     orchestrator: Option<ActorWrapper<Orchestrator>>,
+/// }
+/// impl ActorFactory<Stub> for StubFactory {
+///     fn new_actor(&self, _context: ActorContext<Stub>) -> Stub {
+///         Stub { orchestrator: self.orchestrator.clone() }
+///     }
+/// }
+///
+/// struct Orchestrator {
+///     payments: Option<ActorWrapper<Stub>>,
+///     shipping: Option<ActorWrapper<Stub>>,
+///     // a broken orchestrator charges twice and ships before the card has been charged
+///     is_broken: bool,
+/// }
+/// impl Actor for Orchestrator {}
+/// impl Handler<PlaceOrder> for Orchestrator {
+///     fn handle(&mut self, msg: PlaceOrder, _context: &ActorContext<Self>) {
+///         let payments = self.payments.as_ref().unwrap();
+///         payments.send(ChargeCard { amount: msg.amount });
+///         if self.is_broken {
+///             payments.send(ChargeCard { amount: msg.amount });
+///             self.shipping.as_ref().unwrap().send(Ship {});
+///         }
+///     }
+/// }
+/// impl Handler<CardCharged> for Orchestrator {
+///     fn handle(&mut self, _msg: CardCharged, _context: &ActorContext<Self>) {
+///         if !self.is_broken {
+///             self.shipping.as_ref().unwrap().send(Ship {});
+///         }
+///     }
+/// }
+/// struct Wiring {
+///     payments: ActorWrapper<Stub>,
+///     shipping: ActorWrapper<Stub>,
+/// }
+/// impl Handler<Wiring> for Orchestrator {
+///     fn handle(&mut self, msg: Wiring, _context: &ActorContext<Self>) {
+///         self.payments = Some(msg.payments);
+///         self.shipping = Some(msg.shipping);
+///     }
+/// }
+/// impl ActorMessage for Wiring {}
+///
+/// struct OrchestratorFactory { is_broken: bool }
+/// impl ActorFactory<Orchestrator> for OrchestratorFactory {
+///     fn new_actor(&self, _context: ActorContext<Orchestrator>) -> Orchestrator {
+///         Orchestrator { payments: None, shipping: None, is_broken: self.is_broken }
+///     }
+/// }
+///
+/// fn run(is_broken: bool) -> Result<(), VerificationReport> {
+///     let testkit = TestActorSystem::new(TyractorsaurConfig::new().unwrap());
+///     let orchestrator = testkit.builder().spawn("orchestrator", OrchestratorFactory { is_broken }).unwrap();
+///     let payments = testkit
+///         .builder()
+///         .spawn("payments", StubFactory { orchestrator: Some(orchestrator.clone()) })
+///         .unwrap();
+///     let shipping = testkit.builder().spawn("shipping", StubFactory { orchestrator: None }).unwrap();
+///     orchestrator.send(Wiring { payments: payments.clone(), shipping: shipping.clone() });
+///     testkit.pump();
+///
+///     testkit
+///         .expect(&orchestrator)
+///         .to_send::<ChargeCard>()
+///         .to(&payments)
+///         .matching(|msg| msg.amount == 100)
+///         .times(1);
+///     let ship = testkit.expect(&orchestrator).to_send::<Ship>().to(&shipping).times(1);
+///     testkit.expect(&payments).to_handle::<ChargeCard>().before(&ship);
+///     testkit.expect(&payments).to_send::<CardCharged>().to(&orchestrator).times(1).before(&ship);
+///     testkit.expect(&shipping).to_send_nothing_to(&payments);
+///
+///     orchestrator.send(PlaceOrder { amount: 100 });
+///     assert!(testkit.pump() > 0);
+///     testkit.verify()
+/// }
+///
+/// run(false).unwrap();
+///
+/// let report = run(true).unwrap_err();
+/// assert_eq!(
+///     vec![
+///         VerificationFailure::Unmatched {
+///             expectation: String::from("orchestrator sends ChargeCard to payments matching the predicate exactly once"),
+///             actual: 2,
+///         },
+///         VerificationFailure::Unmatched {
+///             expectation: String::from("payments sends CardCharged to orchestrator exactly once"),
+///             actual: 2,
+///         },
+///         VerificationFailure::OutOfOrder {
+///             expectation: String::from("payments handles ChargeCard at least once"),
+///             other: String::from("orchestrator sends Ship to shipping exactly once"),
+///             at: 9,
+///             other_at: 6,
+///         },
+///         VerificationFailure::OutOfOrder {
+///             expectation: String::from("payments sends CardCharged to orchestrator exactly once"),
+///             other: String::from("orchestrator sends Ship to shipping exactly once"),
+///             at: 10,
+///             other_at: 6,
+///         },
+///     ],
+///     report.failures
+/// );
+/// let report = report.to_string();
+/// assert!(report.contains("expected payments handles ChargeCard at least once before orchestrator sends Ship to shipping exactly once, but #9 came after #6"));
+/// assert!(report.contains("  #6 orchestrator -> shipping Ship\n"));
+/// ```
+///
+/// Unexpected interactions and dead letters:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::testing::*;
+///
+/// struct Ping {}
+/// impl ActorMessage for Ping {}
+///
+/// struct Pinger { target: ActorWrapper<Sink> }
+/// impl Actor for Pinger {}
+/// impl Handler<Ping> for Pinger {
+///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+///         self.target.send(Ping {});
+///         self.target.send(Ping {});
+///     }
+/// }
+///
+/// struct Sink {}
+/// impl Actor for Sink {}
+/// impl Handler<Ping> for Sink {
+///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+/// }
+///
+/// struct SinkFactory {}
+/// impl ActorFactory<Sink> for SinkFactory {
+///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+///         Sink {}
+///     }
+/// }
+///
+/// struct PingerFactory { target: ActorWrapper<Sink> }
+/// impl ActorFactory<Pinger> for PingerFactory {
+///     fn new_actor(&self, _context: ActorContext<Pinger>) -> Pinger {
+///         Pinger { target: self.target.clone() }
+///     }
+/// }
+///
+/// let testkit = TestActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let sink = testkit.builder().spawn("sink", SinkFactory {}).unwrap();
+/// let pinger = testkit.builder().spawn("pinger", PingerFactory { target: sink.clone() }).unwrap();
+/// sink.stop();
+/// testkit.pump();
+///
+/// testkit.expect(&pinger).to_send::<Ping>().to(&sink).as_dead_letter().times(1);
+/// testkit.expect(&sink).to_handle::<Ping>();
+/// pinger.send(Ping {});
+/// assert_eq!(1, testkit.pump());
+///
+/// let report = testkit.verify().unwrap_err();
+/// assert_eq!(
+///     vec![
+///         VerificationFailure::Unmatched { expectation: String::from("pinger sends Ping to sink as dead letter exactly once"), actual: 2 },
+///         VerificationFailure::Unmatched { expectation: String::from("sink handles Ping at least once"), actual: 0 },
+///     ],
+///     report.failures
+/// );
+/// assert_eq!(InteractionKind::DeadLetter, report.interactions[2].kind);
+///
+/// // an Actor with send expectations must not send anything else
+/// let testkit = TestActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let sink = testkit.builder().spawn("sink", SinkFactory {}).unwrap();
+/// let other = testkit.builder().spawn("other", SinkFactory {}).unwrap();
+/// let pinger = testkit.builder().spawn("pinger", PingerFactory { target: sink.clone() }).unwrap();
+/// testkit.expect(&pinger).to_send::<Ping>().to(&other);
+/// testkit.expect(&sink).to_send_nothing_to(&pinger);
+/// pinger.send(Ping {});
+/// testkit.pump();
+/// assert_eq!(
+///     vec![
+///         VerificationFailure::Unmatched { expectation: String::from("pinger sends Ping to other at least once"), actual: 0 },
+///         VerificationFailure::Unexpected { interaction: String::from("#2 pinger -> sink Ping") },
+///         VerificationFailure::Unexpected { interaction: String::from("#3 pinger -> sink Ping") },
+///     ],
+///     testkit.verify().unwrap_err().failures
+/// );
+/// ```
+pub struct TestActorSystem {
+    system: ActorSystem,
+    interactions: Arc<InteractionLog>,
+    expectations: Mutex<Vec<ExpectationSpec>>,
+    deduplication: Mutex<HashMap<ActorAddress, Instant>>,
+    clock: Mutex<VirtualClock>,
+}
+
+impl TestActorSystem {
+    pub fn new(config: TyractorsaurConfig) -> Self {
+        let interactions = Arc::new(InteractionLog::new());
+        Self {
+            system: ActorSystem::new_for_interaction_testing(config, interactions.clone()),
+            interactions,
+            expectations: Mutex::new(Vec::new()),
+            deduplication: Mutex::new(HashMap::new()),
+            clock: Mutex::new(VirtualClock::default()),
+        }
+    }
+
+    pub fn builder<A>(&self) -> ActorBuilder<A>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.system.builder()
+    }
+
+    /// The underlying ActorSystem, Actors are only executed through [pump](#method.pump)
+    pub fn get_system(&self) -> &ActorSystem {
+        &self.system
+    }
+
+    /// Executes Actors on the calling thread until all mailboxes are empty, returns the amount of handled messages
+    ///
+    /// Delivers the timers that are due, see [advance_time](#method.advance_time).
+    /// Does not return as long as Actors keep sending each other messages
+    pub fn pump(&self) -> usize {
+        let handled = self.interactions.count(InteractionKind::Handled);
+        let mut pools: Vec<String> = self.system.get_config().thread_pool.config.keys().cloned().collect();
+        pools.sort();
+        loop {
+            self.fire_due();
+            if self.handle_runnable(&pools) {
+                continue;
+            }
+            if !self.wake() {
+                break;
+            }
+        }
+        self.interactions.count(InteractionKind::Handled) - handled
+    }
+
+    /// Moves the clock of timers and scheduled wakeups forward, then [pumps](#method.pump) and returns the amount of handled messages
+    ///
+    /// The clock is the wall clock plus all advanced durations, Actors themselves still see the wall clock
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::testing::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// struct Start {}
+    /// impl ActorMessage for Start {}
+    /// struct Remind {}
+    /// impl ActorMessage for Remind {}
+    ///
+    /// struct Reminder {
+    ///     reminded: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Reminder {}
+    /// impl Handler<Start> for Reminder {
+    ///     fn handle(&mut self, _msg: Start, context: &ActorContext<Self>) {
+    ///         context.schedule_once(Duration::from_secs(60), Remind {});
+    ///     }
+    /// }
+    /// impl Handler<Remind> for Reminder {
+    ///     fn handle(&mut self, _msg: Remind, _context: &ActorContext<Self>) {
+    ///         self.reminded.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct ReminderFactory {
+    ///     reminded: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Reminder> for ReminderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Reminder>) -> Reminder {
+    ///         Reminder { reminded: self.reminded.clone() }
+    ///     }
+    /// }
+    ///
+    /// let testkit = TestActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let reminded = Arc::new(AtomicUsize::new(0));
+    /// let reminder = testkit.builder().spawn("reminder", ReminderFactory { reminded: reminded.clone() }).unwrap();
+    /// reminder.send(Start {}).unwrap();
+    /// assert_eq!(1, testkit.pump());
+    /// testkit.expect_no_messages(&reminder);
+    ///
+    /// assert_eq!(0, testkit.advance_time(Duration::from_secs(59)));
+    /// assert_eq!(0, reminded.load(Ordering::Relaxed));
+    /// assert_eq!(1, testkit.advance_time(Duration::from_secs(1)));
+    /// assert_eq!(1, reminded.load(Ordering::Relaxed));
+    /// ```
+    pub fn advance_time(&self, duration: Duration) -> usize {
+        self.clock.lock().unwrap().advanced += duration;
+        self.pump()
+    }
+
+    /// Handles the next message of `actor` only, all other Actors keep their messages
+    ///
+    /// Returns `false` if `actor` had no message to handle
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::testing::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// struct Count {}
+    /// impl ActorMessage for Count {}
+    ///
+    /// struct Counter {
+    ///     count: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Counter {}
+    /// impl Handler<Count> for Counter {
+    ///     fn handle(&mut self, _msg: Count, _context: &ActorContext<Self>) {
+    ///         self.count.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct CounterFactory {
+    ///     count: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { count: self.count.clone() }
+    ///     }
+    /// }
+    ///
+    /// let testkit = TestActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (first_count, second_count) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    /// let first = testkit.builder().spawn("first", CounterFactory { count: first_count.clone() }).unwrap();
+    /// let second = testkit.builder().spawn("second", CounterFactory { count: second_count.clone() }).unwrap();
+    /// for _ in 0..2 {
+    ///     first.send(Count {}).unwrap();
+    ///     second.send(Count {}).unwrap();
+    /// }
+    ///
+    /// assert!(testkit.run_one(&second));
+    /// assert_eq!(0, first_count.load(Ordering::Relaxed));
+    /// assert_eq!(1, second_count.load(Ordering::Relaxed));
+    /// assert!(testkit.run_one(&second));
+    /// assert!(!testkit.run_one(&second));
+    /// testkit.expect_no_messages(&second);
+    ///
+    /// assert_eq!(2, testkit.pump());
+    /// assert_eq!(2, first_count.load(Ordering::Relaxed));
+    /// ```
+    pub fn run_one<A>(&self, actor: &ActorWrapper<A>) -> bool
+    where
+        A: Actor + UnwindSafe,
+    {
+        while self.wake() {}
+        let address = actor.get_address();
+        let queue = self.system.get_thread_pool_manager().get_actor_queue(address);
+        let mut others = Vec::new();
+        let mut found = None;
+        while let Some(executor) = queue.try_pop() {
+            if found.is_none() && executor.read().unwrap().get_address() == *address {
+                found = Some(executor);
+            } else {
+                others.push(executor);
+            }
+        }
+        // keeps the order of all other Actors
+        for executor in others {
+            queue.push(executor);
+        }
+        let executor = match found {
+            Some(executor) => executor,
+            None => return false,
+        };
+        let handled = self.interactions.count(InteractionKind::Handled);
+        let actor_state = {
+            let mut actor_ref = executor.write().unwrap();
+            let actor_state = actor_ref.handle(false);
+            actor_ref.end_slot();
+            actor_state
+        };
+        self.reschedule(&queue, executor, actor_state);
+        self.interactions.count(InteractionKind::Handled) > handled
+    }
+
+    /// Panics if messages are waiting in the mailbox of `actor`
+    pub fn expect_no_messages<A>(&self, actor: &ActorWrapper<A>)
+    where
+        A: Actor + UnwindSafe,
+    {
+        let waiting = actor.get_mailbox().len();
+        assert!(waiting == 0, "expected no messages for {}, but {} are waiting", actor.get_address().actor, waiting);
+    }
+
+    /// Starts the expectations on `actor`, see [ActorExpectations](./struct.ActorExpectations.html)
+    pub fn expect<A>(&self, actor: &ActorWrapper<A>) -> ActorExpectations<'_>
+    where
+        A: Actor + UnwindSafe,
+    {
+        ActorExpectations::new(self, actor.get_address().clone())
+    }
+
+    /// Checks all expectations, reports every failure together with the complete interaction log
+    pub fn verify(&self) -> Result<(), VerificationReport> {
+        expectation::verify(&self.expectations.lock().unwrap(), self.interactions.get_interactions())
+    }
+
+    /// All sends and handled messages so far, oldest first
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.get_interactions()
+    }
+
+    pub(crate) fn add_expectation(&self, spec: ExpectationSpec) -> usize {
+        let mut expectations = self.expectations.lock().unwrap();
+        expectations.push(spec);
+        expectations.len() - 1
+    }
+
+    pub(crate) fn update_expectation(&self, index: usize, f: impl FnOnce(&mut ExpectationSpec)) {
+        f(&mut self.expectations.lock().unwrap()[index]);
+    }
+
+    pub(crate) fn get_interaction_log(&self) -> &InteractionLog {
+        &self.interactions
+    }
+
+    /// Executes a single Actor of the first pool that has one, like a pool worker
+    fn handle_runnable(&self, pools: &[String]) -> bool {
+        let manager = self.system.get_thread_pool_manager();
+        let (queue, executor): (_, Executor) = match pools.iter().find_map(|pool| {
+            let queue = manager.get_pool_queue(pool);
+            queue.try_pop().map(|executor| (queue, executor))
+        }) {
+            Some(runnable) => runnable,
+            None => return false,
+        };
+        let mut actor_state = ActorState::Running;
+        {
+            let mut actor_ref = executor.write().unwrap();
+            for _j in 0..actor_ref.get_message_budget() {
+                actor_state = actor_ref.handle(false);
+                if actor_state != ActorState::Running {
+                    break;
+                }
+            }
+            actor_ref.end_slot();
+        }
+        self.reschedule(&queue, executor, actor_state);
+        true
+    }
+
+    /// Hands the Actor back after its slot, like a pool worker
+    fn reschedule(&self, queue: &PoolQueue, executor: Executor, actor_state: ActorState) {
+        let address = executor.read().unwrap().get_address();
+        match actor_state {
+            ActorState::Running => queue.push(executor),
+            ActorState::Sleeping | ActorState::Suspended => {
+                self.system.get_wakeup_manager().add_sleeping_actor(address, executor)
+            }
+            ActorState::Stopped => {
+                let pool = self.system.get_thread_pool_manager().get_actor_pool(&address);
+                self.system.get_state().remove_actor(&address, &pool)
+            }
+        }
+    }
+
+    /// Delivers the timers and scheduled wakeups that are due according to [advance_time](#method.advance_time)
+    fn fire_due(&self) {
+        let mut clock = self.clock.lock().unwrap();
+        let now = Instant::now() + clock.advanced;
+        let VirtualClock { scheduled, timers, .. } = &mut *clock;
+        self.system.get_wakeup_manager().process_due(
+            now,
+            scheduled,
+            timers,
+            self.system.get_state(),
+            self.system.get_thread_pool_manager(),
+        );
+    }
+
+    /// Processes a single pending wakeup, like the wakeup manager
+    fn wake(&self) -> bool {
+        let mut deduplication = self.deduplication.lock().unwrap();
+        self.system.get_wakeup_manager().try_process_wakeup(
+            self.system.get_state(),
+            self.system.get_thread_pool_manager(),
+            &mut deduplication,
+        )
+    }
+}
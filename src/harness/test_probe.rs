@@ -0,0 +1,258 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::control_flow::{ActorResult, ControlFlowHandler, ControlFlowMessage};
+use crate::system::actor_system::ActorSystem;
+use std::any::{type_name, Any};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// names of probes are never reused within the process
+static NEXT_PROBE: AtomicUsize = AtomicUsize::new(0);
+
+/// A message received by a [TestProbe](./struct.TestProbe.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecordedMessage {
+    pub type_name: &'static str,
+    pub received_at: Instant,
+}
+
+struct PendingMessage {
+    type_name: &'static str,
+    msg: Box<dyn Any + Send>,
+}
+
+#[derive(Default)]
+struct ProbeQueue {
+    /// received, but not yet taken by an expectation
+    pending: VecDeque<PendingMessage>,
+    received: Vec<RecordedMessage>,
+}
+
+#[derive(Default)]
+struct ProbeState {
+    queue: Mutex<ProbeQueue>,
+    arrived: Condvar,
+}
+
+/// Actor behind a [TestProbe](./struct.TestProbe.html), it handles every [ControlFlowMessage](../prelude/trait.ControlFlowMessage.html)
+///
+/// A generic `Handler<M>` would conflict with the `Handler` of every [ControlFlowHandler](../prelude/trait.ControlFlowHandler.html),
+/// messages sent to a probe therefore need the `ControlFlowMessage` marker, which does not change how any other Actor handles them
+pub struct ProbeActor {
+    context: ActorContext<Self>,
+    state: Arc<ProbeState>,
+}
+
+impl Actor for ProbeActor {
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl<M> ControlFlowHandler<M> for ProbeActor
+where
+    M: ControlFlowMessage + 'static,
+{
+    fn handle_flow(&mut self, msg: M, _context: &ActorContext<Self>) -> ActorResult {
+        let mut queue = self.state.queue.lock().unwrap();
+        queue.received.push(RecordedMessage {
+            type_name: type_name::<M>(),
+            received_at: Instant::now(),
+        });
+        queue.pending.push_back(PendingMessage {
+            type_name: type_name::<M>(),
+            msg: Box::new(msg),
+        });
+        self.state.arrived.notify_all();
+        ActorResult::Ok
+    }
+}
+
+struct ProbeActorFactory {
+    state: Arc<ProbeState>,
+}
+
+impl ActorFactory<ProbeActor> for ProbeActorFactory {
+    fn new_actor(&self, context: ActorContext<ProbeActor>) -> ProbeActor {
+        ProbeActor {
+            context,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Records everything that is sent to its Actor, so that tests can assert on what a collaborator received
+///
+/// Expectations take the oldest matching message, messages of other types stay pending for later expectations.
+/// Failed expectations panic with the type names of all pending messages. The Actor is stopped together with the ActorSystem
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::*;
+/// use tyractorsaur::testing::*;
+/// use std::panic::{catch_unwind, AssertUnwindSafe};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Charge { amount: u32 }
+/// impl ActorMessage for Charge {}
+/// impl FallibleMessage for Charge {}
+/// impl ControlFlowMessage for Charge {}
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Ship { order: u32 }
+/// impl ActorMessage for Ship {}
+/// impl FallibleMessage for Ship {}
+/// impl ControlFlowMessage for Ship {}
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let timeout = Duration::from_secs(1);
+///
+/// // expectations can be met in a different order than the messages arrived
+/// let probe = TestProbe::new(&actor_system);
+/// let payments = probe.wrapper_for::<Charge>();
+/// payments.send(Charge { amount: 10 }).unwrap();
+/// probe.wrapper_for::<Ship>().send(Ship { order: 1 }).unwrap();
+/// payments.send(Charge { amount: 20 }).unwrap();
+/// assert_eq!(Ship { order: 1 }, probe.expect_msg::<Ship>(timeout));
+/// assert_eq!(Charge { amount: 20 }, probe.expect_msg_matching(timeout, |charge: &Charge| charge.amount > 10));
+/// assert_eq!(Charge { amount: 10 }, probe.expect_msg::<Charge>(timeout));
+/// probe.expect_no_msg(Duration::from_millis(50));
+/// let received = probe.received();
+/// assert_eq!(3, received.len());
+/// assert!(received[1].type_name.ends_with("Ship"));
+/// assert!(received[0].received_at <= received[2].received_at);
+///
+/// // expectations fail once their timeout expired
+/// let expired = catch_unwind(AssertUnwindSafe(|| probe.expect_msg::<Ship>(Duration::from_millis(50))));
+/// assert!(expired.is_err());
+/// payments.send(Charge { amount: 30 }).unwrap();
+/// let unexpected = catch_unwind(AssertUnwindSafe(|| probe.expect_no_msg(Duration::from_millis(50))));
+/// assert!(unexpected.is_err());
+///
+/// // probes behind a router, with bounded mailboxes
+/// let router = actor_system.builder().spawn("payments", RoundRobinRouterFactory::new()).unwrap();
+/// let first = TestProbe::with_builder(actor_system.builder().set_mailbox_size(2));
+/// let second = TestProbe::with_builder(actor_system.builder().set_mailbox_size(2));
+/// router.send(AddActorMessage::new(first.wrapper_for::<Charge>())).unwrap();
+/// router.send(AddActorMessage::new(second.wrapper_for::<Charge>())).unwrap();
+/// for amount in 0..20 {
+///     router.send(RouterMessage::new(Charge { amount })).unwrap();
+/// }
+/// // every probe receives every second message, in the order they have been routed
+/// let amounts = |probe: &TestProbe| -> Vec<u32> { (0..10).map(|_| probe.expect_msg::<Charge>(timeout).amount).collect() };
+/// let (first_amounts, second_amounts) = (amounts(&first), amounts(&second));
+/// assert!(first_amounts.windows(2).all(|pair| pair[1] == pair[0] + 2));
+/// assert!(second_amounts.windows(2).all(|pair| pair[1] == pair[0] + 2));
+/// assert_eq!(1, first_amounts[0] + second_amounts[0]);
+/// first.expect_no_msg(Duration::from_millis(50));
+/// second.expect_no_msg(Duration::from_millis(50));
+///
+/// // probes stop together with the system
+/// actor_system.stop(Duration::from_secs(1));
+/// assert_eq!(0, actor_system.await_shutdown());
+/// assert_eq!(0, actor_system.get_actor_count());
+/// ```
+pub struct TestProbe {
+    wrapper: ActorWrapper<ProbeActor>,
+    state: Arc<ProbeState>,
+}
+
+impl TestProbe {
+    /// Spawns the Actor of the probe with the default settings of `actor_system`
+    pub fn new(actor_system: &ActorSystem) -> Self {
+        Self::with_builder(actor_system.builder())
+    }
+
+    /// Spawns the Actor of the probe with the settings of `builder`, for example a bounded or unbounded mailbox
+    pub fn with_builder(builder: ActorBuilder<ProbeActor>) -> Self {
+        let state = Arc::new(ProbeState::default());
+        let name = format!("test-probe-{}", NEXT_PROBE.fetch_add(1, Ordering::Relaxed));
+        let wrapper = builder
+            .spawn(name, ProbeActorFactory { state: state.clone() })
+            .expect("the ActorSystem is stopping");
+        Self { wrapper, state }
+    }
+
+    /// Handle to the Actor of the probe, `M` only asserts that the probe accepts it
+    pub fn wrapper_for<M>(&self) -> ActorWrapper<ProbeActor>
+    where
+        ProbeActor: Handler<M>,
+        M: ControlFlowMessage + 'static,
+    {
+        self.wrapper.clone()
+    }
+
+    /// Takes the oldest pending `M`, panics if none arrived within `timeout`
+    pub fn expect_msg<M>(&self, timeout: Duration) -> M
+    where
+        M: ControlFlowMessage + 'static,
+    {
+        self.expect_msg_matching(timeout, |_: &M| true)
+    }
+
+    /// Takes the oldest pending `M` that matches `predicate`, panics if none arrived within `timeout`
+    pub fn expect_msg_matching<M, F>(&self, timeout: Duration, predicate: F) -> M
+    where
+        M: ControlFlowMessage + 'static,
+        F: Fn(&M) -> bool,
+    {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.state.queue.lock().unwrap();
+        loop {
+            let position = queue
+                .pending
+                .iter()
+                .position(|pending| pending.msg.downcast_ref::<M>().is_some_and(&predicate));
+            if let Some(position) = position {
+                let pending = queue.pending.remove(position).unwrap();
+                return *pending.msg.downcast::<M>().unwrap();
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let pending = Self::pending_types(&queue);
+                // the queue is released first, so that a caught failure does not poison the probe
+                drop(queue);
+                panic!("expected {} within {:?}, pending: {:?}", type_name::<M>(), timeout, pending);
+            }
+            queue = self.state.arrived.wait_timeout(queue, remaining).unwrap().0;
+        }
+    }
+
+    /// Panics if a message is pending or arrives within `duration`
+    pub fn expect_no_msg(&self, duration: Duration) {
+        let deadline = Instant::now() + duration;
+        let mut queue = self.state.queue.lock().unwrap();
+        loop {
+            if !queue.pending.is_empty() {
+                let pending = Self::pending_types(&queue);
+                drop(queue);
+                panic!("expected no message, pending: {:?}", pending);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return;
+            }
+            queue = self.state.arrived.wait_timeout(queue, remaining).unwrap().0;
+        }
+    }
+
+    /// All messages received so far in the order they have been handled, including the ones taken by expectations
+    pub fn received(&self) -> Vec<RecordedMessage> {
+        self.state.queue.lock().unwrap().received.clone()
+    }
+
+    fn pending_types(queue: &ProbeQueue) -> Vec<&'static str> {
+        queue.pending.iter().map(|pending| pending.type_name).collect()
+    }
+}
@@ -0,0 +1,180 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::config::tyractorsaur_config::TyractorsaurConfig;
+use crate::message::actor_message::ActorMessage;
+use crate::system::actor_system::ActorSystem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{sleep, yield_now};
+use std::time::{Duration, Instant};
+
+/// Settings of a [WakeupStress](./struct.WakeupStress.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WakeupStressConfig {
+    /// number of threads sending to the Actor
+    pub producers: usize,
+    /// bursts per producer, every burst has to be handled before the producer sends the next one
+    pub bursts: usize,
+    pub burst_size: usize,
+    /// upper bound of the pause between two bursts of a producer, long enough for the Actor to fall asleep in between
+    pub max_pause: Duration,
+    /// a burst that is not handled within this time counts as stalled and ends its producer
+    pub max_latency: Duration,
+}
+
+impl Default for WakeupStressConfig {
+    fn default() -> Self {
+        Self {
+            producers: 8,
+            bursts: 200,
+            burst_size: 3,
+            max_pause: Duration::from_micros(200),
+            max_latency: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Outcome of [WakeupStress.run](./struct.WakeupStress.html#method.run)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WakeupStressReport {
+    pub sent: u64,
+    pub handled: u64,
+    /// longest time between sending and handling a single message
+    pub max_latency: Duration,
+    /// bursts that have not been handled within `max_latency`
+    pub stalled: u64,
+}
+
+struct StressMessage {
+    producer: usize,
+    sent: Instant,
+}
+
+impl ActorMessage for StressMessage {}
+
+struct Latencies {
+    handled: Vec<AtomicU64>,
+    max: Mutex<Duration>,
+}
+
+struct Sink {
+    latencies: Arc<Latencies>,
+}
+
+impl Actor for Sink {}
+
+impl Handler<StressMessage> for Sink {
+    fn handle(&mut self, msg: StressMessage, _context: &ActorContext<Self>) {
+        let latency = msg.sent.elapsed();
+        let mut max = self.latencies.max.lock().unwrap();
+        *max = (*max).max(latency);
+        self.latencies.handled[msg.producer].fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+struct SinkFactory {
+    latencies: Arc<Latencies>,
+}
+
+impl ActorFactory<Sink> for SinkFactory {
+    fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+        Sink {
+            latencies: self.latencies.clone(),
+        }
+    }
+}
+
+/// Hammers a single Actor of a running system with tiny bursts from many threads, while the Actor goes to sleep as soon as its mailbox is empty
+///
+/// Unlike the [MailboxHarness](./struct.MailboxHarness.html) the real pool workers and wakeup manager are used, a lost wakeup shows up as a stalled burst
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::testing::*;
+/// use std::time::Duration;
+///
+/// let report = WakeupStress::new(WakeupStressConfig::default()).run();
+/// assert_eq!(0, report.stalled);
+/// assert_eq!(report.sent, report.handled);
+/// assert!(report.max_latency < Duration::from_secs(1));
+/// ```
+pub struct WakeupStress {
+    config: WakeupStressConfig,
+}
+
+impl WakeupStress {
+    pub fn new(config: WakeupStressConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn run(&self) -> WakeupStressReport {
+        let config = self.config;
+        let producers = config.producers.max(1);
+        let system = ActorSystem::new_with_idle_sleep_after(TyractorsaurConfig::new().unwrap(), Duration::from_secs(0));
+        let latencies = Arc::new(Latencies {
+            handled: (0..producers).map(|_| AtomicU64::new(0)).collect(),
+            max: Mutex::new(Duration::from_secs(0)),
+        });
+        let actor = system
+            .builder()
+            .set_mailbox_unbounded()
+            .spawn("wakeup-stress", SinkFactory { latencies: latencies.clone() })
+            .unwrap();
+        let sent = AtomicU64::new(0);
+        let stalled = AtomicU64::new(0);
+
+        std::thread::scope(|scope| {
+            for producer in 0..producers {
+                let actor = actor.clone();
+                let latencies = &latencies;
+                let sent = &sent;
+                let stalled = &stalled;
+                scope.spawn(move || {
+                    // xorshift64*, the seed must not be zero
+                    let mut state = (producer as u64 + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+                    let mut own_sent = 0;
+                    for _ in 0..config.bursts {
+                        for _ in 0..config.burst_size {
+                            let _ = actor.send(StressMessage {
+                                producer,
+                                sent: Instant::now(),
+                            });
+                        }
+                        own_sent += config.burst_size as u64;
+                        sent.fetch_add(config.burst_size as u64, Ordering::SeqCst);
+                        let burst_sent = Instant::now();
+                        while latencies.handled[producer].load(Ordering::SeqCst) < own_sent {
+                            if burst_sent.elapsed() >= config.max_latency {
+                                stalled.fetch_add(1, Ordering::SeqCst);
+                                return;
+                            }
+                            yield_now();
+                        }
+                        state ^= state >> 12;
+                        state ^= state << 25;
+                        state ^= state >> 27;
+                        let nanos = config.max_pause.as_nanos() as u64;
+                        if nanos > 0 {
+                            sleep(Duration::from_nanos(state.wrapping_mul(0x2545_f491_4f6c_dd1d) % nanos));
+                        }
+                    }
+                });
+            }
+        });
+
+        system.stop(Duration::from_secs(1));
+        system.await_shutdown();
+        let max_latency = *latencies.max.lock().unwrap();
+        WakeupStressReport {
+            sent: sent.into_inner(),
+            handled: latencies.handled.iter().map(|handled| handled.load(Ordering::SeqCst)).sum(),
+            max_latency,
+            stalled: stalled.into_inner(),
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::actor::journal::JournalEntry;
+use crate::http::http_bridge_ask::{HttpBridgeAsk, HttpBridgeReply};
+use crate::http::http_bridge_request::HttpBridgeRequest;
+use crate::http::http_bridge_response::HttpBridgeResponse;
+use crate::http::http_bridge_routes::HttpBridgeRoutes;
+use crate::message::actor_message::ActorMessage;
+use crate::system::actor_system::ActorSystem;
+use crossbeam_channel::bounded;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::panic::UnwindSafe;
+use std::time::{Duration, UNIX_EPOCH};
+
+type Authorizer = Box<dyn Fn(&HttpBridgeRequest) -> bool + Send + Sync>;
+type MessageSender = Box<dyn Fn(&[u8]) -> Result<(), String> + Send + Sync>;
+type MessageAsker = Box<dyn Fn(&[u8], Duration) -> Result<Option<String>, String> + Send + Sync>;
+
+const DEFAULT_ASK_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct ExposedActor {
+    address: ActorAddress,
+    metrics: ActorMetrics,
+    tags: BTreeMap<String, String>,
+    senders: HashMap<String, MessageSender>,
+    askers: HashMap<String, MessageAsker>,
+    stop: Box<dyn Fn() + Send + Sync>,
+    journal: Box<dyn Fn() -> Vec<JournalEntry> + Send + Sync>,
+    effect_ledger_len: Box<dyn Fn() -> usize + Send + Sync>,
+    attached_streams: Box<dyn Fn() -> usize + Send + Sync>,
+}
+
+/// Server-agnostic core to expose Actors as HTTP/JSON endpoints
+///
+/// The bridge does not contain an HTTP server, the web framework of choice forwards requests to [HttpBridge.handle_request](#method.handle_request)
+///
+/// Supported routes:
+///
+/// - `POST /actors/{name}/messages/{type_name}` deserializes the JSON body and sends it, answers with `202`
+/// - `POST /actors/{name}/messages/{type_name}?ask=true&timeout_ms=...` waits for the [HttpBridgeReply](./struct.HttpBridgeReply.html), answers with `200`
+/// - `GET /actors/{name}` returns the inspection report of the Actor, including its journal
+/// - `GET /actors?tag=key:value` returns the names of all Actors with matching tags
+/// - `GET /metrics` returns the Prometheus rendering of the whole system, see [ActorSystem.render_prometheus](../prelude/struct.ActorSystem.html#method.render_prometheus)
+/// - `POST /actors/{name}/stop` stops the Actor
+///
+/// Only Actors and message types that have been registered with the bridge are reachable
+pub struct HttpBridge {
+    system: ActorSystem,
+    actors: BTreeMap<String, ExposedActor>,
+    routes: HttpBridgeRoutes,
+    authorizer: Option<Authorizer>,
+}
+
+impl HttpBridge {
+    pub fn new(system: ActorSystem) -> Self {
+        Self {
+            system,
+            actors: BTreeMap::new(),
+            routes: HttpBridgeRoutes::default(),
+            authorizer: None,
+        }
+    }
+
+    pub fn set_routes(&mut self, routes: HttpBridgeRoutes) {
+        self.routes = routes;
+    }
+
+    /// Every request is passed to the authorizer before dispatch, rejected requests are answered with `403`
+    pub fn set_authorizer<F>(&mut self, authorizer: F)
+    where
+        F: Fn(&HttpBridgeRequest) -> bool + Send + Sync + 'static,
+    {
+        self.authorizer = Some(Box::new(authorizer));
+    }
+
+    /// Makes the Actor reachable through its name, without accepting any messages
+    pub fn expose<A>(&mut self, actor: &ActorWrapper<A>)
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.get_or_expose(actor);
+    }
+
+    /// Adds a tag that can be queried through `GET /actors?tag=key:value`
+    pub fn tag<A>(&mut self, actor: &ActorWrapper<A>, key: impl Into<String>, value: impl Into<String>)
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.get_or_expose(actor).tags.insert(key.into(), value.into());
+    }
+
+    /// Accepts JSON messages of type `M` under `type_name` for the given Actor
+    pub fn register_message<A, M>(&mut self, actor: &ActorWrapper<A>, type_name: impl Into<String>)
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + DeserializeOwned + 'static,
+    {
+        let wrapper = actor.clone();
+        let sender: MessageSender = Box::new(move |body| {
+            let msg: M = serde_json::from_slice(body).map_err(|e| e.to_string())?;
+            let _ = wrapper.send(msg);
+            Ok(())
+        });
+        self.get_or_expose(actor).senders.insert(type_name.into(), sender);
+    }
+
+    /// Same as [HttpBridge.register_message](#method.register_message), but additionally allows `?ask=true`
+    pub fn register_ask<A, M>(&mut self, actor: &ActorWrapper<A>, type_name: impl Into<String>)
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: HttpBridgeAsk,
+    {
+        let type_name = type_name.into();
+        self.register_message::<A, M>(actor, type_name.clone());
+        let wrapper = actor.clone();
+        let asker: MessageAsker = Box::new(move |body, timeout| {
+            let mut msg: M = serde_json::from_slice(body).map_err(|e| e.to_string())?;
+            let (sender, receiver) = bounded(1);
+            msg.set_reply(HttpBridgeReply::new(sender));
+            let _ = wrapper.send(msg);
+            match receiver.recv_timeout(timeout) {
+                Ok(response) => Ok(Some(serde_json::to_string(&response).map_err(|e| e.to_string())?)),
+                Err(_) => Ok(None),
+            }
+        });
+        self.get_or_expose(actor).askers.insert(type_name, asker);
+    }
+
+    /// Routes and dispatches a single request
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::http_bridge::*;
+    /// use serde::Deserialize;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Add { value: usize }
+    /// impl ActorMessage for Add {}
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Get {
+    ///     #[serde(skip)]
+    ///     reply: Option<HttpBridgeReply<usize>>,
+    /// }
+    /// impl ActorMessage for Get {}
+    /// impl HttpBridgeAsk for Get {
+    ///     type Response = usize;
+    ///     fn set_reply(&mut self, reply: HttpBridgeReply<usize>) {
+    ///         self.reply = Some(reply);
+    ///     }
+    /// }
+    ///
+    /// struct Counter { sum: usize, touched: Arc<AtomicUsize> }
+    /// impl Actor for Counter {}
+    /// impl Handler<Add> for Counter {
+    ///     fn handle(&mut self, msg: Add, _context: &ActorContext<Self>) {
+    ///         self.touched.fetch_add(1, Ordering::Relaxed);
+    ///         self.sum += msg.value;
+    ///     }
+    /// }
+    /// impl Handler<Get> for Counter {
+    ///     fn handle(&mut self, msg: Get, _context: &ActorContext<Self>) {
+    ///         msg.reply.unwrap().reply(self.sum);
+    ///     }
+    /// }
+    ///
+    /// struct CounterFactory { touched: Arc<AtomicUsize> }
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { sum: 0, touched: self.touched.clone() }
+    ///     }
+    /// }
+    ///
+    /// let touched = Arc::new(AtomicUsize::new(0));
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let actor = actor_system.builder().set_journal_capacity(4).spawn("counter", CounterFactory { touched: touched.clone() }).unwrap();
+    /// actor_system.builder().spawn("say \"hi\"\n", CounterFactory { touched: touched.clone() }).unwrap();
+    ///
+    /// let mut bridge = HttpBridge::new(actor_system.clone());
+    /// bridge.register_message::<Counter, Add>(&actor, "add");
+    /// bridge.register_ask::<Counter, Get>(&actor, "get");
+    /// bridge.tag(&actor, "team", "core");
+    /// bridge.set_authorizer(|request| request.method != "DELETE");
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("POST", "/actors/counter/messages/add", br#"{"value":2}"#.to_vec()));
+    /// assert_eq!(202, response.status);
+    /// assert_eq!(r#"{"status":"accepted"}"#, response.body);
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("POST", "/actors/counter/messages/get?ask=true&timeout_ms=1000", b"{}".to_vec()));
+    /// assert_eq!(200, response.status);
+    /// assert_eq!("2", response.body);
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("GET", "/actors/counter", Vec::new()));
+    /// assert_eq!(200, response.status);
+    /// assert!(response.body.contains(r#""processed":2"#));
+    /// assert!(response.body.contains(r#""tags":{"team":"core"}"#));
+    /// assert!(response.body.contains(r#""event":"spawned""#));
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("GET", "/actors?tag=team:core", Vec::new()));
+    /// assert_eq!(r#"{"actors":["counter"]}"#, response.body);
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("GET", "/actors?tag=team:other", Vec::new()));
+    /// assert_eq!(r#"{"actors":[]}"#, response.body);
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("GET", "/metrics", Vec::new()));
+    /// assert_eq!(200, response.status);
+    /// assert!(response.body.contains("tyractorsaur_messages_handled_total{system=") && response.body.contains("pool=\"default\",actor=\"counter\"} 2"));
+    /// // label values are escaped
+    /// assert!(response.body.contains("pool=\"default\",actor=\"say \\\"hi\\\"\\n\"} 0"));
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("POST", "/actors/missing/messages/add", b"{}".to_vec()));
+    /// assert_eq!(404, response.status);
+    /// assert_eq!(r#"{"error":"actor_not_found","message":"missing"}"#, response.body);
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("POST", "/actors/counter/messages/unknown", b"{}".to_vec()));
+    /// assert_eq!(404, response.status);
+    /// assert_eq!(r#"{"error":"message_type_not_found","message":"unknown"}"#, response.body);
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("DELETE", "/actors/counter/messages/add", br#"{"value":1}"#.to_vec()));
+    /// assert_eq!(403, response.status);
+    /// assert_eq!(1, touched.load(Ordering::Relaxed));
+    ///
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("POST", "/actors/counter/stop", Vec::new()));
+    /// assert_eq!(r#"{"status":"stopping"}"#, response.body);
+    /// sleep(Duration::from_millis(100));
+    /// let response = bridge.handle_request(HttpBridgeRequest::new("GET", "/actors/counter", Vec::new()));
+    /// assert!(response.body.contains(r#""active":false"#));
+    /// ```
+    pub fn handle_request(&self, request: HttpBridgeRequest) -> HttpBridgeResponse {
+        if let Some(authorizer) = &self.authorizer {
+            if !authorizer(&request) {
+                return HttpBridgeResponse::error(403, "unauthorized", "request rejected by authorizer");
+            }
+        }
+        let (segments, params) = request.split();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("POST", ["actors", name, "messages", type_name]) => {
+                let is_ask = params.iter().any(|(key, value)| *key == "ask" && *value == "true");
+                if is_ask {
+                    self.ask(name, type_name, &params, &request.body)
+                } else {
+                    self.send(name, type_name, &request.body)
+                }
+            }
+            ("POST", ["actors", name, "stop"]) => self.stop(name),
+            ("GET", ["actors", name]) => self.inspect(name),
+            ("GET", ["actors"]) => self.query(&params),
+            ("GET", ["metrics"]) => self.metrics(),
+            _ => HttpBridgeResponse::error(404, "route_not_found", format!("{} {}", request.method, request.path)),
+        }
+    }
+
+    fn get_or_expose<A>(&mut self, actor: &ActorWrapper<A>) -> &mut ExposedActor
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        let address = actor.get_address().clone();
+        let wrapper = actor.clone();
+        let journal = actor.clone();
+        let effect_ledger = actor.clone();
+        let streams = actor.clone();
+        self.actors.entry(address.actor.clone()).or_insert_with(|| ExposedActor {
+            address,
+            metrics: wrapper.get_metrics().clone(),
+            tags: BTreeMap::new(),
+            senders: HashMap::new(),
+            askers: HashMap::new(),
+            stop: Box::new(move || {
+                wrapper.stop();
+            }),
+            journal: Box::new(move || journal.journal()),
+            effect_ledger_len: Box::new(move || effect_ledger.effect_ledger_len()),
+            attached_streams: Box::new(move || streams.attached_streams()),
+        })
+    }
+
+    fn get_actor(&self, name: &str) -> Result<&ExposedActor, HttpBridgeResponse> {
+        self.actors
+            .get(name)
+            .ok_or_else(|| HttpBridgeResponse::error(404, "actor_not_found", name))
+    }
+
+    fn disabled(route: &str) -> HttpBridgeResponse {
+        HttpBridgeResponse::error(403, "route_disabled", route)
+    }
+
+    fn send(&self, name: &str, type_name: &str, body: &[u8]) -> HttpBridgeResponse {
+        if !self.routes.messages {
+            return Self::disabled("messages");
+        }
+        let actor = match self.get_actor(name) {
+            Ok(actor) => actor,
+            Err(response) => return response,
+        };
+        let sender = match actor.senders.get(type_name) {
+            Some(sender) => sender,
+            None => return HttpBridgeResponse::error(404, "message_type_not_found", type_name),
+        };
+        match sender(body) {
+            Ok(()) => HttpBridgeResponse::json(202, json!({ "status": "accepted" })),
+            Err(e) => HttpBridgeResponse::error(400, "invalid_body", e),
+        }
+    }
+
+    fn ask(&self, name: &str, type_name: &str, params: &[(&str, &str)], body: &[u8]) -> HttpBridgeResponse {
+        if !self.routes.ask {
+            return Self::disabled("ask");
+        }
+        let actor = match self.get_actor(name) {
+            Ok(actor) => actor,
+            Err(response) => return response,
+        };
+        let asker = match actor.askers.get(type_name) {
+            Some(asker) => asker,
+            None => return HttpBridgeResponse::error(404, "message_type_not_found", type_name),
+        };
+        let timeout = match params.iter().find(|(key, _)| *key == "timeout_ms") {
+            None => DEFAULT_ASK_TIMEOUT,
+            Some((_, value)) => match value.parse::<u64>() {
+                Ok(millis) => Duration::from_millis(millis),
+                Err(_) => return HttpBridgeResponse::error(400, "invalid_query", "timeout_ms"),
+            },
+        };
+        match asker(body, timeout) {
+            Ok(Some(response)) => HttpBridgeResponse {
+                status: 200,
+                content_type: "application/json",
+                body: response,
+            },
+            Ok(None) => HttpBridgeResponse::error(504, "ask_timeout", type_name),
+            Err(e) => HttpBridgeResponse::error(400, "invalid_body", e),
+        }
+    }
+
+    fn stop(&self, name: &str) -> HttpBridgeResponse {
+        if !self.routes.stop {
+            return Self::disabled("stop");
+        }
+        match self.get_actor(name) {
+            Ok(actor) => {
+                (actor.stop)();
+                HttpBridgeResponse::json(200, json!({ "status": "stopping" }))
+            }
+            Err(response) => response,
+        }
+    }
+
+    fn inspect(&self, name: &str) -> HttpBridgeResponse {
+        if !self.routes.inspect {
+            return Self::disabled("inspect");
+        }
+        let actor = match self.get_actor(name) {
+            Ok(actor) => actor,
+            Err(response) => return response,
+        };
+        let mut message_types: Vec<&String> = actor.senders.keys().collect();
+        message_types.sort();
+        let protocol = self.system.get_protocol(&actor.address).map(|protocol| {
+            json!({
+                "version": protocol.version,
+                "accepts": [*protocol.accepts.start(), *protocol.accepts.end()],
+            })
+        });
+        let journal: Vec<Value> = (actor.journal)()
+            .iter()
+            .map(|entry| {
+                json!({
+                    "sequence": entry.sequence,
+                    "at_ms": entry.at.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+                    "event": entry.event.to_string(),
+                })
+            })
+            .collect();
+        let report = json!({
+            "name": name,
+            "address": {
+                "system": actor.address.system,
+                "pool": actor.address.pool,
+                "actor": actor.address.actor,
+            },
+            "active": self.system.get_state().is_actor_active(&actor.address),
+            "mailbox_len": actor.metrics.get_mailbox_len(),
+            "processed": actor.metrics.get_processed(),
+            "message_budget": actor.metrics.get_message_budget(),
+            "restarts": actor.metrics.get_restarts(),
+            "shed": actor.metrics.get_shed(),
+            "superseded": actor.metrics.get_superseded(),
+            "dropped": actor.metrics.get_dropped(),
+            "expired": actor.metrics.get_expired(),
+            "suspended_ms": actor.metrics.get_suspended_time().as_millis() as u64,
+            "awaiting_release": actor.metrics.is_awaiting_release(),
+            "protocol": protocol,
+            "message_types": message_types,
+            "tags": actor.tags,
+            "journal": journal,
+            "effect_ledger_len": (actor.effect_ledger_len)(),
+            "attached_streams": (actor.attached_streams)(),
+        });
+        HttpBridgeResponse::json(200, report)
+    }
+
+    fn query(&self, params: &[(&str, &str)]) -> HttpBridgeResponse {
+        if !self.routes.query {
+            return Self::disabled("query");
+        }
+        let mut filters = Vec::new();
+        for (key, value) in params {
+            if *key != "tag" {
+                continue;
+            }
+            match value.find(':') {
+                Some(index) => filters.push((&value[..index], &value[index + 1..])),
+                None => return HttpBridgeResponse::error(400, "invalid_query", *value),
+            }
+        }
+        let names: Vec<Value> = self
+            .actors
+            .iter()
+            .filter(|(_, actor)| {
+                filters
+                    .iter()
+                    .all(|(key, value)| actor.tags.get(*key).map(|v| v.as_str()) == Some(*value))
+            })
+            .map(|(name, _)| Value::from(name.as_str()))
+            .collect();
+        HttpBridgeResponse::json(200, json!({ "actors": names }))
+    }
+
+    fn metrics(&self) -> HttpBridgeResponse {
+        if !self.routes.metrics {
+            return Self::disabled("metrics");
+        }
+        HttpBridgeResponse::text(200, self.system.render_prometheus())
+    }
+}
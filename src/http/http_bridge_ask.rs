@@ -0,0 +1,29 @@
+use crate::message::actor_message::ActorMessage;
+use crossbeam_channel::Sender;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Messages that can be sent with `?ask=true` through the [HttpBridge](./struct.HttpBridge.html)
+///
+/// The bridge injects a [HttpBridgeReply] after deserialization, the Handler answers through it
+pub trait HttpBridgeAsk: ActorMessage + DeserializeOwned + 'static {
+    type Response: Serialize + Send + 'static;
+
+    fn set_reply(&mut self, reply: HttpBridgeReply<Self::Response>);
+}
+
+/// Reply channel of a [HttpBridgeAsk] message
+pub struct HttpBridgeReply<R> {
+    sender: Sender<R>,
+}
+
+impl<R> HttpBridgeReply<R> {
+    pub(crate) fn new(sender: Sender<R>) -> Self {
+        Self { sender }
+    }
+
+    /// Sends the response, it is dropped if the request already timed out
+    pub fn reply(&self, response: R) {
+        let _ = self.sender.send(response);
+    }
+}
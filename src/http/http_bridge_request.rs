@@ -0,0 +1,36 @@
+/// Server-agnostic HTTP request handed to [HttpBridge.handle_request](./struct.HttpBridge.html#method.handle_request)
+///
+/// `path` may contain a query string, i.e. `/actors?tag=team:core`
+pub struct HttpBridgeRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpBridgeRequest {
+    pub fn new(method: impl Into<String>, path: impl Into<String>, body: Vec<u8>) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            body,
+        }
+    }
+
+    /// Splits the path into its segments and the query parameters
+    pub(crate) fn split(&self) -> (Vec<&str>, Vec<(&str, &str)>) {
+        let (path, query) = match self.path.find('?') {
+            Some(index) => (&self.path[..index], &self.path[index + 1..]),
+            None => (self.path.as_str(), ""),
+        };
+        let segments = path.split('/').filter(|s| !s.is_empty()).collect();
+        let params = query
+            .split('&')
+            .filter(|s| !s.is_empty())
+            .map(|param| match param.find('=') {
+                Some(index) => (&param[..index], &param[index + 1..]),
+                None => (param, ""),
+            })
+            .collect();
+        (segments, params)
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::{json, Value};
+
+/// Server-agnostic HTTP response returned by [HttpBridge.handle_request](./struct.HttpBridge.html#method.handle_request)
+///
+/// Errors are always returned as `{"error": "<code>", "message": "<details>"}`
+#[derive(Debug, PartialEq)]
+pub struct HttpBridgeResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpBridgeResponse {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+
+    pub fn text(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; version=0.0.4",
+            body,
+        }
+    }
+
+    pub fn error(status: u16, error: &str, message: impl Into<String>) -> Self {
+        Self::json(status, json!({ "error": error, "message": message.into() }))
+    }
+}
@@ -0,0 +1,31 @@
+/// Enables or disables single routes of the [HttpBridge](./struct.HttpBridge.html)
+///
+/// Disabled routes answer with `403` and `route_disabled`
+#[derive(Clone, Copy)]
+pub struct HttpBridgeRoutes {
+    /// `POST /actors/{name}/messages/{type_name}`
+    pub messages: bool,
+    /// `POST /actors/{name}/messages/{type_name}?ask=true&timeout_ms=...`
+    pub ask: bool,
+    /// `GET /actors/{name}`
+    pub inspect: bool,
+    /// `GET /actors?tag=key:value`
+    pub query: bool,
+    /// `GET /metrics`
+    pub metrics: bool,
+    /// `POST /actors/{name}/stop`
+    pub stop: bool,
+}
+
+impl Default for HttpBridgeRoutes {
+    fn default() -> Self {
+        Self {
+            messages: true,
+            ask: true,
+            inspect: true,
+            query: true,
+            metrics: true,
+            stop: true,
+        }
+    }
+}
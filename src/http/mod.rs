@@ -0,0 +1,13 @@
+mod http_bridge;
+mod http_bridge_ask;
+mod http_bridge_request;
+mod http_bridge_response;
+mod http_bridge_routes;
+
+pub mod prelude {
+    pub use crate::http::http_bridge::HttpBridge;
+    pub use crate::http::http_bridge_ask::{HttpBridgeAsk, HttpBridgeReply};
+    pub use crate::http::http_bridge_request::HttpBridgeRequest;
+    pub use crate::http::http_bridge_response::HttpBridgeResponse;
+    pub use crate::http::http_bridge_routes::HttpBridgeRoutes;
+}
@@ -103,7 +103,15 @@
 //! ```
 //!
 mod actor;
+mod benchmark;
+mod capacity;
 mod config;
+#[cfg(feature = "ffi")]
+mod embedding;
+#[cfg(feature = "testing")]
+mod harness;
+#[cfg(feature = "http-bridge")]
+mod http;
 mod message;
 mod routers;
 mod system;
@@ -114,9 +122,41 @@ pub mod prelude {
     pub use crate::config::prelude::*;
     pub use crate::message::prelude::*;
     pub use crate::system::prelude::*;
+    #[cfg(feature = "derive")]
+    pub use tyractorsaur_derive::{handlers, Actor, ActorMessage};
 }
 
 /// collection of different router implementations
 pub mod router {
     pub use crate::routers::prelude::*;
 }
+
+/// capacity planning for actor topologies, either declared or observed on a running system
+pub mod planning {
+    pub use crate::capacity::prelude::*;
+}
+
+/// cost of optional features on the current machine, see `cargo bench --bench overhead`
+pub mod bench {
+    pub use crate::benchmark::prelude::*;
+}
+
+/// server-agnostic HTTP/JSON bridge, requires the `http-bridge` feature
+#[cfg(feature = "http-bridge")]
+pub mod http_bridge {
+    pub use crate::http::prelude::*;
+}
+
+/// C-compatible API to drive an ActorSystem from non-Rust hosts, requires the `ffi` feature
+///
+/// The declarations for C and C++ are available in [include/tyractorsaur.h](https://github.com/sers-dev/tyractorsaur/blob/master/include/tyractorsaur.h)
+#[cfg(feature = "ffi")]
+pub mod ffi {
+    pub use crate::embedding::prelude::*;
+}
+
+/// multi-threaded harness for the mailbox and wakeup protocol, deterministic interaction tests and probes, requires the `testing` feature
+#[cfg(feature = "testing")]
+pub mod testing {
+    pub use crate::harness::prelude::*;
+}
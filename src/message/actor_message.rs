@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 /// Core trait to define Messages
 ///
 /// # Examples
@@ -10,4 +12,51 @@
 /// struct FooBar {}
 /// impl ActorMessage for FooBar {}
 /// ```
-pub trait ActorMessage: Send + Sync {}
+///
+/// Messages are moved into the handler, they never have to implement `Clone`:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// // deliberately not Clone
+/// struct Frame { pixels: Vec<u8> }
+/// impl ActorMessage for Frame {}
+///
+/// struct Encoder { received: Arc<Mutex<Vec<usize>>> }
+/// impl Actor for Encoder {}
+/// impl Handler<Frame> for Encoder {
+///     fn handle(&mut self, msg: Frame, _context: &ActorContext<Self>) {
+///         self.received.lock().unwrap().push(msg.pixels.as_ptr() as usize);
+///     }
+/// }
+/// struct EncoderFactory { received: Arc<Mutex<Vec<usize>>> }
+/// impl ActorFactory<Encoder> for EncoderFactory {
+///     fn new_actor(&self, _context: ActorContext<Encoder>) -> Encoder {
+///         Encoder { received: self.received.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let received = Arc::new(Mutex::new(Vec::new()));
+/// let encoder = actor_system.builder().spawn("encoder", EncoderFactory { received: received.clone() }).unwrap();
+/// let frame = Frame { pixels: vec![0; 4 * 1024 * 1024] };
+/// let sent = frame.pixels.as_ptr() as usize;
+/// encoder.send(frame).unwrap();
+/// sleep(Duration::from_millis(100));
+/// // the handler received the very same buffer
+/// assert_eq!(vec![sent], *received.lock().unwrap());
+/// ```
+pub trait ActorMessage: Send + Sync {
+    /// Approximate size of the message in bytes, checked against [ActorBuilder.set_max_message_bytes](../prelude/struct.ActorBuilder.html#method.set_max_message_bytes) and counted against [ActorBuilder.set_max_mailbox_bytes](../prelude/struct.ActorBuilder.html#method.set_max_mailbox_bytes)
+    ///
+    /// Defaults to the shallow size, types that own large buffers on the heap should add them, `#[message(size = "...")]` of the derive does so
+    fn approx_size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// Shares a single message between several sends without copying it, see [ActorWrapper.send_shared](../prelude/struct.ActorWrapper.html#method.send_shared)
+impl<M> ActorMessage for Arc<M> where M: ActorMessage + ?Sized {}
@@ -0,0 +1,8 @@
+use crate::actor::supervision::ChildTerminated;
+use crate::message::actor_message::ActorMessage;
+
+pub struct ChildTerminatedMessage {
+    pub child: ChildTerminated,
+}
+
+impl ActorMessage for ChildTerminatedMessage {}
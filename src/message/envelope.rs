@@ -1,20 +1,81 @@
 use crate::actor::actor::Actor;
+use crate::actor::behavior::BehaviorDispatch;
 use crate::actor::context::ActorContext;
+use crate::actor::mailbox_bytes::BytesToken;
 use crate::actor::handler::Handler;
+use crate::actor::panic_report::{CapturedPayload, PayloadCaptureRegistry};
+use crate::actor::pressure::PressureToken;
+use crate::actor::reply::ReplyPath;
+use crate::actor::unhandled::{self, UnhandledInfo};
 use crate::message::actor_message::ActorMessage;
 use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::headers::Headers;
 use crate::message::message_type::MessageType;
+use crate::message::receipt::{Receipt, ReceiptSlot};
+use crate::message::suspension_ended_message::SuspensionEndedMessage;
 use crate::message::system_stop_message::SystemStopMessage;
+use crate::system::message_budget::BudgetToken;
 use std::any::{Any, TypeId};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 pub trait MessageEnvelopeTrait<A>: Send + Sync
 where
     A: Actor,
 {
     fn handle(&mut self, actor: &mut A, context: &ActorContext<A>) -> MessageType;
+    fn get_type_id(&self) -> TypeId;
+    fn get_type_name(&self) -> &'static str;
+    /// Returns `None` if the message type is not registered for capturing
+    fn capture_payload(&self, registry: &PayloadCaptureRegistry, max_bytes: usize) -> Option<CapturedPayload>;
+    /// Time spent constructing a lazy message within `handle`
+    fn get_construction_time(&self) -> Duration;
+    /// Takes the message out of an envelope that has not been handled, `None` for lazily constructed messages
+    fn take_message(&mut self) -> Option<Box<dyn Any + Send>>;
+    /// Returns `None` once the message has been handled, or if it is constructed lazily
+    fn get_message(&self) -> Option<&dyn Any>;
+    /// See [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size), lazily constructed messages are measured by their shallow size
+    fn approx_size(&self) -> usize;
 }
 
-pub struct MessageEnvelope<A>(Box<dyn MessageEnvelopeTrait<A> + Send + Sync>);
+/// Deferred construction of a message, only executed once the message is handled
+pub(crate) struct Constructor<M>(Mutex<Box<dyn FnOnce() -> M + Send>>);
+
+impl<M> Constructor<M> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() -> M + Send + 'static,
+    {
+        Self(Mutex::new(Box::new(f)))
+    }
+
+    pub fn construct(self) -> M {
+        (self.0.into_inner().unwrap())()
+    }
+}
+
+pub struct MessageEnvelope<A>(
+    Box<dyn MessageEnvelopeTrait<A> + Send + Sync>,
+    Option<Box<Headers>>,
+    Option<Instant>,
+    Option<PressureToken>,
+    /// only stamped if the Actor has superseding message types
+    u64,
+    /// queued in the priority lane of the mailbox, see [ActorWrapper.send_priority](../prelude/struct.ActorWrapper.html#method.send_priority)
+    bool,
+    /// only set for messages sent through [ActorWrapper.send_from](../prelude/struct.ActorWrapper.html#method.send_from)
+    Option<Box<ReplyPath>>,
+    /// only set for messages with a time to live, see [ActorWrapper.send_with_ttl](../prelude/struct.ActorWrapper.html#method.send_with_ttl)
+    Option<Instant>,
+    /// only set while the message counts towards the message budget of the system
+    Option<BudgetToken>,
+    /// only set for messages sent through [ActorWrapper.send_with_receipt](../prelude/struct.ActorWrapper.html#method.send_with_receipt)
+    Option<ReceiptSlot>,
+    /// queued in the continuation lane of the mailbox, see [ActorContext.send_to_self](../prelude/struct.ActorContext.html#method.send_to_self)
+    bool,
+    /// only set while the message counts towards the bytes of a mailbox with `max_mailbox_bytes`
+    Option<BytesToken>,
+);
 
 impl<A> MessageEnvelope<A> {
     pub fn new<M>(msg: M) -> Self
@@ -22,7 +83,168 @@ impl<A> MessageEnvelope<A> {
         A: Handler<M> + Actor,
         M: ActorMessage + Send + Sync + 'static,
     {
-        MessageEnvelope(Box::new(SyncMessageEnvelope { msg: Some(msg) }))
+        MessageEnvelope(Box::new(SyncMessageEnvelope { msg: Some(msg) }), None, None, None, 0, false, None, None, None, None, false, None)
+    }
+
+    pub fn with_headers<M>(msg: M, headers: Option<Headers>) -> Self
+    where
+        A: Handler<M> + Actor,
+        M: ActorMessage + Send + Sync + 'static,
+    {
+        MessageEnvelope(
+            Box::new(SyncMessageEnvelope { msg: Some(msg) }),
+            headers.filter(|h| !h.is_empty()).map(Box::new),
+            None,
+            None,
+            0,
+            false,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+    }
+
+    pub(crate) fn lazy<M>(constructor: Constructor<M>, headers: Option<Headers>) -> Self
+    where
+        A: Handler<M> + Actor,
+        M: ActorMessage + 'static,
+    {
+        MessageEnvelope(
+            Box::new(LazyMessageEnvelope {
+                constructor: Some(constructor),
+                construction_time: Duration::from_secs(0),
+            }),
+            headers.filter(|h| !h.is_empty()).map(Box::new),
+            None,
+            None,
+            0,
+            false,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+    }
+
+    pub fn get_headers(&self) -> Option<&Headers> {
+        self.1.as_deref()
+    }
+
+    pub fn take_headers(&mut self) -> Option<Box<Headers>> {
+        self.1.take()
+    }
+
+    /// Only set for Actors with a latency SLO, or while an [InstrumentationHook](../prelude/trait.InstrumentationHook.html) is registered
+    pub fn get_enqueued(&self) -> Option<Instant> {
+        self.2
+    }
+
+    pub(crate) fn set_enqueued(&mut self, enqueued: Instant) {
+        self.2 = Some(enqueued);
+    }
+
+    pub(crate) fn get_sequence(&self) -> u64 {
+        self.4
+    }
+
+    pub(crate) fn set_sequence(&mut self, sequence: u64) {
+        self.4 = sequence;
+    }
+
+    pub(crate) fn is_urgent(&self) -> bool {
+        self.5
+    }
+
+    pub(crate) fn set_urgent(&mut self) {
+        self.5 = true;
+    }
+
+    pub(crate) fn is_continuation(&self) -> bool {
+        self.10
+    }
+
+    pub(crate) fn set_continuation(&mut self) {
+        self.10 = true;
+    }
+
+    pub(crate) fn set_reply_path(&mut self, path: ReplyPath) {
+        self.6 = Some(Box::new(path));
+    }
+
+    pub(crate) fn take_reply_path(&mut self) -> Option<Box<ReplyPath>> {
+        self.6.take()
+    }
+
+    pub(crate) fn has_reply_path(&self) -> bool {
+        self.6.is_some()
+    }
+
+    pub fn get_expires_at(&self) -> Option<Instant> {
+        self.7
+    }
+
+    pub(crate) fn set_expires_at(&mut self, expires_at: Instant) {
+        self.7 = Some(expires_at);
+    }
+
+    /// Only reads the clock for messages with a time to live
+    pub(crate) fn is_expired(&self) -> bool {
+        self.7.is_some_and(|expires_at| Instant::now() >= expires_at)
+    }
+
+    /// Returns the message of an envelope that has not been queued, e.g. to hand it back to the sender
+    pub(crate) fn into_message<M>(mut self) -> Option<M>
+    where
+        A: Actor,
+        M: 'static,
+    {
+        self.take_message()?.downcast::<M>().ok().map(|msg| *msg)
+    }
+
+    /// Takes over headers, sender and the queueing metadata of `other`, except the mailbox it counts towards
+    pub(crate) fn with_metadata_of(mut self, other: &Self) -> Self {
+        self.1 = other.1.clone();
+        self.2 = other.2;
+        self.4 = other.4;
+        self.5 = other.5;
+        self.6 = other.6.clone();
+        self.7 = other.7;
+        self
+    }
+
+    /// Replaces the mailbox the message counts towards, the previous one is released
+    pub(crate) fn set_pressure(&mut self, pressure: Option<PressureToken>) {
+        self.3 = pressure;
+    }
+
+    /// The message stops counting towards the message budget once the token is released
+    pub(crate) fn set_budget(&mut self, budget: Option<BudgetToken>) {
+        self.8 = budget;
+    }
+
+    /// The message stops counting towards the bytes of the mailbox once the token is released
+    pub(crate) fn set_bytes(&mut self, bytes: Option<BytesToken>) {
+        self.11 = bytes;
+    }
+
+    pub(crate) fn set_receipt(&mut self, receipt: ReceiptSlot) {
+        self.9 = Some(receipt);
+    }
+
+    pub(crate) fn has_receipt(&self) -> bool {
+        self.9.is_some()
+    }
+
+    /// Without a settled receipt, dropping the envelope settles it as [Receipt::Dropped] with [DeadLetterReason::Undeliverable](../prelude/enum.DeadLetterReason.html#variant.Undeliverable)
+    pub(crate) fn settle_receipt(&mut self, receipt: Receipt) {
+        if let Some(slot) = self.9.take() {
+            slot.settle(receipt);
+        }
     }
 }
 
@@ -33,6 +255,34 @@ where
     fn handle(&mut self, act: &mut A, context: &ActorContext<A>) -> MessageType {
         self.0.handle(act, context)
     }
+
+    fn get_type_id(&self) -> TypeId {
+        self.0.get_type_id()
+    }
+
+    fn get_type_name(&self) -> &'static str {
+        self.0.get_type_name()
+    }
+
+    fn capture_payload(&self, registry: &PayloadCaptureRegistry, max_bytes: usize) -> Option<CapturedPayload> {
+        self.0.capture_payload(registry, max_bytes)
+    }
+
+    fn get_construction_time(&self) -> Duration {
+        self.0.get_construction_time()
+    }
+
+    fn take_message(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.0.take_message()
+    }
+
+    fn get_message(&self) -> Option<&dyn Any> {
+        self.0.get_message()
+    }
+
+    fn approx_size(&self) -> usize {
+        self.0.approx_size()
+    }
 }
 
 pub struct SyncMessageEnvelope<M>
@@ -50,13 +300,108 @@ where
     fn handle(&mut self, act: &mut A, context: &ActorContext<A>) -> MessageType {
         if let Some(msg) = self.msg.take() {
             let msg_type_id = msg.type_id();
-            act.handle(msg, context);
+            match context.dispatch_behavior(msg_type_id) {
+                BehaviorDispatch::Override(handler) => handler(act, Box::new(msg), context),
+                BehaviorDispatch::Unhandled => unhandled::dispatch(
+                    act,
+                    UnhandledInfo::Typed {
+                        type_name: std::any::type_name::<M>(),
+                    },
+                    context,
+                ),
+                BehaviorDispatch::Static => act.handle(msg, context),
+            }
             if msg_type_id == TypeId::of::<ActorStopMessage>() {
                 return MessageType::ActorStopMessage;
             } else if msg_type_id == TypeId::of::<SystemStopMessage>() {
                 return MessageType::SystemStopMessage;
+            } else if msg_type_id == TypeId::of::<SuspensionEndedMessage>() {
+                return MessageType::SuspensionEndedMessage;
             }
         }
         MessageType::Other
     }
+
+    fn get_type_id(&self) -> TypeId {
+        TypeId::of::<M>()
+    }
+
+    fn get_type_name(&self) -> &'static str {
+        std::any::type_name::<M>()
+    }
+
+    fn capture_payload(&self, registry: &PayloadCaptureRegistry, max_bytes: usize) -> Option<CapturedPayload> {
+        registry.capture(self.msg.as_ref()?, max_bytes)
+    }
+
+    fn get_construction_time(&self) -> Duration {
+        Duration::from_secs(0)
+    }
+
+    fn take_message(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.msg.take().map(|msg| Box::new(msg) as Box<dyn Any + Send>)
+    }
+
+    fn get_message(&self) -> Option<&dyn Any> {
+        self.msg.as_ref().map(|msg| msg as &dyn Any)
+    }
+
+    fn approx_size(&self) -> usize {
+        self.msg.as_ref().map_or(0, |msg| msg.approx_size())
+    }
+}
+
+// provenance of audited messages travels within the headers, envelopes of all other messages only hold the message
+const _: () = assert!(std::mem::size_of::<SyncMessageEnvelope<SystemStopMessage>>() == std::mem::size_of::<Option<SystemStopMessage>>());
+
+/// Holds the constructor instead of the message, all decisions before the message is handled never construct it
+pub struct LazyMessageEnvelope<M> {
+    constructor: Option<Constructor<M>>,
+    construction_time: Duration,
+}
+
+impl<A, M> MessageEnvelopeTrait<A> for LazyMessageEnvelope<M>
+where
+    M: ActorMessage + 'static,
+    A: Handler<M> + Actor,
+{
+    fn handle(&mut self, act: &mut A, context: &ActorContext<A>) -> MessageType {
+        let constructor = match self.constructor.take() {
+            Some(constructor) => constructor,
+            None => return MessageType::Other,
+        };
+        let started = Instant::now();
+        let msg = constructor.construct();
+        self.construction_time = started.elapsed();
+        SyncMessageEnvelope { msg: Some(msg) }.handle(act, context)
+    }
+
+    fn get_type_id(&self) -> TypeId {
+        TypeId::of::<M>()
+    }
+
+    fn get_type_name(&self) -> &'static str {
+        std::any::type_name::<M>()
+    }
+
+    /// The payload does not exist before the message is handled
+    fn capture_payload(&self, _registry: &PayloadCaptureRegistry, _max_bytes: usize) -> Option<CapturedPayload> {
+        None
+    }
+
+    fn get_construction_time(&self) -> Duration {
+        self.construction_time
+    }
+
+    fn take_message(&mut self) -> Option<Box<dyn Any + Send>> {
+        None
+    }
+
+    fn get_message(&self) -> Option<&dyn Any> {
+        None
+    }
+
+    fn approx_size(&self) -> usize {
+        std::mem::size_of::<M>()
+    }
 }
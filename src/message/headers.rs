@@ -0,0 +1,259 @@
+use crate::actor::priority::{CHAIN_HEADER, DEPTH_HEADER, PRIORITY_HEADER};
+use crate::actor::retry_policy::ATTEMPT_HEADER;
+use crate::message::provenance::{DERIVED_HEADER, PROVENANCE_HEADER};
+use crate::message::trace::TRACE_HEADER;
+use crate::system::cycle_detection::CYCLE_HEADER;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::sync::{Arc, RwLock};
+
+/// Interned header name, see [ActorSystem.register_header](../prelude/struct.ActorSystem.html#method.register_header)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HeaderKey(pub u16);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderValue {
+    U64(u64),
+    Str(Arc<str>),
+    Bytes(Arc<[u8]>),
+}
+
+/// Defines how a header is passed on to messages sent while a message carrying it is handled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Propagate {
+    /// copied onto every send
+    Always,
+    /// only visible to the receiver of the original message
+    Never,
+    /// hop count style [HeaderValue::U64](./enum.HeaderValue.html#variant.U64), decremented on every hop and no longer propagated once it reached zero
+    Decrement,
+}
+
+/// Headers of a single message
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Headers {
+    entries: Vec<(HeaderKey, HeaderValue)>,
+}
+
+impl Headers {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn with(mut self, key: HeaderKey, value: HeaderValue) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Inserts or replaces the value of the given key
+    pub fn insert(&mut self, key: HeaderKey, value: HeaderValue) {
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, key: HeaderKey) -> Option<&HeaderValue> {
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(HeaderKey, HeaderValue)> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries of `other` replace entries with the same key
+    pub fn merge(&mut self, other: Headers) {
+        for (key, value) in other.entries {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// Maps header keys of a remote system onto the keys of the local system
+///
+/// See [ActorSystem.header_mapping](../prelude/struct.ActorSystem.html#method.header_mapping)
+pub struct HeaderMapping {
+    keys: HashMap<u16, HeaderKey>,
+}
+
+#[derive(Clone)]
+pub struct HeaderRegistry {
+    headers: Arc<RwLock<Vec<(String, Propagate)>>>,
+}
+
+impl HeaderRegistry {
+    pub fn new() -> Self {
+        let registry = Self {
+            headers: Arc::new(RwLock::new(Vec::new())),
+        };
+        registry.register(PRIORITY_HEADER.0, Propagate::Always);
+        registry.register(CHAIN_HEADER.0, Propagate::Always);
+        registry.register(DEPTH_HEADER.0, Propagate::Decrement);
+        registry.register(PROVENANCE_HEADER.0, Propagate::Never);
+        registry.register(DERIVED_HEADER.0, Propagate::Never);
+        registry.register(CYCLE_HEADER.0, Propagate::Never);
+        registry.register(TRACE_HEADER.0, Propagate::Always);
+        registry.register(ATTEMPT_HEADER.0, Propagate::Never);
+        registry
+    }
+
+    pub fn register(&self, name: &str, propagate: Propagate) -> HeaderKey {
+        let mut headers = self.headers.write().unwrap();
+        if let Some(index) = headers.iter().position(|(n, _)| n == name) {
+            headers[index].1 = propagate;
+            return HeaderKey(index as u16);
+        }
+        assert!(headers.len() < u16::MAX as usize, "too many registered headers");
+        headers.push((String::from(name), propagate));
+        HeaderKey((headers.len() - 1) as u16)
+    }
+
+    pub fn get_key(&self, name: &str) -> Option<HeaderKey> {
+        let headers = self.headers.read().unwrap();
+        headers.iter().position(|(n, _)| n == name).map(|index| HeaderKey(index as u16))
+    }
+
+    pub fn table(&self) -> Vec<(HeaderKey, String)> {
+        let headers = self.headers.read().unwrap();
+        headers
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| (HeaderKey(index as u16), name.clone()))
+            .collect()
+    }
+
+    pub fn mapping(&self, remote_table: &[(HeaderKey, String)]) -> HeaderMapping {
+        let mut keys = HashMap::new();
+        for (remote_key, name) in remote_table {
+            if let Some(local_key) = self.get_key(name) {
+                keys.insert(remote_key.0, local_key);
+            }
+        }
+        HeaderMapping { keys }
+    }
+
+    /// Returns the headers that are passed on to sends made while handling a message with the given headers
+    pub fn propagate(&self, headers: &Headers) -> Option<Headers> {
+        let registered = self.headers.read().unwrap();
+        let mut propagated = Headers::new();
+        for (key, value) in headers.iter() {
+            let rule = registered.get(key.0 as usize).map_or(Propagate::Never, |(_, rule)| *rule);
+            match (rule, value) {
+                (Propagate::Always, _) => propagated.entries.push((*key, value.clone())),
+                (Propagate::Decrement, HeaderValue::U64(hops)) if *hops > 0 => {
+                    propagated.entries.push((*key, HeaderValue::U64(hops - 1)))
+                }
+                _ => {}
+            }
+        }
+        if propagated.is_empty() {
+            return None;
+        }
+        Some(propagated)
+    }
+}
+
+impl Default for HeaderRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn encode(headers: &Headers) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&(headers.len() as u16).to_le_bytes());
+    for (key, value) in headers.iter() {
+        bytes.extend_from_slice(&key.0.to_le_bytes());
+        match value {
+            HeaderValue::U64(v) => {
+                bytes.push(0);
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            HeaderValue::Str(v) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&(v.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(v.as_bytes());
+            }
+            HeaderValue::Bytes(v) => {
+                bytes.push(2);
+                bytes.extend_from_slice(&(v.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(v);
+            }
+        }
+    }
+    bytes
+}
+
+/// Returns `None` if the bytes are malformed, headers unknown to the mapping are skipped
+pub fn decode(bytes: &[u8], mapping: &HeaderMapping) -> Option<Headers> {
+    let mut headers = Headers::new();
+    if bytes.is_empty() {
+        return Some(headers);
+    }
+    let mut reader = Reader::new(bytes);
+    let count = u16::from_le_bytes(reader.take(2)?.try_into().ok()?);
+    for _ in 0..count {
+        let key = u16::from_le_bytes(reader.take(2)?.try_into().ok()?);
+        let value = match reader.take(1)?[0] {
+            0 => HeaderValue::U64(u64::from_le_bytes(reader.take(8)?.try_into().ok()?)),
+            tag => {
+                let len = u32::from_le_bytes(reader.take(4)?.try_into().ok()?) as usize;
+                let content = reader.take(len)?;
+                match tag {
+                    1 => HeaderValue::Str(Arc::from(std::str::from_utf8(content).ok()?)),
+                    2 => HeaderValue::Bytes(Arc::from(content)),
+                    _ => return None,
+                }
+            }
+        };
+        if let Some(local_key) = mapping.keys.get(&key) {
+            headers.insert(*local_key, value);
+        }
+    }
+    Some(headers)
+}
+
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let slice = self.bytes.get(self.position..end)?;
+        self.position = end;
+        Some(slice)
+    }
+}
+
+thread_local! {
+    static PROPAGATED: RefCell<Option<Headers>> = const { RefCell::new(None) };
+}
+
+/// Sets the headers that are attached to every send of the current thread
+pub(crate) fn set_propagated(headers: Option<Headers>) {
+    PROPAGATED.with(|propagated| *propagated.borrow_mut() = headers);
+}
+
+pub(crate) fn get_propagated() -> Option<Headers> {
+    PROPAGATED.with(|propagated| propagated.borrow().clone())
+}
+
+/// Inspects the headers that are attached to every send of the current thread without cloning them
+pub(crate) fn with_propagated<R>(f: impl FnOnce(Option<&Headers>) -> R) -> R {
+    PROPAGATED.with(|propagated| f(propagated.borrow().as_ref()))
+}
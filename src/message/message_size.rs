@@ -0,0 +1,188 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::system::event_bus::EventBus;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Topic of the [EventBus](../prelude/struct.EventBus.html) on which every [OversizedMessage](./struct.OversizedMessage.html) is published
+pub const OVERSIZED_TOPIC: &str = "system.oversized_message";
+
+/// Oversized messages of the same type are reported at most once per interval, rejections and warnings separately
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Marks messages whose size is never checked, meant for types that share their payload or can't be measured meaningfully
+///
+/// See [ActorSystem.register_size_unchecked](../prelude/struct.ActorSystem.html#method.register_size_unchecked)
+pub trait SizeUnchecked: ActorMessage {}
+
+/// Rejection of a message whose approximate size exceeds the limit of its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Oversized {
+    pub size: usize,
+    pub limit: usize,
+    pub type_name: &'static str,
+}
+
+impl Oversized {
+    pub fn into_error<M>(self, msg: M) -> SendError<M> {
+        SendError::MessageTooLarge {
+            msg,
+            size: self.size,
+            limit: self.limit,
+            type_name: self.type_name,
+        }
+    }
+}
+
+/// Published on [OVERSIZED_TOPIC](./constant.OVERSIZED_TOPIC.html) for messages that exceed the limit or the warning threshold of their target
+///
+/// Reports of the same message type are rate limited to one rejection and one warning per 10 seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct OversizedMessage {
+    /// Actor whose handler made the send, `None` for sends from outside of an Actor
+    pub sender: Option<ActorAddress>,
+    pub target: ActorAddress,
+    pub type_name: &'static str,
+    pub size: usize,
+    /// the exceeded limit, or the exceeded warning threshold if the message has been queued
+    pub threshold: usize,
+    pub is_rejected: bool,
+}
+
+impl ActorMessage for OversizedMessage {}
+
+thread_local! {
+    static SENDER: RefCell<Option<ActorAddress>> = const { RefCell::new(None) };
+}
+
+/// Sets the Actor that is attributed to the sends of the current thread
+pub(crate) fn set_sender(sender: Option<ActorAddress>) {
+    SENDER.with(|current| *current.borrow_mut() = sender);
+}
+
+/// Registered message types and the reports of a system
+pub(crate) struct MessageSizes {
+    unchecked: RwLock<HashSet<TypeId>>,
+    max_bytes: Option<usize>,
+    warn_bytes: Option<usize>,
+    /// set once an Actor with a limit or a warning threshold has been spawned
+    is_checking: AtomicBool,
+    reported: Mutex<HashMap<(TypeId, bool), Instant>>,
+    event_bus: EventBus,
+}
+
+impl MessageSizes {
+    pub fn new(max_bytes: Option<usize>, warn_bytes: Option<usize>, event_bus: EventBus) -> Self {
+        let mut unchecked = HashSet::new();
+        // a report must never be reported itself
+        unchecked.insert(TypeId::of::<OversizedMessage>());
+        Self {
+            unchecked: RwLock::new(unchecked),
+            max_bytes,
+            warn_bytes,
+            is_checking: AtomicBool::new(false),
+            reported: Mutex::new(HashMap::new()),
+            event_bus,
+        }
+    }
+
+    pub fn register_unchecked<M>(&self)
+    where
+        M: SizeUnchecked + 'static,
+    {
+        self.unchecked.write().unwrap().insert(TypeId::of::<M>());
+    }
+
+    pub fn get_max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    pub fn is_checking(&self) -> bool {
+        self.is_checking.load(Ordering::Relaxed)
+    }
+
+    /// Returns `None` if the Actor has neither a limit nor is the warning threshold set, its sends are not checked at all
+    pub fn limit(self: &Arc<Self>, max_bytes: Option<usize>) -> Option<Arc<SizeLimit>> {
+        if max_bytes.is_none() && self.warn_bytes.is_none() {
+            return None;
+        }
+        self.is_checking.store(true, Ordering::Relaxed);
+        Some(Arc::new(SizeLimit {
+            max_bytes,
+            warn_bytes: self.warn_bytes,
+            sizes: self.clone(),
+            rejected: AtomicUsize::new(0),
+        }))
+    }
+
+    fn report(&self, type_id: TypeId, event: OversizedMessage) {
+        let now = Instant::now();
+        {
+            let mut reported = self.reported.lock().unwrap();
+            let key = (type_id, event.is_rejected);
+            if reported.get(&key).is_some_and(|last| now.saturating_duration_since(*last) < REPORT_INTERVAL) {
+                return;
+            }
+            reported.insert(key, now);
+        }
+        let _ = self.event_bus.publish(OVERSIZED_TOPIC, event);
+    }
+}
+
+/// Limit and warning threshold of a single Actor, only exists if at least one of them is set
+pub(crate) struct SizeLimit {
+    max_bytes: Option<usize>,
+    warn_bytes: Option<usize>,
+    sizes: Arc<MessageSizes>,
+    rejected: AtomicUsize,
+}
+
+impl SizeLimit {
+    /// Measures the message through [ActorMessage.approx_size](../prelude/trait.ActorMessage.html#method.approx_size), `Ok` for unchecked types
+    pub fn admit<M>(&self, msg: &M, target: &ActorAddress) -> Result<(), Oversized>
+    where
+        M: ActorMessage + 'static,
+    {
+        if self.sizes.unchecked.read().unwrap().contains(&TypeId::of::<M>()) {
+            return Ok(());
+        }
+        self.check(msg.approx_size(), TypeId::of::<M>(), std::any::type_name::<M>(), target)
+    }
+
+    pub fn check(&self, size: usize, type_id: TypeId, type_name: &'static str, target: &ActorAddress) -> Result<(), Oversized> {
+        if let Some(limit) = self.max_bytes {
+            if size > limit {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                self.report(size, limit, true, type_id, type_name, target);
+                return Err(Oversized { size, limit, type_name });
+            }
+        }
+        if let Some(threshold) = self.warn_bytes {
+            if size > threshold {
+                self.report(size, threshold, false, type_id, type_name, target);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get_rejected(&self) -> usize {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    fn report(&self, size: usize, threshold: usize, is_rejected: bool, type_id: TypeId, type_name: &'static str, target: &ActorAddress) {
+        let event = OversizedMessage {
+            sender: SENDER.with(|current| current.borrow().clone()),
+            target: target.clone(),
+            type_name,
+            size,
+            threshold,
+            is_rejected,
+        };
+        self.sizes.report(type_id, event);
+    }
+}
@@ -3,4 +3,5 @@ pub enum MessageType {
     Other,
     ActorStopMessage,
     SystemStopMessage,
+    SuspensionEndedMessage,
 }
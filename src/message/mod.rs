@@ -1,11 +1,26 @@
 pub mod actor_message;
 pub mod actor_stop_message;
+pub mod child_terminated_message;
 pub mod envelope;
+pub mod headers;
+pub mod message_size;
 pub mod message_type;
+pub mod provenance;
+pub mod receipt;
+pub mod send_error;
+pub(crate) mod serialized_dispatch;
 pub mod serialized_message;
+pub mod suspension_ended_message;
 pub mod system_stop_message;
+pub mod trace;
 
 pub mod prelude {
     pub use crate::message::actor_message::ActorMessage;
+    pub use crate::message::headers::{HeaderKey, HeaderMapping, HeaderValue, Headers, Propagate};
+    pub use crate::message::message_size::{OversizedMessage, SizeUnchecked, OVERSIZED_TOPIC};
+    pub use crate::message::provenance::{Audited, Provenance, ProvenanceAction, ProvenanceHop};
+    pub use crate::message::receipt::{Receipt, ReceiptHandle};
+    pub use crate::message::send_error::SendError;
     pub use crate::message::serialized_message::SerializedMessage;
+    pub use crate::message::trace::TraceId;
 }
@@ -0,0 +1,273 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::message::headers::{HeaderKey, HeaderValue, Headers, Reader};
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Full chain of an audited message, never propagated
+pub(crate) const PROVENANCE_HEADER: (&str, HeaderKey) = ("tyractorsaur.provenance", HeaderKey(3));
+/// Derivation link that is attached to every send while an audited message is handled, never propagated
+pub(crate) const DERIVED_HEADER: (&str, HeaderKey) = ("tyractorsaur.provenance_parent", HeaderKey(4));
+
+static NEXT_MESSAGE: AtomicU64 = AtomicU64::new(1);
+
+/// Marks messages that carry a [Provenance](./struct.Provenance.html) chain
+///
+/// See [ActorSystem.register_audited](../prelude/struct.ActorSystem.html#method.register_audited)
+pub trait Audited: ActorMessage {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvenanceAction {
+    Handled,
+    /// passed on through [ActorContext.forward](../prelude/struct.ActorContext.html#method.forward)
+    Forwarded,
+    /// sent while the audited message with the given id has been handled
+    DerivedFrom(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvenanceHop {
+    pub actor: ActorAddress,
+    pub at: SystemTime,
+    pub action: ProvenanceAction,
+}
+
+/// Append-only hop log of an audited message, see [ActorContext.provenance](../prelude/struct.ActorContext.html#method.provenance)
+///
+/// The chain is carried within the [Headers](./struct.Headers.html) of the message, so that it is encoded together with them for remote systems.
+/// Every hop holds a full [ActorAddress](./struct.ActorAddress.html) and the whole chain is copied on every hop, so that its memory grows linearly with `general.max_provenance_hops`.
+/// Hops beyond that limit are not recorded, but counted in `truncated_hops`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Provenance {
+    pub message_id: u64,
+    pub hops: Vec<ProvenanceHop>,
+    pub truncated_hops: u64,
+}
+
+impl Provenance {
+    pub(crate) fn new(hops: Vec<ProvenanceHop>) -> Self {
+        Self {
+            message_id: NEXT_MESSAGE.fetch_add(1, Ordering::Relaxed),
+            hops,
+            truncated_hops: 0,
+        }
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_hops > 0
+    }
+
+    pub(crate) fn push(&mut self, hop: ProvenanceHop, max_hops: usize) {
+        if self.hops.len() < max_hops {
+            self.hops.push(hop);
+        } else {
+            self.truncated_hops += 1;
+        }
+    }
+
+    /// Renders the chain for an audit log
+    ///
+    /// Timestamps are nanoseconds since the unix epoch
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"message_id\":{},\"truncated_hops\":{},\"hops\":[",
+            self.message_id, self.truncated_hops
+        );
+        for (index, hop) in self.hops.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let address = &hop.actor;
+            json.push_str("{\"actor\":{");
+            for (field, value) in [
+                ("remote", &address.remote),
+                ("system", &address.system),
+                ("pool", &address.pool),
+                ("actor", &address.actor),
+            ]
+            .iter()
+            {
+                let _ = write!(json, "\"{}\":\"{}\",", field, escape(value));
+            }
+            json.pop();
+            let _ = write!(json, "}},\"at\":{},", nanos(hop.at));
+            match hop.action {
+                ProvenanceAction::Handled => json.push_str("\"action\":\"Handled\"}"),
+                ProvenanceAction::Forwarded => json.push_str("\"action\":\"Forwarded\"}"),
+                ProvenanceAction::DerivedFrom(parent) => {
+                    let _ = write!(json, "\"action\":\"DerivedFrom\",\"parent\":{}}}", parent);
+                }
+            }
+        }
+        json.push_str("]}");
+        json
+    }
+
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.message_id.to_le_bytes());
+        bytes.extend_from_slice(&self.truncated_hops.to_le_bytes());
+        bytes.extend_from_slice(&(self.hops.len() as u32).to_le_bytes());
+        for hop in self.hops.iter() {
+            encode_hop(hop, &mut bytes);
+        }
+        bytes
+    }
+
+    pub(crate) fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader::new(bytes);
+        let message_id = u64::from_le_bytes(reader.take(8)?.try_into().ok()?);
+        let truncated_hops = u64::from_le_bytes(reader.take(8)?.try_into().ok()?);
+        let count = u32::from_le_bytes(reader.take(4)?.try_into().ok()?);
+        let mut hops = Vec::new();
+        for _ in 0..count {
+            hops.push(decode_hop(&mut reader)?);
+        }
+        Some(Self {
+            message_id,
+            hops,
+            truncated_hops,
+        })
+    }
+
+    pub(crate) fn from_headers(headers: &Headers) -> Option<Self> {
+        match headers.get(PROVENANCE_HEADER.1)? {
+            HeaderValue::Bytes(bytes) => Self::decode(bytes),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_header(&self) -> HeaderValue {
+        HeaderValue::Bytes(Arc::from(self.encode()))
+    }
+}
+
+/// Returns the chain of an audited message that is about to be handled, including its derivation link
+pub(crate) fn receive(headers: &Headers) -> Provenance {
+    if let Some(provenance) = Provenance::from_headers(headers) {
+        return provenance;
+    }
+    let derived = match headers.get(DERIVED_HEADER.1) {
+        Some(HeaderValue::Bytes(bytes)) => decode_hop(&mut Reader::new(bytes)),
+        _ => None,
+    };
+    Provenance::new(derived.into_iter().collect())
+}
+
+pub(crate) fn derivation(parent: u64, actor: &ActorAddress) -> HeaderValue {
+    let mut bytes = Vec::new();
+    encode_hop(
+        &ProvenanceHop {
+            actor: actor.clone(),
+            at: SystemTime::now(),
+            action: ProvenanceAction::DerivedFrom(parent),
+        },
+        &mut bytes,
+    );
+    HeaderValue::Bytes(Arc::from(bytes))
+}
+
+fn encode_hop(hop: &ProvenanceHop, bytes: &mut Vec<u8>) {
+    let address = &hop.actor;
+    for value in [&address.remote, &address.system, &address.pool, &address.actor].iter() {
+        bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(value.as_bytes());
+    }
+    bytes.extend_from_slice(&nanos(hop.at).to_le_bytes());
+    match hop.action {
+        ProvenanceAction::Handled => bytes.push(0),
+        ProvenanceAction::Forwarded => bytes.push(1),
+        ProvenanceAction::DerivedFrom(parent) => {
+            bytes.push(2);
+            bytes.extend_from_slice(&parent.to_le_bytes());
+        }
+    }
+}
+
+fn decode_hop(reader: &mut Reader) -> Option<ProvenanceHop> {
+    let mut fields = Vec::with_capacity(4);
+    for _ in 0..4 {
+        let len = u32::from_le_bytes(reader.take(4)?.try_into().ok()?) as usize;
+        fields.push(String::from(std::str::from_utf8(reader.take(len)?).ok()?));
+    }
+    let at = UNIX_EPOCH + Duration::from_nanos(u64::from_le_bytes(reader.take(8)?.try_into().ok()?));
+    let action = match reader.take(1)?[0] {
+        0 => ProvenanceAction::Handled,
+        1 => ProvenanceAction::Forwarded,
+        2 => ProvenanceAction::DerivedFrom(u64::from_le_bytes(reader.take(8)?.try_into().ok()?)),
+        _ => return None,
+    };
+    let actor = fields.pop()?;
+    let pool = fields.pop()?;
+    let system = fields.pop()?;
+    let remote = fields.pop()?;
+    Some(ProvenanceHop {
+        actor: ActorAddress {
+            remote,
+            system,
+            pool,
+            actor,
+        },
+        at,
+        action,
+    })
+}
+
+fn nanos(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_nanos() as u64)
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Message types registered as [Audited](./trait.Audited.html)
+#[derive(Clone)]
+pub struct AuditRegistry {
+    types: Arc<RwLock<HashSet<TypeId>>>,
+    is_enabled: Arc<AtomicBool>,
+}
+
+impl AuditRegistry {
+    pub fn new() -> Self {
+        Self {
+            types: Arc::new(RwLock::new(HashSet::new())),
+            is_enabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn register<M>(&self)
+    where
+        M: Audited + 'static,
+    {
+        self.types.write().unwrap().insert(TypeId::of::<M>());
+        self.is_enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Only a single atomic load as long as no type has been registered
+    pub fn is_audited(&self, type_id: TypeId) -> bool {
+        self.is_enabled.load(Ordering::Relaxed) && self.types.read().unwrap().contains(&type_id)
+    }
+}
+
+impl Default for AuditRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
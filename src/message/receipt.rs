@@ -0,0 +1,83 @@
+use crate::system::dead_letters::DeadLetterReason;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Outcome of a message sent through [ActorWrapper.send_with_receipt](../prelude/struct.ActorWrapper.html#method.send_with_receipt)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Receipt {
+    /// the handler returned, including handlers that failed through a [TryHandler](../prelude/trait.TryHandler.html)
+    Handled,
+    /// the handler panicked, the [RestartPolicy](../prelude/enum.RestartPolicy.html) of the Actor decides what happens afterwards
+    Panicked,
+    /// a newer message of the same type replaced it, see [ActorBuilder.add_superseding](../prelude/struct.ActorBuilder.html#method.add_superseding)
+    Superseded,
+    /// the message has never been handled, messages still queued when the Actor stopped are [Undeliverable](../prelude/enum.DeadLetterReason.html#variant.Undeliverable)
+    Dropped(DeadLetterReason),
+}
+
+#[derive(Default)]
+struct ReceiptState {
+    receipt: Mutex<Option<Receipt>>,
+    settled: Condvar,
+}
+
+impl ReceiptState {
+    /// Only the first receipt counts
+    fn settle(&self, receipt: Receipt) {
+        let mut current = self.receipt.lock().unwrap();
+        if current.is_none() {
+            *current = Some(receipt);
+            self.settled.notify_all();
+        }
+    }
+}
+
+/// Travels with the message, settles the receipt as dropped if the envelope is discarded without being handled
+pub(crate) struct ReceiptSlot(Arc<ReceiptState>);
+
+impl ReceiptSlot {
+    pub fn settle(self, receipt: Receipt) {
+        self.0.settle(receipt);
+    }
+}
+
+impl Drop for ReceiptSlot {
+    fn drop(&mut self) {
+        self.0.settle(Receipt::Dropped(DeadLetterReason::Undeliverable));
+    }
+}
+
+/// Waits for the [Receipt] of a message, see [ActorWrapper.send_with_receipt](../prelude/struct.ActorWrapper.html#method.send_with_receipt)
+#[derive(Clone)]
+pub struct ReceiptHandle(Arc<ReceiptState>);
+
+impl ReceiptHandle {
+    /// `None` while the message has neither been handled nor dropped
+    pub fn poll(&self) -> Option<Receipt> {
+        *self.0.receipt.lock().unwrap()
+    }
+
+    /// Blocks until the receipt is settled, `None` if that did not happen within `timeout`
+    pub fn wait(&self, timeout: Duration) -> Option<Receipt> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut receipt = self.0.receipt.lock().unwrap();
+        while receipt.is_none() {
+            receipt = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return None;
+                    }
+                    self.0.settled.wait_timeout(receipt, remaining).unwrap().0
+                }
+                None => self.0.settled.wait(receipt).unwrap(),
+            };
+        }
+        *receipt
+    }
+}
+
+pub(crate) fn channel() -> (ReceiptSlot, ReceiptHandle) {
+    let state = Arc::new(ReceiptState::default());
+    (ReceiptSlot(state.clone()), ReceiptHandle(state))
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+
+/// Reason why a message has not been queued, the message is handed back so that it can be retried or spilled elsewhere
+///
+/// See [ActorWrapper.try_send](../prelude/struct.ActorWrapper.html#method.try_send) and [ActorWrapper.send_timeout](../prelude/struct.ActorWrapper.html#method.send_timeout)
+#[derive(Clone, PartialEq)]
+pub enum SendError<M> {
+    /// the bounded mailbox of the target is full, only returned by sends that do not wait
+    Full(M),
+    /// the bounded mailbox of the target stayed full until the timeout
+    Timeout(M),
+    /// the target has stopped and does not accept any more messages
+    Stopped(M),
+    /// the system exceeded its message budget, see [MessageBudgetConfig](../prelude/struct.MessageBudgetConfig.html)
+    Overloaded(M),
+    /// the Actor sent to its own full bounded mailbox, waiting for room would never end, see [ActorContext.send_to_self](../prelude/struct.ActorContext.html#method.send_to_self)
+    WouldDeadlock(M),
+    /// the approximate size of the message exceeds the limit of the target
+    MessageTooLarge {
+        msg: M,
+        size: usize,
+        limit: usize,
+        type_name: &'static str,
+    },
+}
+
+impl<M> SendError<M> {
+    /// Returns the message that has not been queued
+    pub fn into_inner(self) -> M {
+        match self {
+            SendError::Full(msg) | SendError::Timeout(msg) | SendError::Stopped(msg) | SendError::Overloaded(msg) => msg,
+            SendError::WouldDeadlock(msg) => msg,
+            SendError::MessageTooLarge { msg, .. } => msg,
+        }
+    }
+
+    /// Replaces the message that has not been queued, the reason stays the same
+    pub(crate) fn map<N, F>(self, f: F) -> SendError<N>
+    where
+        F: FnOnce(M) -> N,
+    {
+        match self {
+            SendError::Full(msg) => SendError::Full(f(msg)),
+            SendError::Timeout(msg) => SendError::Timeout(f(msg)),
+            SendError::Stopped(msg) => SendError::Stopped(f(msg)),
+            SendError::Overloaded(msg) => SendError::Overloaded(f(msg)),
+            SendError::WouldDeadlock(msg) => SendError::WouldDeadlock(f(msg)),
+            SendError::MessageTooLarge {
+                msg,
+                size,
+                limit,
+                type_name,
+            } => SendError::MessageTooLarge {
+                msg: f(msg),
+                size,
+                limit,
+                type_name,
+            },
+        }
+    }
+
+    /// `true` if a later send of the same message may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, SendError::Full(_) | SendError::Timeout(_) | SendError::Overloaded(_))
+    }
+}
+
+/// Messages are not required to implement `Debug`, so they are never printed
+impl<M> fmt::Debug for SendError<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Full(_) => f.write_str("Full(..)"),
+            SendError::Timeout(_) => f.write_str("Timeout(..)"),
+            SendError::Stopped(_) => f.write_str("Stopped(..)"),
+            SendError::Overloaded(_) => f.write_str("Overloaded(..)"),
+            SendError::WouldDeadlock(_) => f.write_str("WouldDeadlock(..)"),
+            SendError::MessageTooLarge {
+                size,
+                limit,
+                type_name,
+                ..
+            } => f
+                .debug_struct("MessageTooLarge")
+                .field("size", size)
+                .field("limit", limit)
+                .field("type_name", type_name)
+                .finish_non_exhaustive(),
+        }
+    }
+}
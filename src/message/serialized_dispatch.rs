@@ -0,0 +1,106 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::serialized_message::SerializedMessage;
+use std::collections::HashMap;
+use std::panic::UnwindSafe;
+use std::sync::Arc;
+
+/// Deserializes the content of a [SerializedMessage] and sends the typed message to the Actor, see [ActorBuilder.register_serialized](../prelude/struct.ActorBuilder.html#method.register_serialized)
+pub(crate) type Decoder<A> = Arc<dyn Fn(&ActorWrapper<A>, &[u8]) -> Result<(), String> + Send + Sync>;
+
+type Dispatch = Box<dyn Fn(u16, &[u8]) -> Option<Result<(), String>> + Send + Sync>;
+
+/// Outcome of [SerializedDispatch.dispatch](#method.dispatch)
+pub(crate) enum Dispatched {
+    Sent,
+    /// the content could not be deserialized, the message is handed to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+    Undecodable(Box<SerializedMessage>),
+    /// the message carries a tag without a decoder, it is handed to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+    Unregistered(Box<SerializedMessage>),
+    /// the message carries no type tag, it is handed to [Actor.handle_serialized_message_mut](../prelude/trait.Actor.html#method.handle_serialized_message_mut)
+    Untyped(Box<SerializedMessage>),
+}
+
+/// Hands a [SerializedMessage] without type tag to the Actor, so it is handled within the mailbox order like any other message
+pub(crate) struct SerializedRequest {
+    msg: SerializedMessage,
+}
+
+impl SerializedRequest {
+    pub fn new(msg: SerializedMessage) -> Self {
+        Self { msg }
+    }
+}
+
+impl ActorMessage for SerializedRequest {}
+
+impl<A> Handler<SerializedRequest> for A
+where
+    A: Actor + Sized + 'static,
+{
+    fn handle(&mut self, msg: SerializedRequest, context: &ActorContext<A>) {
+        let reply_to = msg.msg.reply_to.clone();
+        let correlation_id = msg.msg.correlation_id;
+        let mut response = match self.handle_serialized_message_mut(msg.msg, context) {
+            Some(response) => response,
+            None => return,
+        };
+        match reply_to {
+            Some(reply_to) => {
+                if response.correlation_id.is_none() {
+                    response.correlation_id = correlation_id;
+                }
+                context.system.send_to_address(&reply_to, response);
+            }
+            None => context
+                .system
+                .report_dead_letter(context.actor_ref.get_address(), std::any::type_name::<SerializedMessage>()),
+        }
+    }
+}
+
+/// Decoders of a running Actor by type tag
+pub(crate) struct SerializedDispatch {
+    address: ActorAddress,
+    dispatch: Dispatch,
+    metrics: ActorMetrics,
+}
+
+impl SerializedDispatch {
+    pub fn new<A>(wrapper: ActorWrapper<A>, decoders: HashMap<u16, Decoder<A>>, metrics: ActorMetrics) -> Self
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        Self {
+            address: wrapper.get_address().clone(),
+            dispatch: Box::new(move |type_tag, content| {
+                decoders.get(&type_tag).map(|decoder| decoder(&wrapper, content))
+            }),
+            metrics,
+        }
+    }
+
+    pub fn dispatch(&self, msg: SerializedMessage) -> Dispatched {
+        let type_tag = match msg.type_tag {
+            Some(type_tag) => type_tag,
+            None => return Dispatched::Untyped(Box::new(msg)),
+        };
+        match (self.dispatch)(type_tag, &msg.content) {
+            None => Dispatched::Unregistered(Box::new(msg)),
+            Some(Ok(())) => Dispatched::Sent,
+            Some(Err(reason)) => {
+                self.metrics.increment_undecodable();
+                println!(
+                    "DESERIALIZATION FAILED: type tag {} for {:?}: {}",
+                    type_tag, self.address, reason
+                );
+                Dispatched::Undecodable(Box::new(msg))
+            }
+        }
+    }
+}
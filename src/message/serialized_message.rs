@@ -1,4 +1,6 @@
+use crate::actor::actor_address::ActorAddress;
 use crate::message::actor_message::ActorMessage;
+use serde::{Deserialize, Serialize};
 
 /// For Remote message handling
 ///
@@ -8,16 +10,80 @@ use crate::message::actor_message::ActorMessage;
 /// and it may also include some additional fields to make deserialization easier for Endusers
 ///
 /// [ActorSystem.send_to_address](../prelude/struct.ActorSystem.html#method.send_to_address) uses this object to send serialized messages to Actors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SerializedMessage {
     pub content: Vec<u8>,
+    /// encoded through [ActorSystem.encode_headers](../prelude/struct.ActorSystem.html#method.encode_headers), empty if the message carries no headers
+    pub headers: Vec<u8>,
+    /// protocol version of `content`, checked and upgraded against the protocol of the receiving Actor, see [ActorBuilder.protocol_version](../prelude/struct.ActorBuilder.html#method.protocol_version)
+    pub version: Option<u32>,
+    /// message type of `content`, deserialized and handled as a typed message if the receiving Actor registered the tag, see [ActorBuilder.register_serialized](../prelude/struct.ActorBuilder.html#method.register_serialized)
+    pub type_tag: Option<u16>,
+    /// copied into the response of [ActorContext.reply_serialized](../prelude/struct.ActorContext.html#method.reply_serialized)
+    #[serde(default)]
+    pub correlation_id: Option<u128>,
+    /// receiver of the response of [ActorContext.reply_serialized](../prelude/struct.ActorContext.html#method.reply_serialized)
+    #[serde(default)]
+    pub reply_to: Option<ActorAddress>,
+    /// format of `content`, not interpreted by the system
+    #[serde(default)]
+    pub content_type: Option<String>,
 }
 
 impl SerializedMessage {
     pub fn new(content: Vec<u8>) -> Self {
         Self {
-            content
+            content,
+            headers: Vec::new(),
+            version: None,
+            type_tag: None,
+            correlation_id: None,
+            reply_to: None,
+            content_type: None,
         }
     }
+
+    pub fn with_headers(content: Vec<u8>, headers: Vec<u8>) -> Self {
+        Self {
+            content,
+            headers,
+            version: None,
+            type_tag: None,
+            correlation_id: None,
+            reply_to: None,
+            content_type: None,
+        }
+    }
+
+    /// Size of the content and the encoded headers, checked against `general.max_message_bytes`
+    pub fn payload_size(&self) -> usize {
+        self.content.len() + self.headers.len()
+    }
+
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_type_tag(mut self, type_tag: u16) -> Self {
+        self.type_tag = Some(type_tag);
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: u128) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    pub fn with_reply_to(mut self, reply_to: ActorAddress) -> Self {
+        self.reply_to = Some(reply_to);
+        self
+    }
+
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
 }
 
 impl ActorMessage for SerializedMessage {}
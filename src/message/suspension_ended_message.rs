@@ -0,0 +1,8 @@
+use crate::actor::suspension::SuspensionEndReason;
+use crate::message::actor_message::ActorMessage;
+
+pub struct SuspensionEndedMessage {
+    pub reason: SuspensionEndReason,
+}
+
+impl ActorMessage for SuspensionEndedMessage {}
@@ -0,0 +1,37 @@
+use crate::message::headers::{self, HeaderKey, HeaderValue, Headers};
+use std::fmt;
+
+/// Trace id of the message, copied onto every send made while it is handled
+pub(crate) const TRACE_HEADER: (&str, HeaderKey) = ("tyractorsaur.trace", HeaderKey(6));
+
+/// Identifies a logical request across all Actors it passes through
+///
+/// Set with [ActorWrapper.send_traced](../prelude/struct.ActorWrapper.html#method.send_traced) and read with [ActorContext.trace_id](../prelude/struct.ActorContext.html#method.trace_id).
+/// Every message sent while a traced message is handled inherits its trace id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TraceId(pub u64);
+
+impl TraceId {
+    pub(crate) fn to_header(self) -> HeaderValue {
+        HeaderValue::U64(self.0)
+    }
+}
+
+impl fmt::Display for TraceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Trace id carried by the given headers
+pub(crate) fn of(headers: Option<&Headers>) -> Option<TraceId> {
+    match headers?.get(TRACE_HEADER.1)? {
+        HeaderValue::U64(id) => Some(TraceId(*id)),
+        _ => None,
+    }
+}
+
+/// Trace id of the message that is currently handled on this thread
+pub(crate) fn current() -> Option<TraceId> {
+    headers::with_propagated(of)
+}
@@ -0,0 +1,509 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::collections::VecDeque;
+use std::panic::UnwindSafe;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckoutError {
+    /// all Actors stayed leased until the timeout
+    Timeout,
+    /// the lease exceeded the maximum lease duration and the Actor has been reclaimed by the pool
+    LeaseExpired,
+}
+
+/// Counters of a [CheckoutPool](./struct.CheckoutPool.html)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CheckoutPoolMetrics {
+    pub leases_outstanding: usize,
+    pub checkouts: u64,
+    pub total_wait_time: Duration,
+    pub max_wait_time: Duration,
+    /// leases that were reclaimed after exceeding the maximum lease duration
+    pub forced_reclaims: u64,
+}
+
+struct Lease {
+    id: u64,
+    deadline: Option<Instant>,
+}
+
+struct Slot<A>
+where
+    A: Actor + 'static,
+{
+    actor: ActorWrapper<A>,
+    lease: Mutex<Option<Lease>>,
+}
+
+struct PoolState {
+    free: VecDeque<usize>,
+    waiters: VecDeque<u64>,
+    next_ticket: u64,
+    next_lease: u64,
+    metrics: CheckoutPoolMetrics,
+}
+
+type Reset<A> = Arc<dyn Fn(&ActorWrapper<A>) + Send + Sync>;
+
+struct PoolInner<A>
+where
+    A: Actor + 'static,
+{
+    slots: Vec<Slot<A>>,
+    state: Mutex<PoolState>,
+    available: Condvar,
+    max_lease: Option<Duration>,
+    reset: Option<Reset<A>>,
+}
+
+/// Fixed set of identical Actors that are checked out for exclusive temporary use
+///
+/// Unlike a router, every message sent through a [CheckedOutActor](./struct.CheckedOutActor.html) reaches the same Actor and no other lease holder can interleave messages with it.
+/// The pooled Actors themselves are never handed out.
+///
+/// Waiting checkouts are served in FIFO order.
+/// Leases that exceed the [maximum lease duration](#method.with_max_lease) are reclaimed by the next checkout that has to wait, or by the next send of the holder.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::CheckoutPool;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Step { lease: u64 }
+/// impl ActorMessage for Step {}
+///
+/// struct Connection { seen: Arc<Mutex<Vec<u64>>> }
+/// impl Actor for Connection {}
+/// impl Handler<Step> for Connection {
+///     fn handle(&mut self, msg: Step, _context: &ActorContext<Self>) {
+///         self.seen.lock().unwrap().push(msg.lease);
+///     }
+/// }
+/// #[derive(Clone)]
+/// struct ConnectionFactory { seen: Arc<Mutex<Vec<Arc<Mutex<Vec<u64>>>>>> }
+/// impl ActorFactory<Connection> for ConnectionFactory {
+///     fn new_actor(&self, _context: ActorContext<Connection>) -> Connection {
+///         let seen = Arc::new(Mutex::new(Vec::new()));
+///         self.seen.lock().unwrap().push(seen.clone());
+///         Connection { seen }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let pool = CheckoutPool::new(actor_system.builder(), "connection", 3, ConnectionFactory { seen: seen.clone() }).unwrap();
+///
+/// // 8 threads compete for 3 Actors, each lease sends a sequence of 5 related messages
+/// let threads: Vec<_> = (0..8).map(|_| {
+///     let pool = pool.clone();
+///     std::thread::spawn(move || {
+///         for _ in 0..20 {
+///             let connection = pool.checkout(Duration::from_secs(10)).unwrap();
+///             for _ in 0..5 {
+///                 connection.send(Step { lease: connection.lease_id() }).unwrap();
+///             }
+///             // checked in on drop
+///         }
+///     })
+/// }).collect();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+/// sleep(Duration::from_millis(200));
+///
+/// // every lease forms a single uninterrupted block of 5 messages on its Actor
+/// let mut total = 0;
+/// for seen in seen.lock().unwrap().iter() {
+///     let seen = seen.lock().unwrap();
+///     total += seen.len();
+///     for block in seen.chunks(5) {
+///         assert!(block.iter().all(|lease| *lease == block[0]));
+///     }
+/// }
+/// assert_eq!(total, 8 * 20 * 5);
+/// assert_eq!(pool.metrics().leases_outstanding, 0);
+/// assert_eq!(pool.metrics().checkouts, 160);
+/// ```
+pub struct CheckoutPool<A>
+where
+    A: Actor + 'static,
+{
+    inner: Arc<PoolInner<A>>,
+}
+
+impl<A> Clone for CheckoutPool<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<A> CheckoutPool<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Spawns `size` Actors named `{name}-{index}` through the given builder
+    ///
+    /// `None` if any of the Actors is already running, see [ActorBuilder.spawn](../prelude/struct.ActorBuilder.html#method.spawn)
+    pub fn new<P>(builder: ActorBuilder<A>, name: &str, size: usize, factory: P) -> Option<Self>
+    where
+        P: ActorFactory<A> + Clone + 'static,
+    {
+        let mut slots = Vec::with_capacity(size);
+        for index in 0..size {
+            let actor = builder.spawn(format!("{}-{}", name, index), factory.clone())?;
+            slots.push(Slot {
+                actor,
+                lease: Mutex::new(None),
+            });
+        }
+        Some(Self {
+            inner: Arc::new(PoolInner {
+                slots,
+                state: Mutex::new(PoolState {
+                    free: (0..size).collect(),
+                    waiters: VecDeque::new(),
+                    next_ticket: 0,
+                    next_lease: 1,
+                    metrics: CheckoutPoolMetrics::default(),
+                }),
+                available: Condvar::new(),
+                max_lease: None,
+                reset: None,
+            }),
+        })
+    }
+
+    /// Leases are reclaimed once they are held longer than `max_lease`, the holder receives [CheckoutError::LeaseExpired](./enum.CheckoutError.html) on its next send
+    ///
+    /// Must be configured before the pool is cloned
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::{CheckoutError, CheckoutPool};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Query {}
+    /// impl ActorMessage for Query {}
+    /// struct Reset {}
+    /// impl ActorMessage for Reset {}
+    ///
+    /// struct Session { resets: Arc<Mutex<usize>> }
+    /// impl Actor for Session {}
+    /// impl Handler<Query> for Session {
+    ///     fn handle(&mut self, _msg: Query, _context: &ActorContext<Self>) {}
+    /// }
+    /// impl Handler<Reset> for Session {
+    ///     fn handle(&mut self, _msg: Reset, _context: &ActorContext<Self>) {
+    ///         *self.resets.lock().unwrap() += 1;
+    ///     }
+    /// }
+    /// #[derive(Clone)]
+    /// struct SessionFactory { resets: Arc<Mutex<usize>> }
+    /// impl ActorFactory<Session> for SessionFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Session>) -> Session {
+    ///         Session { resets: self.resets.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let resets = Arc::new(Mutex::new(0));
+    /// let pool = CheckoutPool::new(actor_system.builder(), "session", 1, SessionFactory { resets: resets.clone() })
+    ///     .unwrap()
+    ///     .with_max_lease(Duration::from_millis(100))
+    ///     .with_reset(|| Reset {});
+    ///
+    /// let held = pool.checkout(Duration::from_secs(1)).unwrap();
+    /// held.send(Query {}).unwrap();
+    /// assert_eq!(pool.checkout(Duration::from_millis(10)).err(), Some(CheckoutError::Timeout));
+    ///
+    /// // the waiting checkout reclaims the Actor once the lease expired
+    /// let session = pool.checkout(Duration::from_secs(1)).unwrap();
+    /// assert_eq!(held.send(Query {}), Err(CheckoutError::LeaseExpired));
+    /// assert_eq!(pool.metrics().forced_reclaims, 1);
+    /// session.checkin();
+    ///
+    /// // a leaked handle does not shrink the pool either
+    /// std::mem::forget(pool.checkout(Duration::from_secs(1)).unwrap());
+    /// assert!(pool.checkout(Duration::from_secs(1)).is_ok());
+    /// assert_eq!(pool.metrics().forced_reclaims, 2);
+    /// drop(held);
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(*resets.lock().unwrap(), 4);
+    /// ```
+    pub fn with_max_lease(mut self, max_lease: Duration) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("the pool has already been cloned")
+            .max_lease = Some(max_lease);
+        self
+    }
+
+    /// Sends the message created by `reset` to every Actor before it returns to the pool
+    ///
+    /// Must be configured before the pool is cloned
+    pub fn with_reset<M, F>(mut self, reset: F) -> Self
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        F: Fn() -> M + Send + Sync + 'static,
+    {
+        Arc::get_mut(&mut self.inner)
+            .expect("the pool has already been cloned")
+            .reset = Some(Arc::new(move |actor: &ActorWrapper<A>| {
+                let _ = actor.send(reset());
+            }));
+        self
+    }
+
+    /// Leases an Actor exclusively, waiting at most `timeout` for one to become available
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::CheckoutPool;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// #[derive(Clone)]
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let pool = CheckoutPool::new(actor_system.builder(), "worker", 1, WorkerFactory {}).unwrap();
+    /// let held = pool.checkout(Duration::from_secs(1)).unwrap();
+    ///
+    /// // waiters are queued one after another and served in the same order
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// let mut waiters = Vec::new();
+    /// for waiter in 0..5 {
+    ///     let pool = pool.clone();
+    ///     let order = order.clone();
+    ///     waiters.push(std::thread::spawn(move || {
+    ///         let worker = pool.checkout(Duration::from_secs(10)).unwrap();
+    ///         order.lock().unwrap().push(waiter);
+    ///         sleep(Duration::from_millis(10));
+    ///         worker.checkin();
+    ///     }));
+    ///     sleep(Duration::from_millis(50));
+    /// }
+    /// held.checkin();
+    /// for waiter in waiters {
+    ///     waiter.join().unwrap();
+    /// }
+    /// assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+    /// assert!(pool.metrics().max_wait_time >= Duration::from_millis(200));
+    /// ```
+    pub fn checkout(&self, timeout: Duration) -> Result<CheckedOutActor<A>, CheckoutError> {
+        let started = Instant::now();
+        let deadline = started + timeout;
+        let mut state = self.inner.state.lock().unwrap();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiters.push_back(ticket);
+        loop {
+            let next_expiry = self.reclaim_expired(&mut state);
+            if state.waiters.front() == Some(&ticket) && !state.free.is_empty() {
+                let slot = state.free.pop_front().unwrap();
+                state.waiters.pop_front();
+                let lease = state.next_lease;
+                state.next_lease += 1;
+                let waited = started.elapsed();
+                state.metrics.leases_outstanding += 1;
+                state.metrics.checkouts += 1;
+                state.metrics.total_wait_time += waited;
+                state.metrics.max_wait_time = state.metrics.max_wait_time.max(waited);
+                *self.inner.slots[slot].lease.lock().unwrap() = Some(Lease {
+                    id: lease,
+                    deadline: self.inner.max_lease.map(|max_lease| Instant::now() + max_lease),
+                });
+                // the next waiter may be served by another free Actor
+                self.inner.available.notify_all();
+                return Ok(CheckedOutActor {
+                    pool: self.clone(),
+                    slot,
+                    lease,
+                    checked_in: false,
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                state.waiters.retain(|waiter| *waiter != ticket);
+                self.inner.available.notify_all();
+                return Err(CheckoutError::Timeout);
+            }
+            let wake_at = next_expiry.map_or(deadline, |expiry| expiry.min(deadline));
+            state = self
+                .inner
+                .available
+                .wait_timeout(state, wake_at.saturating_duration_since(now))
+                .unwrap()
+                .0;
+        }
+    }
+
+    pub fn metrics(&self) -> CheckoutPoolMetrics {
+        self.inner.state.lock().unwrap().metrics
+    }
+
+    pub fn size(&self) -> usize {
+        self.inner.slots.len()
+    }
+
+    /// Returns expired leases to the free list, and the earliest deadline of the remaining leases
+    fn reclaim_expired(&self, state: &mut PoolState) -> Option<Instant> {
+        let now = Instant::now();
+        let mut next_expiry: Option<Instant> = None;
+        for (index, slot) in self.inner.slots.iter().enumerate() {
+            let mut lease = slot.lease.lock().unwrap();
+            let deadline = match lease.as_ref().and_then(|lease| lease.deadline) {
+                Some(deadline) => deadline,
+                None => continue,
+            };
+            if deadline > now {
+                next_expiry = Some(next_expiry.map_or(deadline, |expiry| expiry.min(deadline)));
+                continue;
+            }
+            *lease = None;
+            self.reset(&slot.actor);
+            state.free.push_back(index);
+            state.metrics.leases_outstanding -= 1;
+            state.metrics.forced_reclaims += 1;
+        }
+        next_expiry
+    }
+
+    fn reset(&self, actor: &ActorWrapper<A>) {
+        if let Some(reset) = &self.inner.reset {
+            reset(actor);
+        }
+    }
+
+    /// Runs `f` with the Actor of the slot, as long as the lease is still valid
+    ///
+    /// The lease stays locked while `f` runs, so that a reclaim can never interleave with it
+    fn with_lease<F>(&self, slot: usize, lease: u64, f: F) -> Result<(), CheckoutError>
+    where
+        F: FnOnce(&ActorWrapper<A>),
+    {
+        let slot_ref = &self.inner.slots[slot];
+        let mut current = slot_ref.lease.lock().unwrap();
+        match current.as_ref() {
+            Some(current_lease) if current_lease.id == lease => {
+                if current_lease.deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+                    *current = None;
+                    self.reset(&slot_ref.actor);
+                    drop(current);
+                    let mut state = self.inner.state.lock().unwrap();
+                    state.free.push_back(slot);
+                    state.metrics.leases_outstanding -= 1;
+                    state.metrics.forced_reclaims += 1;
+                    self.inner.available.notify_all();
+                    return Err(CheckoutError::LeaseExpired);
+                }
+                f(&slot_ref.actor);
+                Ok(())
+            }
+            _ => Err(CheckoutError::LeaseExpired),
+        }
+    }
+
+    fn checkin(&self, slot: usize, lease: u64) {
+        let slot_ref = &self.inner.slots[slot];
+        {
+            let mut current = slot_ref.lease.lock().unwrap();
+            match current.as_ref() {
+                Some(current_lease) if current_lease.id == lease => {
+                    *current = None;
+                    self.reset(&slot_ref.actor);
+                }
+                // already reclaimed
+                _ => return,
+            }
+        }
+        let mut state = self.inner.state.lock().unwrap();
+        state.free.push_back(slot);
+        state.metrics.leases_outstanding -= 1;
+        self.inner.available.notify_all();
+    }
+}
+
+/// Exclusive lease of an Actor of a [CheckoutPool](./struct.CheckoutPool.html), checked in when dropped
+pub struct CheckedOutActor<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pool: CheckoutPool<A>,
+    slot: usize,
+    lease: u64,
+    checked_in: bool,
+}
+
+impl<A> CheckedOutActor<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Fails with [CheckoutError::LeaseExpired](./enum.CheckoutError.html) once the pool reclaimed the Actor
+    pub fn send<M>(&self, msg: M) -> Result<(), CheckoutError>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.pool.with_lease(self.slot, self.lease, |actor| {
+            let _ = actor.send(msg);
+        })
+    }
+
+    /// Unique id of this lease within its pool
+    pub fn lease_id(&self) -> u64 {
+        self.lease
+    }
+
+    /// Returns the Actor to the pool, after sending it the configured reset message
+    pub fn checkin(mut self) {
+        self.checked_in = true;
+        self.pool.checkin(self.slot, self.lease);
+    }
+}
+
+impl<A> Drop for CheckedOutActor<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn drop(&mut self) {
+        if !self.checked_in {
+            self.pool.checkin(self.slot, self.lease);
+        }
+    }
+}
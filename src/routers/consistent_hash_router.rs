@@ -0,0 +1,110 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::context::ActorContext;
+use crate::routers::round_robin_router::{EmptyRouterPolicy, RoundRobinRouter, RoundRobinRouterFactory};
+use crate::routers::routing_strategy::RoutingStrategy;
+
+/// implements [ActorFactory](../prelude/trait.ActorFactory.html) to spawn a router that shards messages across its routees by their key
+///
+/// Shorthand for a [RoundRobinRouterFactory](./struct.RoundRobinRouterFactory.html) with [RoutingStrategy::ConsistentHash](./enum.RoutingStrategy.html#variant.ConsistentHash).
+/// Keys are taken from [RouterMessage::hashed](./struct.RouterMessage.html#method.hashed) or [RouterMessage::with_key](./struct.RouterMessage.html#method.with_key), messages without a key are routed round robin.
+/// Every routee is placed `virtual_nodes` times on the hash ring, so that adding or removing a routee only moves the keys of that routee
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::{AddActorMessage, ConsistentHashRouterFactory, HashableMessage, RemoveActorMessage, RouterMessage};
+/// use std::collections::HashMap;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Order { customer: u64 }
+/// impl ActorMessage for Order {}
+/// impl HashableMessage for Order {
+///     fn hash_key(&self) -> u64 {
+///         self.customer
+///     }
+/// }
+///
+/// type Shards = Arc<Mutex<HashMap<u64, &'static str>>>;
+///
+/// struct Shard { name: &'static str, shards: Shards }
+/// impl Actor for Shard {}
+/// impl Handler<Order> for Shard {
+///     fn handle(&mut self, msg: Order, _context: &ActorContext<Self>) {
+///         self.shards.lock().unwrap().insert(msg.customer, self.name);
+///     }
+/// }
+/// struct ShardFactory { name: &'static str, shards: Shards }
+/// impl ActorFactory<Shard> for ShardFactory {
+///     fn new_actor(&self, _context: ActorContext<Shard>) -> Shard {
+///         Shard { name: self.name, shards: self.shards.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let shards = Shards::default();
+/// let router = actor_system.builder().spawn("orders", ConsistentHashRouterFactory::new(128)).unwrap();
+/// let mut routees = Vec::new();
+/// for name in ["a", "b", "c", "d"].iter() {
+///     let routee = actor_system.builder().spawn(*name, ShardFactory { name: *name, shards: shards.clone() }).unwrap();
+///     router.send(AddActorMessage::new(routee.clone()));
+///     routees.push(routee);
+/// }
+/// let route_all = || {
+///     for customer in 0..200 {
+///         router.send(RouterMessage::hashed(Order { customer }));
+///     }
+///     sleep(Duration::from_millis(200));
+///     shards.lock().unwrap().clone()
+/// };
+///
+/// // the same key always ends up on the same routee
+/// let before = route_all();
+/// assert_eq!(before, route_all());
+/// assert_eq!(4, before.values().collect::<std::collections::HashSet<_>>().len());
+///
+/// // only the keys of the removed routee move
+/// router.send(RemoveActorMessage::new(routees[2].clone()));
+/// let after = route_all();
+/// for (customer, shard) in before.iter() {
+///     if *shard == "c" {
+///         assert_ne!("c", after[customer]);
+///     } else {
+///         assert_eq!(shard, &after[customer]);
+///     }
+/// }
+/// ```
+pub struct ConsistentHashRouterFactory {
+    router: RoundRobinRouterFactory,
+}
+
+impl ConsistentHashRouterFactory {
+    /// `virtual_nodes` is the number of points of every routee on the hash ring, see [RoundRobinRouterFactory.with_virtual_nodes](./struct.RoundRobinRouterFactory.html#method.with_virtual_nodes)
+    pub fn new(virtual_nodes: u64) -> Self {
+        Self {
+            router: RoundRobinRouterFactory::new()
+                .with_strategy(RoutingStrategy::ConsistentHash)
+                .with_virtual_nodes(virtual_nodes),
+        }
+    }
+
+    /// See [RoundRobinRouterFactory.with_empty_policy](./struct.RoundRobinRouterFactory.html#method.with_empty_policy)
+    pub fn with_empty_policy(mut self, empty_policy: EmptyRouterPolicy) -> Self {
+        self.router = self.router.with_empty_policy(empty_policy);
+        self
+    }
+}
+
+impl<A> ActorFactory<RoundRobinRouter<A>> for ConsistentHashRouterFactory
+where
+    A: Actor + 'static,
+{
+    fn new_actor(&self, context: ActorContext<RoundRobinRouter<A>>) -> RoundRobinRouter<A> {
+        self.router.new_actor(context)
+    }
+}
@@ -1,11 +1,27 @@
 mod add_actor_message;
+mod checkout_pool;
+mod consistent_hash_router;
+mod pipeline;
 mod remove_actor_message;
 mod round_robin_router;
+mod routee_supervision;
 mod router_message;
+mod routing_strategy;
+mod smallest_mailbox_router;
+mod worker_pool;
 
 pub mod prelude {
     pub use crate::routers::add_actor_message::AddActorMessage;
+    pub use crate::routers::checkout_pool::{CheckedOutActor, CheckoutError, CheckoutPool, CheckoutPoolMetrics};
+    pub use crate::routers::consistent_hash_router::ConsistentHashRouterFactory;
+    pub use crate::routers::pipeline::{Pipeline, PipelineDecision, PipelineFactory};
     pub use crate::routers::remove_actor_message::RemoveActorMessage;
-    pub use crate::routers::round_robin_router::RoundRobinRouterFactory;
-    pub use crate::routers::router_message::RouterMessage;
+    pub use crate::routers::round_robin_router::{EmptyRouterPolicy, RoundRobinRouterFactory, SupervisedRouterFactory};
+    pub use crate::routers::routee_supervision::{RebuildAll, RouteeSupervision};
+    pub use crate::routers::router_message::{HashableMessage, LazyRouterMessage, RouterMessage};
+    pub use crate::routers::routing_strategy::{
+        AdaptiveStrategy, RoutingStrategy, RoutingStrategyChanged, SetStrategy, ROUTING_STRATEGY_TOPIC,
+    };
+    pub use crate::routers::smallest_mailbox_router::SmallestMailboxRouterFactory;
+    pub use crate::routers::worker_pool::{WorkerPool, WorkerPoolBuilder};
 }
@@ -0,0 +1,195 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::actor::reply::ReplyTo;
+use crate::message::actor_message::ActorMessage;
+use crate::routers::router_message::RouterMessage;
+use std::panic::UnwindSafe;
+use std::sync::Arc;
+
+type Stage<M> = Arc<dyn Fn(&M) -> PipelineDecision<M> + Send + Sync>;
+
+/// Returned by every inspection stage of a [Pipeline](./struct.Pipeline.html)
+pub enum PipelineDecision<M> {
+    /// hands the message to the next stage, or to the consumer after the last stage
+    Continue,
+    /// skips all remaining stages and the consumer and sends the message to the given Actor instead
+    Forward(ReplyTo<M>),
+    /// the message is dropped, neither the remaining stages nor the consumer see it
+    Drop,
+}
+
+/// Inspects every message through an ordered list of stages that only borrow it, and moves it to the consumer afterwards
+///
+/// Messages are sent wrapped into a [RouterMessage](./struct.RouterMessage.html), whose key is ignored.
+/// Messages are never copied, they are handled one after another in the order they have been sent, like by any other Actor.
+/// A stage that panics is treated like a panicking handler of the Pipeline, the message is lost and the [RestartPolicy](../prelude/enum.RestartPolicy.html) of the Pipeline applies.
+/// Sends to a stopped consumer or forward target are dead letters.
+/// Spawned through a [PipelineFactory](./struct.PipelineFactory.html)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::{PipelineDecision, PipelineFactory, RouterMessage};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// // not Clone, the payload can only ever be moved
+/// struct Frame { id: usize, bytes: Vec<u8> }
+/// impl ActorMessage for Frame {}
+///
+/// type Log = Arc<Mutex<Vec<(&'static str, usize, usize)>>>;
+///
+/// struct Sink { name: &'static str, log: Log }
+/// impl Actor for Sink {}
+/// impl Handler<Frame> for Sink {
+///     fn handle(&mut self, msg: Frame, _context: &ActorContext<Self>) {
+///         self.log.lock().unwrap().push((self.name, msg.id, msg.bytes.as_ptr() as usize));
+///     }
+/// }
+/// struct SinkFactory { name: &'static str, log: Log }
+/// impl ActorFactory<Sink> for SinkFactory {
+///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+///         Sink { name: self.name, log: self.log.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let log = Log::default();
+/// let consumer = actor_system.builder().spawn("consumer", SinkFactory { name: "consumer", log: log.clone() }).unwrap();
+/// let quarantine = actor_system.builder().spawn("quarantine", SinkFactory { name: "quarantine", log: log.clone() }).unwrap();
+/// let quarantine = ReplyTo::new(&quarantine);
+///
+/// let record = |stage: &'static str| {
+///     let log = log.clone();
+///     move |frame: &Frame| log.lock().unwrap().push((stage, frame.id, frame.bytes.as_ptr() as usize))
+/// };
+/// let (validate, measure) = (record("validate"), record("measure"));
+/// let pipeline = PipelineFactory::new(ReplyTo::new(&consumer))
+///     .inspect(move |frame| {
+///         validate(frame);
+///         match frame.bytes.len() {
+///             0 => PipelineDecision::Drop,
+///             _ => PipelineDecision::Continue,
+///         }
+///     })
+///     .inspect(move |frame| {
+///         measure(frame);
+///         PipelineDecision::Continue
+///     })
+///     .inspect(move |frame| match frame.bytes[0] {
+///         0xff => PipelineDecision::Forward(quarantine.clone()),
+///         _ => PipelineDecision::Continue,
+///     });
+/// let pipeline = actor_system.builder().spawn("frames", pipeline).unwrap();
+///
+/// let mut sent = Vec::new();
+/// for (id, first) in [0x01u8, 0xff, 0x02].iter().enumerate() {
+///     let mut bytes = vec![0u8; 1024 * 1024];
+///     bytes[0] = *first;
+///     sent.push(bytes.as_ptr() as usize);
+///     pipeline.send(RouterMessage::new(Frame { id, bytes })).unwrap();
+/// }
+/// pipeline.send(RouterMessage::new(Frame { id: 3, bytes: Vec::new() })).unwrap();
+/// sleep(Duration::from_millis(200));
+///
+/// let log = log.lock().unwrap();
+/// let seen = |id: usize| log.iter().filter(|(_, i, _)| *i == id).map(|(stage, _, _)| *stage).collect::<Vec<_>>();
+/// assert_eq!(vec!["validate", "measure", "consumer"], seen(0));
+/// assert_eq!(vec!["validate", "measure", "quarantine"], seen(1));
+/// assert_eq!(vec!["validate"], seen(3));
+/// // every stage and the consumer saw the very buffer that has been sent
+/// for (id, ptr) in sent.iter().enumerate() {
+///     assert!(log.iter().filter(|(_, i, _)| *i == id).all(|(_, _, p)| p == ptr));
+/// }
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+pub struct Pipeline<M>
+where
+    M: ActorMessage + 'static,
+{
+    context: ActorContext<Self>,
+    stages: Vec<Stage<M>>,
+    consumer: ReplyTo<M>,
+}
+
+// stages are shared with the factory, a restarted Pipeline starts with the same stages
+impl<M> UnwindSafe for Pipeline<M> where M: ActorMessage + 'static {}
+
+impl<M> Actor for Pipeline<M>
+where
+    M: ActorMessage + 'static,
+{
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl<M> Handler<RouterMessage<M>> for Pipeline<M>
+where
+    M: ActorMessage + 'static,
+{
+    fn handle(&mut self, msg: RouterMessage<M>, _context: &ActorContext<Self>) {
+        let msg = msg.msg;
+        for stage in &self.stages {
+            match stage(&msg) {
+                PipelineDecision::Continue => {}
+                PipelineDecision::Forward(target) => {
+                    let _ = target.send(msg);
+                    return;
+                }
+                PipelineDecision::Drop => return,
+            }
+        }
+        let _ = self.consumer.send(msg);
+    }
+}
+
+/// implements [ActorFactory](../prelude/trait.ActorFactory.html) to spawn a [Pipeline](./struct.Pipeline.html)
+pub struct PipelineFactory<M>
+where
+    M: ActorMessage + 'static,
+{
+    stages: Vec<Stage<M>>,
+    consumer: ReplyTo<M>,
+}
+
+impl<M> PipelineFactory<M>
+where
+    M: ActorMessage + 'static,
+{
+    /// `consumer` receives every message that passed all stages
+    pub fn new(consumer: ReplyTo<M>) -> Self {
+        Self {
+            stages: Vec::new(),
+            consumer,
+        }
+    }
+
+    /// Appends a stage, stages are executed in the order they have been added
+    pub fn inspect<F>(mut self, stage: F) -> Self
+    where
+        F: Fn(&M) -> PipelineDecision<M> + Send + Sync + 'static,
+    {
+        self.stages.push(Arc::new(stage));
+        self
+    }
+}
+
+impl<M> ActorFactory<Pipeline<M>> for PipelineFactory<M>
+where
+    M: ActorMessage + 'static,
+{
+    fn new_actor(&self, context: ActorContext<Pipeline<M>>) -> Pipeline<M> {
+        Pipeline {
+            context,
+            stages: self.stages.clone(),
+            consumer: self.consumer.clone(),
+        }
+    }
+}
@@ -3,21 +3,49 @@ use crate::actor::actor_factory::ActorFactory;
 use crate::actor::actor_wrapper::ActorWrapper;
 use crate::actor::context::ActorContext;
 use crate::actor::handler::Handler;
+use crate::actor::pressure::PressureLevel;
 use crate::message::actor_message::ActorMessage;
 use crate::routers::add_actor_message::AddActorMessage;
 use crate::routers::remove_actor_message::RemoveActorMessage;
-use crate::routers::router_message::RouterMessage;
+use crate::routers::routee_supervision::{RebuildAll, RebuildStep, ReplaceRoutee, RouteeSupervision, RouteeTerminated, Supervisor};
+use crate::routers::router_message::{LazyRouterMessage, RouterMessage};
+use crate::routers::routing_strategy::{
+    AdaptiveStrategy, RouteSelector, RoutingStrategy, RoutingStrategyChanged, SetStrategy, DEFAULT_VIRTUAL_NODES,
+    ROUTING_STRATEGY_TOPIC,
+};
+use std::collections::VecDeque;
+use std::panic::UnwindSafe;
+
+/// Delivery of a message that is held back until the router has a routee again
+type Pending<A> = Box<dyn FnOnce(&ActorWrapper<A>) + Send + Sync>;
+
+/// What a router does with messages while it has no routees, see [RoundRobinRouterFactory.with_empty_policy](./struct.RoundRobinRouterFactory.html#method.with_empty_policy)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmptyRouterPolicy {
+    /// messages are dropped
+    Drop,
+    /// up to the given number of messages are held back and routed as soon as a routee is added, further messages are dropped
+    Buffer(usize),
+}
 
 pub struct RoundRobinRouter<A>
 where
     A: Actor + 'static,
 {
     context: ActorContext<Self>,
-    route_index: usize,
+    selector: RouteSelector,
     route_to: Vec<ActorWrapper<A>>,
     can_route: bool,
+    skip_high_pressure: bool,
+    empty_policy: EmptyRouterPolicy,
+    pending: VecDeque<(Option<u64>, Pending<A>)>,
+    /// only set if the router has been spawned through a [SupervisedRouterFactory](./struct.SupervisedRouterFactory.html)
+    supervisor: Option<Supervisor<A>>,
 }
 
+// held back messages are only routed by the router itself, a restarted router starts without them
+impl<A> UnwindSafe for RoundRobinRouter<A> where A: Actor + 'static {}
+
 /// implements [ActorFactory](../prelude/trait.ActorFactory.html) to spawn a RoundRobinRouter within an [ActorSystem](../prelude/struct.ActorSystem.html)
 ///
 /// # Examples
@@ -74,11 +102,337 @@ where
 /// router.send(AddActorMessage::new(actor.clone()));
 /// router.send(RouterMessage::new(FooBar{}));
 /// ```
-pub struct RoundRobinRouterFactory {}
+pub struct RoundRobinRouterFactory {
+    skip_high_pressure: bool,
+    strategy: RoutingStrategy,
+    adaptive: Option<AdaptiveStrategy>,
+    empty_policy: EmptyRouterPolicy,
+    virtual_nodes: u64,
+}
 
 impl RoundRobinRouterFactory {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            skip_high_pressure: false,
+            strategy: RoutingStrategy::RoundRobin,
+            adaptive: None,
+            empty_policy: EmptyRouterPolicy::Drop,
+            virtual_nodes: DEFAULT_VIRTUAL_NODES,
+        }
+    }
+
+    /// Initial [RoutingStrategy](./enum.RoutingStrategy.html) of the router, it can be switched at runtime through [SetStrategy](./struct.SetStrategy.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::{AddActorMessage, RoundRobinRouterFactory, RouterMessage, RoutingStrategy};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Request { session: &'static str }
+    /// impl ActorMessage for Request {}
+    ///
+    /// struct Backend { name: &'static str, log: Arc<Mutex<Vec<(&'static str, &'static str)>>> }
+    /// impl Actor for Backend {}
+    /// impl Handler<Request> for Backend {
+    ///     fn handle(&mut self, msg: Request, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push((msg.session, self.name));
+    ///     }
+    /// }
+    /// struct BackendFactory { name: &'static str, log: Arc<Mutex<Vec<(&'static str, &'static str)>>> }
+    /// impl ActorFactory<Backend> for BackendFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Backend>) -> Backend {
+    ///         Backend { name: self.name, log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let router = actor_system
+    ///     .builder()
+    ///     .spawn("router", RoundRobinRouterFactory::new().with_strategy(RoutingStrategy::Sticky))
+    ///     .unwrap();
+    /// for name in ["a", "b"].iter() {
+    ///     let backend = actor_system.builder().spawn(*name, BackendFactory { name: *name, log: log.clone() }).unwrap();
+    ///     router.send(AddActorMessage::new(backend));
+    /// }
+    /// let request = |session: &'static str| {
+    ///     router.send(RouterMessage::with_key(Request { session }, session));
+    ///     sleep(Duration::from_millis(50));
+    ///     log.lock().unwrap().last().unwrap().1
+    /// };
+    /// assert_eq!("b", request("x"));
+    /// assert_eq!("a", request("y"));
+    /// assert_eq!("b", request("x"));
+    ///
+    /// // sessions survive a switch if they are preserved
+    /// router.set_strategy(RoutingStrategy::RoundRobin, true);
+    /// router.set_strategy(RoutingStrategy::Sticky, true);
+    /// assert_eq!("b", request("x"));
+    ///
+    /// // flushed sessions are assigned again
+    /// router.set_strategy(RoutingStrategy::Sticky, false);
+    /// assert_eq!("b", request("y"));
+    /// assert_eq!("a", request("x"));
+    /// assert_eq!("b", request("y"));
+    /// ```
+    pub fn with_strategy(mut self, strategy: RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Points of every routee on the hash ring of [RoutingStrategy::ConsistentHash](./enum.RoutingStrategy.html#variant.ConsistentHash), defaults to 64
+    ///
+    /// More virtual nodes spread the keys more evenly across the routees, see [ConsistentHashRouterFactory](./struct.ConsistentHashRouterFactory.html)
+    pub fn with_virtual_nodes(mut self, virtual_nodes: u64) -> Self {
+        self.virtual_nodes = virtual_nodes;
+        self
+    }
+
+    /// Lets the router switch its strategy depending on the mailbox lengths of its routees, starting with `uniform`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::*;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job {}
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { gate: Arc<AtomicBool> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, _msg: Job, _context: &ActorContext<Self>) {
+    ///         while !self.gate.load(Ordering::SeqCst) {
+    ///             sleep(Duration::from_millis(1));
+    ///         }
+    ///     }
+    /// }
+    /// struct WorkerFactory { gate: Arc<AtomicBool> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Recorder { events: Arc<Mutex<Vec<RoutingStrategyChanged>>> }
+    /// impl Actor for Recorder {}
+    /// impl Handler<RoutingStrategyChanged> for Recorder {
+    ///     fn handle(&mut self, msg: RoutingStrategyChanged, _context: &ActorContext<Self>) {
+    ///         self.events.lock().unwrap().push(msg);
+    ///     }
+    /// }
+    /// struct RecorderFactory { events: Arc<Mutex<Vec<RoutingStrategyChanged>>> }
+    /// impl ActorFactory<Recorder> for RecorderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Recorder>) -> Recorder {
+    ///         Recorder { events: self.events.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let recorder = actor_system.builder().spawn("recorder", RecorderFactory { events: events.clone() }).unwrap();
+    /// actor_system
+    ///     .get_event_bus()
+    ///     .subscribe_pattern::<RoutingStrategyChanged, _>(ROUTING_STRATEGY_TOPIC, &recorder)
+    ///     .unwrap();
+    ///
+    /// let adaptive = AdaptiveStrategy::new(RoutingStrategy::RoundRobin, RoutingStrategy::SmallestMailbox, 4.0, 1.0)
+    ///     .with_sample_every(4);
+    /// let router = actor_system
+    ///     .builder()
+    ///     .spawn("router", RoundRobinRouterFactory::new().with_adaptive_strategy(adaptive))
+    ///     .unwrap();
+    /// let closed = Arc::new(AtomicBool::new(false));
+    /// let stuck = actor_system.builder().spawn("stuck", WorkerFactory { gate: closed.clone() }).unwrap();
+    /// let fast = actor_system.builder().spawn("fast", WorkerFactory { gate: Arc::new(AtomicBool::new(true)) }).unwrap();
+    /// router.send(AddActorMessage::new(stuck.clone()));
+    /// router.send(AddActorMessage::new(fast.clone()));
+    ///
+    /// // the stuck routee builds up a queue until the variance crosses the upper threshold
+    /// for _ in 0..40 {
+    ///     router.send(RouterMessage::new(Job {}));
+    ///     sleep(Duration::from_millis(5));
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// let events = events.lock().unwrap();
+    /// assert_eq!(1, events.len());
+    /// assert_eq!(RoutingStrategy::RoundRobin, events[0].previous);
+    /// assert_eq!(RoutingStrategy::SmallestMailbox, events[0].strategy);
+    /// assert!(events[0].adaptive);
+    /// assert!(stuck.mailbox_len() < 10);
+    /// ```
+    pub fn with_adaptive_strategy(mut self, adaptive: AdaptiveStrategy) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Routees with [PressureLevel::High](../prelude/enum.PressureLevel.html#variant.High) are skipped, unless all of them are under high pressure
+    ///
+    /// Only routees with [Watermarks](../prelude/struct.Watermarks.html) can be under high pressure
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::{AddActorMessage, RoundRobinRouterFactory, RouterMessage};
+    /// use crossbeam_channel::{unbounded, Receiver};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Job { slow: bool }
+    /// impl ActorMessage for Job {}
+    ///
+    /// // a slow job waits until the gate opens
+    /// struct Worker { handled: Arc<Mutex<usize>>, gate: Receiver<()> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         if msg.slow {
+    ///             let _ = self.gate.recv();
+    ///         }
+    ///         *self.handled.lock().unwrap() += 1;
+    ///     }
+    /// }
+    /// struct WorkerFactory { handled: Arc<Mutex<usize>>, gate: Receiver<()> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { handled: self.handled.clone(), gate: self.gate.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = [Arc::new(Mutex::new(0)), Arc::new(Mutex::new(0))];
+    /// let (gate_sender, gate) = unbounded();
+    /// let busy = actor_system
+    ///     .builder()
+    ///     .set_watermarks(Watermarks::new(4, 1))
+    ///     .spawn("busy", WorkerFactory { handled: handled[0].clone(), gate: gate.clone() })
+    ///     .unwrap();
+    /// let idle = actor_system.builder().spawn("idle", WorkerFactory { handled: handled[1].clone(), gate }).unwrap();
+    /// busy.send(Job { slow: true });
+    /// for _ in 0..4 {
+    ///     busy.send(Job { slow: false });
+    /// }
+    /// assert_eq!(busy.pressure(), PressureLevel::High);
+    ///
+    /// let router = actor_system
+    ///     .builder()
+    ///     .spawn("router", RoundRobinRouterFactory::new().skip_high_pressure())
+    ///     .unwrap();
+    /// router.send(AddActorMessage::new(busy.clone()));
+    /// router.send(AddActorMessage::new(idle.clone()));
+    /// for _ in 0..10 {
+    ///     router.send(RouterMessage::new(Job { slow: false }));
+    /// }
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// while *handled[1].lock().unwrap() < 10 && Instant::now() < deadline {
+    ///     sleep(Duration::from_millis(1));
+    /// }
+    /// assert_eq!(*handled[1].lock().unwrap(), 10);
+    /// assert_eq!(*handled[0].lock().unwrap(), 0);
+    /// drop(gate_sender);
+    /// ```
+    pub fn skip_high_pressure(mut self) -> Self {
+        self.skip_high_pressure = true;
+        self
+    }
+
+    /// Defines what happens to messages while the router has no routees, defaults to [EmptyRouterPolicy::Drop](./enum.EmptyRouterPolicy.html#variant.Drop)
+    ///
+    /// Routees that have been added through [AddActorMessage](./struct.AddActorMessage.html) leave the router as soon as they stopped, a router whose last routee stopped is empty again
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use tyractorsaur::router::{AddActorMessage, EmptyRouterPolicy, RoundRobinRouterFactory, RouterMessage};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { name: &'static str, log: Arc<Mutex<Vec<(&'static str, usize)>>> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         self.log.lock().unwrap().push((self.name, msg.id));
+    ///     }
+    /// }
+    /// struct WorkerFactory { name: &'static str, log: Arc<Mutex<Vec<(&'static str, usize)>>> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { name: self.name, log: self.log.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let router = actor_system
+    ///     .builder()
+    ///     .spawn("router", RoundRobinRouterFactory::new().with_empty_policy(EmptyRouterPolicy::Buffer(2)))
+    ///     .unwrap();
+    /// let spawn = |name: &'static str| actor_system.builder().spawn(name, WorkerFactory { name, log: log.clone() }).unwrap();
+    ///
+    /// // without routees the first two jobs are held back, the third one is dropped
+    /// for id in 0..3 {
+    ///     router.send(RouterMessage::new(Job { id }));
+    /// }
+    /// let a = spawn("a");
+    /// router.send(AddActorMessage::new(a.clone()));
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![("a", 0), ("a", 1)], *log.lock().unwrap());
+    ///
+    /// // a routee added at runtime picks up traffic, a stopped one is skipped
+    /// router.send(AddActorMessage::new(spawn("b")));
+    /// a.stop();
+    /// sleep(Duration::from_millis(100));
+    /// log.lock().unwrap().clear();
+    /// for id in 3..6 {
+    ///     router.send(RouterMessage::new(Job { id }));
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![("b", 3), ("b", 4), ("b", 5)], *log.lock().unwrap());
+    /// ```
+    pub fn with_empty_policy(mut self, empty_policy: EmptyRouterPolicy) -> Self {
+        self.empty_policy = empty_policy;
+        self
+    }
+
+    /// The router spawns and supervises its own routees, see [RouteeSupervision](./struct.RouteeSupervision.html)
+    pub fn with_supervision<A>(self, supervision: RouteeSupervision<A>) -> SupervisedRouterFactory<A>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        SupervisedRouterFactory {
+            router: self,
+            supervision,
+        }
+    }
+}
+
+impl Default for RoundRobinRouterFactory {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -87,7 +441,35 @@ where
     A: Actor + 'static,
 {
     fn new_actor(&self, context: ActorContext<RoundRobinRouter<A>>) -> RoundRobinRouter<A> {
-        RoundRobinRouter::new(context)
+        let mut router = RoundRobinRouter::new(context);
+        router.skip_high_pressure = self.skip_high_pressure;
+        router.empty_policy = self.empty_policy;
+        router.selector = RouteSelector::new(self.strategy, self.adaptive).with_virtual_nodes(self.virtual_nodes);
+        router
+    }
+}
+
+/// implements [ActorFactory](../prelude/trait.ActorFactory.html) to spawn a RoundRobinRouter that owns its routees, see [RoundRobinRouterFactory.with_supervision](./struct.RoundRobinRouterFactory.html#method.with_supervision)
+pub struct SupervisedRouterFactory<A>
+where
+    A: Actor + 'static,
+{
+    router: RoundRobinRouterFactory,
+    supervision: RouteeSupervision<A>,
+}
+
+impl<A> ActorFactory<RoundRobinRouter<A>> for SupervisedRouterFactory<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn new_actor(&self, context: ActorContext<RoundRobinRouter<A>>) -> RoundRobinRouter<A> {
+        let mut router = self.router.new_actor(context);
+        let supervisor = Supervisor::new(self.supervision.clone());
+        supervisor.start(&router.context, &mut router.route_to);
+        router.supervisor = Some(supervisor);
+        router.can_route = true;
+        router.selector.rebuild(&router.route_to);
+        router
     }
 }
 
@@ -98,17 +480,107 @@ where
     pub fn new(context: ActorContext<Self>) -> Self {
         Self {
             context,
-            route_index: 0,
+            selector: RouteSelector::new(RoutingStrategy::RoundRobin, None),
             route_to: Vec::new(),
             can_route: false,
+            skip_high_pressure: false,
+            empty_policy: EmptyRouterPolicy::Drop,
+            pending: VecDeque::new(),
+            supervisor: None,
+        }
+    }
+
+    fn publish_change(&self, previous: RoutingStrategy, adaptive: bool) {
+        let strategy = self.selector.get_strategy();
+        if previous == strategy {
+            return;
+        }
+        let event = RoutingStrategyChanged {
+            router: self.context.actor_ref.get_address().actor.clone(),
+            previous,
+            strategy,
+            adaptive,
+        };
+        let _ = self.context.system.get_event_bus().publish(ROUTING_STRATEGY_TOPIC, event);
+    }
+
+    /// Returns `None` if the router does not route yet or has no routees
+    ///
+    /// Stopped routees that are not supervised are removed on the way, supervised ones are replaced by their supervisor
+    fn next_routee(&mut self, key: Option<u64>) -> Option<usize> {
+        if !self.can_route {
+            return None;
+        }
+
+        if let Some(previous) = self.selector.adapt(&self.route_to) {
+            self.publish_change(previous, true);
+        }
+        let skip_high_pressure = self.skip_high_pressure;
+        while !self.route_to.is_empty() {
+            let index = self.selector.select(key, &self.route_to, |routee| {
+                !skip_high_pressure || routee.pressure() == PressureLevel::Normal
+            });
+            if self.supervisor.is_some() || !self.route_to[index].get_mailbox().is_stopped() {
+                return Some(index);
+            }
+            self.route_to.remove(index);
+            self.selector.rebuild(&self.route_to);
+        }
+        None
+    }
+
+    /// Hands the message to the next routee, messages that can not be delivered are counted by a supervised router
+    fn route<F>(&mut self, key: Option<u64>, send: F)
+    where
+        F: FnOnce(&ActorWrapper<A>) + Send + Sync + 'static,
+    {
+        let is_delivered = match self.next_routee(key) {
+            Some(index) => {
+                let routee = &self.route_to[index];
+                let is_stopped = routee.get_mailbox().is_stopped();
+                send(routee);
+                !is_stopped
+            }
+            None => self.hold(key, Box::new(send)),
+        };
+        if let (false, Some(supervisor)) = (is_delivered, &self.supervisor) {
+            supervisor.add_dead_letters(1);
+        }
+    }
+
+    /// Keeps a message that arrived without routees according to the [EmptyRouterPolicy], returns `false` if it is dropped
+    fn hold(&mut self, key: Option<u64>, send: Pending<A>) -> bool {
+        match self.empty_policy {
+            EmptyRouterPolicy::Buffer(capacity) if self.pending.len() < capacity => {
+                self.pending.push_back((key, send));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Rebuilds the routing after routees have been added or removed and routes the messages that have been held back
+    fn routees_changed(&mut self) {
+        self.selector.rebuild(&self.route_to);
+        while !self.route_to.is_empty() {
+            match self.pending.pop_front() {
+                Some((key, send)) => self.route(key, send),
+                None => return,
+            }
         }
     }
 }
 
 impl<A> Actor for RoundRobinRouter<A>
 where
-    A: Actor + 'static,
+    A: Actor + UnwindSafe + 'static,
 {
+    fn on_actor_stop(&mut self) {
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.stop_all();
+        }
+    }
+
     fn on_system_stop(&mut self) {
         self.context.actor_ref.stop();
     }
@@ -118,9 +590,15 @@ impl<A> Handler<AddActorMessage<A>> for RoundRobinRouter<A>
 where
     A: Actor + 'static,
 {
-    fn handle(&mut self, msg: AddActorMessage<A>, _context: &ActorContext<Self>) {
+    fn handle(&mut self, msg: AddActorMessage<A>, context: &ActorContext<Self>) {
+        let router = context.actor_ref.clone();
+        let address = msg.actor.get_address().clone();
+        msg.actor.on_termination(move |_| {
+            let _ = router.send(RouteeTerminated { address });
+        });
         self.route_to.push(msg.actor);
         self.can_route = true;
+        self.routees_changed();
     }
 }
 
@@ -135,6 +613,10 @@ where
             .position(|x| x.get_address() == msg.actor.get_address())
         {
             self.route_to.remove(pos);
+            self.routees_changed();
+        }
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.release(msg.actor.get_address());
         }
     }
 }
@@ -145,16 +627,98 @@ where
     M: ActorMessage + 'static,
 {
     fn handle(&mut self, msg: RouterMessage<M>, _context: &ActorContext<Self>) {
-        if !self.can_route {
-            return;
+        self.route(msg.key, |forward_to| {
+            let _ = forward_to.send(msg.msg);
+        });
+    }
+}
+
+impl<A, M> Handler<LazyRouterMessage<M>> for RoundRobinRouter<A>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    fn handle(&mut self, msg: LazyRouterMessage<M>, _context: &ActorContext<Self>) {
+        self.route(msg.key, |forward_to| forward_to.send_constructor(msg.constructor));
+    }
+}
+
+impl<A> Handler<SetStrategy> for RoundRobinRouter<A>
+where
+    A: Actor + 'static,
+{
+    fn handle(&mut self, msg: SetStrategy, _context: &ActorContext<Self>) {
+        let previous = self.selector.set_strategy(msg, &self.route_to);
+        self.publish_change(previous, false);
+    }
+}
+
+impl<A> Handler<RouteeTerminated> for RoundRobinRouter<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn handle(&mut self, msg: RouteeTerminated, context: &ActorContext<Self>) {
+        let is_supervised = match self.supervisor.as_mut() {
+            Some(supervisor) => supervisor.terminated(&msg.address, context, &mut self.route_to),
+            None => false,
+        };
+        if !is_supervised {
+            // an Actor that has been added with the same address in the meantime is kept
+            self.route_to
+                .retain(|routee| routee.get_address() != &msg.address || !routee.get_mailbox().is_stopped());
         }
+        self.routees_changed();
+    }
+}
 
-        self.route_index += 1;
-        if self.route_index >= self.route_to.len() {
-            self.route_index = 0;
+impl<A> Handler<ReplaceRoutee> for RoundRobinRouter<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn handle(&mut self, msg: ReplaceRoutee, context: &ActorContext<Self>) {
+        if let Some(supervisor) = &self.supervisor {
+            if supervisor.replace(&msg.previous, context, &mut self.route_to).is_some() {
+                self.routees_changed();
+            }
+        }
+    }
+}
+
+impl<A> Handler<RebuildAll> for RoundRobinRouter<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn handle(&mut self, _msg: RebuildAll, context: &ActorContext<Self>) {
+        if let Some(supervisor) = self.supervisor.as_mut() {
+            supervisor.rebuild_all(context);
         }
+    }
+}
+
+impl<A> Handler<RebuildStep> for RoundRobinRouter<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn handle(&mut self, _msg: RebuildStep, context: &ActorContext<Self>) {
+        if let Some(supervisor) = self.supervisor.as_mut() {
+            if supervisor.rebuild_step(context, &mut self.route_to) {
+                self.routees_changed();
+            }
+        }
+    }
+}
+
+impl<A> ActorWrapper<RoundRobinRouter<A>>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Sends [RebuildAll](../router/struct.RebuildAll.html) to the router, a router without [RouteeSupervision](../router/struct.RouteeSupervision.html) ignores it
+    pub fn rebuild_all(&self) {
+        let _ = self.send(RebuildAll {});
+    }
 
-        let forward_to = self.route_to.get(self.route_index).unwrap();
-        forward_to.send(msg.msg);
+    /// Sends [SetStrategy](../router/struct.SetStrategy.html) to the router, it is applied after all messages that have been sent to the router before
+    pub fn set_strategy(&self, strategy: RoutingStrategy, preserve_sessions: bool) {
+        let _ = self.send(SetStrategy::new(strategy, preserve_sessions));
     }
 }
@@ -0,0 +1,454 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_config::ActorDefaults;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::message::actor_message::ActorMessage;
+use crate::routers::round_robin_router::RoundRobinRouter;
+use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::{self, SpawnSpec};
+use std::collections::VecDeque;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type RouteeSpawner<A> = Arc<dyn Fn(&ActorSystem, String, ActorDefaults) -> Option<ActorWrapper<A>> + Send + Sync + RefUnwindSafe>;
+type ReplacedHook<A> = Arc<dyn Fn(&ActorAddress, &ActorWrapper<A>) + Send + Sync + RefUnwindSafe>;
+
+/// Tears down and recreates every supervised routee of a router, one after the other
+///
+/// Every replacement is spawned before the routee it replaces is stopped, so the capacity of the router never drops.
+/// Replaced routees are stopped gracefully and handle everything that has already been routed to them
+pub struct RebuildAll {}
+
+impl ActorMessage for RebuildAll {}
+
+/// A routee stopped for good
+pub(crate) struct RouteeTerminated {
+    pub address: ActorAddress,
+}
+
+impl ActorMessage for RouteeTerminated {}
+
+/// The backoff of a terminated routee is over
+pub(crate) struct ReplaceRoutee {
+    pub previous: ActorAddress,
+}
+
+impl ActorMessage for ReplaceRoutee {}
+
+/// Replaces the next routee of a running [RebuildAll](./struct.RebuildAll.html)
+pub(crate) struct RebuildStep {}
+
+impl ActorMessage for RebuildStep {}
+
+struct SupervisionState<A>
+where
+    A: Actor + 'static,
+{
+    routees: Mutex<Vec<ActorWrapper<A>>>,
+    next_index: AtomicUsize,
+    is_started: AtomicBool,
+    replacements: AtomicUsize,
+    capped: AtomicUsize,
+    dead_letters: AtomicUsize,
+}
+
+/// Lets a router own its routees, see [RoundRobinRouterFactory.with_supervision](./struct.RoundRobinRouterFactory.html#method.with_supervision)
+///
+/// The router spawns `routees` Actors named `<name>-<n>` from the factory when it is spawned.
+/// Whenever one of them stops for good, for example after a panic with [RestartPolicy::Never](../prelude/enum.RestartPolicy.html#variant.Never), it is removed from the router and replaced by a new one after the backoff.
+/// At most `max_rebuilds` routees are replaced within the rebuild window, further terminations only shrink the router.
+/// Messages that the router can not deliver, because no routee is left or the chosen routee has already stopped, are dropped and counted as dead letters together with the messages that were still queued by a terminated routee.
+///
+/// The supervision is shared with the router, a clone keeps observing it
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::*;
+/// use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Job { key: u64 }
+/// impl ActorMessage for Job {}
+/// struct Poison {}
+/// impl ActorMessage for Poison {}
+///
+/// struct Worker { name: String, poisoned: bool, handled: Arc<Mutex<Vec<(String, u64)>>> }
+/// impl Actor for Worker {}
+/// impl Handler<Job> for Worker {
+///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+///         if self.poisoned {
+///             panic!("poisoned");
+///         }
+///         self.handled.lock().unwrap().push((self.name.clone(), msg.key));
+///     }
+/// }
+/// impl Handler<Poison> for Worker {
+///     fn handle(&mut self, _msg: Poison, _context: &ActorContext<Self>) {
+///         panic!("poisoned");
+///     }
+/// }
+/// #[derive(Clone)]
+/// struct WorkerFactory { poisoned: Arc<AtomicBool>, handled: Arc<Mutex<Vec<(String, u64)>>> }
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+///         let name = context.actor_ref.get_address().actor.clone();
+///         Worker { name, poisoned: self.poisoned.load(Ordering::SeqCst), handled: self.handled.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let poisoned = Arc::new(AtomicBool::new(false));
+/// let handled = Arc::new(Mutex::new(Vec::new()));
+/// let replaced = Arc::new(Mutex::new(Vec::new()));
+/// let log = replaced.clone();
+/// let supervision = RouteeSupervision::new(4, "worker", WorkerFactory { poisoned: poisoned.clone(), handled: handled.clone() })
+///     .with_overrides(ActorDefaults { restart_policy: Some(RestartPolicy::Never), ..ActorDefaults::default() })
+///     .with_backoff(Duration::from_millis(100))
+///     .with_max_rebuilds(3, Duration::from_secs(60))
+///     .on_routee_replaced(move |previous, replacement| {
+///         log.lock().unwrap().push((previous.actor.clone(), replacement.get_address().actor.clone()));
+///     });
+/// let router = actor_system
+///     .builder()
+///     .spawn("router", RoundRobinRouterFactory::new().with_strategy(RoutingStrategy::ConsistentHash).with_supervision(supervision.clone()))
+///     .unwrap();
+/// let names = || -> Vec<String> { supervision.routees().iter().map(|routee| routee.get_address().actor.clone()).collect() };
+/// let route = |keys: std::ops::Range<u64>| {
+///     for key in keys {
+///         router.send(RouterMessage::with_key(Job { key }, &key));
+///     }
+/// };
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(vec!["worker-0", "worker-1", "worker-2", "worker-3"], names());
+///
+/// // a terminated routee is replaced after the backoff, traffic in between is rerouted or counted as dead letters
+/// supervision.routees()[1].send(Poison {});
+/// route(0..200);
+/// sleep(Duration::from_millis(50));
+/// assert_eq!(vec!["worker-0", "worker-2", "worker-3"], names());
+/// sleep(Duration::from_millis(250));
+/// assert_eq!(vec!["worker-0", "worker-2", "worker-3", "worker-4"], names());
+/// assert_eq!(vec![(String::from("worker-1"), String::from("worker-4"))], *replaced.lock().unwrap());
+/// assert_eq!(200, handled.lock().unwrap().len() + supervision.dead_letters());
+///
+/// // the hash ring includes the replacement
+/// handled.lock().unwrap().clear();
+/// route(0..100);
+/// sleep(Duration::from_millis(200));
+/// assert_eq!(100, handled.lock().unwrap().len());
+/// assert!(handled.lock().unwrap().iter().any(|(name, _)| name == "worker-4"));
+///
+/// // a rolling rebuild keeps the capacity of the router
+/// let capacity = Arc::new(AtomicUsize::new(usize::MAX));
+/// let is_sampling = Arc::new(AtomicBool::new(true));
+/// let sampler = {
+///     let (supervision, capacity, is_sampling) = (supervision.clone(), capacity.clone(), is_sampling.clone());
+///     std::thread::spawn(move || {
+///         while is_sampling.load(Ordering::SeqCst) {
+///             capacity.fetch_min(supervision.routees().len(), Ordering::SeqCst);
+///         }
+///     })
+/// };
+/// router.rebuild_all();
+/// route(100..200);
+/// sleep(Duration::from_millis(300));
+/// is_sampling.store(false, Ordering::SeqCst);
+/// sampler.join().unwrap();
+/// assert!(capacity.load(Ordering::SeqCst) >= 3);
+/// assert_eq!(vec!["worker-5", "worker-6", "worker-7", "worker-8"], names());
+/// assert_eq!(200, handled.lock().unwrap().len());
+/// assert_eq!(5, supervision.replacements());
+///
+/// // a poisoned factory can not cause a rebuild storm
+/// poisoned.store(true, Ordering::SeqCst);
+/// supervision.routees()[0].send(Poison {});
+/// for key in 0..100 {
+///     router.send(RouterMessage::with_key(Job { key }, &key));
+///     sleep(Duration::from_millis(5));
+/// }
+/// sleep(Duration::from_millis(300));
+/// assert_eq!(7, supervision.replacements());
+/// assert_eq!(1, supervision.capped_replacements());
+/// assert_eq!(vec!["worker-6", "worker-7", "worker-8"], names());
+/// ```
+pub struct RouteeSupervision<A>
+where
+    A: Actor + 'static,
+{
+    routees: usize,
+    name: String,
+    spawner: RouteeSpawner<A>,
+    overrides: ActorDefaults,
+    backoff: Duration,
+    max_rebuilds: usize,
+    rebuild_window: Duration,
+    on_replaced: Option<ReplacedHook<A>>,
+    state: Arc<SupervisionState<A>>,
+}
+
+impl<A> Clone for RouteeSupervision<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            routees: self.routees,
+            name: self.name.clone(),
+            spawner: self.spawner.clone(),
+            overrides: self.overrides,
+            backoff: self.backoff,
+            max_rebuilds: self.max_rebuilds,
+            rebuild_window: self.rebuild_window,
+            on_replaced: self.on_replaced.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<A> RouteeSupervision<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Replaces terminated routees after 100ms, at most 10 within a minute
+    pub fn new<P>(routees: usize, name: impl Into<String>, factory: P) -> Self
+    where
+        P: ActorFactory<A> + Clone + Send + Sync + RefUnwindSafe + 'static,
+    {
+        let spawner = move |system: &ActorSystem, name: String, overrides: ActorDefaults| {
+            let spec = SpawnSpec::new(name, factory.clone()).with_overrides(overrides);
+            bulk_spawn::spawn_one(&system.builder(), spec).ok()
+        };
+        Self {
+            routees,
+            name: name.into(),
+            spawner: Arc::new(spawner),
+            overrides: ActorDefaults::default(),
+            backoff: Duration::from_millis(100),
+            max_rebuilds: 10,
+            rebuild_window: Duration::from_secs(60),
+            on_replaced: None,
+            state: Arc::new(SupervisionState {
+                routees: Mutex::new(Vec::new()),
+                next_index: AtomicUsize::new(0),
+                is_started: AtomicBool::new(false),
+                replacements: AtomicUsize::new(0),
+                capped: AtomicUsize::new(0),
+                dead_letters: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Settings of all routees, see [ActorSystem.spawn_many](../prelude/struct.ActorSystem.html#method.spawn_many)
+    pub fn with_overrides(mut self, overrides: ActorDefaults) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Time between the termination of a routee and the spawn of its replacement
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Replaces at most `max_rebuilds` terminated routees within `window`, [RebuildAll](./struct.RebuildAll.html) is not limited
+    pub fn with_max_rebuilds(mut self, max_rebuilds: usize, window: Duration) -> Self {
+        self.max_rebuilds = max_rebuilds;
+        self.rebuild_window = window;
+        self
+    }
+
+    /// Executed by the router for every replaced routee, so that state bound to the previous address can be migrated
+    pub fn on_routee_replaced<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ActorAddress, &ActorWrapper<A>) + Send + Sync + RefUnwindSafe + 'static,
+    {
+        self.on_replaced = Some(Arc::new(hook));
+        self
+    }
+
+    /// Current supervised routees, in the order they have been added to the router
+    pub fn routees(&self) -> Vec<ActorWrapper<A>> {
+        self.state.routees.lock().unwrap().clone()
+    }
+
+    /// Routees that have been replaced, either after their termination or through [RebuildAll](./struct.RebuildAll.html)
+    pub fn replacements(&self) -> usize {
+        self.state.replacements.load(Ordering::Relaxed)
+    }
+
+    /// Terminated routees that have not been replaced, because the rebuild limit has been reached
+    pub fn capped_replacements(&self) -> usize {
+        self.state.capped.load(Ordering::Relaxed)
+    }
+
+    /// Messages the router could not deliver to a routee
+    pub fn dead_letters(&self) -> usize {
+        self.state.dead_letters.load(Ordering::Relaxed)
+    }
+}
+
+/// Supervision as executed by the router
+pub(crate) struct Supervisor<A>
+where
+    A: Actor + 'static,
+{
+    supervision: RouteeSupervision<A>,
+    rebuilds: VecDeque<Instant>,
+    pending_rebuild: VecDeque<ActorAddress>,
+}
+
+impl<A> Supervisor<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub fn new(supervision: RouteeSupervision<A>) -> Self {
+        Self {
+            supervision,
+            rebuilds: VecDeque::new(),
+            pending_rebuild: VecDeque::new(),
+        }
+    }
+
+    /// Spawns the routees when the router is created for the first time, a restarted router adopts the running ones
+    pub fn start(&self, context: &ActorContext<RoundRobinRouter<A>>, route_to: &mut Vec<ActorWrapper<A>>) {
+        let state = &self.supervision.state;
+        if !state.is_started.swap(true, Ordering::SeqCst) {
+            let spawned: Vec<ActorWrapper<A>> = (0..self.supervision.routees).filter_map(|_| self.spawn(context)).collect();
+            state.routees.lock().unwrap().extend(spawned);
+        }
+        route_to.extend(state.routees.lock().unwrap().iter().cloned());
+    }
+
+    fn spawn(&self, context: &ActorContext<RoundRobinRouter<A>>) -> Option<ActorWrapper<A>> {
+        let index = self.supervision.state.next_index.fetch_add(1, Ordering::Relaxed);
+        let name = format!("{}-{}", self.supervision.name, index);
+        let routee = (self.supervision.spawner)(&context.system, name, self.supervision.overrides)?;
+        let router = context.actor_ref.clone();
+        let address = routee.get_address().clone();
+        routee.on_termination(move |_| {
+            let _ = router.send(RouteeTerminated { address });
+        });
+        Some(routee)
+    }
+
+    /// Removes the routee from the supervision and returns it, `None` if it is not supervised
+    pub fn release(&self, address: &ActorAddress) -> Option<ActorWrapper<A>> {
+        let mut routees = self.supervision.state.routees.lock().unwrap();
+        let position = routees.iter().position(|routee| routee.get_address() == address)?;
+        Some(routees.remove(position))
+    }
+
+    pub fn add_dead_letters(&self, amount: usize) {
+        self.supervision.state.dead_letters.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Removes a terminated routee from `route_to` and schedules its replacement, returns `false` if it is not supervised
+    pub fn terminated(&mut self, address: &ActorAddress, context: &ActorContext<RoundRobinRouter<A>>, route_to: &mut Vec<ActorWrapper<A>>) -> bool {
+        let routee = match self.release(address) {
+            Some(routee) => routee,
+            None => return false,
+        };
+        route_to.retain(|candidate| candidate.get_address() != address);
+        // everything that was still queued is lost together with the routee
+        self.add_dead_letters(routee.mailbox_len());
+
+        let now = Instant::now();
+        while self
+            .rebuilds
+            .front()
+            .is_some_and(|rebuild| now.duration_since(*rebuild) > self.supervision.rebuild_window)
+        {
+            self.rebuilds.pop_front();
+        }
+        if self.rebuilds.len() >= self.supervision.max_rebuilds {
+            self.supervision.state.capped.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        self.rebuilds.push_back(now);
+
+        let previous = address.clone();
+        if self.supervision.backoff == Duration::from_secs(0) {
+            self.replace(&previous, context, route_to);
+            return true;
+        }
+        // a timer of the router, it ends together with the router or the system
+        context.schedule_once(self.supervision.backoff, ReplaceRoutee { previous });
+        true
+    }
+
+    /// Spawns the replacement of `previous` and adds it to `route_to`
+    pub fn replace(&self, previous: &ActorAddress, context: &ActorContext<RoundRobinRouter<A>>, route_to: &mut Vec<ActorWrapper<A>>) -> Option<ActorWrapper<A>> {
+        let replacement = self.spawn(context)?;
+        self.supervision.state.routees.lock().unwrap().push(replacement.clone());
+        route_to.push(replacement.clone());
+        self.supervision.state.replacements.fetch_add(1, Ordering::Relaxed);
+        if let Some(hook) = &self.supervision.on_replaced {
+            hook(previous, &replacement);
+        }
+        Some(replacement)
+    }
+
+    pub fn rebuild_all(&mut self, context: &ActorContext<RoundRobinRouter<A>>) {
+        let is_running = !self.pending_rebuild.is_empty();
+        self.pending_rebuild = self
+            .supervision
+            .state
+            .routees
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|routee| routee.get_address().clone())
+            .collect();
+        if !is_running && !self.pending_rebuild.is_empty() {
+            let _ = context.actor_ref.send(RebuildStep {});
+        }
+    }
+
+    /// Replaces the next routee of a running rebuild, returns `false` if nothing changed
+    pub fn rebuild_step(&mut self, context: &ActorContext<RoundRobinRouter<A>>, route_to: &mut Vec<ActorWrapper<A>>) -> bool {
+        let mut is_changed = false;
+        while let Some(previous) = self.pending_rebuild.pop_front() {
+            let is_supervised = self
+                .supervision
+                .state
+                .routees
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|routee| *routee.get_address() == previous);
+            if !is_supervised {
+                continue;
+            }
+            // the previous routee is only removed once its replacement is running
+            if self.replace(&previous, context, route_to).is_some() {
+                if let Some(routee) = self.release(&previous) {
+                    route_to.retain(|candidate| *candidate.get_address() != previous);
+                    routee.stop();
+                }
+                is_changed = true;
+            }
+            break;
+        }
+        if !self.pending_rebuild.is_empty() {
+            let _ = context.actor_ref.send(RebuildStep {});
+        }
+        is_changed
+    }
+
+    /// Stops all supervised routees, they are not replaced
+    pub fn stop_all(&self) {
+        for routee in self.supervision.state.routees.lock().unwrap().drain(..) {
+            routee.stop();
+        }
+    }
+}
@@ -1,4 +1,15 @@
 use crate::message::actor_message::ActorMessage;
+use crate::message::envelope::Constructor;
+use crate::routers::routing_strategy::hash_key;
+use std::hash::Hash;
+
+/// Message that carries its own routing key, see [RouterMessage::hashed](./struct.RouterMessage.html#method.hashed)
+pub trait HashableMessage: ActorMessage {
+    /// Equal keys are routed to the same routee by [RoutingStrategy::ConsistentHash](./enum.RoutingStrategy.html#variant.ConsistentHash)
+    ///
+    /// The key is hashed again before it is placed on the hash ring, so ids work as well as hashes
+    fn hash_key(&self) -> u64;
+}
 
 /// Wraps any [ActorMessage](../prelude/trait.ActorMessage.html) to be sent to a Router
 pub struct RouterMessage<M>
@@ -6,6 +17,8 @@ where
     M: ActorMessage + 'static,
 {
     pub msg: M,
+    /// hash of the routing key, used by [RoutingStrategy::ConsistentHash](./enum.RoutingStrategy.html#variant.ConsistentHash) and [RoutingStrategy::Sticky](./enum.RoutingStrategy.html#variant.Sticky)
+    pub key: Option<u64>,
 }
 
 impl<M> ActorMessage for RouterMessage<M> where M: ActorMessage + 'static {}
@@ -15,6 +28,108 @@ where
     M: ActorMessage + 'static,
 {
     pub fn new(msg: M) -> Self {
-        Self { msg }
+        Self { msg, key: None }
+    }
+
+    /// Routes `msg` by its own [HashableMessage.hash_key](./trait.HashableMessage.html#tymethod.hash_key)
+    pub fn hashed(msg: M) -> Self
+    where
+        M: HashableMessage,
+    {
+        let key = hash_key(&msg.hash_key());
+        Self { msg, key: Some(key) }
+    }
+
+    pub fn with_key<K>(msg: M, key: &K) -> Self
+    where
+        K: Hash + ?Sized,
+    {
+        Self {
+            msg,
+            key: Some(hash_key(key)),
+        }
+    }
+}
+
+/// Same as [RouterMessage](./struct.RouterMessage.html), but the message is only constructed right before the routee handles it
+///
+/// See [ActorWrapper.send_lazy](../prelude/struct.ActorWrapper.html#method.send_lazy)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::{AddActorMessage, LazyRouterMessage, RoundRobinRouterFactory};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Report { text: String }
+/// impl ActorMessage for Report {}
+///
+/// struct Printer { name: &'static str, printed: Arc<Mutex<Vec<(&'static str, String)>>> }
+/// impl Actor for Printer {}
+/// impl Handler<Report> for Printer {
+///     fn handle(&mut self, msg: Report, _context: &ActorContext<Self>) {
+///         self.printed.lock().unwrap().push((self.name, msg.text));
+///     }
+/// }
+/// struct PrinterFactory { name: &'static str, printed: Arc<Mutex<Vec<(&'static str, String)>>> }
+/// impl ActorFactory<Printer> for PrinterFactory {
+///     fn new_actor(&self, _context: ActorContext<Printer>) -> Printer {
+///         Printer { name: self.name, printed: self.printed.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let printed = Arc::new(Mutex::new(Vec::new()));
+/// let router = actor_system.builder().spawn("router", RoundRobinRouterFactory::new()).unwrap();
+/// for name in ["a", "b"].iter() {
+///     let printer = actor_system.builder().spawn(*name, PrinterFactory { name: *name, printed: printed.clone() }).unwrap();
+///     router.send(AddActorMessage::new(printer));
+/// }
+/// for page in 0..4 {
+///     router.send(LazyRouterMessage::new(move || Report { text: format!("page {}", page) }));
+/// }
+/// sleep(Duration::from_millis(200));
+/// let printed = printed.lock().unwrap();
+/// assert_eq!(4, printed.len());
+/// assert_eq!(2, printed.iter().filter(|(name, _)| *name == "a").count());
+/// ```
+pub struct LazyRouterMessage<M>
+where
+    M: ActorMessage + 'static,
+{
+    pub(crate) constructor: Constructor<M>,
+    pub key: Option<u64>,
+}
+
+impl<M> ActorMessage for LazyRouterMessage<M> where M: ActorMessage + 'static {}
+
+impl<M> LazyRouterMessage<M>
+where
+    M: ActorMessage + 'static,
+{
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() -> M + Send + 'static,
+    {
+        Self {
+            constructor: Constructor::new(f),
+            key: None,
+        }
+    }
+
+    pub fn with_key<F, K>(f: F, key: &K) -> Self
+    where
+        F: FnOnce() -> M + Send + 'static,
+        K: Hash + ?Sized,
+    {
+        Self {
+            constructor: Constructor::new(f),
+            key: Some(hash_key(key)),
+        }
     }
 }
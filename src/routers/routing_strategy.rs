@@ -0,0 +1,343 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::message::actor_message::ActorMessage;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+/// Topic of the [EventBus](../prelude/struct.EventBus.html) on which every [RoutingStrategyChanged](./struct.RoutingStrategyChanged.html) is published
+pub const ROUTING_STRATEGY_TOPIC: &str = "router.strategy";
+
+/// Virtual nodes per routee on the consistent hash ring, unless configured otherwise
+pub(crate) const DEFAULT_VIRTUAL_NODES: u64 = 64;
+
+/// Selection algorithm of a router, see [SetStrategy](./struct.SetStrategy.html)
+///
+/// Messages without a key, see [RouterMessage.with_key](./struct.RouterMessage.html#method.with_key), are routed round robin by the key based strategies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoutingStrategy {
+    RoundRobin,
+    /// routes to the routee with the shortest mailbox, ties are resolved round robin
+    SmallestMailbox,
+    /// routes equal keys to the same routee, only keys of removed routees move to another routee
+    ConsistentHash,
+    /// routes a key to the routee it has first been routed to, as long as that routee is part of the router
+    Sticky,
+}
+
+/// Switches the [RoutingStrategy](./enum.RoutingStrategy.html) of a router, routees and queued messages are kept
+///
+/// - the consistent hash ring is rebuilt from the current routees
+/// - sticky sessions are kept if `preserve_sessions` is set, otherwise they are flushed
+/// - an [AdaptiveStrategy](./struct.AdaptiveStrategy.html) of the router is disabled
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::{AddActorMessage, RoundRobinRouterFactory, RouterMessage, RoutingStrategy};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Job { id: u64 }
+/// impl ActorMessage for Job {}
+///
+/// struct Worker {
+///     name: &'static str,
+///     gate: Arc<AtomicBool>,
+///     received: Arc<Mutex<Vec<(&'static str, u64)>>>,
+/// }
+/// impl Actor for Worker {}
+/// impl Handler<Job> for Worker {
+///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+///         while !self.gate.load(Ordering::SeqCst) {
+///             sleep(Duration::from_millis(1));
+///         }
+///         self.received.lock().unwrap().push((self.name, msg.id));
+///     }
+/// }
+/// struct WorkerFactory {
+///     name: &'static str,
+///     gate: Arc<AtomicBool>,
+///     received: Arc<Mutex<Vec<(&'static str, u64)>>>,
+/// }
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+///         Worker { name: self.name, gate: self.gate.clone(), received: self.received.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let received = Arc::new(Mutex::new(Vec::new()));
+/// let closed = Arc::new(AtomicBool::new(false));
+/// let open = Arc::new(AtomicBool::new(true));
+/// let router = actor_system.builder().spawn("router", RoundRobinRouterFactory::new()).unwrap();
+/// for (name, gate) in [("slow", &closed), ("fast-1", &open), ("fast-2", &open)].iter() {
+///     let factory = WorkerFactory { name: *name, gate: (*gate).clone(), received: received.clone() };
+///     router.send(AddActorMessage::new(actor_system.builder().spawn(*name, factory).unwrap()));
+/// }
+///
+/// // round robin keeps feeding the slow routee
+/// for id in 0..60 {
+///     router.send(RouterMessage::new(Job { id }));
+/// }
+/// sleep(Duration::from_millis(300));
+/// assert_eq!(40, received.lock().unwrap().len());
+///
+/// router.set_strategy(RoutingStrategy::SmallestMailbox, false);
+/// for id in 60..80 {
+///     router.send(RouterMessage::new(Job { id }));
+/// }
+/// sleep(Duration::from_millis(300));
+/// assert_eq!(60, received.lock().unwrap().len());
+/// assert!(received.lock().unwrap().iter().all(|(name, _)| *name != "slow"));
+///
+/// // nothing has been lost or duplicated during the switch
+/// closed.store(true, Ordering::SeqCst);
+/// sleep(Duration::from_millis(300));
+/// let mut ids: Vec<u64> = received.lock().unwrap().iter().map(|(_, id)| *id).collect();
+/// ids.sort();
+/// assert_eq!((0..80).collect::<Vec<u64>>(), ids);
+/// ```
+pub struct SetStrategy {
+    pub strategy: RoutingStrategy,
+    pub preserve_sessions: bool,
+}
+
+impl SetStrategy {
+    pub fn new(strategy: RoutingStrategy, preserve_sessions: bool) -> Self {
+        Self {
+            strategy,
+            preserve_sessions,
+        }
+    }
+}
+
+impl ActorMessage for SetStrategy {}
+
+/// Published on [ROUTING_STRATEGY_TOPIC](./constant.ROUTING_STRATEGY_TOPIC.html) whenever a router switches its strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingStrategyChanged {
+    /// name of the router
+    pub router: String,
+    pub previous: RoutingStrategy,
+    pub strategy: RoutingStrategy,
+    /// `true` if the switch was decided by an [AdaptiveStrategy](./struct.AdaptiveStrategy.html)
+    pub adaptive: bool,
+}
+
+impl ActorMessage for RoutingStrategyChanged {}
+
+/// Switches between two strategies depending on the variance of the mailbox lengths of all routees
+///
+/// Switches to `skewed` once the variance reached `high_variance` and back to `uniform` once it fell below `low_variance`.
+/// The variance is sampled every `sample_every` routed messages
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveStrategy {
+    pub uniform: RoutingStrategy,
+    pub skewed: RoutingStrategy,
+    pub high_variance: f64,
+    pub low_variance: f64,
+    pub sample_every: usize,
+}
+
+impl AdaptiveStrategy {
+    /// Panics unless `low_variance < high_variance`
+    pub fn new(uniform: RoutingStrategy, skewed: RoutingStrategy, high_variance: f64, low_variance: f64) -> Self {
+        assert!(low_variance < high_variance, "low_variance must be lower than high_variance");
+        Self {
+            uniform,
+            skewed,
+            high_variance,
+            low_variance,
+            sample_every: 8,
+        }
+    }
+
+    pub fn with_sample_every(mut self, sample_every: usize) -> Self {
+        self.sample_every = sample_every.max(1);
+        self
+    }
+
+    /// Returns the strategy for the sampled variance, keeps `current` between both thresholds
+    fn decide(&self, current: RoutingStrategy, variance: f64) -> RoutingStrategy {
+        if current != self.skewed && variance >= self.high_variance {
+            return self.skewed;
+        }
+        if current != self.uniform && variance < self.low_variance {
+            return self.uniform;
+        }
+        current
+    }
+}
+
+pub(crate) fn hash_key<K>(key: &K) -> u64
+where
+    K: Hash + ?Sized,
+{
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Selects routees for a router, all state that has to be carried over between strategies lives here
+pub(crate) struct RouteSelector {
+    strategy: RoutingStrategy,
+    adaptive: Option<AdaptiveStrategy>,
+    route_index: usize,
+    routed: usize,
+    ring: BTreeMap<u64, usize>,
+    virtual_nodes: u64,
+    sessions: HashMap<u64, ActorAddress>,
+}
+
+impl RouteSelector {
+    pub fn new(strategy: RoutingStrategy, adaptive: Option<AdaptiveStrategy>) -> Self {
+        Self {
+            strategy: adaptive.map_or(strategy, |adaptive| adaptive.uniform),
+            adaptive,
+            route_index: 0,
+            routed: 0,
+            ring: BTreeMap::new(),
+            virtual_nodes: DEFAULT_VIRTUAL_NODES,
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// Points of every routee on the hash ring of [RoutingStrategy::ConsistentHash]
+    pub fn with_virtual_nodes(mut self, virtual_nodes: u64) -> Self {
+        self.virtual_nodes = virtual_nodes.max(1);
+        self
+    }
+
+    pub fn get_strategy(&self) -> RoutingStrategy {
+        self.strategy
+    }
+
+    /// Returns the previous strategy
+    pub fn set_strategy<A>(&mut self, msg: SetStrategy, routees: &[ActorWrapper<A>]) -> RoutingStrategy
+    where
+        A: Actor + 'static,
+    {
+        self.adaptive = None;
+        if !msg.preserve_sessions {
+            self.sessions.clear();
+        }
+        self.switch(msg.strategy, routees)
+    }
+
+    fn switch<A>(&mut self, strategy: RoutingStrategy, routees: &[ActorWrapper<A>]) -> RoutingStrategy
+    where
+        A: Actor + 'static,
+    {
+        let previous = self.strategy;
+        self.strategy = strategy;
+        self.rebuild(routees);
+        previous
+    }
+
+    /// Called whenever the routees changed
+    pub fn rebuild<A>(&mut self, routees: &[ActorWrapper<A>])
+    where
+        A: Actor + 'static,
+    {
+        self.ring.clear();
+        if self.strategy == RoutingStrategy::ConsistentHash {
+            for (index, routee) in routees.iter().enumerate() {
+                let address = routee.get_address();
+                for node in 0..self.virtual_nodes {
+                    self.ring.insert(hash_key(&(address, node)), index);
+                }
+            }
+        }
+        self.sessions
+            .retain(|_, session| routees.iter().any(|routee| routee.get_address() == session));
+    }
+
+    /// Samples the mailboxes every `sample_every` messages, returns the previous strategy if the adaptive strategy switched
+    // is_multiple_of needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn adapt<A>(&mut self, routees: &[ActorWrapper<A>]) -> Option<RoutingStrategy>
+    where
+        A: Actor + 'static,
+    {
+        let adaptive = self.adaptive?;
+        self.routed += 1;
+        if self.routed % adaptive.sample_every != 0 || routees.is_empty() {
+            return None;
+        }
+        let lengths: Vec<f64> = routees.iter().map(|routee| routee.mailbox_len() as f64).collect();
+        let mean = lengths.iter().sum::<f64>() / lengths.len() as f64;
+        let variance = lengths.iter().map(|length| (length - mean).powi(2)).sum::<f64>() / lengths.len() as f64;
+        let strategy = adaptive.decide(self.strategy, variance);
+        if strategy == self.strategy {
+            return None;
+        }
+        Some(self.switch(strategy, routees))
+    }
+
+    /// `is_available` filters routees for the round robin selection, it is ignored if no routee is available
+    pub fn select<A>(
+        &mut self,
+        key: Option<u64>,
+        routees: &[ActorWrapper<A>],
+        is_available: impl Fn(&ActorWrapper<A>) -> bool,
+    ) -> usize
+    where
+        A: Actor + 'static,
+    {
+        match (self.strategy, key) {
+            (RoutingStrategy::SmallestMailbox, _) => {
+                let start = self.next_index(routees.len());
+                // stopped routees have an empty mailbox, but they would only swallow the message
+                let index = (0..routees.len())
+                    .map(|offset| (start + offset) % routees.len())
+                    .filter(|index| !routees[*index].get_mailbox().is_stopped())
+                    .min_by_key(|index| routees[*index].mailbox_len())
+                    .unwrap_or(start);
+                self.route_index = index;
+                index
+            }
+            (RoutingStrategy::ConsistentHash, Some(key)) => {
+                let node = self.ring.range(key..).next().or_else(|| self.ring.iter().next());
+                node.map_or(0, |(_, index)| *index)
+            }
+            (RoutingStrategy::Sticky, Some(key)) => {
+                if let Some(session) = self.sessions.get(&key) {
+                    if let Some(index) = routees.iter().position(|routee| routee.get_address() == session) {
+                        return index;
+                    }
+                }
+                let index = self.round_robin(routees, is_available);
+                self.sessions.insert(key, routees[index].get_address().clone());
+                index
+            }
+            _ => self.round_robin(routees, is_available),
+        }
+    }
+
+    fn next_index(&self, routees: usize) -> usize {
+        if self.route_index + 1 >= routees {
+            0
+        } else {
+            self.route_index + 1
+        }
+    }
+
+    fn round_robin<A>(&mut self, routees: &[ActorWrapper<A>], is_available: impl Fn(&ActorWrapper<A>) -> bool) -> usize
+    where
+        A: Actor + 'static,
+    {
+        let start = self.next_index(routees.len());
+        self.route_index = (0..routees.len())
+            .map(|offset| (start + offset) % routees.len())
+            .find(|index| is_available(&routees[*index]))
+            .unwrap_or(start);
+        self.route_index
+    }
+}
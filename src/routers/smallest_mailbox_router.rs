@@ -0,0 +1,92 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::context::ActorContext;
+use crate::routers::round_robin_router::{EmptyRouterPolicy, RoundRobinRouter, RoundRobinRouterFactory};
+use crate::routers::routing_strategy::RoutingStrategy;
+
+/// implements [ActorFactory](../prelude/trait.ActorFactory.html) to spawn a router that routes every message to the routee with the fewest queued messages
+///
+/// Shorthand for a [RoundRobinRouterFactory](./struct.RoundRobinRouterFactory.html) with [RoutingStrategy::SmallestMailbox](./enum.RoutingStrategy.html#variant.SmallestMailbox).
+/// Ties are resolved round robin, stopped routees are skipped. Routees are added and removed with [AddActorMessage](./struct.AddActorMessage.html) and [RemoveActorMessage](./struct.RemoveActorMessage.html)
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::{AddActorMessage, RouterMessage, SmallestMailboxRouterFactory};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Job;
+/// impl ActorMessage for Job {}
+///
+/// struct Worker { delay: Duration, handled: Arc<AtomicUsize> }
+/// impl Actor for Worker {}
+/// impl Handler<Job> for Worker {
+///     fn handle(&mut self, _msg: Job, _context: &ActorContext<Self>) {
+///         sleep(self.delay);
+///         self.handled.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+/// struct WorkerFactory { delay: Duration, handled: Arc<AtomicUsize> }
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+///         Worker { delay: self.delay, handled: self.handled.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let router = actor_system.builder().spawn("jobs", SmallestMailboxRouterFactory::new()).unwrap();
+/// let slow = Arc::new(AtomicUsize::new(0));
+/// let fast = Arc::new(AtomicUsize::new(0));
+/// let factory = WorkerFactory { delay: Duration::from_millis(50), handled: slow.clone() };
+/// router.send(AddActorMessage::new(actor_system.builder().spawn("slow", factory).unwrap()));
+/// let factory = WorkerFactory { delay: Duration::from_millis(1), handled: fast.clone() };
+/// router.send(AddActorMessage::new(actor_system.builder().spawn("fast", factory).unwrap()));
+///
+/// for _ in 0..40 {
+///     router.send(RouterMessage::new(Job));
+///     sleep(Duration::from_millis(5));
+/// }
+/// sleep(Duration::from_millis(500));
+/// assert_eq!(40, slow.load(Ordering::SeqCst) + fast.load(Ordering::SeqCst));
+/// // the slow routee keeps a backlog, most jobs end up on the fast one
+/// assert!(fast.load(Ordering::SeqCst) > 2 * slow.load(Ordering::SeqCst));
+/// actor_system.stop(Duration::from_secs(1));
+/// ```
+pub struct SmallestMailboxRouterFactory {
+    router: RoundRobinRouterFactory,
+}
+
+impl SmallestMailboxRouterFactory {
+    pub fn new() -> Self {
+        Self {
+            router: RoundRobinRouterFactory::new().with_strategy(RoutingStrategy::SmallestMailbox),
+        }
+    }
+
+    /// See [RoundRobinRouterFactory.with_empty_policy](./struct.RoundRobinRouterFactory.html#method.with_empty_policy)
+    pub fn with_empty_policy(mut self, empty_policy: EmptyRouterPolicy) -> Self {
+        self.router = self.router.with_empty_policy(empty_policy);
+        self
+    }
+}
+
+impl Default for SmallestMailboxRouterFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> ActorFactory<RoundRobinRouter<A>> for SmallestMailboxRouterFactory
+where
+    A: Actor + 'static,
+{
+    fn new_actor(&self, context: ActorContext<RoundRobinRouter<A>>) -> RoundRobinRouter<A> {
+        self.router.new_actor(context)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::routers::add_actor_message::AddActorMessage;
+use crate::routers::remove_actor_message::RemoveActorMessage;
+use crate::routers::round_robin_router::{RoundRobinRouter, RoundRobinRouterFactory};
+use crate::routers::router_message::RouterMessage;
+use crate::routers::routing_strategy::RoutingStrategy;
+use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::{self, SpawnError, SpawnSpec};
+use std::hash::Hash;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type WorkerSpawner<A> = Arc<dyn Fn(String) -> Result<ActorWrapper<A>, SpawnError> + Send + Sync>;
+
+/// Spawns a [WorkerPool](./struct.WorkerPool.html), see [ActorSystem.worker_pool_builder](../prelude/struct.ActorSystem.html#method.worker_pool_builder)
+pub struct WorkerPoolBuilder<A>
+where
+    A: Actor + 'static,
+{
+    system: ActorSystem,
+    name: String,
+    size: usize,
+    strategy: RoutingStrategy,
+    builder: ActorBuilder<A>,
+}
+
+impl<A> WorkerPoolBuilder<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub(crate) fn new(system: ActorSystem, name: String) -> Self {
+        let builder = system.builder();
+        Self {
+            system,
+            name,
+            size: 1,
+            strategy: RoutingStrategy::RoundRobin,
+            builder,
+        }
+    }
+
+    /// Amount of workers that are spawned, defaults to 1
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// [RoutingStrategy](./enum.RoutingStrategy.html) of the router in front of the workers, defaults to `RoundRobin`
+    pub fn router(mut self, strategy: RoutingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Spawns every worker with the settings of `builder`, including the ones spawned by [WorkerPool.resize](./struct.WorkerPool.html#method.resize)
+    pub fn actor_builder(mut self, builder: ActorBuilder<A>) -> Self {
+        self.builder = builder;
+        self
+    }
+
+    /// Spawns the router `<name>` and the workers `<name>-0` to `<name>-<size - 1>`
+    ///
+    /// Nothing keeps running if a single worker can not be spawned
+    pub fn spawn<P>(self, factory: P) -> Result<WorkerPool<A>, SpawnError>
+    where
+        P: ActorFactory<A> + Clone + Send + Sync + 'static,
+    {
+        let router = self
+            .system
+            .builder()
+            .try_spawn(self.name.clone(), RoundRobinRouterFactory::new().with_strategy(self.strategy))?;
+        let builder = self.builder;
+        let spawner = move |name: String| bulk_spawn::spawn_one(&builder, SpawnSpec::new(name, factory.clone()));
+        let pool = WorkerPool {
+            name: self.name,
+            router,
+            state: Arc::new(WorkerPoolState {
+                workers: Mutex::new(Vec::new()),
+                next_index: AtomicUsize::new(0),
+                spawner: Arc::new(spawner),
+            }),
+        };
+        if let Err(error) = pool.resize(self.size) {
+            pool.stop();
+            return Err(error);
+        }
+        Ok(pool)
+    }
+}
+
+struct WorkerPoolState<A>
+where
+    A: Actor + 'static,
+{
+    /// in spawn order, the pool shrinks from the end
+    workers: Mutex<Vec<ActorWrapper<A>>>,
+    /// names are never reused, a removed worker might still be stopping
+    next_index: AtomicUsize,
+    spawner: WorkerSpawner<A>,
+}
+
+/// Identical workers behind a single router, see [ActorSystem.worker_pool_builder](../prelude/struct.ActorSystem.html#method.worker_pool_builder)
+///
+/// Restarted workers keep their place in the router. Workers that stop for good are skipped by the router, [resize](#method.resize) replaces them.
+/// Clones share the same workers
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use tyractorsaur::router::*;
+/// use std::collections::HashMap;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Job { id: usize }
+/// impl ActorMessage for Job {}
+///
+/// struct Worker { name: String, handled: Arc<Mutex<HashMap<usize, String>>> }
+/// impl Actor for Worker {}
+/// impl Handler<Job> for Worker {
+///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+///         sleep(Duration::from_millis(1));
+///         self.handled.lock().unwrap().insert(msg.id, self.name.clone());
+///     }
+/// }
+/// #[derive(Clone)]
+/// struct WorkerFactory { handled: Arc<Mutex<HashMap<usize, String>>> }
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+///         Worker { name: context.actor_ref.get_address().actor.clone(), handled: self.handled.clone() }
+///     }
+/// }
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let handled = Arc::new(Mutex::new(HashMap::new()));
+/// let pool = actor_system
+///     .worker_pool_builder("workers")
+///     .size(4)
+///     .router(RoutingStrategy::RoundRobin)
+///     .actor_builder(actor_system.builder().set_mailbox_size(1000))
+///     .spawn(WorkerFactory { handled: handled.clone() })
+///     .unwrap();
+/// let names = |pool: &WorkerPool<Worker>| -> Vec<String> { pool.workers().iter().map(|worker| worker.get_address().actor.clone()).collect() };
+/// assert_eq!(vec!["workers-0", "workers-1", "workers-2", "workers-3"], names(&pool));
+/// assert_eq!(5, actor_system.get_actor_count());
+///
+/// // messages are distributed across all workers
+/// for id in 0..100 {
+///     pool.send(Job { id }).unwrap();
+/// }
+/// sleep(Duration::from_millis(300));
+/// let per_worker = |range: std::ops::Range<usize>| {
+///     let handled = handled.lock().unwrap();
+///     let mut per_worker: HashMap<String, usize> = HashMap::new();
+///     for id in range {
+///         *per_worker.entry(handled[&id].clone()).or_default() += 1;
+///     }
+///     per_worker
+/// };
+/// assert!(per_worker(0..100).values().all(|count| *count == 25));
+///
+/// // resizing under traffic neither loses nor duplicates messages
+/// for id in 100..400 {
+///     pool.send(Job { id }).unwrap();
+///     if id == 150 {
+///         pool.resize(6).unwrap();
+///     }
+///     if id == 300 {
+///         pool.resize(2).unwrap();
+///     }
+/// }
+/// sleep(Duration::from_millis(800));
+/// assert_eq!(400, handled.lock().unwrap().len());
+/// assert_eq!(vec!["workers-0", "workers-1"], names(&pool));
+/// assert!(per_worker(150..300).contains_key("workers-5"));
+/// assert_eq!(3, actor_system.get_actor_count());
+///
+/// // stopping the pool stops the router and every worker
+/// pool.stop();
+/// assert!(pool.await_stop(Duration::from_secs(1)));
+/// sleep(Duration::from_millis(100));
+/// assert_eq!(0, actor_system.get_actor_count());
+/// assert!(matches!(pool.send(Job { id: 400 }), Err(SendError::Stopped(_))));
+/// ```
+pub struct WorkerPool<A>
+where
+    A: Actor + 'static,
+{
+    name: String,
+    router: ActorWrapper<RoundRobinRouter<A>>,
+    state: Arc<WorkerPoolState<A>>,
+}
+
+impl<A> Clone for WorkerPool<A>
+where
+    A: Actor + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            router: self.router.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<A> WorkerPool<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// Routes `msg` to one of the workers
+    pub fn send<M>(&self, msg: M) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        self.router.send(RouterMessage::new(msg)).map_err(|error| error.map(|msg| msg.msg))
+    }
+
+    /// Same as [send](#method.send), `key` is used by the key based [RoutingStrategy](./enum.RoutingStrategy.html)s
+    pub fn send_with_key<M, K>(&self, msg: M, key: &K) -> Result<(), SendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+        K: Hash + ?Sized,
+    {
+        self.router
+            .send(RouterMessage::with_key(msg, key))
+            .map_err(|error| error.map(|msg| msg.msg))
+    }
+
+    /// Spawns or stops workers until the pool holds `size` of them
+    ///
+    /// New workers are added to the router once they are spawned. Removed workers, the most recent ones first, leave the router before they are stopped,
+    /// so they only handle what has already been routed to them. Stops at the first worker that can not be spawned
+    pub fn resize(&self, size: usize) -> Result<(), SpawnError> {
+        let mut workers = self.state.workers.lock().unwrap();
+        while workers.len() < size {
+            let index = self.state.next_index.fetch_add(1, Ordering::Relaxed);
+            let worker = (self.state.spawner)(format!("{}-{}", self.name, index))?;
+            let _ = self.router.send(AddActorMessage::new(worker.clone()));
+            workers.push(worker);
+        }
+        while workers.len() > size {
+            let worker = workers.pop().unwrap();
+            let _ = self.router.send(RemoveActorMessage::new(worker.clone()));
+            self.stop_after_router(vec![worker]);
+        }
+        Ok(())
+    }
+
+    /// Stops `workers` once the router handled everything that has been sent to it so far, right away if the router already stopped
+    fn stop_after_router(&self, workers: Vec<ActorWrapper<A>>) {
+        let stopping = workers.clone();
+        let stopped = self.router.exec(move |_router, _context| {
+            for worker in stopping {
+                worker.stop();
+            }
+        });
+        if stopped.is_err() {
+            for worker in workers {
+                worker.stop();
+            }
+        }
+    }
+
+    /// Amount of workers, including workers that stopped for good since the last [resize](#method.resize)
+    pub fn size(&self) -> usize {
+        self.state.workers.lock().unwrap().len()
+    }
+
+    /// All workers in spawn order
+    pub fn workers(&self) -> Vec<ActorWrapper<A>> {
+        self.state.workers.lock().unwrap().clone()
+    }
+
+    /// Stops the router and all workers, messages that have already been sent are handled first
+    pub fn stop(&self) {
+        self.stop_after_router(self.workers());
+        self.router.stop();
+    }
+
+    /// Blocks until the router and all workers stopped, returns `false` once `timeout` elapsed
+    pub fn await_stop(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let remaining = || deadline.saturating_duration_since(Instant::now());
+        self.router.await_stop(remaining()) && self.workers().iter().all(|worker| worker.await_stop(remaining()))
+    }
+}
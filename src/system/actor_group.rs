@@ -0,0 +1,287 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_factory::TryActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::{self, SpawnError, SpawnSpec};
+use std::any::Any;
+use std::collections::HashSet;
+use std::panic::UnwindSafe;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type SpawnMember = Box<dyn FnOnce(&ActorSystem, String) -> Result<Box<dyn GroupMember>, SpawnError>>;
+
+/// Type erased [ActorWrapper] of a member of a [GroupHandle]
+trait GroupMember: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn get_address(&self) -> &ActorAddress;
+    fn stop(&self) -> bool;
+    fn stop_now(&self) -> bool;
+    fn await_stop(&self, timeout: Duration) -> bool;
+    fn is_running(&self) -> bool;
+}
+
+impl<A> GroupMember for ActorWrapper<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_address(&self) -> &ActorAddress {
+        ActorWrapper::get_address(self)
+    }
+
+    fn stop(&self) -> bool {
+        ActorWrapper::stop(self)
+    }
+
+    fn stop_now(&self) -> bool {
+        ActorWrapper::stop_now(self)
+    }
+
+    fn await_stop(&self, timeout: Duration) -> bool {
+        ActorWrapper::await_stop(self, timeout)
+    }
+
+    fn is_running(&self) -> bool {
+        let mailbox = self.get_mailbox();
+        !mailbox.is_stopped() && !mailbox.is_stop_requested()
+    }
+}
+
+/// Reason [ActorGroupBuilder.spawn](./struct.ActorGroupBuilder.html#method.spawn) failed
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupSpawnError {
+    /// name of the first member that could not be spawned
+    pub member: String,
+    pub error: SpawnError,
+    /// members that had been spawned and were stopped again
+    pub rolled_back: usize,
+}
+
+/// Collects the members of a group, see [ActorSystem.group](../prelude/struct.ActorSystem.html#method.group)
+pub struct ActorGroupBuilder {
+    system: ActorSystem,
+    name: String,
+    members: Vec<(String, SpawnMember)>,
+}
+
+impl ActorGroupBuilder {
+    pub(crate) fn new(system: ActorSystem, name: String) -> Self {
+        Self {
+            system,
+            name,
+            members: Vec::new(),
+        }
+    }
+
+    /// Adds a member, members are spawned in the order they have been added
+    ///
+    /// The Actor is spawned through the default [ActorBuilder](../prelude/struct.ActorBuilder.html) as `<group>-<name>`
+    pub fn add<A, P>(mut self, name: impl Into<String>, factory: P) -> Self
+    where
+        A: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<A> + 'static,
+    {
+        let spawn: SpawnMember = Box::new(move |system, actor_name| {
+            let actor = bulk_spawn::spawn_one(&system.builder::<A>(), SpawnSpec::new(actor_name, factory))?;
+            Ok(Box::new(actor) as Box<dyn GroupMember>)
+        });
+        self.members.push((name.into(), spawn));
+        self
+    }
+
+    /// Spawns all members or none of them
+    ///
+    /// If a member can not be spawned, all members spawned before are stopped again in reverse order.
+    /// Fails with [SpawnError::DuplicateName](../prelude/enum.SpawnError.html#variant.DuplicateName) without spawning anything if a name has been added twice
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Reader {}
+    /// impl Actor for Reader {}
+    /// struct ReaderFactory {}
+    /// impl ActorFactory<Reader> for ReaderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Reader>) -> Reader {
+    ///         Reader {}
+    ///     }
+    /// }
+    ///
+    /// struct Writer {}
+    /// impl Actor for Writer {}
+    /// struct WriterFactory { is_reachable: bool }
+    /// impl TryActorFactory<Writer> for WriterFactory {
+    ///     fn try_new_actor(&self, _context: ActorContext<Writer>) -> Result<Writer, ActorInitError> {
+    ///         match self.is_reachable {
+    ///             true => Ok(Writer {}),
+    ///             false => Err(ActorInitError::new("sink unreachable")),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let error = actor_system
+    ///     .group("pipeline")
+    ///     .add("reader", ReaderFactory {})
+    ///     .add("writer", WriterFactory { is_reachable: false })
+    ///     .spawn()
+    ///     .err()
+    ///     .unwrap();
+    /// assert_eq!("writer", error.member);
+    /// assert_eq!(SpawnError::FactoryFailed(ActorInitError::new("sink unreachable")), error.error);
+    /// assert_eq!(1, error.rolled_back);
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(0, actor_system.get_actor_count());
+    ///
+    /// let group = actor_system
+    ///     .group("pipeline")
+    ///     .add("reader", ReaderFactory {})
+    ///     .add("writer", WriterFactory { is_reachable: true })
+    ///     .spawn()
+    ///     .unwrap();
+    /// assert_eq!(2, actor_system.get_actor_count());
+    /// assert_eq!("pipeline-writer", group.get::<Writer>("writer").unwrap().get_address().actor);
+    /// // typed accessors check the type of the member
+    /// assert!(group.get::<Reader>("writer").is_none());
+    /// ```
+    pub fn spawn(self) -> Result<GroupHandle, GroupSpawnError> {
+        let mut names = HashSet::new();
+        if let Some((name, _)) = self.members.iter().find(|(name, _)| !names.insert(name.as_str())) {
+            return Err(GroupSpawnError {
+                member: name.clone(),
+                error: SpawnError::DuplicateName,
+                rolled_back: 0,
+            });
+        }
+        let mut spawned: Vec<(String, Box<dyn GroupMember>)> = Vec::with_capacity(self.members.len());
+        for (name, spawn) in self.members {
+            let actor_name = format!("{}-{}", self.name, name);
+            match spawn(&self.system, actor_name) {
+                Ok(member) => spawned.push((name, member)),
+                Err(error) => {
+                    for (_, member) in spawned.iter().rev() {
+                        member.stop();
+                    }
+                    return Err(GroupSpawnError {
+                        member: name,
+                        error,
+                        rolled_back: spawned.len(),
+                    });
+                }
+            }
+        }
+        Ok(GroupHandle {
+            name: self.name,
+            members: Arc::new(spawned),
+        })
+    }
+}
+
+/// Members of a spawned group, see [ActorSystem.group](../prelude/struct.ActorSystem.html#method.group)
+///
+/// Members are regular Actors, they count towards [ActorSystem.get_actor_count](../prelude/struct.ActorSystem.html#method.get_actor_count) until they are removed and are stopped with the system as any other Actor
+#[derive(Clone)]
+pub struct GroupHandle {
+    name: String,
+    members: Arc<Vec<(String, Box<dyn GroupMember>)>>,
+}
+
+impl GroupHandle {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// `None` if there is no member with that name or if it is not of type `A`
+    pub fn get<A>(&self, name: &str) -> Option<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.members
+            .iter()
+            .find(|(member, _)| member == name)
+            .and_then(|(_, actor)| actor.as_any().downcast_ref::<ActorWrapper<A>>())
+            .cloned()
+    }
+
+    /// Names and addresses of all members, in creation order
+    pub fn members(&self) -> Vec<(&str, &ActorAddress)> {
+        self.members.iter().map(|(name, actor)| (name.as_str(), actor.get_address())).collect()
+    }
+
+    /// `true` as long as no member is stopping or has stopped, regardless of how it has been stopped
+    pub fn is_complete(&self) -> bool {
+        self.members.iter().all(|(_, actor)| actor.is_running())
+    }
+
+    /// Stops the members in reverse creation order, each one is awaited before the next one is stopped
+    ///
+    /// Once `graceful` is exceeded the remaining members are stopped through [ActorWrapper.stop_now](../prelude/struct.ActorWrapper.html#method.stop_now) without awaiting them.
+    /// Returns `false` in that case
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Stage { name: String, stopped: Arc<Mutex<Vec<String>>> }
+    /// impl Actor for Stage {
+    ///     fn post_stop(&mut self) {
+    ///         self.stopped.lock().unwrap().push(self.name.clone());
+    ///     }
+    /// }
+    /// struct StageFactory { name: &'static str, stopped: Arc<Mutex<Vec<String>>> }
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stage>) -> Stage {
+    ///         Stage { name: self.name.to_string(), stopped: self.stopped.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let stopped = Arc::new(Mutex::new(Vec::new()));
+    /// let mut builder = actor_system.group("pipeline");
+    /// for name in ["source", "transform", "sink"] {
+    ///     builder = builder.add(name, StageFactory { name, stopped: stopped.clone() });
+    /// }
+    /// let group = builder.spawn().unwrap();
+    /// assert!(group.is_complete());
+    ///
+    /// // stopping a single member is reflected by the group
+    /// let transform = group.get::<Stage>("transform").unwrap();
+    /// transform.stop();
+    /// assert!(!group.is_complete());
+    /// assert!(transform.await_stop(Duration::from_secs(1)));
+    ///
+    /// assert!(group.stop(Duration::from_secs(1)));
+    /// assert_eq!(vec!["transform", "sink", "source"], *stopped.lock().unwrap());
+    /// assert_eq!(0, actor_system.get_actor_count());
+    /// ```
+    pub fn stop(&self, graceful: Duration) -> bool {
+        let deadline = Instant::now() + graceful;
+        let mut is_graceful = true;
+        for (_, actor) in self.members.iter().rev() {
+            if is_graceful {
+                actor.stop();
+                if actor.await_stop(deadline.saturating_duration_since(Instant::now())) {
+                    continue;
+                }
+                is_graceful = false;
+            }
+            actor.stop_now();
+        }
+        is_graceful
+    }
+}
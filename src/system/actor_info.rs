@@ -0,0 +1,39 @@
+use crate::actor::actor_address::ActorAddress;
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Scheduling state of a listed Actor, see [ActorInfo]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ActorStatus {
+    /// woken up, waiting for or running on a thread of its pool, idle Actors stay scheduled for a few seconds before they go to sleep
+    Scheduled,
+    /// waits for the next message or the end of a suspension without occupying a thread
+    Sleeping,
+    /// spawned suspended and not released yet, see [ActorWrapper.release](../prelude/struct.ActorWrapper.html#method.release)
+    AwaitingRelease,
+    /// the system is stopping, the Actor handles or already handled the system stop
+    Stopping,
+}
+
+/// Entry of [ActorSystem.list_actors](../prelude/struct.ActorSystem.html#method.list_actors)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActorInfo {
+    pub address: ActorAddress,
+    /// pool the Actor is scheduled on, the pool of its address unless it has been moved, see [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor)
+    #[serde(default)]
+    pub pool: String,
+    pub status: ActorStatus,
+    /// messages waiting in the regular mailbox
+    pub mailbox_len: usize,
+    pub processed: usize,
+    pub panics: usize,
+    pub restarts: usize,
+    /// when the Actor finished handling its last message, `None` if it has not handled any message yet
+    pub last_activity: Option<SystemTime>,
+    /// stopped after all other Actors, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon)
+    #[serde(default)]
+    pub daemon: bool,
+    /// message types the Actor accepts, see [ActorBuilder.register_message](../prelude/struct.ActorBuilder.html#method.register_message)
+    #[serde(default)]
+    pub accepted_messages: Vec<String>,
+}
@@ -0,0 +1,88 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::message::serialized_message::SerializedMessage;
+use crate::system::actor_system::ActorSystem;
+use std::panic::UnwindSafe;
+
+/// Actors whose `pool/name` matched the pattern of [ActorSystem.select](../prelude/struct.ActorSystem.html#method.select)
+///
+/// The match is computed once when the selection is created, Actors spawned afterwards are not part of it
+pub struct ActorSelection {
+    system: ActorSystem,
+    /// sorted
+    addresses: Vec<ActorAddress>,
+}
+
+impl ActorSelection {
+    pub(crate) fn new(system: ActorSystem, mut addresses: Vec<ActorAddress>) -> Self {
+        addresses.sort();
+        Self { system, addresses }
+    }
+
+    pub fn get_addresses(&self) -> &[ActorAddress] {
+        &self.addresses
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// Sends a copy of `msg` to every selected Actor, returns the amount of Actors that accepted it
+    ///
+    /// Same as [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address) per Actor, Actors that stopped in the meantime are dead letters
+    pub fn send_serialized(&self, msg: SerializedMessage) -> usize {
+        self.addresses
+            .iter()
+            .filter(|address| self.system.try_send_to_address(address, msg.clone()).is_ok())
+            .count()
+    }
+
+    /// Typed references of all selected Actors of type `A` that are still running, Actors of other types are skipped
+    pub fn try_into_typed<A>(&self) -> Vec<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.addresses
+            .iter()
+            .filter_map(|address| self.system.get_state().get_actor_wrapper(address))
+            .collect()
+    }
+}
+
+/// Matches `path` against `pattern` segment by segment, `*` matches any amount of characters within a segment
+pub(crate) fn matches(pattern: &str, path: &str) -> bool {
+    let patterns: Vec<&str> = pattern.split('/').collect();
+    let segments: Vec<&str> = path.split('/').collect();
+    patterns.len() == segments.len()
+        && patterns
+            .iter()
+            .zip(segments)
+            .all(|(pattern, segment)| matches_segment(pattern.as_bytes(), segment.as_bytes()))
+}
+
+fn matches_segment(pattern: &[u8], segment: &[u8]) -> bool {
+    let (mut p, mut s) = (0, 0);
+    // position of the last `*` and of the first character it has not taken yet
+    let mut star: Option<(usize, usize)> = None;
+    while s < segment.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, s));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == segment[s] {
+            p += 1;
+            s += 1;
+        } else if let Some((star_p, star_s)) = star {
+            p = star_p + 1;
+            s = star_s + 1;
+            star = Some((star_p, s));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
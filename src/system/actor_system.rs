@@ -1,15 +1,78 @@
-use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_address::{ActorAddress, ResolveError};
 use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_config::ActorDefaults;
+use crate::actor::actor_metrics::ActorStats;
+use crate::actor::debug_snapshot::SnapshotError;
+use crate::system::actor_info::{ActorInfo, ActorStatus};
+use crate::system::actor_selection::{self, ActorSelection};
+use crate::actor::protocol::{ActorProtocol, ProtocolError};
+use crate::actor::relocation::MoveError;
 use crate::config::pool_config::ThreadPoolConfig;
 use crate::config::tyractorsaur_config::{TyractorsaurConfig, DEFAULT_POOL};
+#[cfg(feature = "replay")]
+use crate::actor::actor_state::ActorState;
+#[cfg(feature = "replay")]
+use crate::system::execution_recorder::{parse_log, ExecutionRecorder, LogEntry, ReplayReport, ReplayStatus};
+#[cfg(feature = "testing")]
+use crate::harness::interaction_log::InteractionLog;
+use crate::message::headers::{self, HeaderKey, HeaderMapping, HeaderRegistry, Headers, Propagate};
+use crate::message::provenance::{AuditRegistry, Audited};
+use crate::message::message_size::{MessageSizes, SizeUnchecked};
 use crate::message::serialized_message::SerializedMessage;
+#[cfg(feature = "async")]
+use crate::system::await_shutdown::AwaitShutdown;
+#[cfg(feature = "metrics")]
+use crate::system::prometheus::MetricsSnapshot;
+#[cfg(feature = "dyn-spawn")]
+use crate::system::dyn_spawn::{DynActorFactory, DynActorRef, DynFactoryRegistry, DynSpawnConfig, SpawnDynError};
+use crate::system::actor_group::ActorGroupBuilder;
+use crate::routers::prelude::WorkerPoolBuilder;
+use crate::system::bulk_spawn::{BulkSpawnConfig, BulkSpawnReport, SpawnSpec};
+use crate::system::cycle_detection::{CycleDetector, CycleExempt};
+use crate::system::message_budget::MessageBudget;
+use crate::system::dead_letters::{DeadLetter, DeadLetterReason};
+use crate::system::drop_reporter::{DropReporter, DropStats, LogDropReporter};
+use crate::system::event_bus::EventBus;
+use crate::system::instrumentation::InstrumentationHook;
+use crate::system::integrity::{IntegrityCounts, IntegrityReport};
+use crate::system::memory_pressure::{MemoryMonitor, MemorySample, MemoryStatus};
+use crate::system::metrics_history::PoolMetricsHistory;
+use crate::system::name_guard::{self, NameGuard, TyractorsaurError};
+use crate::system::shutdown_hooks::ShutdownHookError;
+use crate::system::pool_stats::{PoolError, PoolLoad, PoolRef, PoolStats};
+use crate::system::peers::{PeerEndpoint, Peers};
+use crate::system::panic_handler::{self, PanicHandler, PanicInfoContext};
+use crate::system::slow_handler::{self, SlowHandler, SlowHandlerHook};
+use crate::system::remote_transport::RemoteTransport;
+use crate::system::worker_stats::PoolWorkerStats;
+#[cfg(all(feature = "signals", unix))]
+use crate::system::signals::{SignalConfig, SignalError, SignalGuard, SignalShutdownReport};
 use crate::system::system_state::SystemState;
 use crate::system::thread_pool_manager::ThreadPoolManager;
 use crate::system::wakeup_manager::WakeupManager;
-use std::sync::Arc;
-use std::thread::sleep;
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use crate::actor::actor::Actor;
+use crate::actor::actor_factory::TryActorFactory;
+use crate::actor::handler::Handler;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handoff;
+use crate::actor::panic_report::{DebugCapture, PayloadCaptureRegistry};
+use crate::actor::send_batch::{self, SendBatch, SendBatchReport};
+use crate::actor::slo::{SloEvent, SloEventBus};
+use crate::actor::state_capture::{self, CaptureDiff, StateCapture};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use crate::message::actor_message::ActorMessage;
+use crate::actor::handoff::{HandoffError, HandoffMode, HandoffReport};
+use crate::actor::transaction::Transaction;
+use crate::system::channel_ingestion::{IngestConfig, IngestHandle, IngestionReactor};
+use crate::system::pipe::{Pipe, PipeHandle};
+use crate::actor::control_flow::ControlFlowMessage;
+use crate::system::channel_sink::{SinkActor, SinkReceiver};
+use crate::system::entity_region::EntityRegion;
+use crate::actor::replacement::{ReplaceActorMessage, ReplaceError};
+use crate::actor::context::ActorContext;
+use std::panic::UnwindSafe;
 
 /// Manages thread pools and actors
 #[derive(Clone)]
@@ -18,7 +81,34 @@ pub struct ActorSystem {
     thread_pool_manager: ThreadPoolManager,
     wakeup_manager: WakeupManager,
     name: String,
+    incarnation: String,
     config: Arc<TyractorsaurConfig>,
+    header_registry: HeaderRegistry,
+    payload_capture: PayloadCaptureRegistry,
+    audit: AuditRegistry,
+    idle_sleep_after: Duration,
+    slo_events: SloEventBus,
+    ingestion_reactor: IngestionReactor,
+    cycle_detector: Option<Arc<CycleDetector>>,
+    /// only set if `general.message_budget` is configured
+    message_budget: Option<Arc<MessageBudget>>,
+    message_sizes: Arc<MessageSizes>,
+    /// receiving side of this system for its peers, see [register_peer](#method.register_peer)
+    endpoint: Arc<PeerEndpoint>,
+    /// shared by all clones, see [register_peer](#method.register_peer)
+    peers: Peers,
+    /// shared by all clones, see [set_remote_transport](#method.set_remote_transport)
+    remote_transport: Arc<RwLock<Option<Arc<dyn RemoteTransport>>>>,
+    /// shared by all clones, see [set_panic_handler](#method.set_panic_handler)
+    panic_handler: Arc<RwLock<PanicHandler>>,
+    /// shared by all clones, see [set_slow_handler_hook](#method.set_slow_handler_hook)
+    slow_handler_hook: Arc<RwLock<Option<SlowHandlerHook>>>,
+    #[cfg(feature = "dyn-spawn")]
+    dyn_factories: DynFactoryRegistry,
+    #[cfg(feature = "replay")]
+    recorder: Option<ExecutionRecorder>,
+    #[cfg(feature = "testing")]
+    interactions: Option<Arc<InteractionLog>>,
 }
 
 impl ActorSystem {
@@ -34,37 +124,213 @@ impl ActorSystem {
     /// let actor_config = TyractorsaurConfig::new().unwrap();
     /// let actor_system = ActorSystem::new(actor_config);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `general.unique_name_guard` is set and the name is in use, see [try_new](#method.try_new)
     pub fn new(config: TyractorsaurConfig) -> Self {
+        Self::try_new(config).expect("invalid config or unable to acquire the system name")
+    }
+
+    /// Same as [new](#method.new), fails if `general.unique_name_guard` is set and another live system already holds the name
+    ///
+    /// Also fails if the config does not pass [TyractorsaurConfig.check](../prelude/struct.TyractorsaurConfig.html#method.check)
+    ///
+    /// The name is released as soon as the system is stopped, lock files left behind by crashed processes are reclaimed
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("tyractorsaur-guard-{}", std::process::id()));
+    /// // left behind by a process that crashed
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// std::fs::write(dir.join("billing.lock"), "4294967295").unwrap();
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("billing");
+    /// config.general.unique_name_guard = Some(GuardConfig::host(&dir));
+    /// let first = ActorSystem::try_new(config.clone()).unwrap();
+    ///
+    /// let expected = TyractorsaurError::SystemNameInUse {
+    ///     name: String::from("billing"),
+    ///     holder_pid: Some(std::process::id()),
+    /// };
+    /// assert_eq!(expected, ActorSystem::try_new(config.clone()).err().unwrap());
+    ///
+    /// first.stop(Duration::from_secs(1));
+    /// first.await_shutdown();
+    /// let second = ActorSystem::try_new(config).unwrap();
+    /// second.stop(Duration::from_secs(1));
+    /// second.await_shutdown();
+    ///
+    /// // every name has a lock file of its own, even if its characters are not allowed in a file name
+    /// let systems: Vec<ActorSystem> = ["jobs/eu", "jobs_eu", "Jobs_EU"]
+    ///     .iter()
+    ///     .map(|name| {
+    ///         let mut config = TyractorsaurConfig::new().unwrap();
+    ///         config.general.name = String::from(*name);
+    ///         config.general.unique_name_guard = Some(GuardConfig::host(&dir));
+    ///         ActorSystem::try_new(config).unwrap()
+    ///     })
+    ///     .collect();
+    /// for system in systems {
+    ///     system.stop(Duration::from_secs(1));
+    ///     system.await_shutdown();
+    /// }
+    /// std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn try_new(config: TyractorsaurConfig) -> Result<Self, TyractorsaurError> {
+        config.check().map_err(TyractorsaurError::InvalidConfig)?;
+        let name_guard = match &config.general.unique_name_guard {
+            Some(guard_config) => Some(NameGuard::acquire(&config.general.name, guard_config)?),
+            None => None,
+        };
+        let system = Self::create(config, true);
+        if let Some(name_guard) = name_guard {
+            system.state.set_name_guard(name_guard);
+        }
+        Ok(system)
+    }
+
+    /// Creates a new ActorSystem that does not execute any Actors by itself
+    ///
+    /// Actors are only executed through [ActorSystem.replay_execution](#method.replay_execution)
+    #[cfg(feature = "replay")]
+    pub fn new_for_replay(config: TyractorsaurConfig) -> Self {
+        Self::create(config, false)
+    }
+
+    /// Creates a new ActorSystem that does not execute any Actors by itself, used by [MailboxHarness](../testing/struct.MailboxHarness.html)
+    ///
+    /// Idle Actors go to sleep after `idle_sleep_after` instead of 5 seconds
+    #[cfg(feature = "testing")]
+    pub(crate) fn new_for_testing(config: TyractorsaurConfig, idle_sleep_after: Duration) -> Self {
+        let mut system = Self::create(config, false);
+        system.idle_sleep_after = idle_sleep_after;
+        system
+    }
+
+    /// Same as [new](#method.new), but idle Actors go to sleep after `idle_sleep_after` instead of 5 seconds, used by [WakeupStress](../testing/struct.WakeupStress.html)
+    #[cfg(feature = "testing")]
+    pub(crate) fn new_with_idle_sleep_after(config: TyractorsaurConfig, idle_sleep_after: Duration) -> Self {
+        let mut system = Self::create(config, true);
+        system.idle_sleep_after = idle_sleep_after;
+        system
+    }
+
+    /// Same as [new_for_testing](#method.new_for_testing), every Actor records its interactions into `interactions`, used by [TestActorSystem](../testing/struct.TestActorSystem.html)
+    #[cfg(feature = "testing")]
+    pub(crate) fn new_for_interaction_testing(config: TyractorsaurConfig, interactions: Arc<InteractionLog>) -> Self {
+        let mut system = Self::new_for_testing(config, Duration::from_secs(0));
+        system.interactions = Some(interactions);
+        system
+    }
+
+    fn create(config: TyractorsaurConfig, start_execution: bool) -> Self {
         let thread_pool_config = config.thread_pool.clone();
+        panic_handler::install_hook();
 
         let state = SystemState::new();
-        let thread_pool_manager = ThreadPoolManager::new();
+        state.get_dead_letters().set_logging(config.general.log_dead_letters);
+        if config.general.log_drops_every > 0 {
+            let reporter = LogDropReporter::new(config.general.log_drops_every);
+            state.get_dead_letters().get_drops().set_reporter(Arc::new(reporter));
+        }
+        let thread_pool_manager = ThreadPoolManager::new(&config.general.name, config.general.metrics_history, config.general.worker_stats_interval);
         let wakeup_manager = WakeupManager::new();
 
         for (key, value) in thread_pool_config.config.iter() {
             thread_pool_manager.add_pool_with_config(key, value.clone());
         }
 
-        let s = state.clone();
-        let t = thread_pool_manager.clone();
-        let w = wakeup_manager.clone();
-        std::thread::spawn(move || t.manage(s, w));
-        let s = state.clone();
-        let t = thread_pool_manager.clone();
-        let w = wakeup_manager.clone();
-        std::thread::spawn(move || w.manage(s, t));
+        if start_execution {
+            let s = state.clone();
+            let t = thread_pool_manager.clone();
+            let w = wakeup_manager.clone();
+            let history_config = config.general.metrics_history;
+            let integrity_sweep_interval = config.general.integrity_sweep_interval;
+            let memory_monitor = config.general.memory_pressure.map(|memory_pressure| {
+                MemoryMonitor::new(
+                    memory_pressure,
+                    state.get_memory_pressure().clone(),
+                    state.get_event_bus().clone(),
+                )
+            });
+            let wakeups = std::thread::spawn(move || w.manage(s, t, history_config, memory_monitor, integrity_sweep_interval));
+            let s = state.clone();
+            let t = thread_pool_manager.clone();
+            let w = wakeup_manager.clone();
+            // joins the wakeup manager together with the workers, the stop joins this thread before the system counts as stopped
+            state.add_thread(std::thread::spawn(move || t.manage(s, w, vec![wakeups])));
+        } else {
+            thread_pool_manager.set_workers_joined();
+        }
+
+        #[cfg(feature = "replay")]
+        let recorder = match &config.general.record_execution {
+            Some(path) if start_execution => {
+                Some(ExecutionRecorder::new(path).expect("unable to create execution log"))
+            }
+            _ => None,
+        };
+
+        let cycle_detector = config
+            .general
+            .cycle_detection
+            .map(|cycle_detection| Arc::new(CycleDetector::new(cycle_detection, state.get_event_bus().clone())));
+        let message_budget = config.general.message_budget.map(|budget| Arc::new(MessageBudget::new(budget)));
+        let message_sizes = Arc::new(MessageSizes::new(
+            config.general.max_message_bytes,
+            config.general.warn_message_bytes,
+            state.get_event_bus().clone(),
+        ));
+        let incarnation = name_guard::new_incarnation();
+        let endpoint = Arc::new(PeerEndpoint::new(
+            config.general.name.clone(),
+            local_remote(&incarnation),
+            state.clone(),
+            message_sizes.clone(),
+        ));
 
         ActorSystem {
             state,
             thread_pool_manager,
             wakeup_manager,
             name: config.general.name.clone(),
+            incarnation,
             config: Arc::new(config.clone()),
+            header_registry: HeaderRegistry::new(),
+            payload_capture: PayloadCaptureRegistry::new(),
+            audit: AuditRegistry::new(),
+            idle_sleep_after: Duration::from_secs(5),
+            slo_events: SloEventBus::new(),
+            ingestion_reactor: IngestionReactor::new(),
+            cycle_detector,
+            message_budget,
+            message_sizes,
+            endpoint,
+            peers: Peers::default(),
+            remote_transport: Arc::new(RwLock::new(None)),
+            panic_handler: Arc::new(RwLock::new(Arc::new(panic_handler::log_panic))),
+            slow_handler_hook: Arc::new(RwLock::new(None)),
+            #[cfg(feature = "dyn-spawn")]
+            dyn_factories: DynFactoryRegistry::default(),
+            #[cfg(feature = "replay")]
+            recorder,
+            #[cfg(feature = "testing")]
+            interactions: None,
         }
     }
 
     /// Adds a new named pool using the [default pool configuration](https://github.com/sers-dev/tyractorsaur/blob/master/src/config/default.toml)
     ///
+    /// Returns a [PoolRef](../prelude/struct.PoolRef.html) for [ActorBuilder.set_pool](../prelude/struct.ActorBuilder.html#method.set_pool), a pool that is already running is kept as it is
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -74,9 +340,10 @@ impl ActorSystem {
     ///
     /// let actor_config = TyractorsaurConfig::new().unwrap();
     /// let actor_system = ActorSystem::new(actor_config);
-    /// actor_system.add_pool("test");
+    /// let pool = actor_system.add_pool("test");
+    /// assert_eq!("test", pool.name());
     /// ```
-    pub fn add_pool(&self, name: &str) {
+    pub fn add_pool(&self, name: &str) -> PoolRef {
         let default_config = self.config.thread_pool.config.get(DEFAULT_POOL).unwrap();
         let config = self
             .config
@@ -84,7 +351,33 @@ impl ActorSystem {
             .config
             .get(name)
             .unwrap_or(default_config);
-        self.add_pool_with_config(name, config.clone());
+        self.add_pool_with_config(name, config.clone())
+    }
+
+    /// Handle to a pool of the system, `None` if the pool has neither been added nor configured in `thread_pool.config`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.thread_pool.config.insert(String::from("io"), ThreadPoolConfig::new(0, 2, 2, 1.0));
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.add_pool("cpu");
+    /// assert!(actor_system.get_pool("default").is_some());
+    /// assert!(actor_system.get_pool("io").is_some());
+    /// assert!(actor_system.get_pool("cpu").is_some());
+    /// assert!(actor_system.get_pool("defualt").is_none());
+    /// ```
+    pub fn get_pool(&self, name: &str) -> Option<PoolRef> {
+        if self.thread_pool_manager.has_pool(name) {
+            Some(PoolRef::new(name))
+        } else {
+            None
+        }
     }
 
     /// Adds a new named pool with custom [pool configuration](../prelude/struct.ThreadPoolConfig.html)
@@ -101,16 +394,37 @@ impl ActorSystem {
     /// let pool_config = ThreadPoolConfig::new(0, 2, 4, 1.0);
     /// actor_system.add_pool_with_config("test", pool_config);
     /// ```
-    pub fn add_pool_with_config(&self, name: &str, thread_pool_config: ThreadPoolConfig) {
+    pub fn add_pool_with_config(&self, name: &str, thread_pool_config: ThreadPoolConfig) -> PoolRef {
         self.thread_pool_manager
             .add_pool_with_config(name, thread_pool_config);
+        PoolRef::new(name)
     }
 
-    /// Sends a [SerializedMessage](../prelude/struct.SerializedMessage.html) to an Actor by Address
+    /// Replaces the [ActorDefaults](../prelude/struct.ActorDefaults.html) of a running pool
+    ///
+    /// Only Actors spawned afterwards use the new defaults, already running Actors keep their config
     ///
-    /// # Important Note
+    /// Returns `false` if the pool does not exist
+    pub fn set_pool_actor_defaults(&self, name: &str, actor_defaults: Option<ActorDefaults>) -> bool {
+        self.thread_pool_manager.set_actor_defaults(name, actor_defaults)
+    }
+
+    /// Defaults of a running pool, or of a configured pool that has not been started yet
+    pub(crate) fn get_pool_actor_defaults(&self, name: &str) -> Option<ActorDefaults> {
+        match self.thread_pool_manager.get_actor_defaults(name) {
+            Some(actor_defaults) => actor_defaults,
+            None => self
+                .config
+                .thread_pool
+                .config
+                .get(name)
+                .and_then(|pool| pool.get_actor_defaults()),
+        }
+    }
+
+    /// Sends a [SerializedMessage](../prelude/struct.SerializedMessage.html) to an Actor by Address
     ///
-    /// This function will call the [ActorTrait.handle_serialized_message](../prelude/trait.Actor.html#method.handle_serialized_message) on the same thread that this function is called
+    /// Messages without type tag are queued for [Actor.handle_serialized_message_mut](../prelude/trait.Actor.html#method.handle_serialized_message_mut), messages that can not be queued are dead letters
     ///
     /// # Examples
     ///
@@ -143,132 +457,3984 @@ impl ActorSystem {
     /// actor_system.send_to_address(address, SerializedMessage::new(Vec::new()));
     /// ```
     pub fn send_to_address(&self, address: &ActorAddress, msg: SerializedMessage) {
-        self.state.send_to_address(address, msg);
+        let _ = self.try_send_to_address(address, msg);
     }
 
-    /// Returns a Builder to configure and spawn an actor in the system
+    /// Same as [send_to_address](#method.send_to_address), but fails if no Actor is running at the address or the Actor does not accept the version of the message
+    ///
+    /// Messages whose [payload_size](../prelude/struct.SerializedMessage.html#method.payload_size) exceeds `general.max_message_bytes` fail with [ProtocolError::PayloadTooLarge](../prelude/enum.ProtocolError.html#variant.PayloadTooLarge) before they are deserialized.
+    /// The error is meant to be returned to the peer, which can check its sends beforehand through [RemoteProtocols.check_message](../prelude/struct.RemoteProtocols.html#method.check_message)
+    ///
+    /// Messages to an address of a peer, see [register_peer](#method.register_peer), are delivered to the peer directly and fail as they would within the peer.
+    /// Messages to an address of any other system are handed to the [RemoteTransport](../prelude/trait.RemoteTransport.html) and always succeed, errors of the receiving system are up to the transport.
+    /// Without a transport they fail with [ProtocolError::ForeignSystem](../prelude/enum.ProtocolError.html#variant.ForeignSystem) and are reported as dead letter
+    ///
+    /// See [ActorBuilder.protocol_version](../prelude/struct.ActorBuilder.html#method.protocol_version)
+    pub fn try_send_to_address(&self, address: &ActorAddress, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        if self.endpoint.owns(address) {
+            return self.endpoint.deliver(address, msg);
+        }
+        if let Some(peer) = self.peers.get(address) {
+            return peer.deliver(address, msg);
+        }
+        if address.remote != self.get_local_remote() {
+            let transport = self.remote_transport.read().unwrap().clone();
+            if let Some(transport) = transport {
+                transport.send(address, msg);
+                return Ok(());
+            }
+        }
+        self.state.get_dead_letters().report_with_reason(
+            address,
+            std::any::type_name::<SerializedMessage>(),
+            DeadLetterReason::ForeignSystem,
+        );
+        Err(ProtocolError::ForeignSystem)
+    }
+
+    /// Hands a message that arrived through a [RemoteTransport](../prelude/trait.RemoteTransport.html) to the Actor at `target`
+    ///
+    /// Fails like [try_send_to_address](#method.try_send_to_address) does for a local Actor, messages are never passed on to the transport again
+    pub fn receive_remote(&self, msg: SerializedMessage, target: ActorAddress) -> Result<(), ProtocolError> {
+        self.endpoint.deliver(&target, msg)
+    }
+
+    /// Sends [SerializedMessages](../prelude/struct.SerializedMessage.html) to addresses of other systems through `transport`, replacing the previous transport
+    ///
+    /// An address belongs to another system if its `remote` differs from the one of the Actors of this system, which includes other systems of the same process
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```rust
-    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, Actor, ActorFactory, ActorContext, SerializedMessage};
+    /// use tyractorsaur::prelude::*;
+    /// use std::collections::HashMap;
+    /// use std::sync::{Arc, Mutex, RwLock};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
     ///
-    /// struct TestActor {}
+    /// // hands messages directly to the system that owns the address, a real transport would serialize them over the network
+    /// #[derive(Default)]
+    /// struct Loopback {
+    ///     systems: RwLock<HashMap<String, ActorSystem>>,
+    /// }
+    /// impl Loopback {
+    ///     fn connect(self: &Arc<Self>, system: &ActorSystem, remote: String) {
+    ///         self.systems.write().unwrap().insert(remote, system.clone());
+    ///         system.set_remote_transport(self.clone());
+    ///     }
+    /// }
+    /// impl RemoteTransport for Loopback {
+    ///     fn send(&self, address: &ActorAddress, msg: SerializedMessage) {
+    ///         if let Some(system) = self.systems.read().unwrap().get(&address.remote) {
+    ///             let _ = system.receive_remote(msg, address.clone());
+    ///         }
+    ///     }
+    /// }
     ///
-    /// impl Actor for TestActor {
+    /// struct Inbox {
+    ///     received: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// }
+    /// impl Actor for Inbox {
     ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
-    ///         assert_eq!(0, msg.content.len());
+    ///         self.received.lock().unwrap().push(msg.content);
+    ///     }
+    /// }
+    /// struct InboxFactory {
+    ///     received: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// }
+    /// impl ActorFactory<Inbox> for InboxFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Inbox>) -> Inbox {
+    ///         Inbox { received: self.received.clone() }
     ///     }
     /// }
     ///
-    /// struct TestFactory {}
+    /// let first = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let second = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let first_received = Arc::new(Mutex::new(Vec::new()));
+    /// let second_received = Arc::new(Mutex::new(Vec::new()));
+    /// let first_inbox = first.builder().spawn("inbox", InboxFactory { received: first_received.clone() }).unwrap();
+    /// let second_inbox = second.builder().spawn("inbox", InboxFactory { received: second_received.clone() }).unwrap();
     ///
-    /// impl ActorFactory<TestActor> for TestFactory {
-    ///     fn new_actor(&self, _context: ActorContext<TestActor>) -> TestActor {
-    ///         TestActor {}
+    /// // without a transport the address of the other system is unknown
+    /// let unreachable = first.try_send_to_address(second_inbox.get_address(), SerializedMessage::new(vec![0]));
+    /// assert_eq!(Err(ProtocolError::ForeignSystem), unreachable);
+    ///
+    /// let loopback = Arc::new(Loopback::default());
+    /// loopback.connect(&first, first_inbox.get_address().remote.clone());
+    /// loopback.connect(&second, second_inbox.get_address().remote.clone());
+    /// first.send_to_address(second_inbox.get_address(), SerializedMessage::new(vec![1]));
+    /// second.send_to_address(first_inbox.get_address(), SerializedMessage::new(vec![2]));
+    /// // local addresses never reach the transport
+    /// first.send_to_address(first_inbox.get_address(), SerializedMessage::new(vec![3]));
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// assert_eq!(vec![vec![2], vec![3]], *first_received.lock().unwrap());
+    /// assert_eq!(vec![vec![1]], *second_received.lock().unwrap());
+    /// first.stop(Duration::from_secs(1));
+    /// second.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_remote_transport(&self, transport: Arc<dyn RemoteTransport>) {
+        *self.remote_transport.write().unwrap() = Some(transport);
+    }
+
+    /// Lets [send_to_address](#method.send_to_address) of this system deliver to the Actors of `peer` directly, without a [RemoteTransport](../prelude/trait.RemoteTransport.html)
+    ///
+    /// Only this system sends to the peer, the peer has to register this system to send back.
+    /// The peer is held weakly and no longer reached once it stopped, it does not keep the other system alive
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Inbox {
+    ///     received: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// }
+    /// impl Actor for Inbox {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         self.received.lock().unwrap().push(msg.content);
+    ///     }
+    /// }
+    /// struct InboxFactory {
+    ///     received: Arc<Mutex<Vec<Vec<u8>>>>,
+    /// }
+    /// impl ActorFactory<Inbox> for InboxFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Inbox>) -> Inbox {
+    ///         Inbox { received: self.received.clone() }
     ///     }
     /// }
     ///
+    /// let tenant_a = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let tenant_b = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let received = Arc::new(Mutex::new(Vec::new()));
+    /// let inbox = tenant_b.builder().spawn("inbox", InboxFactory { received: received.clone() }).unwrap();
     ///
-    /// let actor_config = TyractorsaurConfig::new().unwrap();
-    /// let actor_system = ActorSystem::new(actor_config);
-    /// let builder = actor_system.builder();
-    /// builder.spawn("test", TestFactory{}).unwrap();
+    /// // the address belongs to another system, even though both systems have the same name
+    /// let rejected = tenant_a.try_send_to_address(inbox.get_address(), SerializedMessage::new(vec![1]));
+    /// assert_eq!(Err(ProtocolError::ForeignSystem), rejected);
+    /// assert_eq!(1, tenant_a.dead_letter_count());
+    /// assert!(received.lock().unwrap().is_empty());
+    ///
+    /// tenant_a.register_peer(&tenant_b);
+    /// tenant_a.try_send_to_address(inbox.get_address(), SerializedMessage::new(vec![2])).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(vec![vec![2]], *received.lock().unwrap());
+    /// // registering is one-directional
+    /// let tenant_a_inbox = tenant_a.builder().spawn("inbox", InboxFactory { received: received.clone() }).unwrap();
+    /// assert!(tenant_b.try_send_to_address(tenant_a_inbox.get_address(), SerializedMessage::new(vec![3])).is_err());
+    ///
+    /// // a stopped peer is no longer reached
+    /// tenant_b.stop(Duration::from_secs(1));
+    /// tenant_b.await_shutdown();
+    /// let rejected = tenant_a.try_send_to_address(inbox.get_address(), SerializedMessage::new(vec![4]));
+    /// assert_eq!(Err(ProtocolError::ForeignSystem), rejected);
+    /// assert_eq!(2, tenant_a.dead_letter_count());
+    /// tenant_a.stop(Duration::from_secs(1));
     /// ```
-    pub fn builder<A>(&self) -> ActorBuilder<A>
+    pub fn register_peer(&self, peer: &ActorSystem) {
+        self.peers.register(&peer.endpoint);
+    }
+
+    /// Replaces the handler of panics within Actors, which defaults to logging a single line per panic
+    ///
+    /// Called on the thread of the Actor before its [RestartPolicy](../prelude/enum.RestartPolicy.html) is applied, a panicking handler is ignored.
+    /// Panics within Actors are no longer printed by the panic hook of the process, all other panics still are
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Charge { cents: i64 }
+    /// impl ActorMessage for Charge {}
+    ///
+    /// struct Billing {}
+    /// impl Actor for Billing {}
+    /// impl Handler<Charge> for Billing {
+    ///     fn handle(&mut self, msg: Charge, _context: &ActorContext<Self>) {
+    ///         assert!(msg.cents >= 0, "negative charge");
+    ///     }
+    /// }
+    /// struct BillingFactory {}
+    /// impl ActorFactory<Billing> for BillingFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Billing>) -> Billing {
+    ///         Billing {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let payments = actor_system.add_pool("payments");
+    /// let panics = Arc::new(Mutex::new(Vec::new()));
+    /// let recorded = panics.clone();
+    /// actor_system.set_panic_handler(move |info: PanicInfoContext| {
+    ///     recorded.lock().unwrap().push(info.clone());
+    ///     // does not take down the thread of the Actor
+    ///     panic!("broken handler");
+    /// });
+    /// let billing = actor_system
+    ///     .builder()
+    ///     .set_pool(payments)
+    ///     .set_restart_policy(RestartPolicy::Always)
+    ///     .spawn("billing", BillingFactory {})
+    ///     .unwrap();
+    /// billing.send(Charge { cents: -1 }).unwrap();
+    /// billing.send(Charge { cents: -2 }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// let panics = panics.lock().unwrap();
+    /// assert_eq!(2, panics.len());
+    /// assert_eq!(billing.get_address(), &panics[0].address);
+    /// assert_eq!("payments", panics[0].pool_name);
+    /// assert!(panics[0].message_type.ends_with("Charge"));
+    /// assert_eq!(Some("negative charge"), panics[0].payload.as_deref());
+    /// assert!(panics[0].location.as_ref().unwrap().contains(".rs:"));
+    /// assert_eq!((0, 1), (panics[0].restarts, panics[1].restarts));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_panic_handler<F>(&self, handler: F)
     where
-        A: Actor
+        F: Fn(PanicInfoContext) + Send + Sync + 'static,
     {
-        ActorBuilder::new(self.clone(), self.state.clone(), self.wakeup_manager.clone())
+        *self.panic_handler.write().unwrap() = Arc::new(handler);
     }
 
-    /// Sends a SystemStopMessage to all running Actors, and wakes them up if necessary.
-    /// Users can implement their own clean system stop behavior, by implementing [Actor.on_system_stop](../prelude/trait.Actor.html#method.on_system_stop) and [Actor.on_actor_stop](../prelude/trait.Actor.html#method.on_actor_stop)
+    /// Hands a panic within an Actor to the handler of [set_panic_handler](#method.set_panic_handler)
+    pub(crate) fn handle_panic(&self, info: PanicInfoContext) {
+        let handler = self.panic_handler.read().unwrap().clone();
+        panic_handler::notify(&handler, info);
+    }
+
+    /// Executes `hook` for every handler invocation that exceeded the [slow handler threshold](../prelude/struct.ActorBuilder.html#method.set_slow_handler_threshold) of its Actor
     ///
-    /// System will stop after all actors have been stopped or after `graceful_termination_timeout`
+    /// The hook runs on the pool thread of the slow Actor right after the handler returned, so it should not block.
+    /// Slow invocations are counted in [ActorStats](../prelude/struct.ActorStats.html) regardless of whether a hook is set
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```rust
-    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
     /// use std::time::Duration;
     ///
-    /// let actor_config = TyractorsaurConfig::new().unwrap();
-    /// let actor_system = ActorSystem::new(actor_config);
+    /// struct Work { millis: u64 }
+    /// impl ActorMessage for Work {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Work> for Worker {
+    ///     fn handle(&mut self, msg: Work, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(msg.millis));
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let slow_handlers = Arc::new(Mutex::new(Vec::new()));
+    /// let collected = slow_handlers.clone();
+    /// actor_system.set_slow_handler_hook(move |slow_handler: SlowHandler| collected.lock().unwrap().push(slow_handler));
+    ///
+    /// let watched = actor_system
+    ///     .builder()
+    ///     .set_slow_handler_threshold(Duration::from_millis(50))
+    ///     .spawn("watched", WorkerFactory {})
+    ///     .unwrap();
+    /// let unwatched = actor_system.builder().spawn("unwatched", WorkerFactory {}).unwrap();
+    /// for millis in [1, 120, 1, 100] {
+    ///     watched.send(Work { millis }).unwrap();
+    ///     unwatched.send(Work { millis: millis / 2 }).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(500));
+    ///
+    /// let slow_handlers = slow_handlers.lock().unwrap().clone();
+    /// assert_eq!(2, slow_handlers.len());
+    /// for (slow_handler, millis) in slow_handlers.iter().zip([120, 100]) {
+    ///     assert_eq!(watched.get_address(), &slow_handler.address);
+    ///     assert!(slow_handler.message_type.ends_with("Work"));
+    ///     assert!(slow_handler.duration >= Duration::from_millis(millis));
+    ///     assert!(slow_handler.duration < Duration::from_millis(millis + 200));
+    ///     assert_eq!(Duration::from_millis(50), slow_handler.threshold);
+    /// }
+    ///
+    /// // rank the Actors by the time they kept a pool thread busy
+    /// let mut stats = actor_system.all_stats();
+    /// stats.sort_by(|(_, a), (_, b)| b.busy_time.cmp(&a.busy_time));
+    /// assert_eq!(watched.get_address(), &stats[0].0);
+    /// assert_eq!(2, stats[0].1.slow_handlers);
+    /// assert!(stats[0].1.busy_time >= Duration::from_millis(222));
+    /// assert_eq!(0, stats[1].1.slow_handlers);
     /// actor_system.stop(Duration::from_secs(1));
     /// ```
-    pub fn stop(&self, graceful_termination_timeout: Duration) {
-        self.state.stop(graceful_termination_timeout);
+    pub fn set_slow_handler_hook<F>(&self, hook: F)
+    where
+        F: Fn(SlowHandler) + Send + Sync + 'static,
+    {
+        *self.slow_handler_hook.write().unwrap() = Some(Arc::new(hook));
     }
 
-    /// Waits for the system to stop
+    /// Hands a slow handler invocation to the hook of [set_slow_handler_hook](#method.set_slow_handler_hook)
+    pub(crate) fn handle_slow_handler(&self, slow_handler: SlowHandler) {
+        let hook = self.slow_handler_hook.read().unwrap().clone();
+        if let Some(hook) = hook {
+            slow_handler::notify(&hook, slow_handler);
+        }
+    }
+
+    /// Registers a hook that observes every message sent to and handled by the Actors of the system, replaces a previously registered hook
     ///
-    /// # Returns
+    /// Without a hook, sends and handlers do not capture any timestamps for it.
+    /// With the `tracing` feature, [TracingInstrumentation](../prelude/struct.TracingInstrumentation.html) emits a `tracing` event per callback
     ///
-    /// `0 as i32` if cleanly stopped by removing all actors from system
+    /// # Examples
     ///
-    /// `1 as i32` if force stopped after stop timeout
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Ping> for Worker {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// impl Handler<Crash> for Worker {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         panic!("crash");
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// #[derive(Default)]
+    /// struct CountingHook {
+    ///     events: Mutex<Vec<String>>,
+    ///     latencies: Mutex<Vec<Option<Duration>>>,
+    /// }
+    /// impl CountingHook {
+    ///     fn record(&self, event: &str, address: &ActorAddress, message_type: &str) {
+    ///         let message_type = message_type.rsplit("::").next().unwrap();
+    ///         self.events.lock().unwrap().push(format!("{} {} {}", event, address.actor, message_type));
+    ///     }
+    /// }
+    /// impl InstrumentationHook for CountingHook {
+    ///     fn on_send(&self, address: &ActorAddress, message_type: &'static str) {
+    ///         self.record("send", address, message_type);
+    ///     }
+    ///     fn on_handle_start(&self, address: &ActorAddress, message_type: &'static str, queue_latency: Option<Duration>) {
+    ///         self.latencies.lock().unwrap().push(queue_latency);
+    ///         self.record("start", address, message_type);
+    ///     }
+    ///     fn on_handle_end(&self, address: &ActorAddress, message_type: &'static str, _duration: Duration) {
+    ///         self.record("end", address, message_type);
+    ///     }
+    ///     fn on_panic(&self, address: &ActorAddress, message_type: &'static str) {
+    ///         self.record("panic", address, message_type);
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.set_panic_handler(|_info: PanicInfoContext| {});
+    /// let worker = actor_system.builder().spawn("worker", WorkerFactory {}).unwrap();
+    /// // not observed, there is no hook yet
+    /// worker.send(Ping {}).unwrap();
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// let hook = Arc::new(CountingHook::default());
+    /// actor_system.set_instrumentation(hook.clone());
+    /// worker.send(Ping {}).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// worker.send(Crash {}).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// worker.stop();
+    /// assert!(worker.await_stop(Duration::from_secs(1)));
+    ///
+    /// let expected = vec![
+    ///     "send worker Ping",
+    ///     "start worker Ping",
+    ///     "end worker Ping",
+    ///     "send worker Crash",
+    ///     "start worker Crash",
+    ///     "panic worker Crash",
+    ///     "send worker ActorStopMessage",
+    ///     "start worker ActorStopMessage",
+    ///     "end worker ActorStopMessage",
+    /// ];
+    /// assert_eq!(expected, *hook.events.lock().unwrap());
+    /// assert!(hook.latencies.lock().unwrap().iter().all(|latency| latency.is_some()));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_instrumentation(&self, hook: Arc<dyn InstrumentationHook>) {
+        self.state.get_instrumentation().set(hook);
+    }
+
+    /// Looks up a running Actor by the pool and name it has been spawned with
+    ///
+    /// Returns `None` if there is no such Actor, if it is of another type than `A` or if it is already stopping
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```rust
-    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, Actor, ActorFactory, ActorContext};
+    /// use std::thread::sleep;
     /// use std::time::Duration;
-    /// use std::process::exit;
+    ///
+    /// struct Counter {}
+    /// impl Actor for Counter {}
+    ///
+    /// struct Logger {}
+    /// impl Actor for Logger {}
+    ///
+    /// struct CounterFactory {}
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter {}
+    ///     }
+    /// }
     ///
     /// let actor_config = TyractorsaurConfig::new().unwrap();
     /// let actor_system = ActorSystem::new(actor_config);
-    /// actor_system.stop(Duration::from_secs(1));
-    /// exit(actor_system.await_shutdown());
+    /// let counter = actor_system.builder().spawn("counter", CounterFactory {}).unwrap();
+    ///
+    /// let found = actor_system.get_actor::<Counter>("default", "counter").unwrap();
+    /// assert_eq!(counter.get_address(), found.get_address());
+    /// assert!(actor_system.get_actor::<Logger>("default", "counter").is_none());
+    /// assert!(actor_system.get_actor::<Counter>("default", "unknown").is_none());
+    ///
+    /// counter.stop();
+    /// sleep(Duration::from_millis(100));
+    /// assert!(actor_system.get_actor::<Counter>("default", "counter").is_none());
     /// ```
-    pub fn await_shutdown(&self) -> i32 {
-        while !self.state.is_stopped() {
-            sleep(Duration::from_secs(1));
-        }
-        self.state.is_force_stopped() as i32
+    pub fn get_actor<A>(&self, pool: &str, name: &str) -> Option<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.state.get_actor_wrapper(&self.address_of(pool, name))
     }
 
-    /// Returns a reference to the [TyractorsaurConfig](../prelude/struct.TyractorsaurConfig.html)
+    /// Looks up a running Actor by an address that has been handed out before, for example one that has been persisted
+    ///
+    /// Addresses of a previous run of this system resolve to the Actor with the same pool and name, once it has been spawned again.
+    /// Addresses of other systems, including registered peers, are never resolved
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```rust
-    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use tyractorsaur::prelude::*;
     /// use std::time::Duration;
-    /// use std::process::exit;
     ///
-    /// let actor_config = TyractorsaurConfig::new().unwrap();
-    /// let actor_system = ActorSystem::new(actor_config);
-    /// let conf = actor_system.get_config();
+    /// struct Counter {}
+    /// impl Actor for Counter {}
+    /// struct CounterFactory {}
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter {}
+    ///     }
+    /// }
+    /// struct Logger {}
+    /// impl Actor for Logger {}
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let counter = actor_system.builder().spawn("counter", CounterFactory {}).unwrap();
+    ///
+    /// let persisted = serde_json::to_string(counter.get_address()).unwrap();
+    /// let address: ActorAddress = serde_json::from_str(&persisted).unwrap();
+    /// let resolved = actor_system.resolve::<Counter>(&address).unwrap();
+    /// assert_eq!(counter.get_address(), resolved.get_address());
+    /// assert_eq!(Some(ResolveError::TypeMismatch), actor_system.resolve::<Logger>(&address).err());
+    ///
+    /// // handed out by a previous run of the system
+    /// let mut previous = address.clone();
+    /// previous.remote = String::from("local/previous-run");
+    /// assert_eq!(counter.get_address(), actor_system.resolve::<Counter>(&previous).unwrap().get_address());
+    /// previous.actor = String::from("not-spawned-yet");
+    /// assert_eq!(Some(ResolveError::NotFound), actor_system.resolve::<Counter>(&previous).err());
+    ///
+    /// let mut foreign = address.clone();
+    /// foreign.system = String::from("other-system");
+    /// assert_eq!(Some(ResolveError::WrongSystem), actor_system.resolve::<Counter>(&foreign).err());
+    ///
+    /// counter.stop();
+    /// assert!(counter.await_stop(Duration::from_secs(1)));
+    /// assert_eq!(Some(ResolveError::NotFound), actor_system.resolve::<Counter>(&address).err());
     /// ```
-    pub fn get_config(&self) -> &TyractorsaurConfig {
-        &self.config
+    pub fn resolve<A>(&self, address: &ActorAddress) -> Result<ActorWrapper<A>, ResolveError>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        if address.system != self.get_name() || self.peers.get(address).is_some() {
+            return Err(ResolveError::WrongSystem);
+        }
+        self.state.resolve_actor_wrapper(&self.address_of(&address.pool, &address.actor))
     }
 
-    /// Returns the configured name of the system
+    /// Address an Actor of this system with the given pool and name has, regardless of whether it is running
+    pub fn address_of(&self, pool: &str, name: &str) -> ActorAddress {
+        ActorAddress {
+            actor: String::from(name),
+            system: String::from(self.get_name()),
+            pool: String::from(pool),
+            remote: self.get_local_remote(),
+        }
+    }
+
+    /// Counters of a running Actor, `None` once it stopped
+    ///
+    /// The counters are maintained with relaxed atomics, the processed count lags behind by at most one scheduling slot
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```rust
-    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
     /// use std::time::Duration;
-    /// use std::process::exit;
     ///
-    /// let actor_config = TyractorsaurConfig::new().unwrap();
-    /// let actor_system = ActorSystem::new(actor_config);
-    /// let name = actor_system.get_name();
+    /// struct Job { crash: bool }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         if msg.crash {
+    ///             panic!("crash");
+    ///         }
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let worker = actor_system
+    ///     .builder()
+    ///     .set_restart_policy(RestartPolicy::Always)
+    ///     .spawn("worker", WorkerFactory {})
+    ///     .unwrap();
+    /// let address = worker.get_address().clone();
+    /// assert_eq!(None, actor_system.actor_stats(&address).unwrap().last_activity);
+    ///
+    /// for _ in 0..100 {
+    ///     worker.send(Job { crash: false });
+    /// }
+    /// worker.send(Job { crash: true });
+    /// sleep(Duration::from_millis(200));
+    /// let stats = actor_system.actor_stats(&address).unwrap();
+    /// assert_eq!(100, stats.processed);
+    /// assert_eq!(0, stats.mailbox_len);
+    /// assert_eq!(1, stats.panics);
+    /// assert_eq!(1, stats.restarts);
+    /// assert!(stats.last_activity.is_some());
+    /// assert_eq!(vec![address.clone()], actor_system.all_stats().into_iter().map(|(address, _)| address).collect::<Vec<_>>());
+    ///
+    /// worker.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(None, actor_system.actor_stats(&address));
+    /// assert!(actor_system.all_stats().is_empty());
     /// ```
-    pub fn get_name(&self) -> &str {
-        &self.name
+    pub fn actor_stats(&self, address: &ActorAddress) -> Option<ActorStats> {
+        self.state.get_metrics_of(address).map(|metrics| metrics.get_stats())
+    }
+
+    /// Counters of all running Actors, sorted by address, see [actor_stats](#method.actor_stats)
+    pub fn all_stats(&self) -> Vec<(ActorAddress, ActorStats)> {
+        let mut stats: Vec<(ActorAddress, ActorStats)> = self
+            .state
+            .get_actor_metrics()
+            .into_iter()
+            .map(|(address, metrics)| (address, metrics.get_stats()))
+            .collect();
+        stats.sort_by(|(a, _), (b, _)| a.cmp(b));
+        stats
     }
+
+    /// Lists all running Actors with their scheduling state and counters, sorted by address
+    ///
+    /// Every Actor is only looked at briefly without locking its executor, so the entries might not reflect the exact same point in time.
+    /// Once the system is stopping, all entries report [ActorStatus::Stopping](../prelude/enum.ActorStatus.html#variant.Stopping)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job { millis: u64 }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { context: ActorContext<Worker> }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(msg.millis));
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let io = actor_system.add_pool("io");
+    /// let first = actor_system.builder().spawn("first", WorkerFactory {}).unwrap();
+    /// let second = actor_system.builder().set_pool(io.clone()).spawn("second", WorkerFactory {}).unwrap();
+    /// let held = actor_system
+    ///     .builder()
+    ///     .set_pool(io)
+    ///     .start_suspended(true)
+    ///     .spawn("third", WorkerFactory {})
+    ///     .unwrap();
+    /// for _ in 0..10 {
+    ///     first.send(Job { millis: 0 }).unwrap();
+    /// }
+    /// second.send(Job { millis: 0 }).unwrap();
+    /// held.send(Job { millis: 0 }).unwrap();
+    /// held.send(Job { millis: 0 }).unwrap();
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// let actors = actor_system.list_actors();
+    /// let names: Vec<(&str, &str)> = actors.iter().map(|info| (info.address.pool.as_str(), info.address.actor.as_str())).collect();
+    /// assert_eq!(vec![("default", "first"), ("io", "second"), ("io", "third")], names);
+    /// // idle Actors only go to sleep after a few seconds
+    /// let status: Vec<ActorStatus> = actors.iter().map(|info| info.status).collect();
+    /// assert_eq!(vec![ActorStatus::Scheduled, ActorStatus::Scheduled, ActorStatus::AwaitingRelease], status);
+    /// assert_eq!(vec![10, 1, 0], actors.iter().map(|info| info.processed).collect::<Vec<_>>());
+    /// assert_eq!(vec![0, 0, 2], actors.iter().map(|info| info.mailbox_len).collect::<Vec<_>>());
+    /// assert!(actors.iter().all(|info| info.restarts == 0));
+    ///
+    /// // plain data for a health endpoint
+    /// let json = serde_json::to_value(&actors[0]).unwrap();
+    /// assert_eq!("first", json["address"]["actor"]);
+    /// assert_eq!("Scheduled", json["status"]);
+    ///
+    /// // still busy with its last message when the system stops
+    /// first.send(Job { millis: 500 }).unwrap();
+    /// sleep(Duration::from_millis(100));
+    /// actor_system.stop(Duration::from_secs(3));
+    /// let actors = actor_system.list_actors();
+    /// assert_eq!("first", actors[0].address.actor);
+    /// assert!(actors.iter().all(|info| info.status == ActorStatus::Stopping));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert!(actor_system.list_actors().is_empty());
+    /// ```
+    pub fn list_actors(&self) -> Vec<ActorInfo> {
+        let is_stopping = self.state.is_stopping();
+        let mut actors: Vec<ActorInfo> = self
+            .state
+            .get_actor_metrics()
+            .into_iter()
+            .map(|(address, metrics)| {
+                let status = if is_stopping {
+                    ActorStatus::Stopping
+                } else if metrics.is_awaiting_release() {
+                    ActorStatus::AwaitingRelease
+                } else if self.wakeup_manager.is_sleeping(&address) {
+                    ActorStatus::Sleeping
+                } else {
+                    ActorStatus::Scheduled
+                };
+                let stats = metrics.get_stats();
+                let daemon = self.state.is_daemon(&address);
+                let accepted_messages = self
+                    .state
+                    .get_accepted_messages(&address)
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                ActorInfo {
+                    pool: self.thread_pool_manager.get_actor_pool(&address),
+                    address,
+                    status,
+                    mailbox_len: stats.mailbox_len,
+                    processed: stats.processed,
+                    panics: stats.panics,
+                    restarts: stats.restarts,
+                    last_activity: stats.last_activity,
+                    daemon,
+                    accepted_messages,
+                }
+            })
+            .collect();
+        actors.sort_by(|a, b| a.address.cmp(&b.address));
+        actors
+    }
+
+    /// Returns the [DebuggableActor.debug_snapshot](../prelude/trait.DebuggableActor.html#tymethod.debug_snapshot) of a running Actor, waits at most `timeout` for it
+    ///
+    /// The request is queued in the regular mailbox and handled on the thread of the Actor like any other message, so the snapshot never races with a handler.
+    /// A request that timed out stays queued, its snapshot is discarded once the Actor gets to it
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::collections::BTreeMap;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Put { key: &'static str, value: u32 }
+    /// impl ActorMessage for Put {}
+    /// struct Compact { millis: u64 }
+    /// impl ActorMessage for Compact {}
+    ///
+    /// struct Cache { entries: BTreeMap<&'static str, u32> }
+    /// impl Actor for Cache {}
+    /// impl Handler<Put> for Cache {
+    ///     fn handle(&mut self, msg: Put, _context: &ActorContext<Self>) {
+    ///         self.entries.insert(msg.key, msg.value);
+    ///     }
+    /// }
+    /// impl Handler<Compact> for Cache {
+    ///     fn handle(&mut self, msg: Compact, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(msg.millis));
+    ///     }
+    /// }
+    /// impl DebuggableActor for Cache {
+    ///     fn debug_snapshot(&self) -> String {
+    ///         format!("{:?}", self.entries)
+    ///     }
+    /// }
+    /// struct CacheFactory {}
+    /// impl ActorFactory<Cache> for CacheFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Cache>) -> Cache {
+    ///         Cache { entries: BTreeMap::new() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let cache = actor_system.builder().set_debuggable().spawn("cache", CacheFactory {}).unwrap();
+    /// cache.send(Put { key: "a", value: 1 }).unwrap();
+    /// cache.send(Put { key: "b", value: 2 }).unwrap();
+    /// let snapshot = actor_system.debug_snapshot(cache.get_address(), Duration::from_secs(1));
+    /// assert_eq!(Ok(String::from("{\"a\": 1, \"b\": 2}")), snapshot);
+    ///
+    /// // a busy Actor does not get to the request in time
+    /// cache.send(Compact { millis: 500 }).unwrap();
+    /// let snapshot = actor_system.debug_snapshot(cache.get_address(), Duration::from_millis(100));
+    /// assert_eq!(Err(SnapshotError::Timeout), snapshot);
+    /// sleep(Duration::from_millis(500));
+    /// assert!(actor_system.debug_snapshot(cache.get_address(), Duration::from_secs(1)).is_ok());
+    ///
+    /// // only Actors spawned through `set_debuggable` are inspected
+    /// let plain = actor_system.builder().spawn("plain", CacheFactory {}).unwrap();
+    /// assert_eq!(Err(SnapshotError::NotSupported), actor_system.debug_snapshot(plain.get_address(), Duration::from_secs(1)));
+    /// let missing = actor_system.address_of("default", "missing");
+    /// assert_eq!(Err(SnapshotError::NotFound), actor_system.debug_snapshot(&missing, Duration::from_secs(1)));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn debug_snapshot(&self, address: &ActorAddress, timeout: Duration) -> Result<String, SnapshotError> {
+        let started = Instant::now();
+        let receiver = self.state.request_debug_snapshot(address, timeout)?;
+        match receiver.recv_timeout(timeout.saturating_sub(started.elapsed())) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(SnapshotError::Timeout),
+            // the request is dropped together with the sender if the Actor stops before it is handled
+            Err(RecvTimeoutError::Disconnected) => Err(SnapshotError::Stopped),
+        }
+    }
+
+    /// Selects all running Actors whose `pool/name` matches `pattern`, `*` matches any amount of characters within a segment
+    ///
+    /// Matched against the Actors that exist when called, Actors that are stopping are skipped
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Shard { received: Arc<AtomicUsize> }
+    /// impl Actor for Shard {
+    ///     fn handle_serialized_message(&self, _msg: SerializedMessage) {
+    ///         self.received.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct ShardFactory { received: Arc<AtomicUsize> }
+    /// impl ActorFactory<Shard> for ShardFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shard>) -> Shard {
+    ///         Shard { received: self.received.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Auditor {}
+    /// impl Actor for Auditor {}
+    /// struct AuditorFactory {}
+    /// impl ActorFactory<Auditor> for AuditorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Auditor>) -> Auditor {
+    ///         Auditor {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let received = Arc::new(AtomicUsize::new(0));
+    /// for i in 0..3 {
+    ///     let factory = ShardFactory { received: received.clone() };
+    ///     actor_system.builder().spawn(format!("ingest-shard-{}", i), factory).unwrap();
+    /// }
+    /// let factory = ShardFactory { received: received.clone() };
+    /// actor_system.builder().set_pool(actor_system.add_pool("io")).spawn("ingest-shard-3", factory).unwrap();
+    /// actor_system.builder().spawn("ingest-shard-audit", AuditorFactory {}).unwrap();
+    /// let factory = ShardFactory { received: received.clone() };
+    /// actor_system.builder().spawn("egress-shard-0", factory).unwrap();
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// let selection = actor_system.select("default/ingest-shard-*");
+    /// assert_eq!(4, selection.len());
+    /// assert_eq!("ingest-shard-0", selection.get_addresses()[0].actor);
+    /// // only the Actors of the requested type
+    /// assert_eq!(3, selection.try_into_typed::<Shard>().len());
+    /// assert_eq!(1, selection.try_into_typed::<Auditor>().len());
+    ///
+    /// let shards = actor_system.select("*/ingest-shard-*").try_into_typed::<Shard>();
+    /// assert_eq!(4, shards.len());
+    /// assert_eq!(6, actor_system.select("*/*-shard-*").len());
+    ///
+    /// // the Auditor accepts the message as well, but ignores it
+    /// let msg = SerializedMessage::new(Vec::new());
+    /// assert_eq!(5, actor_system.select("*/ingest-shard-*").send_serialized(msg));
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(4, received.load(Ordering::Relaxed));
+    /// assert!(actor_system.select("default/unknown-*").is_empty());
+    /// assert!(actor_system.select("ingest-shard-0").is_empty());
+    ///
+    /// // Actors that are stopping are not selected anymore
+    /// shards[0].stop();
+    /// assert_eq!(3, actor_system.select("*/ingest-shard-*").try_into_typed::<Shard>().len());
+    /// ```
+    pub fn select(&self, pattern: &str) -> ActorSelection {
+        let addresses = self
+            .state
+            .select(|address| actor_selection::matches(pattern, &format!("{}/{}", address.pool, address.actor)));
+        ActorSelection::new(self.clone(), addresses)
+    }
+
+    /// Message types the Actor accepts sorted by name, empty if it is not running, see [ActorBuilder.register_message](../prelude/struct.ActorBuilder.html#method.register_message)
+    pub fn accepted_messages(&self, address: &ActorAddress) -> Vec<&'static str> {
+        self.state.get_accepted_messages(address)
+    }
+
+    /// Protocol of the Actor, `None` if it did not declare one or is not running
+    pub fn get_protocol(&self, address: &ActorAddress) -> Option<ActorProtocol> {
+        self.state.get_protocol(address)
+    }
+
+    /// Protocols of all running Actors that declared one, sorted by address
+    ///
+    /// Passed to a peer system, which can reject unsupported versions before sending them through [RemoteProtocols](../prelude/struct.RemoteProtocols.html)
+    pub fn protocol_table(&self) -> Vec<(ActorAddress, ActorProtocol)> {
+        self.state.get_protocols()
+    }
+
+    /// Returns a Builder to configure and spawn an actor in the system
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, Actor, ActorFactory, ActorContext, SerializedMessage};
+    ///
+    /// struct TestActor {}
+    ///
+    /// impl Actor for TestActor {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         assert_eq!(0, msg.content.len());
+    ///     }
+    /// }
+    ///
+    /// struct TestFactory {}
+    ///
+    /// impl ActorFactory<TestActor> for TestFactory {
+    ///     fn new_actor(&self, _context: ActorContext<TestActor>) -> TestActor {
+    ///         TestActor {}
+    ///     }
+    /// }
+    ///
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// let builder = actor_system.builder();
+    /// builder.spawn("test", TestFactory{}).unwrap();
+    /// ```
+    pub fn builder<A>(&self) -> ActorBuilder<A>
+    where
+        A: Actor
+    {
+        ActorBuilder::new(self.clone(), self.state.clone(), self.wakeup_manager.clone())
+    }
+
+    /// Spawns identical workers behind a single router, see [WorkerPool](../router/struct.WorkerPool.html)
+    ///
+    /// The router is named `name`, the workers `<name>-<n>`
+    pub fn worker_pool_builder<A>(&self, name: impl Into<String>) -> WorkerPoolBuilder<A>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        WorkerPoolBuilder::new(self.clone(), name.into())
+    }
+
+    /// Spawns an Actor for every spec, a failing spec does not keep the others from being spawned
+    ///
+    /// Every spec is attempted once and reported in the order of `specs`, a spec is a [SpawnSpec](./struct.SpawnSpec.html) or a tuple of `(name, factory, overrides)`.
+    /// Names that appear more than once within `specs` are reported as [SpawnError::DuplicateName](./enum.SpawnError.html#variant.DuplicateName) before any factory runs.
+    /// Factories run on up to [BulkSpawnConfig.with_concurrency](./struct.BulkSpawnConfig.html#method.with_concurrency) threads at the same time.
+    /// Use [ActorBuilder.spawn_many](./struct.ActorBuilder.html#method.spawn_many) to apply further settings to all specs
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Device { pinged: Arc<AtomicUsize> }
+    /// impl Actor for Device {}
+    /// impl Handler<Ping> for Device {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         self.pinged.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    /// struct DeviceFactory { id: usize, connect: Duration, created: Arc<AtomicUsize>, pinged: Arc<AtomicUsize> }
+    /// impl ActorFactory<Device> for DeviceFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Device>) -> Device {
+    ///         self.created.fetch_add(1, Ordering::SeqCst);
+    ///         sleep(self.connect);
+    ///         if self.id % 40 == 17 {
+    ///             panic!("device {} unreachable", self.id);
+    ///         }
+    ///         Device { pinged: self.pinged.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let created = Arc::new(AtomicUsize::new(0));
+    /// let pinged = Arc::new(AtomicUsize::new(0));
+    /// let specs = |prefix: &str, count: usize, connect: Duration| -> Vec<SpawnSpec<DeviceFactory>> {
+    ///     (0..count)
+    ///         .map(|id| {
+    ///             let factory = DeviceFactory { id, connect, created: created.clone(), pinged: pinged.clone() };
+    ///             SpawnSpec::new(format!("{}-{}", prefix, id), factory)
+    ///         })
+    ///         .collect()
+    /// };
+    ///
+    /// // all or nothing, the spawned subset is stopped again
+    /// let report = actor_system.spawn_many(specs("rollback", 200, Duration::from_secs(0)), BulkSpawnConfig::new().with_all_or_nothing(true));
+    /// assert_eq!((0, 5, 195), (report.spawned, report.failed, report.rolled_back));
+    /// assert_eq!(5, report.outcomes.len());
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(0, actor_system.get_actor_count());
+    ///
+    /// // the broken devices do not keep the others from being spawned
+    /// let progress = Arc::new(Mutex::new(Vec::new()));
+    /// let reported = progress.clone();
+    /// let config = BulkSpawnConfig::new().with_progress(50, move |progress| reported.lock().unwrap().push(progress.completed));
+    /// let report = actor_system.spawn_many(specs("device", 200, Duration::from_secs(0)), config);
+    /// assert_eq!((195, 5, 200), (report.spawned, report.failed, report.outcomes.len()));
+    /// let expected: Vec<(String, SpawnError)> = [17, 57, 97, 137, 177]
+    ///     .iter()
+    ///     .map(|id| (format!("device-{}", id), SpawnError::FactoryPanicked { reason: Some(format!("device {} unreachable", id)) }))
+    ///     .collect();
+    /// let errors: Vec<(String, SpawnError)> = report.errors().into_iter().map(|(name, error)| (String::from(name), error.clone())).collect();
+    /// assert_eq!(expected, errors);
+    /// assert_eq!(vec![50, 100, 150, 200], *progress.lock().unwrap());
+    /// for device in report.actors() {
+    ///     device.send(Ping {});
+    /// }
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(195, pinged.load(Ordering::SeqCst));
+    /// assert_eq!(195, actor_system.get_actor_count());
+    ///
+    /// // slow factories are spawned in parallel
+    /// let serial = actor_system.spawn_many(specs("serial", 32, Duration::from_millis(5)), BulkSpawnConfig::new());
+    /// let parallel = actor_system.spawn_many(specs("parallel", 32, Duration::from_millis(5)), BulkSpawnConfig::new().with_concurrency(8));
+    /// assert_eq!((31, 31), (serial.spawned, parallel.spawned));
+    /// assert!(parallel.elapsed * 2 < serial.elapsed);
+    ///
+    /// // duplicates within the batch are rejected before any factory runs
+    /// created.store(0, Ordering::SeqCst);
+    /// let names = ["sensor-a", "sensor-b", "sensor-a"];
+    /// let batch = names.iter().enumerate().map(|(id, name)| {
+    ///     (*name, DeviceFactory { id, connect: Duration::from_secs(0), created: created.clone(), pinged: pinged.clone() }, ActorDefaults::default())
+    /// });
+    /// let report = actor_system.spawn_many(batch, BulkSpawnConfig::new());
+    /// let errors: Vec<(&str, &SpawnError)> = report.errors();
+    /// assert_eq!(vec![("sensor-a", &SpawnError::DuplicateName), ("sensor-a", &SpawnError::DuplicateName)], errors);
+    /// assert_eq!(1, report.spawned);
+    /// assert_eq!(1, created.load(Ordering::SeqCst));
+    /// ```
+    pub fn spawn_many<A, P, I>(&self, specs: I, config: BulkSpawnConfig) -> BulkSpawnReport<A>
+    where
+        A: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<A> + Send + 'static,
+        I: IntoIterator,
+        I::Item: Into<SpawnSpec<P>>,
+    {
+        self.builder().spawn_many(specs, config)
+    }
+
+    /// Returns a builder for a group of Actors that are spawned and stopped as one unit
+    ///
+    /// Members can be of different types, see [ActorGroupBuilder.spawn](../prelude/struct.ActorGroupBuilder.html#method.spawn) and [GroupHandle.stop](../prelude/struct.GroupHandle.html#method.stop)
+    pub fn group(&self, name: impl Into<String>) -> ActorGroupBuilder {
+        ActorGroupBuilder::new(self.clone(), name.into())
+    }
+
+    /// Registers a factory under `key` for [spawn_dyn](#method.spawn_dyn), replaces a factory registered under the same key
+    ///
+    /// Requires the `dyn-spawn` feature
+    #[cfg(feature = "dyn-spawn")]
+    pub fn register_dyn_factory(&self, key: impl Into<String>, factory: Box<dyn DynActorFactory>) {
+        self.dyn_factories.register(key.into(), factory);
+    }
+
+    /// Keys of all registered factories, sorted
+    #[cfg(feature = "dyn-spawn")]
+    pub fn dyn_factory_keys(&self) -> Vec<String> {
+        self.dyn_factories.keys()
+    }
+
+    /// Spawns an Actor through the factory registered under `factory_key`, the concrete type of the Actor is only known to the factory
+    ///
+    /// `settings` are passed to the factory, see [DynFactory](./struct.DynFactory.html). Requires the `dyn-spawn` feature
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Sender};
+    /// use serde::Deserialize;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// #[derive(Deserialize, Default)]
+    /// struct S3Settings { bucket: String }
+    /// struct S3Uploader { bucket: String, uploaded: Sender<String> }
+    /// impl Actor for S3Uploader {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         let key = String::from_utf8(msg.content).unwrap();
+    ///         self.uploaded.send(format!("s3://{}/{}", self.bucket, key)).unwrap();
+    ///     }
+    /// }
+    /// struct S3UploaderFactory { bucket: String, uploaded: Sender<String> }
+    /// impl ActorFactory<S3Uploader> for S3UploaderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<S3Uploader>) -> S3Uploader {
+    ///         S3Uploader { bucket: self.bucket.clone(), uploaded: self.uploaded.clone() }
+    ///     }
+    /// }
+    ///
+    /// #[derive(Deserialize, Default)]
+    /// struct DiskSettings { dir: String }
+    /// struct LocalDiskUploader { dir: String, uploaded: Sender<String> }
+    /// impl Actor for LocalDiskUploader {
+    ///     fn handle_serialized_message(&self, msg: SerializedMessage) {
+    ///         let key = String::from_utf8(msg.content).unwrap();
+    ///         self.uploaded.send(format!("{}/{}", self.dir, key)).unwrap();
+    ///     }
+    /// }
+    /// struct LocalDiskUploaderFactory { dir: String, uploaded: Sender<String> }
+    /// impl ActorFactory<LocalDiskUploader> for LocalDiskUploaderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<LocalDiskUploader>) -> LocalDiskUploader {
+    ///         LocalDiskUploader { dir: self.dir.clone(), uploaded: self.uploaded.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (uploaded, received) = unbounded();
+    /// let s3 = uploaded.clone();
+    /// actor_system.register_dyn_factory(
+    ///     "s3-uploader",
+    ///     Box::new(DynFactory::new(move |settings: S3Settings| S3UploaderFactory { bucket: settings.bucket, uploaded: s3.clone() })),
+    /// );
+    /// let disk = uploaded.clone();
+    /// actor_system.register_dyn_factory(
+    ///     "local-disk-uploader",
+    ///     Box::new(DynFactory::new(move |settings: DiskSettings| LocalDiskUploaderFactory { dir: settings.dir, uploaded: disk.clone() })),
+    /// );
+    /// assert_eq!(vec!["local-disk-uploader", "s3-uploader"], actor_system.dyn_factory_keys());
+    ///
+    /// // the topology is only known from the configuration
+    /// let document: serde_json::Value = serde_json::from_str(r#"[
+    ///     { "factory": "s3-uploader", "name": "archive", "config": { "overrides": { "mailbox_size": 16 } }, "settings": { "bucket": "logs" } },
+    ///     { "factory": "local-disk-uploader", "name": "scratch", "settings": { "dir": "/tmp/scratch" } }
+    /// ]"#).unwrap();
+    /// let mut sinks = Vec::new();
+    /// for sink in document.as_array().unwrap() {
+    ///     let config: DynSpawnConfig = serde_json::from_value(sink["config"].clone()).unwrap_or_default();
+    ///     let actor = actor_system
+    ///         .spawn_dyn(sink["factory"].as_str().unwrap(), sink["name"].as_str().unwrap(), config, Some(sink["settings"].clone()))
+    ///         .unwrap();
+    ///     sinks.push(actor);
+    /// }
+    /// for sink in sinks.iter() {
+    ///     sink.send_serialized(SerializedMessage::new(b"report.csv".to_vec())).unwrap();
+    /// }
+    /// // both sinks handle the message on their own, in any order
+    /// let mut reports: Vec<String> = (0..2).map(|_| received.recv_timeout(Duration::from_secs(1)).unwrap()).collect();
+    /// reports.sort();
+    /// assert_eq!(vec!["/tmp/scratch/report.csv", "s3://logs/report.csv"], reports);
+    /// assert_eq!("archive", sinks[0].get_address().actor);
+    /// assert_eq!(std::any::type_name::<LocalDiskUploader>(), sinks[1].get_type_name());
+    ///
+    /// // plugins that know the type get the typed wrapper back
+    /// assert!(sinks[0].is::<S3Uploader>());
+    /// let archive = sinks[0].downcast::<S3Uploader>().unwrap();
+    /// assert_eq!(sinks[0].get_address(), archive.get_address());
+    /// assert!(sinks[0].downcast::<LocalDiskUploader>().is_none());
+    ///
+    /// // unknown keys and invalid settings are rejected before anything is spawned
+    /// let result = actor_system.spawn_dyn("ftp-uploader", "legacy", DynSpawnConfig::new(), None);
+    /// assert_eq!(Some(SpawnDynError::UnknownFactory { key: String::from("ftp-uploader") }), result.err());
+    /// let settings = serde_json::json!({ "bucket": 42 });
+    /// let result = actor_system.spawn_dyn("s3-uploader", "broken", DynSpawnConfig::new(), Some(settings));
+    /// assert!(matches!(result.err(), Some(SpawnDynError::InvalidSettings { .. })));
+    /// let result = actor_system.spawn_dyn("s3-uploader", "archive", DynSpawnConfig::new(), None);
+    /// assert_eq!(Some(SpawnDynError::Spawn(SpawnError::NameTaken)), result.err());
+    /// assert_eq!(2, actor_system.get_actor_count());
+    ///
+    /// sinks[1].stop();
+    /// let deadline = Instant::now() + Duration::from_secs(1);
+    /// while !sinks[1].is_stopped() && Instant::now() < deadline {
+    ///     std::thread::sleep(Duration::from_millis(5));
+    /// }
+    /// assert!(sinks[1].is_stopped());
+    /// ```
+    #[cfg(feature = "dyn-spawn")]
+    pub fn spawn_dyn(
+        &self,
+        factory_key: &str,
+        name: impl Into<String>,
+        actor_config: DynSpawnConfig,
+        settings: Option<serde_json::Value>,
+    ) -> Result<DynActorRef, SpawnDynError> {
+        let factory = self.dyn_factories.get(factory_key).ok_or_else(|| SpawnDynError::UnknownFactory {
+            key: String::from(factory_key),
+        })?;
+        factory.spawn(self, name.into(), &actor_config, settings)
+    }
+
+    /// Number of Actors that are currently running
+    pub fn get_actor_count(&self) -> usize {
+        self.state.get_actor_count()
+    }
+
+    /// Releases all Actors spawned through [ActorBuilder.start_suspended](../prelude/struct.ActorBuilder.html#method.start_suspended) whose address matches `filter`
+    ///
+    /// Actors are released in the order of their addresses, returns how many have been released
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// struct Stage {}
+    /// impl Actor for Stage {}
+    ///
+    /// struct StageFactory {}
+    /// impl ActorFactory<Stage> for StageFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Stage>) -> Stage {
+    ///         Stage {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let builder = actor_system.builder().start_suspended(true);
+    /// let stages: Vec<ActorWrapper<Stage>> = (0..3)
+    ///     .map(|i| builder.spawn(format!("stage-{}", i), StageFactory {}).unwrap())
+    ///     .collect();
+    /// let other = builder.spawn("other", StageFactory {}).unwrap();
+    ///
+    /// assert_eq!(3, actor_system.release_all(|address| address.actor.starts_with("stage-")));
+    /// assert_eq!(0, actor_system.release_all(|address| address.actor.starts_with("stage-")));
+    /// assert!(stages.iter().all(|stage| !stage.is_awaiting_release()));
+    /// assert!(other.is_awaiting_release());
+    /// ```
+    pub fn release_all<F>(&self, filter: F) -> usize
+    where
+        F: Fn(&ActorAddress) -> bool,
+    {
+        self.state.release_all(filter)
+    }
+
+    /// Waits until every Actor that is running when it is called is ready, returns the addresses of those that are not, sorted, once `timeout` elapsed
+    ///
+    /// An Actor is ready once [Actor.is_ready](../prelude/trait.Actor.html#method.is_ready) returned `true` after `pre_start` or a handled message, or once it called [ActorContext.notify_ready](../prelude/struct.ActorContext.html#method.notify_ready).
+    /// Actors that have not been executed yet are woken up to run `pre_start`, lazy Actors are ready from the start, Actors awaiting their release are not ready before they have been released.
+    /// Actors that stop in the meantime and Actors spawned after the call do not hold it up
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Warmup {}
+    /// impl ActorMessage for Warmup {}
+    ///
+    /// // ready once pre_start finished
+    /// struct Cache {}
+    /// impl Actor for Cache {
+    ///     fn pre_start(&mut self) {
+    ///         sleep(Duration::from_millis(300));
+    ///     }
+    /// }
+    /// struct CacheFactory {}
+    /// impl ActorFactory<Cache> for CacheFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Cache>) -> Cache {
+    ///         Cache {}
+    ///     }
+    /// }
+    ///
+    /// // ready once it has been warmed up
+    /// struct Index { is_warm: bool }
+    /// impl Actor for Index {
+    ///     fn is_ready(&self) -> bool {
+    ///         self.is_warm
+    ///     }
+    /// }
+    /// impl Handler<Warmup> for Index {
+    ///     fn handle(&mut self, _msg: Warmup, _context: &ActorContext<Self>) {
+    ///         self.is_warm = true;
+    ///     }
+    /// }
+    /// struct IndexFactory {}
+    /// impl ActorFactory<Index> for IndexFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Index>) -> Index {
+    ///         Index { is_warm: false }
+    ///     }
+    /// }
+    ///
+    /// // signals its readiness from a background thread
+    /// struct Feed {}
+    /// impl Actor for Feed {
+    ///     fn is_ready(&self) -> bool {
+    ///         false
+    ///     }
+    /// }
+    /// struct FeedFactory {}
+    /// impl ActorFactory<Feed> for FeedFactory {
+    ///     fn new_actor(&self, context: ActorContext<Feed>) -> Feed {
+    ///         std::thread::spawn(move || {
+    ///             sleep(Duration::from_millis(100));
+    ///             context.notify_ready();
+    ///         });
+    ///         Feed {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let cache = actor_system.builder().spawn("cache", CacheFactory {}).unwrap();
+    /// let index = actor_system.builder().spawn("index", IndexFactory {}).unwrap();
+    /// let feed = actor_system.builder().spawn("feed", FeedFactory {}).unwrap();
+    ///
+    /// let laggards = actor_system.await_all_ready(Duration::from_millis(50)).unwrap_err();
+    /// let expected = vec![cache.get_address().clone(), feed.get_address().clone(), index.get_address().clone()];
+    /// assert_eq!(expected, laggards);
+    ///
+    /// let laggards = actor_system.await_all_ready(Duration::from_millis(500)).unwrap_err();
+    /// assert_eq!(vec![index.get_address().clone()], laggards);
+    ///
+    /// index.send(Warmup {}).unwrap();
+    /// assert_eq!(Ok(()), actor_system.await_all_ready(Duration::from_secs(1)));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn await_all_ready(&self, timeout: Duration) -> Result<(), Vec<ActorAddress>> {
+        let pending = self.state.get_unready();
+        for address in &pending {
+            self.wakeup_manager.wakeup(address.clone());
+        }
+        self.state.await_ready(pending, timeout)
+    }
+
+    /// Sends a SystemStopMessage to all running Actors in the order of their shutdown phases, and wakes them up if necessary.
+    /// Users can implement their own clean system stop behavior, by implementing [Actor.on_system_stop](../prelude/trait.Actor.html#method.on_system_stop) and [Actor.on_actor_stop](../prelude/trait.Actor.html#method.on_actor_stop)
+    ///
+    /// System will stop after all actors have been stopped or after `graceful_termination_timeout`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use std::time::Duration;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    ///
+    /// Actors are stopped phase by phase, see [ActorBuilder.set_shutdown_phase](../prelude/struct.ActorBuilder.html#method.set_shutdown_phase), daemons are stopped after all other Actors, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon).
+    /// Spawns fail with [SpawnError::SystemStopping](./enum.SpawnError.html#variant.SystemStopping) once the phase of the Actor completed, an Actor that is spawned before is stopped like every other Actor.
+    /// Sends are still delivered until the system is stopped, afterwards they are reported as dead letters:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Barrier};
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Worker {
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Worker {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let barrier = Arc::new(Barrier::new(9));
+    /// let spawners: Vec<_> = (0..8)
+    ///     .map(|spawner| {
+    ///         let builder = actor_system.builder();
+    ///         let barrier = barrier.clone();
+    ///         std::thread::spawn(move || {
+    ///             barrier.wait();
+    ///             (0..200)
+    ///                 .map(|i| builder.try_spawn(format!("worker-{}-{}", spawner, i), WorkerFactory {}))
+    ///                 .collect::<Vec<Result<ActorWrapper<Worker>, SpawnError>>>()
+    ///         })
+    ///     })
+    ///     .collect();
+    /// let stopper = actor_system.clone();
+    /// let stopping = std::thread::spawn(move || {
+    ///     barrier.wait();
+    ///     stopper.stop(Duration::from_secs(10));
+    /// });
+    /// let results: Vec<_> = spawners.into_iter().flat_map(|spawner| spawner.join().unwrap()).collect();
+    /// stopping.join().unwrap();
+    ///
+    /// assert!(results.iter().all(|result| matches!(result, Ok(_) | Err(SpawnError::SystemStopping))));
+    /// // every Actor that has been spawned received the system stop, none is left behind
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert_eq!(0, actor_system.get_actor_count());
+    ///
+    /// let worker = results.into_iter().find_map(|result| result.ok());
+    /// if let Some(worker) = worker {
+    ///     let dead_letters = actor_system.dead_letter_count();
+    ///     assert!(matches!(worker.send(Ping {}), Err(SendError::Stopped(_))));
+    ///     assert_eq!(dead_letters + 1, actor_system.dead_letter_count());
+    /// }
+    /// assert!(matches!(actor_system.builder::<Worker>().try_spawn("late", WorkerFactory {}), Err(SpawnError::SystemStopping)));
+    /// ```
+    pub fn stop(&self, graceful_termination_timeout: Duration) {
+        // the shutdown itself is not part of a recorded execution
+        #[cfg(feature = "replay")]
+        if let Some(recorder) = &self.recorder {
+            recorder.finish();
+        }
+        self.state.stop(graceful_termination_timeout);
+        // Actors that have never received a message are only woken up by the loop
+        self.wakeup_manager.notify_stopping();
+    }
+
+    /// Executes `hook` once the system stopped, after all Actors stopped or have been force stopped and before [await_shutdown](#method.await_shutdown) returns
+    ///
+    /// Hooks are executed in reverse registration order on the thread that stops the system, exactly once no matter how often [stop](#method.stop) is called.
+    /// A panicking hook does not keep the remaining hooks from being executed. Hooks added once the execution started are rejected
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Worker {
+    ///     context: ActorContext<Self>,
+    /// }
+    /// impl Actor for Worker {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, context: ActorContext<Worker>) -> Worker {
+    ///         Worker { context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.builder().spawn("worker", WorkerFactory {}).unwrap();
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let (system, exporter) = (actor_system.clone(), log.clone());
+    /// actor_system
+    ///     .add_shutdown_hook(Box::new(move || exporter.lock().unwrap().push(format!("exporter {}", system.get_actor_count()))))
+    ///     .unwrap();
+    /// actor_system.add_shutdown_hook(Box::new(|| panic!("temp dir is gone"))).unwrap();
+    /// let temp_dir = log.clone();
+    /// actor_system.add_shutdown_hook(Box::new(move || temp_dir.lock().unwrap().push(String::from("temp dir")))).unwrap();
+    ///
+    /// // concurrent stops execute the hooks only once
+    /// let stoppers: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let system = actor_system.clone();
+    ///         std::thread::spawn(move || system.stop(Duration::from_secs(1)))
+    ///     })
+    ///     .collect();
+    /// stoppers.into_iter().for_each(|stopper| stopper.join().unwrap());
+    /// assert_eq!(0, actor_system.await_shutdown());
+    ///
+    /// // executed in reverse order after the last Actor stopped, despite the panicking hook
+    /// assert_eq!(vec!["temp dir", "exporter 0"], *log.lock().unwrap());
+    /// let late = actor_system.add_shutdown_hook(Box::new(|| {}));
+    /// assert_eq!(Err(ShutdownHookError::AlreadyFinalizing), late);
+    /// ```
+    pub fn add_shutdown_hook(&self, hook: Box<dyn FnOnce() + Send>) -> Result<(), ShutdownHookError> {
+        self.state.add_shutdown_hook(hook)
+    }
+
+    /// Waits for the system to stop
+    ///
+    /// The system counts as stopped once all Actors stopped and its worker and background threads have been joined, or once the stop timeout elapsed
+    ///
+    /// # Returns
+    ///
+    /// `0 as i32` if cleanly stopped by removing all actors from system
+    ///
+    /// `1 as i32` if force stopped after stop timeout
+    ///
+    /// `3 as i32` if stopped because of a panic, see [PanicEscalation::StopSystem](../prelude/enum.PanicEscalation.html#variant.StopSystem)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use std::time::Duration;
+    /// use std::process::exit;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// actor_system.stop(Duration::from_secs(1));
+    /// exit(actor_system.await_shutdown());
+    /// ```
+    pub fn await_shutdown(&self) -> i32 {
+        self.state.await_stopped(None);
+        self.state.get_exit_code()
+    }
+
+    /// Waits for the system to stop, but no longer than `timeout`
+    ///
+    /// # Returns
+    ///
+    /// `Some(i32)` as returned by [await_shutdown](#method.await_shutdown) if the system stopped in time
+    ///
+    /// `None` if it is still running or stopping after `timeout`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem};
+    /// use std::time::Duration;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// assert_eq!(actor_system.await_shutdown_timeout(Duration::from_millis(10)), None);
+    ///
+    /// actor_system.stop(Duration::from_secs(10));
+    /// assert_eq!(actor_system.await_shutdown_timeout(Duration::from_secs(1)), Some(0));
+    /// ```
+    pub fn await_shutdown_timeout(&self, timeout: Duration) -> Option<i32> {
+        if !self.state.await_stopped(Some(timeout)) {
+            return None;
+        }
+        Some(self.state.get_exit_code())
+    }
+
+    /// Same as [await_shutdown](#method.await_shutdown), but the returned future yields instead of blocking the thread
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem};
+    /// use std::time::Duration;
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let code = runtime.block_on(async {
+    ///     let stopper = actor_system.clone();
+    ///     tokio::spawn(async move { stopper.stop(Duration::from_secs(1)) });
+    ///     actor_system.await_shutdown_async().await
+    /// });
+    /// assert_eq!(0, code);
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn await_shutdown_async(&self) -> AwaitShutdown {
+        AwaitShutdown::new(self.state.clone())
+    }
+
+    /// Waits for the system to stop and for all worker and background threads to exit, but no longer than `timeout` in total
+    ///
+    /// [await_shutdown](#method.await_shutdown) already waits for these threads, unless the stop timeout elapsed before they exited; this reports whether they did.
+    /// Background threads are woken up by the stop itself, an idle system is joined right after it stopped
+    ///
+    /// # Returns
+    ///
+    /// `true` if every worker and background thread has been joined in time
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem};
+    /// use std::time::Duration;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// assert!(!actor_system.join_workers(Duration::from_millis(10)));
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert!(actor_system.join_workers(Duration::from_secs(3)));
+    /// ```
+    ///
+    /// Idle systems stop without waiting for periodic checks:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let start = Instant::now();
+    /// actor_system.stop(Duration::from_millis(100));
+    /// assert!(actor_system.join_workers(Duration::from_millis(500)));
+    /// assert!(start.elapsed() < Duration::from_millis(500));
+    /// ```
+    pub fn join_workers(&self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        if !self.state.await_stopped(Some(timeout)) {
+            return false;
+        }
+        self.thread_pool_manager
+            .await_workers_joined(timeout.saturating_sub(start.elapsed()))
+    }
+
+    /// Ends a running [stop](#method.stop) immediately, as if its timeout had elapsed
+    ///
+    /// Initiates the stop first if required
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem};
+    /// use std::time::Duration;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// actor_system.stop(Duration::from_secs(60));
+    /// actor_system.force_stop();
+    /// ```
+    pub fn force_stop(&self) {
+        self.stop(Duration::from_secs(0));
+        self.state.force_stop();
+    }
+
+    /// Blocks until one of the configured signals arrives, stops the system and waits until it stopped
+    ///
+    /// See [ActorSystem.install_signal_handler](#method.install_signal_handler) for applications with their own main loop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust,no_run
+    /// use tyractorsaur::prelude::*;
+    /// use std::process::exit;
+    /// use std::time::Duration;
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let report = actor_system.run_until_signal(SignalConfig::new(Duration::from_secs(25))).unwrap();
+    /// eprintln!("stopped by {:?}", report.signal);
+    /// exit(report.exit_code());
+    /// ```
+    #[cfg(all(feature = "signals", unix))]
+    pub fn run_until_signal(&self, config: SignalConfig) -> Result<SignalShutdownReport, SignalError> {
+        Ok(self.install_signal_handler(config)?.wait())
+    }
+
+    /// Installs handlers for the configured signals, the first signal stops the system with the configured timeout
+    ///
+    /// Only a single guard can exist per process, the previous handlers are restored once it is dropped
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::io::{BufRead, BufReader};
+    /// use std::process::{Command, Stdio};
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Block {}
+    /// impl ActorMessage for Block {}
+    /// struct Service { context: ActorContext<Self> }
+    /// impl Actor for Service {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Block> for Service {
+    ///     fn handle(&mut self, _msg: Block, _context: &ActorContext<Self>) {
+    ///         println!("blocked");
+    ///         sleep(Duration::from_secs(60));
+    ///     }
+    /// }
+    /// struct ServiceFactory {}
+    /// impl ActorFactory<Service> for ServiceFactory {
+    ///     fn new_actor(&self, context: ActorContext<Service>) -> Service {
+    ///         Service { context }
+    ///     }
+    /// }
+    ///
+    /// // the example runs itself a second time as the process that receives the signals
+    /// if let Ok(mode) = std::env::var("TYRACTORSAUR_SIGNAL_EXAMPLE") {
+    ///     let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    ///     let service = actor_system.builder().spawn("service", ServiceFactory {}).unwrap();
+    ///     let guard = actor_system.install_signal_handler(SignalConfig::new(Duration::from_secs(30))).unwrap();
+    ///     let other_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    ///     let other_config = SignalConfig::new(Duration::from_secs(30));
+    ///     assert_eq!(other_system.install_signal_handler(other_config).err(), Some(SignalError::AlreadyInstalled));
+    ///     if mode == "busy" {
+    ///         service.send(Block {});
+    ///     } else {
+    ///         println!("idle");
+    ///     }
+    ///     let report = guard.wait();
+    ///     println!("{:?} {}", report.signal, report.escalated);
+    ///     std::process::exit(report.exit_code());
+    /// }
+    ///
+    /// let run = |mode: &str, signals: usize| {
+    ///     let mut child = Command::new(std::env::current_exe().unwrap())
+    ///         .env("TYRACTORSAUR_SIGNAL_EXAMPLE", mode)
+    ///         .stdout(Stdio::piped())
+    ///         .spawn()
+    ///         .unwrap();
+    ///     let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    ///     lines.next().unwrap().unwrap();
+    ///     let started = Instant::now();
+    ///     for _ in 0..signals {
+    ///         Command::new("kill").arg("-TERM").arg(child.id().to_string()).status().unwrap();
+    ///         sleep(Duration::from_millis(200));
+    ///     }
+    ///     let code = child.wait().unwrap().code();
+    ///     let report = lines.map(|line| line.unwrap()).last().unwrap();
+    ///     (code, started.elapsed(), report)
+    /// };
+    ///
+    /// // a single SIGTERM stops gracefully, well within the timeout of 30 seconds
+    /// let (code, elapsed, report) = run("idle", 1);
+    /// assert_eq!(code, Some(0));
+    /// assert!(elapsed < Duration::from_secs(10));
+    /// assert_eq!(report, "Some(Terminate) false");
+    ///
+    /// // the blocked Actor keeps the graceful stop from completing, a second SIGTERM forces it
+    /// let (code, elapsed, report) = run("busy", 2);
+    /// assert_eq!(code, Some(1));
+    /// assert!(elapsed < Duration::from_secs(10));
+    /// assert_eq!(report, "Some(Terminate) true");
+    ///
+    /// // handlers can be installed again once the guard is dropped
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// drop(actor_system.install_signal_handler(SignalConfig::new(Duration::from_secs(1))).unwrap());
+    /// assert!(actor_system.install_signal_handler(SignalConfig::new(Duration::from_secs(1))).is_ok());
+    /// ```
+    #[cfg(all(feature = "signals", unix))]
+    pub fn install_signal_handler(&self, config: SignalConfig) -> Result<SignalGuard, SignalError> {
+        SignalGuard::install(self, config)
+    }
+
+    /// Re-executes a log recorded through `general.record_execution` on a system created with [ActorSystem.new_for_replay](#method.new_for_replay)
+    ///
+    /// The scenario has to be set up the same way as during the recording, i.e. the same Actors have to be spawned and the same external messages have to be sent.
+    /// Afterwards Actors handle their messages in exactly the recorded order, so that interleaving bugs reproduce on every run.
+    /// The recording ends as soon as [ActorSystem.stop](#method.stop) is called.
+    /// A log can be truncated to bisect a bug, the replay then stops with [ReplayStatus::EndOfLog](../prelude/enum.ReplayStatus.html#variant.EndOfLog).
+    ///
+    /// Only the ordering controlled by the framework is replayed, non-determinism within handlers (random numbers, wall clock) is not
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Tick {}
+    /// impl ActorMessage for Tick {}
+    /// struct Mix { value: u64 }
+    /// impl ActorMessage for Mix {}
+    ///
+    /// struct Source { id: u64, sink: ActorWrapper<Sink> }
+    /// impl Actor for Source {}
+    /// impl Handler<Tick> for Source {
+    ///     fn handle(&mut self, _msg: Tick, _context: &ActorContext<Self>) {
+    ///         self.sink.send(Mix { value: self.id });
+    ///     }
+    /// }
+    /// struct SourceFactory { id: u64, sink: ActorWrapper<Sink> }
+    /// impl ActorFactory<Source> for SourceFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Source>) -> Source {
+    ///         Source { id: self.id, sink: self.sink.clone() }
+    ///     }
+    /// }
+    ///
+    /// struct Sink { state: Arc<Mutex<(u64, Vec<u64>)>> }
+    /// impl Actor for Sink {}
+    /// impl Handler<Mix> for Sink {
+    ///     fn handle(&mut self, msg: Mix, context: &ActorContext<Self>) {
+    ///         let mut state = self.state.lock().unwrap();
+    ///         state.0 = state.0.wrapping_mul(31).wrapping_add(msg.value);
+    ///         state.1.push(msg.value);
+    ///         if state.1.len() == 40 {
+    ///             context.actor_ref.stop();
+    ///         }
+    ///     }
+    /// }
+    /// struct SinkFactory { state: Arc<Mutex<(u64, Vec<u64>)>> }
+    /// impl ActorFactory<Sink> for SinkFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+    ///         Sink { state: self.state.clone() }
+    ///     }
+    /// }
+    ///
+    /// fn scenario(actor_system: &ActorSystem) -> Arc<Mutex<(u64, Vec<u64>)>> {
+    ///     let state = Arc::new(Mutex::new((0, Vec::new())));
+    ///     let sink = actor_system.builder().spawn("sink", SinkFactory { state: state.clone() }).unwrap();
+    ///     let a = actor_system.builder().spawn("a", SourceFactory { id: 1, sink: sink.clone() }).unwrap();
+    ///     let b = actor_system.builder().spawn("b", SourceFactory { id: 2, sink }).unwrap();
+    ///     for _ in 0..20 {
+    ///         a.send(Tick {});
+    ///         b.send(Tick {});
+    ///     }
+    ///     a.stop();
+    ///     b.stop();
+    ///     state
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join(format!("tyractorsaur-replay-{}.log", std::process::id()));
+    /// let path = path.to_str().unwrap().to_string();
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.record_execution = Some(path.clone());
+    /// let actor_system = ActorSystem::new(config);
+    /// let recorded = scenario(&actor_system);
+    /// while recorded.lock().unwrap().1.len() < 40 {
+    ///     std::thread::sleep(Duration::from_millis(10));
+    /// }
+    /// actor_system.stop(Duration::from_secs(5));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// let recorded = recorded.lock().unwrap().clone();
+    ///
+    /// let mut handled = None;
+    /// for _ in 0..20 {
+    ///     let replay_system = ActorSystem::new_for_replay(TyractorsaurConfig::new().unwrap());
+    ///     let replayed = scenario(&replay_system);
+    ///     let report = replay_system.replay_execution(&path).unwrap();
+    ///     assert_eq!(ReplayStatus::Completed, report.status);
+    ///     assert_eq!(recorded, *replayed.lock().unwrap());
+    ///     if let Some(handled) = &handled {
+    ///         assert_eq!(handled, &report.handled);
+    ///     }
+    ///     handled = Some(report.handled);
+    /// }
+    ///
+    /// // cut the log in the middle of a line
+    /// let content = std::fs::read_to_string(&path).unwrap();
+    /// std::fs::write(&path, &content[..content.len() / 2]).unwrap();
+    /// let replay_system = ActorSystem::new_for_replay(TyractorsaurConfig::new().unwrap());
+    /// scenario(&replay_system);
+    /// let report = replay_system.replay_execution(&path).unwrap();
+    /// assert_eq!(ReplayStatus::EndOfLog, report.status);
+    /// assert_eq!(handled.unwrap()[..report.handled.len()], report.handled[..]);
+    /// std::fs::remove_file(&path).unwrap();
+    /// ```
+    #[cfg(feature = "replay")]
+    pub fn replay_execution(&self, path: &str) -> std::io::Result<ReplayReport> {
+        let content = std::fs::read_to_string(path)?;
+        let mut report = ReplayReport {
+            status: ReplayStatus::EndOfLog,
+            handled: Vec::new(),
+        };
+        let entries = match parse_log(&content) {
+            Some(entries) => entries,
+            None => {
+                report.status = ReplayStatus::InvalidLog;
+                return Ok(report);
+            }
+        };
+        for (index, entry) in entries.into_iter().enumerate() {
+            // the first line is the header
+            let line = index + 2;
+            let (pool, actor, type_name) = match entry {
+                Some(LogEntry::Handled { pool, actor, type_name }) => (pool, actor, type_name),
+                Some(LogEntry::Degraded) => {
+                    report.status = ReplayStatus::Degraded { line };
+                    return Ok(report);
+                }
+                Some(LogEntry::End) => {
+                    report.status = ReplayStatus::Completed;
+                    return Ok(report);
+                }
+                None => {
+                    report.status = ReplayStatus::InvalidLog;
+                    return Ok(report);
+                }
+            };
+            let address = self.address_of(pool, actor);
+            let executor = match self.wakeup_manager.get_sleeping_actor(&address) {
+                Some(executor) => executor,
+                None => {
+                    report.status = ReplayStatus::UnknownActor { line };
+                    return Ok(report);
+                }
+            };
+            let mut executor = executor.write().unwrap();
+            executor.replay_next(type_name);
+            if executor.last_handled() != Some(type_name) {
+                report.status = ReplayStatus::Diverged { line };
+                return Ok(report);
+            }
+            report.handled.push((String::from(actor), String::from(type_name)));
+            if executor.is_stopped() && !executor.is_wakeup_pending() && executor.handle(false) == ActorState::Stopped {
+                self.wakeup_manager.remove_sleeping_actor(&address);
+                self.state.remove_actor(&address, &self.thread_pool_manager.get_actor_pool(&address));
+            }
+        }
+        Ok(report)
+    }
+
+    #[cfg(feature = "replay")]
+    pub(crate) fn get_recorder(&self) -> Option<&ExecutionRecorder> {
+        self.recorder.as_ref()
+    }
+
+    /// Returns the recent metrics history of a thread pool
+    ///
+    /// `None` if the pool does not exist or [GeneralConfig.metrics_history](../prelude/struct.HistoryConfig.html) is not configured
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, HistoryConfig};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let mut actor_config = TyractorsaurConfig::new().unwrap();
+    /// actor_config.general.metrics_history = Some(HistoryConfig::new(Duration::from_millis(10), Duration::from_millis(50)));
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// sleep(Duration::from_millis(100));
+    /// let history = actor_system.pool_history("default").unwrap();
+    /// assert!(!history.samples.is_empty());
+    /// assert!(history.samples.len() <= 5);
+    /// assert!(actor_system.pool_history("unknown").is_none());
+    /// ```
+    pub fn pool_history(&self, pool: &str) -> Option<PoolMetricsHistory> {
+        self.thread_pool_manager.get_history(pool)
+    }
+
+    /// Returns how many worker threads of a pool died and have been replaced
+    ///
+    /// Worker threads are named `{system}-{pool}-{index}`, a replacement keeps the name of the thread it replaces.
+    /// The Actor that was executed by the dead worker is lost, Actors waiting for the worker are handed to the rest of the pool.
+    /// `None` if the pool does not exist
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::panic::resume_unwind;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Crash {}
+    /// impl ActorMessage for Crash {}
+    ///
+    /// struct Fragile {}
+    /// impl Actor for Fragile {
+    ///     fn on_panic(&mut self, _report: ActorPanicReport) {
+    ///         // panicking outside of a handler takes the worker thread down
+    ///         resume_unwind(Box::new("unrecoverable"));
+    ///     }
+    /// }
+    /// impl Handler<Crash> for Fragile {
+    ///     fn handle(&mut self, _msg: Crash, _context: &ActorContext<Self>) {
+    ///         panic!("crash");
+    ///     }
+    /// }
+    /// struct FragileFactory {}
+    /// impl ActorFactory<Fragile> for FragileFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Fragile>) -> Fragile {
+    ///         Fragile {}
+    ///     }
+    /// }
+    ///
+    /// struct WhoAmI {}
+    /// impl ActorMessage for WhoAmI {}
+    ///
+    /// struct Probe {
+    ///     thread_name: Arc<Mutex<Option<String>>>,
+    /// }
+    /// impl Actor for Probe {}
+    /// impl Handler<WhoAmI> for Probe {
+    ///     fn handle(&mut self, _msg: WhoAmI, _context: &ActorContext<Self>) {
+    ///         *self.thread_name.lock().unwrap() = std::thread::current().name().map(String::from);
+    ///     }
+    /// }
+    /// struct ProbeFactory {
+    ///     thread_name: Arc<Mutex<Option<String>>>,
+    /// }
+    /// impl ActorFactory<Probe> for ProbeFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Probe>) -> Probe {
+    ///         Probe { thread_name: self.thread_name.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("fragile", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let thread_name = Arc::new(Mutex::new(None));
+    /// let fragile = actor_system.builder().set_pool_name("fragile").unwrap().spawn("fragile", FragileFactory {}).unwrap();
+    /// let probe = actor_system.builder().set_pool_name("fragile").unwrap().spawn("probe", ProbeFactory { thread_name: thread_name.clone() }).unwrap();
+    ///
+    /// fragile.send(Crash {});
+    /// sleep(Duration::from_millis(1500));
+    /// assert_eq!(actor_system.pool_worker_panics("fragile"), Some(1));
+    ///
+    /// probe.send(WhoAmI {});
+    /// sleep(Duration::from_millis(200));
+    /// let expected = format!("{}-fragile-0", actor_system.get_name());
+    /// assert_eq!(*thread_name.lock().unwrap(), Some(expected));
+    /// assert_eq!(actor_system.pool_worker_panics("unknown"), None);
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert!(actor_system.join_workers(Duration::from_secs(3)));
+    /// ```
+    pub fn pool_worker_panics(&self, pool: &str) -> Option<usize> {
+        self.thread_pool_manager.get_worker_panics(pool)
+    }
+
+    /// Changes the thread limits of a pool while the system is running
+    ///
+    /// Growing spawns the additional worker threads right away, shrinking lets the surplus threads exit after their current Actor.
+    /// The number of threads is still derived from `threads_factor`, see [ThreadPoolConfig.thread_count](../prelude/struct.ThreadPoolConfig.html#method.thread_count)
+    ///
+    /// # Returns
+    ///
+    /// `Err(PoolError::UnknownPool)` if the pool does not exist
+    ///
+    /// `Err(PoolError::InvalidThreadCount)` if `threads_max` is 0 or smaller than `threads_min`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Count {}
+    /// impl ActorMessage for Count {}
+    ///
+    /// struct Counter {
+    ///     count: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Counter {}
+    /// impl Handler<Count> for Counter {
+    ///     fn handle(&mut self, _msg: Count, _context: &ActorContext<Self>) {
+    ///         self.count.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// struct CounterFactory {
+    ///     count: Arc<AtomicUsize>,
+    /// }
+    /// impl ActorFactory<Counter> for CounterFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Counter>) -> Counter {
+    ///         Counter { count: self.count.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("elastic", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// let count = Arc::new(AtomicUsize::new(0));
+    /// let counters: Vec<_> = (0..4)
+    ///     .map(|i| {
+    ///         let factory = CounterFactory { count: count.clone() };
+    ///         actor_system.builder().set_pool_name("elastic").unwrap().spawn(format!("counter-{}", i), factory).unwrap()
+    ///     })
+    ///     .collect();
+    /// let send_all = || counters.iter().for_each(|counter| counter.send(Count {}).unwrap());
+    ///
+    /// send_all();
+    /// actor_system.resize_pool("elastic", 3, 3).unwrap();
+    /// send_all();
+    /// sleep(Duration::from_millis(500));
+    /// assert_eq!(actor_system.pool_stats("elastic").unwrap().threads, 3);
+    /// send_all();
+    ///
+    /// actor_system.resize_pool("elastic", 1, 1).unwrap();
+    /// send_all();
+    /// sleep(Duration::from_millis(1500));
+    /// assert_eq!(actor_system.pool_stats("elastic").unwrap().threads, 1);
+    /// send_all();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(count.load(Ordering::Relaxed), 20);
+    ///
+    /// assert_eq!(actor_system.resize_pool("unknown", 1, 1), Err(PoolError::UnknownPool));
+    /// assert_eq!(actor_system.resize_pool("elastic", 2, 1), Err(PoolError::InvalidThreadCount));
+    /// ```
+    pub fn resize_pool(&self, name: &str, threads_min: usize, threads_max: usize) -> Result<(), PoolError> {
+        self.thread_pool_manager.resize(name, threads_min, threads_max)
+    }
+
+    /// Moves a running Actor to the pool `pool` without stopping it, see [ActorContext.move_to_pool](../prelude/struct.ActorContext.html#method.move_to_pool)
+    ///
+    /// The move is queued in the priority lane and takes effect between two handler invocations, the mailbox is kept as it is.
+    /// The address of the Actor keeps the pool it has been spawned on, [list_actors](#method.list_actors) reports the pool it is scheduled on
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{unbounded, Receiver};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Event { id: usize }
+    /// impl ActorMessage for Event {}
+    /// struct Pause { gate: Receiver<()> }
+    /// impl ActorMessage for Pause {}
+    ///
+    /// struct Aggregator { handled: Arc<Mutex<Vec<(usize, String)>>> }
+    /// impl Actor for Aggregator {}
+    /// impl Handler<Event> for Aggregator {
+    ///     fn handle(&mut self, msg: Event, _context: &ActorContext<Self>) {
+    ///         let thread = String::from(std::thread::current().name().unwrap());
+    ///         self.handled.lock().unwrap().push((msg.id, thread.split('-').nth(1).unwrap().to_string()));
+    ///     }
+    /// }
+    /// impl Handler<Pause> for Aggregator {
+    ///     fn handle(&mut self, msg: Pause, _context: &ActorContext<Self>) {
+    ///         let _ = msg.gate.recv();
+    ///     }
+    /// }
+    /// struct AggregatorFactory { handled: Arc<Mutex<Vec<(usize, String)>>> }
+    /// impl ActorFactory<Aggregator> for AggregatorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Aggregator>) -> Aggregator {
+    ///         Aggregator { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("analytics");
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.add_pool("heavy");
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let aggregator = actor_system.builder().spawn("aggregator", AggregatorFactory { handled: handled.clone() }).unwrap();
+    ///
+    /// // the Actor is moved while another thread keeps sending
+    /// let sender = aggregator.clone();
+    /// let load = std::thread::spawn(move || {
+    ///     for id in 0..20000 {
+    ///         sender.send(Event { id }).unwrap();
+    ///         if id % 100 == 0 {
+    ///             sleep(Duration::from_millis(1));
+    ///         }
+    ///     }
+    /// });
+    /// sleep(Duration::from_millis(50));
+    /// actor_system.move_actor(aggregator.get_address(), "heavy").unwrap();
+    /// load.join().unwrap();
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// // every message is handled exactly once and in order, only on the new pool once the Actor moved
+    /// let ids: Vec<usize> = handled.lock().unwrap().iter().map(|(id, _)| *id).collect();
+    /// assert_eq!((0..20000).collect::<Vec<usize>>(), ids);
+    /// let pools: Vec<String> = handled.lock().unwrap().iter().map(|(_, pool)| pool.clone()).collect();
+    /// let moved = pools.iter().position(|pool| pool == "heavy").unwrap();
+    /// assert!(moved > 0 && pools[..moved].iter().all(|pool| pool == "default"));
+    /// assert!(pools[moved..].iter().all(|pool| pool == "heavy"));
+    /// let info = &actor_system.list_actors()[0];
+    /// assert_eq!(("heavy", "default"), (info.pool.as_str(), info.address.pool.as_str()));
+    /// // including the move itself
+    /// assert_eq!(20001, info.processed);
+    ///
+    /// // a stopping Actor stays where it is
+    /// assert_eq!(Err(MoveError::UnknownPool), actor_system.move_actor(aggregator.get_address(), "unknown"));
+    /// let (open, gate) = unbounded();
+    /// aggregator.send(Pause { gate }).unwrap();
+    /// aggregator.stop();
+    /// assert_eq!(Err(MoveError::Stopping), actor_system.move_actor(aggregator.get_address(), "default"));
+    /// open.send(()).unwrap();
+    /// assert!(aggregator.await_stop(Duration::from_secs(1)));
+    /// assert_eq!(Err(MoveError::NotFound), actor_system.move_actor(aggregator.get_address(), "default"));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn move_actor(&self, address: &ActorAddress, pool: &str) -> Result<(), MoveError> {
+        if !self.thread_pool_manager.has_pool(pool) {
+            return Err(MoveError::UnknownPool);
+        }
+        if self.thread_pool_manager.is_dedicated(address) {
+            return Err(MoveError::DedicatedThread);
+        }
+        self.state.move_actor(address, pool)
+    }
+
+    /// Replaces a running Actor with one created by `new_factory`, the address, the mailbox and everything registered for the Actor stay the same
+    ///
+    /// The replacement takes place right after the message the Actor currently handles, like a restart without a panic:
+    /// [Actor.handover](../prelude/trait.Actor.html#method.handover) and `post_stop` of the old Actor are executed, then the new one is created,
+    /// receives the handed over state through [Actor.receive_handover](../prelude/trait.Actor.html#method.receive_handover) and is started with `pre_start`.
+    /// Queued messages and messages sent in the meantime are handled by the new Actor, its restarts use `new_factory` as well
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::convert::TryInto;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::{sleep, spawn};
+    /// use std::time::Duration;
+    ///
+    /// struct Order { n: usize }
+    /// impl ActorMessage for Order {}
+    ///
+    /// struct Pricing { version: usize, handled: usize, seen: Arc<Mutex<Vec<(usize, usize)>>> }
+    /// impl Actor for Pricing {
+    ///     fn handover(&mut self) -> Option<Vec<u8>> {
+    ///         Some(self.handled.to_le_bytes().to_vec())
+    ///     }
+    ///     fn receive_handover(&mut self, state: Vec<u8>) {
+    ///         self.handled = usize::from_le_bytes(state.try_into().unwrap());
+    ///     }
+    /// }
+    /// impl Handler<Order> for Pricing {
+    ///     fn handle(&mut self, msg: Order, _context: &ActorContext<Self>) {
+    ///         self.handled += 1;
+    ///         self.seen.lock().unwrap().push((self.version, msg.n));
+    ///     }
+    /// }
+    /// struct Other {}
+    /// impl Actor for Other {}
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let shared = seen.clone();
+    /// let pricing = actor_system
+    ///     .builder()
+    ///     .spawn_fn("pricing", move |_context| Pricing { version: 1, handled: 0, seen: shared.clone() })
+    ///     .unwrap();
+    ///
+    /// // orders keep coming in while the Actor is replaced
+    /// let sender = pricing.clone();
+    /// let orders = spawn(move || {
+    ///     for n in 0..200 {
+    ///         sender.send(Order { n }).unwrap();
+    ///         sleep(Duration::from_millis(1));
+    ///     }
+    /// });
+    /// sleep(Duration::from_millis(50));
+    /// let shared = seen.clone();
+    /// let result = actor_system.replace_actor(pricing.get_address(), move |_context: ActorContext<Pricing>| {
+    ///     Pricing { version: 2, handled: 0, seen: shared.clone() }
+    /// });
+    /// assert_eq!(Ok(()), result);
+    /// orders.join().unwrap();
+    /// sleep(Duration::from_millis(100));
+    ///
+    /// // every order has been handled exactly once and in order, the new Actor took over at a single point
+    /// let seen = seen.lock().unwrap().clone();
+    /// assert_eq!((0..200).collect::<Vec<_>>(), seen.iter().map(|(_, n)| *n).collect::<Vec<_>>());
+    /// let swap = seen.iter().position(|(version, _)| *version == 2).unwrap();
+    /// assert!(swap > 0);
+    /// assert!(seen[..swap].iter().all(|(version, _)| *version == 1));
+    /// assert!(seen[swap..].iter().all(|(version, _)| *version == 2));
+    /// let handled = Arc::new(Mutex::new(0));
+    /// let result = handled.clone();
+    /// pricing.exec(move |pricing, _context| *result.lock().unwrap() = pricing.handled).unwrap();
+    /// sleep(Duration::from_millis(50));
+    /// assert_eq!(200, *handled.lock().unwrap());
+    ///
+    /// let unknown = actor_system.address_of("default", "unknown");
+    /// let factory = |_context: ActorContext<Other>| Other {};
+    /// assert_eq!(Err(ReplaceError::NotFound), actor_system.replace_actor(&unknown, factory));
+    /// assert_eq!(Err(ReplaceError::TypeMismatch), actor_system.replace_actor(pricing.get_address(), factory));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn replace_actor<A, P>(&self, address: &ActorAddress, new_factory: P) -> Result<(), ReplaceError>
+    where
+        A: Actor + UnwindSafe + 'static,
+        P: TryActorFactory<A> + Send + 'static,
+    {
+        let actor = self.resolve::<A>(address).map_err(|error| match error {
+            ResolveError::TypeMismatch => ReplaceError::TypeMismatch,
+            ResolveError::NotFound | ResolveError::WrongSystem => ReplaceError::NotFound,
+        })?;
+        actor
+            .send_priority(ReplaceActorMessage::new(Box::new(new_factory)))
+            .map_err(|_| ReplaceError::NotFound)
+    }
+
+    /// Returns the current number of worker threads of a pool and the Actors waiting for one of them
+    ///
+    /// `None` if the pool does not exist
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("small", ThreadPoolConfig::new(0, 2, 2, 1.0));
+    /// sleep(Duration::from_millis(200));
+    /// let stats = actor_system.pool_stats("small").unwrap();
+    /// assert_eq!(stats.threads, 2);
+    /// assert_eq!(stats.queued_actors, 0);
+    /// assert!(actor_system.pool_stats("unknown").is_none());
+    /// ```
+    pub fn pool_stats(&self, name: &str) -> Option<PoolStats> {
+        self.thread_pool_manager.get_stats(name)
+    }
+
+    /// Returns how backed up a pool is, to reject work at the edge of the system before it times out within the pool
+    ///
+    /// `None` if the pool does not exist. See [is_saturated](#method.is_saturated) for a single decision.
+    /// An Actor that handled its last message keeps being scheduled for a few seconds before it goes to sleep, until then it is counted like any other Actor
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Request {}
+    /// impl ActorMessage for Request {}
+    ///
+    /// struct Slow {}
+    /// impl Actor for Slow {}
+    /// impl Handler<Request> for Slow {
+    ///     fn handle(&mut self, _msg: Request, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(200));
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// actor_system.add_pool_with_config("backend", ThreadPoolConfig::new(0, 1, 1, 1.0));
+    /// sleep(Duration::from_millis(100));
+    /// let load = actor_system.pool_load("backend").unwrap();
+    /// assert_eq!((0, 0, 1), (load.queued_actors, load.busy_threads, load.threads));
+    /// assert_eq!(Duration::from_secs(0), load.queue_latency);
+    ///
+    /// // four slow Actors share the single thread
+    /// let builder = actor_system.builder().set_pool_name("backend").unwrap();
+    /// for n in 0..4 {
+    ///     let slow = builder.spawn_fn(format!("slow-{}", n), |_context| Slow {}).unwrap();
+    ///     slow.send(Request {}).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(100));
+    /// let load = actor_system.pool_load("backend").unwrap();
+    /// assert_eq!((3, 1, 1), (load.queued_actors, load.busy_threads, load.threads));
+    /// assert!(actor_system.is_saturated("backend", 3));
+    /// assert!(!actor_system.is_saturated("backend", 4));
+    ///
+    /// // the Actors that wait for the thread raise the latency
+    /// sleep(Duration::from_millis(300));
+    /// assert!(actor_system.pool_load("backend").unwrap().queue_latency >= Duration::from_millis(20));
+    ///
+    /// assert!(actor_system.pool_load("unknown").is_none());
+    /// assert!(!actor_system.is_saturated("unknown", 0));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn pool_load(&self, name: &str) -> Option<PoolLoad> {
+        self.thread_pool_manager.get_load(name)
+    }
+
+    /// `true` if every thread of the pool is busy and at least `threshold` Actors are waiting for one of them, see [pool_load](#method.pool_load)
+    ///
+    /// Always `false` for a pool that does not exist
+    pub fn is_saturated(&self, name: &str, threshold: usize) -> bool {
+        self.pool_load(name)
+            .is_some_and(|load| load.busy_threads >= load.threads && load.queued_actors >= threshold)
+    }
+
+    /// Renders the metrics of all Actors and pools in the Prometheus text exposition format
+    ///
+    /// Actor series are labeled with `system`, `pool` and `actor`, or only summed up per pool if `general.metrics_per_actor` is disabled.
+    /// The series of an Actor ends once it stopped, while the counters of a pool keep the counts of its stopped Actors and never decrease.
+    /// Values are collected the same way as [list_actors](#method.list_actors) and [pool_stats](#method.pool_stats), no lock is held while rendering.
+    /// Requires the `metrics` feature
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Ping> for Worker {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// fn value(metrics: &str, series: &str) -> u64 {
+    ///     let line = metrics.lines().find(|line| line.starts_with(series)).unwrap();
+    ///     line[series.len()..].trim().parse().unwrap()
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("prometheus");
+    /// let actor_system = ActorSystem::new(config);
+    /// let first = actor_system.builder().spawn("first", WorkerFactory {}).unwrap();
+    /// let quoted = actor_system.builder().spawn("say \"hi\"", WorkerFactory {}).unwrap();
+    /// for _ in 0..7 {
+    ///     first.send(Ping {}).unwrap();
+    /// }
+    /// for _ in 0..3 {
+    ///     quoted.send(Ping {}).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// let metrics = actor_system.render_prometheus();
+    /// assert!(metrics.contains("# TYPE tyractorsaur_messages_handled_total counter"));
+    /// assert_eq!(7, value(&metrics, "tyractorsaur_messages_handled_total{system=\"prometheus\",pool=\"default\",actor=\"first\"}"));
+    /// assert_eq!(3, value(&metrics, "tyractorsaur_messages_handled_total{system=\"prometheus\",pool=\"default\",actor=\"say \\\"hi\\\"\"}"));
+    /// assert_eq!(0, value(&metrics, "tyractorsaur_mailbox_depth{system=\"prometheus\",pool=\"default\",actor=\"first\"}"));
+    /// assert_eq!(0, value(&metrics, "tyractorsaur_actor_restarts_total{system=\"prometheus\",pool=\"default\",actor=\"first\"}"));
+    /// assert!(value(&metrics, "tyractorsaur_pool_threads{system=\"prometheus\",pool=\"default\"}") > 0);
+    /// // idle Actors only go to sleep after a few seconds
+    /// assert_eq!(2, value(&metrics, "tyractorsaur_actors{system=\"prometheus\",state=\"active\"}"));
+    /// assert_eq!(0, value(&metrics, "tyractorsaur_actors{system=\"prometheus\",state=\"sleeping\"}"));
+    /// actor_system.stop(Duration::from_secs(1));
+    ///
+    /// // aggregated per pool
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.name = String::from("prometheus-per-pool");
+    /// config.general.metrics_per_actor = false;
+    /// let actor_system = ActorSystem::new(config);
+    /// let first = actor_system.builder().spawn("first", WorkerFactory {}).unwrap();
+    /// let second = actor_system.builder().spawn("second", WorkerFactory {}).unwrap();
+    /// for _ in 0..5 {
+    ///     first.send(Ping {}).unwrap();
+    ///     second.send(Ping {}).unwrap();
+    /// }
+    /// sleep(Duration::from_millis(300));
+    ///
+    /// let metrics = actor_system.render_prometheus();
+    /// assert!(!metrics.contains("actor=\""));
+    /// assert_eq!(10, value(&metrics, "tyractorsaur_messages_handled_total{system=\"prometheus-per-pool\",pool=\"default\"}"));
+    ///
+    /// // the counts of a stopped Actor stay part of its pool, including the stop message it handled last
+    /// first.stop();
+    /// assert!(first.await_stop(Duration::from_secs(1)));
+    /// let metrics = actor_system.render_prometheus();
+    /// assert_eq!(11, value(&metrics, "tyractorsaur_messages_handled_total{system=\"prometheus-per-pool\",pool=\"default\"}"));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn render_prometheus(&self) -> String {
+        let mut pools: Vec<String> = self.thread_pool_manager.get_pool_configs().into_iter().map(|(name, _)| name).collect();
+        pools.sort();
+        let (actors, retired) = self.state.with_retired_totals(|| self.list_actors());
+        let snapshot = MetricsSnapshot {
+            system: self.name.clone(),
+            actors,
+            retired,
+            pools: pools
+                .into_iter()
+                .filter_map(|pool| self.thread_pool_manager.get_stats(&pool).map(|stats| (pool, stats)))
+                .collect(),
+            dead_letters: self.dead_letter_count(),
+            per_actor: self.config.general.metrics_per_actor,
+        };
+        snapshot.render()
+    }
+
+    /// Returns the utilization, scheduling slots and Actor migrations of each worker thread of a pool for the last `general.worker_stats_interval`
+    ///
+    /// Per Actor migrations are available through [ActorWrapper.migration_count](../prelude/struct.ActorWrapper.html#method.migration_count).
+    /// `None` if the pool does not exist
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Work {}
+    /// impl ActorMessage for Work {}
+    /// struct Loop {}
+    /// impl ActorMessage for Loop {}
+    ///
+    /// fn spin() {
+    ///     let start = Instant::now();
+    ///     while start.elapsed() < Duration::from_micros(300) {}
+    /// }
+    ///
+    /// struct Busy { context: ActorContext<Self> }
+    /// impl Actor for Busy {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Work> for Busy {
+    ///     fn handle(&mut self, _msg: Work, _context: &ActorContext<Self>) {
+    ///         spin();
+    ///     }
+    /// }
+    /// impl Handler<Loop> for Busy {
+    ///     fn handle(&mut self, _msg: Loop, context: &ActorContext<Self>) {
+    ///         spin();
+    ///         context.actor_ref.send(Loop {});
+    ///     }
+    /// }
+    /// struct BusyFactory {}
+    /// impl ActorFactory<Busy> for BusyFactory {
+    ///     fn new_actor(&self, context: ActorContext<Busy>) -> Busy {
+    ///         Busy { context }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.worker_stats_interval = Duration::from_millis(200);
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.add_pool_with_config("affine", ThreadPoolConfig::new(0, 2, 2, 1.0).with_soft_affinity(true));
+    /// actor_system.add_pool_with_config("skewed", ThreadPoolConfig::new(0, 2, 2, 1.0).with_soft_affinity(true));
+    /// sleep(Duration::from_millis(1100));
+    ///
+    /// let spawn = |pool: &str, name: &str| {
+    ///     actor_system.builder().set_pool_name(pool).unwrap().spawn(format!("{}-{}", pool, name), BusyFactory {}).unwrap()
+    /// };
+    /// let affine = vec![spawn("affine", "first"), spawn("affine", "second")];
+    /// for _ in 0..200 {
+    ///     for actor in affine.iter() {
+    ///         actor.send(Work {});
+    ///     }
+    ///     sleep(Duration::from_millis(2));
+    /// }
+    ///
+    /// let stats = actor_system.pool_worker_stats("affine").unwrap();
+    /// assert_eq!(2, stats.workers.len());
+    /// assert!(stats.utilization_skew() < 0.5);
+    /// let migrations: usize = affine.iter().map(|actor| actor.migration_count()).sum();
+    /// assert!(migrations <= 1);
+    ///
+    /// // a single Actor that never runs out of work keeps its worker, the other worker idles
+    /// let single = spawn("skewed", "single");
+    /// single.send(Loop {});
+    /// sleep(Duration::from_millis(500));
+    /// let stats = actor_system.pool_worker_stats("skewed").unwrap();
+    /// assert!(stats.utilization_skew() > 0.5);
+    /// assert_eq!(0.0, stats.migrations_per_second());
+    /// assert_eq!(1, single.recent_workers().len());
+    /// assert!(actor_system.pool_worker_stats("unknown").is_none());
+    /// ```
+    pub fn pool_worker_stats(&self, pool: &str) -> Option<PoolWorkerStats> {
+        self.thread_pool_manager.get_worker_stats(pool)
+    }
+
+    /// Hands the pending work of an Actor over to another running Actor of the same type
+    ///
+    /// All clones of the source [ActorWrapper](../prelude/struct.ActorWrapper.html) are redirected to the target, and the source Actor is stopped.
+    /// Messages are handled exactly once and everything queued in the source Actor is handled before anything sent afterwards.
+    ///
+    /// - [HandoffMode::DrainThenRedirect](../prelude/enum.HandoffMode.html#variant.DrainThenRedirect) blocks until the source Actor has handled its queue
+    /// - [HandoffMode::TransferQueue](../prelude/enum.HandoffMode.html#variant.TransferQueue) moves the queue to the front of the target mailbox, a message that is being handled at that moment still completes on the source Actor
+    ///
+    /// If the target Actor stops during the handoff, the redirect is rolled back and the source Actor keeps running with the messages that have been held back.
+    /// Moved messages never wait for room: beyond the capacity of a bounded mailbox they follow its [overflow policy](../prelude/enum.MailboxOverflowPolicy.html) and are reported as dead letters
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Job { seq: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { handled: Arc<Mutex<Vec<usize>>> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         self.handled.lock().unwrap().push(msg.seq);
+    ///         sleep(Duration::from_millis(2));
+    ///     }
+    /// }
+    ///
+    /// struct WorkerFactory { handled: Arc<Mutex<Vec<usize>>> }
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker { handled: self.handled.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// for mode in [HandoffMode::DrainThenRedirect, HandoffMode::TransferQueue].iter() {
+    ///     let handled = Arc::new(Mutex::new(Vec::new()));
+    ///     let a = actor_system.builder().spawn(format!("a-{:?}", mode), WorkerFactory { handled: handled.clone() }).unwrap();
+    ///     let b = actor_system.builder().spawn(format!("b-{:?}", mode), WorkerFactory { handled: handled.clone() }).unwrap();
+    ///     for seq in 0..50 {
+    ///         a.send(Job { seq });
+    ///     }
+    ///     let producer = a.clone();
+    ///     let load = std::thread::spawn(move || {
+    ///         for seq in 50..100 {
+    ///             producer.send(Job { seq });
+    ///             sleep(Duration::from_micros(200));
+    ///         }
+    ///     });
+    ///     let report = actor_system.handoff(&a, &b, *mode).unwrap();
+    ///     assert!(report.drained + report.moved > 0);
+    ///     load.join().unwrap();
+    ///     sleep(Duration::from_millis(500));
+    ///     // every message exactly once and in send order across both Actors
+    ///     assert_eq!((0..100).collect::<Vec<usize>>(), *handled.lock().unwrap());
+    /// }
+    ///
+    /// // the target stops during the handoff, the source keeps all of its work
+    /// let handled = Arc::new(Mutex::new(Vec::new()));
+    /// let a = actor_system.builder().spawn("a", WorkerFactory { handled: handled.clone() }).unwrap();
+    /// let b = actor_system.builder().spawn("b", WorkerFactory { handled: Arc::new(Mutex::new(Vec::new())) }).unwrap();
+    /// for seq in 0..50 {
+    ///     a.send(Job { seq });
+    /// }
+    /// let target = b.clone();
+    /// std::thread::spawn(move || {
+    ///     sleep(Duration::from_millis(20));
+    ///     target.stop();
+    /// });
+    /// let system = actor_system.clone();
+    /// let (source, target) = (a.clone(), b.clone());
+    /// let handoff = std::thread::spawn(move || system.handoff(&source, &target, HandoffMode::DrainThenRedirect));
+    /// sleep(Duration::from_millis(40));
+    /// for seq in 50..60 {
+    ///     a.send(Job { seq });
+    /// }
+    /// assert_eq!(Err(HandoffError::TargetStopped), handoff.join().unwrap());
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!((0..60).collect::<Vec<usize>>(), *handled.lock().unwrap());
+    /// ```
+    pub fn handoff<A>(&self, from: &ActorWrapper<A>, to: &ActorWrapper<A>, mode: HandoffMode) -> Result<HandoffReport, HandoffError>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        handoff::handoff(&self.state, from, to, mode)
+    }
+
+    /// Starts a [Transaction](../prelude/struct.Transaction.html) that updates all of its participants or none of them
+    ///
+    /// Participants implement [TransactionalHandler](../prelude/trait.TransactionalHandler.html) for the message they receive
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::collections::HashMap;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Debit { amount: i64 }
+    /// impl ActorMessage for Debit {}
+    /// struct Credit { amount: i64 }
+    /// impl ActorMessage for Credit {}
+    ///
+    /// // survives restarts of the wallet, like a database would
+    /// #[derive(Default)]
+    /// struct Ledger {
+    ///     balance: i64,
+    ///     reserved: HashMap<u64, i64>,
+    ///     crash_next_commit: bool,
+    /// }
+    ///
+    /// struct Wallet { ledger: Arc<Mutex<Ledger>> }
+    /// impl Actor for Wallet {}
+    /// impl Wallet {
+    ///     fn reserve(&self, txn_id: u64, delta: i64) -> PrepareVote {
+    ///         let mut ledger = self.ledger.lock().unwrap();
+    ///         let debits: i64 = ledger.reserved.values().filter(|reserved| **reserved < 0).sum();
+    ///         if ledger.balance + debits + delta.min(0) < 0 {
+    ///             return PrepareVote::No;
+    ///         }
+    ///         ledger.reserved.insert(txn_id, delta);
+    ///         PrepareVote::Yes
+    ///     }
+    ///     fn apply(&self, txn_id: u64) {
+    ///         let crash = std::mem::replace(&mut self.ledger.lock().unwrap().crash_next_commit, false);
+    ///         if crash {
+    ///             panic!("crashed between prepare and commit");
+    ///         }
+    ///         let mut ledger = self.ledger.lock().unwrap();
+    ///         if let Some(delta) = ledger.reserved.remove(&txn_id) {
+    ///             ledger.balance += delta;
+    ///         }
+    ///     }
+    ///     fn release(&self, txn_id: u64) {
+    ///         self.ledger.lock().unwrap().reserved.remove(&txn_id);
+    ///     }
+    /// }
+    /// impl TransactionalHandler<Debit> for Wallet {
+    ///     fn prepare(&mut self, txn_id: u64, msg: Debit, _context: &ActorContext<Self>) -> PrepareVote {
+    ///         self.reserve(txn_id, -msg.amount)
+    ///     }
+    ///     fn commit(&mut self, txn_id: u64) {
+    ///         self.apply(txn_id);
+    ///     }
+    ///     fn abort(&mut self, txn_id: u64) {
+    ///         self.release(txn_id);
+    ///     }
+    /// }
+    /// impl TransactionalHandler<Credit> for Wallet {
+    ///     fn prepare(&mut self, txn_id: u64, msg: Credit, _context: &ActorContext<Self>) -> PrepareVote {
+    ///         self.reserve(txn_id, msg.amount)
+    ///     }
+    ///     fn commit(&mut self, txn_id: u64) {
+    ///         self.apply(txn_id);
+    ///     }
+    ///     fn abort(&mut self, txn_id: u64) {
+    ///         self.release(txn_id);
+    ///     }
+    /// }
+    /// struct WalletFactory { ledger: Arc<Mutex<Ledger>> }
+    /// impl ActorFactory<Wallet> for WalletFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Wallet>) -> Wallet {
+    ///         Wallet { ledger: self.ledger.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let ledger_a = Arc::new(Mutex::new(Ledger { balance: 100, ..Ledger::default() }));
+    /// let ledger_b = Arc::new(Mutex::new(Ledger::default()));
+    /// let a = actor_system.builder().spawn("wallet-a", WalletFactory { ledger: ledger_a.clone() }).unwrap();
+    /// let b = actor_system.builder().spawn("wallet-b", WalletFactory { ledger: ledger_b.clone() }).unwrap();
+    /// let balances = || (ledger_a.lock().unwrap().balance, ledger_b.lock().unwrap().balance);
+    /// let timeout = Duration::from_secs(2);
+    /// let transfer = |amount: i64| {
+    ///     actor_system
+    ///         .transaction()
+    ///         .participant(&a, Debit { amount })
+    ///         .participant(&b, Credit { amount })
+    ///         .execute(timeout)
+    /// };
+    ///
+    /// assert!(transfer(30).is_committed());
+    /// assert_eq!((70, 30), balances());
+    ///
+    /// // wallet a votes no, the credit reserved by wallet b is aborted
+    /// let outcome = transfer(500);
+    /// assert_eq!(TxnDecision::Abort, outcome.decision);
+    /// let votes: Vec<Option<PrepareVote>> = outcome.votes.iter().map(|(_, vote)| *vote).collect();
+    /// assert_eq!(vec![Some(PrepareVote::No), Some(PrepareVote::Yes)], votes);
+    /// assert_eq!((70, 30), balances());
+    /// assert!(ledger_b.lock().unwrap().reserved.is_empty());
+    ///
+    /// // the restarted wallet b receives the commit again
+    /// ledger_b.lock().unwrap().crash_next_commit = true;
+    /// let outcome = transfer(20);
+    /// assert!(outcome.is_committed());
+    /// assert!(outcome.stuck.is_empty());
+    /// assert!(outcome.abandoned.is_empty());
+    /// assert_eq!((50, 50), balances());
+    ///
+    /// // concurrent transfers are serialized by the mailbox of wallet a
+    /// let transfers: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let (actor_system, a, b) = (actor_system.clone(), a.clone(), b.clone());
+    ///         std::thread::spawn(move || {
+    ///             actor_system
+    ///                 .transaction()
+    ///                 .participant(&a, Debit { amount: 20 })
+    ///                 .participant(&b, Credit { amount: 20 })
+    ///                 .execute(timeout)
+    ///         })
+    ///     })
+    ///     .collect();
+    /// let committed = transfers
+    ///     .into_iter()
+    ///     .map(|transfer| transfer.join().unwrap())
+    ///     .filter(TxnOutcome::is_committed)
+    ///     .count();
+    /// assert_eq!(2, committed);
+    /// assert_eq!((10, 90), balances());
+    /// ```
+    pub fn transaction(&self) -> Transaction {
+        Transaction::new(self.state.clone(), self.wakeup_manager.clone())
+    }
+
+    /// Forwards all messages of `receiver` to `target`, but only as fast as `target` handles them
+    ///
+    /// The target can not take further messages while its mailbox is full, its [pressure](../prelude/struct.ActorWrapper.html#method.pressure) is high or more than [max_in_flight](../prelude/struct.IngestConfig.html#structfield.max_in_flight) messages are queued.
+    /// In that case the ingestion either stops pulling, so that a bounded `receiver` blocks its senders, or drops the messages, see [IngestOverflow](../prelude/enum.IngestOverflow.html).
+    ///
+    /// All ingestions of a system share a single thread, they end once the channel disconnected, the target stopped, the ingestion has been cancelled or the system stopped, see [IngestShutdown](../prelude/enum.IngestShutdown.html).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::bounded;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Item;
+    /// impl ActorMessage for Item {}
+    ///
+    /// struct Slow { handled: Arc<AtomicUsize>, context: ActorContext<Self> }
+    /// impl Actor for Slow {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Item> for Slow {
+    ///     fn handle(&mut self, _msg: Item, _context: &ActorContext<Self>) {
+    ///         sleep(Duration::from_millis(10));
+    ///         self.handled.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    /// struct SlowFactory { handled: Arc<AtomicUsize> }
+    /// impl ActorFactory<Slow> for SlowFactory {
+    ///     fn new_actor(&self, context: ActorContext<Slow>) -> Slow {
+    ///         Slow { handled: self.handled.clone(), context }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let handled = Arc::new(AtomicUsize::new(0));
+    /// let slow = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(5)
+    ///     .spawn("slow", SlowFactory { handled: handled.clone() })
+    ///     .unwrap();
+    ///
+    /// // the producer is slowed down to the pace of the actor
+    /// let (sender, receiver) = bounded(10);
+    /// let producer = std::thread::spawn(move || (0..100).take_while(|_| sender.send(Item).is_ok()).count());
+    /// let ingestion = actor_system.ingest_channel(receiver, &slow, IngestConfig::new());
+    /// sleep(Duration::from_millis(300));
+    /// let stats = ingestion.stats();
+    /// assert_eq!(10, stats.channel_len);
+    /// assert_eq!(Some(10), stats.channel_capacity);
+    /// assert!(slow.mailbox_len() <= 5);
+    /// assert!(stats.stalled > Duration::from_millis(0));
+    /// assert!(stats.forwarded < 100);
+    ///
+    /// ingestion.cancel();
+    /// sleep(Duration::from_millis(50));
+    /// assert!(ingestion.is_finished());
+    /// let forwarded = ingestion.stats().forwarded;
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(forwarded, ingestion.stats().forwarded);
+    /// // the receiver has been dropped together with the ingestion
+    /// assert!(producer.join().unwrap() < 100);
+    ///
+    /// // a draining ingestion forwards everything before the actor receives the system stop
+    /// let drained = Arc::new(AtomicUsize::new(0));
+    /// let drain = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(5)
+    ///     .spawn("drain", SlowFactory { handled: drained.clone() })
+    ///     .unwrap();
+    /// let (sender, receiver) = bounded(40);
+    /// (0..40).for_each(|_| sender.send(Item).unwrap());
+    /// let config = IngestConfig::new().with_shutdown(IngestShutdown::Drain(Duration::from_secs(5)));
+    /// let ingestion = actor_system.ingest_channel(receiver, &drain, config);
+    /// actor_system.stop(Duration::from_secs(10));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// assert_eq!(40, drained.load(Ordering::SeqCst));
+    /// assert_eq!(40, ingestion.stats().forwarded);
+    /// assert!(ingestion.is_finished());
+    /// ```
+    pub fn ingest_channel<M, A>(&self, receiver: Receiver<M>, target: &ActorWrapper<A>, config: IngestConfig) -> IngestHandle
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        self.ingestion_reactor.ingest(&self.state, receiver, target, config)
+    }
+
+    /// Forwards every item of `receiver` to `target` in order, with the same semantics as [ActorWrapper.send](../prelude/struct.ActorWrapper.html#method.send)
+    ///
+    /// Pipes are polled by the same thread as [ingest_channel](#method.ingest_channel), but follow the mailbox of their target instead of its pressure:
+    /// a full mailbox holds the pipe back if the [overflow policy](../prelude/enum.MailboxOverflowPolicy.html) of the target blocks its senders, otherwise the policy decides what happens to the item.
+    /// Items the policy dropped are counted as dropped, not as forwarded.
+    ///
+    /// The pipe ends once all senders of the channel have been dropped and everything that was left has been forwarded, the target stopped, the system is stopping or [PipeHandle.cancel](../prelude/struct.PipeHandle.html#method.cancel) has been called
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use crossbeam_channel::{bounded, unbounded, Sender};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// struct Job { id: usize }
+    /// impl ActorMessage for Job {}
+    ///
+    /// struct Worker { handled: Sender<usize> }
+    /// impl Actor for Worker {}
+    /// impl Handler<Job> for Worker {
+    ///     fn handle(&mut self, msg: Job, _context: &ActorContext<Self>) {
+    ///         let _ = self.handled.send(msg.id);
+    ///     }
+    /// }
+    ///
+    /// fn wait_until(condition: impl Fn() -> bool) {
+    ///     let deadline = Instant::now() + Duration::from_secs(3);
+    ///     while !condition() {
+    ///         assert!(Instant::now() < deadline);
+    ///         std::thread::sleep(Duration::from_millis(1));
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let (handled, handled_ids) = unbounded();
+    /// let worker = actor_system
+    ///     .builder()
+    ///     .spawn_fn("worker", move |_context| Worker { handled: handled.clone() })
+    ///     .unwrap();
+    ///
+    /// // items arrive in order, the pipe ends once the source disconnected
+    /// let (sender, receiver) = unbounded();
+    /// let pipe = actor_system.pipe_from_receiver(receiver, worker.clone());
+    /// for id in 0..100 {
+    ///     sender.send(Job { id }).unwrap();
+    /// }
+    /// drop(sender);
+    /// assert_eq!(Some(PipeEndReason::Disconnected), pipe.await_end(Duration::from_secs(1)));
+    /// assert_eq!(100, pipe.forwarded());
+    /// let ids: Vec<usize> = (0..100).map(|_| handled_ids.recv_timeout(Duration::from_secs(1)).unwrap()).collect();
+    /// assert_eq!((0..100).collect::<Vec<_>>(), ids);
+    ///
+    /// // a pipe that waits for room in a full mailbox is cancelled promptly, the waiting item is dropped
+    /// let suspended = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .start_suspended(true)
+    ///     .spawn_fn("suspended", |_context| Worker { handled: unbounded().0 })
+    ///     .unwrap();
+    /// let (sender, receiver) = bounded(10);
+    /// let pipe = actor_system.pipe_from_receiver(receiver, suspended.clone());
+    /// for id in 0..3 {
+    ///     sender.send(Job { id }).unwrap();
+    /// }
+    /// // the first item is queued, the second waits for room and the third is still in the channel
+    /// wait_until(|| pipe.forwarded() == 1 && pipe.stats().channel_len == 1);
+    /// assert_eq!(None, pipe.end_reason());
+    /// let cancelled = Instant::now();
+    /// pipe.cancel();
+    /// assert_eq!(Some(PipeEndReason::Cancelled), pipe.await_end(Duration::from_secs(1)));
+    /// assert!(cancelled.elapsed() < Duration::from_millis(100));
+    /// assert_eq!(1, pipe.dropped());
+    ///
+    /// // items the overflow policy drops are not counted as forwarded
+    /// let dropping = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(1)
+    ///     .set_overflow_policy(MailboxOverflowPolicy::DropNewest)
+    ///     .start_suspended(true)
+    ///     .spawn_fn("dropping", |_context| Worker { handled: unbounded().0 })
+    ///     .unwrap();
+    /// let (sender, receiver) = unbounded();
+    /// let pipe = actor_system.pipe_from_receiver(receiver, dropping.clone());
+    /// for id in 0..3 {
+    ///     sender.send(Job { id }).unwrap();
+    /// }
+    /// drop(sender);
+    /// assert_eq!(Some(PipeEndReason::Disconnected), pipe.await_end(Duration::from_secs(1)));
+    /// assert_eq!((1, 2), (pipe.forwarded(), pipe.dropped()));
+    ///
+    /// // a stopped target ends the pipe, even though its source is still connected
+    /// let (sender, receiver) = unbounded::<Job>();
+    /// let pipe = actor_system.pipe_from_receiver(receiver, worker.clone());
+    /// worker.stop();
+    /// assert!(worker.await_stop(Duration::from_secs(1)));
+    /// assert_eq!(Some(PipeEndReason::TargetStopped), pipe.await_end(Duration::from_secs(1)));
+    /// // the receiver has been dropped with the pipe
+    /// assert!(sender.send(Job { id: 0 }).is_err());
+    ///
+    /// // so does the stop of the system
+    /// let (_sender, receiver) = unbounded::<Job>();
+    /// let pipe = actor_system.pipe_from_receiver(receiver, suspended.clone());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(Some(PipeEndReason::SystemStopped), pipe.await_end(Duration::from_secs(1)));
+    /// ```
+    pub fn pipe_from_receiver<M, A>(&self, receiver: Receiver<M>, target: ActorWrapper<A>) -> PipeHandle
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        let (pipe, handle) = Pipe::new(receiver, target);
+        self.ingestion_reactor.register(&self.state, Box::new(pipe));
+        handle
+    }
+
+    /// Spawns a [SinkActor](../prelude/struct.SinkActor.html) named `name` that forwards every `M` into the returned [SinkReceiver](../prelude/struct.SinkReceiver.html), `None` if the Actor could not be spawned
+    ///
+    /// The receiver consumes the messages on a thread that is not part of the system, e.g. the main thread of a GUI or a library that has to be called from a specific thread.
+    /// The sink is a regular message target for messages with the [ControlFlowMessage](../prelude/trait.ControlFlowMessage.html) marker, messages of a single producer keep their order.
+    /// Stopping the sink disconnects the receiver once everything that has been forwarded is received, dropping the receiver stops the sink
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Frame { producer: usize, seq: usize }
+    /// impl ActorMessage for Frame {}
+    /// impl FallibleMessage for Frame {}
+    /// impl ControlFlowMessage for Frame {}
+    ///
+    /// struct Produce;
+    /// impl ActorMessage for Produce {}
+    ///
+    /// struct Producer { id: usize, sink: ActorWrapper<SinkActor<Frame>>, context: ActorContext<Self> }
+    /// impl Actor for Producer {
+    ///     fn on_system_stop(&mut self) {
+    ///         self.context.actor_ref.stop();
+    ///     }
+    /// }
+    /// impl Handler<Produce> for Producer {
+    ///     fn handle(&mut self, _msg: Produce, _context: &ActorContext<Self>) {
+    ///         for seq in 0..100 {
+    ///             self.sink.send(Frame { producer: self.id, seq }).unwrap();
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let timeout = Duration::from_secs(1);
+    /// let (sink, frames) = actor_system.channel_sink::<Frame>("frames").unwrap();
+    ///
+    /// // fan-in from several producers, consumed by the current thread
+    /// for id in 0..3 {
+    ///     let sink = sink.clone();
+    ///     let producer = actor_system
+    ///         .builder()
+    ///         .spawn_fn(format!("producer-{}", id), move |context| Producer { id, sink: sink.clone(), context })
+    ///         .unwrap();
+    ///     producer.send(Produce).unwrap();
+    /// }
+    /// let mut next = [0; 3];
+    /// for _ in 0..300 {
+    ///     let frame = frames.recv(timeout).unwrap();
+    ///     assert_eq!(next[frame.producer], frame.seq);
+    ///     next[frame.producer] += 1;
+    /// }
+    /// assert_eq!(Err(SinkRecvError::Empty), frames.try_recv());
+    ///
+    /// // stopping the sink disconnects the receiver after the remaining messages
+    /// sink.send(Frame { producer: 0, seq: 100 }).unwrap();
+    /// sink.stop();
+    /// let remaining: Vec<Frame> = frames.collect();
+    /// assert_eq!(vec![Frame { producer: 0, seq: 100 }], remaining);
+    ///
+    /// // dropping the receiver stops the sink, further sends are dead letters
+    /// let (sink, frames) = actor_system.channel_sink::<Frame>("other-frames").unwrap();
+    /// sink.send(Frame { producer: 0, seq: 0 }).unwrap();
+    /// assert_eq!(Ok(Frame { producer: 0, seq: 0 }), frames.recv(timeout));
+    /// drop(frames);
+    /// sleep(Duration::from_millis(100));
+    /// let dead_letters = actor_system.dead_letter_count();
+    /// assert!(sink.send(Frame { producer: 0, seq: 1 }).is_err());
+    /// assert_eq!(dead_letters + 1, actor_system.dead_letter_count());
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// assert_eq!(0, actor_system.await_shutdown());
+    /// ```
+    pub fn channel_sink<M>(&self, name: impl Into<String>) -> Option<(ActorWrapper<SinkActor<M>>, SinkReceiver<M>)>
+    where
+        M: ControlFlowMessage + 'static,
+    {
+        SinkReceiver::spawn(self.builder(), name)
+    }
+
+    /// One Actor per entity id, each spawned by the first message sent to its id through [EntityRegion.send](../prelude/struct.EntityRegion.html#method.send)
+    ///
+    /// `factory_fn` creates the Actor of an entity from its id, the Actor is named `<name>-<id>`.
+    /// Use [ActorBuilder.entity_region](../prelude/struct.ActorBuilder.html#method.entity_region) to spawn the entities with other settings, e.g. an idle timeout that stops entities that are not used
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::{Arc, Barrier};
+    /// use std::thread::{sleep, spawn};
+    /// use std::time::Duration;
+    ///
+    /// struct Order {}
+    /// impl ActorMessage for Order {}
+    ///
+    /// struct Customer {
+    ///     orders: Arc<AtomicUsize>,
+    /// }
+    /// impl Actor for Customer {}
+    /// impl Handler<Order> for Customer {
+    ///     fn handle(&mut self, _msg: Order, _context: &ActorContext<Self>) {
+    ///         self.orders.fetch_add(1, Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let spawns = Arc::new(AtomicUsize::new(0));
+    /// let orders = Arc::new(AtomicUsize::new(0));
+    /// let (counted_spawns, counted_orders) = (spawns.clone(), orders.clone());
+    /// let customers = actor_system.entity_region("customer", move |_id: &str, _context: ActorContext<Customer>| {
+    ///     counted_spawns.fetch_add(1, Ordering::Relaxed);
+    ///     Customer { orders: counted_orders.clone() }
+    /// });
+    ///
+    /// // concurrent first messages spawn the entity once
+    /// let barrier = Arc::new(Barrier::new(2));
+    /// let senders: Vec<_> = (0..2)
+    ///     .map(|_| {
+    ///         let (customers, barrier) = (customers.clone(), barrier.clone());
+    ///         spawn(move || {
+    ///             barrier.wait();
+    ///             customers.send("42", Order {}).unwrap();
+    ///         })
+    ///     })
+    ///     .collect();
+    /// senders.into_iter().for_each(|sender| sender.join().unwrap());
+    /// sleep(Duration::from_millis(100));
+    /// assert_eq!(1, spawns.load(Ordering::Relaxed));
+    /// assert_eq!(2, orders.load(Ordering::Relaxed));
+    /// assert_eq!("customer-42", customers.get("42").unwrap().get_address().actor);
+    ///
+    /// // every id gets its own Actor
+    /// customers.send("7", Order {}).unwrap();
+    /// assert_eq!(2, spawns.load(Ordering::Relaxed));
+    /// assert_ne!(customers.get("7").unwrap().get_address(), customers.get("42").unwrap().get_address());
+    /// assert!(customers.get("8").is_none());
+    ///
+    /// // idle entities stop and are spawned again by their next message
+    /// let sessions = actor_system
+    ///     .builder()
+    ///     .set_idle_timeout(Duration::from_millis(50))
+    ///     .entity_region("session", |_id: &str, _context: ActorContext<Customer>| Customer { orders: Arc::new(AtomicUsize::new(0)) });
+    /// sessions.send("1", Order {}).unwrap();
+    /// sleep(Duration::from_millis(300));
+    /// assert!(sessions.get("1").is_none());
+    /// sessions.send("1", Order {}).unwrap();
+    /// assert!(sessions.get("1").is_some());
+    ///
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn entity_region<A, F>(&self, name: impl Into<String>, factory_fn: F) -> EntityRegion<A>
+    where
+        A: Actor + UnwindSafe + 'static,
+        F: Fn(&str, ActorContext<A>) -> A + Send + Sync + 'static,
+    {
+        self.builder().entity_region(name, factory_fn)
+    }
+
+    /// Registers a header name together with its [Propagate](../prelude/enum.Propagate.html) rule
+    ///
+    /// Registering the same name again returns the same [HeaderKey](../prelude/struct.HeaderKey.html) and replaces the rule
+    ///
+    /// See [ActorWrapper.send_with_headers](../prelude/struct.ActorWrapper.html#method.send_with_headers)
+    pub fn register_header(&self, name: &str, propagate: Propagate) -> HeaderKey {
+        self.header_registry.register(name, propagate)
+    }
+
+    /// Returns all registered header names with their keys, to be exchanged with remote systems
+    pub fn header_table(&self) -> Vec<(HeaderKey, String)> {
+        self.header_registry.table()
+    }
+
+    /// Maps the header keys of a remote system onto the local keys by name, unknown names are ignored
+    pub fn header_mapping(&self, remote_table: &[(HeaderKey, String)]) -> HeaderMapping {
+        self.header_registry.mapping(remote_table)
+    }
+
+    /// Encodes headers for [SerializedMessage.headers](../prelude/struct.SerializedMessage.html#structfield.headers)
+    pub fn encode_headers(&self, headers: &Headers) -> Vec<u8> {
+        headers::encode(headers)
+    }
+
+    /// Decodes headers of a [SerializedMessage](../prelude/struct.SerializedMessage.html) sent by a remote system
+    ///
+    /// Returns `None` if the bytes are malformed
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// let local = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let trace = local.register_header("trace", Propagate::Always);
+    /// let tenant = local.register_header("tenant", Propagate::Always);
+    ///
+    /// let remote = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let remote_tenant = remote.register_header("tenant", Propagate::Always);
+    /// assert_ne!(tenant, remote_tenant);
+    ///
+    /// let headers = Headers::new()
+    ///     .with(trace, HeaderValue::Bytes(vec![1, 2, 3].into()))
+    ///     .with(tenant, HeaderValue::Str("acme".into()));
+    /// let msg = SerializedMessage::with_headers(Vec::new(), local.encode_headers(&headers));
+    ///
+    /// let mapping = remote.header_mapping(&local.header_table());
+    /// let received = remote.decode_headers(&msg.headers, &mapping).unwrap();
+    /// assert_eq!(1, received.len());
+    /// assert_eq!(Some(&HeaderValue::Str("acme".into())), received.get(remote_tenant));
+    /// assert!(remote.decode_headers(&[1, 0, 0], &mapping).is_none());
+    /// ```
+    pub fn decode_headers(&self, bytes: &[u8], mapping: &HeaderMapping) -> Option<Headers> {
+        headers::decode(bytes, mapping)
+    }
+
+    pub(crate) fn get_header_registry(&self) -> &HeaderRegistry {
+        &self.header_registry
+    }
+
+    /// Registers a serializer that is used to attach messages of type `M` to an [ActorPanicReport](../prelude/struct.ActorPanicReport.html)
+    ///
+    /// Messages are only serialized if `general.max_captured_payload_bytes` is greater than `0`, larger payloads are truncated
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::convert::TryInto;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Order { id: u64, note: String }
+    /// impl ActorMessage for Order {}
+    ///
+    /// fn serialize(order: &Order) -> Vec<u8> {
+    ///     let mut bytes = order.id.to_le_bytes().to_vec();
+    ///     bytes.extend_from_slice(order.note.as_bytes());
+    ///     bytes
+    /// }
+    /// fn deserialize(bytes: &[u8]) -> Order {
+    ///     let id = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+    ///     Order { id, note: String::from_utf8(bytes[8..].to_vec()).unwrap() }
+    /// }
+    ///
+    /// struct Shop { reports: Arc<Mutex<Vec<ActorPanicReport>>> }
+    /// impl Actor for Shop {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         self.reports.lock().unwrap().push(report);
+    ///     }
+    /// }
+    /// impl Handler<Order> for Shop {
+    ///     fn handle(&mut self, msg: Order, _context: &ActorContext<Self>) {
+    ///         if msg.id == 13 {
+    ///             panic!("unlucky order");
+    ///         }
+    ///     }
+    /// }
+    /// struct ShopFactory { reports: Arc<Mutex<Vec<ActorPanicReport>>> }
+    /// impl ActorFactory<Shop> for ShopFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Shop>) -> Shop {
+    ///         Shop { reports: self.reports.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_captured_payload_bytes = 32;
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.register_payload_serializer(serialize);
+    /// let reports = Arc::new(Mutex::new(Vec::new()));
+    /// let shop = actor_system.builder().spawn("shop", ShopFactory { reports: reports.clone() }).unwrap();
+    ///
+    /// shop.send(Order { id: 13, note: String::from("gift") });
+    /// shop.send(Order { id: 13, note: "x".repeat(100) });
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let reports = reports.lock().unwrap();
+    /// assert_eq!(2, reports.len());
+    /// assert_eq!(Some(String::from("unlucky order")), reports[0].panic_message);
+    /// let payload = reports[0].payload.as_ref().unwrap();
+    /// assert_eq!(PayloadFormat::Serialized, payload.format);
+    /// assert!(!payload.truncated);
+    /// assert_eq!(Order { id: 13, note: String::from("gift") }, deserialize(&payload.bytes));
+    ///
+    /// let oversized = reports[1].payload.as_ref().unwrap();
+    /// assert!(oversized.truncated);
+    /// assert_eq!(32, oversized.bytes.len());
+    /// ```
+    pub fn register_payload_serializer<M, F>(&self, serializer: F)
+    where
+        M: ActorMessage + 'static,
+        F: Fn(&M) -> Vec<u8> + Send + Sync + 'static,
+    {
+        self.payload_capture.register_serializer(serializer);
+    }
+
+    /// Attaches the `Debug` rendering of messages of type `M` to an [ActorPanicReport](../prelude/struct.ActorPanicReport.html), if no serializer is registered for them
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug)]
+    /// struct Ping { attempt: u8 }
+    /// impl ActorMessage for Ping {}
+    /// impl DebugCapture for Ping {}
+    ///
+    /// struct Flaky { report: Arc<Mutex<Option<ActorPanicReport>>> }
+    /// impl Actor for Flaky {
+    ///     fn on_panic(&mut self, report: ActorPanicReport) {
+    ///         *self.report.lock().unwrap() = Some(report);
+    ///     }
+    /// }
+    /// impl Handler<Ping> for Flaky {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {
+    ///         panic!();
+    ///     }
+    /// }
+    /// struct FlakyFactory { report: Arc<Mutex<Option<ActorPanicReport>>> }
+    /// impl ActorFactory<Flaky> for FlakyFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Flaky>) -> Flaky {
+    ///         Flaky { report: self.report.clone() }
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.max_captured_payload_bytes = 1024;
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.register_debug_capture::<Ping>();
+    /// let report = Arc::new(Mutex::new(None));
+    /// let flaky = actor_system.builder().spawn("flaky", FlakyFactory { report: report.clone() }).unwrap();
+    /// flaky.send(Ping { attempt: 3 });
+    /// sleep(Duration::from_millis(200));
+    ///
+    /// let report = report.lock().unwrap().clone().unwrap();
+    /// let payload = report.payload.unwrap();
+    /// assert_eq!(PayloadFormat::Debug, payload.format);
+    /// assert_eq!(b"Ping { attempt: 3 }".to_vec(), payload.bytes);
+    /// assert_eq!(report.message_type, payload.message_type);
+    /// ```
+    pub fn register_debug_capture<M>(&self)
+    where
+        M: DebugCapture + 'static,
+    {
+        self.payload_capture.register_debug::<M>();
+    }
+
+    /// Compares two captures of [ActorWrapper.capture_state](../prelude/struct.ActorWrapper.html#method.capture_state), usually of the same Actor
+    ///
+    /// Captures of [SnapshotFormat::Json](../prelude/enum.SnapshotFormat.html#variant.Json) are compared field by field, all others or invalid JSON fall back to a [ByteSummary](../prelude/struct.ByteSummary.html)
+    pub fn diff_captures(a: &StateCapture, b: &StateCapture) -> CaptureDiff {
+        state_capture::diff(a, b)
+    }
+
+    /// Stages all sends of `f` and delivers them once `f` returns, with a single wakeup per pool
+    ///
+    /// Meant for bulk injection from outside of the system, see [ActorContext.batch_sends](../prelude/struct.ActorContext.html#method.batch_sends)
+    pub fn batch_sends<'t, F>(&self, f: F) -> SendBatchReport
+    where
+        F: FnOnce(&mut SendBatch<'t>),
+    {
+        send_batch::run(f)
+    }
+
+    pub(crate) fn get_payload_capture(&self) -> &PayloadCaptureRegistry {
+        &self.payload_capture
+    }
+
+    /// Attaches a [Provenance](../prelude/struct.Provenance.html) chain to every message of type `M`
+    ///
+    /// Every Actor that handles such a message appends a hop to its chain, see [ActorContext.forward](../prelude/struct.ActorContext.html#method.forward) to pass it on.
+    /// Messages sent while it is handled start their own chain with a [DerivedFrom](../prelude/enum.ProvenanceAction.html#variant.DerivedFrom) link, if they are audited as well.
+    /// Messages of types that are not registered never carry a chain, only sends made while an audited message is handled carry the derivation link.
+    ///
+    /// See [ActorContext.provenance](../prelude/struct.ActorContext.html#method.provenance) for an example
+    pub fn register_audited<M>(&self)
+    where
+        M: Audited + 'static,
+    {
+        self.audit.register::<M>();
+    }
+
+    pub(crate) fn get_audit_registry(&self) -> &AuditRegistry {
+        &self.audit
+    }
+
+    /// Sends of messages of type `M` are never dropped by the cycle detection, see [CycleConfig](./struct.CycleConfig.html)
+    pub fn register_cycle_exempt<M>(&self)
+    where
+        M: CycleExempt + 'static,
+    {
+        if let Some(cycle_detector) = &self.cycle_detector {
+            cycle_detector.register_exempt::<M>();
+        }
+    }
+
+    /// Number of sends that have been dropped by the cycle detection
+    pub fn cycles_suspected(&self) -> u64 {
+        self.cycle_detector.as_ref().map_or(0, |cycle_detector| cycle_detector.get_suspected())
+    }
+
+    /// Sends of messages of type `M` are never checked against a message size limit or warning threshold
+    pub fn register_size_unchecked<M>(&self)
+    where
+        M: SizeUnchecked + 'static,
+    {
+        self.message_sizes.register_unchecked::<M>();
+    }
+
+    pub(crate) fn get_message_sizes(&self) -> &Arc<MessageSizes> {
+        &self.message_sizes
+    }
+
+    pub(crate) fn get_cycle_detector(&self) -> Option<&Arc<CycleDetector>> {
+        self.cycle_detector.as_ref()
+    }
+
+    /// Messages queued in all mailboxes that count towards the message budget, `0` without a budget, see [MessageBudgetConfig](./struct.MessageBudgetConfig.html)
+    pub fn total_queued_messages(&self) -> usize {
+        self.message_budget.as_ref().map_or(0, |budget| budget.get_queued())
+    }
+
+    /// Number of sends that have been shed because the system exceeded its message budget, regardless of the [SheddingPolicy](./enum.SheddingPolicy.html)
+    pub fn budget_shed_count(&self) -> u64 {
+        self.message_budget.as_ref().map_or(0, |budget| budget.get_shed())
+    }
+
+    pub(crate) fn get_message_budget(&self) -> Option<&Arc<MessageBudget>> {
+        self.message_budget.as_ref()
+    }
+
+    /// Returns a reference to the [TyractorsaurConfig](../prelude/struct.TyractorsaurConfig.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use std::time::Duration;
+    /// use std::process::exit;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// let conf = actor_system.get_config();
+    /// ```
+    pub fn get_config(&self) -> &TyractorsaurConfig {
+        &self.config
+    }
+
+    pub(crate) fn get_state(&self) -> &SystemState {
+        &self.state
+    }
+
+    /// Returns the [EventBus](../prelude/struct.EventBus.html) of the system to publish messages by topic or by message type
+    pub fn get_event_bus(&self) -> &EventBus {
+        self.state.get_event_bus()
+    }
+
+    /// Delivers a [DeadLetter](../prelude/struct.DeadLetter.html) to the Actor for every message that is sent to a stopped Actor, replaces an earlier subscription of the same Actor
+    ///
+    /// Sends through [send_to_address](#method.send_to_address) to an unknown address are dead letters as well.
+    /// Dead letters are counted even without a subscriber and printed if `general.log_dead_letters` is set.
+    /// Stopped subscribers are dropped with their first dead letter, dead letters of subscribers themselves are only counted
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// struct Ping {}
+    /// impl ActorMessage for Ping {}
+    ///
+    /// struct Pong {}
+    /// impl Actor for Pong {}
+    /// impl Handler<Ping> for Pong {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct PongFactory {}
+    /// impl ActorFactory<Pong> for PongFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Pong>) -> Pong {
+    ///         Pong {}
+    ///     }
+    /// }
+    ///
+    /// struct Collector { letters: Arc<Mutex<Vec<DeadLetter>>> }
+    /// impl Actor for Collector {}
+    /// impl Handler<DeadLetter> for Collector {
+    ///     fn handle(&mut self, msg: DeadLetter, _context: &ActorContext<Self>) {
+    ///         self.letters.lock().unwrap().push(msg);
+    ///     }
+    /// }
+    /// struct CollectorFactory { letters: Arc<Mutex<Vec<DeadLetter>>> }
+    /// impl ActorFactory<Collector> for CollectorFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Collector>) -> Collector {
+    ///         Collector { letters: self.letters.clone() }
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let letters = Arc::new(Mutex::new(Vec::new()));
+    /// let collector = actor_system.builder().spawn("collector", CollectorFactory { letters: letters.clone() }).unwrap();
+    /// actor_system.subscribe_dead_letters(&collector);
+    /// let pong = actor_system.builder().spawn("pong", PongFactory {}).unwrap();
+    /// pong.send(Ping {}).unwrap();
+    /// pong.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(0, actor_system.dead_letter_count());
+    ///
+    /// assert!(pong.send(Ping {}).is_err());
+    /// sleep(Duration::from_millis(200));
+    /// {
+    ///     let letters = letters.lock().unwrap();
+    ///     assert_eq!(1, letters.len());
+    ///     assert_eq!(pong.get_address(), &letters[0].target);
+    ///     assert!(letters[0].type_name.ends_with("Ping"));
+    /// }
+    /// assert_eq!(1, actor_system.dead_letter_count());
+    ///
+    /// // a stopped subscriber is dropped instead of being notified about its own dead letters
+    /// collector.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert!(pong.send(Ping {}).is_err());
+    /// assert!(pong.send(Ping {}).is_err());
+    /// assert_eq!(4, actor_system.dead_letter_count());
+    /// assert_eq!(1, letters.lock().unwrap().len());
+    /// assert!(!actor_system.unsubscribe_dead_letters(collector.get_address()));
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn subscribe_dead_letters<A>(&self, subscriber: &ActorWrapper<A>)
+    where
+        A: Actor + Handler<DeadLetter> + UnwindSafe + 'static,
+    {
+        self.state.get_dead_letters().subscribe(subscriber);
+    }
+
+    pub(crate) fn report_dead_letter(&self, target: &ActorAddress, type_name: &'static str) {
+        self.state.get_dead_letters().report(target, type_name);
+    }
+
+    /// Ends the subscription of [subscribe_dead_letters](#method.subscribe_dead_letters), `false` if the Actor was not subscribed
+    pub fn unsubscribe_dead_letters(&self, address: &ActorAddress) -> bool {
+        self.state.get_dead_letters().unsubscribe(address)
+    }
+
+    /// Amount of messages sent to stopped Actors since the system has been created
+    pub fn dead_letter_count(&self) -> u64 {
+        self.state.get_dead_letters().get_count()
+    }
+
+    /// Replaces the [DropReporter](../prelude/trait.DropReporter.html), which defaults to none, or to a [LogDropReporter](../prelude/struct.LogDropReporter.html) if `general.log_drops_every` is set
+    ///
+    /// Every dead letter is a drop, as are messages dropped by an overflow policy or shed under load without being reported as dead letter.
+    /// Drops are counted per reason and Actor either way, the reporter only receives the sampled ones
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// struct Sample { id: usize }
+    /// impl ActorMessage for Sample {}
+    ///
+    /// struct Sink {}
+    /// impl Actor for Sink {}
+    /// impl Handler<Sample> for Sink {
+    ///     fn handle(&mut self, _msg: Sample, _context: &ActorContext<Self>) {}
+    /// }
+    /// struct SinkFactory {}
+    /// impl ActorFactory<Sink> for SinkFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Sink>) -> Sink {
+    ///         Sink {}
+    ///     }
+    /// }
+    ///
+    /// struct Recorder { sampled: Arc<Mutex<Vec<(DeadLetterReason, u64)>>> }
+    /// impl DropReporter for Recorder {
+    ///     fn on_drop(&self, dropped: &DroppedMessage) {
+    ///         self.sampled.lock().unwrap().push((dropped.reason, dropped.count));
+    ///     }
+    ///     fn sample_rate(&self) -> u64 {
+    ///         3
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let sampled = Arc::new(Mutex::new(Vec::new()));
+    /// actor_system.set_drop_reporter(Arc::new(Recorder { sampled: sampled.clone() }));
+    ///
+    /// // the suspended Actor keeps two messages, the overflow policy drops the others
+    /// let full = actor_system
+    ///     .builder()
+    ///     .set_mailbox_size(2)
+    ///     .set_overflow_policy(MailboxOverflowPolicy::DropNewest)
+    ///     .start_suspended(true)
+    ///     .spawn("full", SinkFactory {})
+    ///     .unwrap();
+    /// for id in 0..9 {
+    ///     full.send(Sample { id }).unwrap();
+    /// }
+    /// let stopped = actor_system.builder().spawn("stopped", SinkFactory {}).unwrap();
+    /// stopped.stop();
+    /// assert!(stopped.await_stop(Duration::from_secs(1)));
+    /// for id in 0..4 {
+    ///     assert!(stopped.send(Sample { id }).is_err());
+    /// }
+    ///
+    /// let stats = actor_system.drop_stats();
+    /// assert_eq!(7, stats.get(DeadLetterReason::Overflow, full.get_address()));
+    /// assert_eq!(4, stats.get(DeadLetterReason::Undeliverable, stopped.get_address()));
+    /// assert_eq!(11, stats.total());
+    /// // only the messages sent to the stopped Actor are dead letters
+    /// assert_eq!(4, actor_system.dead_letter_count());
+    ///
+    /// // the first and then every third drop of each reason and Actor
+    /// let expected = vec![
+    ///     (DeadLetterReason::Overflow, 1),
+    ///     (DeadLetterReason::Overflow, 4),
+    ///     (DeadLetterReason::Overflow, 7),
+    ///     (DeadLetterReason::Undeliverable, 1),
+    ///     (DeadLetterReason::Undeliverable, 4),
+    /// ];
+    /// assert_eq!(expected, *sampled.lock().unwrap());
+    /// actor_system.stop(Duration::from_secs(1));
+    /// ```
+    pub fn set_drop_reporter(&self, reporter: Arc<dyn DropReporter>) {
+        self.state.get_dead_letters().get_drops().set_reporter(reporter);
+    }
+
+    /// Messages dropped since the system has been created, per reason and Actor, see [set_drop_reporter](#method.set_drop_reporter)
+    pub fn drop_stats(&self) -> DropStats {
+        self.state.get_dead_letters().get_drops().snapshot()
+    }
+
+    /// Replaces the source of the memory monitor, which defaults to [cgroup_memory_sample](../prelude/fn.cgroup_memory_sample.html) on linux and to no source on other platforms
+    ///
+    /// Only used if `general.memory_pressure` is configured, `None` skips a sample
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// config.general.memory_pressure = Some(MemoryPressureConfig::new(0.8, 0.9, 0.7, Duration::from_millis(10)));
+    /// let actor_system = ActorSystem::new(config);
+    /// actor_system.set_memory_source(|| Some(MemorySample { used: 850, limit: 1000 }));
+    /// sleep(Duration::from_millis(200));
+    /// let status = actor_system.memory_status();
+    /// assert_eq!(MemoryMode::Degraded, status.mode);
+    /// assert_eq!(Some(MemorySample { used: 850, limit: 1000 }), status.last_sample);
+    /// assert_eq!(1, status.transitions);
+    /// ```
+    pub fn set_memory_source<F>(&self, source: F)
+    where
+        F: Fn() -> Option<MemorySample> + Send + Sync + 'static,
+    {
+        self.state.get_memory_pressure().set_source(Box::new(source));
+    }
+
+    pub fn memory_status(&self) -> MemoryStatus {
+        self.state.get_memory_pressure().get_status()
+    }
+
+    /// Sweeps the internal registries right away and returns the [IntegrityReport](../prelude/struct.IntegrityReport.html), which is also published on [INTEGRITY_TOPIC](../prelude/constant.INTEGRITY_TOPIC.html)
+    ///
+    /// The wakeup loop sweeps every `general.integrity_sweep_interval` by itself and reclaims entries that outlived their Actor, e.g. scheduled wakeups or activation watchers of stopped Actors.
+    /// Entries of registries keyed by address are only reclaimed once they are orphaned in two consecutive sweeps, because they might belong to an Actor that is being spawned.
+    /// Every shard is locked on its own and only briefly, Actors keep running during a sweep.
+    ///
+    /// Returns `None` if the system does not run its wakeup loop or the loop did not answer within 5 seconds
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Clone)]
+    /// struct Ping;
+    /// impl ActorMessage for Ping {}
+    ///
+    /// // panics before it replies
+    /// struct Responder {}
+    /// impl Actor for Responder {}
+    /// impl RequestHandler<Ping> for Responder {
+    ///     type Response = ();
+    ///     fn handle_request(&mut self, _request: Ping, _context: &ActorContext<Self>) -> Result<(), String> {
+    ///         panic!("no reply");
+    ///     }
+    /// }
+    /// struct ResponderFactory {}
+    /// impl ActorFactory<Responder> for ResponderFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Responder>) -> Responder {
+    ///         Responder {}
+    ///     }
+    /// }
+    ///
+    /// struct Ask;
+    /// impl ActorMessage for Ask {}
+    /// struct Client { responder: ActorWrapper<Responder>, errors: Arc<Mutex<Vec<ChainError>>> }
+    /// impl Actor for Client {}
+    /// impl Handler<Ask> for Client {
+    ///     fn handle(&mut self, _msg: Ask, context: &ActorContext<Self>) {
+    ///         context
+    ///             .request_chain()
+    ///             .then(&self.responder, Ping)
+    ///             .on_complete(|_, _client: &mut Client, _context| {})
+    ///             .on_error(|_stage, error, client: &mut Client, _context| client.errors.lock().unwrap().push(error))
+    ///             .execute(Duration::from_secs(60))
+    ///             .unwrap();
+    ///     }
+    /// }
+    /// struct ClientFactory { responder: ActorWrapper<Responder>, errors: Arc<Mutex<Vec<ChainError>>> }
+    /// impl ActorFactory<Client> for ClientFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Client>) -> Client {
+    ///         Client { responder: self.responder.clone(), errors: self.errors.clone() }
+    ///     }
+    /// }
+    ///
+    /// // subscribes to the event bus and waits for an Actor that is never spawned
+    /// struct Wait(ActorAddress);
+    /// impl ActorMessage for Wait {}
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// impl Handler<Ping> for Worker {
+    ///     fn handle(&mut self, _msg: Ping, _context: &ActorContext<Self>) {}
+    /// }
+    /// impl Handler<Wait> for Worker {
+    ///     fn handle(&mut self, msg: Wait, context: &ActorContext<Self>) {
+    ///         let _token = context.suspend_until(SuspendCondition::UntilActorStarted(msg.0));
+    ///     }
+    /// }
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// let mut config = TyractorsaurConfig::new().unwrap();
+    /// // only sweep on demand
+    /// config.general.integrity_sweep_interval = Duration::from_secs(0);
+    /// let actor_system = ActorSystem::new(config);
+    /// let responder = actor_system.builder().spawn("responder", ResponderFactory {}).unwrap();
+    /// let errors = Arc::new(Mutex::new(Vec::new()));
+    /// let factory = ClientFactory { responder, errors: errors.clone() };
+    /// let client = actor_system.builder().spawn("client", factory).unwrap();
+    ///
+    /// // the chain fails as soon as the responder panicked, long before its timeout
+    /// client.send(Ask);
+    /// sleep(Duration::from_millis(300));
+    /// assert_eq!(vec![ChainError::Dropped], *errors.lock().unwrap());
+    /// assert_eq!(0, client.active_request_chains());
+    ///
+    /// let mut missing = client.get_address().clone();
+    /// missing.actor = String::from("missing");
+    /// for round in 0..5 {
+    ///     let workers: Vec<ActorWrapper<Worker>> = (0..20)
+    ///         .map(|i| actor_system.builder().spawn(format!("worker-{}-{}", round, i), WorkerFactory {}).unwrap())
+    ///         .collect();
+    ///     for worker in &workers {
+    ///         actor_system.get_event_bus().subscribe_pattern::<Ping, _>("churn.#", worker).unwrap();
+    ///         worker.send(Wait(missing.clone()));
+    ///     }
+    ///     sleep(Duration::from_millis(100));
+    ///     for worker in &workers {
+    ///         worker.stop();
+    ///     }
+    ///     sleep(Duration::from_millis(500));
+    ///
+    ///     // the cleanup of the stopped workers left nothing behind
+    ///     let report = actor_system.sweep_integrity().unwrap();
+    ///     assert_eq!(round + 1, report.sweep);
+    ///     assert_eq!(0, report.reclaimed.total());
+    ///     assert_eq!(0, report.suspected);
+    ///     assert_eq!(2, report.retained.actor_metrics);
+    ///     assert_eq!(0, report.retained.activation_watchers);
+    ///     assert_eq!(0, report.retained.event_subscriptions);
+    /// }
+    /// assert_eq!(Some(5), actor_system.last_integrity_report().map(|report| report.sweep));
+    /// assert_eq!(IntegrityCounts::default(), actor_system.integrity_reclaimed_total());
+    /// ```
+    pub fn sweep_integrity(&self) -> Option<IntegrityReport> {
+        self.wakeup_manager.request_integrity_sweep()
+    }
+
+    /// Report of the latest integrity sweep, see [sweep_integrity](#method.sweep_integrity)
+    pub fn last_integrity_report(&self) -> Option<IntegrityReport> {
+        self.wakeup_manager.get_integrity().get_last_report()
+    }
+
+    /// Orphaned entries reclaimed by all integrity sweeps so far, per registry
+    ///
+    /// Anything but zero points to a bug in the cleanup of the registry, see [sweep_integrity](#method.sweep_integrity)
+    pub fn integrity_reclaimed_total(&self) -> IntegrityCounts {
+        self.wakeup_manager.get_integrity().get_reclaimed_total()
+    }
+
+    /// Number of times an Actor has been added to the sleeping Actors of the wakeup loop while it was listed already
+    ///
+    /// An Actor keeps a single entry across restarts, anything but zero points to a bug in the scheduling, see [ActorWrapper.empty_wakeup_count](../prelude/struct.ActorWrapper.html#method.empty_wakeup_count)
+    pub fn integrity_duplicate_entries(&self) -> usize {
+        self.wakeup_manager.get_integrity().get_duplicate_entries()
+    }
+
+    /// Returns a receiver of all [SloEvent](../prelude/enum.SloEvent.html)s emitted after subscribing
+    ///
+    /// See [ActorBuilder.set_latency_slo](../prelude/struct.ActorBuilder.html#method.set_latency_slo)
+    pub fn subscribe_slo_events(&self) -> Receiver<SloEvent> {
+        self.slo_events.subscribe()
+    }
+
+    pub(crate) fn get_slo_events(&self) -> &SloEventBus {
+        &self.slo_events
+    }
+
+    pub(crate) fn get_idle_sleep_after(&self) -> Duration {
+        self.idle_sleep_after
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn get_wakeup_manager(&self) -> &WakeupManager {
+        &self.wakeup_manager
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn get_interaction_log(&self) -> Option<&Arc<InteractionLog>> {
+        self.interactions.as_ref()
+    }
+
+    pub(crate) fn get_thread_pool_manager(&self) -> &ThreadPoolManager {
+        &self.thread_pool_manager
+    }
+
+    /// Returns the configured name of the system
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::{TyractorsaurConfig, ActorSystem, ThreadPoolConfig};
+    /// use std::time::Duration;
+    /// use std::process::exit;
+    ///
+    /// let actor_config = TyractorsaurConfig::new().unwrap();
+    /// let actor_system = ActorSystem::new(actor_config);
+    /// let name = actor_system.get_name();
+    /// ```
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Identifies this instance of the system, unique even among systems with the same name
+    ///
+    /// Consists of the start time and a random suffix and is part of the `remote` of every local [ActorAddress](./struct.ActorAddress.html)
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    ///
+    /// struct Worker {}
+    /// impl Actor for Worker {}
+    /// struct WorkerFactory {}
+    /// impl ActorFactory<Worker> for WorkerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+    ///         Worker {}
+    ///     }
+    /// }
+    ///
+    /// // both systems use the default name
+    /// let first = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let second = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// assert_ne!(first.get_incarnation(), second.get_incarnation());
+    ///
+    /// let first_worker = first.builder().spawn("worker", WorkerFactory {}).unwrap();
+    /// let second_worker = second.builder().spawn("worker", WorkerFactory {}).unwrap();
+    /// assert!(first_worker.get_address().remote.ends_with(first.get_incarnation()));
+    /// assert_ne!(first_worker.get_address(), second_worker.get_address());
+    /// ```
+    pub fn get_incarnation(&self) -> &str {
+        &self.incarnation
+    }
+
+    /// `remote` of all Actors of this system
+    pub(crate) fn get_local_remote(&self) -> String {
+        local_remote(&self.incarnation)
+    }
+}
+
+fn local_remote(incarnation: &str) -> String {
+    format!("local/{}", incarnation)
 }
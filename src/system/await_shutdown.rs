@@ -0,0 +1,25 @@
+use crate::system::system_state::SystemState;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Future returned by [ActorSystem.await_shutdown_async](./struct.ActorSystem.html#method.await_shutdown_async)
+///
+/// Resolves to the same code as [ActorSystem.await_shutdown](./struct.ActorSystem.html#method.await_shutdown)
+pub struct AwaitShutdown {
+    state: SystemState,
+}
+
+impl AwaitShutdown {
+    pub(crate) fn new(state: SystemState) -> Self {
+        Self { state }
+    }
+}
+
+impl Future for AwaitShutdown {
+    type Output = i32;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        self.state.poll_stopped(context.waker())
+    }
+}
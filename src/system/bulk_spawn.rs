@@ -0,0 +1,284 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_config::ActorDefaults;
+use crate::actor::actor_factory::{ActorInitError, TryActorFactory};
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::panic_report;
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe, UnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type ProgressCallback = Arc<dyn Fn(BulkSpawnProgress) + Send + Sync>;
+type SpawnResult<A> = Result<ActorWrapper<A>, SpawnError>;
+
+/// A single Actor of [ActorSystem.spawn_many](./struct.ActorSystem.html#method.spawn_many)
+///
+/// Tuples of `(name, factory, overrides)` convert into a spec, so that they can be passed directly
+pub struct SpawnSpec<P> {
+    pub name: String,
+    pub factory: P,
+    /// settings that are set replace the ones of the builder
+    pub overrides: ActorDefaults,
+}
+
+impl<P> SpawnSpec<P> {
+    pub fn new(name: impl Into<String>, factory: P) -> Self {
+        Self {
+            name: name.into(),
+            factory,
+            overrides: ActorDefaults::default(),
+        }
+    }
+
+    pub fn with_overrides(mut self, overrides: ActorDefaults) -> Self {
+        self.overrides = overrides;
+        self
+    }
+}
+
+impl<N, P> From<(N, P, ActorDefaults)> for SpawnSpec<P>
+where
+    N: Into<String>,
+{
+    fn from((name, factory, overrides): (N, P, ActorDefaults)) -> Self {
+        Self::new(name, factory).with_overrides(overrides)
+    }
+}
+
+/// Reason a single spec of [ActorSystem.spawn_many](./struct.ActorSystem.html#method.spawn_many) has not been spawned
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnError {
+    /// the name is used by more than one spec of the batch, none of them has been attempted
+    DuplicateName,
+    /// an Actor with the same address is already running
+    NameTaken,
+    /// the factory panicked, `reason` is `None` if the panic payload is not a string
+    FactoryPanicked { reason: Option<String> },
+    /// the factory returned an error, see [TryActorFactory](../prelude/trait.TryActorFactory.html)
+    FactoryFailed(ActorInitError),
+    /// the system is stopping, see [ActorBuilder.try_spawn](../prelude/struct.ActorBuilder.html#method.try_spawn)
+    SystemStopping,
+    /// the pool of the Actor has neither been added nor configured, see [ActorBuilder.set_pool](../prelude/struct.ActorBuilder.html#method.set_pool)
+    UnknownPool,
+    /// the dependencies of the Actor lead back to it, the cycle starts and ends with the Actor, see [ActorBuilder.depends_on](../prelude/struct.ActorBuilder.html#method.depends_on)
+    DependencyCycle(Vec<ActorAddress>),
+}
+
+/// Progress of a running [ActorSystem.spawn_many](./struct.ActorSystem.html#method.spawn_many), see [BulkSpawnConfig.with_progress](./struct.BulkSpawnConfig.html#method.with_progress)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BulkSpawnProgress {
+    pub completed: usize,
+    pub failed: usize,
+    pub total: usize,
+}
+
+/// See [ActorSystem.spawn_many](./struct.ActorSystem.html#method.spawn_many)
+#[derive(Clone)]
+pub struct BulkSpawnConfig {
+    concurrency: usize,
+    all_or_nothing: bool,
+    progress: Option<(usize, ProgressCallback)>,
+}
+
+impl BulkSpawnConfig {
+    /// Spawns one Actor after the other on the calling thread and keeps every Actor that could be spawned
+    pub fn new() -> Self {
+        Self {
+            concurrency: 1,
+            all_or_nothing: false,
+            progress: None,
+        }
+    }
+
+    /// Spawns up to `concurrency` Actors at the same time, worth it if the factories are slow
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Stops all spawned Actors again if a single spec failed
+    pub fn with_all_or_nothing(mut self, all_or_nothing: bool) -> Self {
+        self.all_or_nothing = all_or_nothing;
+        self
+    }
+
+    /// Calls `callback` after every `every` completed specs and once all of them are completed
+    ///
+    /// The callback is executed on the spawning threads, but never concurrently
+    pub fn with_progress<F>(mut self, every: usize, callback: F) -> Self
+    where
+        F: Fn(BulkSpawnProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some((every.max(1), Arc::new(callback)));
+        self
+    }
+}
+
+impl Default for BulkSpawnConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of a single spec, in the order of the specs
+pub struct SpawnOutcome<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub name: String,
+    pub result: Result<ActorWrapper<A>, SpawnError>,
+}
+
+/// Returned by [ActorSystem.spawn_many](./struct.ActorSystem.html#method.spawn_many)
+pub struct BulkSpawnReport<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// only contains the failed specs if the batch has been rolled back
+    pub outcomes: Vec<SpawnOutcome<A>>,
+    pub spawned: usize,
+    pub failed: usize,
+    /// Actors that had been spawned and were stopped again, see [BulkSpawnConfig.with_all_or_nothing](./struct.BulkSpawnConfig.html#method.with_all_or_nothing)
+    pub rolled_back: usize,
+    pub elapsed: Duration,
+}
+
+impl<A> BulkSpawnReport<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    /// All spawned Actors, in the order of the specs
+    pub fn actors(&self) -> Vec<&ActorWrapper<A>> {
+        self.outcomes.iter().filter_map(|outcome| outcome.result.as_ref().ok()).collect()
+    }
+
+    /// Names and errors of all failed specs, in the order of the specs
+    pub fn errors(&self) -> Vec<(&str, &SpawnError)> {
+        self.outcomes
+            .iter()
+            .filter_map(|outcome| outcome.result.as_ref().err().map(|error| (outcome.name.as_str(), error)))
+            .collect()
+    }
+}
+
+struct Progress {
+    /// completed and failed specs
+    state: Mutex<(usize, usize)>,
+    total: usize,
+    callback: Option<(usize, ProgressCallback)>,
+}
+
+impl Progress {
+    // is_multiple_of needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    fn complete(&self, is_failed: bool) {
+        let mut state = self.state.lock().unwrap();
+        state.0 += 1;
+        if is_failed {
+            state.1 += 1;
+        }
+        if let Some((every, callback)) = &self.callback {
+            if state.0 % *every == 0 || state.0 == self.total {
+                callback(BulkSpawnProgress {
+                    completed: state.0,
+                    failed: state.1,
+                    total: self.total,
+                });
+            }
+        }
+    }
+}
+
+pub(crate) fn spawn_many<A, P>(builder: &ActorBuilder<A>, specs: Vec<SpawnSpec<P>>, config: BulkSpawnConfig) -> BulkSpawnReport<A>
+where
+    A: Actor + UnwindSafe + 'static,
+    P: TryActorFactory<A> + Send + 'static,
+{
+    let started = Instant::now();
+    let total = specs.len();
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    for spec in specs.iter() {
+        *occurrences.entry(spec.name.clone()).or_insert(0) += 1;
+    }
+    let names: Vec<String> = specs.iter().map(|spec| spec.name.clone()).collect();
+    let results: Mutex<Vec<Option<SpawnResult<A>>>> = Mutex::new((0..total).map(|_| None).collect());
+    let mut pending = Vec::with_capacity(total);
+    let mut duplicates = 0;
+    for (index, spec) in specs.into_iter().enumerate() {
+        if occurrences[&spec.name] > 1 {
+            results.lock().unwrap()[index] = Some(Err(SpawnError::DuplicateName));
+            duplicates += 1;
+        } else {
+            pending.push((index, spec));
+        }
+    }
+
+    let progress = Progress {
+        state: Mutex::new((duplicates, duplicates)),
+        total,
+        callback: config.progress.clone(),
+    };
+    let workers = config.concurrency.min(pending.len());
+    let queue = Mutex::new(pending.into_iter());
+    let work = || loop {
+        let next = queue.lock().unwrap().next();
+        let (index, spec) = match next {
+            Some(next) => next,
+            None => return,
+        };
+        let result = spawn_one(builder, spec);
+        progress.complete(result.is_err());
+        results.lock().unwrap()[index] = Some(result);
+    };
+    if workers > 1 {
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(work);
+            }
+        });
+    } else {
+        work();
+    }
+
+    let mut outcomes: Vec<SpawnOutcome<A>> = names
+        .into_iter()
+        .zip(results.into_inner().unwrap())
+        .map(|(name, result)| SpawnOutcome {
+            name,
+            result: result.unwrap(),
+        })
+        .collect();
+    let failed = outcomes.iter().filter(|outcome| outcome.result.is_err()).count();
+    let mut spawned = total - failed;
+    let mut rolled_back = 0;
+    if config.all_or_nothing && failed > 0 {
+        for actor in outcomes.iter().filter_map(|outcome| outcome.result.as_ref().ok()) {
+            actor.stop();
+        }
+        outcomes.retain(|outcome| outcome.result.is_err());
+        rolled_back = spawned;
+        spawned = 0;
+    }
+    BulkSpawnReport {
+        outcomes,
+        spawned,
+        failed,
+        rolled_back,
+        elapsed: started.elapsed(),
+    }
+}
+
+pub(crate) fn spawn_one<A, P>(builder: &ActorBuilder<A>, spec: SpawnSpec<P>) -> SpawnResult<A>
+where
+    A: Actor + UnwindSafe + 'static,
+    P: TryActorFactory<A> + 'static,
+{
+    match catch_unwind(AssertUnwindSafe(|| builder.spawn_with_overrides(spec.name, spec.factory, spec.overrides))) {
+        Ok(result) => result,
+        Err(panic) => Err(SpawnError::FactoryPanicked {
+            reason: panic_report::panic_message(panic.as_ref()),
+        }),
+    }
+}
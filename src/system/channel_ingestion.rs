@@ -0,0 +1,395 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::actor::pressure::PressureLevel;
+use crate::message::actor_message::ActorMessage;
+use crate::system::system_state::SystemState;
+use crossbeam_channel::{select, unbounded, Receiver, Sender, TryRecvError};
+use std::any::TypeId;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Maximum amount of messages a single ingestion forwards before the next ingestion is polled
+const POLL_BUDGET: usize = 64;
+/// Pause of the reactor after a round without any progress
+const IDLE_POLL: Duration = Duration::from_millis(1);
+
+/// Defines what an ingestion does while its target can not take further messages
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IngestOverflow {
+    /// stops pulling from the channel, so that the channel applies its own backpressure upstream
+    Stall,
+    /// keeps pulling and drops the messages, see [IngestStats.dropped](./struct.IngestStats.html#structfield.dropped)
+    Drop,
+}
+
+/// Defines what an ingestion does once the system stops
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IngestShutdown {
+    /// forwards everything that is left in the channel for at most the given duration, the target does not receive the system stop before
+    Drain(Duration),
+    /// stops pulling immediately
+    Abandon,
+}
+
+/// See [ActorSystem.ingest_channel](./struct.ActorSystem.html#method.ingest_channel)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IngestConfig {
+    /// maximum amount of queued messages of the target, before the ingestion stalls
+    pub max_in_flight: Option<usize>,
+    pub on_overflow: IngestOverflow,
+    pub shutdown: IngestShutdown,
+}
+
+impl IngestConfig {
+    /// Stalls only on a full mailbox or high pressure of the target and abandons the channel once the system stops
+    pub fn new() -> Self {
+        Self {
+            max_in_flight: None,
+            on_overflow: IngestOverflow::Stall,
+            shutdown: IngestShutdown::Abandon,
+        }
+    }
+
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+
+    pub fn with_overflow(mut self, on_overflow: IngestOverflow) -> Self {
+        self.on_overflow = on_overflow;
+        self
+    }
+
+    pub fn with_shutdown(mut self, shutdown: IngestShutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counters of a single ingestion, see [IngestHandle.stats](./struct.IngestHandle.html#method.stats)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IngestStats {
+    pub forwarded: u64,
+    /// messages dropped on overflow or because the target stopped
+    pub dropped: u64,
+    /// time in which the target could not take further messages
+    pub stalled: Duration,
+    /// messages waiting in the source channel
+    pub channel_len: usize,
+    /// `None` for unbounded channels
+    pub channel_capacity: Option<usize>,
+    pub is_finished: bool,
+}
+
+/// Counters of an ingestion, shared between the reactor and its [IngestHandle]
+pub(crate) struct IngestShared {
+    forwarded: AtomicU64,
+    dropped: AtomicU64,
+    stalled_nanos: AtomicU64,
+    channel_len: AtomicUsize,
+    channel_capacity: Option<usize>,
+    is_cancelled: AtomicBool,
+    is_finished: AtomicBool,
+}
+
+impl IngestShared {
+    pub fn new<M>(receiver: &Receiver<M>) -> Arc<Self> {
+        Arc::new(Self {
+            forwarded: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            stalled_nanos: AtomicU64::new(0),
+            channel_len: AtomicUsize::new(receiver.len()),
+            channel_capacity: receiver.capacity(),
+            is_cancelled: AtomicBool::new(false),
+            is_finished: AtomicBool::new(false),
+        })
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_forwarded(&self) {
+        self.forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn increment_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Adds the time since `stalled_since` to the stalled time, `is_stalled` starts the next stall right away
+    pub fn record_stall(&self, stalled_since: &mut Option<Instant>, is_stalled: bool) {
+        let now = Instant::now();
+        if let Some(since) = stalled_since.take() {
+            let stalled = now.saturating_duration_since(since).as_nanos() as u64;
+            self.stalled_nanos.fetch_add(stalled, Ordering::Relaxed);
+        }
+        if is_stalled {
+            *stalled_since = Some(now);
+        }
+    }
+
+    pub fn set_channel_len(&self, channel_len: usize) {
+        self.channel_len.store(channel_len, Ordering::Relaxed);
+    }
+
+    pub fn set_finished(&self) {
+        self.is_finished.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Returned by [ActorSystem.ingest_channel](./struct.ActorSystem.html#method.ingest_channel), dropping it does not end the ingestion
+#[derive(Clone)]
+pub struct IngestHandle {
+    shared: Arc<IngestShared>,
+}
+
+impl IngestHandle {
+    pub(crate) fn new(shared: Arc<IngestShared>) -> Self {
+        Self { shared }
+    }
+
+    /// Stops pulling from the channel, messages that have already been forwarded are still handled
+    pub fn cancel(&self) {
+        self.shared.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// `true` once the ingestion stopped pulling, because it has been cancelled, the channel disconnected, the target stopped or the system stopped
+    ///
+    /// The receiver is dropped at that point, so that the senders of the channel disconnect
+    pub fn is_finished(&self) -> bool {
+        self.shared.is_finished.load(Ordering::Relaxed)
+    }
+
+    pub fn stats(&self) -> IngestStats {
+        IngestStats {
+            forwarded: self.shared.forwarded.load(Ordering::Relaxed),
+            dropped: self.shared.dropped.load(Ordering::Relaxed),
+            stalled: Duration::from_nanos(self.shared.stalled_nanos.load(Ordering::Relaxed)),
+            channel_len: self.shared.channel_len.load(Ordering::Relaxed),
+            channel_capacity: self.shared.channel_capacity,
+            is_finished: self.is_finished(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Poll {
+    Progress,
+    Idle,
+    Finished,
+}
+
+/// Source that is polled by the [IngestionReactor] until it returns [Poll::Finished], `finish` is called once afterwards
+pub(crate) trait Ingestion: Send {
+    fn poll(&mut self, is_system_stopping: bool) -> Poll;
+    fn finish(&mut self);
+}
+
+pub(crate) type BoxedIngestion = Box<dyn Ingestion>;
+
+struct ChannelIngestion<M, A>
+where
+    A: Actor + 'static,
+{
+    receiver: Receiver<M>,
+    target: ActorWrapper<A>,
+    config: IngestConfig,
+    shared: Arc<IngestShared>,
+    state: SystemState,
+    stalled_since: Option<Instant>,
+    drain_deadline: Option<Instant>,
+}
+
+impl<M, A> ChannelIngestion<M, A>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    fn is_target_full(&self) -> bool {
+        let mailbox = self.target.get_mailbox();
+        mailbox.get_lane(TypeId::of::<M>()).0.is_full()
+            || self.target.pressure() == PressureLevel::High
+            || self
+                .config
+                .max_in_flight
+                .is_some_and(|max_in_flight| self.target.mailbox_len() >= max_in_flight)
+    }
+
+    fn record_stall(&mut self, is_stalled: bool) {
+        self.shared.record_stall(&mut self.stalled_since, is_stalled);
+    }
+}
+
+impl<M, A> Ingestion for ChannelIngestion<M, A>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    fn poll(&mut self, is_system_stopping: bool) -> Poll {
+        if self.shared.is_cancelled() || self.target.get_mailbox().is_stopped() {
+            return Poll::Finished;
+        }
+        if is_system_stopping {
+            match self.config.shutdown {
+                IngestShutdown::Abandon => return Poll::Finished,
+                IngestShutdown::Drain(timeout) => {
+                    let deadline = *self.drain_deadline.get_or_insert_with(|| Instant::now() + timeout);
+                    if Instant::now() >= deadline {
+                        return Poll::Finished;
+                    }
+                }
+            }
+        }
+
+        let mut progress = 0;
+        let mut result = Poll::Idle;
+        while progress < POLL_BUDGET {
+            let is_full = self.is_target_full();
+            self.record_stall(is_full);
+            if is_full && self.config.on_overflow == IngestOverflow::Stall {
+                break;
+            }
+            match self.receiver.try_recv() {
+                Ok(_) if is_full => self.shared.increment_dropped(),
+                Ok(msg) => match self.target.send(msg) {
+                    Ok(()) => self.shared.increment_forwarded(),
+                    // the target stopped
+                    Err(_) => self.shared.increment_dropped(),
+                },
+                Err(TryRecvError::Empty) => {
+                    // everything that was left has been forwarded
+                    if is_system_stopping {
+                        result = Poll::Finished;
+                    }
+                    break;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    result = Poll::Finished;
+                    break;
+                }
+            }
+            progress += 1;
+        }
+        self.shared.set_channel_len(self.receiver.len());
+        if result == Poll::Idle && progress > 0 {
+            result = Poll::Progress;
+        }
+        result
+    }
+
+    fn finish(&mut self) {
+        self.record_stall(false);
+        self.shared.set_channel_len(self.receiver.len());
+        self.shared.set_finished();
+        if let IngestShutdown::Drain(_) = self.config.shutdown {
+            self.state.release_shutdown();
+        }
+    }
+}
+
+/// Single thread that polls all ingestions of a system, started with the first ingestion
+#[derive(Clone)]
+pub(crate) struct IngestionReactor {
+    incoming: Arc<Mutex<Option<Sender<BoxedIngestion>>>>,
+}
+
+impl IngestionReactor {
+    pub fn new() -> Self {
+        Self {
+            incoming: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn ingest<M, A>(
+        &self,
+        state: &SystemState,
+        receiver: Receiver<M>,
+        target: &ActorWrapper<A>,
+        config: IngestConfig,
+    ) -> IngestHandle
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        let shared = IngestShared::new(&receiver);
+        // held from the start, so that no Actor receives the system stop before the reactor noticed it
+        if let IngestShutdown::Drain(_) = config.shutdown {
+            state.hold_shutdown();
+        }
+        let ingestion = ChannelIngestion {
+            receiver,
+            target: target.clone(),
+            config,
+            shared: shared.clone(),
+            state: state.clone(),
+            stalled_since: None,
+            drain_deadline: None,
+        };
+        self.register(state, Box::new(ingestion));
+        IngestHandle { shared }
+    }
+
+    /// Hands the ingestion to the reactor thread, which is started with the first ingestion of the system
+    pub fn register(&self, state: &SystemState, ingestion: BoxedIngestion) {
+        let mut incoming = self.incoming.lock().unwrap();
+        let sender = incoming.get_or_insert_with(|| {
+            let (sender, receiver) = unbounded();
+            let reactor_state = state.clone();
+            state.add_thread(std::thread::spawn(move || run(receiver, reactor_state)));
+            sender
+        });
+        sender.send(ingestion).unwrap();
+    }
+}
+
+fn run(incoming: Receiver<BoxedIngestion>, state: SystemState) {
+    let mut ingestions: Vec<BoxedIngestion> = Vec::new();
+    loop {
+        while let Ok(ingestion) = incoming.try_recv() {
+            ingestions.push(ingestion);
+        }
+        if ingestions.is_empty() {
+            if state.is_halted() {
+                return;
+            }
+            // the halt is noticed right away instead of with the next timeout
+            select! {
+                recv(incoming) -> ingestion => {
+                    if let Ok(ingestion) = ingestion {
+                        ingestions.push(ingestion);
+                    }
+                },
+                recv(state.get_stopped()) -> _ => {},
+                default(Duration::from_millis(100)) => {},
+            }
+            continue;
+        }
+        let is_system_stopping = state.is_stopping();
+        let mut has_progress = false;
+        ingestions.retain_mut(|ingestion| match ingestion.poll(is_system_stopping) {
+            Poll::Progress => {
+                has_progress = true;
+                true
+            }
+            Poll::Idle => true,
+            Poll::Finished => {
+                ingestion.finish();
+                false
+            }
+        });
+        if !has_progress {
+            sleep(IDLE_POLL);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::control_flow::{ActorResult, ControlFlowHandler, ControlFlowMessage};
+use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::any::type_name;
+use std::panic::UnwindSafe;
+use std::time::Duration;
+
+/// Returned by [SinkReceiver.recv](./struct.SinkReceiver.html#method.recv) and [SinkReceiver.try_recv](./struct.SinkReceiver.html#method.try_recv)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SinkRecvError {
+    /// no message has been forwarded within the timeout
+    Empty,
+    /// the sink Actor stopped and every message it forwarded has been received
+    Disconnected,
+}
+
+/// Actor that forwards every `M` into the channel of its [SinkReceiver](./struct.SinkReceiver.html), see [ActorSystem.channel_sink](./struct.ActorSystem.html#method.channel_sink)
+///
+/// Messages that arrive after the receiver has been dropped are dead letters.
+/// Like for a [TestProbe](../testing/struct.TestProbe.html), a generic `Handler<M>` would conflict with the `Handler` of every system message,
+/// messages sent to a sink therefore need the [ControlFlowMessage](./trait.ControlFlowMessage.html) marker
+pub struct SinkActor<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    context: ActorContext<Self>,
+    sender: Sender<M>,
+}
+
+// the sender is shared with the factory, a restarted sink forwards into the same channel
+impl<M> UnwindSafe for SinkActor<M> where M: ControlFlowMessage + 'static {}
+
+impl<M> Actor for SinkActor<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    fn on_system_stop(&mut self) {
+        self.context.actor_ref.stop();
+    }
+}
+
+impl<M> ControlFlowHandler<M> for SinkActor<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    fn handle_flow(&mut self, msg: M, context: &ActorContext<Self>) -> ActorResult {
+        if self.sender.send(msg).is_err() {
+            context.system.report_dead_letter(context.actor_ref.get_address(), type_name::<M>());
+        }
+        ActorResult::Ok
+    }
+}
+
+/// Holds the last sender once the sink stopped, so that the channel disconnects together with the Executor
+struct SinkActorFactory<M> {
+    sender: Sender<M>,
+}
+
+impl<M> UnwindSafe for SinkActorFactory<M> {}
+
+impl<M> ActorFactory<SinkActor<M>> for SinkActorFactory<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    fn new_actor(&self, context: ActorContext<SinkActor<M>>) -> SinkActor<M> {
+        SinkActor {
+            context,
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+/// Receiving end of a [SinkActor](./struct.SinkActor.html), to consume messages on a thread that is not part of the ActorSystem
+///
+/// Messages of a single producer are received in the order they have been sent.
+/// Once the sink Actor stopped, the remaining messages are received and [SinkRecvError::Disconnected](./enum.SinkRecvError.html#variant.Disconnected) afterwards.
+/// Dropping the receiver stops the sink Actor, so that further sends fail and are reported as dead letters.
+/// Iterating blocks until the next message arrives and ends once the receiver disconnected
+pub struct SinkReceiver<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    receiver: Receiver<M>,
+    sink: ActorWrapper<SinkActor<M>>,
+}
+
+impl<M> SinkReceiver<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    pub(crate) fn spawn(builder: ActorBuilder<SinkActor<M>>, name: impl Into<String>) -> Option<(ActorWrapper<SinkActor<M>>, Self)> {
+        let (sender, receiver) = unbounded();
+        let sink = builder.spawn(name, SinkActorFactory { sender })?;
+        Some((sink.clone(), Self { receiver, sink }))
+    }
+
+    /// Blocks until the next message arrives, at most for `timeout`
+    pub fn recv(&self, timeout: Duration) -> Result<M, SinkRecvError> {
+        self.receiver.recv_timeout(timeout).map_err(|error| match error {
+            RecvTimeoutError::Timeout => SinkRecvError::Empty,
+            RecvTimeoutError::Disconnected => SinkRecvError::Disconnected,
+        })
+    }
+
+    /// Returns the next message if one has already arrived
+    pub fn try_recv(&self) -> Result<M, SinkRecvError> {
+        self.receiver.try_recv().map_err(|error| match error {
+            TryRecvError::Empty => SinkRecvError::Empty,
+            TryRecvError::Disconnected => SinkRecvError::Disconnected,
+        })
+    }
+
+    /// Messages that arrived, but have not been received yet
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+}
+
+impl<M> Iterator for SinkReceiver<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    type Item = M;
+
+    fn next(&mut self) -> Option<M> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl<M> Drop for SinkReceiver<M>
+where
+    M: ControlFlowMessage + 'static,
+{
+    fn drop(&mut self) {
+        self.sink.stop();
+    }
+}
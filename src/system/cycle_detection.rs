@@ -0,0 +1,322 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::message::actor_stop_message::ActorStopMessage;
+use crate::message::headers::{HeaderKey, HeaderValue, Headers, Reader};
+use crate::message::system_stop_message::SystemStopMessage;
+use crate::system::event_bus::EventBus;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::convert::TryInto;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Causal chain of the message, never propagated as is, every Actor that handles the message attaches the next hop to its sends
+pub(crate) const CYCLE_HEADER: (&str, HeaderKey) = ("tyractorsaur.cycle", HeaderKey(5));
+
+/// Topic of the [EventBus](./struct.EventBus.html) on which every [CycleSuspected](./struct.CycleSuspected.html) is published
+pub const CYCLE_TOPIC: &str = "system.cycle_suspected";
+
+/// Amount of Actors kept per causal chain
+const TRACE_LEN: usize = 8;
+
+/// Enables cycle detection, see `general.cycle_detection`
+///
+/// Every message handled while another message is handled continues the causal chain of the latter.
+/// Sends of an Actor that handles the `max_hops`th message of a chain within `window` are dropped and reported as [CycleSuspected](./struct.CycleSuspected.html).
+/// Sends from outside of an Actor always start a new chain
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// struct Ball { stage: usize }
+/// impl ActorMessage for Ball {}
+/// struct Resync { remaining: usize }
+/// impl ActorMessage for Resync {}
+/// impl CycleExempt for Resync {}
+///
+/// struct Player { next: Arc<Mutex<Option<ActorWrapper<Player>>>>, received: Arc<Mutex<Vec<usize>>> }
+/// impl Actor for Player {}
+/// impl Handler<Ball> for Player {
+///     fn handle(&mut self, msg: Ball, _context: &ActorContext<Self>) {
+///         self.received.lock().unwrap().push(msg.stage);
+///         if let Some(next) = self.next.lock().unwrap().as_ref() {
+///             next.send(Ball { stage: msg.stage + 1 });
+///         }
+///     }
+/// }
+/// impl Handler<Resync> for Player {
+///     fn handle(&mut self, msg: Resync, _context: &ActorContext<Self>) {
+///         self.received.lock().unwrap().push(1000 + msg.remaining);
+///         if msg.remaining > 0 {
+///             self.next.lock().unwrap().as_ref().unwrap().send(Resync { remaining: msg.remaining - 1 });
+///         }
+///     }
+/// }
+/// #[derive(Clone)]
+/// struct PlayerFactory { next: Arc<Mutex<Option<ActorWrapper<Player>>>>, received: Arc<Mutex<Vec<usize>>> }
+/// impl ActorFactory<Player> for PlayerFactory {
+///     fn new_actor(&self, _context: ActorContext<Player>) -> Player {
+///         Player { next: self.next.clone(), received: self.received.clone() }
+///     }
+/// }
+///
+/// struct Monitor { events: Arc<Mutex<Vec<CycleSuspected>>> }
+/// impl Actor for Monitor {}
+/// impl Handler<CycleSuspected> for Monitor {
+///     fn handle(&mut self, msg: CycleSuspected, _context: &ActorContext<Self>) {
+///         self.events.lock().unwrap().push(msg);
+///     }
+/// }
+/// struct MonitorFactory { events: Arc<Mutex<Vec<CycleSuspected>>> }
+/// impl ActorFactory<Monitor> for MonitorFactory {
+///     fn new_actor(&self, _context: ActorContext<Monitor>) -> Monitor {
+///         Monitor { events: self.events.clone() }
+///     }
+/// }
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.cycle_detection = Some(CycleConfig::new(16, Duration::from_secs(10)));
+/// let actor_system = ActorSystem::new(config);
+/// actor_system.register_cycle_exempt::<Resync>();
+/// let events = Arc::new(Mutex::new(Vec::new()));
+/// let monitor = actor_system.builder().spawn("monitor", MonitorFactory { events: events.clone() }).unwrap();
+/// actor_system.get_event_bus().subscribe_pattern::<CycleSuspected, _>(CYCLE_TOPIC, &monitor).unwrap();
+///
+/// // a pipeline of 10 stages stays below the limit
+/// let mut next = None;
+/// let mut pipeline = Vec::new();
+/// for stage in (0..10).rev() {
+///     let factory = PlayerFactory { next: Arc::new(Mutex::new(next.clone())), received: Arc::new(Mutex::new(Vec::new())) };
+///     pipeline.push(factory.received.clone());
+///     next = Some(actor_system.builder().spawn(format!("stage-{}", stage), factory).unwrap());
+/// }
+/// next.unwrap().send(Ball { stage: 0 });
+///
+/// // ping and pong send the ball back and forth forever
+/// let ping = PlayerFactory { next: Arc::new(Mutex::new(None)), received: Arc::new(Mutex::new(Vec::new())) };
+/// let pong = PlayerFactory { next: Arc::new(Mutex::new(None)), received: Arc::new(Mutex::new(Vec::new())) };
+/// let ping_ref = actor_system.builder().spawn("ping", ping.clone()).unwrap();
+/// let pong_ref = actor_system.builder().spawn("pong", pong.clone()).unwrap();
+/// *ping.next.lock().unwrap() = Some(pong_ref.clone());
+/// *pong.next.lock().unwrap() = Some(ping_ref.clone());
+/// ping_ref.send(Ball { stage: 0 });
+/// sleep(Duration::from_millis(300));
+///
+/// assert_eq!(vec![9], *pipeline[0].lock().unwrap());
+/// let handled = ping.received.lock().unwrap().len() + pong.received.lock().unwrap().len();
+/// assert_eq!(16, handled);
+/// let events = events.lock().unwrap().clone();
+/// assert_eq!(1, events.len());
+/// assert_eq!(16, events[0].hops);
+/// assert_eq!("ping", events[0].target.actor);
+/// let chain: Vec<&str> = events[0].chain.iter().map(|address| address.actor.as_str()).collect();
+/// assert_eq!(vec!["ping", "pong", "ping", "pong", "ping", "pong", "ping", "pong"], chain);
+///
+/// // the system is still responsive, exempt types are never cut
+/// ping_ref.send(Ball { stage: 100 });
+/// ping_ref.send(Resync { remaining: 40 });
+/// sleep(Duration::from_millis(300));
+/// assert!(ping.received.lock().unwrap().contains(&100));
+/// assert!(ping.received.lock().unwrap().contains(&1000));
+/// assert_eq!(2, actor_system.cycles_suspected());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct CycleConfig {
+    pub max_hops: u64,
+    /// chains older than `window` start over
+    pub window: Duration,
+}
+
+impl CycleConfig {
+    pub fn new(max_hops: u64, window: Duration) -> Self {
+        Self { max_hops, window }
+    }
+}
+
+/// Marks messages whose sends are never cut by the cycle detection
+///
+/// See [ActorSystem.register_cycle_exempt](../prelude/struct.ActorSystem.html#method.register_cycle_exempt)
+pub trait CycleExempt: ActorMessage {}
+
+/// Published on [CYCLE_TOPIC](./constant.CYCLE_TOPIC.html) for every send that has been dropped by the cycle detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleSuspected {
+    /// most recent Actors of the causal chain, oldest first, the last one made the dropped send
+    pub chain: Vec<ActorAddress>,
+    pub target: ActorAddress,
+    pub message_type: &'static str,
+    /// messages handled within the chain so far
+    pub hops: u64,
+}
+
+impl ActorMessage for CycleSuspected {}
+
+/// Causal chain as carried within the cycle header
+struct Trace {
+    hops: u64,
+    started: u64,
+    actors: VecDeque<ActorAddress>,
+}
+
+impl Trace {
+    fn decode(headers: Option<&Headers>) -> Option<Self> {
+        let bytes = match headers?.get(CYCLE_HEADER.1)? {
+            HeaderValue::Bytes(bytes) => bytes.clone(),
+            _ => return None,
+        };
+        let mut reader = Reader::new(&bytes);
+        let hops = u64::from_le_bytes(reader.take(8)?.try_into().ok()?);
+        let started = u64::from_le_bytes(reader.take(8)?.try_into().ok()?);
+        let len = reader.take(1)?[0] as usize;
+        let mut actors = VecDeque::with_capacity(TRACE_LEN);
+        for _ in 0..len {
+            let mut fields = Vec::with_capacity(4);
+            for _ in 0..4 {
+                let len = u32::from_le_bytes(reader.take(4)?.try_into().ok()?) as usize;
+                fields.push(String::from(std::str::from_utf8(reader.take(len)?).ok()?));
+            }
+            actors.push_back(ActorAddress {
+                actor: fields.pop()?,
+                pool: fields.pop()?,
+                system: fields.pop()?,
+                remote: fields.pop()?,
+            });
+        }
+        Some(Self { hops, started, actors })
+    }
+
+    fn encode(&self) -> HeaderValue {
+        let mut bytes = Vec::with_capacity(17 + self.actors.len() * 64);
+        bytes.extend_from_slice(&self.hops.to_le_bytes());
+        bytes.extend_from_slice(&self.started.to_le_bytes());
+        bytes.push(self.actors.len() as u8);
+        for address in self.actors.iter() {
+            for value in [&address.remote, &address.system, &address.pool, &address.actor].iter() {
+                bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(value.as_bytes());
+            }
+        }
+        HeaderValue::Bytes(Arc::from(bytes))
+    }
+}
+
+/// Dropped send that is reported once the executor has handled the message
+struct Suspicion {
+    detector: Arc<CycleDetector>,
+    chain: Vec<ActorAddress>,
+    hops: u64,
+}
+
+thread_local! {
+    static SUSPICION: RefCell<Option<Suspicion>> = const { RefCell::new(None) };
+}
+
+/// Tracks the causal chains of all Actors of a system, only exists if `general.cycle_detection` is set
+pub(crate) struct CycleDetector {
+    config: CycleConfig,
+    exempt: RwLock<HashSet<TypeId>>,
+    event_bus: EventBus,
+    suspected: AtomicU64,
+}
+
+impl CycleDetector {
+    pub fn new(config: CycleConfig, event_bus: EventBus) -> Self {
+        let mut exempt = HashSet::new();
+        // reporting a cycle and stopping the Actors of a cycle must never be cut
+        exempt.insert(TypeId::of::<CycleSuspected>());
+        exempt.insert(TypeId::of::<ActorStopMessage>());
+        exempt.insert(TypeId::of::<SystemStopMessage>());
+        Self {
+            config,
+            exempt: RwLock::new(exempt),
+            event_bus,
+            suspected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn register_exempt<M>(&self)
+    where
+        M: CycleExempt + 'static,
+    {
+        self.exempt.write().unwrap().insert(TypeId::of::<M>());
+    }
+
+    pub fn get_suspected(&self) -> u64 {
+        self.suspected.load(Ordering::Relaxed)
+    }
+
+    /// Continues the chain of a message that is about to be handled by `address` on the headers of its sends
+    ///
+    /// Returns `true` if the chain exceeded `max_hops`, sends are dropped until [end_handle](#method.end_handle)
+    pub fn begin_handle(self: &Arc<Self>, received: Option<&Headers>, address: &ActorAddress, propagated: &mut Option<Headers>) -> bool {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_nanos() as u64);
+        let window = self.config.window.as_nanos() as u64;
+        let mut trace = match Trace::decode(received) {
+            Some(trace) if now.saturating_sub(trace.started) <= window => trace,
+            _ => Trace {
+                hops: 0,
+                started: now,
+                actors: VecDeque::with_capacity(TRACE_LEN),
+            },
+        };
+        trace.hops += 1;
+        if trace.actors.len() == TRACE_LEN {
+            trace.actors.pop_front();
+        }
+        trace.actors.push_back(address.clone());
+        propagated.get_or_insert_with(Headers::new).insert(CYCLE_HEADER.1, trace.encode());
+        if trace.hops < self.config.max_hops {
+            return false;
+        }
+        let suspicion = Suspicion {
+            detector: self.clone(),
+            chain: trace.actors.into_iter().collect(),
+            hops: trace.hops,
+        };
+        SUSPICION.with(|current| *current.borrow_mut() = Some(suspicion));
+        true
+    }
+
+    pub fn end_handle() {
+        SUSPICION.with(|current| *current.borrow_mut() = None);
+    }
+}
+
+/// Returns `true` if a send of the current thread has to be dropped, reports it on [CYCLE_TOPIC](./constant.CYCLE_TOPIC.html)
+///
+/// Only a single thread local lookup unless the handled message exceeded the limit
+pub(crate) fn is_cut(target: &ActorAddress, type_id: TypeId, message_type: &'static str) -> bool {
+    let event = SUSPICION.with(|current| {
+        let current = current.borrow();
+        let suspicion = current.as_ref()?;
+        if suspicion.detector.exempt.read().unwrap().contains(&type_id) {
+            return None;
+        }
+        suspicion.detector.suspected.fetch_add(1, Ordering::Relaxed);
+        let event = CycleSuspected {
+            chain: suspicion.chain.clone(),
+            target: target.clone(),
+            message_type,
+            hops: suspicion.hops,
+        };
+        Some((suspicion.detector.event_bus.clone(), event))
+    });
+    match event {
+        // published outside of the borrow, so that the event itself passes through the check
+        Some((event_bus, event)) => {
+            let _ = event_bus.publish(CYCLE_TOPIC, event);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,165 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::message::trace::{self, TraceId};
+use crate::system::drop_reporter::DropCounters;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+/// Message that has not been queued, because its target has stopped or does not exist, or that expired in the mailbox
+///
+/// Only the metadata is reported, the message itself is handed back to the sender through [SendError::Stopped](../prelude/enum.SendError.html#variant.Stopped).
+/// See [ActorSystem.subscribe_dead_letters](../prelude/struct.ActorSystem.html#method.subscribe_dead_letters)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub target: ActorAddress,
+    pub type_name: &'static str,
+    pub timestamp: SystemTime,
+    pub reason: DeadLetterReason,
+    /// trace id of the message, see [ActorWrapper.send_traced](../prelude/struct.ActorWrapper.html#method.send_traced)
+    pub trace_id: Option<TraceId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeadLetterReason {
+    /// the target has stopped or does not exist
+    Undeliverable,
+    /// the time to live of the message passed before it was handled, see [ActorWrapper.send_with_ttl](../prelude/struct.ActorWrapper.html#method.send_with_ttl)
+    Expired,
+    /// the target belongs to another system that is neither a peer nor reachable through a transport, see [ActorSystem.try_send_to_address](../prelude/struct.ActorSystem.html#method.try_send_to_address)
+    ForeignSystem,
+    /// the target could not handle the message, see [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message)
+    Unhandled,
+    /// the target gave up on the message after the last attempt of its [RetryPolicy](../prelude/struct.RetryPolicy.html), see [ActorContext.retry](../prelude/struct.ActorContext.html#method.retry)
+    ExhaustedRetries,
+    /// a middleware of the target did not let the message through, see [ActorMiddleware](../prelude/trait.ActorMiddleware.html)
+    Rejected,
+    /// the target only accepts registered message types, see [ActorBuilder.set_strict_messages](../prelude/struct.ActorBuilder.html#method.set_strict_messages)
+    Unregistered,
+    /// the system exceeded its message budget, see [SheddingPolicy::DeadLetter](../prelude/enum.SheddingPolicy.html#variant.DeadLetter), or the target sheds messages under memory pressure
+    Overloaded,
+    /// the overflow policy of the full mailbox of the target dropped the message, see [ActorBuilder.set_overflow_policy](../prelude/struct.ActorBuilder.html#method.set_overflow_policy)
+    Overflow,
+    /// the message was queued while the target restarted and has been flushed, see [RestartMailboxPolicy](../prelude/enum.RestartMailboxPolicy.html)
+    FlushedOnRestart,
+}
+
+impl ActorMessage for DeadLetter {}
+
+/// Returns `false` if the subscriber has stopped
+type Deliver = Box<dyn Fn(DeadLetter) -> bool + Send + Sync>;
+
+struct Subscriber {
+    address: ActorAddress,
+    deliver: Deliver,
+}
+
+/// Shared between the system and all mailboxes
+#[derive(Clone)]
+pub struct DeadLetterOffice {
+    count: Arc<AtomicU64>,
+    is_logging: Arc<AtomicBool>,
+    subscribers: Arc<RwLock<Vec<Arc<Subscriber>>>>,
+    drops: Arc<DropCounters>,
+}
+
+impl DeadLetterOffice {
+    pub fn new() -> Self {
+        Self {
+            count: Arc::new(AtomicU64::new(0)),
+            is_logging: Arc::new(AtomicBool::new(false)),
+            subscribers: Arc::new(RwLock::new(Vec::new())),
+            drops: Arc::new(DropCounters::new()),
+        }
+    }
+
+    pub(crate) fn set_logging(&self, is_logging: bool) {
+        self.is_logging.store(is_logging, Ordering::Relaxed);
+    }
+
+    /// Replaces an earlier subscription of the same Actor
+    pub(crate) fn subscribe<A>(&self, subscriber: &ActorWrapper<A>)
+    where
+        A: Actor + Handler<DeadLetter> + UnwindSafe + 'static,
+    {
+        let address = subscriber.get_address().clone();
+        let target = subscriber.clone();
+        let subscriber = Arc::new(Subscriber {
+            address: address.clone(),
+            deliver: Box::new(move |letter| !matches!(target.send(letter), Err(SendError::Stopped(_)))),
+        });
+        let mut subscribers = self.subscribers.write().unwrap();
+        subscribers.retain(|existing| existing.address != address);
+        subscribers.push(subscriber);
+    }
+
+    pub(crate) fn unsubscribe(&self, address: &ActorAddress) -> bool {
+        let mut subscribers = self.subscribers.write().unwrap();
+        let len = subscribers.len();
+        subscribers.retain(|existing| &existing.address != address);
+        subscribers.len() != len
+    }
+
+    pub(crate) fn get_count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn get_drops(&self) -> &DropCounters {
+        &self.drops
+    }
+
+    /// Counts a drop that is not a dead letter, every dead letter is counted as drop as well
+    pub(crate) fn report_drop(&self, target: &ActorAddress, type_name: &'static str, reason: DeadLetterReason) {
+        self.drops.record(target, type_name, reason);
+    }
+
+    /// Counts the dead letter and delivers it to every subscriber, stopped subscribers are dropped
+    ///
+    /// Dead letters of subscribers are only counted, otherwise a stopped subscriber would be notified about its own dead letters forever
+    pub(crate) fn report(&self, target: &ActorAddress, type_name: &'static str) {
+        self.report_with_reason(target, type_name, DeadLetterReason::Undeliverable);
+    }
+
+    /// The trace id is taken from the message that is currently handled on this thread
+    pub(crate) fn report_with_reason(&self, target: &ActorAddress, type_name: &'static str, reason: DeadLetterReason) {
+        self.report_traced(target, type_name, reason, trace::current());
+    }
+
+    pub(crate) fn report_traced(
+        &self,
+        target: &ActorAddress,
+        type_name: &'static str,
+        reason: DeadLetterReason,
+        trace_id: Option<TraceId>,
+    ) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.drops.record(target, type_name, reason);
+        if self.is_logging.load(Ordering::Relaxed) {
+            println!("DEAD LETTER: {} to {:?} ({:?})", type_name, target, reason);
+        }
+        let subscribers: Vec<Arc<Subscriber>> = self.subscribers.read().unwrap().clone();
+        if subscribers.is_empty() || subscribers.iter().any(|subscriber| &subscriber.address == target) {
+            return;
+        }
+        let letter = DeadLetter {
+            target: target.clone(),
+            type_name,
+            timestamp: SystemTime::now(),
+            reason,
+            trace_id,
+        };
+        let stopped: Vec<ActorAddress> = subscribers
+            .iter()
+            .filter(|subscriber| !(subscriber.deliver)(letter.clone()))
+            .map(|subscriber| subscriber.address.clone())
+            .collect();
+        if !stopped.is_empty() {
+            self.subscribers.write().unwrap().retain(|subscriber| !stopped.contains(&subscriber.address));
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::system::dead_letters::DeadLetterReason;
+use crate::system::panic_handler;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Message that has been dropped by the system, handed to the [DropReporter]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedMessage {
+    pub target: ActorAddress,
+    pub type_name: &'static str,
+    pub reason: DeadLetterReason,
+    /// drops of the same reason and target so far, including this one
+    pub count: u64,
+}
+
+/// Receives sampled drops, see [ActorSystem.set_drop_reporter](../prelude/struct.ActorSystem.html#method.set_drop_reporter)
+pub trait DropReporter: Send + Sync {
+    /// executed on the thread that dropped the message, a panic is ignored
+    fn on_drop(&self, dropped: &DroppedMessage);
+    /// only the first and then every `sample_rate`th drop of each reason and target is reported, defaults to every drop
+    fn sample_rate(&self) -> u64 {
+        1
+    }
+}
+
+/// Prints sampled drops, installed through `general.log_drops_every`
+pub struct LogDropReporter {
+    sample_rate: u64,
+}
+
+impl LogDropReporter {
+    pub fn new(sample_rate: u64) -> Self {
+        Self { sample_rate }
+    }
+}
+
+impl DropReporter for LogDropReporter {
+    fn on_drop(&self, dropped: &DroppedMessage) {
+        println!(
+            "DROPPED MESSAGE: {} to {:?} ({:?}, {} so far)",
+            dropped.type_name, dropped.target, dropped.reason, dropped.count
+        );
+    }
+
+    fn sample_rate(&self) -> u64 {
+        self.sample_rate
+    }
+}
+
+/// Drops per reason and target, see [ActorSystem.drop_stats](../prelude/struct.ActorSystem.html#method.drop_stats)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DropStats {
+    counts: HashMap<ActorAddress, HashMap<DeadLetterReason, u64>>,
+}
+
+impl DropStats {
+    /// Drops of all reasons and targets
+    pub fn total(&self) -> u64 {
+        self.counts.values().flat_map(|reasons| reasons.values()).sum()
+    }
+
+    /// Drops of `reason` across all targets
+    pub fn by_reason(&self, reason: DeadLetterReason) -> u64 {
+        self.counts.values().filter_map(|reasons| reasons.get(&reason)).sum()
+    }
+
+    /// Drops of all reasons to `target`
+    pub fn by_target(&self, target: &ActorAddress) -> u64 {
+        self.counts.get(target).map(|reasons| reasons.values().sum()).unwrap_or(0)
+    }
+
+    pub fn get(&self, reason: DeadLetterReason, target: &ActorAddress) -> u64 {
+        self.counts
+            .get(target)
+            .and_then(|reasons| reasons.get(&reason))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Counts every drop, the reporter is only consulted if one has been set
+pub(crate) struct DropCounters {
+    counts: RwLock<HashMap<ActorAddress, HashMap<DeadLetterReason, AtomicU64>>>,
+    has_reporter: AtomicBool,
+    reporter: RwLock<Option<Arc<dyn DropReporter>>>,
+}
+
+impl DropCounters {
+    pub fn new() -> Self {
+        Self {
+            counts: RwLock::new(HashMap::new()),
+            has_reporter: AtomicBool::new(false),
+            reporter: RwLock::new(None),
+        }
+    }
+
+    pub fn set_reporter(&self, reporter: Arc<dyn DropReporter>) {
+        *self.reporter.write().unwrap() = Some(reporter);
+        self.has_reporter.store(true, Ordering::Relaxed);
+    }
+
+    // is_multiple_of needs Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn record(&self, target: &ActorAddress, type_name: &'static str, reason: DeadLetterReason) {
+        let count = self.increment(target, reason);
+        if !self.has_reporter.load(Ordering::Relaxed) {
+            return;
+        }
+        let reporter = match self.reporter.read().unwrap().clone() {
+            Some(reporter) => reporter,
+            None => return,
+        };
+        let sample_rate = reporter.sample_rate().max(1);
+        if (count - 1) % sample_rate != 0 {
+            return;
+        }
+        let dropped = DroppedMessage {
+            target: target.clone(),
+            type_name,
+            reason,
+            count,
+        };
+        if panic_handler::catch(|| reporter.on_drop(&dropped)).is_err() {
+            println!("DROP REPORTER PANIC");
+        }
+    }
+
+    /// Returns the count including this drop, the address is only cloned for the first drop of each reason and target
+    fn increment(&self, target: &ActorAddress, reason: DeadLetterReason) -> u64 {
+        if let Some(count) = self.counts.read().unwrap().get(target).and_then(|reasons| reasons.get(&reason)) {
+            return count.fetch_add(1, Ordering::Relaxed) + 1;
+        }
+        let mut counts = self.counts.write().unwrap();
+        let count = counts.entry(target.clone()).or_default().entry(reason).or_default();
+        count.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn snapshot(&self) -> DropStats {
+        let counts = self
+            .counts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(target, reasons)| {
+                let reasons = reasons.iter().map(|(reason, count)| (*reason, count.load(Ordering::Relaxed))).collect();
+                (target.clone(), reasons)
+            })
+            .collect();
+        DropStats { counts }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_config::ActorDefaults;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::protocol::ProtocolError;
+use crate::message::serialized_message::SerializedMessage;
+use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::{self, SpawnError, SpawnSpec};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::panic::UnwindSafe;
+use std::sync::{Arc, RwLock};
+
+type SpawnFn = Box<dyn Fn(&ActorSystem, String, &DynSpawnConfig, Option<Value>) -> Result<DynActorRef, SpawnDynError> + Send + Sync>;
+
+/// Reason [ActorSystem.spawn_dyn](./struct.ActorSystem.html#method.spawn_dyn) did not spawn an Actor
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpawnDynError {
+    /// no factory has been registered under the key
+    UnknownFactory { key: String },
+    /// the settings could not be deserialized into the settings of the factory
+    InvalidSettings { reason: String },
+    Spawn(SpawnError),
+}
+
+/// Pool and settings of an Actor spawned through [ActorSystem.spawn_dyn](./struct.ActorSystem.html#method.spawn_dyn), deserializable from the same document as its settings
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct DynSpawnConfig {
+    pub pool_name: String,
+    /// settings that are set replace the defaults of the pool
+    pub overrides: ActorDefaults,
+}
+
+impl DynSpawnConfig {
+    pub fn new() -> Self {
+        Self {
+            pool_name: String::from("default"),
+            overrides: ActorDefaults::default(),
+        }
+    }
+
+    pub fn with_pool_name(mut self, pool_name: impl Into<String>) -> Self {
+        self.pool_name = pool_name.into();
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: ActorDefaults) -> Self {
+        self.overrides = overrides;
+        self
+    }
+}
+
+impl Default for DynSpawnConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Spawns an Actor whose type is only known to the factory, see [ActorSystem.register_dyn_factory](./struct.ActorSystem.html#method.register_dyn_factory)
+///
+/// Usually implemented through [DynFactory](./struct.DynFactory.html)
+pub trait DynActorFactory: Send + Sync {
+    fn spawn(&self, system: &ActorSystem, name: String, config: &DynSpawnConfig, settings: Option<Value>) -> Result<DynActorRef, SpawnDynError>;
+}
+
+/// [DynActorFactory](./trait.DynActorFactory.html) that deserializes its settings into `S` and passes them to a typed [ActorFactory](./trait.ActorFactory.html)
+pub struct DynFactory {
+    spawn: SpawnFn,
+}
+
+impl DynFactory {
+    /// `create` builds the factory of the Actor from its settings, which default to `S::default()` if none are passed
+    pub fn new<A, S, P, F>(create: F) -> Self
+    where
+        A: Actor + UnwindSafe + 'static,
+        S: DeserializeOwned + Default,
+        P: ActorFactory<A> + 'static,
+        F: Fn(S) -> P + Send + Sync + 'static,
+    {
+        let spawn = move |system: &ActorSystem, name: String, config: &DynSpawnConfig, settings: Option<Value>| {
+            let settings = match settings {
+                Some(settings) => serde_json::from_value::<S>(settings)
+                    .map_err(|error| SpawnDynError::InvalidSettings { reason: error.to_string() })?,
+                None => S::default(),
+            };
+            let builder = system
+                .builder::<A>()
+                .set_pool_name(config.pool_name.clone())
+                .map_err(|_| SpawnDynError::Spawn(SpawnError::UnknownPool))?;
+            let spec = SpawnSpec::new(name, create(settings)).with_overrides(config.overrides);
+            let actor = bulk_spawn::spawn_one(&builder, spec).map_err(SpawnDynError::Spawn)?;
+            Ok(DynActorRef::new(actor, system.clone()))
+        };
+        Self { spawn: Box::new(spawn) }
+    }
+}
+
+impl DynActorFactory for DynFactory {
+    fn spawn(&self, system: &ActorSystem, name: String, config: &DynSpawnConfig, settings: Option<Value>) -> Result<DynActorRef, SpawnDynError> {
+        (self.spawn)(system, name, config, settings)
+    }
+}
+
+trait ErasedActor: Send + Sync {
+    fn stop(&self);
+    fn is_stopped(&self) -> bool;
+    fn mailbox_len(&self) -> usize;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<A> ErasedActor for ActorWrapper<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn stop(&self) {
+        ActorWrapper::stop(self);
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.get_mailbox().is_stopped()
+    }
+
+    fn mailbox_len(&self) -> usize {
+        ActorWrapper::mailbox_len(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Untyped reference to an Actor spawned through [ActorSystem.spawn_dyn](./struct.ActorSystem.html#method.spawn_dyn)
+///
+/// Callers that know the type of the Actor get its [ActorWrapper](./struct.ActorWrapper.html) through [downcast](#method.downcast)
+#[derive(Clone)]
+pub struct DynActorRef {
+    actor: Arc<dyn ErasedActor>,
+    address: ActorAddress,
+    type_id: TypeId,
+    type_name: &'static str,
+    system: ActorSystem,
+}
+
+impl DynActorRef {
+    fn new<A>(actor: ActorWrapper<A>, system: ActorSystem) -> Self
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        Self {
+            address: actor.get_address().clone(),
+            actor: Arc::new(actor),
+            type_id: TypeId::of::<A>(),
+            type_name: std::any::type_name::<A>(),
+            system,
+        }
+    }
+
+    pub fn get_address(&self) -> &ActorAddress {
+        &self.address
+    }
+
+    /// Type name of the Actor
+    pub fn get_type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn is<A>(&self) -> bool
+    where
+        A: Actor + 'static,
+    {
+        self.type_id == TypeId::of::<A>()
+    }
+
+    /// Returns `None` if the Actor is not of type `A`
+    pub fn downcast<A>(&self) -> Option<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.actor.as_any().downcast_ref::<ActorWrapper<A>>().cloned()
+    }
+
+    /// Same as [ActorSystem.try_send_to_address](./struct.ActorSystem.html#method.try_send_to_address) with the address of the Actor
+    pub fn send_serialized(&self, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        self.system.try_send_to_address(&self.address, msg)
+    }
+
+    pub fn stop(&self) {
+        self.actor.stop();
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.actor.is_stopped()
+    }
+
+    /// Number of messages waiting in the regular mailbox
+    pub fn mailbox_len(&self) -> usize {
+        self.actor.mailbox_len()
+    }
+}
+
+/// Factories of a system by key
+#[derive(Clone, Default)]
+pub(crate) struct DynFactoryRegistry {
+    factories: Arc<RwLock<HashMap<String, Arc<dyn DynActorFactory>>>>,
+}
+
+impl DynFactoryRegistry {
+    pub fn register(&self, key: String, factory: Box<dyn DynActorFactory>) {
+        self.factories.write().unwrap().insert(key, Arc::from(factory));
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<dyn DynActorFactory>> {
+        self.factories.read().unwrap().get(key).cloned()
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.factories.read().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
@@ -0,0 +1,170 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ResolveError;
+use crate::actor::actor_builder::ActorBuilder;
+use crate::actor::actor_factory::ActorFactory;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::context::ActorContext;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::system::actor_system::ActorSystem;
+use crate::system::bulk_spawn::SpawnError;
+use crossbeam_utils::Backoff;
+use std::fmt::{Debug, Formatter};
+use std::panic::UnwindSafe;
+use std::sync::Arc;
+
+type EntityFactoryFn<A> = dyn Fn(&str, ActorContext<A>) -> A + Send + Sync;
+
+/// Returned by [EntityRegion.send](./struct.EntityRegion.html#method.send), hands back the message that has not been delivered
+pub enum EntitySendError<M> {
+    /// the entity Actor could not be spawned
+    Spawn(SpawnError, M),
+    /// the entity Actor is running, but did not accept the message
+    Send(SendError<M>),
+}
+
+impl<M> EntitySendError<M> {
+    /// Returns the message that has not been delivered
+    pub fn into_inner(self) -> M {
+        match self {
+            EntitySendError::Spawn(_, msg) => msg,
+            EntitySendError::Send(error) => error.into_inner(),
+        }
+    }
+}
+
+impl<M> Debug for EntitySendError<M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntitySendError::Spawn(error, _) => write!(f, "Spawn({:?}, ..)", error),
+            EntitySendError::Send(error) => write!(f, "Send({:?})", error),
+        }
+    }
+}
+
+/// Creates a single entity, every spawn attempt gets its own factory
+struct EntityFactory<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    entity_id: String,
+    factory: Arc<EntityFactoryFn<A>>,
+}
+
+// a panicking factory leaves nothing behind that is shared with other entities
+impl<A> UnwindSafe for EntityFactory<A> where A: Actor + UnwindSafe + 'static {}
+
+impl<A> ActorFactory<A> for EntityFactory<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn new_actor(&self, context: ActorContext<A>) -> A {
+        (self.factory)(&self.entity_id, context)
+    }
+}
+
+/// One Actor per entity id, spawned on the first message it receives, see [ActorSystem.entity_region](./struct.ActorSystem.html#method.entity_region)
+///
+/// The entity with the id `id` is named `<name>-<id>`.
+/// Clones share the same entities and can be sent into other Actors or threads
+pub struct EntityRegion<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    system: ActorSystem,
+    builder: Arc<ActorBuilder<A>>,
+    name: String,
+    pool: String,
+    factory: Arc<EntityFactoryFn<A>>,
+}
+
+impl<A> Clone for EntityRegion<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn clone(&self) -> Self {
+        EntityRegion {
+            system: self.system.clone(),
+            builder: self.builder.clone(),
+            name: self.name.clone(),
+            pool: self.pool.clone(),
+            factory: self.factory.clone(),
+        }
+    }
+}
+
+impl<A> EntityRegion<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    pub(crate) fn new<F>(system: ActorSystem, builder: ActorBuilder<A>, name: String, factory_fn: F) -> Self
+    where
+        F: Fn(&str, ActorContext<A>) -> A + Send + Sync + 'static,
+    {
+        let pool = builder.get_resolved_config().config.pool_name;
+        EntityRegion {
+            system,
+            builder: Arc::new(builder),
+            name,
+            pool,
+            factory: Arc::new(factory_fn),
+        }
+    }
+
+    /// Name of the Actor of the entity
+    pub fn entity_name(&self, entity_id: &str) -> String {
+        format!("{}-{}", self.name, entity_id)
+    }
+
+    /// Running Actor of the entity, `None` if it has not been spawned yet or has been stopped
+    pub fn get(&self, entity_id: &str) -> Option<ActorWrapper<A>> {
+        let address = self.system.address_of(&self.pool, &self.entity_name(entity_id));
+        self.system.resolve(&address).ok()
+    }
+
+    /// Delivers the message to the entity, its Actor is spawned first if it is not running
+    ///
+    /// Concurrent sends to an entity that is not running spawn it exactly once, senders that lose the race wait until the Actor is running.
+    /// An entity that stopped, e.g. after [ActorBuilder.set_idle_timeout](../prelude/struct.ActorBuilder.html#method.set_idle_timeout), is spawned again by the next message
+    pub fn send<M>(&self, entity_id: &str, msg: M) -> Result<(), EntitySendError<M>>
+    where
+        A: Handler<M>,
+        M: ActorMessage + 'static,
+    {
+        let name = self.entity_name(entity_id);
+        let address = self.system.address_of(&self.pool, &name);
+        let backoff = Backoff::new();
+        let mut msg = msg;
+        loop {
+            let entity = match self.system.resolve::<A>(&address) {
+                Ok(entity) => entity,
+                Err(ResolveError::TypeMismatch) => return Err(EntitySendError::Spawn(SpawnError::NameTaken, msg)),
+                Err(_) => {
+                    let factory = EntityFactory {
+                        entity_id: String::from(entity_id),
+                        factory: self.factory.clone(),
+                    };
+                    match self.builder.try_spawn(name.clone(), factory) {
+                        Ok(entity) => entity,
+                        // another spawn of the entity is in progress, or the stopped entity has not been removed yet
+                        Err(SpawnError::NameTaken) => {
+                            backoff.snooze();
+                            continue;
+                        }
+                        Err(error) => return Err(EntitySendError::Spawn(error, msg)),
+                    }
+                }
+            };
+            match entity.send(msg) {
+                Ok(()) => return Ok(()),
+                // passivated in between, the next attempt spawns the entity again
+                Err(SendError::Stopped(returned)) => {
+                    msg = returned;
+                    backoff.snooze();
+                }
+                Err(error) => return Err(EntitySendError::Send(error)),
+            }
+        }
+    }
+}
@@ -0,0 +1,654 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+const SINGLE_LEVEL: &str = "*";
+const MULTI_LEVEL: &str = "#";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TopicError {
+    /// topics must not be empty and must not contain wildcards
+    InvalidTopic,
+    /// wildcards must fill a whole segment and `#` must be the last segment
+    InvalidPattern,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EventBusMetrics {
+    pub published: u64,
+    pub delivered: u64,
+    /// subscriptions whose pattern matched a published topic, every other subscription is never looked at
+    pub visited: u64,
+    /// matching subscriptions that have been skipped because they expect a different message type
+    pub type_mismatches: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    published: AtomicU64,
+    delivered: AtomicU64,
+    visited: AtomicU64,
+    type_mismatches: AtomicU64,
+}
+
+type Deliver = Box<dyn Fn(&dyn Any) + Send + Sync>;
+
+struct Subscription {
+    address: ActorAddress,
+    type_id: TypeId,
+    deliver: Deliver,
+}
+
+#[derive(Default)]
+struct TopicNode {
+    /// subscriptions with `*` are stored in the child named `*`
+    children: HashMap<String, TopicNode>,
+    /// subscriptions whose pattern ends at this node
+    subscriptions: Vec<Arc<Subscription>>,
+    /// subscriptions whose pattern ends with `#` after this node
+    multi_level: Vec<Arc<Subscription>>,
+}
+
+impl TopicNode {
+    fn is_empty(&self) -> bool {
+        self.children.is_empty() && self.subscriptions.is_empty() && self.multi_level.is_empty()
+    }
+
+    fn insert(&mut self, segments: &[&str], subscription: Arc<Subscription>) {
+        match segments.split_first() {
+            None => {
+                replace(&mut self.subscriptions, subscription);
+            }
+            Some((&MULTI_LEVEL, _)) => {
+                replace(&mut self.multi_level, subscription);
+            }
+            Some((segment, rest)) => {
+                self.children
+                    .entry(String::from(*segment))
+                    .or_default()
+                    .insert(rest, subscription);
+            }
+        }
+    }
+
+    /// Returns whether a subscription has been removed, empty nodes are pruned on the way back
+    fn remove(&mut self, segments: &[&str], address: &ActorAddress) -> bool {
+        match segments.split_first() {
+            None => remove(&mut self.subscriptions, address),
+            Some((&MULTI_LEVEL, _)) => remove(&mut self.multi_level, address),
+            Some((segment, rest)) => {
+                let child = match self.children.get_mut(*segment) {
+                    Some(child) => child,
+                    None => return false,
+                };
+                let is_removed = child.remove(rest, address);
+                if child.is_empty() {
+                    self.children.remove(*segment);
+                }
+                is_removed
+            }
+        }
+    }
+
+    /// Only descends into matching branches, so that the cost depends on the depth of the topic and the number of matches
+    fn collect(&self, segments: &[&str], matches: &mut Vec<Arc<Subscription>>) {
+        // `#` also matches the parent level
+        matches.extend(self.multi_level.iter().cloned());
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                matches.extend(self.subscriptions.iter().cloned());
+                return;
+            }
+        };
+        if let Some(child) = self.children.get(*segment) {
+            child.collect(rest, matches);
+        }
+        if let Some(child) = self.children.get(SINGLE_LEVEL) {
+            child.collect(rest, matches);
+        }
+    }
+}
+
+/// Subscribing again with the same pattern replaces the previous subscription of the Actor
+fn replace(subscriptions: &mut Vec<Arc<Subscription>>, subscription: Arc<Subscription>) {
+    subscriptions.retain(|existing| existing.address != subscription.address);
+    subscriptions.push(subscription);
+}
+
+fn remove(subscriptions: &mut Vec<Arc<Subscription>>, address: &ActorAddress) -> bool {
+    let len = subscriptions.len();
+    subscriptions.retain(|existing| &existing.address != address);
+    len != subscriptions.len()
+}
+
+#[derive(Default)]
+struct Subscriptions {
+    root: TopicNode,
+    /// patterns of every subscribed Actor, used to remove them once it stopped
+    patterns: HashMap<ActorAddress, Vec<String>>,
+    /// subscriptions by message type, independent of any topic
+    by_type: HashMap<TypeId, Vec<Arc<Subscription>>>,
+    /// message types of every Actor subscribed by type, used to remove them once it stopped
+    types: HashMap<ActorAddress, Vec<TypeId>>,
+}
+
+impl Subscriptions {
+    fn remove_type(&mut self, type_id: TypeId, address: &ActorAddress) -> bool {
+        let is_removed = match self.by_type.get_mut(&type_id) {
+            Some(subscriptions) => {
+                let is_removed = remove(subscriptions, address);
+                if subscriptions.is_empty() {
+                    self.by_type.remove(&type_id);
+                }
+                is_removed
+            }
+            None => false,
+        };
+        if let Some(types) = self.types.get_mut(address) {
+            types.retain(|existing| *existing != type_id);
+            if types.is_empty() {
+                self.types.remove(address);
+            }
+        }
+        is_removed
+    }
+}
+
+/// Publishes messages to Actors by hierarchical topics, see [ActorSystem.get_event_bus](../prelude/struct.ActorSystem.html#method.get_event_bus)
+///
+/// Topics are dot-separated, e.g. `orders.eu.create`. Patterns follow the semantics of MQTT:
+/// `*` matches exactly one segment and `#` as the last segment matches any number of segments, including none
+///
+/// Actors can also subscribe to a message type instead of a topic, see [subscribe](#method.subscribe) and [broadcast](#method.broadcast)
+///
+/// Subscriptions are removed automatically once their Actor stopped
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::sync::{Arc, Mutex};
+/// use std::thread::sleep;
+/// use std::time::Duration;
+///
+/// #[derive(Clone)]
+/// struct Order { id: usize }
+/// impl ActorMessage for Order {}
+///
+/// #[derive(Clone)]
+/// struct Invoice {}
+/// impl ActorMessage for Invoice {}
+///
+/// struct Listener {
+///     received: Arc<Mutex<Vec<usize>>>,
+/// }
+/// impl Actor for Listener {}
+/// impl Handler<Order> for Listener {
+///     fn handle(&mut self, msg: Order, _context: &ActorContext<Self>) {
+///         self.received.lock().unwrap().push(msg.id);
+///     }
+/// }
+/// impl Handler<Invoice> for Listener {
+///     fn handle(&mut self, _msg: Invoice, _context: &ActorContext<Self>) {}
+/// }
+///
+/// struct ListenerFactory {
+///     received: Arc<Mutex<Vec<usize>>>,
+/// }
+/// impl ActorFactory<Listener> for ListenerFactory {
+///     fn new_actor(&self, _context: ActorContext<Listener>) -> Listener {
+///         Listener { received: self.received.clone() }
+///     }
+/// }
+///
+/// // the semantics of patterns
+/// let matrix = [
+///     ("orders.eu.create", "orders.eu.create", true),
+///     ("orders.eu.create", "orders.eu", false),
+///     ("orders.*.create", "orders.eu.create", true),
+///     ("orders.*", "orders.eu.create", false),
+///     ("orders.*", "orders", false),
+///     ("orders.#", "orders", true),
+///     ("orders.#", "orders.eu.create", true),
+///     ("orders.eu.#", "orders.us.create", false),
+///     ("#", "orders", true),
+///     ("*", "orders", true),
+///     ("*", "orders.eu", false),
+///     ("*.#", "orders", true),
+///     ("*.eu.#", "orders.eu", true),
+///     ("orders", "orders", true),
+///     ("orders", "invoices", false),
+/// ];
+/// for (pattern, topic, expected) in matrix.iter() {
+///     assert_eq!(*expected, EventBus::matches(pattern, topic).unwrap(), "{} {}", pattern, topic);
+/// }
+/// assert_eq!(Err(TopicError::InvalidPattern), EventBus::matches("orders.#.create", "orders"));
+/// assert_eq!(Err(TopicError::InvalidPattern), EventBus::matches("orders.eu*", "orders"));
+/// assert_eq!(Err(TopicError::InvalidTopic), EventBus::matches("orders.#", "orders.*"));
+///
+/// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+/// let event_bus = actor_system.get_event_bus();
+/// let builder = actor_system.builder();
+///
+/// // the subscriptions of the trie agree with the matrix
+/// let mut subscribers = Vec::new();
+/// for (i, (pattern, _, _)) in matrix.iter().enumerate() {
+///     let listener = builder.spawn(format!("matrix-{}", i), ListenerFactory { received: Arc::new(Mutex::new(Vec::new())) }).unwrap();
+///     event_bus.subscribe_pattern::<Order, _>(pattern, &listener).unwrap();
+///     subscribers.push(listener);
+/// }
+/// for (_, topic, _) in matrix.iter() {
+///     let expected: Vec<_> = matrix
+///         .iter()
+///         .zip(subscribers.iter())
+///         .filter(|((pattern, _, _), _)| EventBus::matches(pattern, topic).unwrap())
+///         .map(|(_, listener)| listener.get_address().clone())
+///         .collect();
+///     let mut actual = event_bus.matching_subscribers(topic).unwrap();
+///     actual.sort_by_key(|address| address.actor[7..].parse::<usize>().unwrap());
+///     assert_eq!(expected, actual, "{}", topic);
+/// }
+/// for (pattern, listener) in matrix.iter().map(|(pattern, _, _)| pattern).zip(subscribers.iter()) {
+///     assert!(event_bus.unsubscribe_pattern(pattern, listener).unwrap());
+/// }
+///
+/// // only matching subscriptions are visited
+/// let noise = builder.spawn("noise", ListenerFactory { received: Arc::new(Mutex::new(Vec::new())) }).unwrap();
+/// for i in 0..10_000 {
+///     event_bus.subscribe_pattern::<Order, _>(&format!("noise.{}.#", i), &noise).unwrap();
+/// }
+/// let received = Arc::new(Mutex::new(Vec::new()));
+/// let listeners: Vec<ActorWrapper<Listener>> = ["orders.eu.create", "orders.*.create", "orders.#"]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, pattern)| {
+///         let listener = builder.spawn(format!("listener-{}", i), ListenerFactory { received: received.clone() }).unwrap();
+///         event_bus.subscribe_pattern::<Order, _>(pattern, &listener).unwrap();
+///         listener
+///     })
+///     .collect();
+/// let before = event_bus.metrics();
+/// assert_eq!(Ok(3), event_bus.publish("orders.eu.create", Order { id: 1 }));
+/// let after = event_bus.metrics();
+/// assert_eq!(3, after.visited - before.visited);
+///
+/// // subscribers expecting a different message type are skipped
+/// let invoices = builder.spawn("invoices", ListenerFactory { received: received.clone() }).unwrap();
+/// event_bus.subscribe_pattern::<Invoice, _>("orders.#", &invoices).unwrap();
+/// assert_eq!(Ok(3), event_bus.publish("orders.eu.create", Order { id: 2 }));
+/// assert_eq!(after.type_mismatches + 1, event_bus.metrics().type_mismatches);
+///
+/// sleep(Duration::from_millis(200));
+/// assert_eq!(6, received.lock().unwrap().len());
+///
+/// // stopped Actors are unsubscribed
+/// listeners[2].stop();
+/// sleep(Duration::from_millis(200));
+/// let remaining = event_bus.matching_subscribers("orders.eu.create").unwrap();
+/// assert_eq!(3, remaining.len());
+/// assert!(!remaining.contains(listeners[2].get_address()));
+/// ```
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscriptions: Arc<RwLock<Subscriptions>>,
+    counters: Arc<Counters>,
+}
+
+impl EventBus {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes the Actor to all topics matching `pattern`, messages of type `M` are delivered to it
+    pub fn subscribe_pattern<M, A>(&self, pattern: &str, subscriber: &ActorWrapper<A>) -> Result<(), TopicError>
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + Clone + 'static,
+    {
+        let segments = parse_pattern(pattern)?;
+        let address = subscriber.get_address().clone();
+        let target = subscriber.clone();
+        let subscription = Arc::new(Subscription {
+            address: address.clone(),
+            type_id: TypeId::of::<M>(),
+            deliver: Box::new(move |msg| {
+                if let Some(msg) = msg.downcast_ref::<M>() {
+                    let _ = target.send(msg.clone());
+                }
+            }),
+        });
+        self.insert(&segments, pattern, subscription);
+        // the Actor might have stopped and been cleaned up before it was subscribed
+        if subscriber.get_mailbox().is_stopped() {
+            self.remove_subscriber(&address);
+        }
+        Ok(())
+    }
+
+    /// Subscribes the Actor to every message of type `M` that is [broadcast](#method.broadcast), regardless of any topic
+    ///
+    /// Subscribing again replaces the previous subscription of the Actor, so that it receives every message only once
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use tyractorsaur::prelude::*;
+    /// use std::sync::{Arc, Mutex};
+    /// use std::thread::sleep;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Clone)]
+    /// struct PriceChanged { price: usize }
+    /// impl ActorMessage for PriceChanged {}
+    ///
+    /// struct Display {
+    ///     prices: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl Actor for Display {}
+    /// impl Handler<PriceChanged> for Display {
+    ///     fn handle(&mut self, msg: PriceChanged, _context: &ActorContext<Self>) {
+    ///         self.prices.lock().unwrap().push(msg.price);
+    ///     }
+    /// }
+    ///
+    /// struct DisplayFactory {
+    ///     prices: Arc<Mutex<Vec<usize>>>,
+    /// }
+    /// impl ActorFactory<Display> for DisplayFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Display>) -> Display {
+    ///         Display { prices: self.prices.clone() }
+    ///     }
+    /// }
+    ///
+    /// // publishes on behalf of whoever tells it to, without knowing the subscribers
+    /// struct Ticker {}
+    /// impl Actor for Ticker {}
+    /// impl Handler<PriceChanged> for Ticker {
+    ///     fn handle(&mut self, msg: PriceChanged, context: &ActorContext<Self>) {
+    ///         context.get_event_bus().broadcast(msg);
+    ///     }
+    /// }
+    /// struct TickerFactory {}
+    /// impl ActorFactory<Ticker> for TickerFactory {
+    ///     fn new_actor(&self, _context: ActorContext<Ticker>) -> Ticker {
+    ///         Ticker {}
+    ///     }
+    /// }
+    ///
+    /// let actor_system = ActorSystem::new(TyractorsaurConfig::new().unwrap());
+    /// let event_bus = actor_system.get_event_bus();
+    /// let builder = actor_system.builder();
+    ///
+    /// // nobody listens yet
+    /// assert_eq!(0, event_bus.broadcast(PriceChanged { price: 0 }));
+    ///
+    /// let first = Arc::new(Mutex::new(Vec::new()));
+    /// let second = Arc::new(Mutex::new(Vec::new()));
+    /// let first_display = builder.spawn("first", DisplayFactory { prices: first.clone() }).unwrap();
+    /// let second_display = builder.spawn("second", DisplayFactory { prices: second.clone() }).unwrap();
+    /// event_bus.subscribe::<PriceChanged, _>(&first_display);
+    /// event_bus.subscribe::<PriceChanged, _>(&second_display);
+    /// // subscribing twice does not deliver twice
+    /// event_bus.subscribe::<PriceChanged, _>(&second_display);
+    ///
+    /// // broadcasting from within a handler
+    /// let ticker = actor_system.builder().spawn("ticker", TickerFactory {}).unwrap();
+    /// ticker.send(PriceChanged { price: 1 });
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(vec![1], *first.lock().unwrap());
+    /// assert_eq!(vec![1], *second.lock().unwrap());
+    ///
+    /// // unsubscribed Actors receive nothing
+    /// assert!(event_bus.unsubscribe::<PriceChanged, _>(&first_display));
+    /// assert!(!event_bus.unsubscribe::<PriceChanged, _>(&first_display));
+    /// assert_eq!(1, event_bus.broadcast(PriceChanged { price: 2 }));
+    ///
+    /// // stopped Actors are unsubscribed
+    /// second_display.stop();
+    /// sleep(Duration::from_millis(200));
+    /// assert_eq!(0, event_bus.broadcast(PriceChanged { price: 3 }));
+    /// assert_eq!(vec![1], *first.lock().unwrap());
+    /// assert_eq!(vec![1, 2], *second.lock().unwrap());
+    /// ```
+    pub fn subscribe<M, A>(&self, subscriber: &ActorWrapper<A>)
+    where
+        A: Actor + Handler<M> + UnwindSafe + 'static,
+        M: ActorMessage + Clone + 'static,
+    {
+        let address = subscriber.get_address().clone();
+        let type_id = TypeId::of::<M>();
+        let target = subscriber.clone();
+        let subscription = Arc::new(Subscription {
+            address: address.clone(),
+            type_id,
+            deliver: Box::new(move |msg| {
+                if let Some(msg) = msg.downcast_ref::<M>() {
+                    let _ = target.send(msg.clone());
+                }
+            }),
+        });
+        {
+            let mut subscriptions = self.subscriptions.write().unwrap();
+            replace(subscriptions.by_type.entry(type_id).or_default(), subscription);
+            let types = subscriptions.types.entry(address.clone()).or_default();
+            if !types.contains(&type_id) {
+                types.push(type_id);
+            }
+        }
+        // the Actor might have stopped and been cleaned up before it was subscribed
+        if subscriber.get_mailbox().is_stopped() {
+            self.remove_subscriber(&address);
+        }
+    }
+
+    /// Returns `false` if the Actor was not subscribed to messages of type `M`
+    pub fn unsubscribe<M, A>(&self, subscriber: &ActorWrapper<A>) -> bool
+    where
+        A: Actor + UnwindSafe + 'static,
+        M: ActorMessage + 'static,
+    {
+        self.subscriptions
+            .write()
+            .unwrap()
+            .remove_type(TypeId::of::<M>(), subscriber.get_address())
+    }
+
+    fn insert(&self, segments: &[&str], pattern: &str, subscription: Arc<Subscription>) {
+        let address = subscription.address.clone();
+        let mut subscriptions = self.subscriptions.write().unwrap();
+        subscriptions.root.insert(segments, subscription);
+        let patterns = subscriptions.patterns.entry(address).or_default();
+        if !patterns.iter().any(|existing| existing == pattern) {
+            patterns.push(String::from(pattern));
+        }
+    }
+
+    /// Subscribes an address that never receives anything, used to create orphaned subscriptions
+    #[cfg(feature = "testing")]
+    pub(crate) fn insert_orphan(&self, pattern: &str, address: ActorAddress) {
+        let segments = parse_pattern(pattern).unwrap();
+        let subscription = Arc::new(Subscription {
+            address,
+            type_id: TypeId::of::<()>(),
+            deliver: Box::new(|_| {}),
+        });
+        self.insert(&segments, pattern, subscription);
+    }
+
+    /// Returns `false` if the Actor was not subscribed with this pattern
+    pub fn unsubscribe_pattern<A>(&self, pattern: &str, subscriber: &ActorWrapper<A>) -> Result<bool, TopicError>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        let segments = parse_pattern(pattern)?;
+        let address = subscriber.get_address();
+        let mut subscriptions = self.subscriptions.write().unwrap();
+        let is_removed = subscriptions.root.remove(&segments, address);
+        if let Some(patterns) = subscriptions.patterns.get_mut(address) {
+            patterns.retain(|existing| existing != pattern);
+            if patterns.is_empty() {
+                subscriptions.patterns.remove(address);
+            }
+        }
+        Ok(is_removed)
+    }
+
+    /// Removes all subscriptions of a stopped Actor, returns `false` if it was not subscribed
+    pub(crate) fn remove_subscriber(&self, address: &ActorAddress) -> bool {
+        let mut subscriptions = self.subscriptions.write().unwrap();
+        let patterns = subscriptions.patterns.remove(address);
+        let types = subscriptions.types.get(address).cloned();
+        if patterns.is_none() && types.is_none() {
+            return false;
+        }
+        for pattern in patterns.unwrap_or_default() {
+            let segments: Vec<&str> = pattern.split('.').collect();
+            subscriptions.root.remove(&segments, address);
+        }
+        for type_id in types.unwrap_or_default() {
+            subscriptions.remove_type(type_id, address);
+        }
+        true
+    }
+
+    /// Addresses of all subscribed Actors
+    pub(crate) fn get_subscribers(&self) -> Vec<ActorAddress> {
+        let subscriptions = self.subscriptions.read().unwrap();
+        let mut addresses: Vec<ActorAddress> = subscriptions
+            .patterns
+            .keys()
+            .chain(subscriptions.types.keys())
+            .cloned()
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+
+    /// Delivers a clone of `msg` to every Actor subscribed to a matching pattern, returns the number of receivers
+    ///
+    /// Actors receive a message only once, even if several of their patterns match.
+    /// Subscriptions of a different message type are skipped and counted in [EventBusMetrics.type_mismatches](./struct.EventBusMetrics.html#structfield.type_mismatches)
+    pub fn publish<M>(&self, topic: &str, msg: M) -> Result<usize, TopicError>
+    where
+        M: ActorMessage + Clone + 'static,
+    {
+        let mut matches = self.collect(topic)?;
+        self.counters.published.fetch_add(1, Ordering::Relaxed);
+        self.counters.visited.fetch_add(matches.len() as u64, Ordering::Relaxed);
+        let type_id = TypeId::of::<M>();
+        let len = matches.len();
+        matches.retain(|subscription| subscription.type_id == type_id);
+        self.counters
+            .type_mismatches
+            .fetch_add((len - matches.len()) as u64, Ordering::Relaxed);
+        matches.sort_by(|a, b| a.address.cmp(&b.address));
+        matches.dedup_by(|a, b| a.address == b.address);
+        // delivered without holding the lock, so that full mailboxes cannot block subscriptions
+        for subscription in matches.iter() {
+            (subscription.deliver)(&msg);
+        }
+        self.counters.delivered.fetch_add(matches.len() as u64, Ordering::Relaxed);
+        Ok(matches.len())
+    }
+
+    /// Delivers a clone of `msg` to every Actor [subscribed](#method.subscribe) to messages of type `M`, returns the number of receivers
+    ///
+    /// Without subscribers nothing is delivered and nothing ends up as dead letter
+    pub fn broadcast<M>(&self, msg: M) -> usize
+    where
+        M: ActorMessage + Clone + 'static,
+    {
+        // cloned, so that handlers can broadcast or subscribe while messages are delivered
+        let matches = match self.subscriptions.read().unwrap().by_type.get(&TypeId::of::<M>()) {
+            Some(subscriptions) => subscriptions.clone(),
+            None => Vec::new(),
+        };
+        self.counters.published.fetch_add(1, Ordering::Relaxed);
+        self.counters.visited.fetch_add(matches.len() as u64, Ordering::Relaxed);
+        for subscription in matches.iter() {
+            (subscription.deliver)(&msg);
+        }
+        self.counters.delivered.fetch_add(matches.len() as u64, Ordering::Relaxed);
+        matches.len()
+    }
+
+    /// Addresses of all Actors subscribed to a pattern matching `topic`, regardless of their message type
+    pub fn matching_subscribers(&self, topic: &str) -> Result<Vec<ActorAddress>, TopicError> {
+        let mut addresses: Vec<ActorAddress> = self
+            .collect(topic)?
+            .iter()
+            .map(|subscription| subscription.address.clone())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        Ok(addresses)
+    }
+
+    pub fn metrics(&self) -> EventBusMetrics {
+        EventBusMetrics {
+            published: self.counters.published.load(Ordering::Relaxed),
+            delivered: self.counters.delivered.load(Ordering::Relaxed),
+            visited: self.counters.visited.load(Ordering::Relaxed),
+            type_mismatches: self.counters.type_mismatches.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Whether `topic` matches `pattern`, without subscribing anything
+    pub fn matches(pattern: &str, topic: &str) -> Result<bool, TopicError> {
+        let pattern = parse_pattern(pattern)?;
+        let topic = parse_topic(topic)?;
+        for (i, segment) in pattern.iter().enumerate() {
+            match *segment {
+                MULTI_LEVEL => return Ok(true),
+                SINGLE_LEVEL if i < topic.len() => {}
+                _ if topic.get(i) == Some(segment) => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(pattern.len() == topic.len())
+    }
+
+    fn collect(&self, topic: &str) -> Result<Vec<Arc<Subscription>>, TopicError> {
+        let segments = parse_topic(topic)?;
+        let mut matches = Vec::new();
+        self.subscriptions.read().unwrap().root.collect(&segments, &mut matches);
+        Ok(matches)
+    }
+}
+
+fn parse_topic(topic: &str) -> Result<Vec<&str>, TopicError> {
+    if topic.is_empty() || topic.contains(['*', '#']) {
+        return Err(TopicError::InvalidTopic);
+    }
+    Ok(topic.split('.').collect())
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<&str>, TopicError> {
+    if pattern.is_empty() {
+        return Err(TopicError::InvalidPattern);
+    }
+    let segments: Vec<&str> = pattern.split('.').collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let is_wildcard = *segment == SINGLE_LEVEL || *segment == MULTI_LEVEL;
+        if !is_wildcard && segment.contains(['*', '#']) {
+            return Err(TopicError::InvalidPattern);
+        }
+        if *segment == MULTI_LEVEL && i != segments.len() - 1 {
+            return Err(TopicError::InvalidPattern);
+        }
+    }
+    Ok(segments)
+}
@@ -0,0 +1,138 @@
+use crate::actor::actor_address::ActorAddress;
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const LOG_HEADER: &str = "tyractorsaur-execution-log v1";
+const RECORD_BUFFER: usize = 65536;
+
+enum RecordEvent {
+    Handled(Arc<str>, &'static str),
+    Finish,
+}
+
+/// Appends every scheduling decision to the file configured through `general.record_execution`
+///
+/// Events are written by a separate thread. If the writer can not keep up, events are dropped and the log is flagged as degraded
+#[derive(Clone)]
+pub struct ExecutionRecorder {
+    sender: Sender<RecordEvent>,
+    degraded: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+    done: Arc<Mutex<Receiver<()>>>,
+}
+
+impl ExecutionRecorder {
+    pub fn new(path: &str) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "{}", LOG_HEADER)?;
+        let (sender, receiver) = bounded(RECORD_BUFFER);
+        let (done_in, done_out) = bounded(1);
+        let degraded = Arc::new(AtomicBool::new(false));
+        let is_degraded = degraded.clone();
+        std::thread::spawn(move || {
+            let mut degraded_written = false;
+            for event in receiver.iter() {
+                if !degraded_written && is_degraded.load(Ordering::Relaxed) {
+                    degraded_written = true;
+                    let _ = writeln!(writer, "D");
+                }
+                match event {
+                    RecordEvent::Handled(actor, type_name) => {
+                        let _ = writeln!(writer, "H\t{}\t{}", actor, type_name);
+                    }
+                    RecordEvent::Finish => {
+                        let _ = writeln!(writer, "END");
+                        let _ = writer.flush();
+                        let _ = done_in.send(());
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            sender,
+            degraded,
+            finished: Arc::new(AtomicBool::new(false)),
+            done: Arc::new(Mutex::new(done_out)),
+        })
+    }
+
+    /// Returns the prefix that identifies the Actor within the log
+    pub fn actor_key(address: &ActorAddress) -> Arc<str> {
+        Arc::from(format!("{}\t{}", address.pool, address.actor))
+    }
+
+    pub fn record_handled(&self, actor: &Arc<str>, type_name: &'static str) {
+        if let Err(TrySendError::Full(_)) = self.sender.try_send(RecordEvent::Handled(actor.clone(), type_name)) {
+            self.degraded.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Writes the end marker and waits until everything is flushed, only the first call has an effect
+    pub fn finish(&self) {
+        if self.finished.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        if self.sender.send(RecordEvent::Finish).is_ok() {
+            let _ = self.done.lock().unwrap().recv_timeout(Duration::from_secs(10));
+        }
+    }
+}
+
+/// Result of [ActorSystem.replay_execution](../prelude/struct.ActorSystem.html#method.replay_execution)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayStatus {
+    /// the whole recording has been replayed
+    Completed,
+    /// the log ended without end marker, i.e. because it has been truncated
+    EndOfLog,
+    /// the recording dropped events from this line on, everything before has been replayed
+    Degraded { line: usize },
+    /// the log references an Actor that does not exist in the replay system
+    UnknownActor { line: usize },
+    /// the Actor handled a different message type than recorded
+    Diverged { line: usize },
+    /// the file is not an execution log
+    InvalidLog,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub status: ReplayStatus,
+    /// replayed Actor names with the type name of the handled message, in execution order
+    pub handled: Vec<(String, String)>,
+}
+
+pub enum LogEntry<'a> {
+    Handled { pool: &'a str, actor: &'a str, type_name: &'a str },
+    Degraded,
+    End,
+}
+
+/// Parses all complete lines, a partially written last line is ignored
+pub fn parse_log(content: &str) -> Option<Vec<Option<LogEntry<'_>>>> {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    // everything after the last newline is either empty or a partially written line
+    lines.pop();
+    let mut lines = lines.into_iter();
+    if lines.next() != Some(LOG_HEADER) {
+        return None;
+    }
+    Some(
+        lines
+            .map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                match fields.as_slice() {
+                    ["H", pool, actor, type_name] => Some(LogEntry::Handled { pool, actor, type_name }),
+                    ["D"] => Some(LogEntry::Degraded),
+                    ["END"] => Some(LogEntry::End),
+                    _ => None,
+                }
+            })
+            .collect(),
+    )
+}
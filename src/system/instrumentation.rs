@@ -0,0 +1,82 @@
+use crate::actor::actor_address::ActorAddress;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Observes the message flow of all Actors of the system, see [ActorSystem.set_instrumentation](../prelude/struct.ActorSystem.html#method.set_instrumentation)
+///
+/// Executed on the thread that sends or handles the message, implementations should return quickly and must not panic
+pub trait InstrumentationHook: Send + Sync {
+    /// executed right before the message is queued, a full mailbox can still reject it afterwards
+    fn on_send(&self, _address: &ActorAddress, _message_type: &'static str) {}
+    /// `queue_latency` is `None` for messages that have been queued before the hook has been registered
+    fn on_handle_start(&self, _address: &ActorAddress, _message_type: &'static str, _queue_latency: Option<Duration>) {}
+    /// the handler returned, `duration` does not include the construction of lazy messages
+    fn on_handle_end(&self, _address: &ActorAddress, _message_type: &'static str, _duration: Duration) {}
+    /// executed instead of [on_handle_end](#method.on_handle_end) if the handler panicked
+    fn on_panic(&self, _address: &ActorAddress, _message_type: &'static str) {}
+}
+
+/// Shared between the system and all mailboxes
+#[derive(Clone)]
+pub(crate) struct Instrumentation {
+    /// checked before the lock, so that a system without a hook does not pay for it
+    is_enabled: Arc<AtomicBool>,
+    hook: Arc<RwLock<Option<Arc<dyn InstrumentationHook>>>>,
+}
+
+impl Instrumentation {
+    pub fn new() -> Self {
+        Self {
+            is_enabled: Arc::new(AtomicBool::new(false)),
+            hook: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub fn set(&self, hook: Arc<dyn InstrumentationHook>) {
+        *self.hook.write().unwrap() = Some(hook);
+        self.is_enabled.store(true, Ordering::Release);
+    }
+
+    pub fn get(&self) -> Option<Arc<dyn InstrumentationHook>> {
+        if !self.is_enabled.load(Ordering::Acquire) {
+            return None;
+        }
+        self.hook.read().unwrap().clone()
+    }
+}
+
+/// Emits a `tracing` event for every callback, at `TRACE` level for sends and handlers and at `ERROR` level for panics
+#[cfg(feature = "tracing")]
+pub struct TracingInstrumentation {}
+
+#[cfg(feature = "tracing")]
+impl InstrumentationHook for TracingInstrumentation {
+    fn on_send(&self, address: &ActorAddress, message_type: &'static str) {
+        tracing::trace!(pool = %address.pool, actor = %address.actor, message_type, "message sent");
+    }
+
+    fn on_handle_start(&self, address: &ActorAddress, message_type: &'static str, queue_latency: Option<Duration>) {
+        tracing::trace!(
+            pool = %address.pool,
+            actor = %address.actor,
+            message_type,
+            queue_latency_us = queue_latency.map(|latency| latency.as_micros() as u64),
+            "message handling started"
+        );
+    }
+
+    fn on_handle_end(&self, address: &ActorAddress, message_type: &'static str, duration: Duration) {
+        tracing::trace!(
+            pool = %address.pool,
+            actor = %address.actor,
+            message_type,
+            duration_us = duration.as_micros() as u64,
+            "message handled"
+        );
+    }
+
+    fn on_panic(&self, address: &ActorAddress, message_type: &'static str) {
+        tracing::error!(pool = %address.pool, actor = %address.actor, message_type, "message handler panicked");
+    }
+}
@@ -0,0 +1,250 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::system::system_state::SystemState;
+use crossbeam_channel::{bounded, Sender};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Topic of the [EventBus](../prelude/struct.EventBus.html) on which every [IntegrityReport](./struct.IntegrityReport.html) is published
+pub const INTEGRITY_TOPIC: &str = "system.integrity";
+
+/// Maximum time [ActorSystem.sweep_integrity](../prelude/struct.ActorSystem.html#method.sweep_integrity) waits for the wakeup loop
+const SWEEP_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Entries of the internal registries of a system, per registry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntegrityCounts {
+    /// Actors waiting for a wakeup
+    pub sleeping_actors: usize,
+    /// recent wakeups, used to drop duplicate wakeups, expired entries are pruned by the wakeup loop and never reported as reclaimed
+    pub wake_deduplication: usize,
+    /// wakeups scheduled for a point in time, e.g. for suspensions and request chains, and scheduled messages
+    pub scheduled_wakeups: usize,
+    pub actor_metrics: usize,
+    pub protocols: usize,
+    /// Actors spawned suspended that have not been released yet
+    pub awaiting_release: usize,
+    /// children of Actors spawned through [ActorContext.spawn_child](../prelude/struct.ActorContext.html#method.spawn_child), stopped together with their parent
+    pub children: usize,
+    /// callbacks waiting for an Actor to be spawned, e.g. of [SuspendCondition::UntilActorStarted](../prelude/enum.SuspendCondition.html#variant.UntilActorStarted)
+    pub activation_watchers: usize,
+    /// Actors subscribed to the [EventBus](../prelude/struct.EventBus.html)
+    pub event_subscriptions: usize,
+}
+
+impl IntegrityCounts {
+    pub fn total(&self) -> usize {
+        self.categories().iter().map(|(_, count)| count).sum()
+    }
+
+    /// Name and count of every registry
+    pub fn categories(&self) -> [(&'static str, usize); 9] {
+        [
+            ("sleeping_actors", self.sleeping_actors),
+            ("wake_deduplication", self.wake_deduplication),
+            ("scheduled_wakeups", self.scheduled_wakeups),
+            ("actor_metrics", self.actor_metrics),
+            ("protocols", self.protocols),
+            ("awaiting_release", self.awaiting_release),
+            ("children", self.children),
+            ("activation_watchers", self.activation_watchers),
+            ("event_subscriptions", self.event_subscriptions),
+        ]
+    }
+
+    fn add(&mut self, other: &IntegrityCounts) {
+        self.sleeping_actors += other.sleeping_actors;
+        self.wake_deduplication += other.wake_deduplication;
+        self.scheduled_wakeups += other.scheduled_wakeups;
+        self.actor_metrics += other.actor_metrics;
+        self.protocols += other.protocols;
+        self.awaiting_release += other.awaiting_release;
+        self.children += other.children;
+        self.activation_watchers += other.activation_watchers;
+        self.event_subscriptions += other.event_subscriptions;
+    }
+}
+
+/// Result of a single integrity sweep, published on [INTEGRITY_TOPIC](./constant.INTEGRITY_TOPIC.html)
+///
+/// A nonzero `reclaimed` count means an entry outlived its Actor, which is a bug in the cleanup of that registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntegrityReport {
+    /// number of the sweep, starting at 1
+    pub sweep: u64,
+    pub duration: Duration,
+    /// orphaned entries removed by this sweep
+    pub reclaimed: IntegrityCounts,
+    /// entries left after this sweep
+    pub retained: IntegrityCounts,
+    /// orphaned registry entries seen for the first time, they are reclaimed by the next sweep if they are still orphaned
+    pub suspected: usize,
+}
+
+impl ActorMessage for IntegrityReport {}
+
+/// Registries of the system state keyed by the address of an Actor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Registry {
+    ActorMetrics,
+    Protocols,
+    AwaitingRelease,
+    Children,
+    EventSubscriptions,
+}
+
+impl Registry {
+    fn count(self, counts: &mut IntegrityCounts) -> &mut usize {
+        match self {
+            Registry::ActorMetrics => &mut counts.actor_metrics,
+            Registry::Protocols => &mut counts.protocols,
+            Registry::AwaitingRelease => &mut counts.awaiting_release,
+            Registry::Children => &mut counts.children,
+            Registry::EventSubscriptions => &mut counts.event_subscriptions,
+        }
+    }
+}
+
+/// Reports of a system, shared between the wakeup loop and the [ActorSystem](../prelude/struct.ActorSystem.html)
+#[derive(Default)]
+pub(crate) struct IntegrityState {
+    last_report: Mutex<Option<IntegrityReport>>,
+    reclaimed_total: Mutex<IntegrityCounts>,
+    /// Actors that have been added as sleeping while they were sleeping already
+    duplicate_entries: AtomicUsize,
+}
+
+impl IntegrityState {
+    pub fn get_last_report(&self) -> Option<IntegrityReport> {
+        self.last_report.lock().unwrap().clone()
+    }
+
+    pub fn get_reclaimed_total(&self) -> IntegrityCounts {
+        *self.reclaimed_total.lock().unwrap()
+    }
+
+    pub fn increment_duplicate_entries(&self) {
+        self.duplicate_entries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_duplicate_entries(&self) -> usize {
+        self.duplicate_entries.load(Ordering::Relaxed)
+    }
+
+    /// Waits for the wakeup loop to sweep, `None` if it does not run or did not answer in time
+    pub fn request_sweep(requests: &Sender<Sender<IntegrityReport>>) -> Option<IntegrityReport> {
+        let (reply_in, reply_out) = bounded(1);
+        requests.send(reply_in).ok()?;
+        reply_out.recv_timeout(SWEEP_REQUEST_TIMEOUT).ok()
+    }
+}
+
+/// Runs inside the wakeup loop, owns the orphans seen by the previous sweep
+pub(crate) struct IntegritySweeper {
+    interval: Duration,
+    last_sweep: Instant,
+    sweep: u64,
+    /// registry entries are only reclaimed if they are orphaned in two consecutive sweeps, they might belong to an Actor that is being spawned
+    suspected: HashSet<(Registry, ActorAddress)>,
+    /// sleeping Actors that are no longer registered, same as `suspected`
+    suspected_sleeping: HashSet<ActorAddress>,
+    state: Arc<IntegrityState>,
+}
+
+impl IntegritySweeper {
+    /// A zero interval disables periodic sweeps, requested sweeps still run
+    pub fn new(interval: Duration, state: Arc<IntegrityState>) -> Self {
+        Self {
+            interval,
+            last_sweep: Instant::now(),
+            sweep: 0,
+            suspected: HashSet::new(),
+            suspected_sleeping: HashSet::new(),
+            state,
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        !self.interval.is_zero() && self.last_sweep.elapsed() >= self.interval
+    }
+
+    /// Returns `true` if the sleeping Actor is orphaned for the second sweep in a row
+    pub fn confirm_sleeping(&self, address: &ActorAddress) -> bool {
+        self.suspected_sleeping.contains(address)
+    }
+
+    pub fn set_suspected_sleeping(&mut self, suspected: HashSet<ActorAddress>) {
+        self.suspected_sleeping = suspected;
+    }
+
+    /// Reclaims registry entries of the system state that are orphaned for the second sweep in a row and dead activation watchers
+    ///
+    /// Every entry is checked and removed on its own, so that each shard is only locked briefly
+    pub fn sweep_registries(&mut self, system_state: &SystemState, reclaimed: &mut IntegrityCounts, examples: &mut Vec<(&'static str, ActorAddress)>) {
+        let orphans: HashSet<(Registry, ActorAddress)> = system_state.find_registry_orphans().into_iter().collect();
+        let mut suspected = HashSet::new();
+        for orphan in orphans {
+            if !self.suspected.contains(&orphan) {
+                suspected.insert(orphan);
+                continue;
+            }
+            let (registry, address) = orphan;
+            if system_state.reclaim_registry_entry(registry, &address) {
+                *registry.count(reclaimed) += 1;
+                examples.push((registry_name(registry), address));
+            }
+        }
+        self.suspected = suspected;
+        for address in system_state.reclaim_dead_activation_watchers() {
+            reclaimed.activation_watchers += 1;
+            examples.push(("activation_watchers", address));
+        }
+    }
+
+    /// Publishes the report and logs every registry with reclaimed entries together with an example address
+    pub fn finish(
+        &mut self,
+        system_state: &SystemState,
+        started: Instant,
+        reclaimed: IntegrityCounts,
+        retained: IntegrityCounts,
+        examples: Vec<(&'static str, ActorAddress)>,
+    ) -> IntegrityReport {
+        self.sweep += 1;
+        self.last_sweep = Instant::now();
+        let report = IntegrityReport {
+            sweep: self.sweep,
+            duration: started.elapsed(),
+            reclaimed,
+            retained,
+            suspected: self.suspected.len() + self.suspected_sleeping.len(),
+        };
+        for (category, count) in reclaimed.categories().iter().filter(|(_, count)| *count > 0) {
+            let example = examples
+                .iter()
+                .find(|(name, _)| name == category)
+                .map(|(_, address)| format!("{:?}", address))
+                .unwrap_or_default();
+            println!(
+                "INTEGRITY WARNING: reclaimed {} orphaned entries of {} in sweep {}, e.g. {}",
+                count, category, self.sweep, example
+            );
+        }
+        self.state.reclaimed_total.lock().unwrap().add(&reclaimed);
+        *self.state.last_report.lock().unwrap() = Some(report.clone());
+        let _ = system_state.get_event_bus().publish(INTEGRITY_TOPIC, report.clone());
+        report
+    }
+}
+
+fn registry_name(registry: Registry) -> &'static str {
+    match registry {
+        Registry::ActorMetrics => "actor_metrics",
+        Registry::Protocols => "protocols",
+        Registry::AwaitingRelease => "awaiting_release",
+        Registry::Children => "children",
+        Registry::EventSubscriptions => "event_subscriptions",
+    }
+}
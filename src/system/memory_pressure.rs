@@ -0,0 +1,233 @@
+use crate::message::actor_message::ActorMessage;
+use crate::system::event_bus::EventBus;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Topic of the [EventBus](./struct.EventBus.html) on which every [SystemMemoryPressure](./struct.SystemMemoryPressure.html) is published
+pub const MEMORY_PRESSURE_TOPIC: &str = "system.memory_pressure";
+
+/// Enables the memory monitor, memory usage is sampled on the wakeup loop every `sample_interval`
+///
+/// Fractions are relative to the memory limit of the process, see [ActorSystem.set_memory_source](../prelude/struct.ActorSystem.html#method.set_memory_source)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct MemoryPressureConfig {
+    /// switches to [MemoryMode::Degraded](./enum.MemoryMode.html#variant.Degraded) once reached
+    pub soft_limit_fraction: f64,
+    /// switches to [MemoryMode::Critical](./enum.MemoryMode.html#variant.Critical) once reached
+    pub hard_limit_fraction: f64,
+    /// restores [MemoryMode::Normal](./enum.MemoryMode.html#variant.Normal) once usage fell below
+    pub recovery_fraction: f64,
+    pub sample_interval: Duration,
+}
+
+impl MemoryPressureConfig {
+    /// Panics unless `recovery_fraction < soft_limit_fraction <= hard_limit_fraction`
+    pub fn new(
+        soft_limit_fraction: f64,
+        hard_limit_fraction: f64,
+        recovery_fraction: f64,
+        sample_interval: Duration,
+    ) -> Self {
+        assert!(
+            recovery_fraction < soft_limit_fraction && soft_limit_fraction <= hard_limit_fraction,
+            "memory pressure fractions must satisfy recovery < soft <= hard"
+        );
+        Self {
+            soft_limit_fraction,
+            hard_limit_fraction,
+            recovery_fraction,
+            sample_interval,
+        }
+    }
+
+    /// Decides the next mode, each mode is only left once usage crossed the next lower threshold
+    fn next_mode(&self, mode: MemoryMode, fraction: f64) -> MemoryMode {
+        if fraction >= self.hard_limit_fraction {
+            return MemoryMode::Critical;
+        }
+        if fraction < self.recovery_fraction {
+            return MemoryMode::Normal;
+        }
+        match mode {
+            MemoryMode::Normal if fraction >= self.soft_limit_fraction => MemoryMode::Degraded,
+            MemoryMode::Critical if fraction < self.soft_limit_fraction => MemoryMode::Degraded,
+            mode => mode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryMode {
+    Normal,
+    /// sends to sheddable Actors are dropped
+    Degraded,
+    /// sends to sheddable Actors are dropped and they do not handle their queued messages
+    Critical,
+}
+
+impl MemoryMode {
+    fn from_u8(mode: u8) -> Self {
+        match mode {
+            1 => MemoryMode::Degraded,
+            2 => MemoryMode::Critical,
+            _ => MemoryMode::Normal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemorySample {
+    /// bytes in use
+    pub used: u64,
+    /// bytes available to the process
+    pub limit: u64,
+}
+
+impl MemorySample {
+    pub fn fraction(&self) -> f64 {
+        if self.limit == 0 {
+            return 0.0;
+        }
+        self.used as f64 / self.limit as f64
+    }
+}
+
+/// Published on [MEMORY_PRESSURE_TOPIC](./constant.MEMORY_PRESSURE_TOPIC.html) before a new [MemoryMode](./enum.MemoryMode.html) takes effect
+///
+/// Subscribers receive it before any send to them is shed, it is never shed itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemMemoryPressure {
+    pub previous: MemoryMode,
+    pub mode: MemoryMode,
+    pub sample: MemorySample,
+}
+
+impl ActorMessage for SystemMemoryPressure {}
+
+/// Mode, counters and last sample of the memory monitor, see [ActorSystem.memory_status](../prelude/struct.ActorSystem.html#method.memory_status)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryStatus {
+    pub mode: MemoryMode,
+    pub last_sample: Option<MemorySample>,
+    pub transitions: u64,
+}
+
+type MemorySource = Box<dyn Fn() -> Option<MemorySample> + Send + Sync>;
+
+/// Shared between the system, the mailboxes of sheddable Actors and the wakeup loop that samples the memory
+#[derive(Clone)]
+pub struct MemoryPressureState {
+    mode: Arc<AtomicU8>,
+    transitions: Arc<AtomicU64>,
+    last_sample: Arc<Mutex<Option<MemorySample>>>,
+    source: Arc<Mutex<Option<MemorySource>>>,
+}
+
+impl MemoryPressureState {
+    pub fn new() -> Self {
+        #[cfg(target_os = "linux")]
+        let source: Option<MemorySource> = Some(Box::new(cgroup_memory_sample));
+        #[cfg(not(target_os = "linux"))]
+        let source: Option<MemorySource> = None;
+        Self {
+            mode: Arc::new(AtomicU8::new(MemoryMode::Normal as u8)),
+            transitions: Arc::new(AtomicU64::new(0)),
+            last_sample: Arc::new(Mutex::new(None)),
+            source: Arc::new(Mutex::new(source)),
+        }
+    }
+
+    pub fn get_mode(&self) -> MemoryMode {
+        MemoryMode::from_u8(self.mode.load(Ordering::SeqCst))
+    }
+
+    pub fn set_source(&self, source: MemorySource) {
+        *self.source.lock().unwrap() = Some(source);
+    }
+
+    pub fn get_status(&self) -> MemoryStatus {
+        MemoryStatus {
+            mode: self.get_mode(),
+            last_sample: *self.last_sample.lock().unwrap(),
+            transitions: self.transitions.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// Samples the memory on the wakeup loop
+pub struct MemoryMonitor {
+    config: MemoryPressureConfig,
+    state: MemoryPressureState,
+    event_bus: EventBus,
+    next_sample: Instant,
+}
+
+impl MemoryMonitor {
+    pub fn new(config: MemoryPressureConfig, state: MemoryPressureState, event_bus: EventBus) -> Self {
+        Self {
+            config,
+            state,
+            event_bus,
+            next_sample: Instant::now(),
+        }
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.config.sample_interval
+    }
+
+    /// Returns `true` if the Critical mode has been left, so that paused Actors can be woken up
+    pub fn sample(&mut self) -> bool {
+        let now = Instant::now();
+        if now < self.next_sample {
+            return false;
+        }
+        self.next_sample = now + self.config.sample_interval;
+        let sample = match self.state.source.lock().unwrap().as_ref().and_then(|source| source()) {
+            Some(sample) => sample,
+            None => return false,
+        };
+        *self.state.last_sample.lock().unwrap() = Some(sample);
+        let previous = self.state.get_mode();
+        let mode = self.config.next_mode(previous, sample.fraction());
+        if mode == previous {
+            return false;
+        }
+        // published before the mode takes effect, so that subscribers can react before their sends are shed
+        let _ = self.event_bus.publish(
+            MEMORY_PRESSURE_TOPIC,
+            SystemMemoryPressure {
+                previous,
+                mode,
+                sample,
+            },
+        );
+        self.state.mode.store(mode as u8, Ordering::SeqCst);
+        self.state.transitions.fetch_add(1, Ordering::SeqCst);
+        previous == MemoryMode::Critical
+    }
+}
+
+/// Reads the limit and usage of the cgroup of the process, supports cgroup v2 and v1
+///
+/// `None` if the cgroup has no limit
+#[cfg(target_os = "linux")]
+pub fn cgroup_memory_sample() -> Option<MemorySample> {
+    fn read(path: &str) -> Option<u64> {
+        std::fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+    let (limit, used) = match read("/sys/fs/cgroup/memory.current") {
+        Some(used) => (read("/sys/fs/cgroup/memory.max")?, used),
+        None => (
+            read("/sys/fs/cgroup/memory/memory.limit_in_bytes")?,
+            read("/sys/fs/cgroup/memory/memory.usage_in_bytes")?,
+        ),
+    };
+    // cgroup v1 reports a huge number instead of an unset limit
+    if limit >= i64::MAX as u64 / 2 {
+        return None;
+    }
+    Some(MemorySample { used, limit })
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// What happens to sends while the system exceeds its message budget, see [MessageBudgetConfig]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SheddingPolicy {
+    /// the send fails with [SendError::Overloaded](../prelude/enum.SendError.html#variant.Overloaded)
+    #[default]
+    Reject,
+    /// the message is dropped and reported as dead letter with [DeadLetterReason::Overloaded](../prelude/enum.DeadLetterReason.html#variant.Overloaded)
+    DeadLetter,
+    /// the message is dropped silently, only [ActorSystem.budget_shed_count](../prelude/struct.ActorSystem.html#method.budget_shed_count) counts it
+    Drop,
+}
+
+/// Limits the amount of messages queued in all mailboxes of a system together, see `general.message_budget`
+///
+/// Sends are shed according to the [SheddingPolicy] as long as `max_total_queued_messages` messages are queued.
+/// Stop messages and the mailboxes of Actors that are [exempt from shedding](../prelude/struct.ActorBuilder.html#method.set_exempt_from_shedding) are neither counted nor shed
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use tyractorsaur::prelude::*;
+/// use std::time::Duration;
+///
+/// struct Ingest {}
+/// impl ActorMessage for Ingest {}
+/// struct Reconfigure {}
+/// impl ActorMessage for Reconfigure {}
+///
+/// struct Worker {}
+/// impl Actor for Worker {}
+/// impl Handler<Ingest> for Worker {
+///     fn handle(&mut self, _msg: Ingest, _context: &ActorContext<Self>) {}
+/// }
+/// impl Handler<Reconfigure> for Worker {
+///     fn handle(&mut self, _msg: Reconfigure, _context: &ActorContext<Self>) {}
+/// }
+/// struct WorkerFactory {}
+/// impl ActorFactory<Worker> for WorkerFactory {
+///     fn new_actor(&self, _context: ActorContext<Worker>) -> Worker {
+///         Worker {}
+///     }
+/// }
+///
+/// let mut config = TyractorsaurConfig::new().unwrap();
+/// config.general.message_budget = Some(MessageBudgetConfig::new(100, SheddingPolicy::Reject));
+/// let actor_system = ActorSystem::new(config);
+/// let builder = actor_system.builder().start_suspended(true);
+/// let first = builder.spawn("first", WorkerFactory {}).unwrap();
+/// let second = builder.spawn("second", WorkerFactory {}).unwrap();
+/// let control = builder.set_exempt_from_shedding(true).spawn("control", WorkerFactory {}).unwrap();
+///
+/// // neither mailbox is full, but together they exceed the budget
+/// for _ in 0..50 {
+///     first.send(Ingest {}).unwrap();
+///     second.send(Ingest {}).unwrap();
+/// }
+/// assert_eq!(100, actor_system.total_queued_messages());
+/// assert!(matches!(first.send(Ingest {}), Err(SendError::Overloaded(_))));
+/// assert!(matches!(second.send(Ingest {}), Err(SendError::Overloaded(_))));
+/// assert_eq!(2, actor_system.budget_shed_count());
+/// // the control plane is not affected
+/// control.send(Reconfigure {}).unwrap();
+/// assert_eq!(100, actor_system.total_queued_messages());
+///
+/// for worker in [&first, &second, &control] {
+///     worker.release();
+///     worker.stop();
+///     assert!(worker.await_stop(Duration::from_secs(1)));
+/// }
+/// assert_eq!(0, actor_system.total_queued_messages());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct MessageBudgetConfig {
+    pub max_total_queued_messages: usize,
+    #[serde(default)]
+    pub policy: SheddingPolicy,
+}
+
+impl MessageBudgetConfig {
+    pub fn new(max_total_queued_messages: usize, policy: SheddingPolicy) -> Self {
+        Self {
+            max_total_queued_messages,
+            policy,
+        }
+    }
+}
+
+/// Messages queued in all counted mailboxes of a system
+pub(crate) struct MessageBudget {
+    config: MessageBudgetConfig,
+    queued: AtomicUsize,
+    shed: AtomicU64,
+}
+
+impl MessageBudget {
+    pub fn new(config: MessageBudgetConfig) -> Self {
+        Self {
+            config,
+            queued: AtomicUsize::new(0),
+            shed: AtomicU64::new(0),
+        }
+    }
+
+    /// Approximate, concurrent sends may exceed the budget by the amount of senders
+    pub fn is_exceeded(&self) -> bool {
+        self.queued.load(Ordering::Relaxed) >= self.config.max_total_queued_messages
+    }
+
+    pub fn get_policy(&self) -> SheddingPolicy {
+        self.config.policy
+    }
+
+    pub fn increment_shed(&self) {
+        self.shed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    pub fn get_shed(&self) -> u64 {
+        self.shed.load(Ordering::Relaxed)
+    }
+}
+
+/// Counts a queued message towards the message budget until it is dropped
+pub(crate) struct BudgetToken(Arc<MessageBudget>);
+
+impl BudgetToken {
+    pub fn new(budget: &Arc<MessageBudget>) -> Self {
+        budget.queued.fetch_add(1, Ordering::Relaxed);
+        Self(budget.clone())
+    }
+}
+
+impl Drop for BudgetToken {
+    fn drop(&mut self) {
+        self.0.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+}
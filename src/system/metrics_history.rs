@@ -0,0 +1,204 @@
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::config::global_config::HistoryConfig;
+use crate::system::system_state::SystemState;
+use crate::system::thread_pool_manager::ThreadPoolManager;
+use serde::Serialize;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Fixed size buffer that overwrites the oldest entry once it is full
+///
+/// Storage is allocated once on creation, pushing a sample never allocates
+pub struct RingBuffer<T>
+where
+    T: Copy,
+{
+    samples: Vec<T>,
+    capacity: usize,
+    next: usize,
+}
+
+impl<T> RingBuffer<T>
+where
+    T: Copy,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: Vec::with_capacity(capacity),
+            capacity,
+            next: 0,
+        }
+    }
+
+    pub fn push(&mut self, sample: T) {
+        if self.samples.len() < self.capacity {
+            self.samples.push(sample);
+        } else {
+            self.samples[self.next] = sample;
+        }
+        self.next = (self.next + 1) % self.capacity;
+    }
+
+    /// Returns all samples from oldest to newest
+    pub fn to_vec(&self) -> Vec<T> {
+        if self.samples.len() < self.capacity {
+            return self.samples.clone();
+        }
+        let mut result = Vec::with_capacity(self.capacity);
+        result.extend_from_slice(&self.samples[self.next..]);
+        result.extend_from_slice(&self.samples[..self.next]);
+        result
+    }
+}
+
+/// Minimum, maximum and latest value of a single gauge within a history window
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Default)]
+pub struct GaugeSummary {
+    pub min: usize,
+    pub max: usize,
+    pub last: usize,
+}
+
+impl GaugeSummary {
+    fn from_values(values: impl Iterator<Item = usize>) -> Option<Self> {
+        let mut summary: Option<GaugeSummary> = None;
+        for value in values {
+            summary = Some(match summary {
+                None => GaugeSummary {
+                    min: value,
+                    max: value,
+                    last: value,
+                },
+                Some(s) => GaugeSummary {
+                    min: s.min.min(value),
+                    max: s.max.max(value),
+                    last: value,
+                },
+            });
+        }
+        summary
+    }
+}
+
+/// Single sample of an [Actor](../prelude/trait.Actor.html)s key gauges
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct ActorMetricsSample {
+    pub timestamp: SystemTime,
+    pub mailbox_len: usize,
+    /// messages processed since the previous sample
+    pub processed: usize,
+    /// total restarts since the Actor was spawned
+    pub restarts: usize,
+}
+
+/// Timestamped series of [ActorMetricsSample]s, ordered from oldest to newest
+#[derive(Debug, Serialize, Clone)]
+pub struct ActorMetricsHistory {
+    pub samples: Vec<ActorMetricsSample>,
+}
+
+/// Compact summary of an [ActorMetricsHistory]
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct ActorMetricsSummary {
+    pub mailbox_len: GaugeSummary,
+    pub processed: GaugeSummary,
+    pub restarts: GaugeSummary,
+}
+
+impl ActorMetricsHistory {
+    /// Returns `None` if no sample has been taken yet
+    pub fn summary(&self) -> Option<ActorMetricsSummary> {
+        Some(ActorMetricsSummary {
+            mailbox_len: GaugeSummary::from_values(self.samples.iter().map(|s| s.mailbox_len))?,
+            processed: GaugeSummary::from_values(self.samples.iter().map(|s| s.processed))?,
+            restarts: GaugeSummary::from_values(self.samples.iter().map(|s| s.restarts))?,
+        })
+    }
+}
+
+/// Single sample of a thread pools key gauges
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct PoolMetricsSample {
+    pub timestamp: SystemTime,
+    pub threads: usize,
+    /// Actors that are waiting to be picked up by a thread of the pool
+    pub queued_actors: usize,
+}
+
+/// Timestamped series of [PoolMetricsSample]s, ordered from oldest to newest
+#[derive(Debug, Serialize, Clone)]
+pub struct PoolMetricsHistory {
+    pub samples: Vec<PoolMetricsSample>,
+}
+
+/// Compact summary of a [PoolMetricsHistory]
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct PoolMetricsSummary {
+    pub threads: GaugeSummary,
+    pub queued_actors: GaugeSummary,
+}
+
+impl PoolMetricsHistory {
+    /// Returns `None` if no sample has been taken yet
+    pub fn summary(&self) -> Option<PoolMetricsSummary> {
+        Some(PoolMetricsSummary {
+            threads: GaugeSummary::from_values(self.samples.iter().map(|s| s.threads))?,
+            queued_actors: GaugeSummary::from_values(self.samples.iter().map(|s| s.queued_actors))?,
+        })
+    }
+}
+
+/// Samples all Actors and pools once per configured resolution
+///
+/// Owned by the wakeup loop. Instead of sampling every Actor at the start of an interval, the sweep is spread across the whole interval:
+/// each call to [sweep](#method.sweep) samples the share of Actors that is due according to the elapsed fraction of the interval
+pub struct HistorySweeper {
+    config: HistoryConfig,
+    interval_start: Instant,
+    timestamp: SystemTime,
+    enrolled: Vec<ActorMetrics>,
+    cursor: usize,
+}
+
+impl HistorySweeper {
+    pub fn new(config: HistoryConfig) -> Self {
+        Self {
+            config,
+            interval_start: Instant::now(),
+            timestamp: SystemTime::now(),
+            enrolled: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Maximum time the wakeup loop may block before it has to call [sweep](#method.sweep) again
+    pub fn poll_interval(&self) -> Duration {
+        (self.config.resolution / 10).max(Duration::from_millis(1))
+    }
+
+    pub fn sweep(&mut self, system_state: &SystemState, thread_pool_manager: &ThreadPoolManager) {
+        let elapsed = self.interval_start.elapsed();
+        if elapsed >= self.config.resolution {
+            self.finish_interval();
+            self.interval_start = Instant::now();
+            self.timestamp = SystemTime::now();
+            self.enrolled.clear();
+            system_state.collect_actor_metrics(&mut self.enrolled);
+            self.cursor = 0;
+            thread_pool_manager.sample_history(self.timestamp);
+            return;
+        }
+        let due = (self.enrolled.len() as u128 * elapsed.as_nanos()
+            / self.config.resolution.as_nanos().max(1)) as usize;
+        while self.cursor < due.min(self.enrolled.len()) {
+            self.enrolled[self.cursor].sample_history(self.timestamp);
+            self.cursor += 1;
+        }
+    }
+
+    fn finish_interval(&mut self) {
+        while self.cursor < self.enrolled.len() {
+            self.enrolled[self.cursor].sample_history(self.timestamp);
+            self.cursor += 1;
+        }
+    }
+}
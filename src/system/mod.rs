@@ -1,8 +1,92 @@
+pub mod actor_group;
+pub mod actor_info;
+pub mod actor_selection;
 pub mod actor_system;
+#[cfg(feature = "async")]
+pub mod await_shutdown;
+pub mod bulk_spawn;
+pub mod channel_ingestion;
+pub mod channel_sink;
+pub mod cycle_detection;
+pub mod dead_letters;
+pub mod drop_reporter;
+pub mod entity_region;
+#[cfg(feature = "dyn-spawn")]
+pub mod dyn_spawn;
+pub mod event_bus;
+pub mod instrumentation;
+pub mod integrity;
+pub mod memory_pressure;
+pub mod message_budget;
+#[cfg(feature = "replay")]
+pub mod execution_recorder;
+pub mod metrics_history;
+pub mod name_guard;
+pub mod panic_handler;
+pub(crate) mod peers;
+pub mod pipe;
+#[cfg(feature = "metrics")]
+pub(crate) mod prometheus;
+pub mod pool_stats;
+pub mod remote_transport;
+pub mod shutdown_hooks;
+pub mod slow_handler;
+pub mod startup_order;
+#[cfg(all(feature = "signals", unix))]
+pub mod signals;
 pub mod system_state;
-mod thread_pool_manager;
+pub(crate) mod thread_pool_manager;
+pub(crate) mod timer_queue;
 pub mod wakeup_manager;
+pub mod worker_stats;
+pub(crate) mod yield_point;
 
 pub mod prelude {
+    pub use crate::system::actor_group::{ActorGroupBuilder, GroupHandle, GroupSpawnError};
+    pub use crate::system::actor_info::{ActorInfo, ActorStatus};
+    pub use crate::system::actor_selection::ActorSelection;
     pub use crate::system::actor_system::ActorSystem;
+    #[cfg(feature = "async")]
+    pub use crate::system::await_shutdown::AwaitShutdown;
+    pub use crate::system::bulk_spawn::{
+        BulkSpawnConfig, BulkSpawnProgress, BulkSpawnReport, SpawnError, SpawnOutcome, SpawnSpec,
+    };
+    pub use crate::system::channel_ingestion::{IngestConfig, IngestHandle, IngestOverflow, IngestShutdown, IngestStats};
+    pub use crate::system::channel_sink::{SinkActor, SinkReceiver, SinkRecvError};
+    pub use crate::system::cycle_detection::{CycleConfig, CycleExempt, CycleSuspected, CYCLE_TOPIC};
+    pub use crate::system::dead_letters::{DeadLetter, DeadLetterReason};
+    pub use crate::system::drop_reporter::{DropReporter, DropStats, DroppedMessage, LogDropReporter};
+    #[cfg(feature = "dyn-spawn")]
+    pub use crate::system::dyn_spawn::{DynActorFactory, DynActorRef, DynFactory, DynSpawnConfig, SpawnDynError};
+    pub use crate::system::entity_region::{EntityRegion, EntitySendError};
+    pub use crate::system::event_bus::{EventBus, EventBusMetrics, TopicError};
+    pub use crate::system::instrumentation::InstrumentationHook;
+    #[cfg(feature = "tracing")]
+    pub use crate::system::instrumentation::TracingInstrumentation;
+    pub use crate::system::integrity::{IntegrityCounts, IntegrityReport, INTEGRITY_TOPIC};
+    #[cfg(target_os = "linux")]
+    pub use crate::system::memory_pressure::cgroup_memory_sample;
+    pub use crate::system::memory_pressure::{
+        MemoryMode, MemoryPressureConfig, MemorySample, MemoryStatus, SystemMemoryPressure, MEMORY_PRESSURE_TOPIC,
+    };
+    pub use crate::system::message_budget::{MessageBudgetConfig, SheddingPolicy};
+    #[cfg(feature = "replay")]
+    pub use crate::system::execution_recorder::{ReplayReport, ReplayStatus};
+    pub use crate::system::metrics_history::{
+        ActorMetricsHistory, ActorMetricsSample, ActorMetricsSummary, GaugeSummary, PoolMetricsHistory,
+        PoolMetricsSample, PoolMetricsSummary,
+    };
+    pub use crate::system::name_guard::{GuardConfig, GuardScope, TyractorsaurError};
+    pub use crate::system::panic_handler::PanicInfoContext;
+    pub use crate::system::pipe::{PipeEndReason, PipeHandle};
+    pub use crate::system::pool_stats::{PoolError, PoolLoad, PoolRef, PoolStats};
+    pub use crate::system::remote_transport::RemoteTransport;
+    pub use crate::system::shutdown_hooks::ShutdownHookError;
+    pub use crate::system::slow_handler::SlowHandler;
+    pub use crate::system::startup_order::{DependencyFailed, DependencyFailurePolicy, DEPENDENCY_TOPIC};
+    pub use crate::system::worker_stats::{PoolWorkerStats, WorkerStats};
+    #[cfg(all(feature = "signals", unix))]
+    pub use crate::system::signals::{
+        SecondSignalBehavior, Signal, SignalConfig, SignalError, SignalGuard, SignalShutdownReport,
+    };
 }
@@ -0,0 +1,128 @@
+use crate::actor::rng::ActorRng;
+use crate::config::tyractorsaur_config::ConfigLoadError;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Range within which [GuardConfig](./struct.GuardConfig.html) keeps a system name unique
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum GuardScope {
+    /// all processes that share the lock directory, usually all processes of a host
+    Host,
+}
+
+/// Keeps two running systems from using the same name, see [ActorSystem.try_new](../prelude/struct.ActorSystem.html#method.try_new)
+///
+/// Every system holds an advisory lock on `<dir>/<name>.lock` while it is running,
+/// every character of the name other than lowercase ASCII letters, digits, `-`, `_` and `.` is percent-encoded in the file name.
+/// The lock is released on a clean stop and by the OS if the process dies, lock files of dead processes are reclaimed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GuardConfig {
+    pub scope: GuardScope,
+    pub dir: PathBuf,
+}
+
+impl GuardConfig {
+    pub fn host(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            scope: GuardScope::Host,
+            dir: dir.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TyractorsaurError {
+    /// another live system holds the name, `holder_pid` is `None` if it has not written its PID yet
+    SystemNameInUse { name: String, holder_pid: Option<u32> },
+    /// the lock file could not be created or locked
+    NameGuardFailed { path: PathBuf, reason: String },
+    /// see [TyractorsaurConfig.check](../prelude/struct.TyractorsaurConfig.html#method.check)
+    InvalidConfig(ConfigLoadError),
+}
+
+/// Lock on the name of a running system, released when dropped
+pub(crate) struct NameGuard {
+    file: File,
+}
+
+impl NameGuard {
+    pub fn acquire(name: &str, config: &GuardConfig) -> Result<Self, TyractorsaurError> {
+        let path = config.dir.join(format!("{}.lock", file_name(name)));
+        let failed = |error: std::io::Error| TyractorsaurError::NameGuardFailed {
+            path: path.clone(),
+            reason: error.to_string(),
+        };
+        fs::create_dir_all(&config.dir).map_err(failed)?;
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(failed)?;
+        match file.try_lock_exclusive() {
+            Ok(()) => {}
+            Err(error) if error.kind() == fs2::lock_contended_error().kind() => {
+                return Err(TyractorsaurError::SystemNameInUse {
+                    name: String::from(name),
+                    holder_pid: read_pid(&mut file),
+                });
+            }
+            Err(error) => return Err(failed(error)),
+        }
+
+        // a clean stop empties the file, a PID is only left behind by a process that died
+        if let Some(pid) = read_pid(&mut file) {
+            println!("NAME GUARD WARNING: reclaimed stale lock {} of process {}", path.display(), pid);
+        }
+        write_pid(&mut file, Some(std::process::id())).map_err(failed)?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for NameGuard {
+    fn drop(&mut self) {
+        let _ = write_pid(&mut self.file, None);
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Percent-encodes every byte that is not allowed in a file name on all platforms, so that two names never share a lock file
+///
+/// Uppercase letters are encoded as well, names that only differ in case would share it on case-insensitive file systems otherwise
+fn file_name(name: &str) -> String {
+    let mut file_name = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => file_name.push(byte as char),
+            _ => file_name.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    file_name
+}
+
+fn read_pid(file: &mut File) -> Option<u32> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut content).ok()?;
+    content.trim().parse().ok()
+}
+
+fn write_pid(file: &mut File, pid: Option<u32>) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    if let Some(pid) = pid {
+        write!(file, "{}", pid)?;
+    }
+    file.sync_all()
+}
+
+/// Start time in milliseconds and a random suffix, tells apart two instances of a system with the same name
+pub(crate) fn new_incarnation() -> String {
+    let started = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+    format!("{:x}-{:08x}", started, ActorRng::from_entropy().next_u32())
+}
@@ -0,0 +1,80 @@
+use crate::actor::actor_address::ActorAddress;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Once};
+
+/// Handler of [ActorSystem.set_panic_handler](../prelude/struct.ActorSystem.html#method.set_panic_handler)
+pub(crate) type PanicHandler = Arc<dyn Fn(PanicInfoContext) + Send + Sync>;
+
+/// Panic of an Actor, handed to the handler of [ActorSystem.set_panic_handler](../prelude/struct.ActorSystem.html#method.set_panic_handler)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanicInfoContext {
+    pub address: ActorAddress,
+    pub pool_name: String,
+    /// restarts of the Actor before the panic
+    pub restarts: usize,
+    /// type name of the handled message, or `new_actor`, `recover`, `pre_start`, `on_idle_stop` and `post_stop`
+    pub message_type: &'static str,
+    /// `None` if the panic payload is neither a `&str` nor a `String`
+    pub payload: Option<String>,
+    pub location: Option<String>,
+}
+
+thread_local! {
+    /// set while the Executor runs code of an Actor, whose panics are reported by the panic handler instead of the panic hook
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Wraps the panic hook of the process once, panics outside of [catch] are still handed to the previous hook
+pub(crate) fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.with(Cell::get) {
+                LOCATION.with(|location| *location.borrow_mut() = info.location().map(|l| l.to_string()));
+                return;
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Same as `catch_unwind`, a panic is not printed by the panic hook and its location is kept for [take_location]
+pub(crate) fn catch<F, R>(f: F) -> Result<R, Box<dyn Any + Send>>
+where
+    F: FnOnce() -> R,
+{
+    let previous = IS_CATCHING.with(|is_catching| is_catching.replace(true));
+    let result = catch_unwind(AssertUnwindSafe(f));
+    IS_CATCHING.with(|is_catching| is_catching.set(previous));
+    result
+}
+
+/// Location of the last panic caught by [catch] on this thread
+pub(crate) fn take_location() -> Option<String> {
+    LOCATION.with(|location| location.borrow_mut().take())
+}
+
+/// Default panic handler, logs a single line per panic
+pub(crate) fn log_panic(info: PanicInfoContext) {
+    println!(
+        "ACTOR PANIC: pool={} actor={} restarts={} message_type={} payload={:?} location={}",
+        info.pool_name,
+        info.address.actor,
+        info.restarts,
+        info.message_type,
+        info.payload.as_deref().unwrap_or("<unknown>"),
+        info.location.as_deref().unwrap_or("<unknown>"),
+    );
+}
+
+/// Hands the panic to `handler`, a panicking handler is ignored
+pub(crate) fn notify(handler: &PanicHandler, info: PanicInfoContext) {
+    if catch(|| handler(info)).is_err() {
+        println!("PANIC HANDLER PANIC");
+    }
+}
@@ -0,0 +1,67 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::protocol::ProtocolError;
+use crate::message::message_size::MessageSizes;
+use crate::message::serialized_message::SerializedMessage;
+use crate::system::system_state::SystemState;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, Weak};
+
+/// Receiving side of a system, handed out weakly to the systems it has been registered with, see [ActorSystem.register_peer](../prelude/struct.ActorSystem.html#method.register_peer)
+pub(crate) struct PeerEndpoint {
+    name: String,
+    remote: String,
+    state: SystemState,
+    message_sizes: Arc<MessageSizes>,
+}
+
+impl PeerEndpoint {
+    pub fn new(name: String, remote: String, state: SystemState, message_sizes: Arc<MessageSizes>) -> Self {
+        Self {
+            name,
+            remote,
+            state,
+            message_sizes,
+        }
+    }
+
+    /// Hands `msg` to the Actor at `address`, the message size limit of this system applies
+    pub fn deliver(&self, address: &ActorAddress, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        if let Some(limit) = self.message_sizes.get_max_bytes() {
+            let size = msg.payload_size();
+            if size > limit {
+                return Err(ProtocolError::PayloadTooLarge { size, limit });
+            }
+        }
+        self.state.send_to_address(address, msg)
+    }
+
+    /// `true` if the address has been handed out by this system
+    pub fn owns(&self, address: &ActorAddress) -> bool {
+        address.remote == self.remote && address.system == self.name
+    }
+}
+
+/// Peers of a system by the `remote` of their Actors, shared by all clones of the system
+#[derive(Clone, Default)]
+pub(crate) struct Peers {
+    peers: Arc<RwLock<HashMap<String, Weak<PeerEndpoint>>>>,
+}
+
+impl Peers {
+    pub fn register(&self, peer: &Arc<PeerEndpoint>) {
+        self.peers.write().unwrap().insert(peer.remote.clone(), Arc::downgrade(peer));
+    }
+
+    /// `None` if the address does not belong to a registered peer, or if the peer is gone or stopped
+    pub fn get(&self, address: &ActorAddress) -> Option<Arc<PeerEndpoint>> {
+        let peer = self.peers.read().unwrap().get(&address.remote)?.upgrade();
+        match peer {
+            Some(peer) if !peer.state.is_stopped() => Some(peer).filter(|peer| peer.owns(address)),
+            _ => {
+                // the peer stopped, it does not come back
+                self.peers.write().unwrap().remove(&address.remote);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::actor::actor::Actor;
+use crate::actor::actor_config::MailboxOverflowPolicy;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::handler::Handler;
+use crate::message::actor_message::ActorMessage;
+use crate::message::send_error::SendError;
+use crate::system::channel_ingestion::{IngestHandle, IngestShared, IngestStats, Ingestion, Poll};
+use crossbeam_channel::{Receiver, TryRecvError};
+use std::panic::UnwindSafe;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Maximum amount of items a single pipe forwards before the next ingestion is polled
+const POLL_BUDGET: usize = 64;
+
+/// Reason why a pipe has ended, see [PipeHandle.end_reason](./struct.PipeHandle.html#method.end_reason)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PipeEndReason {
+    /// all senders of the channel have been dropped and everything that was left has been forwarded
+    Disconnected,
+    /// the target has stopped, the item that could not be delivered is counted as dropped
+    TargetStopped,
+    /// the system is stopping, items that are left in the channel are not forwarded anymore
+    SystemStopped,
+    /// [PipeHandle.cancel](./struct.PipeHandle.html#method.cancel) has been called
+    Cancelled,
+}
+
+struct PipeEnd {
+    reason: Mutex<Option<PipeEndReason>>,
+    ended: Condvar,
+}
+
+/// Returned by [ActorSystem.pipe_from_receiver](./struct.ActorSystem.html#method.pipe_from_receiver), dropping it does not end the pipe
+#[derive(Clone)]
+pub struct PipeHandle {
+    ingest: IngestHandle,
+    end: Arc<PipeEnd>,
+}
+
+impl PipeHandle {
+    /// Ends the pipe within a few milliseconds, even while it waits for room in the mailbox of its target
+    ///
+    /// Items that have already been forwarded are still handled, an item the pipe is waiting to deliver is counted as dropped
+    pub fn cancel(&self) {
+        self.ingest.cancel();
+    }
+
+    /// Items the target queued
+    ///
+    /// An item that made room for itself through [DropOldest](../prelude/enum.MailboxOverflowPolicy.html#variant.DropOldest) has been queued and counts as forwarded
+    pub fn forwarded(&self) -> u64 {
+        self.ingest.stats().forwarded
+    }
+
+    /// Items the target did not queue, e.g. because of its [overflow policy](../prelude/enum.MailboxOverflowPolicy.html) or because it stopped
+    pub fn dropped(&self) -> u64 {
+        self.ingest.stats().dropped
+    }
+
+    /// Counters of the pipe, `stalled` is the time it waited for room in the mailbox of its target
+    pub fn stats(&self) -> IngestStats {
+        self.ingest.stats()
+    }
+
+    /// `None` while the pipe is running
+    pub fn end_reason(&self) -> Option<PipeEndReason> {
+        *self.end.reason.lock().unwrap()
+    }
+
+    /// Waits until the pipe has ended, `None` if it is still running after the timeout
+    pub fn await_end(&self, timeout: Duration) -> Option<PipeEndReason> {
+        let reason = self.end.reason.lock().unwrap();
+        let (reason, _) = self.end.ended.wait_timeout_while(reason, timeout, |reason| reason.is_none()).unwrap();
+        *reason
+    }
+}
+
+/// Forwards every item of its channel to its target, polled by the reactor thread like every other ingestion
+pub(crate) struct Pipe<M, A>
+where
+    A: Actor + 'static,
+{
+    /// dropped once the pipe ended, so that the senders of the channel disconnect before the end is visible
+    receiver: Option<Receiver<M>>,
+    target: ActorWrapper<A>,
+    /// item that waits for room in the mailbox of a target that blocks its senders, it keeps its place in the order
+    pending: Option<M>,
+    shared: Arc<IngestShared>,
+    end: Arc<PipeEnd>,
+    reason: Option<PipeEndReason>,
+    stalled_since: Option<Instant>,
+}
+
+impl<M, A> Pipe<M, A>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    pub fn new(receiver: Receiver<M>, target: ActorWrapper<A>) -> (Self, PipeHandle) {
+        let shared = IngestShared::new(&receiver);
+        let end = Arc::new(PipeEnd {
+            reason: Mutex::new(None),
+            ended: Condvar::new(),
+        });
+        let handle = PipeHandle {
+            ingest: IngestHandle::new(shared.clone()),
+            end: end.clone(),
+        };
+        let pipe = Self {
+            receiver: Some(receiver),
+            target,
+            pending: None,
+            shared,
+            end,
+            reason: None,
+            stalled_since: None,
+        };
+        (pipe, handle)
+    }
+
+    fn check_end(&self, is_system_stopping: bool) -> Option<PipeEndReason> {
+        if self.shared.is_cancelled() {
+            return Some(PipeEndReason::Cancelled);
+        }
+        // the targets stop with the system, the stop of the system is the reason
+        if is_system_stopping {
+            return Some(PipeEndReason::SystemStopped);
+        }
+        if self.target.get_mailbox().is_stopped() {
+            return Some(PipeEndReason::TargetStopped);
+        }
+        None
+    }
+
+    /// Sends the item with the semantics of the mailbox of the target, returns `false` if the item has to wait for room
+    fn forward(&mut self, msg: M) -> Result<bool, PipeEndReason> {
+        match self.target.try_send_with_status(msg) {
+            Ok(status) if status.is_delivered() => self.shared.increment_forwarded(),
+            // dropped by the overflow policy, shed or cut by the cycle detection
+            Ok(_) => self.shared.increment_dropped(),
+            // a target that blocks its senders holds the pipe back until it made room
+            Err(SendError::Full(msg)) if self.target.get_mailbox().overflow_policy == MailboxOverflowPolicy::Block => {
+                self.pending = Some(msg);
+                return Ok(false);
+            }
+            Err(SendError::Stopped(_)) => {
+                self.shared.increment_dropped();
+                return Err(PipeEndReason::TargetStopped);
+            }
+            Err(_) => self.shared.increment_dropped(),
+        }
+        Ok(true)
+    }
+}
+
+impl<M, A> Ingestion for Pipe<M, A>
+where
+    A: Actor + Handler<M> + UnwindSafe + 'static,
+    M: ActorMessage + 'static,
+{
+    fn poll(&mut self, is_system_stopping: bool) -> Poll {
+        if let Some(reason) = self.check_end(is_system_stopping) {
+            self.reason = Some(reason);
+            return Poll::Finished;
+        }
+        let mut progress = 0;
+        let mut result = Poll::Idle;
+        while progress < POLL_BUDGET {
+            let msg = match self.pending.take() {
+                Some(msg) => msg,
+                None => match self.receiver.as_ref().map_or(Err(TryRecvError::Disconnected), |receiver| receiver.try_recv()) {
+                    Ok(msg) => msg,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.reason = Some(PipeEndReason::Disconnected);
+                        result = Poll::Finished;
+                        break;
+                    }
+                },
+            };
+            match self.forward(msg) {
+                Ok(true) => progress += 1,
+                Ok(false) => break,
+                Err(reason) => {
+                    self.reason = Some(reason);
+                    result = Poll::Finished;
+                    break;
+                }
+            }
+        }
+        let is_stalled = self.pending.is_some();
+        self.shared.record_stall(&mut self.stalled_since, is_stalled);
+        if let Some(receiver) = &self.receiver {
+            self.shared.set_channel_len(receiver.len());
+        }
+        if result == Poll::Idle && progress > 0 {
+            result = Poll::Progress;
+        }
+        result
+    }
+
+    fn finish(&mut self) {
+        if self.pending.take().is_some() {
+            self.shared.increment_dropped();
+        }
+        self.shared.record_stall(&mut self.stalled_since, false);
+        if let Some(receiver) = self.receiver.take() {
+            self.shared.set_channel_len(receiver.len());
+        }
+        self.shared.set_finished();
+        *self.end.reason.lock().unwrap() = self.reason;
+        self.end.ended.notify_all();
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Current size and backlog of a pool, see [ActorSystem.pool_stats](../prelude/struct.ActorSystem.html#method.pool_stats)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolStats {
+    /// worker threads that are running, including threads that exit after a shrinking [ActorSystem.resize_pool](../prelude/struct.ActorSystem.html#method.resize_pool)
+    pub threads: usize,
+    /// Actors that have been woken up and are waiting to be picked up by a thread of the pool
+    pub queued_actors: usize,
+    /// scheduling slots the workers of this pool executed for Actors of other pools, see [ThreadPoolConfig.with_steal_from](../prelude/struct.ThreadPoolConfig.html#method.with_steal_from)
+    pub stolen_slots: usize,
+    /// polls of idle workers that found no runnable Actor, see [ThreadPoolConfig.with_idle_strategy](../prelude/struct.ThreadPoolConfig.html#method.with_idle_strategy)
+    pub idle_polls: usize,
+}
+
+/// How backed up a pool is, see [ActorSystem.pool_load](../prelude/struct.ActorSystem.html#method.pool_load)
+///
+/// All values are maintained while the pool runs, reading them does not touch any Actor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolLoad {
+    /// Actors that are ready to run and waiting to be picked up by a thread of the pool
+    pub queued_actors: usize,
+    /// worker threads that are executing an Actor right now
+    pub busy_threads: usize,
+    /// worker threads that are running, see [PoolStats.threads](./struct.PoolStats.html#structfield.threads)
+    pub threads: usize,
+    /// moving average of the time between an Actor being queued and a thread picking it up, zero until the first Actor has been picked up
+    pub queue_latency: Duration,
+}
+
+/// Returned by [ActorSystem.resize_pool](../prelude/struct.ActorSystem.html#method.resize_pool) and [ActorBuilder.set_pool_name](../prelude/struct.ActorBuilder.html#method.set_pool_name)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolError {
+    /// the pool has neither been added nor configured in `thread_pool.config`
+    UnknownPool,
+    /// `threads_max` is 0 or smaller than `threads_min`
+    InvalidThreadCount,
+}
+
+/// Handle to a pool of an [ActorSystem](../prelude/struct.ActorSystem.html), see [ActorSystem.add_pool](../prelude/struct.ActorSystem.html#method.add_pool) and [ActorSystem.get_pool](../prelude/struct.ActorSystem.html#method.get_pool)
+///
+/// Pools are never removed, the handle stays valid no matter how often the pool is resized. Cloning only copies a pointer
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolRef {
+    name: Arc<str>,
+}
+
+impl PoolRef {
+    pub(crate) fn new(name: &str) -> Self {
+        Self { name: Arc::from(name) }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,122 @@
+use crate::system::actor_info::{ActorInfo, ActorStatus};
+use crate::system::pool_stats::PoolStats;
+use crate::system::system_state::RetiredTotals;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Everything [ActorSystem.render_prometheus](../prelude/struct.ActorSystem.html#method.render_prometheus) renders, collected before rendering starts
+pub(crate) struct MetricsSnapshot {
+    pub system: String,
+    pub actors: Vec<ActorInfo>,
+    pub pools: Vec<(String, PoolStats)>,
+    /// counters of the stopped Actors per pool, collected together with `actors`
+    pub retired: HashMap<String, RetiredTotals>,
+    pub dead_letters: u64,
+    /// see `general.metrics_per_actor`
+    pub per_actor: bool,
+}
+
+/// Counters of a single series, either of one Actor or summed up per pool
+#[derive(Default)]
+struct ActorSeries {
+    mailbox_depth: usize,
+    handled: usize,
+    restarts: usize,
+    panics: usize,
+}
+
+/// Name, type, help text and value of a metric rendered for every [ActorSeries]
+type ActorMetric = (&'static str, &'static str, &'static str, fn(&ActorSeries) -> usize);
+
+/// Escapes a label value as required by the text exposition format
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn labels(pairs: &[(&str, &str)]) -> String {
+    let pairs: Vec<String> = pairs.iter().map(|(name, value)| format!("{}=\"{}\"", name, escape(value))).collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+impl MetricsSnapshot {
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let system = self.system.as_str();
+
+        // keyed by their labels, so that the series are sorted
+        let mut series: BTreeMap<String, ActorSeries> = BTreeMap::new();
+        for info in &self.actors {
+            let key = if self.per_actor {
+                labels(&[("system", system), ("pool", &info.pool), ("actor", &info.address.actor)])
+            } else {
+                labels(&[("system", system), ("pool", &info.pool)])
+            };
+            let entry = series.entry(key).or_default();
+            entry.mailbox_depth += info.mailbox_len;
+            entry.handled += info.processed;
+            entry.restarts += info.restarts;
+            entry.panics += info.panics;
+        }
+        // a pool keeps the counts of its stopped Actors, so that its counters never decrease
+        if !self.per_actor {
+            for (pool, totals) in &self.retired {
+                let entry = series.entry(labels(&[("system", system), ("pool", pool)])).or_default();
+                entry.handled += totals.processed;
+                entry.restarts += totals.restarts;
+                entry.panics += totals.panics;
+            }
+        }
+        let actor_metrics: [ActorMetric; 4] = [
+            ("tyractorsaur_mailbox_depth", "gauge", "Messages waiting in the mailbox.", |s| s.mailbox_depth),
+            ("tyractorsaur_messages_handled_total", "counter", "Messages handled, per pool including stopped actors.", |s| s.handled),
+            ("tyractorsaur_actor_restarts_total", "counter", "Restarts after a panic, per pool including stopped actors.", |s| s.restarts),
+            ("tyractorsaur_actor_panics_total", "counter", "Panics, per pool including stopped actors.", |s| s.panics),
+        ];
+        for (name, kind, help, value) in actor_metrics {
+            header(&mut out, name, kind, help);
+            for (key, entry) in &series {
+                let _ = writeln!(out, "{}{} {}", name, key, value(entry));
+            }
+        }
+
+        header(&mut out, "tyractorsaur_pool_threads", "gauge", "Running worker threads of the pool.");
+        for (pool, stats) in &self.pools {
+            let _ = writeln!(out, "tyractorsaur_pool_threads{} {}", labels(&[("system", system), ("pool", pool)]), stats.threads);
+        }
+        header(&mut out, "tyractorsaur_pool_queued_actors", "gauge", "Actors waiting for a worker thread of the pool.");
+        for (pool, stats) in &self.pools {
+            let key = labels(&[("system", system), ("pool", pool)]);
+            let _ = writeln!(out, "tyractorsaur_pool_queued_actors{} {}", key, stats.queued_actors);
+        }
+
+        header(&mut out, "tyractorsaur_actors", "gauge", "Running actors by scheduling state.");
+        let states = [
+            ("active", ActorStatus::Scheduled),
+            ("sleeping", ActorStatus::Sleeping),
+            ("awaiting_release", ActorStatus::AwaitingRelease),
+            ("stopping", ActorStatus::Stopping),
+        ];
+        for (state, status) in states {
+            let count = self.actors.iter().filter(|info| info.status == status).count();
+            let _ = writeln!(out, "tyractorsaur_actors{} {}", labels(&[("system", system), ("state", state)]), count);
+        }
+
+        header(&mut out, "tyractorsaur_dead_letters_total", "counter", "Messages that could not be delivered.");
+        let _ = writeln!(out, "tyractorsaur_dead_letters_total{} {}", labels(&[("system", system)]), self.dead_letters);
+        out
+    }
+}
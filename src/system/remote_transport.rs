@@ -0,0 +1,10 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::serialized_message::SerializedMessage;
+
+/// Delivers serialized messages to Actors of other systems, see [ActorSystem.set_remote_transport](./struct.ActorSystem.html#method.set_remote_transport)
+///
+/// The receiving side hands every message to [ActorSystem.receive_remote](./struct.ActorSystem.html#method.receive_remote) of the system the address belongs to.
+/// Called on whatever thread sends the message, an implementation that blocks holds back the sender
+pub trait RemoteTransport: Send + Sync {
+    fn send(&self, address: &ActorAddress, msg: SerializedMessage);
+}
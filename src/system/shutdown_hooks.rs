@@ -0,0 +1,45 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+pub(crate) type ShutdownHook = Box<dyn FnOnce() + Send>;
+
+/// Returned by [ActorSystem.add_shutdown_hook](../prelude/struct.ActorSystem.html#method.add_shutdown_hook)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShutdownHookError {
+    /// the hooks are already being executed or the system has stopped, the hook has been dropped
+    AlreadyFinalizing,
+}
+
+/// Hooks of a system, executed once when its stop is finalized
+pub(crate) struct ShutdownHooks {
+    /// `None` once the hooks have been taken for the execution
+    hooks: Mutex<Option<Vec<ShutdownHook>>>,
+}
+
+impl ShutdownHooks {
+    pub fn new() -> Self {
+        Self {
+            hooks: Mutex::new(Some(Vec::new())),
+        }
+    }
+
+    pub fn add(&self, hook: ShutdownHook) -> Result<(), ShutdownHookError> {
+        match self.hooks.lock().unwrap().as_mut() {
+            Some(hooks) => {
+                hooks.push(hook);
+                Ok(())
+            }
+            None => Err(ShutdownHookError::AlreadyFinalizing),
+        }
+    }
+
+    /// Executes all hooks in reverse registration order, only the first call executes anything
+    ///
+    /// A panicking hook is skipped, the remaining hooks are still executed
+    pub fn execute(&self) {
+        let hooks = self.hooks.lock().unwrap().take().unwrap_or_default();
+        for hook in hooks.into_iter().rev() {
+            let _ = catch_unwind(AssertUnwindSafe(hook));
+        }
+    }
+}
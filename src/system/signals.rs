@@ -0,0 +1,314 @@
+use crate::system::actor_system::ActorSystem;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{sleep, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// only a single set of handlers can be installed per process
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+/// write end of the pipe of the installed [SignalGuard](./struct.SignalGuard.html), `-1` if there is none
+static PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+/// written to the pipe to end the dispatcher, no signal has the number `0`
+const SHUTDOWN: u8 = 0;
+
+/// Signals that can trigger the stop of an [ActorSystem](./struct.ActorSystem.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    /// `SIGINT`
+    Interrupt,
+    /// `SIGTERM`
+    Terminate,
+    /// `SIGHUP`
+    Hangup,
+    /// `SIGQUIT`
+    Quit,
+}
+
+impl Signal {
+    pub fn as_raw(&self) -> i32 {
+        match self {
+            Signal::Interrupt => libc::SIGINT,
+            Signal::Terminate => libc::SIGTERM,
+            Signal::Hangup => libc::SIGHUP,
+            Signal::Quit => libc::SIGQUIT,
+        }
+    }
+
+    fn from_raw(raw: i32) -> Option<Self> {
+        [Signal::Interrupt, Signal::Terminate, Signal::Hangup, Signal::Quit]
+            .iter()
+            .copied()
+            .find(|signal| signal.as_raw() == raw)
+    }
+}
+
+/// What happens if another signal arrives while the graceful stop is still running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecondSignalBehavior {
+    /// end the graceful stop immediately, as if its timeout had elapsed
+    ForceStop,
+    /// keep waiting for the graceful stop
+    Ignore,
+}
+
+/// Configures [ActorSystem.run_until_signal](./struct.ActorSystem.html#method.run_until_signal)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalConfig {
+    pub signals: Vec<Signal>,
+    /// passed to [ActorSystem.stop](./struct.ActorSystem.html#method.stop), should be shorter than the grace period of the process supervisor
+    pub graceful_timeout: Duration,
+    pub second_signal: SecondSignalBehavior,
+}
+
+impl SignalConfig {
+    /// Reacts to `SIGINT` and `SIGTERM`, a second signal forces the stop
+    pub fn new(graceful_timeout: Duration) -> Self {
+        Self {
+            signals: vec![Signal::Interrupt, Signal::Terminate],
+            graceful_timeout,
+            second_signal: SecondSignalBehavior::ForceStop,
+        }
+    }
+
+    pub fn with_signals(mut self, signals: &[Signal]) -> Self {
+        self.signals = signals.to_vec();
+        self
+    }
+
+    pub fn with_second_signal(mut self, second_signal: SecondSignalBehavior) -> Self {
+        self.second_signal = second_signal;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignalError {
+    /// another [SignalGuard](./struct.SignalGuard.html) is still alive in this process
+    AlreadyInstalled,
+    /// creating the pipe or installing a handler failed with the contained `errno`
+    Os(i32),
+}
+
+/// Outcome of a stop observed by a [SignalGuard](./struct.SignalGuard.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalShutdownReport {
+    /// the signal that initiated the stop, `None` if the system has been stopped by other means
+    pub signal: Option<Signal>,
+    /// the graceful stop has been ended by a second signal
+    pub escalated: bool,
+    /// the system has been force stopped, either by a second signal or because the graceful timeout elapsed
+    pub force_stopped: bool,
+    /// time from the first signal until the system stopped
+    pub shutdown_duration: Duration,
+}
+
+impl SignalShutdownReport {
+    /// Same codes as [ActorSystem.await_shutdown](./struct.ActorSystem.html#method.await_shutdown)
+    pub fn exit_code(&self) -> i32 {
+        self.force_stopped as i32
+    }
+}
+
+#[derive(Default)]
+struct Received {
+    first: Option<(Signal, Instant)>,
+    escalated: bool,
+    stopped_at: Option<Instant>,
+}
+
+/// Installed signal handlers of an [ActorSystem](./struct.ActorSystem.html), restores the previous handlers when dropped
+///
+/// The handlers only write the signal number to a pipe, a dedicated thread stops the system
+///
+/// Created by [ActorSystem.install_signal_handler](./struct.ActorSystem.html#method.install_signal_handler)
+pub struct SignalGuard {
+    system: ActorSystem,
+    received: Arc<Mutex<Received>>,
+    read_fd: i32,
+    write_fd: i32,
+    previous: Vec<(libc::c_int, libc::sigaction)>,
+    dispatcher: Option<JoinHandle<()>>,
+}
+
+impl SignalGuard {
+    pub(crate) fn install(system: &ActorSystem, config: SignalConfig) -> Result<Self, SignalError> {
+        if INSTALLED.swap(true, Ordering::SeqCst) {
+            return Err(SignalError::AlreadyInstalled);
+        }
+        let mut fds = [-1; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            INSTALLED.store(false, Ordering::SeqCst);
+            return Err(last_error());
+        }
+        unsafe {
+            libc::fcntl(fds[0], libc::F_SETFD, libc::FD_CLOEXEC);
+            libc::fcntl(fds[1], libc::F_SETFD, libc::FD_CLOEXEC);
+            // the handler must never block
+            libc::fcntl(fds[1], libc::F_SETFL, libc::fcntl(fds[1], libc::F_GETFL) | libc::O_NONBLOCK);
+        }
+        PIPE_WRITE.store(fds[1], Ordering::SeqCst);
+
+        let received = Arc::new(Mutex::new(Received::default()));
+        {
+            let received = received.clone();
+            // fails only if the system is already stopping, the report then measures until it is requested
+            let _ = system.add_shutdown_hook(Box::new(move || {
+                received.lock().unwrap().stopped_at = Some(Instant::now());
+            }));
+        }
+        let dispatcher = {
+            let system = system.clone();
+            let received = received.clone();
+            let config = config.clone();
+            let read_fd = fds[0];
+            std::thread::spawn(move || dispatch(read_fd, system, config, received))
+        };
+        // from here on the guard cleans up after itself
+        let mut guard = Self {
+            system: system.clone(),
+            received,
+            read_fd: fds[0],
+            write_fd: fds[1],
+            previous: Vec::new(),
+            dispatcher: Some(dispatcher),
+        };
+
+        for signal in config.signals {
+            let raw = signal.as_raw();
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+                action.sa_flags = libc::SA_RESTART;
+                libc::sigemptyset(&mut action.sa_mask);
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(raw, &action, &mut previous) != 0 {
+                    return Err(last_error());
+                }
+                guard.previous.push((raw, previous));
+            }
+        }
+        Ok(guard)
+    }
+
+    /// The signal that initiated the stop, if any arrived yet
+    pub fn signal(&self) -> Option<Signal> {
+        self.received.lock().unwrap().first.map(|(signal, _)| signal)
+    }
+
+    /// `None` as long as the system did not stop
+    pub fn report(&self) -> Option<SignalShutdownReport> {
+        let state = self.system.get_state();
+        if !state.is_stopped() {
+            return None;
+        }
+        let received = self.received.lock().unwrap();
+        let shutdown_duration = received
+            .first
+            .map(|(_, at)| received.stopped_at.unwrap_or_else(Instant::now).saturating_duration_since(at))
+            .unwrap_or_default();
+        Some(SignalShutdownReport {
+            signal: received.first.map(|(signal, _)| signal),
+            escalated: received.escalated,
+            force_stopped: state.is_force_stopped(),
+            shutdown_duration,
+        })
+    }
+
+    /// Blocks until the system stopped, no matter whether a signal or the application stopped it
+    pub fn wait(&self) -> SignalShutdownReport {
+        // the stop initiated by the dispatcher wakes it up
+        self.system.get_state().await_stopped(None);
+        self.report().unwrap()
+    }
+}
+
+impl Drop for SignalGuard {
+    fn drop(&mut self) {
+        // in reverse order, so that a signal that was configured twice ends up with its original handler
+        for (raw, previous) in self.previous.drain(..).rev() {
+            unsafe {
+                libc::sigaction(raw, &previous, std::ptr::null_mut());
+            }
+        }
+        PIPE_WRITE.store(-1, Ordering::SeqCst);
+        if let Some(dispatcher) = self.dispatcher.take() {
+            while !write_byte(self.write_fd, SHUTDOWN) {
+                sleep(Duration::from_millis(1));
+            }
+            let _ = dispatcher.join();
+        }
+        unsafe {
+            libc::close(self.read_fd);
+            libc::close(self.write_fd);
+        }
+        INSTALLED.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Only calls async-signal-safe functions
+extern "C" fn on_signal(raw: libc::c_int) {
+    let fd = PIPE_WRITE.load(Ordering::SeqCst);
+    if fd >= 0 {
+        let errno = unsafe { *errno_location() };
+        write_byte(fd, raw as u8);
+        unsafe { *errno_location() = errno };
+    }
+}
+
+fn write_byte(fd: i32, byte: u8) -> bool {
+    unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) == 1 }
+}
+
+fn dispatch(read_fd: i32, system: ActorSystem, config: SignalConfig, received: Arc<Mutex<Received>>) {
+    loop {
+        let mut poll_fd = libc::pollfd {
+            fd: read_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        if unsafe { libc::poll(&mut poll_fd, 1, -1) } > 0 {
+            let mut byte = 0u8;
+            if unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) } == 1 {
+                if byte == SHUTDOWN {
+                    return;
+                }
+                if let Some(signal) = Signal::from_raw(byte as i32) {
+                    on_received(signal, &system, &config, &received);
+                }
+            }
+        }
+    }
+}
+
+fn on_received(signal: Signal, system: &ActorSystem, config: &SignalConfig, received: &Mutex<Received>) {
+    let mut received = received.lock().unwrap();
+    if received.first.is_none() {
+        received.first = Some((signal, Instant::now()));
+        drop(received);
+        system.stop(config.graceful_timeout);
+    } else if config.second_signal == SecondSignalBehavior::ForceStop && !received.escalated {
+        received.escalated = true;
+        drop(received);
+        system.force_stop();
+    }
+}
+
+fn last_error() -> SignalError {
+    SignalError::Os(std::io::Error::last_os_error().raw_os_error().unwrap_or(0))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "emscripten"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno_location()
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__error()
+}
+
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    libc::__errno()
+}
@@ -0,0 +1,24 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::system::panic_handler;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Hook of [ActorSystem.set_slow_handler_hook](../prelude/struct.ActorSystem.html#method.set_slow_handler_hook)
+pub(crate) type SlowHandlerHook = Arc<dyn Fn(SlowHandler) + Send + Sync>;
+
+/// Handler invocation that exceeded the [slow handler threshold](../prelude/struct.ActorBuilder.html#method.set_slow_handler_threshold) of its Actor
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlowHandler {
+    pub address: ActorAddress,
+    pub message_type: &'static str,
+    /// wall time of the handler, without the construction of a lazily sent message
+    pub duration: Duration,
+    pub threshold: Duration,
+}
+
+/// Hands the slow handler to `hook`, a panicking hook is ignored
+pub(crate) fn notify(hook: &SlowHandlerHook, slow_handler: SlowHandler) {
+    if panic_handler::catch(|| hook(slow_handler)).is_err() {
+        println!("SLOW HANDLER HOOK PANIC");
+    }
+}
@@ -0,0 +1,188 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::message::actor_message::ActorMessage;
+use crate::system::event_bus::EventBus;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Topic of the [EventBus](./struct.EventBus.html) on which every [DependencyFailed](./struct.DependencyFailed.html) is published
+pub const DEPENDENCY_TOPIC: &str = "system.dependency_failed";
+
+/// Defines what happens to an Actor once one of its dependencies stopped before it became ready, see [ActorBuilder.depends_on](../prelude/struct.ActorBuilder.html#method.depends_on)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DependencyFailurePolicy {
+    /// the Actor is stopped without ever starting, its queued messages are dropped
+    #[default]
+    Stop,
+    /// the failed dependency no longer holds the Actor back
+    Proceed,
+}
+
+/// Published on [DEPENDENCY_TOPIC](./constant.DEPENDENCY_TOPIC.html) for every dependency that stopped before it became ready
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyFailed {
+    pub dependent: ActorAddress,
+    pub dependency: ActorAddress,
+    /// applied to the dependent
+    pub policy: DependencyFailurePolicy,
+}
+
+impl ActorMessage for DependencyFailed {}
+
+type StartCallback = Box<dyn Fn() + Send + Sync>;
+
+/// Actor that waits for its dependencies
+struct PendingStart {
+    address: ActorAddress,
+    remaining: HashSet<ActorAddress>,
+    policy: DependencyFailurePolicy,
+    release: StartCallback,
+    stop: StartCallback,
+}
+
+#[derive(Default)]
+struct Graph {
+    /// declared dependencies per dependent, kept until the dependent has been removed
+    edges: HashMap<ActorAddress, Vec<ActorAddress>>,
+    /// Actors waiting for their dependencies
+    pending: HashMap<ActorAddress, PendingStart>,
+}
+
+impl Graph {
+    /// Path from `from` back to `to` along the declared dependencies, if any
+    fn find_path(&self, from: &ActorAddress, to: &ActorAddress, visited: &mut HashSet<ActorAddress>) -> Option<Vec<ActorAddress>> {
+        if from == to {
+            return Some(vec![from.clone()]);
+        }
+        if !visited.insert(from.clone()) {
+            return None;
+        }
+        let dependencies = self.edges.get(from)?;
+        dependencies.iter().find_map(|dependency| {
+            let mut path = self.find_path(dependency, to, visited)?;
+            path.insert(0, from.clone());
+            Some(path)
+        })
+    }
+
+    /// Removes all Actors that no longer wait for any dependency
+    fn take_unblocked(&mut self) -> Vec<PendingStart> {
+        let unblocked: Vec<ActorAddress> = self
+            .pending
+            .values()
+            .filter(|pending| pending.remaining.is_empty())
+            .map(|pending| pending.address.clone())
+            .collect();
+        unblocked.iter().filter_map(|address| self.pending.remove(address)).collect()
+    }
+}
+
+/// Dependencies between Actors of a system, see [ActorBuilder.depends_on](../prelude/struct.ActorBuilder.html#method.depends_on)
+///
+/// Callbacks are executed after the graph has been released, releasing or stopping an Actor can remove other Actors
+#[derive(Clone, Default)]
+pub(crate) struct StartupOrder {
+    graph: Arc<Mutex<Graph>>,
+}
+
+impl StartupOrder {
+    /// Records the dependencies of `dependent`, fails with the cycle they would close, starting and ending with `dependent`
+    pub fn declare(&self, dependent: &ActorAddress, dependencies: &[ActorAddress]) -> Result<(), Vec<ActorAddress>> {
+        let mut graph = self.graph.lock().unwrap();
+        for dependency in dependencies {
+            if let Some(mut cycle) = graph.find_path(dependency, dependent, &mut HashSet::new()) {
+                cycle.insert(0, dependent.clone());
+                return Err(cycle);
+            }
+        }
+        graph.edges.insert(dependent.clone(), dependencies.to_vec());
+        Ok(())
+    }
+
+    /// Holds `dependent` back until all `dependencies` for which `is_ready` returns `false` got ready, releases it right away otherwise
+    pub fn hold(
+        &self,
+        dependent: &ActorAddress,
+        dependencies: &[ActorAddress],
+        is_ready: impl Fn(&ActorAddress) -> bool,
+        policy: DependencyFailurePolicy,
+        release: StartCallback,
+        stop: StartCallback,
+    ) {
+        let mut graph = self.graph.lock().unwrap();
+        // checked while the graph is held, a dependency that gets ready afterwards finds the pending start
+        let remaining: HashSet<ActorAddress> = dependencies.iter().filter(|dependency| !is_ready(dependency)).cloned().collect();
+        if remaining.is_empty() {
+            drop(graph);
+            release();
+            return;
+        }
+        graph.pending.insert(
+            dependent.clone(),
+            PendingStart {
+                address: dependent.clone(),
+                remaining,
+                policy,
+                release,
+                stop,
+            },
+        );
+    }
+
+    /// Releases every Actor for which `address` was the last dependency that was not ready
+    pub fn notify_ready(&self, address: &ActorAddress) {
+        let released = {
+            let mut graph = self.graph.lock().unwrap();
+            for pending in graph.pending.values_mut() {
+                pending.remaining.remove(address);
+            }
+            graph.take_unblocked()
+        };
+        for pending in released {
+            (pending.release)();
+        }
+    }
+
+    /// Forgets the removed Actor and applies the [DependencyFailurePolicy] of every Actor that still waited for it
+    pub fn notify_removed(&self, address: &ActorAddress, event_bus: &EventBus) {
+        let (failed, stopped, released) = {
+            let mut graph = self.graph.lock().unwrap();
+            graph.edges.remove(address);
+            graph.pending.remove(address);
+            let mut failed = Vec::new();
+            for pending in graph.pending.values_mut() {
+                if pending.remaining.remove(address) {
+                    failed.push(DependencyFailed {
+                        dependent: pending.address.clone(),
+                        dependency: address.clone(),
+                        policy: pending.policy,
+                    });
+                }
+            }
+            let stopped: Vec<PendingStart> = failed
+                .iter()
+                .filter(|event| event.policy == DependencyFailurePolicy::Stop)
+                .filter_map(|event| graph.pending.remove(&event.dependent))
+                .collect();
+            (failed, stopped, graph.take_unblocked())
+        };
+        for pending in stopped {
+            (pending.stop)();
+        }
+        for event in failed {
+            let _ = event_bus.publish(DEPENDENCY_TOPIC, event);
+        }
+        for pending in released {
+            (pending.release)();
+        }
+    }
+
+    /// `true` if an Actor waits for the Actor at `address` to get ready
+    pub fn is_awaited(&self, address: &ActorAddress) -> bool {
+        self.graph.lock().unwrap().pending.values().any(|pending| pending.remaining.contains(address))
+    }
+
+    /// Drops the dependencies of a spawn that failed after they have been declared
+    pub fn forget(&self, dependent: &ActorAddress) {
+        self.graph.lock().unwrap().edges.remove(dependent);
+    }
+}
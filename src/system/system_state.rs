@@ -1,89 +1,1207 @@
 use crate::actor::actor::Actor;
-use crate::actor::actor_address::ActorAddress;
+use crate::actor::actor_address::{ActorAddress, ResolveError};
+use crate::actor::actor_config::DEFAULT_SHUTDOWN_PHASE;
+use crate::actor::message_registry::MessageRegistry;
+use crate::actor::actor_metrics::ActorMetrics;
+use crate::actor::actor_wrapper::ActorWrapper;
+use crate::actor::debug_snapshot::{DebugSnapshotMessage, SnapshotError};
+use crate::actor::executor::ExecutorTrait;
+use crate::actor::protocol::{ActorProtocol, ProtocolEntry, ProtocolError};
+use crate::actor::relocation::{MoveError, MoveToPoolMessage};
+use crate::actor::unhandled::{UnhandledInfo, UnhandledMessage};
+use crate::message::serialized_dispatch::{Dispatched, SerializedDispatch, SerializedRequest};
+use crate::message::send_error::SendError;
 use crate::message::serialized_message::SerializedMessage;
+use crate::system::dead_letters::DeadLetterOffice;
+use crate::system::event_bus::EventBus;
+use crate::system::instrumentation::Instrumentation;
+use crate::system::integrity::{IntegrityCounts, Registry};
+use crate::system::memory_pressure::MemoryPressureState;
+use crate::system::name_guard::NameGuard;
+use crate::system::shutdown_hooks::{ShutdownHook, ShutdownHookError, ShutdownHooks};
+use crate::system::startup_order::StartupOrder;
+use crossbeam_channel::{bounded, Receiver, Sender};
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::thread::sleep;
+use std::any::Any;
+use std::collections::HashMap;
+use std::panic::UnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock, Weak};
+use std::thread::JoinHandle;
+#[cfg(feature = "async")]
+use std::task::{Poll, Waker};
 use std::time::{Duration, Instant};
 
+/// Time each daemon gets to stop once it received the system stop, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon)
+const DAEMON_STOP_GRACE: Duration = Duration::from_millis(200);
+
+type ActivationCallback = Box<dyn FnOnce() + Send + Sync>;
+type ReleaseCallback = Box<dyn Fn() -> bool + Send + Sync>;
+type StopCallback = Box<dyn Fn() + Send + Sync>;
+
+/// Type erased operations on a running Actor, implemented by its [ActorWrapper] so that they share one allocation
+trait RegisteredActor: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    /// Sends the info to [Actor.on_unhandled_message](../prelude/trait.Actor.html#method.on_unhandled_message), `false` if the Actor does not accept messages anymore
+    fn send_unhandled(&self, info: UnhandledInfo) -> bool;
+    /// queues a [SerializedMessage] without type tag, see [Actor.handle_serialized_message_mut](../prelude/trait.Actor.html#method.handle_serialized_message_mut)
+    fn send_untyped(&self, msg: SerializedMessage) -> bool;
+    /// asks the Actor to move to the given pool, `false` if the Actor does not accept messages anymore
+    fn relocate(&self, pool: String) -> bool;
+    /// notifies everyone waiting for the removal of the Actor, see [ActorWrapper.await_stop](../prelude/struct.ActorWrapper.html#method.await_stop)
+    fn notify_removed(&self);
+    /// `true` once the Actor has been asked to stop
+    fn is_stopping(&self) -> bool;
+    /// see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    fn is_ready(&self) -> bool;
+    /// queues a snapshot request, waits at most `timeout` for room in the mailbox, see [ActorSystem.debug_snapshot](../prelude/struct.ActorSystem.html#method.debug_snapshot)
+    fn request_debug_snapshot(&self, timeout: Duration) -> Result<Receiver<Result<String, SnapshotError>>, SnapshotError>;
+}
+
+impl<A> RegisteredActor for ActorWrapper<A>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn send_unhandled(&self, info: UnhandledInfo) -> bool {
+        self.send(UnhandledMessage::new(info)).is_ok()
+    }
+
+    fn send_untyped(&self, msg: SerializedMessage) -> bool {
+        self.send(SerializedRequest::new(msg)).is_ok()
+    }
+
+    fn relocate(&self, pool: String) -> bool {
+        self.send_priority(MoveToPoolMessage::new(pool)).is_ok()
+    }
+
+    fn notify_removed(&self) {
+        ActorWrapper::notify_removed(self)
+    }
+
+    fn is_stopping(&self) -> bool {
+        self.get_mailbox().is_stopped() || self.get_mailbox().is_stop_requested()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.get_mailbox().is_ready()
+    }
+
+    fn request_debug_snapshot(&self, timeout: Duration) -> Result<Receiver<Result<String, SnapshotError>>, SnapshotError> {
+        if self.get_mailbox().debug_snapshot.is_none() {
+            return Err(SnapshotError::NotSupported);
+        }
+        let (sender, receiver) = bounded(1);
+        match self.send_timeout(DebugSnapshotMessage { result: sender }, timeout) {
+            Ok(()) => Ok(receiver),
+            Err(SendError::Stopped(_)) => Err(SnapshotError::Stopped),
+            Err(_) => Err(SnapshotError::Timeout),
+        }
+    }
+}
+
+/// Callback of an Actor, or of the outside of the system if `watcher` is `None`
+struct ActivationWatch {
+    watcher: Option<ActorAddress>,
+    callback: ActivationCallback,
+}
+
+/// Running Actor together with a type erased clone of its [ActorWrapper], see [get_actor_wrapper](#method.get_actor_wrapper)
+struct ActiveActor {
+    wrapper: Arc<dyn RegisteredActor>,
+    /// ended by a forced stop of the system
+    executor: Weak<RwLock<dyn ExecutorTrait>>,
+    /// only set if the Actor registered typed serialized messages
+    serialized: Option<Arc<SerializedDispatch>>,
+    accepted_messages: Arc<MessageRegistry>,
+    shutdown_phase: u8,
+    /// spawn order of daemon Actors, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon)
+    daemon: Option<u64>,
+    /// keeps the shutdown phase of the Actor open beyond its share of the timeout, see [ActorContext.extend_system_stop](../prelude/struct.ActorContext.html#method.extend_system_stop)
+    stop_extension: Option<Instant>,
+}
+
+/// Registered Actors without keeping them or the system alive, see [WeakActorWrapper](../prelude/struct.WeakActorWrapper.html)
+#[derive(Clone)]
+pub(crate) struct ActorRegistry(Weak<DashMap<ActorAddress, ActiveActor>>);
+
+impl ActorRegistry {
+    /// `None` once the Actor has stopped or has been removed
+    pub fn get_actor_wrapper<A>(&self, address: &ActorAddress) -> Option<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        let actors = self.0.upgrade()?;
+        get_running_wrapper(&actors, address)
+    }
+}
+
+fn get_running_wrapper<A>(actors: &DashMap<ActorAddress, ActiveActor>, address: &ActorAddress) -> Option<ActorWrapper<A>>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    resolve_running_wrapper(actors, address).ok()
+}
+
+fn resolve_running_wrapper<A>(actors: &DashMap<ActorAddress, ActiveActor>, address: &ActorAddress) -> Result<ActorWrapper<A>, ResolveError>
+where
+    A: Actor + UnwindSafe + 'static,
+{
+    let entry = actors.get(address).ok_or(ResolveError::NotFound)?;
+    let wrapper = entry.wrapper.as_any().downcast_ref::<ActorWrapper<A>>().ok_or(ResolveError::TypeMismatch)?.clone();
+    if wrapper.get_mailbox().is_stopped() {
+        return Err(ResolveError::NotFound);
+    }
+    Ok(wrapper)
+}
+
+/// Address that is being spawned, released once the Actor has been added or the spawn failed
+pub(crate) struct AddressReservation {
+    spawning: Arc<DashMap<ActorAddress, ()>>,
+    address: ActorAddress,
+}
+
+impl Drop for AddressReservation {
+    fn drop(&mut self) {
+        self.spawning.remove(&self.address);
+    }
+}
+
+/// Spawn that is adding its Actor, see [SystemState.begin_registration](./struct.SystemState.html#method.begin_registration)
+pub(crate) struct Registration {
+    state: SystemState,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if self.state.registrations.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.notify_shutdown();
+        }
+    }
+}
+
+/// Counters of the stopped Actors of a pool, so that the totals of the pool never decrease
+#[derive(Clone, Default)]
+pub(crate) struct RetiredTotals {
+    pub processed: usize,
+    pub restarts: usize,
+    pub panics: usize,
+}
+
+/// Child of an Actor, stopped together with its parent
+struct ChildEntry {
+    address: ActorAddress,
+    stop: StopCallback,
+}
+
 #[derive(Clone)]
 pub struct SystemState {
-    actors: Arc<DashMap<ActorAddress, Arc<dyn Actor>>>,
+    actors: Arc<DashMap<ActorAddress, ActiveActor>>,
+    /// addresses of running spawns, see [reserve_address](#method.reserve_address)
+    spawning: Arc<DashMap<ActorAddress, ()>>,
+    actor_metrics: Arc<DashMap<ActorAddress, ActorMetrics>>,
+    /// keyed by pool, the metrics of an Actor move here under the lock once it is removed
+    retired_totals: Arc<Mutex<HashMap<String, RetiredTotals>>>,
+    activation_watchers: Arc<DashMap<ActorAddress, Vec<ActivationWatch>>>,
+    awaiting_release: Arc<DashMap<ActorAddress, ReleaseCallback>>,
+    protocols: Arc<DashMap<ActorAddress, Arc<ProtocolEntry>>>,
+    /// children per parent, see [ActorContext.spawn_child](../prelude/struct.ActorContext.html#method.spawn_child)
+    children: Arc<DashMap<ActorAddress, Vec<ChildEntry>>>,
+    event_bus: EventBus,
+    /// see [ActorBuilder.depends_on](../prelude/struct.ActorBuilder.html#method.depends_on)
+    startup_order: StartupOrder,
+    dead_letters: DeadLetterOffice,
+    instrumentation: Instrumentation,
+    memory_pressure: MemoryPressureState,
     total_actor_count: Arc<AtomicUsize>,
+    /// part of `total_actor_count`, but not of the graceful drain
+    daemon_count: Arc<AtomicUsize>,
+    next_daemon: Arc<AtomicU64>,
+    /// daemons that receive the system stop, they are signalled one by one once all other Actors stopped
+    signalled_daemons: Arc<DashMap<ActorAddress, ()>>,
+    next_transaction_id: Arc<AtomicU64>,
+    shutdown_holds: Arc<AtomicUsize>,
+    /// spawns between the check of the stop flag and the end of the registration of their Actor
+    registrations: Arc<AtomicUsize>,
     is_stopped: Arc<AtomicBool>,
+    /// set once all Actors stopped, worker and background threads end with it and are joined before the system is stopped
+    is_halted: Arc<AtomicBool>,
+    /// background threads that are joined by the stop, see [add_thread](#method.add_thread)
+    threads: Arc<Mutex<Vec<JoinHandle<()>>>>,
     is_stopping: Arc<AtomicBool>,
     is_force_stopped: Arc<AtomicBool>,
+    /// the stop has been started by a [PanicEscalation::StopSystem](../prelude/enum.PanicEscalation.html#variant.StopSystem)
+    is_panic_stopped: Arc<AtomicBool>,
+    is_force_stop_requested: Arc<AtomicBool>,
+    /// Actors of lower shutdown phases receive the system stop, see [ActorBuilder.set_shutdown_phase](../prelude/struct.ActorBuilder.html#method.set_shutdown_phase)
+    signalled_phases: Arc<AtomicU16>,
+    /// spawns of lower shutdown phases are rejected
+    completed_phases: Arc<AtomicU16>,
+    name_guard: Arc<Mutex<Option<NameGuard>>>,
+    /// executed once all Actors stopped, see [ActorSystem.add_shutdown_hook](../prelude/struct.ActorSystem.html#method.add_shutdown_hook)
+    shutdown_hooks: Arc<ShutdownHooks>,
+    /// notified when the actor count reaches zero, a force stop is requested and the stop is finalized
+    shutdown_signal: Arc<(Mutex<()>, Condvar)>,
+    /// notified when an Actor got ready or has been removed, see [ActorSystem.await_all_ready](../prelude/struct.ActorSystem.html#method.await_all_ready)
+    ready_signal: Arc<(Mutex<()>, Condvar)>,
+    /// never receives anything, dropping the sender wakes up every background thread selecting on `stopped` once the system halted
+    stopping: Arc<Mutex<Option<Sender<()>>>>,
+    stopped: Receiver<()>,
+    /// woken together with `shutdown_signal`, see [ActorSystem.await_shutdown_async](../prelude/struct.ActorSystem.html#method.await_shutdown_async)
+    #[cfg(feature = "async")]
+    shutdown_wakers: Arc<Mutex<Vec<Waker>>>,
 }
 
 impl SystemState {
     pub fn new() -> Self {
+        let (stopping, stopped) = bounded(0);
         Self {
             actors: Arc::new(DashMap::new()),
+            spawning: Arc::new(DashMap::new()),
+            actor_metrics: Arc::new(DashMap::new()),
+            retired_totals: Arc::new(Mutex::new(HashMap::new())),
+            activation_watchers: Arc::new(DashMap::new()),
+            awaiting_release: Arc::new(DashMap::new()),
+            protocols: Arc::new(DashMap::new()),
+            children: Arc::new(DashMap::new()),
+            event_bus: EventBus::new(),
+            startup_order: StartupOrder::default(),
+            dead_letters: DeadLetterOffice::new(),
+            instrumentation: Instrumentation::new(),
+            memory_pressure: MemoryPressureState::new(),
             total_actor_count: Arc::new(AtomicUsize::new(0)),
+            daemon_count: Arc::new(AtomicUsize::new(0)),
+            next_daemon: Arc::new(AtomicU64::new(0)),
+            signalled_daemons: Arc::new(DashMap::new()),
+            next_transaction_id: Arc::new(AtomicU64::new(1)),
+            shutdown_holds: Arc::new(AtomicUsize::new(0)),
+            registrations: Arc::new(AtomicUsize::new(0)),
             is_stopped: Arc::new(AtomicBool::new(false)),
+            is_halted: Arc::new(AtomicBool::new(false)),
+            threads: Arc::new(Mutex::new(Vec::new())),
             is_stopping: Arc::new(AtomicBool::new(false)),
             is_force_stopped: Arc::new(AtomicBool::new(false)),
+            is_panic_stopped: Arc::new(AtomicBool::new(false)),
+            is_force_stop_requested: Arc::new(AtomicBool::new(false)),
+            signalled_phases: Arc::new(AtomicU16::new(0)),
+            completed_phases: Arc::new(AtomicU16::new(0)),
+            name_guard: Arc::new(Mutex::new(None)),
+            shutdown_hooks: Arc::new(ShutdownHooks::new()),
+            shutdown_signal: Arc::new((Mutex::new(()), Condvar::new())),
+            ready_signal: Arc::new((Mutex::new(()), Condvar::new())),
+            stopping: Arc::new(Mutex::new(Some(stopping))),
+            stopped,
+            #[cfg(feature = "async")]
+            shutdown_wakers: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     pub fn stop(&self, graceful_termination_timeout: Duration) {
-        if self.is_stopping() {
+        if self.is_stopping.swap(true, Ordering::SeqCst) {
             return;
         }
-        self.is_stopping.store(true, Ordering::Relaxed);
+        // phases below the lowest running Actor have nothing left to stop
+        if let Some(phase) = self.get_shutdown_phases().first() {
+            self.completed_phases.store(u16::from(*phase), Ordering::SeqCst);
+        }
         let s = self.clone();
         std::thread::spawn(move || s.shutdown(graceful_termination_timeout));
     }
 
+    /// Ends a running graceful stop immediately, as if its timeout had elapsed
+    pub fn force_stop(&self) {
+        self.is_force_stop_requested.store(true, Ordering::Relaxed);
+        self.notify_shutdown();
+    }
+
     fn shutdown(&self, timeout: Duration) {
-        let now = Instant::now();
-        while self.get_actor_count() != 0 {
-            if now.elapsed() >= timeout {
-                self.is_force_stopped.store(true, Ordering::Relaxed);
-                self.actors.clear();
+        let deadline = Instant::now().checked_add(timeout);
+        let phases = self.get_shutdown_phases().len().max(1) as u32;
+        let phase_timeout = timeout / phases;
+        loop {
+            // Actors that are being added are counted and executed like all others
+            self.await_registrations();
+            let completed = self.completed_phases.load(Ordering::SeqCst);
+            let phase = match self.get_shutdown_phases().into_iter().find(|phase| u16::from(*phase) >= completed) {
+                Some(phase) => phase,
+                None => break,
+            };
+            self.signalled_phases.store(u16::from(phase) + 1, Ordering::SeqCst);
+            let phase_deadline = Instant::now().checked_add(phase_timeout).into_iter().chain(deadline).min();
+            let is_drained = self.await_phase_stopped(phase, phase_deadline, deadline);
+            // Actors spawned into the phase from now on are rejected, those that are being added are stopped with the last phase
+            self.completed_phases.store(u16::from(phase) + 1, Ordering::SeqCst);
+            if !is_drained && (deadline.is_some_and(|deadline| Instant::now() >= deadline) || self.is_force_stop_requested.load(Ordering::Relaxed)) {
                 break;
             }
-            sleep(timeout / 10);
         }
-        self.is_stopped.store(true, Ordering::Relaxed);
+        self.signalled_phases.store(u16::from(u8::MAX) + 1, Ordering::SeqCst);
+        self.completed_phases.store(u16::from(u8::MAX) + 1, Ordering::SeqCst);
+        self.await_registrations();
+        let remaining = deadline.map_or(timeout, |deadline| deadline.saturating_duration_since(Instant::now()));
+        if !(self.await_actors_stopped(remaining, false) && self.stop_daemons()) {
+            self.is_force_stopped.store(true, Ordering::Relaxed);
+            let executors: Vec<_> = self.actors.iter().filter_map(|entry| entry.executor.upgrade()).collect();
+            self.actors.clear();
+            self.actor_metrics.clear();
+            self.awaiting_release.clear();
+            self.protocols.clear();
+            // Actors that are handling a message end themselves once the handler returned
+            for executor in executors {
+                if let Ok(mut executor) = executor.try_write() {
+                    executor.force_stop();
+                }
+            }
+        }
+        // worker and background threads end once they finished what they are executing
+        self.is_halted.store(true, Ordering::SeqCst);
+        self.stopping.lock().unwrap().take();
+        let remaining = deadline.map_or(timeout, |deadline| deadline.saturating_duration_since(Instant::now()));
+        self.join_threads(remaining);
+        // resources outside of the Actors are released before the stop is observable
+        self.shutdown_hooks.execute();
+        // the name is free again as soon as the stop is observable
+        self.name_guard.lock().unwrap().take();
+        self.is_stopped.store(true, Ordering::SeqCst);
+        self.notify_shutdown();
+    }
+
+    /// Joins the registered threads within `timeout`, threads that are still running after it or after a force stop has been requested are detached
+    fn join_threads(&self, timeout: Duration) {
+        let deadline = Instant::now().checked_add(timeout);
+        let threads = std::mem::take(&mut *self.threads.lock().unwrap());
+        for thread in threads {
+            while !(thread.is_finished()
+                || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+                || self.is_force_stop_requested.load(Ordering::Relaxed))
+            {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            if thread.is_finished() {
+                let _ = thread.join();
+            }
+        }
+    }
+
+    /// Joined by the stop before the system counts as stopped, the thread has to end once the system [halted](#method.is_halted)
+    pub(crate) fn add_thread(&self, thread: JoinHandle<()>) {
+        self.threads.lock().unwrap().push(thread);
+    }
+
+    /// Blocks until all Actors stopped, returns `false` once `timeout` elapsed or a force stop has been requested before
+    fn await_actors_stopped(&self, timeout: Duration, include_daemons: bool) -> bool {
+        let deadline = Instant::now().checked_add(timeout);
+        let (lock, signal) = &*self.shutdown_signal;
+        let mut guard = lock.lock().unwrap();
+        while self.count_running(include_daemons) != 0 {
+            let now = Instant::now();
+            if deadline.is_some_and(|deadline| now >= deadline) || self.is_force_stop_requested.load(Ordering::Relaxed) {
+                return false;
+            }
+            guard = match deadline {
+                Some(deadline) => signal.wait_timeout(guard, deadline - now).unwrap().0,
+                None => signal.wait(guard).unwrap(),
+            };
+        }
+        true
+    }
+
+    /// Delivers the system stop to the daemons in reverse spawn order, each one is awaited for [DAEMON_STOP_GRACE] before the next one is signalled
+    ///
+    /// Returns `false` if daemons are left afterwards or a force stop has been requested
+    fn stop_daemons(&self) -> bool {
+        let mut daemons: Vec<(u64, ActorAddress)> = self
+            .actors
+            .iter()
+            .filter_map(|entry| entry.daemon.map(|order| (order, entry.key().clone())))
+            .collect();
+        daemons.sort_unstable_by_key(|(order, _)| std::cmp::Reverse(*order));
+        let (lock, signal) = &*self.shutdown_signal;
+        for (_, address) in daemons {
+            self.signalled_daemons.insert(address.clone(), ());
+            let grace = Instant::now() + DAEMON_STOP_GRACE;
+            let mut guard = lock.lock().unwrap();
+            while self.actors.contains_key(&address) {
+                let now = Instant::now();
+                if now >= grace || self.is_force_stop_requested.load(Ordering::Relaxed) {
+                    break;
+                }
+                guard = signal.wait_timeout(guard, grace - now).unwrap().0;
+            }
+        }
+        self.await_actors_stopped(Duration::ZERO, true)
+    }
+
+    /// Running Actors, the count only drops to zero once the removal of the last one is complete
+    fn count_running(&self, include_daemons: bool) -> usize {
+        let daemons = if include_daemons { 0 } else { self.daemon_count.load(Ordering::SeqCst) };
+        self.total_actor_count.load(Ordering::SeqCst).saturating_sub(daemons)
+    }
+
+    /// Blocks until no Actor of `phase` or of a lower one is left, returns `false` once `phase_deadline` passed or a force stop has been requested before
+    ///
+    /// Extensions of the Actors of the phase move `phase_deadline`, but never beyond the `deadline` of the whole stop
+    fn await_phase_stopped(&self, phase: u8, phase_deadline: Option<Instant>, deadline: Option<Instant>) -> bool {
+        let (lock, signal) = &*self.shutdown_signal;
+        let mut guard = lock.lock().unwrap();
+        while self.actors.iter().any(|entry| entry.daemon.is_none() && entry.shutdown_phase <= phase) {
+            let now = Instant::now();
+            let extension = self
+                .actors
+                .iter()
+                .filter(|entry| entry.daemon.is_none() && entry.shutdown_phase <= phase)
+                .filter_map(|entry| entry.stop_extension)
+                .max();
+            let deadline = match (phase_deadline, extension) {
+                (Some(phase_deadline), Some(extension)) if extension > phase_deadline => {
+                    Some(deadline.map_or(extension, |deadline| extension.min(deadline)))
+                }
+                _ => phase_deadline,
+            };
+            if deadline.is_some_and(|deadline| now >= deadline) || self.is_force_stop_requested.load(Ordering::Relaxed) {
+                return false;
+            }
+            guard = match deadline {
+                Some(deadline) => signal.wait_timeout(guard, deadline - now).unwrap().0,
+                None => signal.wait(guard).unwrap(),
+            };
+        }
+        true
+    }
+
+    /// Distinct shutdown phases of all running Actors except daemons, ascending
+    fn get_shutdown_phases(&self) -> Vec<u8> {
+        let mut phases: Vec<u8> = self
+            .actors
+            .iter()
+            .filter(|entry| entry.daemon.is_none())
+            .map(|entry| entry.shutdown_phase)
+            .collect();
+        phases.sort_unstable();
+        phases.dedup();
+        phases
+    }
+
+    /// Blocks until no spawn holds a [Registration] anymore, none is admitted once the system is stopping
+    fn await_registrations(&self) {
+        let (lock, signal) = &*self.shutdown_signal;
+        let mut guard = lock.lock().unwrap();
+        while self.registrations.load(Ordering::SeqCst) != 0 {
+            guard = signal.wait(guard).unwrap();
+        }
+    }
+
+    /// Wakes everyone waiting in [shutdown](#method.shutdown) or [await_stopped](#method.await_stopped)
+    ///
+    /// Taking the lock makes sure a waiter either sees the new state or is already waiting for the notification
+    fn notify_shutdown(&self) {
+        let (lock, signal) = &*self.shutdown_signal;
+        let _guard = lock.lock().unwrap();
+        signal.notify_all();
+        #[cfg(feature = "async")]
+        for waker in std::mem::take(&mut *self.shutdown_wakers.lock().unwrap()) {
+            waker.wake();
+        }
+    }
+
+    /// `Ready` with the code of [ActorSystem.await_shutdown](../prelude/struct.ActorSystem.html#method.await_shutdown) once the system is stopped
+    ///
+    /// The waker is registered before the state is checked again, a stop in between wakes it right away
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_stopped(&self, waker: &Waker) -> Poll<i32> {
+        if !self.is_stopped() {
+            let mut wakers = self.shutdown_wakers.lock().unwrap();
+            if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+            drop(wakers);
+            if !self.is_stopped() {
+                return Poll::Pending;
+            }
+        }
+        Poll::Ready(self.get_exit_code())
     }
 
+    /// Blocks until the system is stopped, returns `false` if `timeout` elapsed before
+    pub(crate) fn await_stopped(&self, timeout: Option<Duration>) -> bool {
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        let (lock, signal) = &*self.shutdown_signal;
+        let mut guard = lock.lock().unwrap();
+        while !self.is_stopped() {
+            guard = match deadline {
+                None => signal.wait(guard).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    signal.wait_timeout(guard, deadline - now).unwrap().0
+                }
+            };
+        }
+        true
+    }
+
+    /// Held until the system is stopped
+    pub(crate) fn set_name_guard(&self, name_guard: NameGuard) {
+        *self.name_guard.lock().unwrap() = Some(name_guard);
+    }
+
+    /// `true` once all Actors stopped and the worker and background threads have been joined, or the stop timeout elapsed before
     pub fn is_stopped(&self) -> bool {
-        self.is_stopped.load(Ordering::Relaxed)
+        self.is_stopped.load(Ordering::SeqCst)
+    }
+
+    /// `true` once all Actors stopped, worker and background threads end with it
+    pub(crate) fn is_halted(&self) -> bool {
+        self.is_halted.load(Ordering::SeqCst)
+    }
+
+    /// Disconnected once the system halted
+    pub fn get_stopped(&self) -> &Receiver<()> {
+        &self.stopped
     }
 
     pub fn is_stopping(&self) -> bool {
         self.is_stopping.load(Ordering::Relaxed)
     }
 
+    /// Actors do not receive the system stop as long as it is held, the graceful termination timeout still applies
+    pub fn hold_shutdown(&self) {
+        self.shutdown_holds.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn release_shutdown(&self) {
+        self.shutdown_holds.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// `true` if the system is stopping, no hold is left and the shutdown reached `shutdown_phase`
+    pub fn is_stop_delivered(&self, shutdown_phase: u8) -> bool {
+        self.is_stopping()
+            && self.shutdown_holds.load(Ordering::SeqCst) == 0
+            && u16::from(shutdown_phase) < self.signalled_phases.load(Ordering::SeqCst)
+    }
+
+    /// Same as [is_stop_delivered](#method.is_stop_delivered) for a daemon, which receives the system stop once all other Actors stopped
+    pub fn is_daemon_stop_delivered(&self, address: &ActorAddress) -> bool {
+        self.is_stopping() && self.shutdown_holds.load(Ordering::SeqCst) == 0 && self.signalled_daemons.contains_key(address)
+    }
+
+    /// `false` for unknown Actors, see [ActorBuilder.set_daemon](../prelude/struct.ActorBuilder.html#method.set_daemon)
+    pub fn is_daemon(&self, address: &ActorAddress) -> bool {
+        self.actors.get(address).is_some_and(|entry| entry.daemon.is_some())
+    }
+
+    /// Queues a snapshot request in the regular mailbox of the Actor, see [ActorSystem.debug_snapshot](../prelude/struct.ActorSystem.html#method.debug_snapshot)
+    pub(crate) fn request_debug_snapshot(
+        &self,
+        address: &ActorAddress,
+        timeout: Duration,
+    ) -> Result<Receiver<Result<String, SnapshotError>>, SnapshotError> {
+        let wrapper = match self.actors.get(address) {
+            Some(actor) => actor.wrapper.clone(),
+            None => return Err(SnapshotError::NotFound),
+        };
+        wrapper.request_debug_snapshot(timeout)
+    }
+
+    /// Queues the move in the priority lane of the Actor, see [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor)
+    pub(crate) fn move_actor(&self, address: &ActorAddress, pool: &str) -> Result<(), MoveError> {
+        let wrapper = match self.actors.get(address) {
+            Some(actor) if actor.wrapper.is_stopping() => return Err(MoveError::Stopping),
+            Some(actor) => actor.wrapper.clone(),
+            None => return Err(MoveError::NotFound),
+        };
+        match wrapper.relocate(String::from(pool)) {
+            true => Ok(()),
+            false => Err(MoveError::Stopping),
+        }
+    }
+
+    /// Empty for unknown Actors, see [ActorBuilder.register_message](../prelude/struct.ActorBuilder.html#method.register_message)
+    pub fn get_accepted_messages(&self, address: &ActorAddress) -> Vec<&'static str> {
+        self.actors
+            .get(address)
+            .map_or_else(Vec::new, |entry| entry.accepted_messages.get_accepted())
+    }
+
+    pub fn add_shutdown_hook(&self, hook: ShutdownHook) -> Result<(), ShutdownHookError> {
+        self.shutdown_hooks.add(hook)
+    }
+
     pub fn is_force_stopped(&self) -> bool {
         self.is_force_stopped.load(Ordering::Relaxed)
     }
 
+    /// Marks the stop as caused by a panic, see [PanicEscalation::StopSystem](../prelude/enum.PanicEscalation.html#variant.StopSystem)
+    pub(crate) fn set_panic_stopped(&self) {
+        self.is_panic_stopped.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_panic_stopped(&self) -> bool {
+        self.is_panic_stopped.load(Ordering::Relaxed)
+    }
+
+    /// Code of [ActorSystem.await_shutdown](../prelude/struct.ActorSystem.html#method.await_shutdown), a panic takes precedence over a forced stop
+    pub fn get_exit_code(&self) -> i32 {
+        // `2` is taken by the `Failed` code of the C API
+        if self.is_panic_stopped() {
+            3
+        } else {
+            self.is_force_stopped() as i32
+        }
+    }
+
+    /// Executes the shutdown hooks right away, see [PanicEscalation::Abort](../prelude/enum.PanicEscalation.html#variant.Abort)
+    pub(crate) fn execute_shutdown_hooks(&self) {
+        self.shutdown_hooks.execute();
+    }
+
     pub fn get_actor_count(&self) -> usize {
         self.total_actor_count.load(Ordering::Relaxed)
     }
 
-    pub fn send_to_address(&self, address: &ActorAddress, msg: SerializedMessage) {
-        let target = self.actors.get(address);
-        if target.is_some() {
-            let target = target.unwrap();
-            target.handle_serialized_message(msg);
+    pub fn next_transaction_id(&self) -> u64 {
+        self.next_transaction_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub fn send_to_address(&self, address: &ActorAddress, msg: SerializedMessage) -> Result<(), ProtocolError> {
+        let (wrapper, serialized) = match self.actors.get(address) {
+            Some(target) => (target.wrapper.clone(), target.serialized.clone()),
+            None => {
+                self.dead_letters.report(address, std::any::type_name::<SerializedMessage>());
+                return Err(ProtocolError::ActorNotFound);
+            }
+        };
+        let protocol = self.protocols.get(address).map(|entry| entry.clone());
+        let msg = match protocol {
+            Some(protocol) => protocol.upgrade(msg)?,
+            None => msg,
+        };
+        let msg = match serialized {
+            Some(serialized) => {
+                let type_tag = msg.type_tag;
+                match serialized.dispatch(msg) {
+                    Dispatched::Untyped(msg) => *msg,
+                    Dispatched::Sent => return Ok(()),
+                    Dispatched::Unregistered(msg) => {
+                        self.send_unhandled(address, wrapper.as_ref(), *msg);
+                        return Ok(());
+                    }
+                    Dispatched::Undecodable(msg) => {
+                        self.send_unhandled(address, wrapper.as_ref(), *msg);
+                        return Err(ProtocolError::Undecodable {
+                            type_tag: type_tag.unwrap_or_default(),
+                        });
+                    }
+                }
+            }
+            None => msg,
+        };
+        if !wrapper.send_untyped(msg) {
+            self.dead_letters.report(address, std::any::type_name::<SerializedMessage>());
+        }
+        Ok(())
+    }
+
+    fn send_unhandled(&self, address: &ActorAddress, wrapper: &dyn RegisteredActor, msg: SerializedMessage) {
+        if !wrapper.send_unhandled(UnhandledInfo::Serialized(Box::new(msg))) {
+            self.dead_letters.report(address, std::any::type_name::<SerializedMessage>());
+        }
+    }
+
+    pub(crate) fn add_protocol(&self, address: ActorAddress, protocol: ProtocolEntry) {
+        self.protocols.insert(address, Arc::new(protocol));
+    }
+
+    pub fn get_protocol(&self, address: &ActorAddress) -> Option<ActorProtocol> {
+        self.protocols.get(address).map(|entry| entry.get_protocol().clone())
+    }
+
+    pub fn get_protocols(&self) -> Vec<(ActorAddress, ActorProtocol)> {
+        let mut protocols: Vec<(ActorAddress, ActorProtocol)> = self
+            .protocols
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().get_protocol().clone()))
+            .collect();
+        protocols.sort_by(|a, b| a.0.cmp(&b.0));
+        protocols
+    }
+
+    /// Keeps the shutdown phase of the Actor open until `until`, only while the system is stopping
+    pub fn extend_stop(&self, address: &ActorAddress, until: Instant) {
+        if !self.is_stopping() {
+            return;
+        }
+        if let Some(mut actor) = self.actors.get_mut(address) {
+            actor.stop_extension = Some(until);
+        }
+    }
+
+    /// `pool` is the pool the Actor has been executed in, its counters are retained as part of the totals of that pool
+    pub fn remove_actor(&self, address: &ActorAddress, pool: &str) {
+        let removed = self.actors.remove(address);
+        // a daemon leaves the count after it left the daemons, so that other Actors are never counted short
+        if removed.as_ref().is_some_and(|(_, actor)| actor.daemon.is_some()) {
+            self.daemon_count.fetch_sub(1, Ordering::SeqCst);
+            self.signalled_daemons.remove(address);
+        }
+        let is_last = self.total_actor_count.fetch_sub(1, Ordering::SeqCst) == 1;
+        {
+            let mut retired_totals = self.retired_totals.lock().unwrap();
+            if let Some((_, metrics)) = self.actor_metrics.remove(address) {
+                let stats = metrics.get_stats();
+                let totals = retired_totals.entry(String::from(pool)).or_default();
+                totals.processed += stats.processed;
+                totals.restarts += stats.restarts;
+                totals.panics += stats.panics;
+            }
+        }
+        self.awaiting_release.remove(address);
+        self.protocols.remove(address);
+        self.event_bus.remove_subscriber(address);
+        self.startup_order.notify_removed(address, &self.event_bus);
+        // the shutdown waits for the last Actor of each phase
+        if is_last || self.is_stopping() {
+            self.notify_shutdown();
+        }
+        self.stop_children(address);
+        if let Some((_, actor)) = removed {
+            actor.wrapper.notify_removed();
+        }
+        // a stopped Actor does not hold anyone up
+        self.notify_ready();
+    }
+
+    /// Called once the Actor got ready, releases the Actors that depend on it
+    pub(crate) fn notify_actor_ready(&self, address: &ActorAddress) {
+        self.startup_order.notify_ready(address);
+        self.notify_ready();
+    }
+
+    pub(crate) fn notify_ready(&self) {
+        let (lock, signal) = &*self.ready_signal;
+        let _guard = lock.lock().unwrap();
+        signal.notify_all();
+    }
+
+    /// Running Actors that are not ready yet, sorted by address
+    pub(crate) fn get_unready(&self) -> Vec<ActorAddress> {
+        let mut unready: Vec<ActorAddress> = self
+            .actors
+            .iter()
+            .filter(|entry| !entry.value().wrapper.is_ready())
+            .map(|entry| entry.key().clone())
+            .collect();
+        unready.sort();
+        unready
+    }
+
+    /// Waits until all `pending` Actors are ready or have been removed, returns those that are neither once the timeout elapsed
+    pub(crate) fn await_ready(&self, mut pending: Vec<ActorAddress>, timeout: Duration) -> Result<(), Vec<ActorAddress>> {
+        let deadline = Instant::now().checked_add(timeout);
+        let (lock, signal) = &*self.ready_signal;
+        let mut guard = lock.lock().unwrap();
+        loop {
+            pending.retain(|address| self.actors.get(address).is_some_and(|actor| !actor.wrapper.is_ready()));
+            if pending.is_empty() {
+                return Ok(());
+            }
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::from_secs(3600),
+            };
+            if remaining.is_zero() {
+                return Err(pending);
+            }
+            guard = signal.wait_timeout(guard, remaining).unwrap().0;
+        }
+    }
+
+    /// Registers `child` to be stopped together with `parent`, returns `false` if it is already registered
+    pub(crate) fn add_child(&self, parent: &ActorAddress, child: ActorAddress, stop: StopCallback) -> bool {
+        let mut children = self.children.entry(parent.clone()).or_default();
+        if children.iter().any(|entry| entry.address == child) {
+            return false;
+        }
+        children.push(ChildEntry { address: child, stop });
+        true
+    }
+
+    /// Called once `child` terminated, so that it is not stopped again together with `parent`
+    pub(crate) fn remove_child(&self, parent: &ActorAddress, child: &ActorAddress) {
+        if let Some(mut children) = self.children.get_mut(parent) {
+            children.retain(|entry| &entry.address != child);
+        }
+        self.children.remove_if(parent, |_, children| children.is_empty());
+    }
+
+    /// Children of `parent` that have not terminated yet, in the order they have been spawned
+    pub(crate) fn get_children(&self, parent: &ActorAddress) -> Vec<ActorAddress> {
+        self.children
+            .get(parent)
+            .map_or_else(Vec::new, |children| children.iter().map(|entry| entry.address.clone()).collect())
+    }
+
+    /// Stops all children of `parent`, returns `false` if it has none
+    ///
+    /// The entry is removed before any child is stopped, so that no shard is locked while the stop messages are sent
+    fn stop_children(&self, parent: &ActorAddress) -> bool {
+        let children = match self.children.remove(parent) {
+            Some((_, children)) => children,
+            None => return false,
+        };
+        for child in children {
+            (child.stop)();
+        }
+        true
+    }
+
+    pub fn get_event_bus(&self) -> &EventBus {
+        &self.event_bus
+    }
+
+    pub(crate) fn get_startup_order(&self) -> &StartupOrder {
+        &self.startup_order
+    }
+
+    pub(crate) fn get_dead_letters(&self) -> &DeadLetterOffice {
+        &self.dead_letters
+    }
+
+    pub(crate) fn get_instrumentation(&self) -> &Instrumentation {
+        &self.instrumentation
+    }
+
+    pub(crate) fn get_memory_pressure(&self) -> &MemoryPressureState {
+        &self.memory_pressure
+    }
+
+    pub(crate) fn add_actor<A>(
+        &self,
+        address: ActorAddress,
+        wrapper: ActorWrapper<A>,
+        executor: Weak<RwLock<dyn ExecutorTrait>>,
+        serialized: Option<SerializedDispatch>,
+        metrics: ActorMetrics,
+    ) where
+        A: Actor + UnwindSafe + 'static,
+    {
+        self.total_actor_count.fetch_add(1, Ordering::SeqCst);
+        self.actor_metrics.insert(address.clone(), metrics);
+        let accepted_messages = wrapper.get_mailbox().accepted_messages.clone();
+        // lazy Actors are ready from the start
+        let is_ready = wrapper.get_mailbox().is_ready();
+        // not scheduled before it has been added
+        let (shutdown_phase, is_daemon) = executor.upgrade().map_or((DEFAULT_SHUTDOWN_PHASE, false), |executor| {
+            let executor = executor.read().unwrap();
+            let config = executor.get_config();
+            (config.shutdown_phase, config.daemon)
+        });
+        // counted before it is added, the drain never sees the Actor as a regular one
+        let daemon = is_daemon.then(|| {
+            self.daemon_count.fetch_add(1, Ordering::SeqCst);
+            self.next_daemon.fetch_add(1, Ordering::SeqCst)
+        });
+        let actor = ActiveActor {
+            wrapper: Arc::new(wrapper),
+            executor,
+            serialized: serialized.map(Arc::new),
+            accepted_messages,
+            shutdown_phase,
+            daemon,
+            stop_extension: None,
+        };
+        self.actors.insert(address.clone(), actor);
+        if let Some((_, watches)) = self.activation_watchers.remove(&address) {
+            for watch in watches {
+                (watch.callback)();
+            }
+        }
+        if is_ready {
+            self.startup_order.notify_ready(&address);
+        }
+    }
+
+    /// Executes `callback` once an Actor with the given address has been added
+    ///
+    /// Executes it immediately if the Actor is already active
+    pub fn notify_when_active(&self, address: &ActorAddress, callback: ActivationCallback) {
+        self.watch_activation(address, None, callback);
+    }
+
+    /// Same as [notify_when_active](#method.notify_when_active), the callback is dropped once `watcher` stopped
+    pub(crate) fn notify_watcher_when_active(&self, address: &ActorAddress, watcher: ActorAddress, callback: ActivationCallback) {
+        self.watch_activation(address, Some(watcher), callback);
+    }
+
+    fn watch_activation(&self, address: &ActorAddress, watcher: Option<ActorAddress>, callback: ActivationCallback) {
+        self.activation_watchers
+            .entry(address.clone())
+            .or_default()
+            .push(ActivationWatch { watcher, callback });
+        if self.is_actor_active(address) {
+            if let Some((_, watches)) = self.activation_watchers.remove(address) {
+                for watch in watches {
+                    (watch.callback)();
+                }
+            }
+        }
+    }
+
+    /// Drops all callbacks of `watcher` waiting for `address`
+    pub(crate) fn cancel_activation_watch(&self, address: &ActorAddress, watcher: &ActorAddress) {
+        if let Some(mut watches) = self.activation_watchers.get_mut(address) {
+            watches.retain(|watch| watch.watcher.as_ref() != Some(watcher));
+        }
+        self.activation_watchers.remove_if(address, |_, watches| watches.is_empty());
+    }
+
+    /// Registers an Actor that has been spawned suspended, `release` returns `false` if it has already been released
+    pub fn add_awaiting_release(&self, address: ActorAddress, release: ReleaseCallback) {
+        self.awaiting_release.insert(address, release);
+    }
+
+    /// Releases all suspended Actors matching `filter` in the order of their addresses and returns how many have been released
+    pub fn release_all<F>(&self, filter: F) -> usize
+    where
+        F: Fn(&ActorAddress) -> bool,
+    {
+        let mut addresses: Vec<ActorAddress> = self
+            .awaiting_release
+            .iter()
+            .filter(|entry| filter(entry.key()))
+            .map(|entry| entry.key().clone())
+            .collect();
+        addresses.sort();
+        let mut released = 0;
+        for address in addresses {
+            if let Some((_, release)) = self.awaiting_release.remove(&address) {
+                if release() {
+                    released += 1;
+                }
+            }
+        }
+        released
+    }
+
+    /// Fills `target` with the metrics of all Actors that keep a history, reusing its allocation
+    pub fn collect_actor_metrics(&self, target: &mut Vec<ActorMetrics>) {
+        for entry in self.actor_metrics.iter() {
+            if entry.value().has_history() {
+                target.push(entry.value().clone());
+            }
         }
     }
 
-    pub fn remove_actor(&self, address: &ActorAddress) {
-        self.total_actor_count.fetch_sub(1, Ordering::Relaxed);
-        self.actors.remove(address);
+    pub(crate) fn get_metrics_of(&self, address: &ActorAddress) -> Option<ActorMetrics> {
+        self.actor_metrics.get(address).map(|entry| entry.value().clone())
+    }
+
+    /// Executes `collect` while no Actor is removed, so that the metrics of an Actor are either collected or part of the returned totals
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_retired_totals<R>(&self, collect: impl FnOnce() -> R) -> (R, HashMap<String, RetiredTotals>) {
+        let retired_totals = self.retired_totals.lock().unwrap();
+        (collect(), retired_totals.clone())
+    }
+
+    pub fn get_actor_metrics(&self) -> Vec<(ActorAddress, ActorMetrics)> {
+        self.actor_metrics
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
+    /// Wrapper of the Actor at `address`, `None` if it is not running, already stopping or of another type than `A`
+    pub(crate) fn get_actor_wrapper<A>(&self, address: &ActorAddress) -> Option<ActorWrapper<A>>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        get_running_wrapper(&self.actors, address)
+    }
+
+    /// Same as [get_actor_wrapper](#method.get_actor_wrapper), but tells a missing Actor apart from one of another type
+    pub(crate) fn resolve_actor_wrapper<A>(&self, address: &ActorAddress) -> Result<ActorWrapper<A>, ResolveError>
+    where
+        A: Actor + UnwindSafe + 'static,
+    {
+        resolve_running_wrapper(&self.actors, address)
+    }
+
+    /// Weak handle to the registered Actors, kept by every mailbox for [ActorWrapper.downgrade](../prelude/struct.ActorWrapper.html#method.downgrade)
+    pub(crate) fn get_actor_registry(&self) -> ActorRegistry {
+        ActorRegistry(Arc::downgrade(&self.actors))
     }
 
-    pub fn add_actor(&self, address: ActorAddress, actor: Arc<dyn Actor>) {
-        self.total_actor_count.fetch_add(1, Ordering::Relaxed);
-        self.actors.insert(address, actor);
+    /// Addresses of all Actors that match `filter`, skipping Actors that have been asked to stop
+    pub(crate) fn select<F>(&self, filter: F) -> Vec<ActorAddress>
+    where
+        F: Fn(&ActorAddress) -> bool,
+    {
+        self.actors
+            .iter()
+            .filter(|entry| filter(entry.key()) && !entry.value().wrapper.is_stopping())
+            .map(|entry| entry.key().clone())
+            .collect()
     }
 
     pub fn is_actor_active(&self, address: &ActorAddress) -> bool {
         self.actors.contains_key(address)
     }
+
+    /// `false` if no Actor with the address is running
+    pub(crate) fn is_actor_ready(&self, address: &ActorAddress) -> bool {
+        self.actors.get(address).is_some_and(|actor| actor.wrapper.is_ready())
+    }
+
+    /// `true` if the Actor went to sleep but has not been handed to the wakeup manager yet
+    ///
+    /// An Actor that is still executed by a worker is not, it checks its mailbox again before it is handed over
+    pub(crate) fn is_falling_asleep(&self, address: &ActorAddress) -> bool {
+        let executor = match self.actors.get(address).and_then(|actor| actor.executor.upgrade()) {
+            Some(executor) => executor,
+            None => return false,
+        };
+        let is_falling_asleep = match executor.try_read() {
+            Ok(executor) => executor.is_sleeping(),
+            Err(_) => false,
+        };
+        is_falling_asleep
+    }
+
+    /// Admits the registration of a spawned Actor, `None` once the system is stopping and `shutdown_phase` completed
+    ///
+    /// A phase is completed before the shutdown waits for running registrations, so an Actor is either rejected or added before the shutdown picks the next phase.
+    /// The guard has to be held until the Actor has been added to the [WakeupManager](./struct.WakeupManager.html)
+    pub(crate) fn begin_registration(&self, shutdown_phase: u8) -> Option<Registration> {
+        self.registrations.fetch_add(1, Ordering::SeqCst);
+        let registration = Registration { state: self.clone() };
+        // joins the shutdown as long as its phase has not completed
+        if self.is_stopping.load(Ordering::SeqCst) && u16::from(shutdown_phase) < self.completed_phases.load(Ordering::SeqCst) {
+            return None;
+        }
+        Some(registration)
+    }
+
+    /// Shared with the mailboxes, sends are reported as dead letters once the system is stopped
+    pub(crate) fn get_stopped_flag(&self) -> Arc<AtomicBool> {
+        self.is_stopped.clone()
+    }
+
+    /// Reserves the address for a spawn, `None` if an Actor with the address is running or being spawned
+    ///
+    /// The reservation is dropped after [add_actor](#method.add_actor), the entry of `spawning` is held while `actors` is checked,
+    /// so that two spawns of the same address can not both succeed
+    pub(crate) fn reserve_address(&self, address: &ActorAddress) -> Option<AddressReservation> {
+        match self.spawning.entry(address.clone()) {
+            Entry::Occupied(_) => None,
+            Entry::Vacant(entry) => {
+                if self.actors.contains_key(address) {
+                    return None;
+                }
+                entry.insert(());
+                Some(AddressReservation {
+                    spawning: self.spawning.clone(),
+                    address: address.clone(),
+                })
+            }
+        }
+    }
+
+    /// Entries of registries keyed by address whose Actor is not active
+    ///
+    /// Keys are collected before they are checked, so that no shard is locked while another one is
+    pub(crate) fn find_registry_orphans(&self) -> Vec<(Registry, ActorAddress)> {
+        let mut orphans = Vec::new();
+        let registries = [
+            (Registry::ActorMetrics, keys(&self.actor_metrics)),
+            (Registry::Protocols, keys(&self.protocols)),
+            (Registry::AwaitingRelease, keys(&self.awaiting_release)),
+            (Registry::Children, keys(&self.children)),
+            (Registry::EventSubscriptions, self.event_bus.get_subscribers()),
+        ];
+        for (registry, addresses) in registries {
+            for address in addresses {
+                if !self.is_actor_active(&address) {
+                    orphans.push((registry, address));
+                }
+            }
+        }
+        orphans
+    }
+
+    /// Removes the entry if its Actor is still not active, returns `false` if there was nothing to remove
+    pub(crate) fn reclaim_registry_entry(&self, registry: Registry, address: &ActorAddress) -> bool {
+        if self.is_actor_active(address) {
+            return false;
+        }
+        match registry {
+            Registry::ActorMetrics => self.actor_metrics.remove(address).is_some(),
+            Registry::Protocols => self.protocols.remove(address).is_some(),
+            Registry::AwaitingRelease => self.awaiting_release.remove(address).is_some(),
+            // the children of a parent whose cleanup has been skipped are stopped as they would have been
+            Registry::Children => self.stop_children(address),
+            Registry::EventSubscriptions => self.event_bus.remove_subscriber(address),
+        }
+    }
+
+    /// Drops the activation callbacks of Actors that are no longer active, returns the address of every dropped watcher
+    pub(crate) fn reclaim_dead_activation_watchers(&self) -> Vec<ActorAddress> {
+        let addresses: Vec<ActorAddress> = self.activation_watchers.iter().map(|entry| entry.key().clone()).collect();
+        let mut reclaimed = Vec::new();
+        for address in addresses {
+            let watchers: Vec<ActorAddress> = match self.activation_watchers.get(&address) {
+                Some(watches) => watches.iter().filter_map(|watch| watch.watcher.clone()).collect(),
+                None => continue,
+            };
+            for watcher in watchers {
+                if !self.is_actor_active(&watcher) {
+                    self.cancel_activation_watch(&address, &watcher);
+                    reclaimed.push(watcher);
+                }
+            }
+        }
+        reclaimed
+    }
+
+    /// Adds an entry for the address to every registry keyed by address, as if the cleanup of a stopped Actor had been skipped
+    #[cfg(feature = "testing")]
+    pub(crate) fn inject_orphans(&self, address: &ActorAddress) {
+        let metrics = ActorMetrics::new(Box::new(|| 0), Default::default(), None);
+        self.actor_metrics.insert(address.clone(), metrics);
+        let protocol = ProtocolEntry::new(ActorProtocol::new(1, 1..=1), Default::default());
+        self.protocols.insert(address.clone(), Arc::new(protocol));
+        self.awaiting_release.insert(address.clone(), Box::new(|| false));
+        let mut never_spawned_child = address.clone();
+        never_spawned_child.actor = format!("{}-never-spawned-child", address.actor);
+        self.add_child(address, never_spawned_child, Box::new(|| {}));
+        let mut never_spawned = address.clone();
+        never_spawned.actor = format!("{}-never-spawned", address.actor);
+        self.activation_watchers
+            .entry(never_spawned)
+            .or_default()
+            .push(ActivationWatch {
+                watcher: Some(address.clone()),
+                callback: Box::new(|| {}),
+            });
+        self.event_bus.insert_orphan("system.integrity.orphan", address.clone());
+    }
+
+    /// Sizes of the registries owned by the system state, the other counts are left untouched
+    pub(crate) fn count_registries(&self, counts: &mut IntegrityCounts) {
+        counts.actor_metrics = self.actor_metrics.len();
+        counts.protocols = self.protocols.len();
+        counts.awaiting_release = self.awaiting_release.len();
+        counts.children = self.children.iter().map(|entry| entry.value().len()).sum();
+        counts.activation_watchers = self.activation_watchers.iter().map(|entry| entry.value().len()).sum();
+        counts.event_subscriptions = self.event_bus.get_subscribers().len();
+    }
+}
+
+fn keys<V>(map: &DashMap<ActorAddress, V>) -> Vec<ActorAddress> {
+    map.iter().map(|entry| entry.key().clone()).collect()
 }
@@ -1,131 +1,734 @@
+use crate::actor::actor_address::ActorAddress;
 use crate::actor::actor_state::ActorState;
+use crate::actor::actor_config::ActorDefaults;
 use crate::actor::executor::ExecutorTrait;
-use crate::config::pool_config::ThreadPoolConfig;
+use crate::actor::metric_stripes;
+use crate::actor::priority::ActorPriority;
+use crate::config::global_config::HistoryConfig;
+use crate::config::pool_config::{IdleStrategy, ThreadPoolConfig};
+use crate::system::metrics_history::{PoolMetricsHistory, PoolMetricsSample, RingBuffer};
+use crate::system::pool_stats::{PoolError, PoolLoad, PoolStats};
 use crate::system::system_state::SystemState;
 use crate::system::wakeup_manager::WakeupManager;
-use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use crate::system::worker_stats::{PoolWorkerStats, WorkerAccumulator, WorkerRegistry};
+use crossbeam_channel::{bounded, unbounded, Receiver, Select, Sender, TryRecvError};
 use dashmap::DashMap;
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
-use std::thread::sleep;
-use std::time::Duration;
-use threadpool::ThreadPool;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Runnable Actor together with the time it has been queued
+type QueuedActor = (Arc<RwLock<dyn ExecutorTrait>>, Instant);
+
+/// Actors that are handed to a single idle worker, only used with soft affinity
+#[derive(Clone)]
+struct WorkerLane {
+    is_idle: Arc<AtomicBool>,
+    sender: Sender<QueuedActor>,
+    receiver: Receiver<QueuedActor>,
+}
+
+impl WorkerLane {
+    fn new() -> Self {
+        let (sender, receiver) = unbounded();
+        Self {
+            is_idle: Arc::new(AtomicBool::new(false)),
+            sender,
+            receiver,
+        }
+    }
+}
+
+/// Worker thread of a pool, see [ThreadPoolManager.manage](./struct.ThreadPoolManager.html#method.manage)
+struct WorkerThread {
+    /// identifies the worker in its stats and its soft affinity lane, a replaced worker gets a new one
+    worker: usize,
+    handle: JoinHandle<()>,
+    /// the worker exits after its current slot, set for surplus workers of a shrunk pool
+    retire: Arc<AtomicBool>,
+}
+
+/// Wakes up the [ThreadPoolManager] as soon as a worker exits, including a panic
+struct WorkerExit(Sender<()>);
+
+impl Drop for WorkerExit {
+    fn drop(&mut self) {
+        let _ = self.0.send(());
+    }
+}
+
+/// Counts a worker as busy while it executes a slot, also if the slot panics
+struct BusyWorker(Arc<AtomicUsize>);
+
+impl BusyWorker {
+    fn enter(busy_threads: &Arc<AtomicUsize>) -> Self {
+        busy_threads.fetch_add(1, Ordering::Relaxed);
+        Self(busy_threads.clone())
+    }
+}
+
+impl Drop for BusyWorker {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Actor taken from a queue, together with the index of the victim it has been stolen from
+type Popped = (Arc<RwLock<dyn ExecutorTrait>>, Option<usize>);
+
+/// Runnable Actors of a pool, with one lane per [ActorPriority](../prelude/enum.ActorPriority.html)
+#[derive(Clone)]
+pub struct PoolQueue {
+    senders: Vec<Sender<QueuedActor>>,
+    receivers: Vec<Receiver<QueuedActor>>,
+    soft_affinity: bool,
+    idle_strategy: IdleStrategy,
+    /// empty polls of idle workers, see [PoolStats.idle_polls](../prelude/struct.PoolStats.html#structfield.idle_polls)
+    idle_polls: Arc<AtomicUsize>,
+    /// nanoseconds, see [PoolLoad.queue_latency](../prelude/struct.PoolLoad.html#structfield.queue_latency)
+    queue_latency: Arc<AtomicU64>,
+    workers: Arc<DashMap<usize, WorkerLane>>,
+    /// never receives anything, dropping the sender wakes up all idle workers once the system has stopped
+    closing: Arc<Mutex<Option<Sender<()>>>>,
+    closed: Receiver<()>,
+}
+
+impl PoolQueue {
+    fn new(actor_limit: usize, soft_affinity: bool, idle_strategy: IdleStrategy) -> Self {
+        let (senders, receivers) = (0..ActorPriority::COUNT)
+            .map(|_| if actor_limit == 0 { unbounded() } else { bounded(actor_limit) })
+            .unzip();
+        let (closing, closed) = bounded(0);
+        Self {
+            senders,
+            receivers,
+            soft_affinity,
+            idle_strategy,
+            idle_polls: Arc::new(AtomicUsize::new(0)),
+            queue_latency: Arc::new(AtomicU64::new(0)),
+            workers: Arc::new(DashMap::new()),
+            closing: Arc::new(Mutex::new(Some(closing))),
+            closed,
+        }
+    }
+
+    fn close(&self) {
+        self.closing.lock().unwrap().take();
+    }
+
+    /// Requeues the Actors handed to a worker that is gone
+    fn release_lane(&self, worker: usize) {
+        if let Some((_, lane)) = self.workers.remove(&worker) {
+            while let Ok((actor, queued_at)) = lane.receiver.try_recv() {
+                self.push_queued(actor, queued_at);
+            }
+        }
+    }
+
+    /// Queues the Actor in the lane of its current effective priority
+    ///
+    /// Actors without pending work are queued in the lowest lane, so that idle Actors can not starve busy ones.
+    /// With soft affinity an Actor is handed directly to its previous worker if that worker is idle
+    pub fn push(&self, actor: Arc<RwLock<dyn ExecutorTrait>>) {
+        self.push_queued(actor, Instant::now());
+    }
+
+    /// Same as [push](#method.push), but keeps the time the Actor has originally been queued
+    fn push_queued(&self, actor: Arc<RwLock<dyn ExecutorTrait>>, queued_at: Instant) {
+        let (priority, last_worker) = {
+            let actor = actor.read().unwrap();
+            let priority = if actor.is_wakeup_pending() {
+                actor.get_effective_priority()
+            } else {
+                ActorPriority::Low
+            };
+            (priority, actor.get_last_worker())
+        };
+        if self.soft_affinity {
+            if let Some(lane) = last_worker.and_then(|worker| self.workers.get(&worker)) {
+                if lane.is_idle.load(Ordering::SeqCst) {
+                    lane.sender.send((actor, queued_at)).unwrap();
+                    return;
+                }
+            }
+        }
+        self.senders[priority.index()].send((actor, queued_at)).unwrap();
+    }
+
+    pub(crate) fn try_pop(&self) -> Option<Arc<RwLock<dyn ExecutorTrait>>> {
+        let queued = self.receivers.iter().rev().find_map(|receiver| receiver.try_recv().ok())?;
+        Some(self.picked_up(queued))
+    }
+
+    /// Folds the time the Actor waited into the moving average, the newest sample weighs 1/8
+    fn picked_up(&self, (actor, queued_at): QueuedActor) -> Arc<RwLock<dyn ExecutorTrait>> {
+        let waited = queued_at.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+        let _ = self.queue_latency.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| match average {
+            0 => Some(waited.max(1)),
+            average => Some((average - average / 8 + waited / 8).max(1)),
+        });
+        actor
+    }
+
+    fn get_queue_latency(&self) -> Duration {
+        Duration::from_nanos(self.queue_latency.load(Ordering::Relaxed))
+    }
+
+    /// Falls back to the queues of `victims` if this queue is empty, returns the index of the victim an Actor has been stolen from
+    fn pop(
+        &self,
+        timeout: Duration,
+        lane: &WorkerLane,
+        victims: &[PoolQueue],
+    ) -> Option<Popped> {
+        if let Some(popped) = self.try_pop_or_steal(lane, victims) {
+            return Some(popped);
+        }
+        lane.is_idle.store(true, Ordering::SeqCst);
+        let popped = self.poll_idle(timeout, lane, victims);
+        lane.is_idle.store(false, Ordering::SeqCst);
+        if popped.is_some() {
+            return popped;
+        }
+        if self.idle_strategy == IdleStrategy::Yield {
+            return None;
+        }
+        let mut select = Select::new();
+        for receiver in self.receivers.iter() {
+            select.recv(receiver);
+        }
+        select.recv(&lane.receiver);
+        select.recv(&self.closed);
+        for receiver in victims.iter().flat_map(|victim| victim.receivers.iter()) {
+            select.recv(receiver);
+        }
+        // only waits for readiness, the lane with the highest priority is taken afterwards
+        lane.is_idle.store(true, Ordering::SeqCst);
+        let ready = select.ready_timeout(timeout);
+        lane.is_idle.store(false, Ordering::SeqCst);
+        ready.ok()?;
+        self.try_pop_or_steal(lane, victims)
+    }
+
+    /// Polls the queues according to the [IdleStrategy] before the worker waits, the worker does not wait at all with [IdleStrategy::Yield]
+    fn poll_idle(&self, timeout: Duration, lane: &WorkerLane, victims: &[PoolQueue]) -> Option<Popped> {
+        let (until, is_yielding) = match self.idle_strategy {
+            IdleStrategy::ParkImmediately => return None,
+            IdleStrategy::SpinThenPark { spin_us } => (Instant::now() + Duration::from_micros(spin_us), false),
+            IdleStrategy::Yield => (Instant::now() + timeout, true),
+        };
+        // the sender is only dropped once the system has stopped
+        while Instant::now() < until && self.closed.try_recv() != Err(TryRecvError::Disconnected) {
+            match is_yielding {
+                true => std::thread::yield_now(),
+                false => std::hint::spin_loop(),
+            }
+            if let Some(popped) = self.try_pop_or_steal(lane, victims) {
+                return Some(popped);
+            }
+        }
+        None
+    }
+
+    fn try_pop_or_steal(
+        &self,
+        lane: &WorkerLane,
+        victims: &[PoolQueue],
+    ) -> Option<Popped> {
+        if let Some(actor) = lane.receiver.try_recv().ok().map(|queued| self.picked_up(queued)).or_else(|| self.try_pop()) {
+            return Some((actor, None));
+        }
+        let popped = victims
+            .iter()
+            .enumerate()
+            .find_map(|(index, victim)| victim.try_pop().map(|actor| (actor, Some(index))));
+        if popped.is_none() {
+            self.idle_polls.fetch_add(1, Ordering::Relaxed);
+        }
+        popped
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.receivers.iter().map(|receiver| receiver.len()).sum()
+    }
+}
+
+#[derive(Clone)]
+struct ThreadPoolEntry {
+    config: ThreadPoolConfig,
+    queue: PoolQueue,
+    thread_count: Arc<AtomicUsize>,
+    /// workers that are executing a slot right now
+    busy_threads: Arc<AtomicUsize>,
+    history: Option<Arc<Mutex<RingBuffer<PoolMetricsSample>>>>,
+    worker_stats: Arc<WorkerRegistry>,
+    next_worker: Arc<AtomicUsize>,
+    worker_panics: Arc<AtomicUsize>,
+    /// slots the workers of this pool executed for Actors of the pools in `steal_from`
+    stolen_slots: Arc<AtomicUsize>,
+    /// only set for the thread of a dedicated Actor, set once that Actor has been removed
+    stopped: Option<Arc<AtomicBool>>,
+}
+
+impl ThreadPoolEntry {
+    fn new(config: ThreadPoolConfig, worker_stats_interval: Duration, history_config: Option<HistoryConfig>) -> Self {
+        let queue = PoolQueue::new(config.actor_limit, config.soft_affinity, config.idle_strategy);
+        let worker_stats = WorkerRegistry::new(worker_stats_interval, config.soft_affinity);
+        let history = history_config.map(|config| Arc::new(Mutex::new(RingBuffer::new(config.capacity()))));
+        Self {
+            config,
+            queue,
+            thread_count: Arc::new(AtomicUsize::new(0)),
+            busy_threads: Arc::new(AtomicUsize::new(0)),
+            history,
+            worker_stats: Arc::new(worker_stats),
+            next_worker: Arc::new(AtomicUsize::new(0)),
+            worker_panics: Arc::new(AtomicUsize::new(0)),
+            stolen_slots: Arc::new(AtomicUsize::new(0)),
+            stopped: None,
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped.as_ref().is_some_and(|stopped| stopped.load(Ordering::Relaxed))
+    }
+}
 
 #[derive(Clone)]
 pub struct ThreadPoolManager {
-    thread_pools: Arc<
-        DashMap<
-            String,
-            (
-                ThreadPoolConfig,
-                Sender<Arc<RwLock<dyn ExecutorTrait>>>,
-                Receiver<Arc<RwLock<dyn ExecutorTrait>>>,
-            ),
-        >,
-    >,
+    thread_pools: Arc<DashMap<String, ThreadPoolEntry>>,
+    /// single worker threads of Actors spawned through [ActorBuilder.set_dedicated_thread](../prelude/struct.ActorBuilder.html#method.set_dedicated_thread)
+    dedicated: Arc<DashMap<ActorAddress, ThreadPoolEntry>>,
+    /// Actors that have been moved away from the pool of their address, see [ActorSystem.move_actor](../prelude/struct.ActorSystem.html#method.move_actor)
+    placements: Arc<DashMap<ActorAddress, String>>,
+    history_config: Option<HistoryConfig>,
+    worker_stats_interval: Duration,
+    /// prefix of the names of all worker threads
+    system_name: String,
+    workers_joined: Arc<(Mutex<bool>, Condvar)>,
+    /// new pools and exiting workers, see [manage](#method.manage)
+    changes: (Sender<()>, Receiver<()>),
 }
 
 impl ThreadPoolManager {
-    pub fn new() -> Self {
+    pub fn new(system_name: &str, history_config: Option<HistoryConfig>, worker_stats_interval: Duration) -> Self {
         Self {
             thread_pools: Arc::new(DashMap::new()),
+            dedicated: Arc::new(DashMap::new()),
+            placements: Arc::new(DashMap::new()),
+            history_config,
+            worker_stats_interval,
+            system_name: String::from(system_name),
+            workers_joined: Arc::new((Mutex::new(false), Condvar::new())),
+            changes: unbounded(),
+        }
+    }
+
+    pub fn get_pool_queue(&self, name: &str) -> PoolQueue {
+        self.thread_pools.get(name).unwrap().queue.clone()
+    }
+
+    /// Queue the Actor is scheduled on, the queue of its dedicated thread if it has one
+    pub(crate) fn get_actor_queue(&self, address: &ActorAddress) -> PoolQueue {
+        if let Some(dedicated) = self.dedicated.get(address) {
+            return dedicated.queue.clone();
+        }
+        match self.placements.get(address) {
+            Some(pool) => self.get_pool_queue(pool.value()),
+            None => self.get_pool_queue(&address.pool),
+        }
+    }
+
+    pub(crate) fn is_dedicated(&self, address: &ActorAddress) -> bool {
+        self.dedicated.contains_key(address)
+    }
+
+    /// `true` if the Actor is not scheduled on the pool of its address
+    pub(crate) fn is_relocated(&self, address: &ActorAddress) -> bool {
+        self.dedicated.contains_key(address) || self.placements.contains_key(address)
+    }
+
+    /// Pool the Actor is scheduled on, the pool of its address unless it has been moved
+    pub(crate) fn get_actor_pool(&self, address: &ActorAddress) -> String {
+        match self.placements.get(address) {
+            Some(pool) => pool.value().clone(),
+            None => address.pool.clone(),
         }
     }
 
-    pub fn get_pool_sender(&self, name: &str) -> Sender<Arc<RwLock<dyn ExecutorTrait>>> {
-        let pool = self.thread_pools.get(name).unwrap();
-        let (_, sender, _) = pool.value().clone();
-        sender
+    /// Wakeups queue the Actor in `pool` from now on, the worker that currently executes it has to let go of it
+    pub(crate) fn set_actor_pool(&self, address: &ActorAddress, pool: &str) {
+        if pool == address.pool {
+            self.placements.remove(address);
+        } else {
+            self.placements.insert(address.clone(), String::from(pool));
+        }
+    }
+
+    pub(crate) fn remove_actor_pool(&self, address: &ActorAddress) {
+        self.placements.remove(address);
+    }
+
+    pub fn has_pool(&self, name: &str) -> bool {
+        self.thread_pools.contains_key(name)
     }
 
     pub fn add_pool_with_config(&self, name: &str, thread_pool_config: ThreadPoolConfig) {
         if !self.thread_pools.contains_key(name) {
-            let (sender, receiver) = if thread_pool_config.actor_limit == 0 {
-                unbounded()
-            } else {
-                bounded(thread_pool_config.actor_limit)
-            };
-            self.thread_pools
-                .insert(String::from(name), (thread_pool_config, sender, receiver));
+            let entry = ThreadPoolEntry::new(thread_pool_config, self.worker_stats_interval, self.history_config);
+            self.thread_pools.insert(String::from(name), entry);
+            let _ = self.changes.0.send(());
+        }
+    }
+
+    /// Adds a thread that only executes the given Actor, it exits once the Actor has been removed
+    ///
+    /// Returns `false` if the system does not execute Actors on threads, see [TestActorSystem](../testing/struct.TestActorSystem.html)
+    pub(crate) fn add_dedicated(&self, address: &ActorAddress) -> bool {
+        if *self.workers_joined.0.lock().unwrap() {
+            return false;
+        }
+        let mut entry = ThreadPoolEntry::new(ThreadPoolConfig::new(0, 1, 1, 1.0), self.worker_stats_interval, None);
+        entry.stopped = Some(Arc::new(AtomicBool::new(false)));
+        self.dedicated.insert(address.clone(), entry);
+        let _ = self.changes.0.send(());
+        true
+    }
+
+    /// Returns `None` if the pool does not exist
+    pub fn get_actor_defaults(&self, name: &str) -> Option<Option<ActorDefaults>> {
+        self.thread_pools.get(name).map(|pool| pool.config.get_actor_defaults())
+    }
+
+    pub fn set_actor_defaults(&self, name: &str, actor_defaults: Option<ActorDefaults>) -> bool {
+        match self.thread_pools.get_mut(name) {
+            Some(mut pool) => {
+                pool.config.actor_defaults = actor_defaults;
+                true
+            }
+            None => false,
         }
     }
 
-    pub fn manage(&self, system_state: SystemState, wakeup_manager: WakeupManager) {
-        let mut pools: HashMap<String, ThreadPool> = HashMap::new();
+    pub fn sample_history(&self, timestamp: SystemTime) {
+        for pool in self.thread_pools.iter() {
+            if let Some(history) = &pool.history {
+                let sample = PoolMetricsSample {
+                    timestamp,
+                    threads: pool.thread_count.load(Ordering::Relaxed),
+                    queued_actors: pool.queue.len(),
+                };
+                history.lock().unwrap().push(sample);
+            }
+        }
+    }
+
+    pub fn get_history(&self, name: &str) -> Option<PoolMetricsHistory> {
+        let pool = self.thread_pools.get(name)?;
+        let history = pool.history.as_ref()?;
+        let samples = history.lock().unwrap().to_vec();
+        Some(PoolMetricsHistory { samples })
+    }
+
+    pub fn get_pool_configs(&self) -> Vec<(String, ThreadPoolConfig)> {
+        self.thread_pools
+            .iter()
+            .map(|pool| (pool.key().clone(), pool.config.clone()))
+            .collect()
+    }
+
+    pub fn get_worker_stats(&self, name: &str) -> Option<PoolWorkerStats> {
+        Some(self.thread_pools.get(name)?.worker_stats.get_stats())
+    }
+
+    /// Changes the thread limits of a pool, [manage](#method.manage) spawns or retires workers accordingly
+    pub fn resize(&self, name: &str, threads_min: usize, threads_max: usize) -> Result<(), PoolError> {
+        if threads_max == 0 || threads_min > threads_max {
+            return Err(PoolError::InvalidThreadCount);
+        }
+        match self.thread_pools.get_mut(name) {
+            Some(mut pool) => {
+                pool.config.threads_min = threads_min;
+                pool.config.threads_max = threads_max;
+            }
+            None => return Err(PoolError::UnknownPool),
+        }
+        let _ = self.changes.0.send(());
+        Ok(())
+    }
+
+    pub fn get_stats(&self, name: &str) -> Option<PoolStats> {
+        let pool = self.thread_pools.get(name)?;
+        Some(PoolStats {
+            threads: pool.thread_count.load(Ordering::Relaxed),
+            queued_actors: pool.queue.len(),
+            stolen_slots: pool.stolen_slots.load(Ordering::Relaxed),
+            idle_polls: pool.queue.idle_polls.load(Ordering::Relaxed),
+        })
+    }
+
+    pub fn get_load(&self, name: &str) -> Option<PoolLoad> {
+        let pool = self.thread_pools.get(name)?;
+        Some(PoolLoad {
+            queued_actors: pool.queue.len(),
+            busy_threads: pool.busy_threads.load(Ordering::Relaxed),
+            threads: pool.thread_count.load(Ordering::Relaxed),
+            queue_latency: pool.queue.get_queue_latency(),
+        })
+    }
+
+    /// Number of worker threads of the pool that died and have been replaced, `None` if the pool does not exist
+    pub fn get_worker_panics(&self, name: &str) -> Option<usize> {
+        Some(self.thread_pools.get(name)?.worker_panics.load(Ordering::Relaxed))
+    }
+
+    /// Marks all worker threads as joined, used by systems that never start any
+    pub(crate) fn set_workers_joined(&self) {
+        let (is_joined, joined) = &*self.workers_joined;
+        *is_joined.lock().unwrap() = true;
+        joined.notify_all();
+    }
+
+    /// Returns `false` if the worker threads have not been joined within `timeout`
+    pub(crate) fn await_workers_joined(&self, timeout: Duration) -> bool {
+        let (is_joined, joined) = &*self.workers_joined;
+        let guard = is_joined.lock().unwrap();
+        let (guard, _) = joined.wait_timeout_while(guard, timeout, |is_joined| !*is_joined).unwrap();
+        *guard
+    }
+
+    /// Starts the worker threads of every pool and replaces workers that died, joins all of them and the `background` threads once the system halted
+    ///
+    /// Surplus workers of a shrunk pool are retired, a grown pool gets its additional workers right away
+    pub fn manage(&self, system_state: SystemState, wakeup_manager: WakeupManager, background: Vec<JoinHandle<()>>) {
+        let mut pools: HashMap<String, Vec<Option<WorkerThread>>> = HashMap::new();
+        let mut dedicated: HashMap<ActorAddress, Option<WorkerThread>> = HashMap::new();
         loop {
-            let is_stopped = system_state.is_stopped();
-            if is_stopped {
-                for pool in pools.iter() {
-                    pool.1.join()
+            if system_state.is_halted() {
+                for pool in self.thread_pools.iter() {
+                    pool.queue.close();
+                }
+                for entry in self.dedicated.iter() {
+                    entry.queue.close();
+                }
+                for worker in pools.into_values().flatten().flatten().chain(dedicated.into_values().flatten()) {
+                    let _ = worker.handle.join();
+                }
+                for handle in background {
+                    let _ = handle.join();
                 }
+                self.set_workers_joined();
                 return;
             }
             for pool in self.thread_pools.iter() {
                 let pool_name = pool.key().clone();
-                let (pool_config, pool_sender, pool_receiver) = pool.value().clone();
-                if !pools.contains_key(&pool_name) {
-                    let thread_count = pool_config.threads_factor * num_cpus::get() as f32;
-                    let mut thread_count = thread_count.floor() as usize;
-                    if thread_count < pool_config.threads_min {
-                        thread_count = pool_config.threads_min;
-                    } else if thread_count > pool_config.threads_max {
-                        thread_count = pool_config.threads_max;
+                let thread_count = pool.config.thread_count(num_cpus::get());
+                let workers = pools.entry(pool_name.clone()).or_default();
+                if workers.len() < thread_count {
+                    workers.resize_with(thread_count, || None);
+                }
+                for (index, slot) in workers.iter_mut().enumerate() {
+                    let is_surplus = index >= thread_count;
+                    if let Some(worker) = slot.take_if(|worker| worker.handle.is_finished()) {
+                        if worker.handle.join().is_err() {
+                            pool.worker_panics.fetch_add(1, Ordering::Relaxed);
+                            println!("WORKER PANIC: replacing worker {} of pool {}", index, pool_name);
+                        }
+                        pool.queue.release_lane(worker.worker);
                     }
+                    match slot {
+                        Some(worker) => worker.retire.store(is_surplus, Ordering::Relaxed),
+                        None if !is_surplus && !system_state.is_halted() => {
+                            *slot = Some(self.spawn_worker(&pool, &pool_name, index, &system_state, &wakeup_manager));
+                        }
+                        None => {}
+                    }
+                }
+                while workers.len() > thread_count && matches!(workers.last(), Some(None)) {
+                    workers.pop();
+                }
+                let running = workers.iter().filter(|slot| slot.is_some()).count();
+                pool.thread_count.store(running, Ordering::Relaxed);
+            }
+            self.manage_dedicated(&mut dedicated, &system_state, &wakeup_manager);
+            // the stop is noticed right away instead of with the next periodic check
+            let mut select = Select::new();
+            select.recv(&self.changes.1);
+            select.recv(system_state.get_stopped());
+            let _ = select.ready_timeout(Duration::from_secs(1));
+            while self.changes.1.try_recv().is_ok() {}
+        }
+    }
 
-                    pools.insert(
-                        pool_name.clone(),
-                        ThreadPool::with_name(pool_name.clone(), thread_count),
-                    );
+    /// Replaces dedicated threads that died and drops the threads of removed Actors
+    fn manage_dedicated(
+        &self,
+        workers: &mut HashMap<ActorAddress, Option<WorkerThread>>,
+        system_state: &SystemState,
+        wakeup_manager: &WakeupManager,
+    ) {
+        let mut removed = Vec::new();
+        for entry in self.dedicated.iter() {
+            let address = entry.key();
+            let slot = workers.entry(address.clone()).or_default();
+            if let Some(worker) = slot.take_if(|worker| worker.handle.is_finished()) {
+                if worker.handle.join().is_err() {
+                    entry.worker_panics.fetch_add(1, Ordering::Relaxed);
+                    println!("WORKER PANIC: replacing the dedicated worker of {}", address.actor);
                 }
-                let current = pools.get(&pool_name).unwrap();
-                for _i in current.active_count()..current.max_count() {
-                    let sender = pool_sender.clone();
-                    let receiver = pool_receiver.clone();
-                    let pool_name = pool_name.clone();
-                    let recv_timeout = Duration::from_secs(1);
-                    let system_state = system_state.clone();
-                    let wakeup_manager = wakeup_manager.clone();
-                    pools.get(&pool_name).unwrap().execute(move || loop {
-                        let is_system_stopping = system_state.is_stopping();
-                        let mut actor_state = ActorState::Running;
-                        let msg = receiver.recv_timeout(recv_timeout);
-                        if msg.is_err() {
-                            if system_state.is_stopped() {
-                                return;
-                            }
-                            continue;
+            }
+            if entry.is_stopped() {
+                if slot.is_none() {
+                    removed.push(address.clone());
+                }
+                continue;
+            }
+            if slot.is_none() && !system_state.is_halted() {
+                let name = format!("{}.{}", address.pool, address.actor);
+                *slot = Some(self.spawn_worker(&entry, &name, 0, system_state, wakeup_manager));
+            }
+            entry.thread_count.store(slot.is_some() as usize, Ordering::Relaxed);
+        }
+        for address in removed {
+            self.dedicated.remove(&address);
+            workers.remove(&address);
+        }
+    }
+
+    fn spawn_worker(
+        &self,
+        pool: &ThreadPoolEntry,
+        pool_name: &str,
+        index: usize,
+        system_state: &SystemState,
+        wakeup_manager: &WakeupManager,
+    ) -> WorkerThread {
+        let queue = pool.queue.clone();
+        let recv_timeout = self.worker_stats_interval.min(Duration::from_secs(1));
+        let system_state = system_state.clone();
+        let wakeup_manager = wakeup_manager.clone();
+        let worker_stats = pool.worker_stats.clone();
+        let worker = pool.next_worker.fetch_add(1, Ordering::Relaxed);
+        let exit = WorkerExit(self.changes.0.clone());
+        let retire = Arc::new(AtomicBool::new(false));
+        let is_retired = retire.clone();
+        let stopped = pool.stopped.clone();
+        let thread_pools = self.thread_pools.clone();
+        let placements = self.placements.clone();
+        let steal_from: Vec<String> = pool
+            .config
+            .steal_from
+            .iter()
+            .filter(|name| name.as_str() != pool_name)
+            .cloned()
+            .collect();
+        let stolen_slots = pool.stolen_slots.clone();
+        let busy_threads = pool.busy_threads.clone();
+        let handle = std::thread::Builder::new()
+            .name(format!("{}-{}-{}", self.system_name, pool_name, index))
+            .spawn(move || {
+                let _exit = exit;
+                let lane = WorkerLane::new();
+                if queue.soft_affinity {
+                    queue.workers.insert(worker, lane.clone());
+                }
+                let mut accumulator = WorkerAccumulator::new(worker);
+                let mut next = None;
+                let mut victims: Vec<PoolQueue> = Vec::new();
+                loop {
+                    if is_retired.load(Ordering::Relaxed) {
+                        if let Some(actor) = next.take() {
+                            queue.push(actor);
                         }
-                        let ar = msg.unwrap();
-                        {
-                            let mut actor_ref = ar.write().unwrap();
-                            let actor_config = actor_ref.get_config();
-                            for _j in 0..actor_config.message_throughput {
-                                actor_state = actor_ref.handle(is_system_stopping);
-                                if actor_state != ActorState::Running {
-                                    break;
-                                }
+                        metric_stripes::flush();
+                        return;
+                    }
+                    accumulator.flush_if_due(&worker_stats);
+                    let mut actor_state = ActorState::Running;
+                    // pools that are added later are picked up as soon as they exist
+                    if victims.len() < steal_from.len() {
+                        victims = steal_from
+                            .iter()
+                            .filter_map(|name| thread_pools.get(name).map(|victim| victim.queue.clone()))
+                            .collect();
+                    }
+                    let msg = match next.take() {
+                        Some(actor) => Some((actor, None)),
+                        None => queue.pop(recv_timeout, &lane, &victims),
+                    };
+                    if msg.is_none() {
+                        if system_state.is_halted() {
+                            metric_stripes::flush();
+                            return;
+                        }
+                        continue;
+                    }
+                    let (ar, stolen_from) = msg.unwrap();
+                    {
+                        let _busy = BusyWorker::enter(&busy_threads);
+                        let slot_start = Instant::now();
+                        let mut actor_ref = ar.write().unwrap();
+                        // worker ids are only unique within a pool, a stolen Actor keeps the worker of its own pool
+                        let migrated = match stolen_from {
+                            Some(_) => {
+                                stolen_slots.fetch_add(1, Ordering::Relaxed);
+                                false
                             }
+                            None => actor_ref.record_worker(accumulator.get_worker()),
                         };
+                        let config = actor_ref.get_config();
+                        let is_system_stopping = match config.daemon {
+                            true => system_state.is_daemon_stop_delivered(&actor_ref.get_address()),
+                            false => system_state.is_stop_delivered(config.shutdown_phase),
+                        };
+                        for _j in 0..actor_ref.get_message_budget() {
+                            actor_state = actor_ref.handle(is_system_stopping);
+                            if actor_state != ActorState::Running {
+                                break;
+                            }
+                        }
+                        actor_ref.end_slot();
+                        metric_stripes::flush();
+                        // the pointer only identifies the Actor within the interval
+                        let actor_id = Arc::as_ptr(&ar) as *const () as usize;
+                        accumulator.record_slot(actor_id, slot_start.elapsed(), migrated);
+                    };
 
-                        if actor_state == ActorState::Running {
-                            sender.send(ar).unwrap();
+                    if actor_state == ActorState::Running {
+                        if let Some(victim) = stolen_from {
+                            victims[victim].push(ar);
+                        // keeps running on this worker as long as no other Actor of the pool waits
+                        } else if queue.soft_affinity && queue.len() == 0 {
+                            next = Some(ar);
                         } else {
-                            let address;
-                            {
-                                let actor_ref = ar.write().unwrap();
-                                address = actor_ref.get_address();
-                            }
-                            if actor_state == ActorState::Sleeping {
-                                wakeup_manager.add_sleeping_actor(address, ar);
-                            } else {
-                                println!("Actor has been stopped");
-                                system_state.remove_actor(&address);
+                            queue.push(ar);
+                        }
+                    } else {
+                        let address;
+                        {
+                            let actor_ref = ar.write().unwrap();
+                            address = actor_ref.get_address();
+                        }
+                        if actor_state == ActorState::Sleeping || actor_state == ActorState::Suspended {
+                            wakeup_manager.add_sleeping_actor(address, ar);
+                        } else {
+                            println!("Actor has been stopped");
+                            let pool = placements.remove(&address).map_or_else(|| address.pool.clone(), |(_, pool)| pool);
+                            system_state.remove_actor(&address, &pool);
+                            wakeup_manager.cancel_scheduled_wakeups(address);
+                            // a dedicated thread ends together with its Actor
+                            if let Some(stopped) = &stopped {
+                                stopped.store(true, Ordering::Relaxed);
+                                metric_stripes::flush();
+                                return;
                             }
                         }
-                    });
+                    }
                 }
-            }
-            sleep(Duration::from_secs((1) as u64));
-        }
+            })
+            .expect("unable to spawn a worker thread");
+        WorkerThread { worker, handle, retire }
     }
 }
@@ -0,0 +1,89 @@
+use crate::actor::actor_address::ActorAddress;
+use crate::actor::timer::Timer;
+use crate::system::system_state::SystemState;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Delay before a delivery to a full bounded mailbox is attempted again
+const RETRY_AFTER: Duration = Duration::from_millis(10);
+
+/// Scheduled messages of the wakeup loop, ordered by their deadline and by the order they have been scheduled in
+///
+/// Timers removed before they are due leave their deadline behind, it is skipped once it is popped
+#[derive(Default)]
+pub(crate) struct TimerQueue {
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    timers: HashMap<u64, Timer>,
+    next_sequence: u64,
+}
+
+impl TimerQueue {
+    pub fn push(&mut self, at: Instant, timer: Timer) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.deadlines.push(Reverse((at, sequence)));
+        self.timers.insert(sequence, timer);
+    }
+
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.peek().map(|Reverse((at, _))| *at)
+    }
+
+    /// Delivers every message that is due, a repeating timer keeps its place among timers with the same deadline
+    pub fn fire_due(&mut self, now: Instant) {
+        while let Some(Reverse((due, sequence))) = self.deadlines.peek().copied() {
+            if due > now {
+                return;
+            }
+            self.deadlines.pop();
+            let next = match self.timers.get_mut(&sequence) {
+                Some(timer) => timer.fire(due, now, RETRY_AFTER),
+                None => continue,
+            };
+            match next {
+                Some(next) => self.deadlines.push(Reverse((next, sequence))),
+                None => {
+                    self.timers.remove(&sequence);
+                }
+            }
+        }
+    }
+
+    /// Drops the timers of stopped Actors and all cancelled timers
+    pub fn cancel_owners(&mut self, stopped: &HashSet<ActorAddress>, system_status: &SystemState) {
+        // an Actor with the same address might have been spawned in the meantime
+        self.retain(|timer| match &timer.owner {
+            Some(owner) => !stopped.contains(owner) || system_status.is_actor_active(owner),
+            None => true,
+        });
+    }
+
+    /// Drops the timers whose owner is not active, returns how many have been dropped apart from cancelled ones
+    pub fn reclaim_inactive(&mut self, system_status: &SystemState) -> usize {
+        self.retain(|_| true);
+        let len = self.timers.len();
+        self.retain(|timer| match &timer.owner {
+            Some(owner) => system_status.is_actor_active(owner),
+            None => true,
+        });
+        len - self.timers.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    fn retain<F>(&mut self, keep: F)
+    where
+        F: Fn(&Timer) -> bool,
+    {
+        self.timers.retain(|_, timer| {
+            let is_kept = !timer.is_cancelled() && keep(timer);
+            if !is_kept {
+                timer.finish();
+            }
+            is_kept
+        });
+    }
+}
@@ -1,37 +1,189 @@
 use crate::actor::actor_address::ActorAddress;
 use crate::actor::executor::ExecutorTrait;
-use crate::system::system_state::SystemState;
+use crate::actor::timer::Timer;
+use crate::config::global_config::HistoryConfig;
+use crate::system::integrity::{IntegrityCounts, IntegrityReport, IntegrityState, IntegritySweeper};
+use crate::system::memory_pressure::MemoryMonitor;
+use crate::system::metrics_history::HistorySweeper;
+use crate::system::system_state::{Registration, SystemState};
 use crate::system::thread_pool_manager::ThreadPoolManager;
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crate::system::timer_queue::TimerQueue;
+use crate::system::yield_point::yield_point;
+use crossbeam_channel::{select, unbounded, Receiver, Sender};
 use dashmap::DashMap;
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::atomic::{fence, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+/// Repeated wakeups of an Actor within this window are dropped, entries are pruned once it passed
+const WAKE_DEDUPLICATION: Duration = Duration::from_secs(4);
+
 pub struct Wakeup {
     pub iteration: usize,
     pub actor_address: ActorAddress,
 }
 
+/// Entry of the channel of scheduled wakeups, a replacement shares the channel so it is applied in order with the wakeups scheduled around it
+enum ScheduledWakeup {
+    At(Instant, ActorAddress),
+    /// drops all earlier wakeups of the Actor and schedules the given ones instead
+    Replace(ActorAddress, Vec<Instant>),
+}
+
+impl ScheduledWakeup {
+    fn apply(self, scheduled: &mut BinaryHeap<Reverse<(Instant, ActorAddress)>>) {
+        match self {
+            ScheduledWakeup::At(at, address) => scheduled.push(Reverse((at, address))),
+            ScheduledWakeup::Replace(address, deadlines) => {
+                scheduled.retain(|Reverse((_, scheduled))| *scheduled != address);
+                scheduled.extend(deadlines.into_iter().map(|at| Reverse((at, address.clone()))));
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct WakeupManager {
     sleeping_actors: Arc<DashMap<ActorAddress, Arc<RwLock<dyn ExecutorTrait>>>>,
     wakeup_queue_in: Sender<Wakeup>,
     wakeup_queue_out: Receiver<Wakeup>,
+    scheduled_in: Sender<ScheduledWakeup>,
+    scheduled_out: Receiver<ScheduledWakeup>,
+    /// Messages scheduled through [ActorContext.schedule_once](../prelude/struct.ActorContext.html#method.schedule_once) and [ActorContext.schedule_repeating](../prelude/struct.ActorContext.html#method.schedule_repeating)
+    timers_in: Sender<(Instant, Timer)>,
+    timers_out: Receiver<(Instant, Timer)>,
+    /// Stopped Actors whose scheduled wakeups are dropped
+    cancelled_in: Sender<ActorAddress>,
+    cancelled_out: Receiver<ActorAddress>,
+    /// Actors of a single pool, see [SendBatch](../prelude/struct.SendBatch.html)
+    batch_in: Sender<Vec<ActorAddress>>,
+    batch_out: Receiver<Vec<ActorAddress>>,
+    /// Sweeps requested through [ActorSystem.sweep_integrity](../prelude/struct.ActorSystem.html#method.sweep_integrity)
+    sweep_in: Sender<Sender<IntegrityReport>>,
+    sweep_out: Receiver<Sender<IntegrityReport>>,
+    integrity: Arc<IntegrityState>,
 }
 
 impl WakeupManager {
     pub fn new() -> Self {
         let (wakeup_queue_in, wakeup_queue_out) = unbounded();
+        let (scheduled_in, scheduled_out) = unbounded();
+        let (timers_in, timers_out) = unbounded();
+        let (batch_in, batch_out) = unbounded();
+        let (cancelled_in, cancelled_out) = unbounded();
+        let (sweep_in, sweep_out) = unbounded();
         Self {
             sleeping_actors: Arc::new(DashMap::new()),
             wakeup_queue_in,
             wakeup_queue_out,
+            scheduled_in,
+            scheduled_out,
+            timers_in,
+            timers_out,
+            cancelled_in,
+            cancelled_out,
+            batch_in,
+            batch_out,
+            sweep_in,
+            sweep_out,
+            integrity: Arc::new(IntegrityState::default()),
         }
     }
 
+    pub(crate) fn is_same(&self, other: &WakeupManager) -> bool {
+        Arc::ptr_eq(&self.sleeping_actors, &other.sleeping_actors)
+    }
+
     pub fn add_sleeping_actor(&self, address: ActorAddress, actor: Arc<RwLock<dyn ExecutorTrait>>) {
-        self.sleeping_actors.insert(address, actor);
+        // an Actor is either sleeping or in the queue of a worker, never both
+        if self.sleeping_actors.insert(address.clone(), actor.clone()).is_some() {
+            self.integrity.increment_duplicate_entries();
+        }
+        yield_point();
+        // a wakeup may have been dropped while the actor was on its way to sleep, so the actor is checked once it can be found by wakeups.
+        // Checking it before it is added leaves a window for a send whose wakeup is deduplicated before the actor is added
+        fence(Ordering::SeqCst);
+        let is_wakeup_pending = match actor.try_read() {
+            Ok(actor) => actor.is_wakeup_pending(),
+            // already woken up and executed by a worker
+            Err(_) => false,
+        };
+        if is_wakeup_pending {
+            self.wakeup_queue_in
+                .send(Wakeup {
+                    actor_address: address,
+                    iteration: 1,
+                })
+                .unwrap();
+        }
+    }
+
+    /// Adds a newly spawned Actor, only possible while its registration is held, which is refused once the system is stopping
+    pub(crate) fn add_spawned_actor(&self, address: ActorAddress, actor: Arc<RwLock<dyn ExecutorTrait>>, _registration: &Registration) {
+        self.add_sleeping_actor(address, actor);
+    }
+
+    pub(crate) fn is_sleeping(&self, address: &ActorAddress) -> bool {
+        self.sleeping_actors.contains_key(address)
+    }
+
+    #[cfg(feature = "replay")]
+    pub fn get_sleeping_actor(&self, address: &ActorAddress) -> Option<Arc<RwLock<dyn ExecutorTrait>>> {
+        self.sleeping_actors.get(address).map(|actor| actor.value().clone())
+    }
+
+    #[cfg(feature = "replay")]
+    pub fn remove_sleeping_actor(&self, address: &ActorAddress) {
+        self.sleeping_actors.remove(address);
+    }
+
+    /// Wakes up the Actor at the given point in time, if it is sleeping by then
+    pub fn schedule_wakeup(&self, address: ActorAddress, at: Instant) {
+        self.scheduled_in.send(ScheduledWakeup::At(at, address)).unwrap();
+    }
+
+    /// Drops all scheduled wakeups of the Actor and schedules `deadlines` instead, a restarted Actor does not inherit the wakeups of the crashed one
+    pub(crate) fn replace_scheduled_wakeups(&self, address: ActorAddress, deadlines: Vec<Instant>) {
+        self.scheduled_in.send(ScheduledWakeup::Replace(address, deadlines)).unwrap();
+    }
+
+    /// Hands `timer` to the wakeup loop, which delivers its message at the given point in time
+    pub(crate) fn schedule_timer(&self, at: Instant, timer: Timer) {
+        self.timers_in.send((at, timer)).unwrap();
+    }
+
+    /// Drops all scheduled wakeups and timers of a stopped Actor
+    pub(crate) fn cancel_scheduled_wakeups(&self, address: ActorAddress) {
+        self.cancelled_in.send(address).unwrap();
+    }
+
+    fn wakeup_sleeping(&self, address: &ActorAddress, thread_pool_manager: &ThreadPoolManager) -> bool {
+        let sleeping_actor = self.sleeping_actors.remove(address);
+        if sleeping_actor.is_none() {
+            return false;
+        }
+        let actor_ref = sleeping_actor.unwrap().1;
+        {
+            let mut actor_ref = actor_ref.write().unwrap();
+            actor_ref.wakeup();
+        }
+        thread_pool_manager.get_actor_queue(address).push(actor_ref);
+        true
+    }
+
+    /// Wakes up all sleeping Actors that have work to do, used once sheddable Actors are no longer paused
+    fn wakeup_pending(&self, thread_pool_manager: &ThreadPoolManager) {
+        let pending: Vec<ActorAddress> = self
+            .sleeping_actors
+            .iter()
+            .filter(|actor| actor.value().read().unwrap().is_wakeup_pending())
+            .map(|actor| actor.key().clone())
+            .collect();
+        for address in pending {
+            self.wakeup_sleeping(&address, thread_pool_manager);
+        }
     }
 
     pub fn wakeup(&self, address: ActorAddress) {
@@ -43,13 +195,78 @@ impl WakeupManager {
             .unwrap();
     }
 
-    pub fn manage(&self, system_status: SystemState, thread_pool_manager: ThreadPoolManager) {
+    /// Wakes up multiple Actors of the same pool at once
+    pub(crate) fn wakeup_batch(&self, addresses: Vec<ActorAddress>) {
+        self.batch_in.send(addresses).unwrap();
+    }
+
+    /// Ends the wait of the wakeup loop, which wakes up all sleeping Actors of a stopping system from then on
+    pub(crate) fn notify_stopping(&self) {
+        self.batch_in.send(Vec::new()).unwrap();
+    }
+
+    /// Runs a sweep in the wakeup loop, `None` if the loop does not run or did not answer in time
+    pub(crate) fn request_integrity_sweep(&self) -> Option<IntegrityReport> {
+        IntegrityState::request_sweep(&self.sweep_in)
+    }
+
+    pub(crate) fn get_integrity(&self) -> &IntegrityState {
+        &self.integrity
+    }
+
+    pub fn manage(
+        &self,
+        system_status: SystemState,
+        thread_pool_manager: ThreadPoolManager,
+        history_config: Option<HistoryConfig>,
+        mut memory_monitor: Option<MemoryMonitor>,
+        integrity_sweep_interval: Duration,
+    ) {
         let mut wake_deduplication: HashMap<ActorAddress, Instant> = HashMap::new();
-        let recv_timeout = Duration::from_secs(1);
+        let mut last_deduplication_prune = Instant::now();
+        let mut scheduled: BinaryHeap<Reverse<(Instant, ActorAddress)>> = BinaryHeap::new();
+        let mut timers = TimerQueue::default();
+        let mut integrity_sweeper = IntegritySweeper::new(integrity_sweep_interval, self.integrity.clone());
+        let mut history_sweeper = history_config.map(HistorySweeper::new);
+        let recv_timeout = history_sweeper
+            .as_ref()
+            .map_or(Duration::from_secs(1), |sweeper| sweeper.poll_interval().min(Duration::from_secs(1)));
+        let recv_timeout = memory_monitor
+            .as_ref()
+            .map_or(recv_timeout, |monitor| monitor.poll_interval().min(recv_timeout));
         loop {
-            if system_status.is_stopped() {
+            if system_status.is_halted() {
                 return;
             }
+            if let Some(sweeper) = history_sweeper.as_mut() {
+                sweeper.sweep(&system_status, &thread_pool_manager);
+            }
+            if memory_monitor.as_mut().is_some_and(|monitor| monitor.sample()) {
+                self.wakeup_pending(&thread_pool_manager);
+            }
+            // entries are otherwise only removed by a later wakeup of the same Actor
+            if last_deduplication_prune.elapsed() >= WAKE_DEDUPLICATION {
+                wake_deduplication.retain(|_, last_wakeup| last_wakeup.elapsed() < WAKE_DEDUPLICATION);
+                last_deduplication_prune = Instant::now();
+            }
+            if integrity_sweeper.is_due() {
+                self.sweep_integrity(
+                    &system_status,
+                    &thread_pool_manager,
+                    &mut integrity_sweeper,
+                    &mut wake_deduplication,
+                    &mut scheduled,
+                    &mut timers,
+                );
+            }
+            self.cancel_scheduled(HashSet::new(), &mut scheduled, &mut timers, &system_status);
+            let now = Instant::now();
+            while scheduled.peek().is_some_and(|Reverse((at, _))| *at <= now) {
+                let Reverse((_, address)) = scheduled.pop().unwrap();
+                // actors that are not sleeping check their deadlines by themselves
+                self.wakeup_sleeping(&address, &thread_pool_manager);
+            }
+            timers.fire_due(now);
             if system_status.is_stopping() {
                 let mut keys: Vec<ActorAddress> = Vec::new();
                 for key in self.sleeping_actors.iter() {
@@ -57,64 +274,269 @@ impl WakeupManager {
                 }
                 for key in keys {
                     let sleeping_actor = self.sleeping_actors.remove(&key).unwrap();
-                    let pool_name = sleeping_actor.0.pool;
                     let actor_ref = sleeping_actor.1;
                     {
                         let mut actor_ref = actor_ref.write().unwrap();
                         actor_ref.wakeup();
                     }
-                    let sender = thread_pool_manager.get_pool_sender(&pool_name);
-                    sender.send(actor_ref).unwrap();
+                    thread_pool_manager.get_actor_queue(&sleeping_actor.0).push(actor_ref);
                 }
                 continue;
             }
-            let msg = self.wakeup_queue_out.recv_timeout(recv_timeout);
-            if msg.is_err() {
+            let next_deadline = scheduled.peek().map(|Reverse((at, _))| *at).into_iter().chain(timers.next_deadline()).min();
+            let timeout = next_deadline.map_or(recv_timeout, |at| at.saturating_duration_since(Instant::now()).min(recv_timeout));
+            let msg = select! {
+                recv(self.wakeup_queue_out) -> msg => msg.ok(),
+                recv(self.batch_out) -> batch => {
+                    if let Ok(batch) = batch {
+                        self.process_batch(batch, &system_status, &thread_pool_manager, &mut wake_deduplication);
+                    }
+                    None
+                },
+                recv(self.scheduled_out) -> scheduled_wakeup => {
+                    if let Ok(scheduled_wakeup) = scheduled_wakeup {
+                        scheduled_wakeup.apply(&mut scheduled);
+                    }
+                    None
+                },
+                recv(self.timers_out) -> timer => {
+                    if let Ok((at, timer)) = timer {
+                        timers.push(at, timer);
+                    }
+                    None
+                },
+                recv(self.cancelled_out) -> address => {
+                    if let Ok(address) = address {
+                        self.cancel_scheduled(HashSet::from([address]), &mut scheduled, &mut timers, &system_status);
+                    }
+                    None
+                },
+                // returns right away instead of with the next timeout once the system halted
+                recv(system_status.get_stopped()) -> _ => None,
+                recv(self.sweep_out) -> reply => {
+                    if let Ok(reply) = reply {
+                        let report = self.sweep_integrity(
+                            &system_status,
+                            &thread_pool_manager,
+                            &mut integrity_sweeper,
+                            &mut wake_deduplication,
+                            &mut scheduled,
+                            &mut timers,
+                        );
+                        let _ = reply.send(report);
+                    }
+                    None
+                },
+                default(timeout) => None,
+            };
+            if msg.is_none() {
                 continue;
             }
-            let wakeup_message = msg.unwrap();
-
-            if wake_deduplication.contains_key(&wakeup_message.actor_address)
-                && wakeup_message.iteration == 0
-            {
-                // actors have a minimum uptime of 1 second
-                // this ensures a guaranteed de-duplication of all wakeup calls to a single actor
-                let last_wakeup = wake_deduplication
-                    .get(&wakeup_message.actor_address)
-                    .unwrap();
-                let duration = last_wakeup.elapsed();
-                if duration >= Duration::from_secs(4) {
-                    wake_deduplication.remove(&wakeup_message.actor_address);
-                } else {
-                    continue;
-                }
+            self.process_wakeup(msg.unwrap(), &system_status, &thread_pool_manager, &mut wake_deduplication);
+        }
+    }
+
+    /// Moves all queued wakeups and timers into `scheduled` and `timers` and drops the ones of `cancelled` and of all other queued cancellations
+    fn cancel_scheduled(
+        &self,
+        mut cancelled: HashSet<ActorAddress>,
+        scheduled: &mut BinaryHeap<Reverse<(Instant, ActorAddress)>>,
+        timers: &mut TimerQueue,
+        system_status: &SystemState,
+    ) {
+        while let Ok(scheduled_wakeup) = self.scheduled_out.try_recv() {
+            scheduled_wakeup.apply(scheduled);
+        }
+        while let Ok((at, timer)) = self.timers_out.try_recv() {
+            timers.push(at, timer);
+        }
+        cancelled.extend(self.cancelled_out.try_iter());
+        if cancelled.is_empty() {
+            return;
+        }
+        // an Actor with the same address might have been spawned in the meantime
+        scheduled.retain(|Reverse((_, address))| !cancelled.contains(address) || system_status.is_actor_active(address));
+        timers.cancel_owners(&cancelled, system_status);
+    }
+
+    /// Cross-checks the sleeping Actors and the state of the loop against the registered Actors and reclaims everything that outlived its Actor
+    fn sweep_integrity(
+        &self,
+        system_status: &SystemState,
+        thread_pool_manager: &ThreadPoolManager,
+        sweeper: &mut IntegritySweeper,
+        wake_deduplication: &mut HashMap<ActorAddress, Instant>,
+        scheduled: &mut BinaryHeap<Reverse<(Instant, ActorAddress)>>,
+        timers: &mut TimerQueue,
+    ) -> IntegrityReport {
+        let started = Instant::now();
+        let mut reclaimed = IntegrityCounts::default();
+        let mut examples = Vec::new();
+
+        let sleeping: Vec<(ActorAddress, Arc<RwLock<dyn ExecutorTrait>>)> = self
+            .sleeping_actors
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        let mut suspected_sleeping = HashSet::new();
+        for (address, actor_ref) in sleeping {
+            let is_stopped = actor_ref.read().unwrap().is_stopped();
+            let is_reclaimed = if is_stopped {
+                // a stopped Actor still has to run its stop, it is only missing the wakeup
+                self.wakeup_sleeping(&address, thread_pool_manager)
+            } else if system_status.is_actor_active(&address) {
+                false
+            } else if sweeper.confirm_sleeping(&address) {
+                self.sleeping_actors.remove(&address).is_some()
+            } else {
+                suspected_sleeping.insert(address.clone());
+                false
+            };
+            if is_reclaimed {
+                reclaimed.sleeping_actors += 1;
+                examples.push(("sleeping_actors", address));
             }
+        }
+        sweeper.set_suspected_sleeping(suspected_sleeping);
 
-            wake_deduplication.insert(wakeup_message.actor_address.clone(), Instant::now());
-            if !self
-                .sleeping_actors
-                .contains_key(&wakeup_message.actor_address)
-            {
-                self.wakeup_queue_in
-                    .send(Wakeup {
-                        iteration: (wakeup_message.iteration + 1),
-                        actor_address: wakeup_message.actor_address,
-                    })
-                    .unwrap();
-                continue;
+        // bounded by the pruning of the loop, an expired entry is no leak
+        wake_deduplication.retain(|_, last_wakeup| last_wakeup.elapsed() < WAKE_DEDUPLICATION);
+
+        self.cancel_scheduled(HashSet::new(), scheduled, timers, system_status);
+        let len = scheduled.len();
+        scheduled.retain(|Reverse((_, address))| system_status.is_actor_active(address));
+        reclaimed.scheduled_wakeups = len - scheduled.len() + timers.reclaim_inactive(system_status);
+
+        sweeper.sweep_registries(system_status, &mut reclaimed, &mut examples);
+
+        let mut retained = IntegrityCounts {
+            sleeping_actors: self.sleeping_actors.len(),
+            wake_deduplication: wake_deduplication.len(),
+            scheduled_wakeups: scheduled.len() + timers.len(),
+            ..IntegrityCounts::default()
+        };
+        system_status.count_registries(&mut retained);
+        sweeper.finish(system_status, started, reclaimed, retained, examples)
+    }
+
+    fn process_wakeup(
+        &self,
+        wakeup_message: Wakeup,
+        system_status: &SystemState,
+        thread_pool_manager: &ThreadPoolManager,
+        wake_deduplication: &mut HashMap<ActorAddress, Instant>,
+    ) {
+        if self.wakeup_sleeping(&wakeup_message.actor_address, thread_pool_manager) {
+            wake_deduplication.insert(wakeup_message.actor_address, Instant::now());
+            return;
+        }
+
+        if wake_deduplication.contains_key(&wakeup_message.actor_address) && wakeup_message.iteration == 0 {
+            // actors have a minimum uptime of 1 second
+            // this ensures a guaranteed de-duplication of all wakeup calls to a single actor
+            let last_wakeup = wake_deduplication.get(&wakeup_message.actor_address).unwrap();
+            let duration = last_wakeup.elapsed();
+            if duration >= WAKE_DEDUPLICATION {
+                wake_deduplication.remove(&wakeup_message.actor_address);
+            } else {
+                return;
+            }
+        }
+
+        // the actor stopped while the wakeup was queued, it will never go to sleep again
+        if !system_status.is_actor_active(&wakeup_message.actor_address) {
+            wake_deduplication.remove(&wakeup_message.actor_address);
+            return;
+        }
+
+        wake_deduplication.insert(wakeup_message.actor_address.clone(), Instant::now());
+        if !self.sleeping_actors.contains_key(&wakeup_message.actor_address) {
+            // a running Actor handles the message by itself, retrying until it sleeps again would only spin
+            if !system_status.is_falling_asleep(&wakeup_message.actor_address) {
+                return;
             }
+            self.wakeup_queue_in
+                .send(Wakeup {
+                    iteration: (wakeup_message.iteration + 1),
+                    actor_address: wakeup_message.actor_address,
+                })
+                .unwrap();
+            return;
+        }
+
+        self.wakeup_sleeping(&wakeup_message.actor_address, thread_pool_manager);
+    }
 
-            let actor_ref = self
-                .sleeping_actors
-                .remove(&wakeup_message.actor_address)
-                .unwrap()
-                .1;
-            {
-                let mut actor_ref = actor_ref.write().unwrap();
-                actor_ref.wakeup();
+    /// Looks up the pool queue only once, Actors that are not sleeping yet are retried like single wakeups
+    fn process_batch(
+        &self,
+        addresses: Vec<ActorAddress>,
+        system_status: &SystemState,
+        thread_pool_manager: &ThreadPoolManager,
+        wake_deduplication: &mut HashMap<ActorAddress, Instant>,
+    ) {
+        let mut queue = None;
+        for address in addresses {
+            match self.sleeping_actors.remove(&address) {
+                Some((address, actor_ref)) => {
+                    actor_ref.write().unwrap().wakeup();
+                    // Actors on a dedicated thread or moved to another pool are not queued in the pool of their address
+                    if thread_pool_manager.is_relocated(&address) {
+                        thread_pool_manager.get_actor_queue(&address).push(actor_ref);
+                    } else {
+                        queue
+                            .get_or_insert_with(|| thread_pool_manager.get_pool_queue(&address.pool))
+                            .push(actor_ref);
+                    }
+                    wake_deduplication.insert(address, Instant::now());
+                }
+                None => {
+                    let wakeup = Wakeup {
+                        iteration: 0,
+                        actor_address: address,
+                    };
+                    self.process_wakeup(wakeup, system_status, thread_pool_manager, wake_deduplication);
+                }
+            }
+        }
+    }
+
+    /// Collects all queued wakeups and timers and processes everything that is due at `now`, like the wakeup loop
+    #[cfg(feature = "testing")]
+    pub(crate) fn process_due(
+        &self,
+        now: Instant,
+        scheduled: &mut BinaryHeap<Reverse<(Instant, ActorAddress)>>,
+        timers: &mut TimerQueue,
+        system_status: &SystemState,
+        thread_pool_manager: &ThreadPoolManager,
+    ) {
+        self.cancel_scheduled(HashSet::new(), scheduled, timers, system_status);
+        while scheduled.peek().is_some_and(|Reverse((at, _))| *at <= now) {
+            let Reverse((_, address)) = scheduled.pop().unwrap();
+            self.wakeup_sleeping(&address, thread_pool_manager);
+        }
+        timers.fire_due(now);
+    }
+
+    /// Processes a single queued wakeup or batch of wakeups, returns `false` if none was queued
+    #[cfg(feature = "testing")]
+    pub(crate) fn try_process_wakeup(
+        &self,
+        system_status: &SystemState,
+        thread_pool_manager: &ThreadPoolManager,
+        wake_deduplication: &mut HashMap<ActorAddress, Instant>,
+    ) -> bool {
+        if let Ok(batch) = self.batch_out.try_recv() {
+            self.process_batch(batch, system_status, thread_pool_manager, wake_deduplication);
+            return true;
+        }
+        match self.wakeup_queue_out.try_recv() {
+            Ok(wakeup_message) => {
+                self.process_wakeup(wakeup_message, system_status, thread_pool_manager, wake_deduplication);
+                true
             }
-            let sender = thread_pool_manager.get_pool_sender(&wakeup_message.actor_address.pool);
-            sender.send(actor_ref).unwrap();
+            Err(_) => false,
         }
     }
 }
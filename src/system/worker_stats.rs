@@ -0,0 +1,150 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Scheduling statistics of a single worker thread for the last completed reporting interval
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Default)]
+pub struct WorkerStats {
+    pub worker: usize,
+    pub busy: Duration,
+    pub idle: Duration,
+    /// scheduling slots, each slot handles up to the message budget of a single Actor
+    pub slots: u64,
+    pub distinct_actors: usize,
+    /// slots of Actors whose previous slot ran on another worker
+    pub migrations: u64,
+}
+
+impl WorkerStats {
+    /// Fraction of the interval the worker spent executing Actors
+    pub fn utilization(&self) -> f64 {
+        let total = self.busy + self.idle;
+        if total.as_nanos() == 0 {
+            return 0.0;
+        }
+        self.busy.as_secs_f64() / total.as_secs_f64()
+    }
+}
+
+/// Scheduling statistics of all worker threads of a pool, see [ActorSystem.pool_worker_stats](../prelude/struct.ActorSystem.html#method.pool_worker_stats)
+///
+/// Workers that did not complete a reporting interval yet are not included
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PoolWorkerStats {
+    pub interval: Duration,
+    pub soft_affinity: bool,
+    /// sorted by worker id
+    pub workers: Vec<WorkerStats>,
+}
+
+impl PoolWorkerStats {
+    pub fn max_utilization(&self) -> f64 {
+        self.workers.iter().map(WorkerStats::utilization).fold(0.0, f64::max)
+    }
+
+    pub fn min_utilization(&self) -> f64 {
+        self.workers
+            .iter()
+            .map(WorkerStats::utilization)
+            .fold(None, |min: Option<f64>, utilization| Some(min.map_or(utilization, |min| min.min(utilization))))
+            .unwrap_or(0.0)
+    }
+
+    /// Difference between the most and the least utilized worker, close to `1.0` if one worker does all the work while another one idles
+    pub fn utilization_skew(&self) -> f64 {
+        self.max_utilization() - self.min_utilization()
+    }
+
+    pub fn migrations_per_second(&self) -> f64 {
+        let migrations: u64 = self.workers.iter().map(|worker| worker.migrations).sum();
+        if self.interval.as_nanos() == 0 {
+            return 0.0;
+        }
+        migrations as f64 / self.interval.as_secs_f64()
+    }
+}
+
+/// Shared per pool, workers publish their accumulators once per interval
+pub(crate) struct WorkerRegistry {
+    interval: Duration,
+    soft_affinity: bool,
+    stats: Mutex<BTreeMap<usize, WorkerStats>>,
+}
+
+impl WorkerRegistry {
+    pub fn new(interval: Duration, soft_affinity: bool) -> Self {
+        Self {
+            interval,
+            soft_affinity,
+            stats: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn get_stats(&self) -> PoolWorkerStats {
+        PoolWorkerStats {
+            interval: self.interval,
+            soft_affinity: self.soft_affinity,
+            workers: self.stats.lock().unwrap().values().copied().collect(),
+        }
+    }
+}
+
+/// Thread local accumulator of a single worker, flushed to the `WorkerRegistry` once per interval
+pub(crate) struct WorkerAccumulator {
+    worker: usize,
+    interval_start: Instant,
+    busy: Duration,
+    slots: u64,
+    migrations: u64,
+    actors: HashSet<usize>,
+}
+
+impl WorkerAccumulator {
+    pub fn new(worker: usize) -> Self {
+        Self {
+            worker,
+            interval_start: Instant::now(),
+            busy: Duration::from_secs(0),
+            slots: 0,
+            migrations: 0,
+            actors: HashSet::new(),
+        }
+    }
+
+    pub fn get_worker(&self) -> usize {
+        self.worker
+    }
+
+    /// `actor` identifies the Actor for the distinct count and is never dereferenced
+    pub fn record_slot(&mut self, actor: usize, busy: Duration, migrated: bool) {
+        self.busy += busy;
+        self.slots += 1;
+        if migrated {
+            self.migrations += 1;
+        }
+        self.actors.insert(actor);
+    }
+
+    pub fn flush_if_due(&mut self, registry: &WorkerRegistry) {
+        let elapsed = self.interval_start.elapsed();
+        if elapsed < registry.interval {
+            return;
+        }
+        let busy = self.busy.min(elapsed);
+        let stats = WorkerStats {
+            worker: self.worker,
+            busy,
+            idle: elapsed - busy,
+            slots: self.slots,
+            distinct_actors: self.actors.len(),
+            migrations: self.migrations,
+        };
+        registry.stats.lock().unwrap().insert(self.worker, stats);
+        self.interval_start = Instant::now();
+        self.busy = Duration::from_secs(0);
+        self.slots = 0;
+        self.migrations = 0;
+        self.actors.clear();
+    }
+}
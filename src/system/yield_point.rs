@@ -0,0 +1,8 @@
+/// Marks a point of the mailbox and wakeup protocol where another thread may interleave
+///
+/// Only yields if the `testing` feature is enabled, so that [MailboxHarness](../../testing/struct.MailboxHarness.html) explores more interleavings
+#[inline(always)]
+pub(crate) fn yield_point() {
+    #[cfg(feature = "testing")]
+    std::thread::yield_now();
+}